@@ -2,24 +2,30 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
-use fvm_shared::address::Protocol;
+use fvm_shared::address::{Address, Protocol};
 use fvm_shared::crypto::signature::SignatureType::{Secp256k1, BLS};
 use fvm_shared::crypto::signature::{Signature, SignatureType};
+use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
 use fvm_shared::{MethodNum, METHOD_CONSTRUCTOR};
 use num_derive::FromPrimitive;
 
 use fil_actors_runtime::builtin::singletons::SYSTEM_ACTOR_ADDR;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
-use fil_actors_runtime::{actor_dispatch, ActorDowncast, FIRST_EXPORTED_METHOD_NUMBER};
+use fil_actors_runtime::{
+    actor_dispatch, deserialize_block, extract_send_result, ActorDowncast, AsActorError,
+    FIRST_EXPORTED_METHOD_NUMBER,
+};
 use fil_actors_runtime::{actor_error, ActorError};
 use types::{AuthenticateMessageReturn, ConstructorParams, PubkeyAddressReturn};
 
-use crate::types::AuthenticateMessageParams;
+use crate::types::{AuthenticateMessageParams, SetAuthDelegateParams};
 
 pub use self::state::State;
 
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 pub mod types;
 
@@ -35,6 +41,8 @@ pub enum Method {
     // Deprecated in v10
     // AuthenticateMessage = 3,
     AuthenticateMessageExported = frc42_dispatch::method_hash!("AuthenticateMessage"),
+    PubkeyAddressExported = frc42_dispatch::method_hash!("PubkeyAddress"),
+    SetAuthDelegateExported = frc42_dispatch::method_hash!("SetAuthDelegate"),
 }
 
 /// Account Actor
@@ -52,7 +60,7 @@ impl Actor {
                     "address must use BLS or SECP protocol, got {}", protocol));
             }
         }
-        rt.create(&State { address })?;
+        rt.create(&State { address, auth_delegate: None })?;
         Ok(())
     }
 
@@ -65,6 +73,8 @@ impl Actor {
 
     /// Authenticates whether the provided signature is valid for the provided message.
     /// Should be called with the raw bytes of a signature, NOT a serialized Signature object that includes a SignatureType.
+    /// If the policy permits delegation and the account has a delegate, authentication is
+    /// forwarded to the delegate's AuthenticateMessage method instead.
     /// Errors with USR_ILLEGAL_ARGUMENT if the authentication is invalid.
     pub fn authenticate_message(
         rt: &impl Runtime,
@@ -72,6 +82,9 @@ impl Actor {
     ) -> Result<AuthenticateMessageReturn, ActorError> {
         rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
+        if let Some(delegate) = st.auth_delegate.filter(|_| rt.policy().account_auth_delegation) {
+            return authenticate_with_delegate(rt, &delegate, &params);
+        }
         let address = st.address;
         let sig_type: SignatureType = match address.protocol() {
             Protocol::Secp256k1 => Secp256k1,
@@ -92,6 +105,42 @@ impl Actor {
         Ok(AuthenticateMessageReturn { authenticated: true })
     }
 
+    /// Sets or clears the actor to which message authentication is delegated.
+    /// Only the account itself may call this, and only if the policy permits delegation.
+    /// The delegate must exist and implement AuthenticateMessage, and is stored by ID.
+    /// No actor is created for a delegate address which doesn't resolve.
+    /// Delegation cycles (e.g. A to B to A) are not detected: authentication through one fails
+    /// only when it runs out of call depth or gas.
+    pub fn set_auth_delegate(
+        rt: &impl Runtime,
+        params: SetAuthDelegateParams,
+    ) -> Result<(), ActorError> {
+        let receiver = rt.message().receiver();
+        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+        if !rt.policy().account_auth_delegation {
+            return Err(actor_error!(forbidden; "account authentication delegation is disabled"));
+        }
+        let auth_delegate = match params.delegate {
+            Some(delegate) => {
+                let id = rt
+                    .resolve_address(&delegate)
+                    .ok_or_else(|| actor_error!(not_found; "delegate {} not found", delegate))?;
+                if id == rt.message().receiver().id().unwrap() {
+                    return Err(actor_error!(illegal_argument;
+                        "account cannot delegate authentication to itself"));
+                }
+                rt.get_actor_code_cid(&id)
+                    .ok_or_else(|| actor_error!(not_found; "no code for delegate {}", delegate))?;
+                Some(Address::new_id(id))
+            }
+            None => None,
+        };
+        rt.transaction(|st: &mut State, _| {
+            st.auth_delegate = auth_delegate;
+            Ok(())
+        })
+    }
+
     /// Fallback method for unimplemented method numbers.
     pub fn fallback(
         rt: &impl Runtime,
@@ -107,6 +156,29 @@ impl Actor {
     }
 }
 
+fn authenticate_with_delegate(
+    rt: &impl Runtime,
+    delegate: &Address,
+    params: &AuthenticateMessageParams,
+) -> Result<AuthenticateMessageReturn, ActorError> {
+    let authenticated: bool = extract_send_result(rt.send(
+        delegate,
+        Method::AuthenticateMessageExported as MethodNum,
+        IpldBlock::serialize_cbor(params)?,
+        TokenAmount::default(),
+        None,
+        SendFlags::READ_ONLY,
+    ))
+    .and_then(deserialize_block)
+    .context_code(ExitCode::USR_ILLEGAL_ARGUMENT, "delegated authentication failed")?;
+    if !authenticated {
+        return Err(
+            actor_error!(illegal_argument; "delegate {} rejected authentication", delegate),
+        );
+    }
+    Ok(AuthenticateMessageReturn { authenticated })
+}
+
 impl ActorCode for Actor {
     type Methods = Method;
 
@@ -116,8 +188,9 @@ impl ActorCode for Actor {
 
     actor_dispatch! {
         Constructor => constructor,
-        PubkeyAddress => pubkey_address,
+        PubkeyAddress|PubkeyAddressExported => pubkey_address,
         AuthenticateMessageExported => authenticate_message,
+        SetAuthDelegateExported => set_auth_delegate,
         _ => fallback,
     }
 }
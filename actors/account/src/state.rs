@@ -1,11 +1,26 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
-use fvm_ipld_encoding::tuple::*;
-use fvm_shared::address::Address;
+use fil_actors_runtime::tuple_with_optional_tail;
+use fvm_shared::address::{Address, Payload, BLS_PUB_LEN};
 
 /// State includes the address for the actor
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct State {
     pub address: Address,
+    /// An actor to which message authentication is delegated, if the policy permits.
+    pub auth_delegate: Option<Address>,
+}
+
+tuple_with_optional_tail!(State { address; auth_delegate });
+
+impl State {
+    /// Returns the account's BLS public key, if it has a BLS address.
+    /// A SECP256K1 address holds only a hash of its public key, so no key is available for one.
+    pub fn bls_public_key(&self) -> Option<&[u8; BLS_PUB_LEN]> {
+        match self.address.payload() {
+            Payload::BLS(key) => Some(key),
+            _ => None,
+        }
+    }
 }
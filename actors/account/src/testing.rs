@@ -25,6 +25,12 @@ pub fn check_state_invariants(
         Err(e) => acc.add(format!("error extracting actor ID from address: {e}")),
         _ => (),
     }
+    if let Some(delegate) = state.auth_delegate {
+        acc.require(
+            delegate.protocol() == Protocol::ID,
+            format!("authentication delegate {} must be an ID address", delegate),
+        );
+    }
 
     (StateSummary { pub_key_address: state.address }, acc)
 }
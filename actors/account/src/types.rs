@@ -27,3 +27,10 @@ pub struct AuthenticateMessageParams {
 pub struct AuthenticateMessageReturn {
     pub authenticated: bool,
 }
+
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct SetAuthDelegateParams {
+    /// The actor to delegate message authentication to, or None to stop delegating.
+    pub delegate: Option<Address>,
+}
@@ -2,17 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use anyhow::anyhow;
-use fvm_actor_utils::receiver::UniversalReceiverParams;
+use fil_actors_runtime::frc46::UniversalReceiverParams;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::crypto::signature::Signature;
+use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
 use fvm_shared::MethodNum;
+use num_traits::Zero;
 
-use fil_actor_account::types::AuthenticateMessageParams;
+use fil_actor_account::types::{AuthenticateMessageParams, SetAuthDelegateParams};
 use fil_actor_account::{testing::check_state_invariants, Actor as AccountActor, Method, State};
 use fil_actors_runtime::builtin::SYSTEM_ACTOR_ADDR;
+use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::FIRST_EXPORTED_METHOD_NUMBER;
 
@@ -41,6 +45,15 @@ fn construction() {
                 .deserialize()
                 .unwrap();
             assert_eq!(pk, addr);
+
+            rt.expect_validate_caller_any();
+            let pk: Address = rt
+                .call::<AccountActor>(Method::PubkeyAddressExported as MethodNum, None)
+                .unwrap()
+                .unwrap()
+                .deserialize()
+                .unwrap();
+            assert_eq!(pk, addr);
             check_state(&rt);
         } else {
             expect_abort(
@@ -159,6 +172,185 @@ fn authenticate_message() {
         .unwrap());
 }
 
+#[test]
+fn authenticate_message_with_delegate() {
+    let receiver = Address::new_id(100);
+    let delegate = Address::new_id(1000);
+    let policy = Policy::default().builder().account_auth_delegation(true).build();
+    let rt = MockRuntime { receiver, policy, ..Default::default() };
+    let addr = Address::new_secp256k1(&[2; fvm_shared::address::SECP_PUB_LEN]).unwrap();
+    construct_account(&rt, addr);
+    rt.set_address_actor_type(delegate, *EVM_ACTOR_CODE_ID);
+
+    // Only the account itself may set a delegate.
+    let set_params =
+        IpldBlock::serialize_cbor(&SetAuthDelegateParams { delegate: Some(delegate) }).unwrap();
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(101));
+    rt.expect_validate_caller_addr(vec![receiver]);
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        rt.call::<AccountActor>(Method::SetAuthDelegateExported as MethodNum, set_params.clone()),
+    );
+    rt.verify();
+
+    // Nor delegate to itself.
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, receiver);
+    rt.expect_validate_caller_addr(vec![receiver]);
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        rt.call::<AccountActor>(
+            Method::SetAuthDelegateExported as MethodNum,
+            IpldBlock::serialize_cbor(&SetAuthDelegateParams { delegate: Some(receiver) }).unwrap(),
+        ),
+    );
+    rt.verify();
+
+    // Nor delegate to an actor which doesn't exist, which isn't created for it.
+    for missing in [
+        Address::new_id(1001),
+        Address::new_secp256k1(&[3; fvm_shared::address::SECP_PUB_LEN]).unwrap(),
+    ] {
+        rt.expect_validate_caller_addr(vec![receiver]);
+        expect_abort(
+            ExitCode::USR_NOT_FOUND,
+            rt.call::<AccountActor>(
+                Method::SetAuthDelegateExported as MethodNum,
+                IpldBlock::serialize_cbor(&SetAuthDelegateParams { delegate: Some(missing) })
+                    .unwrap(),
+            ),
+        );
+        rt.verify();
+    }
+
+    rt.expect_validate_caller_addr(vec![receiver]);
+    rt.call::<AccountActor>(Method::SetAuthDelegateExported as MethodNum, set_params).unwrap();
+    rt.verify();
+    let state: State = rt.get_state();
+    assert_eq!(Some(delegate), state.auth_delegate);
+    check_state(&rt);
+
+    // Authentication is forwarded to the delegate, which decides the outcome.
+    let msg = AuthenticateMessageParams { signature: vec![1], message: vec![2] };
+    let params = IpldBlock::serialize_cbor(&msg).unwrap();
+    for authenticated in [true, false] {
+        rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1001));
+        rt.expect_validate_caller_any();
+        rt.expect_send(
+            delegate,
+            Method::AuthenticateMessageExported as MethodNum,
+            params.clone(),
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+            IpldBlock::serialize_cbor(&authenticated).unwrap(),
+            ExitCode::OK,
+            None,
+        );
+        let ret = rt
+            .call::<AccountActor>(Method::AuthenticateMessageExported as MethodNum, params.clone());
+        if authenticated {
+            assert!(ret.unwrap().unwrap().deserialize::<bool>().unwrap());
+        } else {
+            expect_abort_contains_message(ExitCode::USR_ILLEGAL_ARGUMENT, "rejected", ret);
+        }
+        rt.verify();
+    }
+
+    // A failure of the delegate fails authentication.
+    rt.expect_validate_caller_any();
+    rt.expect_send(
+        delegate,
+        Method::AuthenticateMessageExported as MethodNum,
+        params.clone(),
+        TokenAmount::zero(),
+        None,
+        SendFlags::READ_ONLY,
+        None,
+        ExitCode::USR_UNHANDLED_MESSAGE,
+        None,
+    );
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "delegated authentication failed",
+        rt.call::<AccountActor>(Method::AuthenticateMessageExported as MethodNum, params.clone()),
+    );
+    rt.verify();
+
+    // Clearing the delegate restores signature verification.
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, receiver);
+    rt.expect_validate_caller_addr(vec![receiver]);
+    rt.call::<AccountActor>(
+        Method::SetAuthDelegateExported as MethodNum,
+        IpldBlock::serialize_cbor(&SetAuthDelegateParams { delegate: None }).unwrap(),
+    )
+    .unwrap();
+    rt.verify();
+    assert_eq!(None, rt.get_state::<State>().auth_delegate);
+
+    rt.expect_validate_caller_any();
+    rt.expect_verify_signature(ExpectedVerifySig {
+        sig: Signature::new_secp256k1(msg.signature.clone()),
+        signer: addr,
+        plaintext: msg.message.clone(),
+        result: Ok(()),
+    });
+    assert!(rt
+        .call::<AccountActor>(Method::AuthenticateMessageExported as MethodNum, params)
+        .unwrap()
+        .unwrap()
+        .deserialize::<bool>()
+        .unwrap());
+    rt.verify();
+}
+
+#[test]
+fn auth_delegation_disabled_by_policy() {
+    let receiver = Address::new_id(100);
+    let rt = MockRuntime { receiver, ..Default::default() };
+    let addr = Address::new_secp256k1(&[2; fvm_shared::address::SECP_PUB_LEN]).unwrap();
+    construct_account(&rt, addr);
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, receiver);
+    rt.expect_validate_caller_addr(vec![receiver]);
+    expect_abort_contains_message(
+        ExitCode::USR_FORBIDDEN,
+        "delegation is disabled",
+        rt.call::<AccountActor>(
+            Method::SetAuthDelegateExported as MethodNum,
+            IpldBlock::serialize_cbor(&SetAuthDelegateParams {
+                delegate: Some(Address::new_id(1000)),
+            })
+            .unwrap(),
+        ),
+    );
+    rt.verify();
+}
+
+#[test]
+fn state_without_delegate_keeps_encoding() {
+    let address = Address::new_bls(&[1; fvm_shared::address::BLS_PUB_LEN]).unwrap();
+    // State before delegation was introduced was a one-element tuple.
+    let legacy = fvm_ipld_encoding::to_vec(&(address,)).unwrap();
+    let state = State { address, auth_delegate: None };
+    assert_eq!(legacy, fvm_ipld_encoding::to_vec(&state).unwrap());
+    assert_eq!(state, fvm_ipld_encoding::from_slice(&legacy).unwrap());
+
+    let state = State { address, auth_delegate: Some(Address::new_id(1000)) };
+    let encoded = fvm_ipld_encoding::to_vec(&state).unwrap();
+    assert_eq!(state, fvm_ipld_encoding::from_slice(&encoded).unwrap());
+}
+
+#[test]
+fn bls_public_key() {
+    let key = [7; fvm_shared::address::BLS_PUB_LEN];
+    let state = State { address: Address::new_bls(&key).unwrap(), auth_delegate: None };
+    assert_eq!(Some(&key), state.bls_public_key());
+
+    let address = Address::new_secp256k1(&[2; fvm_shared::address::SECP_PUB_LEN]).unwrap();
+    let state = State { address, auth_delegate: None };
+    assert_eq!(None, state.bls_public_key());
+}
+
 #[test]
 fn test_fallback() {
     let rt = MockRuntime { receiver: Address::new_id(100), ..Default::default() };
@@ -195,6 +387,17 @@ fn test_fallback() {
     rt.verify();
 }
 
+fn construct_account(rt: &MockRuntime, addr: Address) {
+    rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+    rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+    rt.call::<AccountActor>(
+        Method::Constructor as MethodNum,
+        IpldBlock::serialize_cbor(&addr).unwrap(),
+    )
+    .unwrap();
+    rt.verify();
+}
+
 fn check_state(rt: &MockRuntime) {
     let test_address = Address::new_id(1000);
     let (_, acc) = check_state_invariants(&rt.get_state(), &test_address);
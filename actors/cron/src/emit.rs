@@ -0,0 +1,19 @@
+// A namespace for helpers that build and emit cron events.
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::error::ExitCode;
+
+use crate::Entry;
+
+/// Indicates that calling an entry failed during an epoch tick, and the entry was skipped.
+pub fn entry_failed(rt: &impl Runtime, entry: &Entry, code: ExitCode) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("cron-entry-failed")
+            .field_indexed("receiver", &entry.receiver)
+            .field("method", &entry.method_num)
+            .field("exit-code", &code.value())
+            .build()?,
+    )
+}
@@ -2,9 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
-use fil_actors_runtime::{
-    actor_dispatch, actor_error, extract_send_result, ActorError, SYSTEM_ACTOR_ADDR,
-};
+use fil_actors_runtime::{actor_dispatch, actor_error, ActorError, SYSTEM_ACTOR_ADDR};
+use fvm_shared::sys::SendFlags;
 
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::econ::TokenAmount;
@@ -15,7 +14,9 @@ use num_traits::Zero;
 
 pub use self::state::{Entry, State};
 
+mod emit;
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 
 #[cfg(feature = "fil-actor")]
@@ -29,6 +30,7 @@ fil_actors_runtime::wasm_trampoline!(Actor);
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
     EpochTick = 2,
+    ListEntriesExported = frc42_dispatch::method_hash!("ListEntries"),
 }
 
 /// Constructor parameters for Cron actor, contains entries
@@ -39,6 +41,12 @@ pub struct ConstructorParams {
     pub entries: Vec<Entry>,
 }
 
+#[derive(Default, Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ListEntriesReturn {
+    pub entries: Vec<Entry>,
+}
+
 /// Cron actor
 pub struct Actor;
 
@@ -56,24 +64,42 @@ impl Actor {
         rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
 
         let st: State = rt.state()?;
+        let gas_limit = rt.policy().cron_entry_gas_limit;
         for entry in st.entries {
-            // Intentionally ignore any error when calling cron methods
-            let res = extract_send_result(rt.send_simple(
+            // A failing entry is reported and skipped, so it can't prevent the others running.
+            let (code, gas_used) = match rt.send_for_result(
                 &entry.receiver,
                 entry.method_num,
                 None,
                 TokenAmount::zero(),
-            ));
-            if let Err(e) = res {
+                Some(gas_limit),
+                SendFlags::empty(),
+            ) {
+                Ok(ret) => (ret.exit_code, ret.gas_used),
+                Err(e) => (ActorError::from(e).exit_code(), 0),
+            };
+            if !code.is_success() {
                 log::error!(
-                    "cron failed to send entry to {}, send error code {}",
+                    "cron failed to send entry to {}, send error code {} after using {} gas",
                     entry.receiver,
-                    e
+                    code,
+                    gas_used
                 );
+                // Failing to report the entry mustn't abort the tick either.
+                if let Err(e) = emit::entry_failed(rt, &entry, code) {
+                    log::error!("cron failed to emit entry failure for {}: {}", entry.receiver, e);
+                }
             }
         }
         Ok(())
     }
+
+    /// Returns the entries called at every epoch tick.
+    fn list_entries(rt: &impl Runtime) -> Result<ListEntriesReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        Ok(ListEntriesReturn { entries: st.entries })
+    }
 }
 
 impl ActorCode for Actor {
@@ -86,5 +112,6 @@ impl ActorCode for Actor {
     actor_dispatch! {
         Constructor => constructor,
         EpochTick => epoch_tick,
+        ListEntriesExported => list_entries,
     }
 }
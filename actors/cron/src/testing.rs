@@ -1,10 +1,11 @@
 use fil_actors_runtime::MessageAccumulator;
 use fvm_shared::address::Protocol;
 
-use crate::State;
+use crate::{Entry, State};
 
 pub struct StateSummary {
     pub entry_count: usize,
+    pub entries: Vec<Entry>,
 }
 
 pub fn check_state_invariants(state: &State) -> (StateSummary, MessageAccumulator) {
@@ -21,5 +22,5 @@ pub fn check_state_invariants(state: &State) -> (StateSummary, MessageAccumulato
         );
     });
 
-    (StateSummary { entry_count: state.entries.len() }, acc)
+    (StateSummary { entry_count: state.entries.len(), entries: state.entries.clone() }, acc)
 }
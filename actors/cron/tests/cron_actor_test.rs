@@ -4,13 +4,16 @@
 use std::cell::RefCell;
 
 use fil_actor_cron::testing::check_state_invariants;
-use fil_actor_cron::{Actor as CronActor, ConstructorParams, Entry, State};
+use fil_actor_cron::{
+    Actor as CronActor, ConstructorParams, Entry, ListEntriesReturn, Method, State,
+};
 use fil_actors_runtime::test_utils::*;
-use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
+use fil_actors_runtime::{EventBuilder, SYSTEM_ACTOR_ADDR};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
 use num_traits::Zero;
 
 fn check_state(rt: &MockRuntime) {
@@ -80,41 +83,62 @@ fn epoch_tick_with_entries() {
 
     construct_and_verify(&rt, &params);
 
-    // ExitCodes dont matter here
-    rt.expect_send_simple(
-        entry1.receiver,
-        entry1.method_num,
-        None,
-        TokenAmount::zero(),
-        None,
-        ExitCode::OK,
-    );
-    rt.expect_send_simple(
-        entry2.receiver,
-        entry2.method_num,
-        None,
-        TokenAmount::zero(),
-        None,
-        ExitCode::USR_ILLEGAL_ARGUMENT,
-    );
-    rt.expect_send_simple(
-        entry3.receiver,
-        entry3.method_num,
+    // A failed entry is reported, and the entries after it still run.
+    expect_entry_send(&rt, &entry1, ExitCode::OK);
+    expect_entry_send(&rt, &entry2, ExitCode::USR_ILLEGAL_ARGUMENT);
+    expect_entry_failed(&rt, &entry2, ExitCode::USR_ILLEGAL_ARGUMENT);
+    expect_entry_send(&rt, &entry3, ExitCode::SYS_OUT_OF_GAS);
+    expect_entry_failed(&rt, &entry3, ExitCode::SYS_OUT_OF_GAS);
+    expect_entry_send(&rt, &entry4, ExitCode::OK);
+
+    epoch_tick_and_verify(&rt);
+}
+
+#[test]
+fn list_entries() {
+    let rt = construct_runtime();
+
+    let entry1 = Entry { receiver: Address::new_id(1001), method_num: 1001 };
+    let entry2 = Entry { receiver: Address::new_id(1002), method_num: 1002 };
+    let params = ConstructorParams { entries: vec![entry1, entry2] };
+    construct_and_verify(&rt, &params);
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(2000));
+    rt.expect_validate_caller_any();
+    let ret: ListEntriesReturn = rt
+        .call::<CronActor>(Method::ListEntriesExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    assert_eq!(params.entries, ret.entries);
+}
+
+fn expect_entry_send(rt: &MockRuntime, entry: &Entry, exit_code: ExitCode) {
+    rt.expect_send(
+        entry.receiver,
+        entry.method_num,
         None,
         TokenAmount::zero(),
+        Some(rt.policy.cron_entry_gas_limit),
+        SendFlags::empty(),
         None,
-        ExitCode::OK,
-    );
-    rt.expect_send_simple(
-        entry4.receiver,
-        entry4.method_num,
-        None,
-        TokenAmount::zero(),
+        exit_code,
         None,
-        ExitCode::OK,
     );
+}
 
-    epoch_tick_and_verify(&rt);
+fn expect_entry_failed(rt: &MockRuntime, entry: &Entry, exit_code: ExitCode) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("cron-entry-failed")
+            .field_indexed("receiver", &entry.receiver)
+            .field("method", &entry.method_num)
+            .field("exit-code", &exit_code.value())
+            .build()
+            .unwrap(),
+    );
 }
 
 fn construct_and_verify(rt: &MockRuntime, params: &ConstructorParams) {
@@ -1,14 +1,11 @@
 use cid::Cid;
-use frc46_token::token::types::{
-    BurnFromParams, BurnFromReturn, BurnParams, BurnReturn, DecreaseAllowanceParams,
-    GetAllowanceParams, IncreaseAllowanceParams, MintReturn, RevokeAllowanceParams,
-    TransferFromParams, TransferFromReturn, TransferParams, TransferReturn,
-};
-use frc46_token::token::{Token, TokenError, TOKEN_PRECISION};
+use frc46_token::token::state::{actor_id_key, decode_actor_id};
+use frc46_token::token::{Token, TokenError};
 use fvm_actor_utils::receiver::ReceiverHookError;
 use fvm_actor_utils::syscalls::{NoStateError, Syscalls};
 use fvm_actor_utils::util::ActorRuntime;
 use fvm_ipld_encoding::RawBytes;
+use fvm_ipld_hamt::BytesKey;
 use fvm_shared::address::Address;
 use fvm_shared::bigint::BigInt;
 use fvm_shared::econ::TokenAmount;
@@ -19,10 +16,15 @@ use lazy_static::lazy_static;
 use log::info;
 use num_derive::FromPrimitive;
 
+use fil_actors_runtime::frc46::{
+    BurnFromParams, BurnFromReturn, BurnParams, BurnReturn, DecreaseAllowanceParams,
+    GetAllowanceParams, IncreaseAllowanceParams, MintReturn, RevokeAllowanceParams,
+    TransferFromParams, TransferFromReturn, TransferParams, TransferReturn, TOKEN_PRECISION,
+};
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
     actor_dispatch, actor_error, extract_send_result, ActorContext, ActorError, AsActorError,
-    SYSTEM_ACTOR_ADDR,
+    ValidateParams, SYSTEM_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -33,6 +35,7 @@ pub use self::types::*;
 fil_actors_runtime::wasm_trampoline!(Actor);
 
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 mod types;
 
@@ -82,6 +85,7 @@ pub enum Method {
     BurnExported = frc42_dispatch::method_hash!("Burn"),
     BurnFromExported = frc42_dispatch::method_hash!("BurnFrom"),
     AllowanceExported = frc42_dispatch::method_hash!("Allowance"),
+    ListBalancesExported = frc42_dispatch::method_hash!("ListBalances"),
 }
 
 pub struct Actor;
@@ -150,6 +154,55 @@ impl Actor {
             .actor_result()
     }
 
+    /// Returns a page of at most `limit` non-zero token balances, i.e. the verified clients
+    /// (and escrowing actors) currently holding data cap.
+    /// This method is not part of the fungible token standard.
+    pub fn list_balances(
+        rt: &impl Runtime,
+        params: ListBalancesParams,
+    ) -> Result<ListBalancesReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params.validate()?;
+        let start = params
+            .cursor
+            .map(|addr| {
+                addr.id().map(actor_id_key).map_err(|_| {
+                    actor_error!(illegal_argument, "list balances cursor {} must be an ID", addr)
+                })
+            })
+            .transpose()?;
+
+        let st: State = rt.state()?;
+        let store = rt.store();
+        let balance_map = st
+            .token
+            .get_balance_map(&store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load balances")?;
+        let mut entries = Vec::new();
+        let (_, next) = balance_map
+            .for_each_ranged(start.as_ref(), Some(params.limit as usize), |k, v| {
+                entries.push((k.clone(), v.clone()));
+                Ok(())
+            })
+            .with_context_code(ExitCode::USR_NOT_FOUND, || {
+                format!("failed to list balances from {:?}", params.cursor)
+            })?;
+
+        let to_address = |k: &BytesKey| {
+            decode_actor_id(k)
+                .map(Address::new_id)
+                .ok_or_else(|| actor_error!(illegal_state, "invalid balance key {:?}", k))
+        };
+        let balances = entries
+            .iter()
+            .map(|(k, balance)| {
+                Ok(HolderBalance { address: to_address(k)?, balance: balance.clone() })
+            })
+            .collect::<Result<Vec<_>, ActorError>>()?;
+        let next_cursor = next.as_ref().map(to_address).transpose()?;
+        Ok(ListBalancesReturn { balances, next_cursor })
+    }
+
     /// Mints new data cap tokens for an address (a verified client).
     /// Simultaneously sets the allowance for any specified operators to effectively infinite.
     /// Only the governor can call this method.
@@ -521,5 +574,6 @@ impl ActorCode for Actor {
         BurnExported => burn,
         BurnFromExported => burn_from,
         AllowanceExported => allowance,
+        ListBalancesExported => list_balances,
     }
 }
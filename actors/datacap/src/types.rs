@@ -1,3 +1,4 @@
+use fil_actors_runtime::validate_params;
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
@@ -83,3 +84,27 @@ pub struct DestroyParams {
 pub struct GranularityReturn {
     pub granularity: u64,
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListBalancesParams {
+    // ID address of the holder from which to resume listing, as returned by a previous call.
+    // None starts from the beginning.
+    pub cursor: Option<Address>,
+    // Maximum number of balances to return.
+    pub limit: u64,
+}
+
+validate_params!(ListBalancesParams { limit: range(1, u64::MAX) });
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct HolderBalance {
+    pub address: Address,
+    pub balance: TokenAmount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListBalancesReturn {
+    pub balances: Vec<HolderBalance>,
+    // Cursor to pass to a subsequent call to continue listing, if more balances remain.
+    pub next_cursor: Option<Address>,
+}
@@ -261,6 +261,77 @@ mod destroy {
     }
 }
 
+mod list {
+    use fvm_ipld_encoding::ipld_block::IpldBlock;
+    use fvm_shared::address::Address;
+    use fvm_shared::econ::TokenAmount;
+    use fvm_shared::error::ExitCode;
+    use fvm_shared::MethodNum;
+
+    use fil_actor_datacap::{Actor, ListBalancesParams, ListBalancesReturn, Method};
+    use fil_actors_runtime::test_utils::{expect_abort, MockRuntime};
+    use fil_actors_runtime::ActorError;
+
+    use crate::*;
+
+    fn list_balances(
+        rt: &MockRuntime,
+        cursor: Option<Address>,
+        limit: u64,
+    ) -> Result<ListBalancesReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = ListBalancesParams { cursor, limit };
+        let ret = rt
+            .call::<Actor>(
+                Method::ListBalancesExported as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        Ok(ret)
+    }
+
+    #[test]
+    fn list_balances_paginates() {
+        let (rt, h) = make_harness();
+        for (i, holder) in [*ALICE, *BOB, *CARLA].iter().enumerate() {
+            h.mint(&rt, holder, &TokenAmount::from_whole(i as i64 + 1), vec![]).unwrap();
+        }
+
+        let first = list_balances(&rt, None, 2).unwrap();
+        assert_eq!(2, first.balances.len());
+        assert!(first.next_cursor.is_some());
+        let second = list_balances(&rt, first.next_cursor, 2).unwrap();
+        assert_eq!(1, second.balances.len());
+        assert_eq!(None, second.next_cursor);
+
+        let mut all = first.balances;
+        all.extend(second.balances);
+        assert_eq!(3, all.len());
+        for entry in all {
+            assert_eq!(h.get_balance(&rt, &entry.address), entry.balance);
+        }
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn list_balances_rejects_bad_params() {
+        let (rt, h) = make_harness();
+        h.mint(&rt, &ALICE, &TokenAmount::from_whole(1), vec![]).unwrap();
+
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, list_balances(&rt, None, 0));
+        rt.reset();
+        let non_id = Address::new_secp256k1(&[1u8; 65]).unwrap();
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, list_balances(&rt, Some(non_id), 1));
+        rt.reset();
+        expect_abort(ExitCode::USR_NOT_FOUND, list_balances(&rt, Some(*BOB), 1));
+        rt.reset();
+        h.check_state(&rt);
+    }
+}
+
 fn make_harness() -> (MockRuntime, Harness) {
     let rt = new_runtime();
     let h = Harness { governor: VERIFIED_REGISTRY_ACTOR_ADDR };
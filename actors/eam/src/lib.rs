@@ -1,6 +1,6 @@
 use std::iter;
 
-use fil_actors_evm_shared::address::EthAddress;
+use fil_actors_evm_shared::address::{EthAddress, EthDelegatedAddress};
 use num_traits::Zero;
 
 use ext::{
@@ -23,7 +23,7 @@ use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 
 use fvm_ipld_encoding::{strict_bytes, tuple::*, RawBytes};
-use fvm_shared::address::{Address, Payload};
+use fvm_shared::address::Address;
 use fvm_shared::crypto::hash::SupportedHashes;
 use num_derive::FromPrimitive;
 
@@ -127,7 +127,7 @@ fn create_actor(
         RawBytes::serialize(ext::evm::ConstructorParams { creator, initcode: initcode.into() })?;
     let value = rt.message().value_received();
 
-    let f4_addr = Address::new_delegated(EAM_ACTOR_ID, &new_addr.0).unwrap();
+    let f4_addr = Address::from(EthDelegatedAddress::from(new_addr));
 
     if let Some(id) = rt.resolve_address(&f4_addr) {
         // Try to resurrect it if it is already an EVM actor (must be "dead")
@@ -173,14 +173,12 @@ fn create_actor(
 }
 
 fn resolve_eth_address(rt: &impl Runtime, actor_id: ActorID) -> Result<EthAddress, ActorError> {
-    match rt.lookup_delegated_address(actor_id).map(|a| *a.payload()) {
-        Some(Payload::Delegated(addr)) if addr.namespace() == EAM_ACTOR_ID => Ok(EthAddress(
-            addr.subaddress()
-                .try_into()
-                .context_code(ExitCode::USR_FORBIDDEN, "caller's eth address isn't valid")?,
-        )),
-        _ => Err(actor_error!(forbidden; "caller doesn't have an eth address")),
-    }
+    let addr = rt
+        .lookup_delegated_address(actor_id)
+        .context_code(ExitCode::USR_FORBIDDEN, "caller doesn't have an eth address")?;
+    EthAddress::from_delegated(&addr)
+        .context_code(ExitCode::USR_FORBIDDEN, "caller's eth address isn't valid")?
+        .context_code(ExitCode::USR_FORBIDDEN, "caller doesn't have an eth address")
 }
 
 fn resolve_caller_external(rt: &impl Runtime) -> Result<(EthAddress, EthAddress), ActorError> {
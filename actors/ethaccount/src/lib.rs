@@ -2,15 +2,21 @@ pub mod types;
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Payload;
+use fvm_shared::crypto::hash::SupportedHashes;
+use fvm_shared::crypto::signature::{SECP_SIG_LEN, SECP_SIG_MESSAGE_HASH_SIZE};
+use fvm_shared::error::ExitCode;
 use fvm_shared::{MethodNum, METHOD_CONSTRUCTOR};
 use num_derive::FromPrimitive;
 
+use fil_actors_evm_shared::address::EthAddress;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, ActorError, EAM_ACTOR_ID, FIRST_EXPORTED_METHOD_NUMBER,
-    SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, ActorError, AsActorError, EAM_ACTOR_ID,
+    FIRST_EXPORTED_METHOD_NUMBER, SYSTEM_ACTOR_ADDR,
 };
 
+use crate::types::{AuthenticateMessageParams, AuthenticateMessageReturn};
+
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(EthAccountActor);
 
@@ -19,8 +25,13 @@ fil_actors_runtime::wasm_trampoline!(EthAccountActor);
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
+    AuthenticateMessageExported = frc42_dispatch::method_hash!("AuthenticateMessage"),
 }
 
+/// Prefix of EIP-191 "personal_sign" messages, which is followed by the decimal
+/// message length and then the message itself.
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
 /// Ethereum Account actor.
 pub struct EthAccountActor;
 
@@ -50,6 +61,63 @@ impl EthAccountActor {
         Ok(())
     }
 
+    /// Authenticates whether the provided signature is valid for the provided message.
+    /// The signature must be a 65-byte secp256k1 signature (r, s, v) over the EIP-191
+    /// "personal_sign" digest of the message, by the key controlling this account's
+    /// Ethereum address.
+    /// Errors with USR_ILLEGAL_ARGUMENT if the authentication is invalid.
+    pub fn authenticate_message(
+        rt: &impl Runtime,
+        params: AuthenticateMessageParams,
+    ) -> Result<AuthenticateMessageReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        let eth_address = rt
+            .lookup_delegated_address(rt.message().receiver().id().unwrap())
+            .and_then(|addr| EthAddress::from_delegated(&addr).ok().flatten())
+            .context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "EthAccount does not have an Ethereum delegated address",
+            )?;
+
+        let mut signature: [u8; SECP_SIG_LEN] =
+            params.signature.as_slice().try_into().map_err(|_| {
+                actor_error!(
+                    illegal_argument,
+                    "signature must be {} bytes, was {}",
+                    SECP_SIG_LEN,
+                    params.signature.len()
+                )
+            })?;
+        // Accept both raw recovery IDs and the legacy Ethereum encoding (27 or 28).
+        if signature[SECP_SIG_LEN - 1] >= 27 {
+            signature[SECP_SIG_LEN - 1] -= 27;
+        }
+
+        let mut prefixed = EIP191_PREFIX.to_vec();
+        prefixed.extend_from_slice(params.message.len().to_string().as_bytes());
+        prefixed.extend_from_slice(&params.message);
+        let digest: [u8; SECP_SIG_MESSAGE_HASH_SIZE] = rt
+            .hash(SupportedHashes::Keccak256, &prefixed)
+            .try_into()
+            .map_err(|_| actor_error!(illegal_state, "unexpected keccak256 digest length"))?;
+
+        let pubkey = rt
+            .recover_secp_public_key(&digest, &signature)
+            .map_err(|e| actor_error!(illegal_argument, "failed to authenticate message, {}", e))?;
+        // The Ethereum address is the last 20 bytes of the hash of the uncompressed
+        // public key, without its 0x04 prefix.
+        let pubkey_hash = rt.hash(SupportedHashes::Keccak256, &pubkey[1..]);
+        if pubkey_hash[12..] != eth_address.0[..] {
+            return Err(actor_error!(
+                illegal_argument,
+                "failed to authenticate message, signature invalid"
+            ));
+        }
+
+        Ok(AuthenticateMessageReturn { authenticated: true })
+    }
+
     // Always succeeds, accepting any transfers.
     pub fn fallback(
         rt: &impl Runtime,
@@ -74,6 +142,7 @@ impl ActorCode for EthAccountActor {
 
     actor_dispatch! {
         Constructor => constructor,
+        AuthenticateMessageExported => authenticate_message,
         _ => fallback,
     }
 }
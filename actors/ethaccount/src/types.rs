@@ -8,3 +8,9 @@ pub struct AuthenticateMessageParams {
     #[serde(with = "strict_bytes")]
     pub message: Vec<u8>,
 }
+
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct AuthenticateMessageReturn {
+    pub authenticated: bool,
+}
@@ -1,7 +1,7 @@
 mod util;
 
 use crate::util::*;
-use fvm_actor_utils::receiver::UniversalReceiverParams;
+use fil_actors_runtime::frc46::UniversalReceiverParams;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
@@ -46,3 +46,94 @@ fn token_receiver() {
         .unwrap();
     assert!(ret.is_none());
 }
+
+mod authenticate_message {
+    use super::*;
+    use fil_actor_ethaccount::types::AuthenticateMessageParams;
+    use fil_actors_runtime::test_utils::{expect_abort, hash};
+    use fil_actors_runtime::EAM_ACTOR_ID;
+    use fvm_shared::crypto::hash::SupportedHashes;
+
+    const MESSAGE: &[u8] = b"hello filecoin";
+
+    fn eth_signing_key() -> (libsecp256k1::SecretKey, [u8; 20]) {
+        let key = libsecp256k1::SecretKey::parse(&[1u8; 32]).unwrap();
+        let pubkey = libsecp256k1::PublicKey::from_secret_key(&key).serialize();
+        let (digest, _) = hash(SupportedHashes::Keccak256, &pubkey[1..]);
+        (key, digest[12..32].try_into().unwrap())
+    }
+
+    fn sign(key: &libsecp256k1::SecretKey, message: &[u8], legacy_v: bool) -> Vec<u8> {
+        let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+        prefixed.extend_from_slice(message);
+        let (digest, _) = hash(SupportedHashes::Keccak256, &prefixed);
+        let msg = libsecp256k1::Message::parse_slice(&digest[..32]).unwrap();
+        let (sig, recovery_id) = libsecp256k1::sign(&msg, key);
+        let mut bytes = sig.serialize().to_vec();
+        bytes.push(recovery_id.serialize() + if legacy_v { 27 } else { 0 });
+        bytes
+    }
+
+    fn setup_with_key() -> (fil_actors_runtime::test_utils::MockRuntime, libsecp256k1::SecretKey) {
+        let (key, eth_addr) = eth_signing_key();
+        let rt = new_runtime();
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        rt.set_delegated_address(
+            EOA.id().unwrap(),
+            Address::new_delegated(EAM_ACTOR_ID, &eth_addr).unwrap(),
+        );
+        rt.call::<EthAccountActor>(Method::Constructor as MethodNum, None).unwrap();
+        rt.verify();
+        (rt, key)
+    }
+
+    fn authenticate(
+        rt: &fil_actors_runtime::test_utils::MockRuntime,
+        signature: Vec<u8>,
+        message: &[u8],
+    ) -> Result<Option<IpldBlock>, fil_actors_runtime::ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(1234));
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<EthAccountActor>(
+            Method::AuthenticateMessageExported as MethodNum,
+            IpldBlock::serialize_cbor(&AuthenticateMessageParams {
+                signature,
+                message: message.to_vec(),
+            })
+            .unwrap(),
+        );
+        rt.verify();
+        ret
+    }
+
+    #[test]
+    fn valid_signature() {
+        let (rt, key) = setup_with_key();
+        for legacy_v in [false, true] {
+            let ret = authenticate(&rt, sign(&key, MESSAGE, legacy_v), MESSAGE).unwrap();
+            assert!(ret.unwrap().deserialize::<bool>().unwrap());
+        }
+    }
+
+    #[test]
+    fn invalid_signature() {
+        let (rt, key) = setup_with_key();
+
+        // signed by a different key
+        let other = libsecp256k1::SecretKey::parse(&[2u8; 32]).unwrap();
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            authenticate(&rt, sign(&other, MESSAGE, false), MESSAGE),
+        );
+
+        // signature over a different message
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            authenticate(&rt, sign(&key, b"other message", false), MESSAGE),
+        );
+
+        // malformed signature
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, authenticate(&rt, vec![1, 2, 3], MESSAGE));
+    }
+}
@@ -1,9 +1,14 @@
 use crate::uints::U256;
-use fil_actors_runtime::EAM_ACTOR_ID;
+use fil_actors_runtime::runtime::Primitives;
+use fil_actors_runtime::{ActorError, DelegatedAddress, EAM_ACTOR_ID};
 use fvm_ipld_encoding::{serde, strict_bytes};
 use fvm_shared::address::Address;
+use fvm_shared::crypto::hash::SupportedHashes;
 use fvm_shared::ActorID;
 
+/// The sub-address of an Ethereum address's f4 address, in the EAM's namespace.
+pub type EthDelegatedAddress = DelegatedAddress<EAM_ACTOR_ID, 20>;
+
 /// A Filecoin address as represented in the FEVM runtime (also called EVM-form).
 #[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy)]
 pub struct EthAddress(#[serde(with = "strict_bytes")] pub [u8; 20]);
@@ -48,7 +53,52 @@ impl From<&EthAddress> for Address {
     }
 }
 
+impl From<EthDelegatedAddress> for EthAddress {
+    fn from(addr: EthDelegatedAddress) -> Self {
+        Self(addr.0)
+    }
+}
+
+impl From<EthAddress> for EthDelegatedAddress {
+    fn from(addr: EthAddress) -> Self {
+        Self(addr.0)
+    }
+}
+
+/// Converts an f4 address in the EAM's namespace, or an ID address, to the EthAddress that
+/// converts back to it.
+impl TryFrom<&Address> for EthAddress {
+    type Error = ActorError;
+
+    fn try_from(addr: &Address) -> Result<Self, Self::Error> {
+        if let Ok(id) = addr.id() {
+            return Ok(Self::from_id(id));
+        }
+        EthDelegatedAddress::try_from(addr).map(Self::from)
+    }
+}
+
 impl EthAddress {
+    /// Returns the Ethereum address of an f4 address in the EAM's namespace, None if the address
+    /// is of another kind or namespace, and an error if its sub-address isn't 20 bytes.
+    pub fn from_delegated(addr: &Address) -> Result<Option<Self>, ActorError> {
+        Ok(EthDelegatedAddress::from_address(addr)?.map(Self::from))
+    }
+
+    /// Formats the address as hex with the mixed-case checksum of EIP-55.
+    pub fn to_checksum_string(&self, prim: &(impl Primitives + ?Sized)) -> String {
+        let hex = hex::encode(self.0);
+        let hash = prim.hash(SupportedHashes::Keccak256, hex.as_bytes());
+        let mut out = String::with_capacity(42);
+        out.push_str("0x");
+        for (i, c) in hex.chars().enumerate() {
+            // Letters are upper-cased where the corresponding nibble of the hash is at least 8.
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0xf;
+            out.push(if nibble >= 8 { c.to_ascii_uppercase() } else { c });
+        }
+        out
+    }
+
     /// Returns a "null" address.
     pub const fn null() -> Self {
         Self([0u8; 20])
@@ -116,6 +166,9 @@ impl AsRef<[u8]> for EthAddress {
 mod tests {
     use super::EthAddress;
     use crate::uints::U256;
+    use fil_actors_runtime::test_utils::MockRuntime;
+    use fil_actors_runtime::EAM_ACTOR_ID;
+    use fvm_shared::address::Address;
 
     // padding (12 bytes)
     const TYPE_PADDING: &[u8] = &[0; 12];
@@ -182,4 +235,42 @@ mod tests {
             vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01].as_slice() // ID address (u64 big endian) (8 bytes)
         ] => None,
     }
+
+    #[test]
+    fn checksum_string() {
+        // Vectors from EIP-55.
+        let rt = MockRuntime::default();
+        for expected in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let bytes = hex::decode(&expected[2..]).unwrap();
+            let addr = EthAddress(bytes.try_into().unwrap());
+            assert_eq!(expected, addr.to_checksum_string(&rt));
+            assert_eq!(expected.to_lowercase(), addr.to_string());
+        }
+    }
+
+    #[test]
+    fn converts_from_address() {
+        let eth = EthAddress([0x11; 20]);
+        let f4 = Address::new_delegated(EAM_ACTOR_ID, &eth.0).unwrap();
+        assert_eq!(f4, Address::from(eth));
+        assert_eq!(eth, EthAddress::try_from(&f4).unwrap());
+        assert_eq!(Some(eth), EthAddress::from_delegated(&f4).unwrap());
+
+        // ID addresses convert to and from their EVM form.
+        let id = Address::new_id(1234);
+        assert_eq!(EthAddress::from_id(1234), EthAddress::try_from(&id).unwrap());
+        assert_eq!(id, Address::from(EthAddress::from_id(1234)));
+        assert_eq!(None, EthAddress::from_delegated(&id).unwrap());
+
+        // Other addresses, and sub-addresses of the wrong length, are rejected.
+        let other = Address::new_delegated(EAM_ACTOR_ID + 1, &eth.0).unwrap();
+        assert!(EthAddress::try_from(&other).is_err());
+        let short = Address::new_delegated(EAM_ACTOR_ID, &eth.0[1..]).unwrap();
+        assert!(EthAddress::from_delegated(&short).is_err());
+    }
 }
@@ -74,6 +74,14 @@ macro_rules! def_opcodes {
             table
         }
         $(pub const $op: u8 = $code;)*
+
+        /// Returns the mnemonic of an opcode, if it's defined.
+        pub fn name(opcode: u8) -> Option<&'static str> {
+            match opcode {
+                $($code => Some(stringify!($op)),)*
+                _ => None,
+            }
+        }
     }
 }
 
@@ -239,6 +247,7 @@ pub mod opcodes {
         // 0xEF Reserved for EIP-3541
         0xf0: CREATE,
         0xf1: CALL,
+        0xf2: CALLCODE,
         0xf3: RETURN,
         0xf4: DELEGATECALL,
         0xf5: CREATE2,
@@ -260,9 +269,20 @@ impl<'r, 'a, RT: Runtime + 'r> Machine<'r, 'a, RT> {
 
     pub fn execute(mut self) -> Result<Output, ActorError> {
         while self.pc < self.bytecode.len() {
+            #[cfg(feature = "trace")]
+            let traced = super::trace::start_step(
+                self.system.rt,
+                self.state,
+                self.pc,
+                self.bytecode[self.pc],
+            );
+            let result = self.step();
+            #[cfg(feature = "trace")]
+            super::trace::end_step(self.system.rt, traced);
+
             // This is faster than the question mark operator, and speed counts here.
             #[allow(clippy::question_mark)]
-            if let Err(e) = self.step() {
+            if let Err(e) = result {
                 return Err(e.wrap(format!("ABORT(pc={})", self.pc)));
             }
         }
@@ -410,6 +430,7 @@ mod tests {
             LOG3,
             LOG4,
             CALL,
+            CALLCODE,
             DELEGATECALL,
             STATICCALL,
             CODECOPY,
@@ -23,6 +23,7 @@ use {
     fil_actors_runtime::ActorError,
     fvm_shared::econ::TokenAmount,
     fvm_shared::error::ErrorNumber,
+    num_traits::Zero,
 };
 
 /// The gas granted on bare "transfers".
@@ -107,6 +108,26 @@ pub fn call_call<RT: Runtime>(
     )
 }
 
+#[inline]
+pub fn call_callcode<RT: Runtime>(
+    state: &mut ExecutionState,
+    system: &mut System<RT>,
+    gas: U256,
+    dst: U256,
+    value: U256,
+    input_offset: U256,
+    input_size: U256,
+    output_offset: U256,
+    output_size: U256,
+) -> Result<U256, ActorError> {
+    call_generic(
+        state,
+        system,
+        CallKind::CallCode,
+        (gas, dst, value, input_offset, input_size, output_offset, output_size),
+    )
+}
+
 #[inline]
 pub fn call_delegatecall<RT: Runtime>(
     state: &mut ExecutionState,
@@ -155,8 +176,9 @@ pub fn call_generic<RT: Runtime>(
 
     let (mut gas, dst, value, input_offset, input_size, output_offset, output_size) = params;
 
-    if system.readonly && value > U256::zero() {
-        // non-zero sends are side-effects and hence a static mode violation
+    if system.readonly && kind != CallKind::CallCode && value > U256::zero() {
+        // non-zero sends are side-effects and hence a static mode violation.
+        // The value of a CALLCODE is only reported to the callee code, never transferred.
         return Err(ActorError::read_only("cannot transfer value when read-only".into()));
     }
 
@@ -261,45 +283,59 @@ pub fn call_generic<RT: Runtime>(
                         },
                     }
                 }
-                CallKind::DelegateCall => match get_contract_type(system.rt, &dst) {
-                    ContractType::EVM(dst_addr) => {
-                        // If we're calling an actual EVM actor, get its code.
-                        if let Some(code) = get_evm_bytecode_cid(system, &dst_addr)? {
-                            // and then invoke self with delegate; readonly context is sticky
-                            let params = DelegateCallParams {
-                                code,
-                                input: input_data.into(),
-                                caller: state.caller,
-                                value: state.value_received.clone(),
-                            };
-                            system
-                                .send(
-                                    &system.rt.message().receiver(),
-                                    Method::InvokeContractDelegate as u64,
-                                    IpldBlock::serialize_dag_cbor(&params)?,
-                                    TokenAmount::from(&value),
-                                    Some(effective_gas_limit(system, gas)),
-                                    SendFlags::default(),
-                                )
-                                .map_err(|mut ae| ae.take_data())
-                        } else {
-                            // If it doesn't have code, short-circuit and return immediately.
-                            Ok(None)
+                CallKind::CallCode if TokenAmount::from(&value) > system.rt.current_balance() => {
+                    // The EVM fails a CALLCODE with more value than the caller could transfer.
+                    Err(None)
+                }
+                CallKind::DelegateCall | CallKind::CallCode => {
+                    match get_contract_type(system.rt, &dst) {
+                        ContractType::EVM(dst_addr) => {
+                            // If we're calling an actual EVM actor, get its code.
+                            if let Some(code) = get_evm_bytecode_cid(system, &dst_addr)? {
+                                // and then invoke self with delegate; readonly context is sticky.
+                                // A DELEGATECALL keeps the current caller and value, while a CALLCODE
+                                // runs the code as a call from this contract with the given value.
+                                let (caller, value) = if kind == CallKind::CallCode {
+                                    (state.receiver, TokenAmount::from(&value))
+                                } else {
+                                    (state.caller, state.value_received.clone())
+                                };
+                                let params = DelegateCallParams {
+                                    code,
+                                    input: input_data.into(),
+                                    caller,
+                                    value,
+                                };
+                                // No value is transferred, as the code runs in this contract's context.
+                                system
+                                    .send(
+                                        &system.rt.message().receiver(),
+                                        Method::InvokeContractDelegate as u64,
+                                        IpldBlock::serialize_dag_cbor(&params)?,
+                                        TokenAmount::zero(),
+                                        Some(effective_gas_limit(system, gas)),
+                                        SendFlags::default(),
+                                    )
+                                    .map_err(|mut ae| ae.take_data())
+                            } else {
+                                // If it doesn't have code, short-circuit and return immediately.
+                                Ok(None)
+                            }
+                        }
+                        // If we're calling an account or a non-existent actor, return nothing because
+                        // this is how the EVM behaves.
+                        ContractType::Account | ContractType::NotFound => Ok(None),
+                        // If we're calling a "native" actor, always revert.
+                        ContractType::Native(cid) => {
+                            log::info!("attempted to {kind:?} a native actor {cid} at {dst:?}");
+                            Err(None)
+                        }
+                        ContractType::Precompile => {
+                            log::error!("reached a precompile address in {kind:?} when a precompile should've been caught earlier in the system");
+                            Err(None)
                         }
                     }
-                    // If we're calling an account or a non-existent actor, return nothing because
-                    // this is how the EVM behaves.
-                    ContractType::Account | ContractType::NotFound => Ok(None),
-                    // If we're calling a "native" actor, always revert.
-                    ContractType::Native(cid) => {
-                        log::info!("attempted to delegatecall a native actor {cid} at {dst:?}");
-                        Err(None)
-                    }
-                    ContractType::Precompile => {
-                        log::error!("reached a precompile address in DelegateCall when a precompile should've been caught earlier in the system");
-                        Err(None)
-                    }
-                },
+                }
             };
             let (code, data) = match call_result {
                 Ok(result) => (1, result),
@@ -804,6 +840,108 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_callcode() {
+        let receiver = FilAddress::new_id(1000);
+        let dest = EthAddress::from_id(1001);
+        let caller = EthAddress::from_id(1002);
+        let fil_dest = FilAddress::new_id(1001);
+        let input_data = vec![0x01, 0x02, 0x03, 0x04];
+        let output_data = vec![0xCA, 0xFE, 0xBA, 0xBE];
+        evm_unit_test! {
+            (rt) {
+                rt.in_call.replace(true);
+                rt.receiver = receiver;
+                rt.set_balance(TokenAmount::from_atto(10));
+                rt.set_address_actor_type(fil_dest, *EVM_ACTOR_CODE_ID);
+
+                let bytecode = vec![0xFE, 0xED, 0x43, 0x33];
+                let bytecode_cid = Cid::try_from("baeaikaia").unwrap();
+                rt.store.put_keyed(&bytecode_cid, bytecode.as_slice()).unwrap();
+
+                rt.expect_send(
+                    fil_dest,
+                    crate::Method::GetBytecode as u64,
+                    Default::default(),
+                    TokenAmount::zero(),
+                    None,
+                    SendFlags::READ_ONLY,
+                    IpldBlock::serialize_cbor(&bytecode_cid).unwrap(),
+                    ExitCode::OK,
+                    None,
+                );
+
+                // The code runs as called by this contract, with the value reported but retained.
+                let params = crate::DelegateCallParams {
+                    code: bytecode_cid,
+                    input: input_data,
+                    caller: EthAddress::from_id(1000),
+                    value: TokenAmount::from_atto(5),
+                };
+
+                rt.expect_send(
+                    receiver,
+                    crate::Method::InvokeContractDelegate as u64,
+                    IpldBlock::serialize_dag_cbor(&params).unwrap(),
+                    TokenAmount::zero(),
+                    Some(1_000_000_000),
+                    SendFlags::empty(),
+                    Some(IpldBlock { codec: IPLD_RAW, data: output_data.clone() }),
+                    ExitCode::OK,
+                    None,
+                );
+                rt.expect_gas_available(10_000_000_000);
+            }
+            (m) {
+                // input data
+                PUSH4; 0x01; 0x02; 0x03; 0x04;
+                PUSH0;
+                MSTORE;
+                // the call
+                CALLCODE;
+            }
+            m.state.caller = caller;
+            m.state.stack.push(U256::from(4)).unwrap();  // output size
+            m.state.stack.push(U256::from(0)).unwrap();  // output offset
+            m.state.stack.push(U256::from(4)).unwrap();  // input size
+            m.state.stack.push(U256::from(28)).unwrap(); // input offset
+            m.state.stack.push(U256::from(5)).unwrap();  // value
+            m.state.stack.push(dest.as_evm_word()).unwrap();  // dest
+            m.state.stack.push(U256::from(1_000_000_000)).unwrap(); // gas
+            for _ in 0..4 {
+                m.step().expect("execution step failed");
+            }
+            assert_eq!(m.state.stack.len(), 1);
+            assert_eq!(m.state.stack.pop().unwrap(), U256::from(1));
+            assert_eq!(&m.state.return_data, &output_data);
+            assert_eq!(&m.state.memory[0..4], &output_data);
+        };
+    }
+
+    #[test]
+    fn test_callcode_insufficient_balance() {
+        let dest = EthAddress::from_id(1001);
+        evm_unit_test! {
+            (rt) {
+                rt.set_balance(TokenAmount::from_atto(4));
+            }
+            (m) {
+                CALLCODE;
+            }
+            m.state.stack.push(U256::from(0)).unwrap();  // output size
+            m.state.stack.push(U256::from(0)).unwrap();  // output offset
+            m.state.stack.push(U256::from(0)).unwrap();  // input size
+            m.state.stack.push(U256::from(0)).unwrap();  // input offset
+            m.state.stack.push(U256::from(5)).unwrap();  // value
+            m.state.stack.push(dest.as_evm_word()).unwrap();  // dest
+            m.state.stack.push(U256::from(1_000_000_000)).unwrap(); // gas
+            m.step().expect("execution step failed");
+            // The call fails without being attempted.
+            assert_eq!(m.state.stack.pop().unwrap(), U256::from(0));
+            assert!(m.state.return_data.is_empty());
+        };
+    }
+
     #[test]
     fn test_staticcall() {
         let dest = EthAddress::from_id(1001);
@@ -6,6 +6,8 @@ mod output;
 mod precompiles;
 mod stack;
 mod system;
+#[cfg(feature = "trace")]
+pub mod trace;
 
 #[cfg(test)]
 pub mod test_util;
@@ -21,6 +23,7 @@ pub use {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CallKind {
     Call,
+    CallCode,
     DelegateCall,
     StaticCall,
 }
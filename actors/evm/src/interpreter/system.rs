@@ -1,14 +1,12 @@
 use std::borrow::Cow;
 
 use fil_actors_evm_shared::{address::EthAddress, uints::U256};
-use fil_actors_runtime::{
-    actor_error, extract_send_result, runtime::EMPTY_ARR_CID, AsActorError, EAM_ACTOR_ID,
-};
+use fil_actors_runtime::{actor_error, extract_send_result, runtime::EMPTY_ARR_CID, AsActorError};
 use fvm_ipld_blockstore::Block;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::CborStore;
 use fvm_ipld_kamt::HashedKey;
-use fvm_shared::address::{Address, Payload};
+use fvm_shared::address::Address;
 use fvm_shared::crypto::hash::SupportedHashes;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::{ErrorNumber, ExitCode};
@@ -484,17 +482,12 @@ impl<'r, RT: Runtime> System<'r, RT> {
     /// - Otherwise encode ID address into Eth address (0xff....\<id>)
     pub fn resolve_ethereum_address(&self, addr: &Address) -> Result<EthAddress, ActorError> {
         // Short-circuit if we already have an EVM actor.
-        match addr.payload() {
-            Payload::Delegated(delegated) if delegated.namespace() == EAM_ACTOR_ID => {
-                let subaddr: [u8; 20] = delegated
-                    .subaddress()
-                    .try_into()
-                    .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-                        format!("invalid ethereum address length: {addr}")
-                    })?;
-                return Ok(EthAddress(subaddr));
-            }
-            _ => {}
+        if let Some(eth_addr) = EthAddress::from_delegated(addr)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("invalid ethereum address length: {addr}")
+            })?
+        {
+            return Ok(eth_addr);
         }
 
         // Otherwise, resolve to an ID address.
@@ -504,17 +497,15 @@ impl<'r, RT: Runtime> System<'r, RT> {
         )?;
 
         // Then attempt to resolve back into an EVM address.
-        match self.rt.lookup_delegated_address(actor_id).map(|a| a.into_payload()) {
-            Some(Payload::Delegated(delegated)) if delegated.namespace() == EAM_ACTOR_ID => {
-                let subaddr: [u8; 20] = delegated.subaddress().try_into().context_code(
-                    ExitCode::USR_ILLEGAL_STATE,
-                    "invalid ethereum address length: {addr}",
-                )?;
-                Ok(EthAddress(subaddr))
-            }
-            // But use an EVM address as the fallback.
-            _ => Ok(EthAddress::from_id(actor_id)),
-        }
+        let delegated = match self.rt.lookup_delegated_address(actor_id) {
+            Some(delegated) => EthAddress::from_delegated(&delegated)
+                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("invalid ethereum address length: {delegated}")
+                })?,
+            None => None,
+        };
+        // But use an EVM address as the fallback.
+        Ok(delegated.unwrap_or_else(|| EthAddress::from_id(actor_id)))
     }
 
     /// Gets the cached EVM randomness seed of the current epoch
@@ -0,0 +1,75 @@
+//! Opcode-level tracing of contract execution, for diagnosing contracts in tests.
+//!
+//! Tracing is compiled in only with the `trace` feature, which actors built for the chain
+//! don't enable. Once compiled in, nothing is recorded until [`start`] is called, and the
+//! trace belongs to the calling thread, as do contracts executed natively by a test VM.
+
+use std::cell::RefCell;
+
+use fil_actors_evm_shared::address::EthAddress;
+use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::runtime::Runtime;
+
+use super::ExecutionState;
+
+/// One instruction executed by a contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceStep {
+    /// The contract executing the instruction.
+    pub receiver: EthAddress,
+    pub pc: usize,
+    pub opcode: u8,
+    /// The stack before the instruction, from the bottom.
+    pub stack: Vec<U256>,
+    pub gas_available: u64,
+    /// The gas used by the instruction, including by any call it made.
+    pub gas_used: u64,
+}
+
+thread_local! {
+    static TRACE: RefCell<Option<Vec<TraceStep>>> = const { RefCell::new(None) };
+}
+
+/// Starts recording instructions executed on this thread, discarding any trace so far.
+pub fn start() {
+    TRACE.with(|t| *t.borrow_mut() = Some(Vec::new()));
+}
+
+/// Stops recording, returning the instructions executed since [`start`], in order.
+/// Those of a contract called by another are between the call instruction and the next.
+pub fn stop() -> Vec<TraceStep> {
+    TRACE.with(|t| t.borrow_mut().take()).unwrap_or_default()
+}
+
+/// Records an instruction about to be executed, returning its index if recording.
+pub(crate) fn start_step(
+    rt: &impl Runtime,
+    state: &ExecutionState,
+    pc: usize,
+    opcode: u8,
+) -> Option<usize> {
+    TRACE.with(|t| {
+        let mut trace = t.borrow_mut();
+        let trace = trace.as_mut()?;
+        trace.push(TraceStep {
+            receiver: state.receiver,
+            pc,
+            opcode,
+            stack: state.stack.as_slice().to_vec(),
+            gas_available: rt.gas_available(),
+            gas_used: 0,
+        });
+        Some(trace.len() - 1)
+    })
+}
+
+/// Records the gas used by an instruction, once executed.
+pub(crate) fn end_step(rt: &impl Runtime, index: Option<usize>) {
+    let Some(index) = index else { return };
+    let gas_available = rt.gas_available();
+    TRACE.with(|t| {
+        if let Some(step) = t.borrow_mut().as_mut().and_then(|trace| trace.get_mut(index)) {
+            step.gas_used = step.gas_available.saturating_sub(gas_available);
+        }
+    });
+}
@@ -1,7 +1,7 @@
 use fil_actors_evm_shared::address::EthAddress;
 use fil_actors_runtime::{
-    actor_dispatch_unrestricted, actor_error, ActorError, AsActorError, WithCodec, EAM_ACTOR_ADDR,
-    INIT_ACTOR_ADDR,
+    actor_dispatch_unrestricted, actor_error, actor_exit_code, ActorError, AsActorError, WithCodec,
+    EAM_ACTOR_ADDR, INIT_ACTOR_ADDR,
 };
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
@@ -32,14 +32,14 @@ pub use state::*;
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(EvmContractActor);
 
-pub const EVM_CONTRACT_REVERTED: ExitCode = ExitCode::new(33);
-pub const EVM_CONTRACT_INVALID_INSTRUCTION: ExitCode = ExitCode::new(34);
-pub const EVM_CONTRACT_UNDEFINED_INSTRUCTION: ExitCode = ExitCode::new(35);
-pub const EVM_CONTRACT_STACK_UNDERFLOW: ExitCode = ExitCode::new(36);
-pub const EVM_CONTRACT_STACK_OVERFLOW: ExitCode = ExitCode::new(37);
-pub const EVM_CONTRACT_ILLEGAL_MEMORY_ACCESS: ExitCode = ExitCode::new(38);
-pub const EVM_CONTRACT_BAD_JUMPDEST: ExitCode = ExitCode::new(39);
-pub const EVM_CONTRACT_SELFDESTRUCT_FAILED: ExitCode = ExitCode::new(40);
+pub const EVM_CONTRACT_REVERTED: ExitCode = actor_exit_code(33);
+pub const EVM_CONTRACT_INVALID_INSTRUCTION: ExitCode = actor_exit_code(34);
+pub const EVM_CONTRACT_UNDEFINED_INSTRUCTION: ExitCode = actor_exit_code(35);
+pub const EVM_CONTRACT_STACK_UNDERFLOW: ExitCode = actor_exit_code(36);
+pub const EVM_CONTRACT_STACK_OVERFLOW: ExitCode = actor_exit_code(37);
+pub const EVM_CONTRACT_ILLEGAL_MEMORY_ACCESS: ExitCode = actor_exit_code(38);
+pub const EVM_CONTRACT_BAD_JUMPDEST: ExitCode = actor_exit_code(39);
+pub const EVM_CONTRACT_SELFDESTRUCT_FAILED: ExitCode = actor_exit_code(40);
 
 const EVM_MAX_RESERVED_METHOD: u64 = 1023;
 pub const NATIVE_METHOD_SIGNATURE: &str = "handle_filecoin_method(uint64,uint64,bytes)";
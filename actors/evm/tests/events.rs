@@ -1,7 +1,13 @@
 mod asm;
 
+use alloy_core::primitives::Address as SolAddress;
+use alloy_core::sol;
+use alloy_core::sol_types::{SolCall, SolEvent};
+use fil_actors_evm_shared::address::EthAddress;
 use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::test_utils::EVM_ACTOR_CODE_ID;
 use fvm_ipld_encoding::IPLD_RAW;
+use fvm_shared::address::Address;
 use fvm_shared::event::{ActorEvent, Entry, Flags};
 
 mod util;
@@ -130,3 +136,60 @@ fn test_events() {
 
     rt.verify();
 }
+
+sol!("./tests/contracts/simplecoin.sol");
+
+#[test]
+fn test_solidity_event_round_trip() {
+    let owner = Address::new_id(100);
+    let rt = util::init_construct_and_verify(
+        hex::decode(include_str!("contracts/simplecoin.hex")).unwrap(),
+        |rt| {
+            rt.actor_code_cids.borrow_mut().insert(owner, *EVM_ACTOR_CODE_ID);
+            rt.set_origin(owner);
+        },
+    );
+    rt.set_caller(*EVM_ACTOR_CODE_ID, owner);
+
+    let from = SolAddress::from(EthAddress::from_id(100).0);
+    let to = SolAddress::from(EthAddress::from_id(101).0);
+    let amount = alloy_core::primitives::U256::from(42);
+
+    // Topics are the event signature hash followed by the indexed arguments, each a 32 byte
+    // word, and the data is the ABI encoding of the remaining arguments.
+    let mut padded_to = [0u8; 32];
+    padded_to[12..].copy_from_slice(to.as_slice());
+    let mut padded_from = [0u8; 32];
+    padded_from[12..].copy_from_slice(from.as_slice());
+    let topics = [SimpleCoin::Transfer::SIGNATURE_HASH.0, padded_from, padded_to];
+    let data = U256::from(42).to_bytes().to_vec();
+
+    let mut entries: Vec<Entry> = topics
+        .iter()
+        .zip(["t1", "t2", "t3"])
+        .map(|(topic, key)| Entry {
+            flags: Flags::FLAG_INDEXED_ALL,
+            key: key.to_string(),
+            codec: IPLD_RAW,
+            value: topic.to_vec(),
+        })
+        .collect();
+    entries.push(Entry {
+        flags: Flags::FLAG_INDEXED_ALL,
+        key: "d".to_string(),
+        codec: IPLD_RAW,
+        value: data.clone(),
+    });
+    rt.expect_emitted_event(ActorEvent { entries });
+
+    let call = SimpleCoin::sendCoinCall { receiver: to, amount };
+    let result = util::invoke_contract(&rt, &call.abi_encode());
+    assert!(SimpleCoin::sendCoinCall::abi_decode_returns(&result, true).unwrap().sufficient);
+    rt.verify();
+
+    // The emitted topics and data decode back to the Solidity event.
+    let decoded = SimpleCoin::Transfer::decode_raw_log(topics, &data, true).unwrap();
+    assert_eq!(from, decoded._from);
+    assert_eq!(to, decoded._to);
+    assert_eq!(amount, decoded._value);
+}
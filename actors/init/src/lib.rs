@@ -5,10 +5,12 @@ use cid::Cid;
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 
+use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, extract_send_result, ActorContext, ActorError, AsActorError,
-    EAM_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, extract_send_result, payment_channel_address, ActorContext,
+    ActorError, AsActorError, EAM_ACTOR_ADDR, EAM_ACTOR_ID, SYSTEM_ACTOR_ADDR,
 };
+use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::error::ExitCode;
 use fvm_shared::{ActorID, METHOD_CONSTRUCTOR};
@@ -18,6 +20,7 @@ pub use self::state::State;
 pub use self::types::*;
 
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 mod types;
 
@@ -31,6 +34,10 @@ pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
     Exec = 2,
     Exec4 = 3,
+    // Method numbers derived from FRC-0042 standards
+    PreviewAddressExported = frc42_dispatch::method_hash!("PreviewAddress"),
+    ExecPaymentChannelExported = frc42_dispatch::method_hash!("ExecPaymentChannel"),
+    ResolveAddressExported = frc42_dispatch::method_hash!("ResolveAddress"),
 }
 
 /// Init actor
@@ -117,6 +124,38 @@ impl Actor {
                 ActorError::illegal_argument(format!("invalid delegated address: {}", e))
             })?;
 
+        Self::exec_delegated(rt, params.code_cid, delegated_address, params.constructor_params)
+    }
+
+    /// Creates a payment channel at the f4 address derived from its parties and a nonce,
+    /// as by `payment_channel_address`, so the payee can address it before it exists.
+    /// The address may already hold a placeholder, e.g. if funds were sent to it.
+    pub fn exec_payment_channel(
+        rt: &impl Runtime,
+        params: ExecPaymentChannelParams,
+    ) -> Result<Exec4Return, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let delegated_address =
+            payment_channel_address(rt, &params.from, &params.to, params.nonce)?;
+        let constructor_params = serialize(
+            &PaymentChannelConstructorParams { from: params.from, to: params.to },
+            "payment channel constructor params",
+        )?;
+        Self::exec_delegated(
+            rt,
+            rt.get_code_cid_for_type(Type::PaymentChannel),
+            delegated_address,
+            constructor_params,
+        )
+    }
+
+    /// Creates an actor with an f4 address, or deploys it over a placeholder at that address.
+    fn exec_delegated(
+        rt: &impl Runtime,
+        code_cid: Cid,
+        delegated_address: Address,
+        constructor_params: RawBytes,
+    ) -> Result<Exec4Return, ActorError> {
         log::trace!("delegated address: {:?}", &delegated_address);
 
         // Compute a re-org-stable address.
@@ -137,31 +176,73 @@ impl Actor {
         // If the f4 address was already assigned, make sure we're deploying over a placeholder and not
         // some other existing actor (and make sure the target actor wasn't deleted either).
         if existing {
-            let code_cid = rt
+            let existing_code = rt
                 .get_actor_code_cid(&id_address)
                 .context_code(ExitCode::USR_FORBIDDEN, "cannot redeploy a deleted actor")?;
             let placeholder_cid = rt.get_code_cid_for_type(Type::Placeholder);
-            if code_cid != placeholder_cid {
+            if existing_code != placeholder_cid {
                 return Err(ActorError::forbidden(format!(
-                    "cannot replace an existing non-placeholder actor with code: {code_cid}"
+                    "cannot replace an existing non-placeholder actor with code: {existing_code}"
                 )));
             }
         }
 
         // Create an empty actor
-        rt.create_actor(params.code_cid, id_address, Some(delegated_address))?;
+        rt.create_actor(code_cid, id_address, Some(delegated_address))?;
 
         // Invoke constructor
         extract_send_result(rt.send_simple(
             &Address::new_id(id_address),
             METHOD_CONSTRUCTOR,
-            params.constructor_params.into(),
+            constructor_params.into(),
             rt.message().value_received(),
         ))
         .context("constructor failed")?;
 
         Ok(Exec4Return { id_address: Address::new_id(id_address), robust_address })
     }
+
+    /// Returns the addresses an Exec or Exec4 request would assign if it were the next actor
+    /// created, without creating it.
+    /// The ID is only stable until another actor is created. The robust address depends on the
+    /// message that eventually creates the actor, so cannot be previewed.
+    pub fn preview_address(
+        rt: &impl Runtime,
+        params: PreviewAddressParams,
+    ) -> Result<PreviewAddressReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+
+        let delegated_address = params
+            .subaddress
+            .map(|subaddress| {
+                Address::new_delegated(EAM_ACTOR_ID, &subaddress).map_err(|e| {
+                    ActorError::illegal_argument(format!("invalid delegated address: {}", e))
+                })
+            })
+            .transpose()?;
+
+        let existing = match &delegated_address {
+            Some(addr) => st.resolve_address(rt.store(), addr)?,
+            None => None,
+        };
+        Ok(PreviewAddressReturn {
+            id_address: existing.unwrap_or(Address::new_id(st.next_id)),
+            delegated_address,
+            existing: existing.is_some(),
+        })
+    }
+
+    /// Returns the ID address to which a robust or delegated address is mapped, without
+    /// creating an actor for an unmapped address. An ID address resolves to itself.
+    pub fn resolve_address(
+        rt: &impl Runtime,
+        params: ResolveAddressParams,
+    ) -> Result<ResolveAddressReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        Ok(ResolveAddressReturn { id_address: st.resolve_address(rt.store(), &params.address)? })
+    }
 }
 
 impl ActorCode for Actor {
@@ -175,6 +256,9 @@ impl ActorCode for Actor {
         Constructor => constructor,
         Exec => exec,
         Exec4 => exec4,
+        PreviewAddressExported => preview_address,
+        ExecPaymentChannelExported => exec_payment_channel,
+        ResolveAddressExported => resolve_address,
     }
 }
 
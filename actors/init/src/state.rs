@@ -8,7 +8,7 @@ use fvm_shared::address::{Address, Protocol};
 use fvm_shared::ActorID;
 
 use fil_actors_runtime::{
-    actor_error, ActorError, Map2, DEFAULT_HAMT_CONFIG, FIRST_NON_SINGLETON_ADDR,
+    actor_error, take_id, ActorError, Map2, DEFAULT_HAMT_CONFIG, FIRST_NON_SINGLETON_ADDR,
 };
 
 #[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug)]
@@ -46,16 +46,13 @@ impl State {
             if let Some(existing_id) = map.get(delegated_addr)? {
                 (*existing_id, true)
             } else {
-                let new_id = self.next_id;
-                self.next_id += 1;
+                let new_id = take_id(&mut self.next_id, "actor")?;
                 map.set(delegated_addr, new_id)?;
                 (new_id, false)
             }
         } else {
             // With no delegated address, always create a new actor ID.
-            let new_id = self.next_id;
-            self.next_id += 1;
-            (new_id, false)
+            (take_id(&mut self.next_id, "actor")?, false)
         };
 
         // Map the robust address to the ID, failing if it's already mapped to anything.
@@ -6,7 +6,9 @@ use fvm_shared::{
     ActorID,
 };
 
-use fil_actors_runtime::{MessageAccumulator, DEFAULT_HAMT_CONFIG, FIRST_NON_SINGLETON_ADDR};
+use fil_actors_runtime::{
+    MessageAccumulator, DEFAULT_HAMT_CONFIG, FIRST_NON_SINGLETON_ADDR, ID_OVERFLOW_RISK_THRESHOLD,
+};
 
 use crate::state::AddressMap;
 use crate::State;
@@ -28,6 +30,13 @@ pub fn check_state_invariants<BS: Blockstore>(
         state.next_id >= FIRST_NON_SINGLETON_ADDR,
         format!("next id {} is too low", state.next_id),
     );
+    acc.require(
+        state.next_id <= ID_OVERFLOW_RISK_THRESHOLD,
+        format!(
+            "next id {} exceeds overflow risk threshold {}",
+            state.next_id, ID_OVERFLOW_RISK_THRESHOLD
+        ),
+    );
 
     let mut init_summary = StateSummary { ids_by_address: HashMap::new(), next_id: state.next_id };
 
@@ -42,6 +51,10 @@ pub fn check_state_invariants<BS: Blockstore>(
                     actor_id >= &FIRST_NON_SINGLETON_ADDR,
                     format!("unexpected singleton ID value {actor_id}"),
                 );
+                acc.require(
+                    *actor_id < state.next_id,
+                    format!("ID {actor_id} of {key} not below next id {}", state.next_id),
+                );
 
                 match key.protocol() {
                     Protocol::ID => {
@@ -38,3 +38,56 @@ pub struct Exec4Params {
 
 /// Init actor Exec4 Return value
 pub type Exec4Return = ExecReturn;
+
+/// Init actor ExecPaymentChannel Params
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ExecPaymentChannelParams {
+    /// Payer, as given to the payment channel constructor.
+    pub from: Address,
+    /// Payee, as given to the payment channel constructor.
+    pub to: Address,
+    /// Chosen by the payer to distinguish channels between the same parties.
+    pub nonce: u64,
+}
+
+/// The payment channel constructor's parameters, constructed by ExecPaymentChannel.
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub(crate) struct PaymentChannelConstructorParams {
+    pub from: Address,
+    pub to: Address,
+}
+
+/// Init actor PreviewAddress Params
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct PreviewAddressParams {
+    /// Subaddress of an Exec4 request in the EAM's namespace, or None for an Exec request.
+    pub subaddress: Option<RawBytes>,
+}
+
+/// Init actor PreviewAddress Return value
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct PreviewAddressReturn {
+    /// ID based address the actor would be assigned.
+    pub id_address: Address,
+    /// Delegated (f4) address the actor would be assigned, for an Exec4 request.
+    pub delegated_address: Option<Address>,
+    /// Whether the delegated address is already mapped to an actor, which must be a
+    /// placeholder for the Exec4 to succeed.
+    pub existing: bool,
+}
+
+/// Init actor ResolveAddress Params
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ResolveAddressParams {
+    pub address: Address,
+}
+
+/// Init actor ResolveAddress Return value
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ResolveAddressReturn {
+    /// ID address to which the address is mapped, or None if it isn't mapped.
+    pub id_address: Option<Address>,
+}
@@ -6,14 +6,16 @@ use std::cell::RefCell;
 use cid::Cid;
 use fil_actor_init::testing::check_state_invariants;
 use fil_actor_init::{
-    Actor as InitActor, ConstructorParams, Exec4Params, Exec4Return, ExecParams, ExecReturn,
-    Method, State,
+    Actor as InitActor, ConstructorParams, Exec4Params, Exec4Return, ExecParams,
+    ExecPaymentChannelParams, ExecReturn, Method, PreviewAddressParams, PreviewAddressReturn,
+    ResolveAddressParams, ResolveAddressReturn, State,
 };
 use fil_actors_runtime::runtime::Runtime;
-use fil_actors_runtime::{test_utils::*, EAM_ACTOR_ADDR, EAM_ACTOR_ID};
 use fil_actors_runtime::{
-    ActorError, Multimap, FIRST_NON_SINGLETON_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    payment_channel_address, payment_channel_nonce, ActorError, Multimap, FIRST_NON_SINGLETON_ADDR,
+    STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
 };
+use fil_actors_runtime::{test_utils::*, EAM_ACTOR_ADDR, EAM_ACTOR_ID};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
@@ -411,6 +413,152 @@ fn call_exec4_placeholder() {
     assert_eq!(expected_id_addr, resolved_id, "f4 address not assigned to the right actor");
 }
 
+#[test]
+fn resolve_address() {
+    let rt = construct_runtime();
+    construct_and_verify(&rt);
+    let robust = Address::new_actor(b"robust");
+    let f4_addr = Address::new_delegated(EAM_ACTOR_ID, b"foobar").unwrap();
+    let mut st: State = rt.get_state();
+    let (id, _) = st.map_addresses_to_id(rt.store(), &robust, Some(&f4_addr)).unwrap();
+    rt.replace_state(&st);
+
+    let resolve = |address: Address| {
+        rt.expect_validate_caller_any();
+        let ret: ResolveAddressReturn = rt
+            .call::<InitActor>(
+                Method::ResolveAddressExported as u64,
+                IpldBlock::serialize_cbor(&ResolveAddressParams { address }).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret.id_address
+    };
+
+    assert_eq!(Some(Address::new_id(id)), resolve(robust));
+    assert_eq!(Some(Address::new_id(id)), resolve(f4_addr));
+    assert_eq!(Some(Address::new_id(1234)), resolve(Address::new_id(1234)));
+
+    // An unmapped address isn't assigned an ID.
+    let next_id = rt.get_state::<State>().next_id;
+    assert_eq!(None, resolve(Address::new_actor(b"unknown")));
+    assert_eq!(next_id, rt.get_state::<State>().next_id);
+    check_state(&rt);
+}
+
+#[test]
+fn preview_address() {
+    let rt = construct_runtime();
+    construct_and_verify(&rt);
+
+    let preview = |subaddress: Option<&[u8]>| {
+        rt.expect_validate_caller_any();
+        let params = PreviewAddressParams { subaddress: subaddress.map(|s| s.to_vec().into()) };
+        let ret: PreviewAddressReturn = rt
+            .call::<InitActor>(
+                Method::PreviewAddressExported as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret
+    };
+
+    // An Exec would receive the next ID.
+    let ret = preview(None);
+    assert_eq!(Address::new_id(FIRST_NON_SINGLETON_ADDR), ret.id_address);
+    assert_eq!(None, ret.delegated_address);
+    assert!(!ret.existing);
+
+    // An Exec4 would also receive the f4 address in the EAM's namespace.
+    let subaddr = b"foobar";
+    let f4_addr = Address::new_delegated(EAM_ACTOR_ID, subaddr).unwrap();
+    let ret = preview(Some(subaddr));
+    assert_eq!(Address::new_id(FIRST_NON_SINGLETON_ADDR), ret.id_address);
+    assert_eq!(Some(f4_addr), ret.delegated_address);
+    assert!(!ret.existing);
+
+    // The preview matches the actor subsequently created.
+    rt.new_actor_addr.replace(Some(Address::new_actor(b"test")));
+    rt.expect_create_actor(*MULTISIG_ACTOR_CODE_ID, FIRST_NON_SINGLETON_ADDR, Some(f4_addr));
+    let fake_params = ConstructorParams { network_name: String::from("fake_param") };
+    rt.expect_send_simple(
+        ret.id_address,
+        METHOD_CONSTRUCTOR,
+        IpldBlock::serialize_cbor(&fake_params).unwrap(),
+        TokenAmount::zero(),
+        None,
+        ExitCode::OK,
+    );
+    let exec_ret =
+        exec4_and_verify(&rt, EAM_ACTOR_ID, subaddr, *MULTISIG_ACTOR_CODE_ID, &fake_params)
+            .unwrap();
+    assert_eq!(ret.id_address, exec_ret.id_address);
+
+    // Once mapped, the existing actor is reported.
+    let ret = preview(Some(subaddr));
+    assert_eq!(exec_ret.id_address, ret.id_address);
+    assert!(ret.existing);
+    let ret = preview(None);
+    assert_eq!(Address::new_id(FIRST_NON_SINGLETON_ADDR + 1), ret.id_address);
+    check_state(&rt);
+}
+
+#[test]
+fn exec_payment_channel_at_derived_address() {
+    let rt = construct_runtime();
+    construct_and_verify(&rt);
+    let (from, to) = (Address::new_id(101), Address::new_id(102));
+    let f4_addr = payment_channel_address(&rt, &from, &to, 7).unwrap();
+    assert_eq!(Some(7), payment_channel_nonce(&f4_addr));
+    assert_ne!(f4_addr, payment_channel_address(&rt, &from, &to, 8).unwrap());
+    assert_ne!(f4_addr, payment_channel_address(&rt, &to, &from, 7).unwrap());
+
+    let unique_address = Address::new_actor(b"test");
+    rt.new_actor_addr.replace(Some(unique_address));
+    let expected_id_addr = Address::new_id(FIRST_NON_SINGLETON_ADDR);
+    rt.expect_create_actor(*PAYCH_ACTOR_CODE_ID, FIRST_NON_SINGLETON_ADDR, Some(f4_addr));
+    // The payment channel constructor params are the (from, to) tuple.
+    let value = TokenAmount::from_atto(100);
+    rt.set_received(value.clone());
+    rt.set_balance(value.clone());
+    rt.expect_send_simple(
+        expected_id_addr,
+        METHOD_CONSTRUCTOR,
+        IpldBlock::serialize_cbor(&(from, to)).unwrap(),
+        value,
+        None,
+        ExitCode::OK,
+    );
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, from);
+    rt.expect_validate_caller_any();
+    let params = ExecPaymentChannelParams { from, to, nonce: 7 };
+    let ret: Exec4Return = rt
+        .call::<InitActor>(
+            Method::ExecPaymentChannelExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    assert_eq!(expected_id_addr, ret.id_address);
+    assert_eq!(unique_address, ret.robust_address);
+
+    let init_state: State = rt.get_state();
+    let resolved_id = init_state.resolve_address(rt.store(), &f4_addr).unwrap();
+    assert_eq!(Some(expected_id_addr), resolved_id);
+    check_state(&rt);
+}
+
 fn construct_and_verify(rt: &MockRuntime) {
     rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
     rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
@@ -103,7 +103,6 @@ where
     }
 
     /// Returns total balance held by this balance table
-    #[allow(dead_code)]
     pub fn total(&self) -> Result<TokenAmount, ActorError> {
         let mut total = TokenAmount::zero();
         self.0.for_each(|_, v: &TokenAmount| {
@@ -113,6 +112,37 @@ where
 
         Ok(total)
     }
+
+    /// Iterates over all balances in the table.
+    pub fn for_each<F>(&self, mut f: F) -> Result<(), ActorError>
+    where
+        F: FnMut(Address, &TokenAmount) -> Result<(), ActorError>,
+    {
+        self.0.for_each(|k, v| f(k, v))
+    }
+
+    /// Removes balances below a threshold, skipping any address for which `can_sweep` is false.
+    /// Returns the removed balances, which the caller must account for elsewhere.
+    pub fn sweep_below<F>(
+        &mut self,
+        threshold: &TokenAmount,
+        mut can_sweep: F,
+    ) -> Result<Vec<(Address, TokenAmount)>, ActorError>
+    where
+        F: FnMut(&Address) -> Result<bool, ActorError>,
+    {
+        let mut swept = Vec::new();
+        self.for_each(|addr, amount| {
+            if amount < threshold && can_sweep(&addr)? {
+                swept.push((addr, amount.clone()));
+            }
+            Ok(())
+        })?;
+        for (addr, _) in &swept {
+            self.0.delete(addr).context("sweeping balance")?;
+        }
+        Ok(swept)
+    }
 }
 
 #[cfg(test)]
@@ -190,4 +220,29 @@ mod tests {
         // Test subtracting more than available
         assert!(bt.must_subtract(&addr, &TokenAmount::from_atto(100u8)).is_err());
     }
+
+    #[test]
+    fn sweep_below_threshold() {
+        let (addr1, addr2, addr3) =
+            (Address::new_id(100), Address::new_id(101), Address::new_id(102));
+        let store = MemoryBlockstore::default();
+        let mut bt = BalanceTable::new(&store, "test");
+        bt.add(&addr1, &TokenAmount::from_atto(5u8)).unwrap();
+        bt.add(&addr2, &TokenAmount::from_atto(50u8)).unwrap();
+        bt.add(&addr3, &TokenAmount::from_atto(1u8)).unwrap();
+
+        let swept = bt.sweep_below(&TokenAmount::from_atto(10u8), |a| Ok(*a != addr3)).unwrap();
+        assert_eq!(vec![(addr1, TokenAmount::from_atto(5u8))], swept);
+        assert!(bt.get(&addr1).unwrap().is_zero());
+        assert_eq!(TokenAmount::from_atto(51u8), bt.total().unwrap());
+
+        let mut count = 0;
+        bt.for_each(|_, amount| {
+            assert!(amount.is_positive());
+            count += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(2, count);
+    }
 }
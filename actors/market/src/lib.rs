@@ -6,8 +6,8 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use cid::multihash::Multihash;
 use cid::Cid;
+use fil_actors_runtime::frc46::{BalanceReturn, TransferFromParams, TransferFromReturn};
 use fil_actors_runtime::reward::ThisEpochRewardReturn;
-use frc46_token::token::types::{BalanceReturn, TransferFromParams, TransferFromReturn};
 use fvm_ipld_bitfield::BitField;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
@@ -33,11 +33,14 @@ use fil_actors_runtime::cbor::{deserialize, serialize};
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Policy, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, ActorContext, ActorDowncast, ActorError,
-    AsActorError, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR,
-    REWARD_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    actor_dispatch, actor_error, actor_exit_code, deserialize_block, ActorContext, ActorDowncast,
+    ActorError, AsActorError, ValidateParams, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR,
+    DATACAP_TOKEN_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fil_actors_runtime::{
+    extract_send_result, BatchReturnGen, QuantSpec, FIRST_ACTOR_SPECIFIC_EXIT_CODE,
 };
-use fil_actors_runtime::{extract_send_result, BatchReturnGen, FIRST_ACTOR_SPECIFIC_EXIT_CODE};
 
 use crate::balance_table::BalanceTable;
 use crate::ext::verifreg::{AllocationID, AllocationRequest};
@@ -52,6 +55,7 @@ pub mod balance_table;
 #[doc(hidden)]
 pub mod ext;
 pub mod policy;
+#[cfg(feature = "testing")]
 pub mod testing;
 
 mod deal;
@@ -65,9 +69,9 @@ fil_actors_runtime::wasm_trampoline!(Actor);
 pub const NO_ALLOCATION_ID: u64 = 0;
 
 // Indicates that information about a past deal is no longer available.
-pub const EX_DEAL_EXPIRED: ExitCode = ExitCode::new(FIRST_ACTOR_SPECIFIC_EXIT_CODE);
+pub const EX_DEAL_EXPIRED: ExitCode = actor_exit_code(FIRST_ACTOR_SPECIFIC_EXIT_CODE);
 // Indicates that information about a deal's activation is not yet available.
-pub const EX_DEAL_NOT_ACTIVATED: ExitCode = ExitCode::new(FIRST_ACTOR_SPECIFIC_EXIT_CODE + 1);
+pub const EX_DEAL_NOT_ACTIVATED: ExitCode = actor_exit_code(FIRST_ACTOR_SPECIFIC_EXIT_CODE + 1);
 
 /// Market actor methods available
 #[derive(FromPrimitive)]
@@ -82,6 +86,7 @@ pub enum Method {
     OnMinerSectorsTerminate = 7,
     // ComputeDataCommitment = 8, // Deprecated
     CronTick = 9,
+    BatchComputeDataCommitment = 10,
     // Method numbers derived from FRC-0042 standards
     AddBalanceExported = frc42_dispatch::method_hash!("AddBalance"),
     WithdrawBalanceExported = frc42_dispatch::method_hash!("WithdrawBalance"),
@@ -98,7 +103,10 @@ pub enum Method {
     GetDealVerifiedExported = frc42_dispatch::method_hash!("GetDealVerified"),
     GetDealActivationExported = frc42_dispatch::method_hash!("GetDealActivation"),
     GetDealSectorExported = frc42_dispatch::method_hash!("GetDealSector"),
+    GetDealCollateralBoundsExported = frc42_dispatch::method_hash!("GetDealCollateralBounds"),
+    BatchComputeDataCommitmentExported = frc42_dispatch::method_hash!("BatchComputeDataCommitment"),
     SettleDealPaymentsExported = frc42_dispatch::method_hash!("SettleDealPayments"),
+    AmendDealExported = frc42_dispatch::method_hash!("AmendDeal"),
     SectorContentChangedExported = ext::miner::SECTOR_CONTENT_CHANGED,
 }
 
@@ -144,9 +152,7 @@ impl Actor {
         rt: &impl Runtime,
         params: WithdrawBalanceParams,
     ) -> Result<WithdrawBalanceReturn, ActorError> {
-        if params.amount < TokenAmount::zero() {
-            return Err(actor_error!(illegal_argument, "negative amount: {}", params.amount));
-        }
+        params.validate()?;
 
         let (nominal, recipient, approved) = escrow_address(rt, &params.provider_or_client)?;
         // for providers -> only corresponding owner or worker can withdraw
@@ -170,15 +176,18 @@ impl Actor {
     }
 
     /// Returns the escrow balance and locked amount for an address.
+    /// An address with no actor has no escrow, so both amounts are zero.
     fn get_balance(
         rt: &impl Runtime,
         params: GetBalanceParams,
     ) -> Result<GetBalanceReturn, ActorError> {
         rt.validate_immediate_caller_accept_any()?;
-        let account = params.account;
-        let nominal = rt.resolve_address(&account).ok_or_else(|| {
-            actor_error!(illegal_argument, "failed to resolve address {}", account)
-        })?;
+        let Some(nominal) = rt.resolve_address(&params.account) else {
+            return Ok(GetBalanceReturn {
+                balance: TokenAmount::zero(),
+                locked: TokenAmount::zero(),
+            });
+        };
         let account = Address::new_id(nominal);
 
         let store = rt.store();
@@ -197,9 +206,7 @@ impl Actor {
         params: PublishStorageDealsParams,
     ) -> Result<PublishStorageDealsReturn, ActorError> {
         rt.validate_immediate_caller_accept_any()?;
-        if params.deals.is_empty() {
-            return Err(actor_error!(illegal_argument, "Empty deals parameter"));
-        }
+        params.validate()?;
 
         // All deals should have the same provider so get worker once
         let provider_raw = params.deals[0].proposal.provider;
@@ -218,24 +225,7 @@ impl Actor {
             ));
         }
 
-        let caller = rt.message().caller();
-        let caller_status: ext::miner::IsControllingAddressReturn =
-            deserialize_block(extract_send_result(rt.send_simple(
-                &Address::new_id(provider_id),
-                ext::miner::IS_CONTROLLING_ADDRESS_EXPORTED,
-                IpldBlock::serialize_cbor(&ext::miner::IsControllingAddressParam {
-                    address: caller,
-                })?,
-                TokenAmount::zero(),
-            ))?)?;
-        if !caller_status.is_controlling {
-            return Err(actor_error!(
-                forbidden,
-                "caller {} is not worker or control address of provider {}",
-                caller,
-                provider_id
-            ));
-        }
+        validate_caller_controls_provider(rt, provider_id)?;
         // Deals that passed `AuthenticateMessage` and other state-less checks.
         let mut validity_index: Vec<bool> = Vec::with_capacity(params.deals.len());
 
@@ -277,6 +267,8 @@ impl Actor {
         let curr_epoch = rt.curr_epoch();
 
         let state: State = rt.state()?;
+        // Batches commonly hold many deals from the same client.
+        let resolver = rt.address_resolver();
 
         for (di, mut deal) in params.deals.into_iter().enumerate() {
             if !*validity_index.get(di).context_code(
@@ -295,7 +287,7 @@ impl Actor {
                 );
                 continue;
             }
-            let client_id = match rt.resolve_address(&deal.proposal.client) {
+            let client_id = match resolver.resolve(&deal.proposal.client) {
                 Some(client) => client,
                 _ => {
                     info!(
@@ -346,7 +338,9 @@ impl Actor {
 
             // check proposalCids for duplication within message batch
             // check state PendingProposals for duplication across messages
-            let duplicate_in_state = state.has_pending_deal(rt.store(), &pcid)?;
+            // check state SupersededProposals for the original terms of an amended deal
+            let duplicate_in_state = state.has_pending_deal(rt.store(), &pcid)?
+                || state.is_superseded_proposal(rt.store(), deal.proposal.start_epoch, &pcid)?;
 
             let duplicate_in_message = proposal_cid_lookup.contains(&pcid);
             if duplicate_in_state || duplicate_in_message {
@@ -439,7 +433,7 @@ impl Actor {
                 pending_deals.push(valid_deal.cid);
 
                 // Allocate a deal ID and store the proposal in the proposals AMT.
-                let deal_id = st.generate_storage_deal_id();
+                let deal_id = st.generate_storage_deal_id()?;
                 deal_proposals.push((deal_id, valid_deal.proposal.clone()));
 
                 // Store verified allocation (if any) in the pending allocation IDs map.
@@ -497,6 +491,85 @@ impl Actor {
         Ok(PublishStorageDealsReturn { ids: new_deal_ids, valid_deals: valid_input_bf })
     }
 
+    /// Amends the start epoch and price of a published deal that hasn't been activated.
+    /// The provider authorizes the amendment by sending it from its worker or a control address,
+    /// and the client by signing the amended proposal.
+    /// The client's locked storage fee is adjusted to the amended terms.
+    fn amend_deal(rt: &impl Runtime, params: AmendDealParams) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let deal_id = params.deal_id;
+        let st: State = rt.state()?;
+        let proposal = st
+            .find_proposal(rt.store(), deal_id)?
+            .ok_or_else(|| actor_error!(not_found, "no such deal {}", deal_id))?;
+        if st.find_deal_state(rt.store(), deal_id)?.is_some() {
+            return Err(actor_error!(forbidden, "deal {} has been activated", deal_id));
+        }
+        // The verified allocation's terms are bound to the original start epoch.
+        if proposal.verified_deal {
+            return Err(actor_error!(forbidden, "verified deal {} cannot be amended", deal_id));
+        }
+        if rt.curr_epoch() > proposal.start_epoch {
+            return Err(ActorError::unchecked(
+                EX_DEAL_EXPIRED,
+                format!("deal {} start epoch {} has elapsed", deal_id, proposal.start_epoch),
+            ));
+        }
+
+        let provider_id = proposal.provider.id().unwrap();
+        validate_caller_controls_provider(rt, provider_id)?;
+
+        let mut amended = proposal.clone();
+        amended.start_epoch = params.start_epoch;
+        amended.end_epoch = params
+            .start_epoch
+            .checked_add(proposal.duration())
+            .ok_or_else(|| actor_error!(illegal_argument, "amended deal end epoch overflows"))?;
+        amended.storage_price_per_epoch = params.storage_price_per_epoch;
+        if amended == proposal {
+            return Err(actor_error!(
+                illegal_argument,
+                "amendment doesn't change deal {}",
+                deal_id
+            ));
+        }
+
+        let baseline_power = request_current_baseline_power(rt)?;
+        let (network_raw_power, _) = request_current_network_power(rt)?;
+        let amended =
+            ClientDealProposal { proposal: amended, client_signature: params.client_signature };
+        validate_deal(rt, &amended, &network_raw_power, &baseline_power)?;
+        let amended = amended.proposal;
+
+        let old_cid = deal_cid(rt, &proposal)?;
+        let new_cid = deal_cid(rt, &amended)?;
+        rt.transaction(|st: &mut State, rt| {
+            if st.has_pending_deal(rt.store(), &new_cid)? {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "amended proposal {} duplicates a pending deal",
+                    new_cid
+                ));
+            }
+            st.remove_pending_deal(rt.store(), old_cid)?.context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                format!("no pending proposal for deal {}", deal_id),
+            )?;
+            st.put_pending_deals(rt.store(), &[new_cid])?;
+            st.put_superseded_proposal(rt.store(), proposal.start_epoch, old_cid)?;
+            st.relock_client_storage_fee(rt.store(), &proposal, &amended)?;
+            let interval = rt.policy().deal_updates_interval;
+            st.reschedule_deal_op(
+                rt.store(),
+                deal_id,
+                next_update_epoch(deal_id, interval, proposal.start_epoch),
+                next_update_epoch(deal_id, interval, amended.start_epoch),
+            )?;
+            st.put_deal_proposals(rt.store(), &[(deal_id, amended)])?;
+            Ok(())
+        })
+    }
+
     /// Verify that a given set of storage deals is valid for a sector currently being PreCommitted
     /// and return UnsealedCID for the set of deals.
     fn verify_deals_for_activation(
@@ -539,6 +612,29 @@ impl Actor {
         Ok(VerifyDealsForActivationReturn { unsealed_cids })
     }
 
+    /// Computes the unsealed CID for each of a batch of sectors from the pieces of its deals,
+    /// in the order given. A sector with no deals has the unsealed CID of an empty sector.
+    /// The deals are not validated for activation, and no state is modified.
+    fn batch_compute_data_commitment(
+        rt: &impl Runtime,
+        params: ComputeDataCommitmentParams,
+    ) -> Result<ComputeDataCommitmentReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        let st: State = rt.state()?;
+        let proposal_array = st.load_proposals(rt.store())?;
+
+        let mut commds = Vec::with_capacity(params.inputs.len());
+        for (i, input) in params.inputs.iter().enumerate() {
+            let sector_proposals = get_proposals(&proposal_array, &input.deal_ids, st.next_id)
+                .with_context(|| format!("failed to load deals for input {}", i))?;
+            let proposals_iter = sector_proposals.iter().map(|(_, p)| p);
+            commds.push(compute_data_commitment(rt, proposals_iter, input.sector_type)?);
+        }
+
+        Ok(ComputeDataCommitmentReturn { commds })
+    }
+
     /// Activate a set of deals grouped by sector, returning the size and
     /// extra info about verified deals.
     /// Sectors' deals are activated in parameter-defined order.
@@ -862,7 +958,7 @@ impl Actor {
                 }
 
                 state.slash_epoch = params.epoch;
-                total_slashed += st.process_slashed_deal(rt.store(), &deal, &state)?;
+                total_slashed += st.process_slashed_deal(rt.policy(), rt.store(), &deal, &state)?;
                 st.remove_completed_deal(rt.store(), id)?;
 
                 emit::deal_terminated(
@@ -901,6 +997,7 @@ impl Actor {
             let mut epochs_completed: Vec<ChainEpoch> = vec![];
 
             for i in (last_cron + 1)..=rt.curr_epoch() {
+                st.remove_superseded_proposals(rt.store(), i)?;
                 let deal_ids = st.get_deals_for_epoch(rt.store(), i)?;
 
                 for deal_id in deal_ids {
@@ -954,6 +1051,7 @@ impl Actor {
                     // reschedule them. eventually, all legacy deals will expire and the below code can be removed.
                     let (slash_amount, _payment_amount, completed, remove_deal) = st
                         .process_deal_update(
+                            rt.policy(),
                             rt.store(),
                             &state,
                             &deal_proposal,
@@ -1217,6 +1315,37 @@ impl Actor {
         }
     }
 
+    /// Returns the bounds that publishing would check the collateral of a deal against at the
+    /// current epoch, given its piece size and duration.
+    fn get_deal_collateral_bounds(
+        rt: &impl Runtime,
+        params: GetDealCollateralBoundsParams,
+    ) -> Result<GetDealCollateralBoundsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params
+            .piece_size
+            .validate()
+            .map_err(|e| actor_error!(illegal_argument, "piece size is invalid: {}", e))?;
+
+        let baseline_power = request_current_baseline_power(rt)?;
+        let (network_raw_power, _) = request_current_network_power(rt)?;
+        let (min_provider_collateral, max_provider_collateral) = deal_provider_collateral_bounds(
+            rt.policy(),
+            params.piece_size,
+            &network_raw_power,
+            &baseline_power,
+            &rt.total_fil_circ_supply(),
+        );
+        let (min_client_collateral, max_client_collateral) =
+            deal_client_collateral_bounds(params.piece_size, params.duration);
+        Ok(GetDealCollateralBoundsReturn {
+            min_provider_collateral,
+            max_provider_collateral,
+            min_client_collateral,
+            max_client_collateral,
+        })
+    }
+
     fn settle_deal_payments(
         rt: &impl Runtime,
         params: SettleDealPaymentsParams,
@@ -1293,6 +1422,7 @@ impl Actor {
                 }
 
                 let (_, payment_amount, completed, remove_deal) = match st.process_deal_update(
+                    rt.policy(),
                     rt.store(),
                     &deal_state,
                     &deal_proposal,
@@ -1442,8 +1572,8 @@ pub fn validate_deals_for_sector(
 fn preactivate_deal<BS: Blockstore>(
     rt: &impl Runtime,
     deal_id: DealID,
-    proposals: &DealArray<BS>,
-    states: &DealMetaArray<BS>,
+    proposals: &DealArray<&BS>,
+    states: &DealMetaArray<&BS>,
     pending_proposals: &PendingProposalsSet<&BS>,
     provider: &Address,
     sector_commitment: ChainEpoch,
@@ -1561,19 +1691,7 @@ fn balance_of(rt: &impl Runtime, owner: &Address) -> Result<TokenAmount, ActorEr
 // Calculates the first update epoch for a deal ID that is no sooner than `earliest`.
 // An ID is processed as a fixed offset within each `interval` of epochs.
 pub fn next_update_epoch(id: DealID, interval: i64, earliest: ChainEpoch) -> ChainEpoch {
-    // Same logic as QuantSpec from the miner actor, but duplicated here to avoid unnecessary
-    // dependencies.
-    let offset = id as i64 % interval;
-    let remainder = (earliest - offset) % interval;
-    let quotient = (earliest - offset) / interval;
-
-    // Don't round if epoch falls on a quantization epoch or when negative (negative truncating
-    // division rounds up).
-    if remainder == 0 || earliest - offset < 0 {
-        interval * quotient + offset
-    } else {
-        interval * (quotient + 1) + offset
-    }
+    QuantSpec { unit: interval, offset: id as i64 % interval }.quantize_up(earliest)
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -1730,6 +1848,30 @@ pub(crate) fn serialized_deal_cid(rt: &impl Runtime, data: &[u8]) -> Result<Cid,
     Ok(Cid::new_v1(DAG_CBOR, hash))
 }
 
+/// Checks that the immediate caller is the worker or a control address of a provider.
+fn validate_caller_controls_provider(
+    rt: &impl Runtime,
+    provider_id: ActorID,
+) -> Result<(), ActorError> {
+    let caller = rt.message().caller();
+    let caller_status: ext::miner::IsControllingAddressReturn =
+        deserialize_block(extract_send_result(rt.send_simple(
+            &Address::new_id(provider_id),
+            ext::miner::IS_CONTROLLING_ADDRESS_EXPORTED,
+            IpldBlock::serialize_cbor(&ext::miner::IsControllingAddressParam { address: caller })?,
+            TokenAmount::zero(),
+        ))?)?;
+    if !caller_status.is_controlling {
+        return Err(actor_error!(
+            forbidden,
+            "caller {} is not worker or control address of provider {}",
+            caller,
+            provider_id
+        ));
+    }
+    Ok(())
+}
+
 fn request_miner_control_addrs(
     rt: &impl Runtime,
     miner_id: ActorID,
@@ -1823,6 +1965,7 @@ impl ActorCode for Actor {
         BatchActivateDeals => batch_activate_deals,
         OnMinerSectorsTerminate => on_miner_sectors_terminate,
         CronTick => cron_tick,
+        BatchComputeDataCommitment|BatchComputeDataCommitmentExported => batch_compute_data_commitment,
         GetBalanceExported => get_balance,
         GetDealDataCommitmentExported => get_deal_data_commitment,
         GetDealClientExported => get_deal_client,
@@ -1835,7 +1978,9 @@ impl ActorCode for Actor {
         GetDealVerifiedExported => get_deal_verified,
         GetDealActivationExported => get_deal_activation,
         GetDealSectorExported => get_deal_sector,
+        GetDealCollateralBoundsExported => get_deal_collateral_bounds,
         SettleDealPaymentsExported => settle_deal_payments,
+        AmendDealExported => amend_deal,
         SectorContentChangedExported => sector_content_changed,
     }
 }
@@ -1,7 +1,7 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
-use std::cmp::max;
+use std::cmp::{max, min};
 
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::runtime::Policy;
@@ -36,6 +36,8 @@ pub(super) fn deal_price_per_epoch_bounds(
     (TokenAmount::zero(), &TOTAL_FILECOIN)
 }
 
+/// Bounds (inclusive) on the provider collateral of a deal, given the network's power and
+/// circulating supply.
 pub fn deal_provider_collateral_bounds(
     policy: &Policy,
     size: PaddedPieceSize,
@@ -56,13 +58,35 @@ pub fn deal_provider_collateral_bounds(
     (TokenAmount::from_atto(num.div_floor(&denom)), TOTAL_FILECOIN.clone())
 }
 
-pub(super) fn deal_client_collateral_bounds(
+/// Bounds (inclusive) on the client collateral of a deal.
+pub fn deal_client_collateral_bounds(
     _: PaddedPieceSize,
     _: ChainEpoch,
 ) -> (TokenAmount, TokenAmount) {
     (TokenAmount::zero(), TOTAL_FILECOIN.clone()) // PARAM_FINISH
 }
 
+/// Penalty to provider deal collateral when a deal is terminated at an epoch before its end.
+/// The collateral is slashed in proportion to the part of the deal's term remaining at
+/// termination, but no less than the policy's floor fraction of it.
+pub fn deal_termination_penalty(
+    policy: &Policy,
+    provider_collateral: &TokenAmount,
+    start_epoch: ChainEpoch,
+    end_epoch: ChainEpoch,
+    termination_epoch: ChainEpoch,
+) -> TokenAmount {
+    let duration = end_epoch - start_epoch;
+    if duration <= 0 {
+        return provider_collateral.clone();
+    }
+    let remaining = (end_epoch - max(termination_epoch, start_epoch)).clamp(0, duration);
+    let prorated = (provider_collateral * remaining).div_floor(duration);
+    let floor = (provider_collateral * policy.deal_termination_penalty_floor_num)
+        .div_floor(policy.deal_termination_penalty_floor_denom);
+    min(max(prorated, floor), provider_collateral.clone())
+}
+
 /// Penalty to provider deal collateral if the deadline expires before sector commitment.
 pub(super) fn collateral_penalty_for_deal_activation_missed(
     provider_collateral: TokenAmount,
@@ -6,7 +6,6 @@ use std::collections::BTreeMap;
 
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
-use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
 use fvm_shared::clock::{ChainEpoch, EPOCH_UNDEFINED};
 use fvm_shared::deal::DealID;
@@ -16,9 +15,10 @@ use fvm_shared::sector::SectorNumber;
 use fvm_shared::{ActorID, HAMT_BIT_WIDTH};
 use num_traits::Zero;
 
+use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::{
-    actor_error, ActorContext, ActorError, Array, AsActorError, Config, Map2, Set, SetMultimap,
-    SetMultimapConfig, DEFAULT_HAMT_CONFIG,
+    actor_error, take_id, tuple_with_optional_tail, ActorContext, ActorError, AsActorError, Config,
+    Map2, Set, SetMultimap, SetMultimapConfig, DEFAULT_HAMT_CONFIG,
 };
 
 use crate::balance_table::BalanceTable;
@@ -35,7 +35,7 @@ pub enum Reason {
 }
 
 /// Market actor state
-#[derive(Clone, Default, Serialize_tuple, Deserialize_tuple, Debug)]
+#[derive(Clone, Default, Debug)]
 pub struct State {
     /// Proposals are deals that have been proposed and not yet cleaned up after expiry or termination.
     /// Array<DealID, DealProposal>
@@ -86,8 +86,31 @@ pub struct State {
     /// of multiple sectors all belonging to the same provider.
     /// HAMT[ActorID]HAMT[SectorNumber][]DealID
     pub provider_sectors: Cid,
+
+    /// Proposals replaced by an amendment, indexed by their start epoch.
+    /// An original proposal still carries a valid client signature, so it's blocked from being
+    /// published again until its start epoch has passed.
+    /// SetMultimap<ChainEpoch, CID>
+    pub superseded_proposals: Option<Cid>,
 }
 
+tuple_with_optional_tail!(State {
+    proposals,
+    states,
+    pending_proposals,
+    escrow_table,
+    locked_table,
+    next_id,
+    deal_ops_by_epoch,
+    last_cron,
+    total_client_locked_collateral,
+    total_provider_locked_collateral,
+    total_client_storage_fee,
+    pending_deal_allocation_ids,
+    provider_sectors;
+    superseded_proposals
+});
+
 pub type PendingProposalsSet<BS> = Set<BS, Cid>;
 pub const PENDING_PROPOSALS_CONFIG: Config = DEFAULT_HAMT_CONFIG;
 
@@ -95,6 +118,10 @@ pub type DealOpsByEpoch<BS> = SetMultimap<BS, ChainEpoch, DealID>;
 pub const DEAL_OPS_BY_EPOCH_CONFIG: SetMultimapConfig =
     SetMultimapConfig { outer: DEFAULT_HAMT_CONFIG, inner: DEFAULT_HAMT_CONFIG };
 
+pub type SupersededProposals<BS> = SetMultimap<BS, ChainEpoch, Cid>;
+pub const SUPERSEDED_PROPOSALS_CONFIG: SetMultimapConfig =
+    SetMultimapConfig { outer: DEFAULT_HAMT_CONFIG, inner: DEFAULT_HAMT_CONFIG };
+
 pub type PendingDealAllocationsMap<BS> = Map2<BS, DealID, AllocationID>;
 pub const PENDING_ALLOCATIONS_CONFIG: Config =
     Config { bit_width: HAMT_BIT_WIDTH, ..DEFAULT_HAMT_CONFIG };
@@ -108,17 +135,8 @@ pub const SECTOR_DEALS_CONFIG: Config = Config { bit_width: HAMT_BIT_WIDTH, ..DE
 
 impl State {
     pub fn new<BS: Blockstore>(store: &BS) -> Result<Self, ActorError> {
-        let empty_proposals_array =
-            Array::<(), BS>::new_with_bit_width(store, PROPOSALS_AMT_BITWIDTH)
-                .flush()
-                .context_code(
-                    ExitCode::USR_ILLEGAL_STATE,
-                    "failed to create empty proposals array",
-                )?;
-
-        let empty_states_array = Array::<(), BS>::new_with_bit_width(store, STATES_AMT_BITWIDTH)
-            .flush()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to create empty states array")?;
+        let empty_proposals_array = DealArray::flush_empty(store, PROPOSALS_AMT_BITWIDTH)?;
+        let empty_states_array = DealMetaArray::flush_empty(store, STATES_AMT_BITWIDTH)?;
 
         let empty_pending_proposals =
             PendingProposalsSet::empty(store, PENDING_PROPOSALS_CONFIG, "pending proposals")
@@ -152,6 +170,7 @@ impl State {
             total_client_storage_fee: TokenAmount::default(),
             pending_deal_allocation_ids: empty_pending_deal_allocation_map,
             provider_sectors: empty_sector_deals_hamt,
+            superseded_proposals: None,
         })
     }
 
@@ -164,21 +183,18 @@ impl State {
     pub fn load_deal_states<'bs, BS>(
         &self,
         store: &'bs BS,
-    ) -> Result<DealMetaArray<'bs, BS>, ActorError>
+    ) -> Result<DealMetaArray<&'bs BS>, ActorError>
     where
         BS: Blockstore,
     {
-        DealMetaArray::load(&self.states, store)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load deal state array")
+        DealMetaArray::load(store, &self.states, "deal states")
     }
 
     fn save_deal_states<BS>(&mut self, states: &mut DealMetaArray<BS>) -> Result<(), ActorError>
     where
         BS: Blockstore,
     {
-        self.states = states
-            .flush()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush deal states")?;
+        self.states = states.flush()?;
         Ok(())
     }
 
@@ -203,12 +219,7 @@ impl State {
         BS: Blockstore,
     {
         let mut states = self.load_deal_states(store)?;
-        new_deal_states.iter().try_for_each(|(id, deal_state)| -> Result<(), ActorError> {
-            states
-                .set(*id, *deal_state)
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to set deal state")?;
-            Ok(())
-        })?;
+        states.batch_set(new_deal_states.iter().copied())?;
         self.save_deal_states(&mut states)
     }
 
@@ -221,19 +232,16 @@ impl State {
         BS: Blockstore,
     {
         let mut states = self.load_deal_states(store)?;
-        let removed = states
-            .delete(deal_id)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to delete deal state")?;
+        let removed = states.delete(deal_id)?;
         self.save_deal_states(&mut states)?;
         Ok(removed)
     }
 
-    pub fn load_proposals<'bs, BS>(&self, store: &'bs BS) -> Result<DealArray<'bs, BS>, ActorError>
+    pub fn load_proposals<'bs, BS>(&self, store: &'bs BS) -> Result<DealArray<&'bs BS>, ActorError>
     where
         BS: Blockstore,
     {
-        DealArray::load(&self.proposals, store)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load deal proposal array")
+        DealArray::load(store, &self.proposals, "deal proposals")
     }
 
     pub fn get_proposal<BS: Blockstore>(
@@ -263,18 +271,9 @@ impl State {
     where
         BS: Blockstore,
     {
-        let mut deal_proposals = DealArray::load(&self.proposals, store)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load deal proposal array")?;
-
-        let proposal = deal_proposals
-            .delete(deal_id)
-            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-                format!("no such deal proposal {}", deal_id)
-            })?;
-
-        self.proposals = deal_proposals
-            .flush()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush deal proposals")?;
+        let mut deal_proposals = self.load_proposals(store)?;
+        let proposal = deal_proposals.delete(deal_id)?;
+        self.proposals = deal_proposals.flush()?;
 
         Ok(proposal)
     }
@@ -287,19 +286,9 @@ impl State {
     where
         BS: Blockstore,
     {
-        let mut deal_proposals = DealArray::load(&self.proposals, store)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load deal proposal array")?;
-
-        new_deal_proposals.iter().try_for_each(|(id, proposal)| -> Result<(), ActorError> {
-            deal_proposals
-                .set(*id, proposal.clone())
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to set deal proposal")?;
-            Ok(())
-        })?;
-
-        self.proposals = deal_proposals
-            .flush()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush deal proposals")?;
+        let mut deal_proposals = self.load_proposals(store)?;
+        deal_proposals.batch_set(new_deal_proposals.iter().cloned())?;
+        self.proposals = deal_proposals.flush()?;
 
         Ok(())
     }
@@ -467,6 +456,24 @@ impl State {
         Ok(())
     }
 
+    /// Moves a deal's next scheduled update from one epoch to another.
+    pub fn reschedule_deal_op<BS>(
+        &mut self,
+        store: &BS,
+        deal_id: DealID,
+        from: ChainEpoch,
+        to: ChainEpoch,
+    ) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        let mut deals_by_epoch = self.load_deal_ops(store)?;
+        deals_by_epoch.remove(&from, deal_id)?;
+        deals_by_epoch.put(&to, deal_id)?;
+        self.deal_ops_by_epoch = deals_by_epoch.flush()?;
+        Ok(())
+    }
+
     pub fn add_balance_to_escrow_table<BS>(
         &mut self,
         store: &BS,
@@ -501,6 +508,27 @@ impl State {
         Ok(ex)
     }
 
+    /// Removes escrow balances below a threshold, for addresses with no locked funds.
+    /// Returns the removed balances. The caller is responsible for disposing of the funds,
+    /// so that the escrow total remains reconciled with the actor's balance.
+    pub fn sweep_escrow_dust<BS>(
+        &mut self,
+        store: &BS,
+        threshold: &TokenAmount,
+    ) -> Result<Vec<(Address, TokenAmount)>, ActorError>
+    where
+        BS: Blockstore,
+    {
+        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
+        let locked_table = BalanceTable::from_root(store, &self.locked_table, "locked table")?;
+
+        let swept =
+            escrow_table.sweep_below(threshold, |addr| Ok(locked_table.get(addr)?.is_zero()))?;
+
+        self.escrow_table = escrow_table.root()?;
+        Ok(swept)
+    }
+
     pub fn load_pending_deals<BS>(&self, store: BS) -> Result<PendingProposalsSet<BS>, ActorError>
     where
         BS: Blockstore,
@@ -564,6 +592,87 @@ impl State {
         Ok(removed)
     }
 
+    /// Loads the proposals superseded by amendments, if any deal has been amended.
+    pub fn load_superseded_proposals<BS>(
+        &self,
+        store: BS,
+    ) -> Result<Option<SupersededProposals<BS>>, ActorError>
+    where
+        BS: Blockstore,
+    {
+        self.superseded_proposals
+            .as_ref()
+            .map(|root| {
+                SupersededProposals::load(
+                    store,
+                    root,
+                    SUPERSEDED_PROPOSALS_CONFIG,
+                    "superseded proposals",
+                )
+            })
+            .transpose()
+    }
+
+    /// Records that a proposal starting at an epoch has been replaced by an amendment.
+    pub fn put_superseded_proposal<BS>(
+        &mut self,
+        store: &BS,
+        start_epoch: ChainEpoch,
+        proposal_cid: Cid,
+    ) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        let mut superseded = match self.load_superseded_proposals(store)? {
+            Some(superseded) => superseded,
+            None => SupersededProposals::empty(
+                store,
+                SUPERSEDED_PROPOSALS_CONFIG,
+                "superseded proposals",
+            ),
+        };
+        superseded.put(&start_epoch, proposal_cid)?;
+        self.superseded_proposals = Some(superseded.flush()?);
+        Ok(())
+    }
+
+    pub fn is_superseded_proposal<BS>(
+        &self,
+        store: &BS,
+        start_epoch: ChainEpoch,
+        proposal_cid: &Cid,
+    ) -> Result<bool, ActorError>
+    where
+        BS: Blockstore,
+    {
+        match self.load_superseded_proposals(store)? {
+            Some(superseded) => match superseded.get(&start_epoch)? {
+                Some(proposals) => proposals.has(proposal_cid),
+                None => Ok(false),
+            },
+            None => Ok(false),
+        }
+    }
+
+    /// Forgets the proposals superseded at an epoch, which can no longer be published once it has
+    /// been reached.
+    pub fn remove_superseded_proposals<BS>(
+        &mut self,
+        store: &BS,
+        start_epoch: ChainEpoch,
+    ) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        if let Some(mut superseded) = self.load_superseded_proposals(store)? {
+            if superseded.get(&start_epoch)?.is_some() {
+                superseded.remove_all(&start_epoch)?;
+                self.superseded_proposals = Some(superseded.flush()?);
+            }
+        }
+        Ok(())
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
     // Provider sector/deal operations
     ////////////////////////////////////////////////////////////////////////////////
@@ -804,6 +913,7 @@ impl State {
     // drop slash_amount, bool return value indicates a completed deal
     pub fn process_deal_update<BS>(
         &mut self,
+        policy: &Policy,
         store: &BS,
         state: &DealState,
         deal: &DealProposal,
@@ -903,10 +1013,23 @@ impl State {
             )
             .context("unlocking client collateral")?;
 
-            // slash provider collateral
-            let slashed = deal.provider_collateral.clone();
+            // slash provider collateral, unlocking the rest
+            let slashed = deal_termination_penalty(
+                policy,
+                &deal.provider_collateral,
+                deal.start_epoch,
+                deal.end_epoch,
+                state.slash_epoch,
+            );
             self.slash_balance(store, &deal.provider, &slashed, Reason::ProviderCollateral)
                 .context("slashing balance")?;
+            self.unlock_balance(
+                store,
+                &deal.provider,
+                &(&deal.provider_collateral - &slashed),
+                Reason::ProviderCollateral,
+            )
+            .context("unlocking provider collateral")?;
 
             return Ok((slashed, payment_remaining + elapsed_payment, false, true));
         }
@@ -921,6 +1044,7 @@ impl State {
 
     pub fn process_slashed_deal<BS>(
         &mut self,
+        policy: &Policy,
         store: &BS,
         proposal: &DealProposal,
         state: &DealState,
@@ -953,10 +1077,23 @@ impl State {
         )
         .context("unlocking client collateral")?;
 
-        // slash provider collateral
-        let slashed = proposal.provider_collateral.clone();
+        // slash provider collateral, unlocking the rest
+        let slashed = deal_termination_penalty(
+            policy,
+            &proposal.provider_collateral,
+            proposal.start_epoch,
+            proposal.end_epoch,
+            state.slash_epoch,
+        );
         self.slash_balance(store, &proposal.provider, &slashed, Reason::ProviderCollateral)
             .context("slashing balance")?;
+        self.unlock_balance(
+            store,
+            &proposal.provider,
+            &(&proposal.provider_collateral - &slashed),
+            Reason::ProviderCollateral,
+        )
+        .context("unlocking provider collateral")?;
 
         Ok(slashed)
     }
@@ -1024,10 +1161,8 @@ impl State {
         Ok(())
     }
 
-    pub fn generate_storage_deal_id(&mut self) -> DealID {
-        let ret = self.next_id;
-        self.next_id += 1;
-        ret
+    pub fn generate_storage_deal_id(&mut self) -> Result<DealID, ActorError> {
+        take_id(&mut self.next_id, "deal")
     }
 
     // Return true when the funds in escrow for the input address can cover an additional lockup of amountToLock
@@ -1097,6 +1232,24 @@ impl State {
         Ok(())
     }
 
+    /// Replaces the client's locked storage fee for a deal with that of its amended proposal.
+    pub fn relock_client_storage_fee<BS>(
+        &mut self,
+        store: &BS,
+        old: &DealProposal,
+        new: &DealProposal,
+    ) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        self.unlock_balance(store, &old.client, &old.total_storage_fee(), Reason::ClientStorageFee)
+            .context("unlocking client storage fee")?;
+        self.maybe_lock_balance(store, &new.client, &new.total_storage_fee())
+            .context("locking client storage fee")?;
+        self.total_client_storage_fee += new.total_storage_fee();
+        Ok(())
+    }
+
     fn unlock_balance<BS>(
         &mut self,
         store: &BS,
@@ -1220,16 +1373,18 @@ pub fn get_proposal<BS: Blockstore>(
     id: DealID,
     next_id: DealID,
 ) -> Result<DealProposal, ActorError> {
-    let found = find_proposal(proposals, id)?.ok_or_else(|| {
-        if id < next_id {
-            // If the deal ID has been used, it must have been cleaned up.
-            ActorError::unchecked(EX_DEAL_EXPIRED, format!("deal {} expired", id))
-        } else {
-            // Never been published.
-            ActorError::not_found(format!("no such deal {}", id))
-        }
-    })?;
-    Ok(found)
+    find_proposal(proposals, id)?.ok_or_else(|| missing_proposal_error(id, next_id))
+}
+
+/// The error to report for a deal proposal that is not found.
+pub(crate) fn missing_proposal_error(id: DealID, next_id: DealID) -> ActorError {
+    if id < next_id {
+        // If the deal ID has been used, it must have been cleaned up.
+        ActorError::unchecked(EX_DEAL_EXPIRED, format!("deal {} expired", id))
+    } else {
+        // Never been published.
+        ActorError::not_found(format!("no such deal {}", id))
+    }
 }
 
 pub fn find_proposal<BS>(
@@ -1239,10 +1394,7 @@ pub fn find_proposal<BS>(
 where
     BS: Blockstore,
 {
-    let proposal = proposals.get(deal_id).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-        format!("failed to load deal proposal {}", deal_id)
-    })?;
-    Ok(proposal.cloned())
+    Ok(proposals.get(deal_id)?.cloned())
 }
 
 pub fn find_deal_state<BS>(
@@ -1252,10 +1404,7 @@ pub fn find_deal_state<BS>(
 where
     BS: Blockstore,
 {
-    let state = states.get(deal_id).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-        format!("failed to load deal state {}", deal_id)
-    })?;
-    Ok(state.cloned())
+    Ok(states.get(deal_id)?.cloned())
 }
 
 pub fn load_provider_sector_deals<BS>(
@@ -8,6 +8,7 @@ use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::DAG_CBOR;
 use fvm_shared::error::ExitCode;
+use fvm_shared::piece::PaddedPieceSize;
 use fvm_shared::sector::SectorNumber;
 use fvm_shared::{
     address::{Address, Protocol},
@@ -24,6 +25,7 @@ use fil_actors_runtime::builtin::HAMT_BIT_WIDTH;
 use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::{
     make_map_with_root_and_bitwidth, ActorError, AsActorError, MessageAccumulator,
+    ID_OVERFLOW_RISK_THRESHOLD,
 };
 
 use crate::ext::verifreg::AllocationID;
@@ -36,6 +38,9 @@ use crate::{
 #[derive(Clone)]
 pub struct DealSummary {
     pub provider: Address,
+    pub client: Address,
+    pub piece_size: PaddedPieceSize,
+    pub verified_deal: bool,
     pub start_epoch: ChainEpoch,
     pub end_epoch: ChainEpoch,
     pub sector_number: SectorNumber,
@@ -49,6 +54,9 @@ impl Default for DealSummary {
     fn default() -> Self {
         Self {
             provider: Address::new_id(0),
+            client: Address::new_id(0),
+            piece_size: PaddedPieceSize(0),
+            verified_deal: false,
             start_epoch: 0,
             end_epoch: 0,
             sector_number: 0,
@@ -60,6 +68,17 @@ impl Default for DealSummary {
     }
 }
 
+/// Counts of deals whose state and proposal records don't correspond, by class of violation.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct DealRecordViolations {
+    /// Deal states with no proposal.
+    pub states_without_proposal: u64,
+    /// Proposals past their start epoch that are neither activated nor scheduled to time out.
+    pub unactivated_past_start: u64,
+    /// Proposals or deal states with an ID not below the next deal ID.
+    pub ids_not_below_next_id: u64,
+}
+
 #[derive(Default, Clone)]
 pub struct StateSummary {
     pub deals: BTreeMap<DealID, DealSummary>,
@@ -70,6 +89,7 @@ pub struct StateSummary {
     pub lock_table_count: u64,
     pub deal_op_epoch_count: u64,
     pub deal_op_count: u64,
+    pub deal_record_violations: DealRecordViolations,
 }
 
 /// Checks internal invariants of market state
@@ -81,6 +101,13 @@ pub fn check_state_invariants<BS: Blockstore>(
 ) -> (StateSummary, MessageAccumulator) {
     let acc = MessageAccumulator::default();
 
+    acc.require(
+        state.next_id <= ID_OVERFLOW_RISK_THRESHOLD,
+        format!(
+            "next deal id {} exceeds overflow risk threshold {}",
+            state.next_id, ID_OVERFLOW_RISK_THRESHOLD
+        ),
+    );
     acc.require(
         !state.total_client_locked_collateral.is_negative(),
         format!(
@@ -102,12 +129,11 @@ pub fn check_state_invariants<BS: Blockstore>(
 
     // Proposals
     let mut proposal_cids = BTreeSet::<Cid>::new();
-    let mut max_deal_id = -1;
     let mut proposal_stats = BTreeMap::<DealID, DealSummary>::new();
     let mut expected_deal_ops = BTreeSet::<DealID>::new();
     let mut total_proposal_collateral = TokenAmount::zero();
 
-    match DealArray::load(&state.proposals, store) {
+    match DealArray::load(store, &state.proposals, "deal proposals") {
         Ok(proposals) => {
             let ret = proposals.for_each(|deal_id, proposal| {
                 let proposal_cid = deal_cid(proposal)?;
@@ -118,12 +144,14 @@ pub fn check_state_invariants<BS: Blockstore>(
 
                 // keep some state
                 proposal_cids.insert(proposal_cid);
-                max_deal_id = max_deal_id.max(deal_id as i64);
 
                 proposal_stats.insert(
                     deal_id,
                     DealSummary {
                         provider: proposal.provider,
+                        client: proposal.client,
+                        piece_size: proposal.piece_size,
+                        verified_deal: proposal.verified_deal,
                         start_epoch: proposal.start_epoch,
                         end_epoch: proposal.end_epoch,
                         piece_cid: Some(proposal.piece_cid),
@@ -149,15 +177,6 @@ pub fn check_state_invariants<BS: Blockstore>(
         Err(e) => acc.add(format!("error loading proposals: {e}")),
     };
 
-    // next id should be higher than any existing deal
-    acc.require(
-        state.next_id as i64 > max_deal_id,
-        format!(
-            "next id, {}, is not greater than highest id in proposals, {max_deal_id}",
-            state.next_id
-        ),
-    );
-
     let mut pending_allocations = BTreeMap::<DealID, AllocationID>::new();
     let mut alloc_id_to_deal_id = BTreeMap::<AllocationID, DealID>::new();
     match make_map_with_root_and_bitwidth(&state.pending_deal_allocation_ids, store, HAMT_BIT_WIDTH)
@@ -182,7 +201,9 @@ pub fn check_state_invariants<BS: Blockstore>(
 
     // deal states
     let mut deal_state_count = 0;
-    match DealMetaArray::load(&state.states, store) {
+    let mut states_without_proposal = BTreeSet::<DealID>::new();
+    let mut state_ids_not_below_next_id = BTreeSet::<DealID>::new();
+    match DealMetaArray::load(store, &state.states, "deal states") {
         Ok(deal_states) => {
             let ret = deal_states.for_each(|deal_id, deal_state| {
                 acc.require(
@@ -213,7 +234,10 @@ pub fn check_state_invariants<BS: Blockstore>(
                     stats.last_update_epoch = deal_state.last_updated_epoch;
                     stats.slash_epoch = deal_state.slash_epoch;
                 } else {
-                    acc.add(format!("no deal proposal for deal state {deal_id}"));
+                    states_without_proposal.insert(deal_id);
+                }
+                if deal_id >= state.next_id {
+                    state_ids_not_below_next_id.insert(deal_id);
                 }
                 acc.require(!pending_allocations.contains_key(&deal_id), format!("deal {deal_id} has pending allocation"));
 
@@ -317,6 +341,25 @@ pub fn check_state_invariants<BS: Blockstore>(
         Err(e) => acc.add(format!("error loading pending proposals: {e}")),
     };
 
+    // superseded proposals are forgotten once cron reaches their start epoch
+    match state.load_superseded_proposals(store) {
+        Ok(Some(superseded)) => {
+            let ret = superseded.for_each(|epoch, _| {
+                acc.require(
+                    epoch > state.last_cron,
+                    format!(
+                        "superseded proposals at epoch {epoch} not pruned by cron at {}",
+                        state.last_cron
+                    ),
+                );
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating superseded proposals");
+        }
+        Ok(None) => {}
+        Err(e) => acc.add(format!("error loading superseded proposals: {e}")),
+    };
+
     // escrow table and locked table
     let mut lock_table_count = 0;
     let escrow_table = BalanceTable::from_root(store, &state.escrow_table, "escrow table");
@@ -346,8 +389,18 @@ pub fn check_state_invariants<BS: Blockstore>(
 
             // assert escrow <= actor balance
             // lock_table item <= escrow item and escrow_total <= balance implies lock_table total <= balance
-            match escrow_table.total() {
-                Ok(escrow_total) => {
+            let mut escrow_total = TokenAmount::zero();
+            let ret = escrow_table.for_each(|address, escrow_amount| {
+                // zero balances are removed from the table rather than stored
+                acc.require(
+                    escrow_amount.is_positive(),
+                    format!("non-positive escrow balance for {address}, {escrow_amount}"),
+                );
+                escrow_total += escrow_amount;
+                Ok(())
+            });
+            match ret {
+                Ok(()) => {
                     acc.require(
                         &escrow_total <= balance,
                         format!(
@@ -356,7 +409,7 @@ pub fn check_state_invariants<BS: Blockstore>(
                     );
                     acc.require(escrow_total >= total_proposal_collateral, format!("escrow total, {escrow_total}, less than sum of proposal collateral, {total_proposal_collateral}"));
                 }
-                Err(e) => acc.add(format!("error calculating escrow total: {e}")),
+                Err(e) => acc.add(format!("error iterating escrow table: {e}")),
             }
         }
         (escrow_table, lock_table) => {
@@ -365,6 +418,14 @@ pub fn check_state_invariants<BS: Blockstore>(
         }
     };
 
+    // Proposals that have started without being activated must still be awaiting the cron
+    // tick that times them out.
+    let mut unactivated_past_start: BTreeSet<DealID> = proposal_stats
+        .iter()
+        .filter(|(_, d)| d.sector_start_epoch == EPOCH_UNDEFINED && d.start_epoch < current_epoch)
+        .map(|(deal_id, _)| *deal_id)
+        .collect();
+
     // deals ops by epoch
     let (mut deal_op_epoch_count, mut deal_op_count) = (0, 0);
     match DealOpsByEpoch::load(
@@ -374,15 +435,37 @@ pub fn check_state_invariants<BS: Blockstore>(
         "deal ops",
     ) {
         Ok(deal_ops) => {
+            let (mut processed_epochs, mut empty_epochs) = (vec![], vec![]);
             let ret = deal_ops.for_each(|epoch: ChainEpoch, _| {
                 deal_op_epoch_count += 1;
+                if epoch <= state.last_cron {
+                    processed_epochs.push(epoch);
+                }
+                let mut epoch_count = 0;
                 deal_ops.for_each_in(&epoch, |deal_id: DealID| {
                     expected_deal_ops.remove(&deal_id);
+                    unactivated_past_start.remove(&deal_id);
                     deal_op_count += 1;
+                    epoch_count += 1;
                     Ok(())
-                })
+                })?;
+                if epoch_count == 0 {
+                    empty_epochs.push(epoch);
+                }
+                Ok(())
             });
             acc.require_no_error(ret, "error iterating all deal ops");
+            acc.require(
+                processed_epochs.is_empty(),
+                format!(
+                    "deal ops remain at epochs {processed_epochs:?} already processed by cron at {}",
+                    state.last_cron
+                ),
+            );
+            acc.require(
+                empty_epochs.is_empty(),
+                format!("empty deal op sets at epochs {empty_epochs:?}"),
+            );
         }
         Err(e) => acc.add(format!("error loading deal ops: {e}")),
     };
@@ -392,6 +475,15 @@ pub fn check_state_invariants<BS: Blockstore>(
         format!("missing deal ops for proposals: {expected_deal_ops:?}"),
     );
 
+    let proposal_ids_not_below_next_id =
+        proposal_stats.range(state.next_id..).map(|(deal_id, _)| *deal_id).collect();
+    let deal_record_violations = check_deal_records(
+        &acc,
+        &states_without_proposal,
+        &unactivated_past_start,
+        &(&proposal_ids_not_below_next_id | &state_ids_not_below_next_id),
+    );
+
     (
         StateSummary {
             deals: proposal_stats,
@@ -402,11 +494,48 @@ pub fn check_state_invariants<BS: Blockstore>(
             deal_op_epoch_count,
             deal_op_count,
             alloc_id_to_deal_id,
+            deal_record_violations,
         },
         acc,
     )
 }
 
+/// Reports each class of disagreement between the deal states and proposals as a single
+/// message carrying the count and IDs of the offending deals.
+fn check_deal_records(
+    acc: &MessageAccumulator,
+    states_without_proposal: &BTreeSet<DealID>,
+    unactivated_past_start: &BTreeSet<DealID>,
+    ids_not_below_next_id: &BTreeSet<DealID>,
+) -> DealRecordViolations {
+    acc.require(
+        states_without_proposal.is_empty(),
+        format!(
+            "{} deal states have no proposal: {states_without_proposal:?}",
+            states_without_proposal.len()
+        ),
+    );
+    acc.require(
+        unactivated_past_start.is_empty(),
+        format!(
+            "{} proposals past their start epoch are neither activated nor awaiting timeout: {unactivated_past_start:?}",
+            unactivated_past_start.len()
+        ),
+    );
+    acc.require(
+        ids_not_below_next_id.is_empty(),
+        format!(
+            "{} deals have an ID not below the next deal ID: {ids_not_below_next_id:?}",
+            ids_not_below_next_id.len()
+        ),
+    );
+    DealRecordViolations {
+        states_without_proposal: states_without_proposal.len() as u64,
+        unactivated_past_start: unactivated_past_start.len() as u64,
+        ids_not_below_next_id: ids_not_below_next_id.len() as u64,
+    }
+}
+
 /// Compute a deal CID directly (the actor code uses a runtime built-in).
 pub(crate) fn deal_cid(proposal: &DealProposal) -> Result<Cid, ActorError> {
     const DIGEST_SIZE: u32 = 32;
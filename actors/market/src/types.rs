@@ -3,7 +3,8 @@
 
 use super::ext::verifreg::AllocationID;
 use cid::Cid;
-use fil_actors_runtime::Array;
+use fil_actors_runtime::validate_params;
+use fil_actors_runtime::Array2;
 use fil_actors_runtime::BatchReturn;
 use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::strict_bytes;
@@ -11,6 +12,7 @@ use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
 use fvm_shared::bigint::{bigint_ser, BigInt};
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::crypto::signature::Signature;
 use fvm_shared::deal::DealID;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::piece::PaddedPieceSize;
@@ -36,6 +38,8 @@ pub struct WithdrawBalanceParams {
     pub amount: TokenAmount,
 }
 
+validate_params!(WithdrawBalanceParams { amount: non_negative });
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 #[serde(transparent)]
 pub struct WithdrawBalanceReturn {
@@ -65,6 +69,8 @@ pub struct PublishStorageDealsParams {
     pub deals: Vec<ClientDealProposal>,
 }
 
+validate_params!(PublishStorageDealsParams { deals: non_empty });
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq)] // Add Eq when BitField does
 pub struct PublishStorageDealsReturn {
     pub ids: Vec<DealID>,
@@ -138,10 +144,10 @@ pub struct DealSpaces {
 }
 
 /// A specialization of a array to deals.
-pub type DealArray<'bs, BS> = Array<'bs, DealProposal, BS>;
+pub type DealArray<BS> = Array2<BS, DealProposal>;
 
 /// A specialization of a array to deals.
-pub type DealMetaArray<'bs, BS> = Array<'bs, DealState, BS>;
+pub type DealMetaArray<BS> = Array2<BS, DealState>;
 
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 pub struct SectorDataSpec {
@@ -149,6 +155,18 @@ pub struct SectorDataSpec {
     pub sector_type: RegisteredSealProof,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ComputeDataCommitmentParams {
+    /// The deals of each sector for which to compute an unsealed CID.
+    pub inputs: Vec<SectorDataSpec>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ComputeDataCommitmentReturn {
+    /// The unsealed CID of each sector, in the order of the inputs.
+    pub commds: Vec<Cid>,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 #[serde(transparent)]
 pub struct DealQueryParams {
@@ -248,6 +266,21 @@ pub struct GetDealSectorReturn {
     pub sector: SectorNumber,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDealCollateralBoundsParams {
+    pub piece_size: PaddedPieceSize,
+    pub duration: ChainEpoch,
+}
+
+/// Inclusive bounds on the collateral of a deal published at the current epoch.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDealCollateralBoundsReturn {
+    pub min_provider_collateral: TokenAmount,
+    pub max_provider_collateral: TokenAmount,
+    pub min_client_collateral: TokenAmount,
+    pub max_client_collateral: TokenAmount,
+}
+
 // Interface market clients can implement to receive notifications from builtin market
 pub const MARKET_NOTIFY_DEAL_METHOD: u64 = frc42_dispatch::method_hash!("MarketNotifyDeal");
 
@@ -279,3 +312,14 @@ pub struct DealSettlementSummary {
     /// Whether the deal has settled for the final time
     pub completed: bool,
 }
+
+/// Amends the start epoch and price of a published deal that hasn't been activated.
+/// The deal keeps its duration, so its end epoch moves with its start.
+/// The client signs the amended proposal, as for publishing.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct AmendDealParams {
+    pub deal_id: DealID,
+    pub start_epoch: ChainEpoch,
+    pub storage_price_per_epoch: TokenAmount,
+    pub client_signature: Signature,
+}
@@ -0,0 +1,165 @@
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::crypto::signature::Signature;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+
+use fil_actor_market::{deal_cid, AmendDealParams, State, EX_DEAL_EXPIRED, NO_ALLOCATION_ID};
+use fil_actors_runtime::network::EPOCHS_IN_DAY;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_utils::*;
+use harness::*;
+
+mod harness;
+
+const START_EPOCH: ChainEpoch = 10;
+const END_EPOCH: ChainEpoch = START_EPOCH + 200 * EPOCHS_IN_DAY;
+
+fn amend_params(deal_id: u64, start_epoch: ChainEpoch) -> AmendDealParams {
+    AmendDealParams {
+        deal_id,
+        start_epoch,
+        storage_price_per_epoch: TokenAmount::from_atto(5),
+        client_signature: Signature::new_bls(vec![]),
+    }
+}
+
+#[test]
+fn amend_moves_deal_and_relocks_client_fee() {
+    let rt = setup();
+    let addrs = MinerAddresses::default();
+    let (deal_id, proposal) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, START_EPOCH, END_EPOCH);
+    let locked_before = get_balance(&rt, &CLIENT_ADDR).locked;
+
+    // Delay the deal far enough that its next update moves to a later epoch.
+    let new_start = START_EPOCH + Policy::default().deal_updates_interval;
+    let amended = amend_deal(&rt, &addrs, deal_id, new_start, TokenAmount::from_atto(5));
+    assert_eq!(new_start, amended.start_epoch);
+    assert_eq!(proposal.duration(), amended.duration());
+    assert_eq!(amended, get_deal_proposal(&rt, deal_id));
+
+    // The client's lock reflects the lower price, and the provider's is unchanged.
+    assert_eq!(
+        locked_before - proposal.total_storage_fee() + amended.total_storage_fee(),
+        get_balance(&rt, &CLIENT_ADDR).locked
+    );
+    assert_eq!(proposal.provider_collateral, get_balance(&rt, &addrs.provider).locked);
+
+    // The pending proposal and scheduled update follow the amended proposal.
+    let st: State = rt.get_state();
+    assert!(!st.has_pending_deal(&rt.store, &deal_cid(&rt, &proposal).unwrap()).unwrap());
+    assert!(st.has_pending_deal(&rt.store, &deal_cid(&rt, &amended).unwrap()).unwrap());
+    let old_epoch = process_epoch(START_EPOCH, deal_id);
+    let new_epoch = process_epoch(new_start, deal_id);
+    assert_ne!(old_epoch, new_epoch);
+    assert!(st.get_deals_for_epoch(&rt.store, old_epoch).unwrap().is_empty());
+    assert_eq!(vec![deal_id], st.get_deals_for_epoch(&rt.store, new_epoch).unwrap());
+    check_state(&rt);
+
+    // The amended deal can be activated for a sector covering its new term.
+    activate_deals(&rt, amended.end_epoch, addrs.provider, 0, 1, &[deal_id]);
+    check_state(&rt);
+}
+
+#[test]
+fn amended_deal_original_proposal_cannot_be_republished() {
+    let rt = setup();
+    let addrs = MinerAddresses::default();
+    let (deal_id, proposal) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, START_EPOCH, END_EPOCH);
+    amend_deal(&rt, &addrs, deal_id, START_EPOCH + 1, TokenAmount::from_atto(5));
+
+    // The client's signature on the original proposal is still valid, and both parties can cover
+    // it again, but the superseded proposal is rejected as a duplicate.
+    add_participant_funds(&rt, CLIENT_ADDR, proposal.client_balance_requirement());
+    add_provider_funds(&rt, proposal.provider_collateral.clone(), &addrs);
+    publish_deals_expect_abort(&rt, &addrs, proposal.clone(), ExitCode::USR_ILLEGAL_ARGUMENT);
+    let old_cid = deal_cid(&rt, &proposal).unwrap();
+    let st: State = rt.get_state();
+    assert!(st.is_superseded_proposal(&rt.store, START_EPOCH, &old_cid).unwrap());
+    check_state(&rt);
+
+    // The record is dropped once the original proposal's start epoch has been reached.
+    rt.set_epoch(START_EPOCH);
+    cron_tick(&rt);
+    let st: State = rt.get_state();
+    assert!(!st.is_superseded_proposal(&rt.store, START_EPOCH, &old_cid).unwrap());
+    check_state(&rt);
+}
+
+#[test]
+fn amend_rejects_price_client_cannot_cover() {
+    let rt = setup();
+    let addrs = MinerAddresses::default();
+    let (deal_id, proposal) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, START_EPOCH, END_EPOCH);
+
+    // The client's escrow covers only the original price.
+    let price = &proposal.storage_price_per_epoch * 2;
+    amend_deal_expect_abort(
+        &rt,
+        &addrs,
+        deal_id,
+        START_EPOCH,
+        price,
+        ExitCode::USR_INSUFFICIENT_FUNDS,
+    );
+    assert_eq!(proposal, get_deal_proposal(&rt, deal_id));
+    check_state(&rt);
+}
+
+#[test]
+fn amend_requires_provider_control_address() {
+    let rt = setup();
+    let addrs = MinerAddresses::default();
+    let (deal_id, _) = generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, START_EPOCH, END_EPOCH);
+
+    rt.expect_validate_caller_any();
+    expect_provider_is_control_address(&rt, addrs.provider, CLIENT_ADDR, false);
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, CLIENT_ADDR);
+    expect_abort(ExitCode::USR_FORBIDDEN, amend_deal_raw(&rt, amend_params(deal_id, START_EPOCH)));
+    rt.verify();
+    check_state(&rt);
+}
+
+#[test]
+fn amend_rejects_activated_verified_and_elapsed_deals() {
+    let rt = setup();
+    let addrs = MinerAddresses::default();
+    let (active_id, _) = publish_and_activate_deal(
+        &rt,
+        CLIENT_ADDR,
+        &addrs,
+        1,
+        START_EPOCH,
+        END_EPOCH,
+        0,
+        END_EPOCH,
+    );
+    let verified = generate_and_publish_verified_deal(
+        &rt,
+        CLIENT_ADDR,
+        &addrs,
+        START_EPOCH,
+        END_EPOCH,
+        NO_ALLOCATION_ID + 1,
+    );
+    let (pending_id, _) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, START_EPOCH + 1, END_EPOCH);
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, addrs.worker);
+    for (id, code) in [
+        (active_id, ExitCode::USR_FORBIDDEN),
+        (verified, ExitCode::USR_FORBIDDEN),
+        (pending_id + 1, ExitCode::USR_NOT_FOUND),
+    ] {
+        rt.expect_validate_caller_any();
+        expect_abort(code, amend_deal_raw(&rt, amend_params(id, START_EPOCH + 100)));
+        rt.verify();
+    }
+
+    rt.set_epoch(START_EPOCH + 2);
+    rt.expect_validate_caller_any();
+    expect_abort(EX_DEAL_EXPIRED, amend_deal_raw(&rt, amend_params(pending_id, START_EPOCH + 100)));
+    rt.verify();
+}
@@ -191,7 +191,7 @@ fn sectors_fail_and_succeed_independently_during_batch_activation() {
 
     // no state for deal2 means deal2 was not activated
     let st: State = rt.get_state();
-    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
     let s = states.get(id_2).unwrap();
     assert!(s.is_none());
 
@@ -349,7 +349,7 @@ fn fails_to_activate_cross_sector_duplicate_deals() {
 
     // no state for deal2 means deal2 was not activated
     let st: State = rt.get_state();
-    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
     let s = states.get(id_2).unwrap();
     assert!(s.is_none());
 
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 //! TODO: Revisit tests here and cleanup https://github.com/filecoin-project/builtin-actors/issues/1389
+use fil_actor_market::State;
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_utils::MockRuntime;
 use fvm_shared::clock::ChainEpoch;
 
 mod harness;
@@ -53,6 +55,46 @@ fn deal_is_correctly_processed_if_first_cron_after_expiry() {
     check_state(&rt);
 }
 
+#[test]
+fn cron_removes_processed_deal_op_epochs() {
+    let sector_number = 7;
+    let rt = setup();
+    let (deal_id, _) = publish_and_activate_deal_legacy(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        sector_number,
+        START_EPOCH,
+        END_EPOCH,
+        0,
+        END_EPOCH,
+    );
+    let scheduled = deal_op_epochs(&rt);
+    assert_eq!(1, scheduled.len());
+
+    let current = scheduled[0];
+    rt.set_epoch(current);
+    cron_tick_and_assert_balances(&rt, CLIENT_ADDR, PROVIDER_ADDR, current, deal_id);
+
+    // The processed epoch is gone and the deal is scheduled again later.
+    let rescheduled = deal_op_epochs(&rt);
+    assert_eq!(1, rescheduled.len());
+    assert!(rescheduled[0] > current);
+    check_state(&rt);
+}
+
+fn deal_op_epochs(rt: &MockRuntime) -> Vec<ChainEpoch> {
+    let st: State = rt.get_state();
+    let dobe = st.load_deal_ops(&rt.store).unwrap();
+    let mut epochs = vec![];
+    dobe.for_each(|epoch, _| {
+        epochs.push(epoch);
+        Ok(())
+    })
+    .unwrap();
+    epochs
+}
+
 // this test needs to have the deal injected into the market actor state to simulate legacy deals
 #[test]
 fn regular_payments_till_deal_expires_and_then_locked_funds_are_unlocked() {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 //! TODO: Revisit tests here and cleanup https://github.com/filecoin-project/builtin-actors/issues/1389
+use fil_actor_market::policy::deal_termination_penalty;
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::runtime::Policy;
 use fvm_shared::clock::ChainEpoch;
@@ -95,7 +96,16 @@ fn deal_is_slashed() {
         );
 
         assert_eq!(tc.payment, pay);
-        assert_eq!(deal_proposal.provider_collateral, slashed);
+        assert_eq!(
+            deal_termination_penalty(
+                &rt.policy,
+                &deal_proposal.provider_collateral,
+                tc.deal_start,
+                tc.deal_end,
+                tc.termination_epoch,
+            ),
+            slashed
+        );
 
         // cron tick to remove final deal op state
         let cron_tick_epoch = process_epoch(tc.deal_start, deal_id);
@@ -185,7 +195,16 @@ fn deal_payment_and_slashing_correctly_processed_in_same_crontick() {
     );
     let duration = slash_epoch - current;
     assert_eq!(duration * &deal_proposal.storage_price_per_epoch, pay);
-    assert_eq!(deal_proposal.provider_collateral, slashed);
+    assert_eq!(
+        deal_termination_penalty(
+            &rt.policy,
+            &deal_proposal.provider_collateral,
+            start_epoch,
+            end_epoch,
+            slash_epoch,
+        ),
+        slashed
+    );
 
     let current = current + Policy::default().deal_updates_interval + 2;
     rt.set_epoch(current);
@@ -308,7 +327,16 @@ fn regular_payments_till_deal_is_slashed_and_then_slashing_is_processed() {
         &[deal_id],
     );
     assert_eq!(pay, 1 * &deal_proposal.storage_price_per_epoch);
-    assert_eq!(slashed, deal_proposal.provider_collateral);
+    assert_eq!(
+        slashed,
+        deal_termination_penalty(
+            &rt.policy,
+            &deal_proposal.provider_collateral,
+            deal_proposal.start_epoch,
+            deal_proposal.end_epoch,
+            process_start + Policy::default().deal_updates_interval + 1,
+        )
+    );
 
     // Setting the epoch to anything less than next schedule will not make any change even though the deal is slashed
     rt.set_epoch(process_start + 2 * Policy::default().deal_updates_interval - 1);
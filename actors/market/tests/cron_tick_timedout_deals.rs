@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fil_actor_market::{
-    Actor as MarketActor, ClientDealProposal, Method, PublishStorageDealsParams,
+    Actor as MarketActor, ClientDealProposal, Method, PublishStorageDealsParams, State,
 };
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::test_utils::*;
@@ -18,6 +18,7 @@ use fil_actor_market::ext::account::{AuthenticateMessageParams, AUTHENTICATE_MES
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::sys::SendFlags;
 use num_traits::Zero;
+use regex::Regex;
 
 mod harness;
 
@@ -197,3 +198,31 @@ fn timed_out_and_verified_deals_are_slashed_deleted() {
     assert_deal_deleted(&rt, deal_ids[2], &deal3, 0, true);
     check_state(&rt);
 }
+
+#[test]
+fn checker_reports_started_deal_with_no_timeout_scheduled() {
+    let rt = setup();
+    let (deal_id, _) = generate_and_publish_deal(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        START_EPOCH,
+        END_EPOCH,
+    );
+
+    // an unactivated deal past its start epoch is fine while cron has yet to time it out
+    rt.set_epoch(START_EPOCH + 1);
+    check_state(&rt);
+
+    // but not once its scheduled processing has gone missing
+    let mut st: State = rt.get_state();
+    st.remove_deals_by_epoch(&rt.store, &[process_epoch(START_EPOCH, deal_id)]).unwrap();
+    rt.replace_state(&st);
+    check_state_with_expected(
+        &rt,
+        &[Regex::new(
+            "1 proposals past their start epoch are neither activated nor awaiting timeout",
+        )
+        .unwrap()],
+    );
+}
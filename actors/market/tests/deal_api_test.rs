@@ -2,18 +2,23 @@ use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::clock::{ChainEpoch, EPOCH_UNDEFINED};
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::piece::{PaddedPieceSize, PieceInfo};
+use fvm_shared::sector::{RegisteredSealProof, StoragePower};
 use num_traits::Zero;
 use serde::de::DeserializeOwned;
 
+use fil_actor_market::policy::{deal_client_collateral_bounds, deal_provider_collateral_bounds};
 use fil_actor_market::{
-    Actor as MarketActor, DealQueryParams, GetDealActivationReturn, GetDealClientCollateralReturn,
-    GetDealClientReturn, GetDealDataCommitmentReturn, GetDealLabelReturn,
-    GetDealProviderCollateralReturn, GetDealProviderReturn, GetDealSectorReturn, GetDealTermReturn,
-    GetDealTotalPriceReturn, GetDealVerifiedReturn, Method, EX_DEAL_EXPIRED, EX_DEAL_NOT_ACTIVATED,
+    Actor as MarketActor, ComputeDataCommitmentParams, ComputeDataCommitmentReturn,
+    DealQueryParams, GetDealActivationReturn, GetDealClientCollateralReturn, GetDealClientReturn,
+    GetDealCollateralBoundsParams, GetDealCollateralBoundsReturn, GetDealDataCommitmentReturn,
+    GetDealLabelReturn, GetDealProviderCollateralReturn, GetDealProviderReturn,
+    GetDealSectorReturn, GetDealTermReturn, GetDealTotalPriceReturn, GetDealVerifiedReturn, Method,
+    SectorDataSpec, EX_DEAL_EXPIRED, EX_DEAL_NOT_ACTIVATED, NO_ALLOCATION_ID,
 };
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::test_utils::{
-    expect_abort, expect_abort_contains_message, MockRuntime, ACCOUNT_ACTOR_CODE_ID,
+    expect_abort, expect_abort_contains_message, make_piece_cid, MockRuntime, ACCOUNT_ACTOR_CODE_ID,
 };
 use fil_actors_runtime::ActorError;
 use harness::*;
@@ -144,6 +149,143 @@ fn activation() {
     query_deal_fails(&rt, Method::GetDealSectorExported, id + 1, ExitCode::USR_NOT_FOUND);
 }
 
+#[test]
+fn collateral_bounds() {
+    let rt = setup();
+    rt.set_circulating_supply(TokenAmount::from_whole(1_000_000));
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, CLIENT_ADDR);
+    let piece_size = PaddedPieceSize(1 << 30);
+    let duration = 200 * EPOCHS_IN_DAY;
+    let params = GetDealCollateralBoundsParams { piece_size, duration };
+
+    // The bounds are those of the library functions at the network's current power and supply.
+    rt.expect_validate_caller_any();
+    expect_query_network_info(&rt);
+    let ret: GetDealCollateralBoundsReturn = rt
+        .call::<MarketActor>(
+            Method::GetDealCollateralBoundsExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    // The network info above has no raw power and a baseline of 2^50 bytes.
+    let (min_provider, max_provider) = deal_provider_collateral_bounds(
+        &rt.policy,
+        piece_size,
+        &StoragePower::zero(),
+        &StoragePower::from(1u64 << 50),
+        &TokenAmount::from_whole(1_000_000),
+    );
+    let (min_client, max_client) = deal_client_collateral_bounds(piece_size, duration);
+    assert_eq!(
+        GetDealCollateralBoundsReturn {
+            min_provider_collateral: min_provider,
+            max_provider_collateral: max_provider,
+            min_client_collateral: min_client,
+            max_client_collateral: max_client,
+        },
+        ret
+    );
+    assert!(ret.min_provider_collateral.is_positive());
+
+    // A piece size that isn't a power of two is rejected.
+    let params = GetDealCollateralBoundsParams { piece_size: PaddedPieceSize(1000), duration };
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        rt.call::<MarketActor>(
+            Method::GetDealCollateralBoundsExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.verify();
+}
+
+#[test]
+fn batch_compute_data_commitment() {
+    let start_epoch = 1000;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let rt = setup();
+    rt.set_epoch(1);
+
+    let proposal1 = generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch,
+    );
+    let proposal2 = generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch + 1,
+    );
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    let ids = publish_deals(
+        &rt,
+        &MinerAddresses::default(),
+        &[proposal1.clone(), proposal2.clone()],
+        TokenAmount::zero(),
+        NO_ALLOCATION_ID,
+    );
+
+    // One sector with both deals, and an empty sector.
+    let seal_proof = RegisteredSealProof::StackedDRG8MiBV1;
+    let params = ComputeDataCommitmentParams {
+        inputs: vec![
+            SectorDataSpec { deal_ids: ids.clone(), sector_type: seal_proof },
+            SectorDataSpec { deal_ids: vec![], sector_type: seal_proof },
+        ],
+    };
+    let full_commd = make_piece_cid("full".as_bytes());
+    let empty_commd = make_piece_cid("empty".as_bytes());
+    rt.expect_compute_unsealed_sector_cid(
+        seal_proof,
+        [&proposal1, &proposal2]
+            .iter()
+            .map(|p| PieceInfo { cid: p.piece_cid, size: p.piece_size })
+            .collect(),
+        full_commd,
+        ExitCode::OK,
+    );
+    rt.expect_compute_unsealed_sector_cid(seal_proof, vec![], empty_commd, ExitCode::OK);
+
+    // Any caller may use the exported method.
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, CLIENT_ADDR);
+    rt.expect_validate_caller_any();
+    let ret: ComputeDataCommitmentReturn = rt
+        .call::<MarketActor>(
+            Method::BatchComputeDataCommitmentExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    assert_eq!(vec![full_commd, empty_commd], ret.commds);
+
+    // An unknown deal fails the whole batch.
+    let params = ComputeDataCommitmentParams {
+        inputs: vec![SectorDataSpec { deal_ids: vec![ids[0], 1234], sector_type: seal_proof }],
+    };
+    rt.expect_validate_caller_any();
+    expect_abort_contains_message(
+        ExitCode::USR_NOT_FOUND,
+        "failed to load deals for input 0",
+        rt.call::<MarketActor>(
+            Method::BatchComputeDataCommitmentExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.verify();
+}
+
 fn query_deal<T: DeserializeOwned>(rt: &MockRuntime, method: Method, id: u64) -> T {
     query_deal_raw(rt, method, id).unwrap().unwrap().deserialize().unwrap()
 }
@@ -1,4 +1,6 @@
+use fil_actor_market::policy::deal_termination_penalty;
 use fil_actor_market::{DealSettlementSummary, EX_DEAL_EXPIRED};
+use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::EPOCHS_IN_DAY;
 use fvm_shared::{clock::ChainEpoch, econ::TokenAmount};
 
@@ -104,7 +106,15 @@ fn deal_is_terminated() {
         );
 
         assert_eq!(tc.termination_payment, pay);
-        assert_eq!(deal_proposal.provider_collateral, slashed);
+        // The provider's collateral is slashed in proportion to the deal's remaining term.
+        let expected_slash = deal_termination_penalty(
+            &rt.policy,
+            &deal_proposal.provider_collateral,
+            tc.deal_start,
+            tc.deal_end,
+            tc.termination_epoch,
+        );
+        assert_eq!(expected_slash, slashed);
 
         assert_deal_deleted(&rt, deal_id, &deal_proposal, sector_number, true);
 
@@ -202,7 +212,13 @@ fn settle_payments_then_terminate_deal_in_the_same_epoch() {
     // end state should be equivalent to only calling termination
     let client_after = get_balance(&rt, &CLIENT_ADDR);
     let provider_after = get_balance(&rt, &PROVIDER_ADDR);
-    let expected_slash = proposal.provider_collateral;
+    let expected_slash = deal_termination_penalty(
+        &rt.policy,
+        &proposal.provider_collateral,
+        start_epoch,
+        end_epoch,
+        termination_epoch,
+    );
     assert_eq!(&client_after.balance, &(client_before.balance - &expected_payment));
     assert!(&client_after.locked.is_zero());
     assert_eq!(
@@ -249,3 +265,22 @@ fn terminate_a_deal_then_settle_it_in_the_same_epoch() {
 
     check_state(&rt);
 }
+
+#[test]
+fn termination_penalty_is_prorated_with_a_floor() {
+    let policy = Policy::default();
+    let collateral = TokenAmount::from_atto(1000);
+    let penalty =
+        |policy: &Policy, epoch| deal_termination_penalty(policy, &collateral, 0, 1000, epoch);
+
+    // Terminating before the deal starts slashes all the collateral.
+    assert_eq!(collateral, penalty(&policy, -10));
+    assert_eq!(collateral, penalty(&policy, 0));
+    // Otherwise the penalty is in proportion to the remaining term.
+    assert_eq!(TokenAmount::from_atto(750), penalty(&policy, 250));
+    // But no less than the floor.
+    assert_eq!(TokenAmount::from_atto(100), penalty(&policy, 950));
+
+    let no_floor = Policy::default().builder().deal_termination_penalty_floor(0, 1).build();
+    assert_eq!(TokenAmount::from_atto(50), penalty(&no_floor, 950));
+}
@@ -5,8 +5,8 @@ use std::collections::BTreeMap;
 use std::{cell::RefCell, collections::HashMap, collections::HashSet};
 
 use cid::Cid;
+use fil_actors_runtime::frc46::{TransferFromParams, TransferFromReturn};
 use fil_actors_runtime::reward::{FilterEstimate, ThisEpochRewardReturn};
-use frc46_token::token::types::{TransferFromParams, TransferFromReturn};
 use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::{to_vec, RawBytes};
@@ -28,11 +28,13 @@ use fil_actor_market::ext::miner::{
     PieceChange, SectorChanges, SectorContentChangedParams, SectorContentChangedReturn,
 };
 use fil_actor_market::ext::verifreg::{AllocationID, AllocationRequest, AllocationsResponse};
+use fil_actor_market::policy::deal_termination_penalty;
 use fil_actor_market::{
-    deal_cid, deal_get_payment_remaining, BatchActivateDealsParams, BatchActivateDealsResult,
-    DealOpsByEpoch, PendingDealAllocationsMap, PendingProposalsSet, ProviderSectorsMap,
-    SectorDealsMap, SettleDealPaymentsParams, SettleDealPaymentsReturn, PENDING_ALLOCATIONS_CONFIG,
-    PENDING_PROPOSALS_CONFIG, PROVIDER_SECTORS_CONFIG, SECTOR_DEALS_CONFIG,
+    deal_cid, deal_get_payment_remaining, AmendDealParams, BatchActivateDealsParams,
+    BatchActivateDealsResult, DealOpsByEpoch, PendingDealAllocationsMap, PendingProposalsSet,
+    ProviderSectorsMap, SectorDealsMap, SettleDealPaymentsParams, SettleDealPaymentsReturn,
+    PENDING_ALLOCATIONS_CONFIG, PENDING_PROPOSALS_CONFIG, PROVIDER_SECTORS_CONFIG,
+    SECTOR_DEALS_CONFIG,
 };
 use fil_actor_market::{
     ext, ext::miner::GetControlAddressesReturnParams, next_update_epoch,
@@ -124,7 +126,7 @@ pub fn assert_deal_ops_clean(rt: &MockRuntime) {
     let st: State = rt.get_state();
 
     let mut proposal_set = HashSet::<DealID>::new();
-    let proposals = DealArray::load(&st.proposals, rt.store()).unwrap();
+    let proposals = DealArray::load(rt.store(), &st.proposals, "deal proposals").unwrap();
     proposals
         .for_each(|deal_id, _| {
             proposal_set.insert(deal_id);
@@ -514,7 +516,7 @@ pub fn get_deal_proposal(rt: &MockRuntime, deal_id: DealID) -> DealProposal {
 
 pub fn find_deal_proposal(rt: &MockRuntime, deal_id: DealID) -> Option<DealProposal> {
     let st: State = rt.get_state();
-    let deals = DealArray::load(&st.proposals, &rt.store).unwrap();
+    let deals = DealArray::load(&rt.store, &st.proposals, "deal proposals").unwrap();
     let d = deals.get(deal_id).unwrap();
     d.cloned()
 }
@@ -533,7 +535,7 @@ pub fn get_pending_deal_allocation(rt: &MockRuntime, deal_id: DealID) -> Allocat
 
 pub fn get_deal_state(rt: &MockRuntime, deal_id: DealID) -> DealState {
     let st: State = rt.get_state();
-    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
     let s = states.get(deal_id).unwrap();
     *s.unwrap()
 }
@@ -583,7 +585,7 @@ pub fn get_sector_deal_ids(
 
 pub fn update_last_updated(rt: &MockRuntime, deal_id: DealID, new_last_updated: ChainEpoch) {
     let st: State = rt.get_state();
-    let mut states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let mut states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
     let s = *states.get(deal_id).unwrap().unwrap();
 
     states.set(deal_id, DealState { last_updated_epoch: new_last_updated, ..s }).unwrap();
@@ -593,7 +595,7 @@ pub fn update_last_updated(rt: &MockRuntime, deal_id: DealID, new_last_updated:
 
 pub fn delete_deal_proposal(rt: &MockRuntime, deal_id: DealID) {
     let mut st: State = rt.get_state();
-    let mut deals = DealArray::load(&st.proposals, &rt.store).unwrap();
+    let mut deals = DealArray::load(&rt.store, &st.proposals, "deal proposals").unwrap();
     deals.delete(deal_id).unwrap();
 
     let root = deals.flush().unwrap();
@@ -622,15 +624,21 @@ pub fn cron_tick_and_assert_balances(
     // end epoch for payment calc
     let mut payment_end = d.end_epoch;
     if s.slash_epoch != EPOCH_UNDEFINED {
+        amount_slashed = deal_termination_penalty(
+            &rt.policy,
+            &d.provider_collateral,
+            d.start_epoch,
+            d.end_epoch,
+            s.slash_epoch,
+        );
         rt.expect_send_simple(
             BURNT_FUNDS_ACTOR_ADDR,
             METHOD_SEND,
             None,
-            d.provider_collateral.clone(),
+            amount_slashed.clone(),
             None,
             ExitCode::OK,
         );
-        amount_slashed = d.provider_collateral;
 
         if s.slash_epoch < d.start_epoch {
             payment_end = d.start_epoch;
@@ -944,6 +952,86 @@ pub fn publish_deals_expect_abort(
     rt.verify();
 }
 
+/// Amends a published deal's start epoch and price, authorized by the worker and the client,
+/// and returns the stored amended proposal.
+pub fn amend_deal(
+    rt: &MockRuntime,
+    addrs: &MinerAddresses,
+    deal_id: DealID,
+    start_epoch: ChainEpoch,
+    storage_price_per_epoch: TokenAmount,
+) -> DealProposal {
+    let (params, amended) =
+        expect_amend_deal(rt, addrs, deal_id, start_epoch, storage_price_per_epoch);
+    amend_deal_raw(rt, params).unwrap();
+    rt.verify();
+    amended
+}
+
+pub fn amend_deal_expect_abort(
+    rt: &MockRuntime,
+    addrs: &MinerAddresses,
+    deal_id: DealID,
+    start_epoch: ChainEpoch,
+    storage_price_per_epoch: TokenAmount,
+    expected_exit_code: ExitCode,
+) {
+    let (params, _) = expect_amend_deal(rt, addrs, deal_id, start_epoch, storage_price_per_epoch);
+    expect_abort(expected_exit_code, amend_deal_raw(rt, params));
+    rt.verify();
+}
+
+fn expect_amend_deal(
+    rt: &MockRuntime,
+    addrs: &MinerAddresses,
+    deal_id: DealID,
+    start_epoch: ChainEpoch,
+    storage_price_per_epoch: TokenAmount,
+) -> (AmendDealParams, DealProposal) {
+    let mut amended = get_deal_proposal(rt, deal_id);
+    amended.end_epoch += start_epoch - amended.start_epoch;
+    amended.start_epoch = start_epoch;
+    amended.storage_price_per_epoch = storage_price_per_epoch.clone();
+
+    rt.expect_validate_caller_any();
+    expect_provider_is_control_address(rt, addrs.provider, addrs.worker, true);
+    expect_query_network_info(rt);
+    let buf = RawBytes::serialize(&amended).unwrap();
+    rt.expect_send(
+        amended.client,
+        AUTHENTICATE_MESSAGE_METHOD,
+        IpldBlock::serialize_cbor(&AuthenticateMessageParams {
+            signature: buf.to_vec(),
+            message: buf.to_vec(),
+        })
+        .unwrap(),
+        TokenAmount::zero(),
+        None,
+        SendFlags::READ_ONLY,
+        AUTHENTICATE_MESSAGE_RESPONSE.clone(),
+        ExitCode::OK,
+        None,
+    );
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, addrs.worker);
+    let params = AmendDealParams {
+        deal_id,
+        start_epoch,
+        storage_price_per_epoch,
+        client_signature: Signature::new_bls(buf.to_vec()),
+    };
+    (params, amended)
+}
+
+pub fn amend_deal_raw(
+    rt: &MockRuntime,
+    params: AmendDealParams,
+) -> Result<Option<IpldBlock>, ActorError> {
+    rt.call::<MarketActor>(
+        Method::AmendDealExported as u64,
+        IpldBlock::serialize_cbor(&params).unwrap(),
+    )
+}
+
 pub fn settle_deal_payments(
     rt: &MockRuntime,
     caller: Address,
@@ -1034,15 +1122,21 @@ pub fn settle_deal_payments_and_assert_balances(
     // end epoch for payment calc
     let mut payment_end = d.end_epoch;
     if s.slash_epoch != EPOCH_UNDEFINED {
+        amount_slashed = deal_termination_penalty(
+            &rt.policy,
+            &d.provider_collateral,
+            d.start_epoch,
+            d.end_epoch,
+            s.slash_epoch,
+        );
         rt.expect_send_simple(
             BURNT_FUNDS_ACTOR_ADDR,
             METHOD_SEND,
             None,
-            d.provider_collateral.clone(),
+            amount_slashed.clone(),
             None,
             ExitCode::OK,
         );
-        amount_slashed = d.provider_collateral;
 
         if s.slash_epoch < d.start_epoch {
             payment_end = d.start_epoch;
@@ -1118,7 +1212,7 @@ pub fn settle_deal_payments_expect_abort(
 pub fn assert_deals_not_activated(rt: &MockRuntime, _epoch: ChainEpoch, deal_ids: &[DealID]) {
     let st: State = rt.get_state();
 
-    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
 
     for d in deal_ids {
         let opt = states.get(*d).unwrap();
@@ -1212,12 +1306,12 @@ pub fn assert_deal_deleted(
     let st: State = rt.get_state();
 
     // Check that the deal_id is not in st.proposals.
-    let deals = DealArray::load(&st.proposals, &rt.store).unwrap();
+    let deals = DealArray::load(&rt.store, &st.proposals, "deal proposals").unwrap();
     let d = deals.get(deal_id).unwrap();
     assert!(d.is_none());
 
     // Check that the deal_id is not in st.states
-    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
     let s = states.get(deal_id).unwrap();
     assert!(s.is_none());
 
@@ -1543,6 +1637,8 @@ pub fn terminate_deals_and_assert_balances(
     // payment to be refunded
     let mut payment_remaining = TokenAmount::zero();
     let mut client_unlocked = TokenAmount::zero();
+    // provider collateral, either slashed or returned
+    let mut provider_unlocked = TokenAmount::zero();
 
     let curr_epoch = *rt.epoch.borrow();
     for (s, d) in &deal_infos {
@@ -1557,7 +1653,14 @@ pub fn terminate_deals_and_assert_balances(
             total_payment += payment;
             payment_remaining += deal_get_payment_remaining(d, curr_epoch).unwrap();
             client_unlocked += &d.client_collateral;
-            total_slashed += &d.provider_collateral;
+            provider_unlocked += &d.provider_collateral;
+            total_slashed += deal_termination_penalty(
+                &rt.policy,
+                &d.provider_collateral,
+                d.start_epoch,
+                d.end_epoch,
+                curr_epoch,
+            );
         }
     }
 
@@ -1569,7 +1672,7 @@ pub fn terminate_deals_and_assert_balances(
     let updated_provider_escrow = &provider_before.balance + &total_payment - &total_slashed;
     let updated_client_locked =
         &client_before.locked - &total_payment - &payment_remaining - &client_unlocked;
-    let updated_provider_locked = &provider_before.locked - &total_slashed;
+    let updated_provider_locked = &provider_before.locked - &provider_unlocked;
 
     terminate_deals(rt, provider_addr, sectors, expected_terminations);
 
@@ -1622,7 +1725,13 @@ pub fn terminate_deals(
             let d = find_deal_proposal(rt, deal_id);
             if let Some(d) = d {
                 if curr_epoch < d.end_epoch {
-                    total_slashed += d.provider_collateral.clone();
+                    total_slashed += deal_termination_penalty(
+                        &rt.policy,
+                        &d.provider_collateral,
+                        d.start_epoch,
+                        d.end_epoch,
+                        curr_epoch,
+                    );
                 }
             }
         }
@@ -4,7 +4,7 @@
 use std::cell::RefCell;
 use std::ops::Add;
 
-use frc46_token::token::types::{TransferFromParams, TransferFromReturn};
+use fil_actors_runtime::frc46::{TransferFromParams, TransferFromReturn};
 use fvm_ipld_amt::Amt;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::{to_vec, RawBytes};
@@ -55,6 +55,27 @@ fn test_remove_all_error() {
         .expect("expected no error");
 }
 
+#[test]
+fn sweep_escrow_dust_spares_locked_balances() {
+    let rt = MockRuntime::default();
+    let store = rt.store();
+    let (dust, locked, large) = (Address::new_id(100), Address::new_id(101), Address::new_id(102));
+
+    let mut st = State::new(store).unwrap();
+    for (addr, amount) in [(dust, 3u64), (locked, 4), (large, 1000)] {
+        st.add_balance_to_escrow_table(store, &addr, &TokenAmount::from_atto(amount)).unwrap();
+    }
+    let mut locked_table = BalanceTable::from_root(store, &st.locked_table, "locked").unwrap();
+    locked_table.add(&locked, &TokenAmount::from_atto(2)).unwrap();
+    st.locked_table = locked_table.root().unwrap();
+
+    let swept = st.sweep_escrow_dust(store, &TokenAmount::from_atto(10)).unwrap();
+    assert_eq!(vec![(dust, TokenAmount::from_atto(3))], swept);
+    let escrow_table = BalanceTable::from_root(store, &st.escrow_table, "escrow").unwrap();
+    assert!(escrow_table.get(&dust).unwrap().is_zero());
+    assert_eq!(TokenAmount::from_atto(1004), escrow_table.total().unwrap());
+}
+
 #[test]
 fn simple_construction() {
     let rt = MockRuntime {
@@ -410,6 +431,26 @@ fn adds_to_non_provider_funds() {
     }
 }
 
+#[test]
+fn get_balance_of_address_without_actor_is_zero() {
+    let rt = setup();
+    let unknown = Address::new_secp256k1(&[7; 65]).unwrap();
+    let ret = get_balance(&rt, &unknown);
+    assert_eq!(TokenAmount::zero(), ret.balance);
+    assert_eq!(TokenAmount::zero(), ret.locked);
+
+    // The address reports its escrow once an actor exists for it and it is funded.
+    rt.add_id_address(unknown, Address::new_id(5000));
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(5000));
+    rt.set_received(TokenAmount::from_atto(10));
+    rt.expect_validate_caller_any();
+    rt.call::<MarketActor>(Method::AddBalance as u64, IpldBlock::serialize_cbor(&unknown).unwrap())
+        .unwrap();
+    rt.verify();
+    assert_eq!(TokenAmount::from_atto(10), get_balance(&rt, &unknown).balance);
+    check_state(&rt);
+}
+
 #[test]
 fn withdraws_from_provider_escrow_funds_and_sends_to_owner() {
     let rt = setup();
@@ -756,11 +797,15 @@ fn deal_expires() {
 
     // No deal state for unactivated deal
     let st: State = rt.get_state();
-    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
     assert!(states.get(deal_id).unwrap().is_none());
 
     // The proposal is gone
-    assert!(DealArray::load(&st.proposals, &rt.store).unwrap().get(deal_id).unwrap().is_none());
+    assert!(DealArray::load(&rt.store, &st.proposals, "deal proposals")
+        .unwrap()
+        .get(deal_id)
+        .unwrap()
+        .is_none());
 
     // Pending allocation ID is gone
     let pending_allocs = PendingDealAllocationsMap::load(
@@ -1638,7 +1683,7 @@ fn fail_to_activate_all_deals_if_one_deal_fails() {
     // no state for deal2 means deal2 activation has failed
     let st: State = rt.get_state();
 
-    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    let states = DealMetaArray::load(&rt.store, &st.states, "deal states").unwrap();
 
     let s = states.get(deal_id2).unwrap();
     assert!(s.is_none());
@@ -6,6 +6,7 @@ mod harness;
 
 use std::collections::BTreeMap;
 
+use fil_actor_market::policy::deal_termination_penalty;
 use fil_actor_market::{
     Actor as MarketActor, DealQueryParams, DealSettlementSummary, Method, State, EX_DEAL_EXPIRED,
 };
@@ -88,7 +89,13 @@ fn deal_scheduled_for_termination_cannot_be_settled_manually() {
         BURNT_FUNDS_ACTOR_ADDR,
         0,
         None,
-        slashed_prop.provider_collateral.clone(),
+        deal_termination_penalty(
+            &rt.policy,
+            &slashed_prop.provider_collateral,
+            slashed_prop.start_epoch,
+            slashed_prop.end_epoch,
+            slashed_epoch,
+        ),
         None,
         ExitCode::OK,
     );
@@ -369,3 +369,37 @@ fn fail_when_the_same_deal_id_is_passed_multiple_times() {
     rt.verify();
     check_state(&rt);
 }
+
+#[test]
+fn deal_ids_are_checked_in_order() {
+    let rt = setup();
+    let (deal_id, _) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &MINER_ADDRESSES, START_EPOCH, END_EPOCH);
+    let missing = deal_id + 1;
+
+    // The first failing deal ID determines the error, whether missing or duplicated.
+    for (deal_ids, exit_code) in [
+        (vec![missing, deal_id, deal_id], ExitCode::USR_NOT_FOUND),
+        (vec![deal_id, deal_id, missing], ExitCode::USR_ILLEGAL_ARGUMENT),
+    ] {
+        rt.set_caller(*MINER_ACTOR_CODE_ID, PROVIDER_ADDR);
+        rt.expect_validate_caller_type(vec![Type::Miner]);
+        let params = VerifyDealsForActivationParams {
+            sectors: vec![SectorDeals {
+                sector_number: 7,
+                sector_type: RegisteredSealProof::StackedDRG8MiBV1,
+                sector_expiry: SECTOR_EXPIRY,
+                deal_ids,
+            }],
+        };
+        expect_abort(
+            exit_code,
+            rt.call::<MarketActor>(
+                Method::VerifyDealsForActivation as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            ),
+        );
+        rt.verify();
+    }
+    check_state(&rt);
+}
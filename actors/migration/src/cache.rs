@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use cid::Cid;
+use fvm_shared::address::Address;
+
+use crate::ActorMigrationOutput;
+
+/// Identifies an actor's prior state, whose migration result may be reused.
+pub type MigrationCacheKey = (Address, Cid);
+
+/// Stores the results of actor migrations, so that a migration run ahead of the upgrade epoch
+/// against an earlier state root can be reused for actors whose state hasn't since changed.
+pub trait MigrationCache: Send + Sync {
+    fn get(&self, key: &MigrationCacheKey) -> Option<ActorMigrationOutput>;
+    fn put(&self, key: MigrationCacheKey, output: ActorMigrationOutput);
+}
+
+/// A cache which stores nothing.
+pub struct NoMigrationCache;
+
+impl MigrationCache for NoMigrationCache {
+    fn get(&self, _key: &MigrationCacheKey) -> Option<ActorMigrationOutput> {
+        None
+    }
+
+    fn put(&self, _key: MigrationCacheKey, _output: ActorMigrationOutput) {}
+}
+
+/// A cache held in memory.
+#[derive(Default)]
+pub struct MemoryMigrationCache {
+    entries: Mutex<HashMap<MigrationCacheKey, ActorMigrationOutput>>,
+}
+
+impl MemoryMigrationCache {
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl MigrationCache for MemoryMigrationCache {
+    fn get(&self, key: &MigrationCacheKey) -> Option<ActorMigrationOutput> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn put(&self, key: MigrationCacheKey, output: ActorMigrationOutput) {
+        self.entries.lock().unwrap().insert(key, output);
+    }
+}
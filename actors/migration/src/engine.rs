@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::mpsc::{sync_channel, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use cid::Cid;
+use fil_actors_runtime::{actor_error, Map2, DEFAULT_HAMT_CONFIG};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use log::info;
+use vm_api::ActorState;
+
+use crate::progress::Progress;
+use crate::{
+    ActorMigration, ActorMigrationInput, MigrationCache, MigrationObserver, NoMigrationCache,
+};
+
+/// Migrations for each code CID in the prior state tree.
+pub type Migrations<BS> = HashMap<Cid, Box<dyn ActorMigration<BS>>>;
+
+type ActorsMap<'bs, BS> = Map2<&'bs BS, Address, ActorState>;
+
+/// Parameters of the migration engine.
+#[derive(Clone)]
+pub struct MigrationConfig {
+    /// Number of worker threads migrating actors in parallel.
+    pub max_workers: usize,
+    /// Number of actors read from the prior tree ahead of the workers.
+    pub job_queue_size: usize,
+    /// Number of migrated actors buffered before being written to the new tree.
+    pub result_queue_size: usize,
+    /// How often progress is logged and reported to the observer.
+    pub progress_log_period: Duration,
+    /// The expected number of actors, used to estimate the time remaining before the prior
+    /// tree has been read in full.
+    pub expected_actors: Option<u64>,
+    /// Receives progress reports.
+    pub observer: Option<Arc<dyn MigrationObserver>>,
+}
+
+impl fmt::Debug for MigrationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MigrationConfig")
+            .field("max_workers", &self.max_workers)
+            .field("job_queue_size", &self.job_queue_size)
+            .field("result_queue_size", &self.result_queue_size)
+            .field("progress_log_period", &self.progress_log_period)
+            .field("expected_actors", &self.expected_actors)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        MigrationConfig {
+            max_workers: thread::available_parallelism().map_or(1, |n| n.get()),
+            job_queue_size: 1000,
+            result_queue_size: 100,
+            progress_log_period: Duration::from_secs(10),
+            expected_actors: None,
+            observer: None,
+        }
+    }
+}
+
+struct MigrationJob<'m, BS> {
+    address: Address,
+    actor: ActorState,
+    migration: &'m dyn ActorMigration<BS>,
+}
+
+impl<BS: Blockstore> MigrationJob<'_, BS> {
+    fn run(
+        self,
+        store: &BS,
+        prior_epoch: ChainEpoch,
+        cache: &dyn MigrationCache,
+        progress: &Progress,
+    ) -> anyhow::Result<(Address, ActorState)> {
+        let start = Instant::now();
+        let key = (self.address, self.actor.state);
+        let cacheable = self.migration.is_cacheable();
+        let cached = cacheable.then(|| cache.get(&key)).flatten();
+        let from_cache = cached.is_some();
+        let output = match cached {
+            Some(output) => output,
+            None => {
+                let input = ActorMigrationInput {
+                    address: self.address,
+                    balance: self.actor.balance.clone(),
+                    head: self.actor.state,
+                    prior_epoch,
+                };
+                let output = self.migration.migrate_state(store, input).map_err(|e| {
+                    e.context(format!(
+                        "failed to migrate actor {} with code {}",
+                        self.address, self.actor.code
+                    ))
+                })?;
+                if cacheable {
+                    cache.put(key, output.clone());
+                }
+                output
+            }
+        };
+        progress.job_done(self.actor.code, start.elapsed(), from_cache);
+        let actor = ActorState { code: output.new_code_cid, state: output.new_head, ..self.actor };
+        Ok((self.address, actor))
+    }
+}
+
+/// Migrates every actor in the state tree rooted at `actors_root`, writing the migrated
+/// actors to a new tree and returning its root.
+/// Every actor's code CID must have a migration.
+pub fn migrate_state_tree<BS: Blockstore + Sync>(
+    store: &BS,
+    actors_root: &Cid,
+    migrations: &Migrations<BS>,
+    prior_epoch: ChainEpoch,
+    config: &MigrationConfig,
+) -> anyhow::Result<Cid> {
+    migrate_state_tree_cached(
+        store,
+        actors_root,
+        migrations,
+        prior_epoch,
+        config,
+        &NoMigrationCache,
+    )
+}
+
+/// Migrates a state tree as [`migrate_state_tree`], reusing the results of cacheable migrations
+/// from `cache` for actors with unchanged state, and caching new results.
+///
+/// Running the migration against an earlier state root, ahead of the upgrade epoch, fills the
+/// cache so that only actors changed since need migrating at the upgrade.
+/// Deferred migrations are never cached.
+pub fn migrate_state_tree_cached<BS: Blockstore + Sync>(
+    store: &BS,
+    actors_root: &Cid,
+    migrations: &Migrations<BS>,
+    prior_epoch: ChainEpoch,
+    config: &MigrationConfig,
+    cache: &dyn MigrationCache,
+) -> anyhow::Result<Cid> {
+    let progress = Progress::new(config.expected_actors);
+    let mut actors_out = ActorsMap::empty(store, DEFAULT_HAMT_CONFIG, "actors");
+
+    let deferred = thread::scope(|s| -> anyhow::Result<Vec<MigrationJob<BS>>> {
+        let (job_tx, job_rx) = sync_channel::<MigrationJob<BS>>(config.job_queue_size);
+        let (result_tx, result_rx) = sync_channel(config.result_queue_size);
+        let progress = &progress;
+
+        // Read the prior tree, queueing jobs for the workers and setting aside deferred ones.
+        let producer = s.spawn(move || -> anyhow::Result<Vec<MigrationJob<BS>>> {
+            let actors_in = ActorsMap::load(store, actors_root, DEFAULT_HAMT_CONFIG, "actors")?;
+            let mut deferred = vec![];
+            let mut aborted = false;
+            let res = actors_in.for_each(|address, actor| {
+                let migration = migrations.get(&actor.code).ok_or_else(|| {
+                    actor_error!(illegal_state; "no migration for actor {} with code {}",
+                        address, actor.code)
+                })?;
+                let job = MigrationJob { address, actor: actor.clone(), migration: &**migration };
+                progress.job_created();
+                if migration.is_deferred() {
+                    deferred.push(job);
+                    return Ok(());
+                }
+                job_tx.send(job).map_err(|_| {
+                    aborted = true;
+                    actor_error!(illegal_state; "migration aborted")
+                })
+            });
+            progress.producer_done();
+            match res {
+                // Sending fails only after a worker's failure, which the writer reports.
+                Err(_) if aborted => Ok(deferred),
+                Err(e) => Err(e.into()),
+                Ok(()) => Ok(deferred),
+            }
+        });
+
+        let job_rx = Arc::new(Mutex::new(job_rx));
+        for _ in 0..config.max_workers.max(1) {
+            let job_rx = Arc::clone(&job_rx);
+            let result_tx = result_tx.clone();
+            s.spawn(move || loop {
+                let Ok(job) = job_rx.lock().unwrap().recv() else { break };
+                let result = job.run(store, prior_epoch, cache, progress);
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(result_tx);
+
+        write_results(&mut actors_out, result_rx, progress, config)?;
+        producer.join().map_err(|_| anyhow!("migration producer panicked"))?
+    })?;
+
+    info!("running {} deferred migrations", deferred.len());
+    for job in deferred {
+        let (address, actor) = job.run(store, prior_epoch, &NoMigrationCache, &progress)?;
+        actors_out.set(&address, actor)?;
+    }
+
+    let root = actors_out.flush()?;
+    let snapshot = progress.snapshot(true);
+    info!(
+        "migrated {} actors ({} cached) in {:.1?}",
+        snapshot.actors_done, snapshot.actors_cached, snapshot.elapsed
+    );
+    if let Some(observer) = &config.observer {
+        observer.on_progress(&snapshot);
+    }
+    Ok(root)
+}
+
+/// Writes migrated actors to the new tree until the workers are done,
+/// reporting progress periodically.
+/// Returning drops the receiver, which stops the workers if migration failed.
+fn write_results<BS: Blockstore>(
+    actors_out: &mut ActorsMap<BS>,
+    results: Receiver<anyhow::Result<(Address, ActorState)>>,
+    progress: &Progress,
+    config: &MigrationConfig,
+) -> anyhow::Result<()> {
+    let period = config.progress_log_period;
+    let mut last_report = Instant::now();
+    loop {
+        match results.recv_timeout(period) {
+            Ok(result) => {
+                let (address, actor) = result?;
+                actors_out.set(&address, actor)?;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        if last_report.elapsed() >= period {
+            let snapshot = progress.snapshot(false);
+            info!(
+                "migration in progress: {} of {} actors done ({:.1}/s), {:.0?} remaining",
+                snapshot.actors_done,
+                snapshot.actors_total.map_or("?".to_string(), |n| n.to_string()),
+                snapshot.actors_per_second,
+                snapshot.estimated_remaining.unwrap_or_default(),
+            );
+            if let Some(observer) = &config.observer {
+                observer.on_progress(&snapshot);
+            }
+            last_report = Instant::now();
+        }
+    }
+}
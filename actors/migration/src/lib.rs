@@ -0,0 +1,81 @@
+//! A state migration engine for network version upgrades, modeled on the Go implementation in
+//! go-state-types.
+//!
+//! A migration maps each actor code CID in the prior state tree to an [`ActorMigration`] that
+//! produces the actor's new code and state. The engine migrates actors in parallel on worker
+//! threads, then runs any deferred migrations, and writes the results to a new state tree.
+//! A migrated tree may be checked against its prior tree with [`verify_migration`].
+
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+
+pub use cache::*;
+pub use engine::*;
+pub use progress::*;
+pub use verify::*;
+
+mod cache;
+mod engine;
+pub mod nv17;
+mod progress;
+mod verify;
+
+/// The prior state of an actor to be migrated.
+#[derive(Clone, Debug)]
+pub struct ActorMigrationInput {
+    pub address: Address,
+    pub balance: TokenAmount,
+    /// The actor's state root in the prior tree.
+    pub head: Cid,
+    /// The last epoch before the upgrade.
+    pub prior_epoch: ChainEpoch,
+}
+
+/// The migrated code and state of an actor.
+/// The actor's balance, sequence and delegated address are carried over unchanged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActorMigrationOutput {
+    pub new_code_cid: Cid,
+    pub new_head: Cid,
+}
+
+/// Migrates the state of all actors with one code CID.
+pub trait ActorMigration<BS: Blockstore>: Send + Sync {
+    fn migrate_state(
+        &self,
+        store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput>;
+
+    /// Whether the migration must wait for all non-deferred migrations to complete,
+    /// e.g. because it consumes information they gather.
+    /// Deferred migrations run one at a time, after the parallel migrations.
+    fn is_deferred(&self) -> bool {
+        false
+    }
+
+    /// Whether the migration's output depends only on the actor's address and prior state,
+    /// so may be cached and reused for a later state tree.
+    fn is_cacheable(&self) -> bool {
+        true
+    }
+}
+
+/// A migration that replaces an actor's code without changing its state.
+#[derive(Clone, Debug)]
+pub struct CodeMigrator {
+    pub new_code_cid: Cid,
+}
+
+impl<BS: Blockstore> ActorMigration<BS> for CodeMigrator {
+    fn migrate_state(
+        &self,
+        _store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head: input.head })
+    }
+}
@@ -0,0 +1,353 @@
+//! The network version 17 migration, which introduced the datacap token actor.
+//!
+//! Verified clients' datacap moves from the verified registry to token balances in the new
+//! datacap actor, and each pending verified deal becomes a verified registry allocation,
+//! backed by datacap tokens held by the registry.
+//! The market records the allocation for each pending deal, and each deal state gains a
+//! (not yet made) verified claim.
+//!
+//! Market states are written in their v9 format; later formats are the concern of later
+//! migrations.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use cid::Cid;
+use fil_actor_datacap::INFINITE_ALLOWANCE;
+use fil_actor_market::{
+    DealArray, DealProposal, PendingDealAllocationsMap, PENDING_ALLOCATIONS_CONFIG,
+    STATES_AMT_BITWIDTH,
+};
+use fil_actor_verifreg::state::{DataCapMap, DATACAP_MAP_CONFIG};
+use fil_actor_verifreg::{AllocationID, DataCap};
+use fil_actors_runtime::frc46::TOKEN_PRECISION;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::{
+    actor_error, Array2, Map2, DATACAP_TOKEN_ACTOR_ADDR, DEFAULT_HAMT_CONFIG,
+    STORAGE_MARKET_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::deal::DealID;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::ActorID;
+use multihash_codetable::Code;
+use num_traits::Zero;
+use vm_api::ActorState;
+
+use crate::{
+    migrate_state_tree, ActorMigration, ActorMigrationInput, ActorMigrationOutput, MigrationConfig,
+    Migrations,
+};
+
+/// Verified registry state before nv17, which tracked verified clients' datacap.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug)]
+pub struct VerifregStateV8 {
+    pub root_key: Address,
+    pub verifiers: Cid,        // HAMT[Address]DataCap
+    pub verified_clients: Cid, // HAMT[Address]DataCap
+    pub remove_data_cap_proposal_ids: Cid,
+}
+
+/// Market state before nv17.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug)]
+pub struct MarketStateV8 {
+    pub proposals: Cid,
+    pub states: Cid,
+    pub pending_proposals: Cid,
+    pub escrow_table: Cid,
+    pub locked_table: Cid,
+    pub next_id: DealID,
+    pub deal_ops_by_epoch: Cid,
+    pub last_cron: ChainEpoch,
+    pub total_client_locked_collateral: TokenAmount,
+    pub total_provider_locked_collateral: TokenAmount,
+    pub total_client_storage_fee: TokenAmount,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DealStateV8 {
+    pub sector_start_epoch: ChainEpoch,
+    pub last_updated_epoch: ChainEpoch,
+    pub slash_epoch: ChainEpoch,
+}
+
+/// Market state after nv17, adding the allocations for pending deals.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug)]
+pub struct MarketStateV9 {
+    pub proposals: Cid,
+    pub states: Cid,
+    pub pending_proposals: Cid,
+    pub escrow_table: Cid,
+    pub locked_table: Cid,
+    pub next_id: DealID,
+    pub deal_ops_by_epoch: Cid,
+    pub last_cron: ChainEpoch,
+    pub total_client_locked_collateral: TokenAmount,
+    pub total_provider_locked_collateral: TokenAmount,
+    pub total_client_storage_fee: TokenAmount,
+    pub pending_deal_allocation_ids: Cid, // HAMT[DealID]AllocationID
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DealStateV9 {
+    pub sector_start_epoch: ChainEpoch,
+    pub last_updated_epoch: ChainEpoch,
+    pub slash_epoch: ChainEpoch,
+    // 0 if the deal's allocation is not yet claimed.
+    pub verified_claim: AllocationID,
+}
+
+/// The new code CIDs of the actors whose state nv17 changes.
+#[derive(Clone, Debug)]
+pub struct Nv17Codes {
+    pub market: Cid,
+    pub verifreg: Cid,
+    pub datacap: Cid,
+}
+
+/// An allocation made for a verified deal which is published but not yet activated.
+#[derive(Clone, Debug)]
+struct PendingAllocation {
+    deal_id: DealID,
+    allocation_id: AllocationID,
+    allocation: fil_actor_verifreg::Allocation,
+}
+
+/// Makes an allocation for each pending verified deal, assigning IDs in deal order.
+fn pending_allocations<BS: Blockstore>(
+    store: &BS,
+    market: &MarketStateV8,
+    policy: &Policy,
+) -> anyhow::Result<Vec<PendingAllocation>> {
+    let proposals = DealArray::load(store, &market.proposals, "proposals")?;
+    let states = Array2::<_, DealStateV8>::load(store, &market.states, "deal states")?;
+    let mut pending = vec![];
+    proposals.for_each(|deal_id, proposal: &DealProposal| {
+        if !proposal.verified_deal || states.get(deal_id)?.is_some() {
+            return Ok(());
+        }
+        pending.push((deal_id, proposal.clone()));
+        Ok(())
+    })?;
+
+    pending
+        .into_iter()
+        .zip(1..)
+        .map(|((deal_id, proposal), allocation_id)| {
+            let (client, provider) = match (proposal.client.id(), proposal.provider.id()) {
+                (Ok(client), Ok(provider)) => (client, provider),
+                _ => return Err(anyhow!("deal {} parties are not ID addresses", deal_id)),
+            };
+            let term_min = proposal.duration();
+            let allocation = fil_actor_verifreg::Allocation {
+                client,
+                provider,
+                data: proposal.piece_cid,
+                size: proposal.piece_size,
+                term_min,
+                term_max: (term_min + policy.market_default_allocation_term_buffer)
+                    .min(policy.maximum_verified_allocation_term),
+                expiration: proposal.start_epoch,
+            };
+            Ok(PendingAllocation { deal_id, allocation_id, allocation })
+        })
+        .collect()
+}
+
+struct VerifregMigrator {
+    new_code_cid: Cid,
+    allocations: Arc<Vec<PendingAllocation>>,
+}
+
+impl<BS: Blockstore> ActorMigration<BS> for VerifregMigrator {
+    fn migrate_state(
+        &self,
+        store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        let old: VerifregStateV8 =
+            store.get_cbor(&input.head)?.context("missing verified registry state")?;
+        let mut st = fil_actor_verifreg::State::new(store, old.root_key)?;
+        st.verifiers = old.verifiers;
+        st.remove_data_cap_proposal_ids = old.remove_data_cap_proposal_ids;
+
+        let mut by_client: BTreeMap<ActorID, Vec<_>> = BTreeMap::new();
+        for a in self.allocations.iter() {
+            by_client.entry(a.allocation.client).or_default().push(a);
+        }
+        let mut allocs = st.load_allocs(store)?;
+        for (client, client_allocs) in by_client {
+            allocs.put_many(
+                client,
+                client_allocs.into_iter().map(|a| (a.allocation_id, a.allocation.clone())),
+            )?;
+        }
+        st.save_allocs(&mut allocs)?;
+        st.next_allocation_id = self.allocations.len() as u64 + 1;
+
+        let new_head = store.put_cbor(&st, Code::Blake2b256)?;
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head })
+    }
+
+    // The allocations depend on the market's state.
+    fn is_cacheable(&self) -> bool {
+        false
+    }
+}
+
+struct MarketMigrator {
+    new_code_cid: Cid,
+    allocations: Arc<Vec<PendingAllocation>>,
+}
+
+impl<BS: Blockstore> ActorMigration<BS> for MarketMigrator {
+    fn migrate_state(
+        &self,
+        store: &BS,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        let old: MarketStateV8 = store.get_cbor(&input.head)?.context("missing market state")?;
+
+        let old_states = Array2::<_, DealStateV8>::load(store, &old.states, "deal states")?;
+        let mut states = Array2::<_, DealStateV9>::empty(store, STATES_AMT_BITWIDTH, "deal states");
+        old_states.for_each(|deal_id, s| {
+            states.set(
+                deal_id,
+                DealStateV9 {
+                    sector_start_epoch: s.sector_start_epoch,
+                    last_updated_epoch: s.last_updated_epoch,
+                    slash_epoch: s.slash_epoch,
+                    verified_claim: 0,
+                },
+            )
+        })?;
+
+        let mut pending_allocation_ids = PendingDealAllocationsMap::empty(
+            store,
+            PENDING_ALLOCATIONS_CONFIG,
+            "pending deal allocations",
+        );
+        for a in self.allocations.iter() {
+            pending_allocation_ids.set(&a.deal_id, a.allocation_id)?;
+        }
+
+        let st = MarketStateV9 {
+            proposals: old.proposals,
+            states: states.flush()?,
+            pending_proposals: old.pending_proposals,
+            escrow_table: old.escrow_table,
+            locked_table: old.locked_table,
+            next_id: old.next_id,
+            deal_ops_by_epoch: old.deal_ops_by_epoch,
+            last_cron: old.last_cron,
+            total_client_locked_collateral: old.total_client_locked_collateral,
+            total_provider_locked_collateral: old.total_provider_locked_collateral,
+            total_client_storage_fee: old.total_client_storage_fee,
+            pending_deal_allocation_ids: pending_allocation_ids.flush()?,
+        };
+        let new_head = store.put_cbor(&st, Code::Blake2b256)?;
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head })
+    }
+}
+
+/// Creates the datacap token state, with a balance for each verified client's remaining datacap
+/// and for the verified registry's datacap backing pending allocations.
+/// Each client authorises the market to spend its tokens, as it did its datacap.
+fn datacap_state<BS: Blockstore>(
+    store: &BS,
+    verifreg: &VerifregStateV8,
+    allocations: &[PendingAllocation],
+) -> anyhow::Result<Cid> {
+    let mut st = fil_actor_datacap::State::new(store, VERIFIED_REGISTRY_ACTOR_ADDR)?;
+    let market_id = STORAGE_MARKET_ACTOR_ADDR.id()?;
+    let mut supply = TokenAmount::zero();
+
+    let clients = DataCapMap::load(
+        store,
+        &verifreg.verified_clients,
+        DATACAP_MAP_CONFIG,
+        "verified clients",
+    )?;
+    let mut balances: Vec<(ActorID, DataCap)> = vec![];
+    clients.for_each(|client, cap| {
+        let id = client.id().map_err(
+            |_| actor_error!(illegal_state; "verified client {} is not an ID address", client),
+        )?;
+        balances.push((id, cap.0.clone()));
+        Ok(())
+    })?;
+    for (client, cap) in balances {
+        let balance = TokenAmount::from_atto(cap) * TOKEN_PRECISION;
+        st.token.set_balance(store, client, &balance)?;
+        st.token.set_allowance(store, client, market_id, &INFINITE_ALLOWANCE)?;
+        supply += balance;
+    }
+
+    let allocated: u64 = allocations.iter().map(|a| a.allocation.size.0).sum();
+    let registry_balance = TokenAmount::from_atto(allocated) * TOKEN_PRECISION;
+    if !registry_balance.is_zero() {
+        st.token.set_balance(store, VERIFIED_REGISTRY_ACTOR_ADDR.id()?, &registry_balance)?;
+    }
+    supply += registry_balance;
+    st.token.change_supply_by(&supply)?;
+
+    store.put_cbor(&st, Code::Blake2b256)
+}
+
+/// Migrates the state tree rooted at `actors_root` across nv17, creating the datacap actor.
+/// `migrations` provides the migrations of all other actors.
+pub fn migrate_nv17<BS: Blockstore + Sync>(
+    store: &BS,
+    actors_root: &Cid,
+    codes: &Nv17Codes,
+    mut migrations: Migrations<BS>,
+    prior_epoch: ChainEpoch,
+    config: &MigrationConfig,
+) -> anyhow::Result<Cid> {
+    let policy = Policy::default();
+    let actors =
+        Map2::<_, Address, ActorState>::load(store, actors_root, DEFAULT_HAMT_CONFIG, "actors")?;
+    let market_actor =
+        actors.get(&STORAGE_MARKET_ACTOR_ADDR)?.context("missing market actor")?.clone();
+    let verifreg_actor =
+        actors.get(&VERIFIED_REGISTRY_ACTOR_ADDR)?.context("missing verified registry actor")?;
+    if actors.get(&DATACAP_TOKEN_ACTOR_ADDR)?.is_some() {
+        return Err(anyhow!("datacap actor already exists"));
+    }
+
+    let market: MarketStateV8 =
+        store.get_cbor(&market_actor.state)?.context("missing market state")?;
+    let verifreg: VerifregStateV8 =
+        store.get_cbor(&verifreg_actor.state)?.context("missing verified registry state")?;
+    let allocations = Arc::new(pending_allocations(store, &market, &policy)?);
+    let datacap_head = datacap_state(store, &verifreg, &allocations)?;
+
+    migrations.insert(
+        market_actor.code,
+        Box::new(MarketMigrator { new_code_cid: codes.market, allocations: allocations.clone() }),
+    );
+    migrations.insert(
+        verifreg_actor.code,
+        Box::new(VerifregMigrator { new_code_cid: codes.verifreg, allocations }),
+    );
+    let root = migrate_state_tree(store, actors_root, &migrations, prior_epoch, config)?;
+
+    let mut actors =
+        Map2::<_, Address, ActorState>::load(store, &root, DEFAULT_HAMT_CONFIG, "actors")?;
+    actors.set(
+        &DATACAP_TOKEN_ACTOR_ADDR,
+        ActorState {
+            code: codes.datacap,
+            state: datacap_head,
+            sequence: 0,
+            balance: TokenAmount::zero(),
+            delegated_address: None,
+        },
+    )?;
+    Ok(actors.flush()?)
+}
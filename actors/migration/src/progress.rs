@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use cid::Cid;
+
+/// Number of buckets in a [`TimingHistogram`].
+pub const TIMING_BUCKETS: usize = 32;
+
+/// A distribution of actor migration times.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimingHistogram {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+    /// Counts of migrations by duration.
+    /// Bucket 0 counts those under 1µs, and bucket `i` those from 2^(i-1)µs up to 2^iµs,
+    /// with the last bucket counting all longer migrations.
+    pub buckets: [u64; TIMING_BUCKETS],
+}
+
+impl TimingHistogram {
+    pub fn record(&mut self, elapsed: Duration) {
+        let micros = elapsed.as_micros();
+        let bucket = (u128::BITS - micros.leading_zeros()) as usize;
+        self.buckets[bucket.min(TIMING_BUCKETS - 1)] += 1;
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        self.total.div_f64(self.count as f64)
+    }
+}
+
+/// A snapshot of a migration's progress.
+#[derive(Clone, Debug)]
+pub struct MigrationProgress {
+    /// Number of actors migrated, including those whose result was cached.
+    pub actors_done: u64,
+    /// Number of actors whose migration result was taken from the cache.
+    pub actors_cached: u64,
+    /// Number of actors to migrate, once the prior tree has been read in full,
+    /// or else as configured by [`MigrationConfig::expected_actors`](crate::MigrationConfig).
+    pub actors_total: Option<u64>,
+    pub elapsed: Duration,
+    pub actors_per_second: f64,
+    /// Estimated time until all actors are migrated, at the rate so far.
+    pub estimated_remaining: Option<Duration>,
+    /// Distribution of migration times, by actor code CID in the prior tree.
+    pub timings: BTreeMap<Cid, TimingHistogram>,
+    /// Whether the migration has finished.
+    pub complete: bool,
+}
+
+/// Receives reports of a migration's progress, periodically and once when it completes.
+pub trait MigrationObserver: Send + Sync {
+    fn on_progress(&self, progress: &MigrationProgress);
+}
+
+impl<F: Fn(&MigrationProgress) + Send + Sync> MigrationObserver for F {
+    fn on_progress(&self, progress: &MigrationProgress) {
+        self(progress)
+    }
+}
+
+/// Tracks progress, shared between the migration's threads.
+pub(crate) struct Progress {
+    start: Instant,
+    expected_actors: Option<u64>,
+    actors_created: AtomicU64,
+    actors_done: AtomicU64,
+    actors_cached: AtomicU64,
+    producer_done: AtomicBool,
+    timings: Mutex<BTreeMap<Cid, TimingHistogram>>,
+}
+
+impl Progress {
+    pub fn new(expected_actors: Option<u64>) -> Self {
+        Progress {
+            start: Instant::now(),
+            expected_actors,
+            actors_created: AtomicU64::new(0),
+            actors_done: AtomicU64::new(0),
+            actors_cached: AtomicU64::new(0),
+            producer_done: AtomicBool::new(false),
+            timings: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn job_created(&self) {
+        self.actors_created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn producer_done(&self) {
+        self.producer_done.store(true, Ordering::Relaxed);
+    }
+
+    pub fn job_done(&self, code: Cid, elapsed: Duration, cached: bool) {
+        self.actors_done.fetch_add(1, Ordering::Relaxed);
+        if cached {
+            self.actors_cached.fetch_add(1, Ordering::Relaxed);
+        }
+        self.timings.lock().unwrap().entry(code).or_default().record(elapsed);
+    }
+
+    pub fn snapshot(&self, complete: bool) -> MigrationProgress {
+        let elapsed = self.start.elapsed();
+        let done = self.actors_done.load(Ordering::Relaxed);
+        let total = if self.producer_done.load(Ordering::Relaxed) {
+            Some(self.actors_created.load(Ordering::Relaxed))
+        } else {
+            self.expected_actors.map(|n| n.max(done))
+        };
+        let actors_per_second = done as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        let estimated_remaining = match total {
+            _ if complete => Some(Duration::ZERO),
+            Some(total) if actors_per_second > 0.0 => {
+                Some(Duration::from_secs_f64(total.saturating_sub(done) as f64 / actors_per_second))
+            }
+            _ => None,
+        };
+        MigrationProgress {
+            actors_done: done,
+            actors_cached: self.actors_cached.load(Ordering::Relaxed),
+            actors_total: total,
+            elapsed,
+            actors_per_second,
+            estimated_remaining,
+            timings: self.timings.lock().unwrap().clone(),
+            complete,
+        }
+    }
+}
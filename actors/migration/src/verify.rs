@@ -0,0 +1,164 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::anyhow;
+use cid::Cid;
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_market::{DealArray, State as MarketState};
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::{
+    Change, Map2, MessageAccumulator, DATACAP_TOKEN_ACTOR_ADDR, DEFAULT_HAMT_CONFIG,
+    STORAGE_MARKET_ACTOR_ADDR,
+};
+use fil_builtin_actors_state::check::check_state_invariants;
+use fil_builtin_actors_state::inspect::diff_trees;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use vm_api::ActorState;
+
+/// The outcome of checking a migrated state tree against its prior tree.
+#[derive(Clone, Debug, Default)]
+pub struct MigrationReport {
+    /// Number of actors in the prior tree.
+    pub prior_actors: usize,
+    /// Actors in the new tree which weren't in the prior tree.
+    pub added_actors: Vec<Address>,
+    /// Violations of the new tree's state invariants.
+    pub invariant_violations: Vec<String>,
+    /// Failed checks of the migration itself.
+    pub migration_failures: Vec<String>,
+}
+
+impl MigrationReport {
+    pub fn is_ok(&self) -> bool {
+        self.invariant_violations.is_empty() && self.migration_failures.is_empty()
+    }
+}
+
+/// Checks the state tree at `new_root` migrated from the tree at `old_root`.
+///
+/// The new tree must satisfy the state invariants given its `manifest`, and:
+/// - every prior actor is present, with its code remapped by `code_map`, and its balance and
+///   sequence unchanged;
+/// - the total balance is unchanged;
+/// - the market holds the same number of deals;
+/// - the datacap token supply is unchanged, if the prior tree has a datacap actor.
+///
+/// Prior states are read in the same formats as new states, so the market and datacap checks
+/// apply only to migrations which preserve those actors' state formats.
+pub fn verify_migration<BS: Blockstore>(
+    store: &BS,
+    old_root: &Cid,
+    new_root: &Cid,
+    code_map: &HashMap<Cid, Cid>,
+    manifest: &BTreeMap<Cid, Type>,
+    policy: &Policy,
+    prior_epoch: ChainEpoch,
+) -> anyhow::Result<MigrationReport> {
+    let old_tree = load_tree(store, old_root)?;
+    let new_tree = load_tree(store, new_root)?;
+    let acc = MessageAccumulator::default();
+
+    let check_actor = |address: &Address, old: &ActorState, new: &ActorState| {
+        let acc = acc.with_prefix(format!("{address} "));
+        match code_map.get(&old.code) {
+            Some(code) => acc.require(
+                new.code == *code,
+                format!("code {} migrated to {}, expected {}", old.code, new.code, code),
+            ),
+            None => acc.add(format!("no new code for prior code {}", old.code)),
+        }
+        acc.require(
+            new.balance == old.balance,
+            format!("balance changed from {} to {}", old.balance, new.balance),
+        );
+        acc.require(
+            new.sequence == old.sequence,
+            format!("sequence changed from {} to {}", old.sequence, new.sequence),
+        );
+    };
+    let mut added_actors = Vec::new();
+    let mut changed = BTreeSet::new();
+    for change in diff_trees(store, old_root, new_root)? {
+        changed.insert(*change.key());
+        match change {
+            Change::Added(address, _) => added_actors.push(address),
+            Change::Removed(address, _) => acc.add(format!("{address} missing from migrated tree")),
+            Change::Modified(address, old, new) => check_actor(&address, &old, &new),
+        }
+    }
+    // An actor absent from the diff is unchanged, which is correct only if its code maps to itself.
+    for (address, old) in old_tree.iter().filter(|(a, _)| !changed.contains(*a)) {
+        check_actor(address, old, old);
+    }
+
+    let deal_count = |tree: &BTreeMap<Address, ActorState>| -> anyhow::Result<Option<u64>> {
+        let Some(actor) = tree.get(&STORAGE_MARKET_ACTOR_ADDR) else { return Ok(None) };
+        let st: MarketState =
+            store.get_cbor(&actor.state)?.ok_or_else(|| anyhow!("missing market state"))?;
+        Ok(Some(DealArray::load(store, &st.proposals, "proposals")?.count()))
+    };
+    match (deal_count(&old_tree), deal_count(&new_tree)) {
+        (Ok(old), Ok(new)) => acc
+            .require(old == new, format!("market deal count changed from {:?} to {:?}", old, new)),
+        (old, new) => {
+            acc.require_no_error(old, "failed to count prior deals");
+            acc.require_no_error(new, "failed to count migrated deals");
+        }
+    }
+
+    let datacap_supply = |tree: &BTreeMap<Address, ActorState>| -> anyhow::Result<Option<_>> {
+        let Some(actor) = tree.get(&DATACAP_TOKEN_ACTOR_ADDR) else { return Ok(None) };
+        let st: DataCapState =
+            store.get_cbor(&actor.state)?.ok_or_else(|| anyhow!("missing datacap state"))?;
+        Ok(Some(st.token.supply))
+    };
+    match (datacap_supply(&old_tree), datacap_supply(&new_tree)) {
+        (Ok(None), _) => {}
+        (Ok(old), Ok(new)) => {
+            acc.require(old == new, format!("datacap supply changed from {:?} to {:?}", old, new))
+        }
+        (old, new) => {
+            acc.require_no_error(old, "failed to load prior datacap supply");
+            acc.require_no_error(new, "failed to load migrated datacap supply");
+        }
+    }
+
+    let total_balance = old_tree.values().map(|a| &a.balance).sum::<TokenAmount>();
+    // A state which can't be loaded for checking is reported as a violation.
+    let invariant_violations = check_state_invariants(
+        store,
+        manifest,
+        policy,
+        &new_tree,
+        Some(total_balance),
+        prior_epoch,
+    )
+    .map_or_else(
+        |e| vec![format!("failed to check state invariants: {e:#}")],
+        |acc| acc.messages(),
+    );
+
+    Ok(MigrationReport {
+        prior_actors: old_tree.len(),
+        added_actors,
+        invariant_violations,
+        migration_failures: acc.messages(),
+    })
+}
+
+fn load_tree<BS: Blockstore>(
+    store: &BS,
+    root: &Cid,
+) -> anyhow::Result<BTreeMap<Address, ActorState>> {
+    let actors = Map2::<_, Address, ActorState>::load(store, root, DEFAULT_HAMT_CONFIG, "actors")?;
+    let mut tree = BTreeMap::new();
+    actors.for_each(|address, actor| {
+        tree.insert(address, actor.clone());
+        Ok(())
+    })?;
+    Ok(tree)
+}
@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use cid::Cid;
+use fil_actors_runtime::test_utils::make_identity_cid;
+use fil_actors_runtime::{Map2, DEFAULT_HAMT_CONFIG};
+use fil_builtin_actors_migration::{
+    migrate_state_tree, migrate_state_tree_cached, ActorMigration, ActorMigrationInput,
+    ActorMigrationOutput, CodeMigrator, MemoryMigrationCache, MigrationConfig, MigrationProgress,
+    Migrations, TimingHistogram,
+};
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use multihash_codetable::Code;
+use vm_api::ActorState;
+
+use util::SyncBlockstore;
+
+mod util;
+
+/// Doubles a number held as an actor's state, and counts the actors migrated.
+struct DoublingMigration {
+    new_code_cid: Cid,
+    migrated: Arc<AtomicU64>,
+}
+
+impl ActorMigration<SyncBlockstore> for DoublingMigration {
+    fn migrate_state(
+        &self,
+        store: &SyncBlockstore,
+        input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        let n: u64 = store.get_cbor(&input.head)?.ok_or_else(|| anyhow!("missing state"))?;
+        let new_head = store.put_cbor(&(n * 2), Code::Blake2b256)?;
+        self.migrated.fetch_add(1, Ordering::SeqCst);
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head })
+    }
+}
+
+/// A deferred migration recording, as the actor's new state, the count of actors migrated
+/// before it.
+struct CountingMigration {
+    new_code_cid: Cid,
+    migrated: Arc<AtomicU64>,
+}
+
+impl ActorMigration<SyncBlockstore> for CountingMigration {
+    fn migrate_state(
+        &self,
+        store: &SyncBlockstore,
+        _input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        let count = self.migrated.load(Ordering::SeqCst);
+        let new_head = store.put_cbor(&count, Code::Blake2b256)?;
+        Ok(ActorMigrationOutput { new_code_cid: self.new_code_cid, new_head })
+    }
+
+    fn is_deferred(&self) -> bool {
+        true
+    }
+}
+
+struct FailingMigration;
+
+impl ActorMigration<SyncBlockstore> for FailingMigration {
+    fn migrate_state(
+        &self,
+        _store: &SyncBlockstore,
+        _input: ActorMigrationInput,
+    ) -> anyhow::Result<ActorMigrationOutput> {
+        Err(anyhow!("bad state"))
+    }
+}
+
+fn code(name: &str) -> Cid {
+    make_identity_cid(name.as_bytes())
+}
+
+/// Creates a tree of actors with the given codes, each with its ID as state and balance.
+fn make_tree(store: &SyncBlockstore, codes: &[Cid], count: u64) -> Cid {
+    let mut actors = Map2::<_, Address, ActorState>::empty(store, DEFAULT_HAMT_CONFIG, "actors");
+    for id in 0..count {
+        let state = store.put_cbor(&id, Code::Blake2b256).unwrap();
+        let code = codes[id as usize % codes.len()];
+        let actor = ActorState {
+            code,
+            state,
+            sequence: id,
+            balance: TokenAmount::from_atto(id),
+            delegated_address: None,
+        };
+        actors.set(&Address::new_id(id), actor).unwrap();
+    }
+    actors.flush().unwrap()
+}
+
+fn load_tree(store: &SyncBlockstore, root: &Cid) -> BTreeMap<u64, (ActorState, u64)> {
+    let actors =
+        Map2::<_, Address, ActorState>::load(store, root, DEFAULT_HAMT_CONFIG, "actors").unwrap();
+    let mut tree = BTreeMap::new();
+    actors
+        .for_each(|addr, actor| {
+            let n: u64 = store.get_cbor(&actor.state).unwrap().unwrap();
+            tree.insert(addr.id().unwrap(), (actor.clone(), n));
+            Ok(())
+        })
+        .unwrap();
+    tree
+}
+
+fn small_queues() -> MigrationConfig {
+    MigrationConfig {
+        max_workers: 4,
+        job_queue_size: 2,
+        result_queue_size: 2,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn migrates_code_and_state() {
+    let store = SyncBlockstore::default();
+    let (old_a, old_b) = (code("a/1"), code("b/1"));
+    let (new_a, new_b) = (code("a/2"), code("b/2"));
+    let root = make_tree(&store, &[old_a, old_b], 500);
+
+    let migrated = Arc::new(AtomicU64::new(0));
+    let mut migrations: Migrations<SyncBlockstore> = HashMap::new();
+    migrations.insert(old_a, Box::new(CodeMigrator { new_code_cid: new_a }));
+    migrations.insert(
+        old_b,
+        Box::new(DoublingMigration { new_code_cid: new_b, migrated: migrated.clone() }),
+    );
+    let new_root = migrate_state_tree(&store, &root, &migrations, 10, &small_queues()).unwrap();
+
+    let tree = load_tree(&store, &new_root);
+    assert_eq!(500, tree.len());
+    assert_eq!(250, migrated.load(Ordering::SeqCst));
+    for (id, (actor, n)) in tree {
+        assert_eq!(id, actor.sequence);
+        assert_eq!(TokenAmount::from_atto(id), actor.balance);
+        if id % 2 == 0 {
+            assert_eq!((new_a, id), (actor.code, n));
+        } else {
+            assert_eq!((new_b, id * 2), (actor.code, n));
+        }
+    }
+    // The prior tree is untouched.
+    assert_eq!(old_a, load_tree(&store, &root)[&0].0.code);
+}
+
+#[test]
+fn deferred_migrations_run_last() {
+    let store = SyncBlockstore::default();
+    let (old_a, old_b) = (code("a/1"), code("b/1"));
+    let root = make_tree(&store, &[old_a, old_a, old_a, old_b], 400);
+
+    let migrated = Arc::new(AtomicU64::new(0));
+    let mut migrations: Migrations<SyncBlockstore> = HashMap::new();
+    migrations.insert(
+        old_a,
+        Box::new(DoublingMigration { new_code_cid: old_a, migrated: migrated.clone() }),
+    );
+    migrations.insert(old_b, Box::new(CountingMigration { new_code_cid: old_b, migrated }));
+    let new_root = migrate_state_tree(&store, &root, &migrations, 10, &small_queues()).unwrap();
+
+    for (actor, n) in load_tree(&store, &new_root).values() {
+        if actor.code == old_b {
+            assert_eq!(300, *n);
+        }
+    }
+}
+
+#[test]
+fn missing_migration_fails() {
+    let store = SyncBlockstore::default();
+    let (old_a, old_b) = (code("a/1"), code("b/1"));
+    let root = make_tree(&store, &[old_a, old_b], 100);
+
+    let mut migrations: Migrations<SyncBlockstore> = HashMap::new();
+    migrations.insert(old_a, Box::new(CodeMigrator { new_code_cid: old_a }));
+    let err = migrate_state_tree(&store, &root, &migrations, 10, &small_queues()).unwrap_err();
+    assert!(err.to_string().contains("no migration"), "{}", err);
+}
+
+#[test]
+fn migration_failure_stops_workers() {
+    let store = SyncBlockstore::default();
+    let (old_a, old_b) = (code("a/1"), code("b/1"));
+    let root = make_tree(&store, &[old_a, old_a, old_a, old_b], 1000);
+
+    let mut migrations: Migrations<SyncBlockstore> = HashMap::new();
+    migrations.insert(old_a, Box::new(CodeMigrator { new_code_cid: old_a }));
+    migrations.insert(old_b, Box::new(FailingMigration));
+    let err = migrate_state_tree(&store, &root, &migrations, 10, &small_queues()).unwrap_err();
+    assert!(format!("{:#}", err).contains("bad state"), "{:#}", err);
+}
+
+#[test]
+fn cached_migration_reuses_unchanged_actors() {
+    let store = SyncBlockstore::default();
+    let (old_a, new_a) = (code("a/1"), code("a/2"));
+    let root = make_tree(&store, &[old_a], 100);
+
+    let migrated = Arc::new(AtomicU64::new(0));
+    let mut migrations: Migrations<SyncBlockstore> = HashMap::new();
+    migrations.insert(
+        old_a,
+        Box::new(DoublingMigration { new_code_cid: new_a, migrated: migrated.clone() }),
+    );
+    let cache = MemoryMigrationCache::default();
+    migrate_state_tree_cached(&store, &root, &migrations, 10, &small_queues(), &cache).unwrap();
+    assert_eq!(100, migrated.load(Ordering::SeqCst));
+    assert_eq!(100, cache.len());
+
+    // Change one actor's state, and migrate the later tree.
+    let mut actors =
+        Map2::<_, Address, ActorState>::load(&store, &root, DEFAULT_HAMT_CONFIG, "actors").unwrap();
+    let mut actor = actors.get(&Address::new_id(7)).unwrap().unwrap().clone();
+    actor.state = store.put_cbor(&1000u64, Code::Blake2b256).unwrap();
+    actors.set(&Address::new_id(7), actor).unwrap();
+    let root = actors.flush().unwrap();
+
+    migrated.store(0, Ordering::SeqCst);
+    let new_root =
+        migrate_state_tree_cached(&store, &root, &migrations, 20, &small_queues(), &cache).unwrap();
+    assert_eq!(1, migrated.load(Ordering::SeqCst));
+    assert_eq!(
+        new_root,
+        migrate_state_tree(&store, &root, &migrations, 20, &small_queues()).unwrap()
+    );
+    let (actor, n) = &load_tree(&store, &new_root)[&7];
+    assert_eq!((new_a, 2000), (actor.code, *n));
+}
+
+#[test]
+fn reports_progress() {
+    let store = SyncBlockstore::default();
+    let (old_a, old_b) = (code("a/1"), code("b/1"));
+    let root = make_tree(&store, &[old_a, old_b, old_b], 300);
+
+    let mut migrations: Migrations<SyncBlockstore> = HashMap::new();
+    migrations.insert(old_a, Box::new(CodeMigrator { new_code_cid: old_a }));
+    migrations.insert(old_b, Box::new(CodeMigrator { new_code_cid: old_b }));
+    let reports = Arc::new(Mutex::new(Vec::<MigrationProgress>::new()));
+    let observed = reports.clone();
+    let config = MigrationConfig {
+        progress_log_period: Duration::from_micros(1),
+        expected_actors: Some(300),
+        observer: Some(Arc::new(move |p: &MigrationProgress| {
+            observed.lock().unwrap().push(p.clone())
+        })),
+        ..small_queues()
+    };
+    migrate_state_tree(&store, &root, &migrations, 10, &config).unwrap();
+
+    let reports = reports.lock().unwrap();
+    let last = reports.last().unwrap();
+    assert!(last.complete);
+    assert_eq!((300, 0, Some(300)), (last.actors_done, last.actors_cached, last.actors_total));
+    assert_eq!(Some(Duration::ZERO), last.estimated_remaining);
+    assert!(last.actors_per_second > 0.0);
+    assert_eq!(100, last.timings[&old_a].count);
+    assert_eq!(200, last.timings[&old_b].count);
+    for p in reports.iter() {
+        assert_eq!(Some(300), p.actors_total);
+        assert!(p.complete == std::ptr::eq(p, last));
+    }
+}
+
+#[test]
+fn timing_histogram_buckets() {
+    let mut h = TimingHistogram::default();
+    for micros in [0, 1, 3, 4, 1000, u64::MAX] {
+        h.record(Duration::from_micros(micros));
+    }
+    assert_eq!(6, h.count);
+    assert_eq!(Duration::from_micros(u64::MAX), h.max);
+    let mut expected = [0; 32];
+    expected[0] = 1;
+    expected[1] = 1;
+    expected[2] = 1;
+    expected[3] = 1;
+    expected[10] = 1;
+    expected[31] = 1;
+    assert_eq!(expected, h.buckets);
+}
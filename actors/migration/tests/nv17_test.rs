@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use cid::Cid;
+use fil_actor_datacap::INFINITE_ALLOWANCE;
+use fil_actor_market::{
+    DealProposal, Label, PendingDealAllocationsMap, PENDING_ALLOCATIONS_CONFIG,
+    PROPOSALS_AMT_BITWIDTH, STATES_AMT_BITWIDTH,
+};
+use fil_actor_verifreg::state::{DataCapMap, DATACAP_MAP_CONFIG};
+use fil_actor_verifreg::Allocation;
+use fil_actors_runtime::frc46::TOKEN_PRECISION;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_utils::{make_identity_cid, make_piece_cid};
+use fil_actors_runtime::{
+    Array2, Map2, DATACAP_TOKEN_ACTOR_ADDR, DEFAULT_HAMT_CONFIG, STORAGE_MARKET_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fil_builtin_actors_migration::nv17::{
+    migrate_nv17, DealStateV8, DealStateV9, MarketStateV8, MarketStateV9, Nv17Codes,
+    VerifregStateV8,
+};
+use fil_builtin_actors_migration::{CodeMigrator, MigrationConfig, Migrations};
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::bigint_ser::BigIntDe;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::StoragePower;
+use multihash_codetable::Code;
+use num_traits::Zero;
+use vm_api::ActorState;
+
+use util::SyncBlockstore;
+
+mod util;
+
+const CLIENT1: u64 = 101;
+const CLIENT2: u64 = 102;
+const PROVIDER: u64 = 200;
+
+fn code(name: &str) -> Cid {
+    make_identity_cid(name.as_bytes())
+}
+
+fn proposal(client: u64, verified_deal: bool, size: u64) -> DealProposal {
+    DealProposal {
+        piece_cid: make_piece_cid(format!("{}-{}", client, size).as_bytes()),
+        piece_size: PaddedPieceSize(size),
+        verified_deal,
+        client: Address::new_id(client),
+        provider: Address::new_id(PROVIDER),
+        label: Label::String("".into()),
+        start_epoch: 1000,
+        end_epoch: 1000 + 600_000,
+        storage_price_per_epoch: TokenAmount::zero(),
+        provider_collateral: TokenAmount::zero(),
+        client_collateral: TokenAmount::zero(),
+    }
+}
+
+fn put_actor(
+    store: &SyncBlockstore,
+    actors: &mut Map2<&SyncBlockstore, Address, ActorState>,
+    addr: &Address,
+    code: Cid,
+    state: &impl serde::Serialize,
+) {
+    let state = store.put_cbor(state, Code::Blake2b256).unwrap();
+    let actor = ActorState {
+        code,
+        state,
+        sequence: 0,
+        balance: TokenAmount::zero(),
+        delegated_address: None,
+    };
+    actors.set(addr, actor).unwrap();
+}
+
+/// Creates a v8 state tree with verified clients, and deals in each stage of their life.
+fn make_v8_tree(store: &SyncBlockstore) -> Cid {
+    let empty_map = DataCapMap::empty(store, DATACAP_MAP_CONFIG, "empty").flush().unwrap();
+    let mut clients = DataCapMap::empty(store, DATACAP_MAP_CONFIG, "verified clients");
+    clients.set(&Address::new_id(CLIENT1), BigIntDe(StoragePower::from(1000))).unwrap();
+    clients.set(&Address::new_id(CLIENT2), BigIntDe(StoragePower::from(0))).unwrap();
+    let verifreg = VerifregStateV8 {
+        root_key: Address::new_id(80),
+        verifiers: empty_map,
+        verified_clients: clients.flush().unwrap(),
+        remove_data_cap_proposal_ids: empty_map,
+    };
+
+    let mut proposals = Array2::empty(store, PROPOSALS_AMT_BITWIDTH, "proposals");
+    // A pending verified deal
+    proposals.set(0, proposal(CLIENT1, true, 2048)).unwrap();
+    // An activated verified deal
+    proposals.set(1, proposal(CLIENT2, true, 4096)).unwrap();
+    // A pending unverified deal
+    proposals.set(2, proposal(CLIENT2, false, 8192)).unwrap();
+    // Another pending verified deal
+    proposals.set(3, proposal(CLIENT2, true, 16384)).unwrap();
+    let mut states = Array2::empty(store, STATES_AMT_BITWIDTH, "deal states");
+    let activated =
+        DealStateV8 { sector_start_epoch: 900, last_updated_epoch: -1, slash_epoch: -1 };
+    states.set(1, activated).unwrap();
+    let market = MarketStateV8 {
+        proposals: proposals.flush().unwrap(),
+        states: states.flush().unwrap(),
+        pending_proposals: empty_map,
+        escrow_table: empty_map,
+        locked_table: empty_map,
+        next_id: 4,
+        deal_ops_by_epoch: empty_map,
+        last_cron: 990,
+        total_client_locked_collateral: TokenAmount::zero(),
+        total_provider_locked_collateral: TokenAmount::zero(),
+        total_client_storage_fee: TokenAmount::zero(),
+    };
+
+    let mut actors = Map2::empty(store, DEFAULT_HAMT_CONFIG, "actors");
+    put_actor(store, &mut actors, &STORAGE_MARKET_ACTOR_ADDR, code("market/8"), &market);
+    put_actor(store, &mut actors, &VERIFIED_REGISTRY_ACTOR_ADDR, code("verifreg/8"), &verifreg);
+    put_actor(store, &mut actors, &Address::new_id(CLIENT1), code("account/8"), &());
+    actors.flush().unwrap()
+}
+
+fn codes() -> Nv17Codes {
+    Nv17Codes { market: code("market/9"), verifreg: code("verifreg/9"), datacap: code("datacap/9") }
+}
+
+fn migrate(store: &SyncBlockstore, root: &Cid) -> anyhow::Result<Cid> {
+    let mut migrations: Migrations<SyncBlockstore> = HashMap::new();
+    migrations
+        .insert(code("account/8"), Box::new(CodeMigrator { new_code_cid: code("account/9") }));
+    migrate_nv17(store, root, &codes(), migrations, 999, &MigrationConfig::default())
+}
+
+fn get_actor(store: &SyncBlockstore, root: &Cid, addr: &Address) -> ActorState {
+    let actors =
+        Map2::<_, Address, ActorState>::load(store, root, DEFAULT_HAMT_CONFIG, "actors").unwrap();
+    actors.get(addr).unwrap().unwrap().clone()
+}
+
+#[test]
+fn migrates_datacap_and_pending_deals() {
+    let store = SyncBlockstore::default();
+    let root = make_v8_tree(&store);
+    let new_root = migrate(&store, &root).unwrap();
+    let policy = Policy::default();
+
+    let account = get_actor(&store, &new_root, &Address::new_id(CLIENT1));
+    assert_eq!(code("account/9"), account.code);
+
+    // Pending verified deals become allocations, in deal order.
+    let verifreg_actor = get_actor(&store, &new_root, &VERIFIED_REGISTRY_ACTOR_ADDR);
+    assert_eq!(codes().verifreg, verifreg_actor.code);
+    let verifreg: fil_actor_verifreg::State =
+        store.get_cbor(&verifreg_actor.state).unwrap().unwrap();
+    assert_eq!(Address::new_id(80), verifreg.root_key);
+    assert_eq!(3, verifreg.next_allocation_id);
+    let mut allocs = verifreg.load_allocs(&store).unwrap();
+    let expected_alloc = |client, size| {
+        let p = proposal(client, true, size);
+        Allocation {
+            client,
+            provider: PROVIDER,
+            data: p.piece_cid,
+            size: p.piece_size,
+            term_min: 600_000,
+            term_max: 600_000 + policy.market_default_allocation_term_buffer,
+            expiration: 1000,
+        }
+    };
+    assert_eq!(Some(&expected_alloc(CLIENT1, 2048)), allocs.get(CLIENT1, 1).unwrap());
+    assert_eq!(Some(&expected_alloc(CLIENT2, 16384)), allocs.get(CLIENT2, 2).unwrap());
+    assert_eq!(None, allocs.get(CLIENT2, 1).unwrap());
+
+    // The market records each pending deal's allocation.
+    let market_actor = get_actor(&store, &new_root, &STORAGE_MARKET_ACTOR_ADDR);
+    assert_eq!(codes().market, market_actor.code);
+    let market: MarketStateV9 = store.get_cbor(&market_actor.state).unwrap().unwrap();
+    assert_eq!((4, 990), (market.next_id, market.last_cron));
+    let pending_ids = PendingDealAllocationsMap::load(
+        &store,
+        &market.pending_deal_allocation_ids,
+        PENDING_ALLOCATIONS_CONFIG,
+        "pending allocations",
+    )
+    .unwrap();
+    let mut pending = vec![];
+    pending_ids
+        .for_each(|deal, alloc| {
+            pending.push((deal, *alloc));
+            Ok(())
+        })
+        .unwrap();
+    pending.sort();
+    assert_eq!(vec![(0, 1), (3, 2)], pending);
+    let states = Array2::<_, DealStateV9>::load(&store, &market.states, "deal states").unwrap();
+    assert_eq!(1, states.count());
+    let expected_state = DealStateV9 {
+        sector_start_epoch: 900,
+        last_updated_epoch: -1,
+        slash_epoch: -1,
+        verified_claim: 0,
+    };
+    assert_eq!(Some(&expected_state), states.get(1).unwrap());
+
+    // Clients' datacap becomes tokens, and the registry holds tokens for the allocations.
+    let datacap_actor = get_actor(&store, &new_root, &DATACAP_TOKEN_ACTOR_ADDR);
+    assert_eq!(codes().datacap, datacap_actor.code);
+    let datacap: fil_actor_datacap::State = store.get_cbor(&datacap_actor.state).unwrap().unwrap();
+    assert_eq!(VERIFIED_REGISTRY_ACTOR_ADDR, datacap.governor);
+    let tokens = |bytes: u64| TokenAmount::from_atto(bytes) * TOKEN_PRECISION;
+    assert_eq!(tokens(1000), datacap.balance(&store, CLIENT1).unwrap());
+    assert_eq!(tokens(0), datacap.balance(&store, CLIENT2).unwrap());
+    let registry_id = VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap();
+    assert_eq!(tokens(2048 + 16384), datacap.balance(&store, registry_id).unwrap());
+    assert_eq!(tokens(1000 + 2048 + 16384), datacap.token.supply);
+    let market_id = STORAGE_MARKET_ACTOR_ADDR.id().unwrap();
+    let allowance = datacap.token.get_allowance_between(&store, CLIENT1, market_id).unwrap();
+    assert_eq!(*INFINITE_ALLOWANCE, allowance);
+}
+
+#[test]
+fn existing_datacap_actor_fails() {
+    let store = SyncBlockstore::default();
+    let root = make_v8_tree(&store);
+    let mut actors =
+        Map2::<_, Address, ActorState>::load(&store, &root, DEFAULT_HAMT_CONFIG, "actors").unwrap();
+    put_actor(&store, &mut actors, &DATACAP_TOKEN_ACTOR_ADDR, code("datacap/9"), &());
+    let root = actors.flush().unwrap();
+    let err = migrate(&store, &root).unwrap_err();
+    assert!(err.to_string().contains("datacap actor already exists"), "{}", err);
+}
@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
+
+/// A blockstore that can be shared between migration workers.
+#[derive(Default)]
+pub struct SyncBlockstore {
+    blocks: Mutex<HashMap<Cid, Vec<u8>>>,
+}
+
+impl Blockstore for SyncBlockstore {
+    fn get(&self, k: &Cid) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.blocks.lock().unwrap().get(k).cloned())
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> anyhow::Result<()> {
+        self.blocks.lock().unwrap().insert(*k, block.to_vec());
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use cid::Cid;
+use fil_actor_market::{DealArray, State as MarketState};
+use fil_actors_integration_tests::deals::{DealBatcher, DealOptions};
+use fil_actors_integration_tests::util::{
+    create_accounts, create_miner, market_add_balance, verifreg_add_client, verifreg_add_verifier,
+};
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::ACTOR_TYPES;
+use fil_actors_runtime::{DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR};
+use fil_builtin_actors_migration::verify_migration;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::{RegisteredSealProof, StoragePower};
+use num_traits::Zero;
+use test_vm::TestVM;
+use vm_api::util::{get_state, DynBlockstore};
+use vm_api::VM;
+
+/// A VM with a verified client, and deals published with a miner.
+fn setup() -> TestVM {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 3, &TokenAmount::from_whole(10_000));
+    let (worker, client, verifier) = (addrs[0], addrs[1], addrs[2]);
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (maddr, _) = create_miner(
+        &v,
+        &worker,
+        &worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+    market_add_balance(&v, &worker, &maddr, &TokenAmount::from_whole(1_000));
+    market_add_balance(&v, &client, &client, &TokenAmount::from_whole(1_000));
+    verifreg_add_verifier(&v, &verifier, StoragePower::from(1u64 << 40));
+    verifreg_add_client(&v, &verifier, &client, StoragePower::from(1u64 << 36));
+
+    let deal_start = v.epoch() + Policy::default().pre_commit_challenge_delay + 1;
+    let opts =
+        DealOptions { deal_start, piece_size: PaddedPieceSize(1 << 30), ..Default::default() };
+    let mut batcher = DealBatcher::new(&v, opts.clone());
+    batcher.stage(client, maddr);
+    batcher.stage_with_opts(client, maddr, DealOptions { verified: true, ..opts });
+    batcher.publish_ok(worker);
+    v
+}
+
+/// Maps every code to itself, as a migration within one actors version would.
+fn identity_codes() -> HashMap<Cid, Cid> {
+    ACTOR_TYPES.keys().map(|c| (*c, *c)).collect()
+}
+
+#[test]
+fn unchanged_tree_verifies() {
+    let v = setup();
+    let root = v.checkpoint();
+    let store = DynBlockstore::wrap(v.blockstore());
+    let report = verify_migration(
+        &store,
+        &root,
+        &root,
+        &identity_codes(),
+        &ACTOR_TYPES,
+        &Policy::default(),
+        v.epoch() - 1,
+    )
+    .unwrap();
+    assert!(report.is_ok(), "{:?}", report);
+    assert!(report.added_actors.is_empty());
+    assert_eq!(v.actor_states().len(), report.prior_actors);
+}
+
+#[test]
+fn broken_migration_is_reported() {
+    let v = setup();
+    let old_root = v.checkpoint();
+
+    // Drop a deal, lose some datacap, and change an actor's code and balance.
+    let mut market: MarketState = get_state(&v, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
+    let store = DynBlockstore::wrap(v.blockstore());
+    let mut proposals = DealArray::load(&store, &market.proposals, "proposals").unwrap();
+    let deal_id = market.next_id - 1;
+    proposals.delete(deal_id).unwrap();
+    market.proposals = proposals.flush().unwrap();
+    let mut actor = v.actor(&STORAGE_MARKET_ACTOR_ADDR).unwrap();
+    actor.state = v.put_store(&market);
+    v.set_actor(&STORAGE_MARKET_ACTOR_ADDR, actor);
+
+    let mut datacap: fil_actor_datacap::State = get_state(&v, &DATACAP_TOKEN_ACTOR_ADDR).unwrap();
+    datacap.token.supply = TokenAmount::zero();
+    let mut actor = v.actor(&DATACAP_TOKEN_ACTOR_ADDR).unwrap();
+    actor.state = v.put_store(&datacap);
+    actor.balance = TokenAmount::from_atto(1);
+    actor.code = *fil_actors_runtime::test_utils::ACCOUNT_ACTOR_CODE_ID;
+    v.set_actor(&DATACAP_TOKEN_ACTOR_ADDR, actor);
+    let new_root = v.checkpoint();
+
+    let report = verify_migration(
+        &store,
+        &old_root,
+        &new_root,
+        &identity_codes(),
+        &ACTOR_TYPES,
+        &Policy::default(),
+        v.epoch() - 1,
+    )
+    .unwrap();
+    assert!(!report.is_ok());
+    assert!(!report.invariant_violations.is_empty());
+    let failures = report.migration_failures.join("\n");
+    for expected in [
+        "f07 code",
+        "f07 balance changed from 0.0 to 0.000000000000000001",
+        "market deal count changed from Some(2) to Some(1)",
+        "datacap supply changed",
+    ] {
+        assert!(failures.contains(expected), "missing {:?} in:\n{}", expected, failures);
+    }
+}
@@ -4,29 +4,20 @@
 use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::Array;
 
+use anyhow::anyhow;
+use fvm_ipld_bitfield::BitField;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::sector::SectorNumber;
 
-use super::{DeadlineInfo, Deadlines, Partition, QuantSpec};
+use super::{DeadlineSectorMap, Deadlines, Partition};
 
-pub fn new_deadline_info(
-    policy: &Policy,
-    proving_period_start: ChainEpoch,
-    deadline_idx: u64,
-    current_epoch: ChainEpoch,
-) -> DeadlineInfo {
-    DeadlineInfo::new(
-        proving_period_start,
-        deadline_idx,
-        current_epoch,
-        policy.wpost_period_deadlines,
-        policy.wpost_proving_period,
-        policy.wpost_challenge_window,
-        policy.wpost_challenge_lookback,
-        policy.fault_declaration_cutoff,
-    )
-}
+// Deadline and quantization math is shared from the runtime, re-exported here for
+// compatibility with existing users of the miner crate.
+pub use fil_actors_runtime::{
+    new_deadline_info, new_deadline_info_from_offset_and_epoch, quant_spec_for_deadline,
+    DeadlineInfo, QuantSpec, NO_QUANTIZATION,
+};
 
 impl Deadlines {
     /// Returns the deadline and partition index for a sector number.
@@ -59,6 +50,45 @@ impl Deadlines {
 
         Err(anyhow::anyhow!("sector {} not due at any deadline", sector_number))
     }
+
+    /// Returns the sectors grouped by the deadline and partition they are assigned to.
+    /// Returns an error if any of the sectors is not tracked by `self`.
+    pub fn find_sectors<BS: Blockstore>(
+        &self,
+        policy: &Policy,
+        store: &BS,
+        sector_numbers: &BitField,
+    ) -> anyhow::Result<DeadlineSectorMap> {
+        let mut remaining = sector_numbers.clone();
+        let mut located = DeadlineSectorMap::new();
+        for i in 0..self.due.len() {
+            if remaining.is_empty() {
+                break;
+            }
+            let deadline_idx = i as u64;
+            let deadline = self.load_deadline(store, deadline_idx)?;
+            let partitions = Array::<Partition, _>::load(&deadline.partitions, store)?;
+
+            let mut found = Vec::new();
+            partitions.for_each_while(|partition_idx, partition| {
+                let in_partition = &remaining & &partition.sectors;
+                if !in_partition.is_empty() {
+                    remaining -= &in_partition;
+                    found.push((partition_idx, in_partition));
+                }
+                Ok(!remaining.is_empty())
+            })?;
+
+            for (partition_idx, in_partition) in found {
+                located.add(policy, deadline_idx, partition_idx, in_partition)?;
+            }
+        }
+
+        if let Some(missing) = remaining.iter().next() {
+            return Err(anyhow!("sector {} not due at any deadline", missing));
+        }
+        Ok(located)
+    }
 }
 
 /// Returns true if the deadline at the given index is currently mutable.
@@ -79,10 +109,6 @@ pub fn deadline_is_mutable(
     current_epoch < deadline_info.open - policy.wpost_challenge_window
 }
 
-pub fn quant_spec_for_deadline(policy: &Policy, di: &DeadlineInfo) -> QuantSpec {
-    QuantSpec { unit: policy.wpost_proving_period, offset: di.last() }
-}
-
 // Returns true if optimistically accepted posts submitted to the given deadline
 // may be disputed. Specifically, this ensures that:
 //
@@ -126,18 +152,3 @@ pub fn deadline_available_for_compaction(
             current_epoch,
         )
 }
-
-// Determine current period start and deadline index directly from current epoch and
-// the offset implied by the proving period. This works correctly even for the state
-// of a miner actor without an active deadline cron
-pub fn new_deadline_info_from_offset_and_epoch(
-    policy: &Policy,
-    period_start_seed: ChainEpoch,
-    current_epoch: ChainEpoch,
-) -> DeadlineInfo {
-    let q = QuantSpec { unit: policy.wpost_proving_period, offset: period_start_seed };
-    let current_period_start = q.quantize_down(current_epoch);
-    let current_deadline_idx =
-        ((current_epoch - current_period_start) / policy.wpost_challenge_window) as u64;
-    new_deadline_info(policy, current_period_start, current_deadline_idx, current_epoch)
-}
@@ -1,6 +1,7 @@
 use cid::Cid;
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_ipld_encoding::{BytesDe, BytesSer};
 use fvm_shared::sector::SectorNumber;
 
 /// Indicates a sector has been pre-committed.
@@ -47,6 +48,23 @@ pub fn sector_terminated(rt: &impl Runtime, sector: SectorNumber) -> Result<(),
     )
 }
 
+/// Indicates the miner's peer ID and/or multiaddrs have been changed, with the new values of
+/// those changed.
+pub fn peer_info_changed(
+    rt: &impl Runtime,
+    peer_id: Option<&[u8]>,
+    multiaddrs: Option<&[BytesDe]>,
+) -> Result<(), ActorError> {
+    let mut event = EventBuilder::new().typ("peer-info-changed");
+    if let Some(peer_id) = peer_id {
+        event = event.field("peer-id", &BytesSer(peer_id));
+    }
+    if let Some(multiaddrs) = multiaddrs {
+        event = event.field("multiaddrs", multiaddrs);
+    }
+    rt.emit_event(&event.build()?)
+}
+
 trait WithSectorInfo {
     fn with_sector_info(
         self,
@@ -131,6 +131,8 @@ pub mod verifreg {
     pub const GET_CLAIMS_METHOD: u64 = 10;
     pub const CLAIM_ALLOCATIONS_METHOD: u64 = 9;
 
+    pub use fil_actors_runtime::{AllocationClaim, ProviderClaims, SectorAllocationClaims};
+
     pub type ClaimID = u64;
     pub type AllocationID = u64;
 
@@ -153,11 +155,8 @@ pub mod verifreg {
         // ID of the provider's sector in which the data is committed.
         pub sector: SectorNumber,
     }
-    #[derive(Debug, Serialize_tuple, Deserialize_tuple)]
-    pub struct GetClaimsParams {
-        pub provider: ActorID,
-        pub claim_ids: Vec<ClaimID>,
-    }
+    pub type GetClaimsParams = ProviderClaims;
+
     #[derive(Debug, Serialize_tuple, Deserialize_tuple)]
 
     pub struct GetClaimsReturn {
@@ -165,21 +164,6 @@ pub mod verifreg {
         pub claims: Vec<Claim>,
     }
 
-    #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-    pub struct SectorAllocationClaims {
-        pub sector: SectorNumber,
-        pub expiry: ChainEpoch,
-        pub claims: Vec<AllocationClaim>,
-    }
-
-    #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-    pub struct AllocationClaim {
-        pub client: ActorID,
-        pub allocation_id: AllocationID,
-        pub data: Cid,
-        pub size: PaddedPieceSize,
-    }
-
     #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
     pub struct ClaimAllocationsParams {
         pub sectors: Vec<SectorAllocationClaims>,
@@ -10,6 +10,7 @@ use std::ops::Neg;
 use anyhow::{anyhow, Error};
 use byteorder::{BigEndian, ByteOrder, WriteBytesExt};
 use cid::Cid;
+use fil_actors_runtime::frc46::UniversalReceiverParams;
 use fvm_ipld_bitfield::{BitField, Validate};
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
@@ -39,7 +40,6 @@ pub use beneficiary::*;
 pub use bitfield_queue::*;
 pub use commd::*;
 pub use deadline_assignment::*;
-pub use deadline_info::*;
 pub use deadline_state::*;
 pub use deadlines::*;
 pub use expiration_queue::*;
@@ -49,15 +49,16 @@ use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::policy_constants::MAX_SECTOR_NUMBER;
 use fil_actors_runtime::runtime::{ActorCode, DomainSeparationTag, Policy, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, extract_send_result, util, ActorContext,
-    ActorDowncast, ActorError, AsActorError, BatchReturn, BatchReturnGen, DealWeight,
-    BURNT_FUNDS_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
-    STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    actor_dispatch, actor_error, actor_exit_code, current_deadline_index,
+    current_proving_period_start, deserialize_block, extract_send_result, util, validate_bitfield,
+    ActorContext, ActorDowncast, ActorError, AsActorError, BatchReturn, BatchReturnGen,
+    BitFieldLimits, DealWeight, ValidateParams, BURNT_FUNDS_ACTOR_ADDR, INIT_ACTOR_ADDR,
+    REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 pub use monies::*;
 pub use partition_state::*;
 pub use policy::*;
-pub use quantize::*;
 pub use sector_map::*;
 pub use sectors::*;
 pub use state::*;
@@ -79,7 +80,6 @@ mod beneficiary;
 mod bitfield_queue;
 mod commd;
 mod deadline_assignment;
-mod deadline_info;
 mod deadline_state;
 mod deadlines;
 mod emit;
@@ -90,11 +90,11 @@ mod monies;
 mod notifications;
 mod partition_state;
 mod policy;
-mod quantize;
 mod sector_map;
 mod sectors;
 mod state;
 mod termination;
+#[cfg(feature = "testing")]
 pub mod testing;
 mod types;
 mod vesting_state;
@@ -139,6 +139,9 @@ pub enum Method {
     ProveCommitSectors3 = 34,
     ProveReplicaUpdates3 = 35,
     ProveCommitSectorsNI = 36,
+    DeclareFaultsBySector = 37,
+    DeclareFaultsRecoveredBySector = 38,
+    GetBalances = 39,
     // Method numbers derived from FRC-0042 standards
     ChangeWorkerAddressExported = frc42_dispatch::method_hash!("ChangeWorkerAddress"),
     ChangePeerIDExported = frc42_dispatch::method_hash!("ChangePeerID"),
@@ -156,15 +159,23 @@ pub enum Method {
     GetVestingFundsExported = frc42_dispatch::method_hash!("GetVestingFunds"),
     GetPeerIDExported = frc42_dispatch::method_hash!("GetPeerID"),
     GetMultiaddrsExported = frc42_dispatch::method_hash!("GetMultiaddrs"),
+    GetVestingReleaseEpochExported = frc42_dispatch::method_hash!("GetVestingReleaseEpoch"),
+    ReleaseVestingFundsExported = frc42_dispatch::method_hash!("ReleaseVestingFunds"),
+    GetDeadlineInfoExported = frc42_dispatch::method_hash!("GetDeadlineInfo"),
+    GetSectorsExported = frc42_dispatch::method_hash!("GetSectors"),
+    GetBalancesExported = frc42_dispatch::method_hash!("GetBalances"),
+    ChangePeerInfoExported = frc42_dispatch::method_hash!("ChangePeerInfo"),
+    GetDisputableWindowedPoStsExported = frc42_dispatch::method_hash!("GetDisputableWindowedPoSts"),
+    UniversalReceiverHook = frc42_dispatch::method_hash!("Receive"),
 }
 
 pub const SECTOR_CONTENT_CHANGED: MethodNum = frc42_dispatch::method_hash!("SectorContentChanged");
 
-pub const ERR_BALANCE_INVARIANTS_BROKEN: ExitCode = ExitCode::new(1000);
-pub const ERR_NOTIFICATION_SEND_FAILED: ExitCode = ExitCode::new(1001);
-pub const ERR_NOTIFICATION_RECEIVER_ABORTED: ExitCode = ExitCode::new(1002);
-pub const ERR_NOTIFICATION_RESPONSE_INVALID: ExitCode = ExitCode::new(1003);
-pub const ERR_NOTIFICATION_REJECTED: ExitCode = ExitCode::new(1004);
+pub const ERR_BALANCE_INVARIANTS_BROKEN: ExitCode = actor_exit_code(1000);
+pub const ERR_NOTIFICATION_SEND_FAILED: ExitCode = actor_exit_code(1001);
+pub const ERR_NOTIFICATION_RECEIVER_ABORTED: ExitCode = actor_exit_code(1002);
+pub const ERR_NOTIFICATION_RESPONSE_INVALID: ExitCode = actor_exit_code(1003);
+pub const ERR_NOTIFICATION_REJECTED: ExitCode = actor_exit_code(1004);
 
 /// Miner Actor
 /// here in order to update the Power Actor to v3.
@@ -304,6 +315,25 @@ impl Actor {
         Ok(GetAvailableBalanceReturn { available_balance })
     }
 
+    /// Returns the miner's balance, the part of it available for withdrawal, and the amounts
+    /// locked or owed that make up the rest.
+    fn get_balances(rt: &impl Runtime) -> Result<GetBalancesReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        let balance = rt.current_balance();
+        let available_balance = state.get_available_balance(&balance).map_err(|e| {
+            actor_error!(illegal_state, "failed to calculate available balance: {}", e)
+        })?;
+        Ok(GetBalancesReturn {
+            balance,
+            available_balance,
+            pre_commit_deposits: state.pre_commit_deposits,
+            initial_pledge: state.initial_pledge,
+            locked_funds: state.locked_funds,
+            fee_debt: state.fee_debt,
+        })
+    }
+
     /// Returns the funds vesting in this miner as a list of (vesting_epoch, vesting_amount) tuples.
     fn get_vesting_funds(rt: &impl Runtime) -> Result<GetVestingFundsReturn, ActorError> {
         rt.validate_immediate_caller_accept_any()?;
@@ -315,6 +345,115 @@ impl Actor {
         Ok(GetVestingFundsReturn { vesting_funds: ret })
     }
 
+    /// Returns the epoch from which a miner that has terminated all its sectors and settled all
+    /// its debt may release its remaining vesting funds, or None if it can't.
+    fn get_vesting_release_epoch(
+        rt: &impl Runtime,
+    ) -> Result<GetVestingReleaseEpochReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        let epoch = state.vesting_release_epoch(rt.policy(), rt.store())?;
+        Ok(GetVestingReleaseEpochReturn { epoch })
+    }
+
+    /// Returns the miner's Window PoSt deadline open at an epoch, which may be in the past
+    /// or future.
+    fn get_deadline_info(
+        rt: &impl Runtime,
+        params: GetDeadlineInfoParams,
+    ) -> Result<GetDeadlineInfoReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params.validate()?;
+        let state: State = rt.state()?;
+        let info = state.deadline_info(rt.policy(), params.epoch);
+        Ok(GetDeadlineInfoReturn {
+            period_start: info.period_start,
+            index: info.index,
+            open: info.open,
+            close: info.close,
+            challenge: info.challenge,
+            fault_cutoff: info.fault_cutoff,
+        })
+    }
+
+    /// Returns summaries of a set of sectors, looking up at most `limit` requested sector
+    /// numbers from the cursor. Requested sectors that don't exist are omitted.
+    fn get_sectors(
+        rt: &impl Runtime,
+        params: GetSectorsParams,
+    ) -> Result<GetSectorsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params.validate()?;
+        let cursor = params.cursor.unwrap_or_default();
+        let mut requested = params.sectors.iter().skip_while(|&n| n < cursor);
+        let state: State = rt.state()?;
+        let sectors = Sectors::load(rt.store(), &state.sectors)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors")?;
+        let mut summaries = Vec::new();
+        for sector_number in requested.by_ref().take(params.limit as usize) {
+            if let Some(info) = sectors.get(sector_number)? {
+                summaries.push(SectorSummary::from(&info));
+            }
+        }
+        Ok(GetSectorsReturn { sectors: summaries, next_cursor: requested.next() })
+    }
+
+    /// Returns the optimistically accepted Window PoSts of a deadline which may currently be
+    /// disputed, with the challenge epoch against which they were verified. The proofs are empty
+    /// outside the deadline's dispute window, or once all have been disputed.
+    fn get_disputable_windowed_posts(
+        rt: &impl Runtime,
+        params: GetDisputableWindowedPoStsParams,
+    ) -> Result<GetDisputableWindowedPoStsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let policy = rt.policy();
+        if params.deadline >= policy.wpost_period_deadlines {
+            return Err(actor_error!(
+                illegal_argument,
+                "invalid deadline {} of {}",
+                params.deadline,
+                policy.wpost_period_deadlines
+            ));
+        }
+        let current_epoch = rt.curr_epoch();
+        let state: State = rt.state()?;
+        let dl_info = state.deadline_info(policy, current_epoch);
+
+        // The proofs were submitted in the deadline's most recent challenge window,
+        // as found by DisputeWindowedPoSt.
+        let mut pp_start = dl_info.period_start;
+        if dl_info.index < params.deadline {
+            pp_start -= policy.wpost_proving_period
+        }
+        let target_deadline = new_deadline_info(policy, pp_start, params.deadline, current_epoch);
+
+        let mut posts = Vec::new();
+        if deadline_available_for_optimistic_post_dispute(
+            policy,
+            dl_info.period_start,
+            params.deadline,
+            current_epoch,
+        ) {
+            let deadline = state
+                .load_deadlines(rt.store())
+                .map_err(|e| e.wrap("failed to load deadlines"))?
+                .load_deadline(rt.store(), params.deadline)?;
+            deadline
+                .optimistic_proofs_snapshot_amt(rt.store())
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load post proofs snapshot")?
+                .for_each(|post_index, post| {
+                    posts.push(DisputableWindowedPoSt {
+                        post_index,
+                        partitions: post.partitions.clone(),
+                        proofs: post.proofs.clone(),
+                    });
+                    Ok(())
+                })
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate post proofs")?;
+        }
+        Ok(GetDisputableWindowedPoStsReturn { challenge_epoch: target_deadline.challenge, posts })
+    }
+
     /// Will ALWAYS overwrite the existing control addresses with the control addresses passed in the params.
     /// If an empty addresses vector is passed, the control addresses will be cleared.
     /// A worker change will be scheduled if the worker passed in the params is different from the existing worker.
@@ -446,24 +585,7 @@ impl Actor {
     }
 
     fn change_peer_id(rt: &impl Runtime, params: ChangePeerIDParams) -> Result<(), ActorError> {
-        let policy = rt.policy();
-        check_peer_info(policy, &params.new_id, &[])?;
-
-        rt.transaction(|state: &mut State, rt| {
-            let mut info = get_miner_info(rt.store(), state)?;
-
-            rt.validate_immediate_caller_is(
-                info.control_addresses.iter().chain(&[info.worker, info.owner]),
-            )?;
-
-            info.peer_id = params.new_id;
-            state.save_info(rt.store(), &info).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "could not save miner info")
-            })?;
-
-            Ok(())
-        })?;
-        Ok(())
+        set_peer_info(rt, Some(params.new_id), None)
     }
 
     /// Returns the multiaddresses set for this miner.
@@ -474,28 +596,25 @@ impl Actor {
         Ok(GetMultiaddrsReturn { multi_addrs })
     }
 
+    // Always succeeds, accepting any transfers, so long as the params are valid `UniversalReceiverParams`.
+    fn universal_receiver_hook(
+        rt: &impl Runtime,
+        _params: UniversalReceiverParams,
+    ) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        Ok(())
+    }
+
     fn change_multiaddresses(
         rt: &impl Runtime,
         params: ChangeMultiaddrsParams,
     ) -> Result<(), ActorError> {
-        let policy = rt.policy();
-        check_peer_info(policy, &[], &params.new_multi_addrs)?;
-
-        rt.transaction(|state: &mut State, rt| {
-            let mut info = get_miner_info(rt.store(), state)?;
-
-            rt.validate_immediate_caller_is(
-                info.control_addresses.iter().chain(&[info.worker, info.owner]),
-            )?;
-
-            info.multi_address = params.new_multi_addrs;
-            state.save_info(rt.store(), &info).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "could not save miner info")
-            })?;
+        set_peer_info(rt, None, Some(params.new_multi_addrs))
+    }
 
-            Ok(())
-        })?;
-        Ok(())
+    /// Changes the peer ID and multiaddrs together, leaving either unchanged if omitted.
+    fn change_peer_info(rt: &impl Runtime, params: ChangePeerInfoParams) -> Result<(), ActorError> {
+        set_peer_info(rt, params.new_id.map(|id| id.0), params.new_multi_addrs)
     }
 
     /// Invoked by miner's worker address to submit their fallback post
@@ -2533,6 +2652,13 @@ impl Actor {
             let deadline = term.deadline;
             let partition = term.partition;
 
+            validate_bitfield(&term.sectors, BitFieldLimits::addressed_sectors(rt.policy()))
+                .map_err(|e| {
+                    e.wrap(format!(
+                        "invalid sectors at deadline {}, partition {}",
+                        deadline, partition
+                    ))
+                })?;
             to_process.add(rt.policy(), deadline, partition, term.sectors).map_err(|e| {
                 actor_error!(
                     illegal_argument,
@@ -2674,6 +2800,13 @@ impl Actor {
             let deadline = term.deadline;
             let partition = term.partition;
 
+            validate_bitfield(&term.sectors, BitFieldLimits::addressed_sectors(rt.policy()))
+                .map_err(|e| {
+                    e.wrap(format!(
+                        "invalid sectors at deadline {}, partition {}",
+                        deadline, partition
+                    ))
+                })?;
             to_process.add(rt.policy(), deadline, partition, term.sectors).map_err(|e| {
                 actor_error!(
                     illegal_argument,
@@ -2685,104 +2818,19 @@ impl Actor {
             })?;
         }
 
-        {
-            let policy = rt.policy();
-            to_process
-                .check(policy.addressed_partitions_max, policy.addressed_sectors_max)
-                .map_err(|e| {
-                    actor_error!(illegal_argument, "cannot process requested parameters: {}", e)
-                })?;
-        }
-
-        let power_delta = rt.transaction(|state: &mut State, rt| {
-            let info = get_miner_info(rt.store(), state)?;
-
-            rt.validate_immediate_caller_is(
-                info.control_addresses.iter().chain(&[info.worker, info.owner]),
-            )?;
-
-            let store = rt.store();
-
-            let mut deadlines =
-                state.load_deadlines(store).map_err(|e| e.wrap("failed to load deadlines"))?;
-
-            let sectors = Sectors::load(store, &state.sectors).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors array")
-            })?;
-
-            let mut new_fault_power_total = PowerPair::zero();
-            let curr_epoch = rt.curr_epoch();
-            for (deadline_idx, partition_map) in to_process.iter() {
-                let policy = rt.policy();
-                let target_deadline = declaration_deadline_info(
-                    policy,
-                    state.current_proving_period_start(policy, curr_epoch),
-                    deadline_idx,
-                    curr_epoch,
-                )
-                .map_err(|e| {
-                    actor_error!(
-                        illegal_argument,
-                        "invalid fault declaration deadline {}: {}",
-                        deadline_idx,
-                        e
-                    )
-                })?;
-
-                validate_fr_declaration_deadline(&target_deadline).map_err(|e| {
-                    actor_error!(
-                        illegal_argument,
-                        "failed fault declaration at deadline {}: {}",
-                        deadline_idx,
-                        e
-                    )
-                })?;
-
-                let mut deadline = deadlines.load_deadline(store, deadline_idx)?;
-
-                let fault_expiration_epoch = target_deadline.last() + policy.fault_max_age;
-
-                let deadline_power_delta = deadline
-                    .record_faults(
-                        store,
-                        &sectors,
-                        info.sector_size,
-                        target_deadline.quant_spec(),
-                        fault_expiration_epoch,
-                        partition_map,
-                    )
-                    .map_err(|e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            format!("failed to declare faults for deadline {}", deadline_idx),
-                        )
-                    })?;
-
-                deadlines.update_deadline(policy, store, deadline_idx, &deadline).map_err(|e| {
-                    e.downcast_default(
-                        ExitCode::USR_ILLEGAL_STATE,
-                        format!("failed to store deadline {} partitions", deadline_idx),
-                    )
-                })?;
-
-                new_fault_power_total += &deadline_power_delta;
-            }
-
-            state.save_deadlines(store, deadlines).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
-            })?;
-
-            Ok(new_fault_power_total)
-        })?;
-
-        // Remove power for new faulty sectors.
-        // NOTE: It would be permissible to delay the power loss until the deadline closes, but that would require
-        // additional accounting state.
-        // https://github.com/filecoin-project/specs-actors/issues/414
-        request_update_power(rt, power_delta)?;
+        validate_control_caller(rt)?;
+        record_declared_faults(rt, to_process)
+    }
 
-        // Payment of penalty for declared faults is deferred to the deadline cron.
-        Ok(())
+    /// Declares sectors faulty without the caller naming their deadline and partition,
+    /// which are looked up from the sectors' current assignment.
+    fn declare_faults_by_sector(
+        rt: &impl Runtime,
+        params: DeclareFaultsBySectorParams,
+    ) -> Result<(), ActorError> {
+        validate_control_caller(rt)?;
+        let to_process = locate_declared_sectors(rt, &params.sectors)?;
+        record_declared_faults(rt, to_process)
     }
 
     fn declare_faults_recovered(
@@ -2807,6 +2855,13 @@ impl Actor {
             let deadline = term.deadline;
             let partition = term.partition;
 
+            validate_bitfield(&term.sectors, BitFieldLimits::addressed_sectors(rt.policy()))
+                .map_err(|e| {
+                    e.wrap(format!(
+                        "invalid sectors at deadline {}, partition {}",
+                        deadline, partition
+                    ))
+                })?;
             to_process.add(rt.policy(), deadline, partition, term.sectors).map_err(|e| {
                 actor_error!(
                     illegal_argument,
@@ -2818,100 +2873,19 @@ impl Actor {
             })?;
         }
 
-        {
-            let policy = rt.policy();
-            to_process
-                .check(policy.addressed_partitions_max, policy.addressed_sectors_max)
-                .map_err(|e| {
-                    actor_error!(illegal_argument, "cannot process requested parameters: {}", e)
-                })?;
-        }
-
-        let fee_to_burn = rt.transaction(|state: &mut State, rt| {
-            // Verify unlocked funds cover both InitialPledgeRequirement and FeeDebt
-            // and repay fee debt now.
-            let fee_to_burn = repay_debts_or_abort(rt, state)?;
-
-            let info = get_miner_info(rt.store(), state)?;
-
-            rt.validate_immediate_caller_is(
-                info.control_addresses.iter().chain(&[info.worker, info.owner]),
-            )?;
-
-            if consensus_fault_active(&info, rt.curr_epoch()) {
-                return Err(actor_error!(
-                    forbidden,
-                    "recovery not allowed during active consensus fault"
-                ));
-            }
-
-            let store = rt.store();
-
-            let mut deadlines =
-                state.load_deadlines(store).map_err(|e| e.wrap("failed to load deadlines"))?;
-
-            let sectors = Sectors::load(store, &state.sectors).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors array")
-            })?;
-            let curr_epoch = rt.curr_epoch();
-            for (deadline_idx, partition_map) in to_process.iter() {
-                let policy = rt.policy();
-                let target_deadline = declaration_deadline_info(
-                    policy,
-                    state.current_proving_period_start(policy, curr_epoch),
-                    deadline_idx,
-                    curr_epoch,
-                )
-                .map_err(|e| {
-                    actor_error!(
-                        illegal_argument,
-                        "invalid recovery declaration deadline {}: {}",
-                        deadline_idx,
-                        e
-                    )
-                })?;
-
-                validate_fr_declaration_deadline(&target_deadline).map_err(|e| {
-                    actor_error!(
-                        illegal_argument,
-                        "failed recovery declaration at deadline {}: {}",
-                        deadline_idx,
-                        e
-                    )
-                })?;
-
-                let mut deadline = deadlines.load_deadline(store, deadline_idx)?;
-
-                deadline
-                    .declare_faults_recovered(store, &sectors, info.sector_size, partition_map)
-                    .map_err(|e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            format!("failed to declare recoveries for deadline {}", deadline_idx),
-                        )
-                    })?;
-
-                deadlines.update_deadline(policy, store, deadline_idx, &deadline).map_err(|e| {
-                    e.downcast_default(
-                        ExitCode::USR_ILLEGAL_STATE,
-                        format!("failed to store deadline {}", deadline_idx),
-                    )
-                })?;
-            }
-
-            state.save_deadlines(store, deadlines).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
-            })?;
-
-            Ok(fee_to_burn)
-        })?;
-
-        burn_funds(rt, fee_to_burn)?;
-        let state: State = rt.state()?;
-        state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
+        validate_control_caller(rt)?;
+        record_declared_recoveries(rt, to_process)
+    }
 
-        // Power is not restored yet, but when the recovered sectors are successfully PoSted.
-        Ok(())
+    /// Declares sectors recovered without the caller naming their deadline and partition,
+    /// which are looked up from the sectors' current assignment.
+    fn declare_faults_recovered_by_sector(
+        rt: &impl Runtime,
+        params: DeclareFaultsRecoveredBySectorParams,
+    ) -> Result<(), ActorError> {
+        validate_control_caller(rt)?;
+        let to_process = locate_declared_sectors(rt, &params.sectors)?;
+        record_declared_recoveries(rt, to_process)
     }
 
     /// Compacts a number of partitions at one deadline by removing terminated sectors, re-ordering the remaining sectors,
@@ -3092,18 +3066,7 @@ impl Actor {
 
     /// Locks up some amount of a the miner's unlocked balance (including funds received alongside the invoking message).
     fn apply_rewards(rt: &impl Runtime, params: ApplyRewardParams) -> Result<(), ActorError> {
-        if params.reward.is_negative() {
-            return Err(actor_error!(
-                illegal_argument,
-                "cannot lock up a negative amount of funds"
-            ));
-        }
-        if params.penalty.is_negative() {
-            return Err(actor_error!(
-                illegal_argument,
-                "cannot penalize a negative amount of funds"
-            ));
-        }
+        params.validate()?;
 
         let (pledge_delta_total, to_burn) = rt.transaction(|st: &mut State, rt| {
             let mut pledge_delta_total = TokenAmount::zero();
@@ -3209,7 +3172,7 @@ impl Actor {
         // These may differ from actual funds send when miner goes into fee debt
         let this_epoch_reward =
             TokenAmount::from_atto(reward_stats.this_epoch_reward_smoothed.estimate());
-        let fault_penalty = consensus_fault_penalty(this_epoch_reward.clone());
+        let fault_penalty = consensus_fault_penalty(rt.policy(), this_epoch_reward.clone());
         let slasher_reward = reward_for_consensus_slash_report(&this_epoch_reward);
 
         let mut pledge_delta = TokenAmount::zero();
@@ -3277,13 +3240,7 @@ impl Actor {
         rt: &impl Runtime,
         params: WithdrawBalanceParams,
     ) -> Result<WithdrawBalanceReturn, ActorError> {
-        if params.amount_requested.is_negative() {
-            return Err(actor_error!(
-                illegal_argument,
-                "negative fund requested for withdrawal: {}",
-                params.amount_requested
-            ));
-        }
+        params.validate()?;
 
         let (info, amount_withdrawn, newly_vested, fee_to_burn, state) =
             rt.transaction(|state: &mut State, rt| {
@@ -3552,6 +3509,51 @@ impl Actor {
         Ok(())
     }
 
+    /// Unlocks all remaining vesting funds of a miner with no sectors, pre-commitments or debt,
+    /// once the cooldown after its last reward has passed.
+    /// The released funds become available to withdraw.
+    fn release_vesting_funds(rt: &impl Runtime) -> Result<ReleaseVestingFundsReturn, ActorError> {
+        let (amount_released, state) = rt.transaction(|state: &mut State, rt| {
+            let info = get_miner_info(rt.store(), state)?;
+            rt.validate_immediate_caller_is(std::iter::once(&info.owner))?;
+
+            let release_epoch =
+                state.vesting_release_epoch(rt.policy(), rt.store())?.ok_or_else(|| {
+                    actor_error!(
+                        forbidden,
+                        "cannot release vesting funds of a miner with live sectors, pre-commits, \
+                        debt or no vesting funds"
+                    )
+                })?;
+            if rt.curr_epoch() < release_epoch {
+                return Err(actor_error!(
+                    forbidden,
+                    "cannot release vesting funds before epoch {}",
+                    release_epoch
+                ));
+            }
+
+            let newly_vested =
+                state.unlock_vested_funds(rt.store(), rt.curr_epoch()).map_err(|e| {
+                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to vest funds")
+                })?;
+            let locked_funds = state.locked_funds.clone();
+            let unvested = state
+                .unlock_unvested_funds(rt.store(), rt.curr_epoch(), &locked_funds)
+                .map_err(|e| {
+                    e.downcast_default(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        "failed to unlock vesting funds",
+                    )
+                })?;
+            Ok((newly_vested + unvested, state.clone()))
+        })?;
+
+        notify_pledge_changed(rt, &amount_released.clone().neg())?;
+        state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
+        Ok(ReleaseVestingFundsReturn { amount_released })
+    }
+
     fn on_deferred_cron_event(
         rt: &impl Runtime,
         params: DeferredCronEventParams,
@@ -4373,6 +4375,7 @@ fn process_early_terminations(
                 let sector_power = qa_power_for_sector(info.sector_size, sector);
                 terminated_sector_nums.push(sector.sector_number);
                 total_penalty += pledge_penalty_for_termination(
+                    rt.policy(),
                     &sector.expected_day_reward,
                     epoch - sector.power_base_epoch,
                     &sector.expected_storage_pledge,
@@ -5300,33 +5303,6 @@ fn assign_proving_period_offset(
     Ok(offset as ChainEpoch)
 }
 
-/// Computes the epoch at which a proving period should start such that it is greater than the current epoch, and
-/// has a defined offset from being an exact multiple of WPoStProvingPeriod.
-/// A miner is exempt from Winow PoSt until the first full proving period starts.
-fn current_proving_period_start(
-    policy: &Policy,
-    current_epoch: ChainEpoch,
-    offset: ChainEpoch,
-) -> ChainEpoch {
-    let curr_modulus = current_epoch % policy.wpost_proving_period;
-
-    let period_progress = if curr_modulus >= offset {
-        curr_modulus - offset
-    } else {
-        policy.wpost_proving_period - (offset - curr_modulus)
-    };
-
-    current_epoch - period_progress
-}
-
-fn current_deadline_index(
-    policy: &Policy,
-    current_epoch: ChainEpoch,
-    period_start: ChainEpoch,
-) -> u64 {
-    ((current_epoch - period_start) / policy.wpost_challenge_window) as u64
-}
-
 /// Computes deadline information for a fault or recovery declaration.
 /// If the deadline has not yet elapsed, the declaration is taken as being for the current proving period.
 /// If the deadline has elapsed, it's instead taken as being for the next proving period after the current epoch.
@@ -5349,6 +5325,223 @@ fn declaration_deadline_info(
     Ok(deadline)
 }
 
+/// Validates that the immediate caller is the miner's owner, worker or a control address.
+fn validate_control_caller(rt: &impl Runtime) -> Result<(), ActorError> {
+    let state: State = rt.state()?;
+    let info = get_miner_info(rt.store(), &state)?;
+    rt.validate_immediate_caller_is(info.control_addresses.iter().chain(&[info.worker, info.owner]))
+}
+
+/// Groups sectors named by a fault or recovery declaration by the deadline and partition
+/// they are currently assigned to.
+fn locate_declared_sectors(
+    rt: &impl Runtime,
+    sectors: &BitField,
+) -> Result<DeadlineSectorMap, ActorError> {
+    let policy = rt.policy();
+    validate_bitfield(sectors, BitFieldLimits::addressed_sectors(policy))
+        .map_err(|e| e.wrap("invalid declared sectors"))?;
+
+    let state: State = rt.state()?;
+    state.find_sectors(policy, rt.store(), sectors).map_err(|e| {
+        e.downcast_default(ExitCode::USR_ILLEGAL_ARGUMENT, "failed to locate declared sectors")
+    })
+}
+
+/// Records the sectors as faulty in their deadlines and removes their power.
+/// The caller must already have been validated.
+fn record_declared_faults(
+    rt: &impl Runtime,
+    mut to_process: DeadlineSectorMap,
+) -> Result<(), ActorError> {
+    {
+        let policy = rt.policy();
+        to_process.check(policy.addressed_partitions_max, policy.addressed_sectors_max).map_err(
+            |e| actor_error!(illegal_argument, "cannot process requested parameters: {}", e),
+        )?;
+    }
+
+    let power_delta = rt.transaction(|state: &mut State, rt| {
+        let info = get_miner_info(rt.store(), state)?;
+        let store = rt.store();
+
+        let mut deadlines =
+            state.load_deadlines(store).map_err(|e| e.wrap("failed to load deadlines"))?;
+
+        let sectors = Sectors::load(store, &state.sectors).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors array")
+        })?;
+
+        let mut new_fault_power_total = PowerPair::zero();
+        let curr_epoch = rt.curr_epoch();
+        for (deadline_idx, partition_map) in to_process.iter() {
+            let policy = rt.policy();
+            let target_deadline = declaration_deadline_info(
+                policy,
+                state.current_proving_period_start(policy, curr_epoch),
+                deadline_idx,
+                curr_epoch,
+            )
+            .map_err(|e| {
+                actor_error!(
+                    illegal_argument,
+                    "invalid fault declaration deadline {}: {}",
+                    deadline_idx,
+                    e
+                )
+            })?;
+
+            validate_fr_declaration_deadline(&target_deadline).map_err(|e| {
+                actor_error!(
+                    illegal_argument,
+                    "failed fault declaration at deadline {}: {}",
+                    deadline_idx,
+                    e
+                )
+            })?;
+
+            let mut deadline = deadlines.load_deadline(store, deadline_idx)?;
+
+            let fault_expiration_epoch = target_deadline.last() + policy.fault_max_age;
+
+            let deadline_power_delta = deadline
+                .record_faults(
+                    store,
+                    &sectors,
+                    info.sector_size,
+                    target_deadline.quant_spec(),
+                    fault_expiration_epoch,
+                    partition_map,
+                )
+                .map_err(|e| {
+                    e.downcast_default(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        format!("failed to declare faults for deadline {}", deadline_idx),
+                    )
+                })?;
+
+            deadlines.update_deadline(policy, store, deadline_idx, &deadline).map_err(|e| {
+                e.downcast_default(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    format!("failed to store deadline {} partitions", deadline_idx),
+                )
+            })?;
+
+            new_fault_power_total += &deadline_power_delta;
+        }
+
+        state.save_deadlines(store, deadlines).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
+        })?;
+
+        Ok(new_fault_power_total)
+    })?;
+
+    // Remove power for new faulty sectors.
+    // NOTE: It would be permissible to delay the power loss until the deadline closes, but that would require
+    // additional accounting state.
+    // https://github.com/filecoin-project/specs-actors/issues/414
+    request_update_power(rt, power_delta)?;
+
+    // Payment of penalty for declared faults is deferred to the deadline cron.
+    Ok(())
+}
+
+/// Records the faulty sectors as recovering in their deadlines, after repaying any fee debt.
+/// The caller must already have been validated.
+fn record_declared_recoveries(
+    rt: &impl Runtime,
+    mut to_process: DeadlineSectorMap,
+) -> Result<(), ActorError> {
+    {
+        let policy = rt.policy();
+        to_process.check(policy.addressed_partitions_max, policy.addressed_sectors_max).map_err(
+            |e| actor_error!(illegal_argument, "cannot process requested parameters: {}", e),
+        )?;
+    }
+
+    let fee_to_burn = rt.transaction(|state: &mut State, rt| {
+        // Verify unlocked funds cover both InitialPledgeRequirement and FeeDebt
+        // and repay fee debt now.
+        let fee_to_burn = repay_debts_or_abort(rt, state)?;
+
+        let info = get_miner_info(rt.store(), state)?;
+        if consensus_fault_active(&info, rt.curr_epoch()) {
+            return Err(actor_error!(
+                forbidden,
+                "recovery not allowed during active consensus fault"
+            ));
+        }
+
+        let store = rt.store();
+
+        let mut deadlines =
+            state.load_deadlines(store).map_err(|e| e.wrap("failed to load deadlines"))?;
+
+        let sectors = Sectors::load(store, &state.sectors).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors array")
+        })?;
+        let curr_epoch = rt.curr_epoch();
+        for (deadline_idx, partition_map) in to_process.iter() {
+            let policy = rt.policy();
+            let target_deadline = declaration_deadline_info(
+                policy,
+                state.current_proving_period_start(policy, curr_epoch),
+                deadline_idx,
+                curr_epoch,
+            )
+            .map_err(|e| {
+                actor_error!(
+                    illegal_argument,
+                    "invalid recovery declaration deadline {}: {}",
+                    deadline_idx,
+                    e
+                )
+            })?;
+
+            validate_fr_declaration_deadline(&target_deadline).map_err(|e| {
+                actor_error!(
+                    illegal_argument,
+                    "failed recovery declaration at deadline {}: {}",
+                    deadline_idx,
+                    e
+                )
+            })?;
+
+            let mut deadline = deadlines.load_deadline(store, deadline_idx)?;
+
+            deadline
+                .declare_faults_recovered(store, &sectors, info.sector_size, partition_map)
+                .map_err(|e| {
+                    e.downcast_default(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        format!("failed to declare recoveries for deadline {}", deadline_idx),
+                    )
+                })?;
+
+            deadlines.update_deadline(policy, store, deadline_idx, &deadline).map_err(|e| {
+                e.downcast_default(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    format!("failed to store deadline {}", deadline_idx),
+                )
+            })?;
+        }
+
+        state.save_deadlines(store, deadlines).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
+        })?;
+
+        Ok(fee_to_burn)
+    })?;
+
+    burn_funds(rt, fee_to_burn)?;
+    let state: State = rt.state()?;
+    state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
+
+    // Power is not restored yet, but when the recovered sectors are successfully PoSted.
+    Ok(())
+}
+
 /// Checks that a fault or recovery declaration at a specific deadline is outside the exclusion window for the deadline.
 fn validate_fr_declaration_deadline(deadline: &DeadlineInfo) -> anyhow::Result<()> {
     if deadline.fault_cutoff_passed() {
@@ -5480,6 +5673,15 @@ fn check_peer_info(
         ));
     }
 
+    if multiaddrs.len() > policy.max_multiaddr_count {
+        return Err(actor_error!(
+            illegal_argument,
+            "multiaddr count of {} exceeds maximum of {}",
+            multiaddrs.len(),
+            policy.max_multiaddr_count
+        ));
+    }
+
     let mut total_size = 0;
     for ma in multiaddrs {
         if ma.0.is_empty() {
@@ -5500,6 +5702,45 @@ fn check_peer_info(
     Ok(())
 }
 
+/// Replaces the peer ID and/or multiaddrs given, on behalf of the owner, worker or a control
+/// address, emitting an event if either is set.
+fn set_peer_info(
+    rt: &impl Runtime,
+    new_id: Option<Vec<u8>>,
+    new_multi_addrs: Option<Vec<BytesDe>>,
+) -> Result<(), ActorError> {
+    check_peer_info(
+        rt.policy(),
+        new_id.as_deref().unwrap_or_default(),
+        new_multi_addrs.as_deref().unwrap_or_default(),
+    )?;
+
+    rt.transaction(|state: &mut State, rt| {
+        let mut info = get_miner_info(rt.store(), state)?;
+
+        rt.validate_immediate_caller_is(
+            info.control_addresses.iter().chain(&[info.worker, info.owner]),
+        )?;
+
+        if let Some(new_id) = &new_id {
+            info.peer_id = new_id.clone();
+        }
+        if let Some(new_multi_addrs) = &new_multi_addrs {
+            info.multi_address = new_multi_addrs.clone();
+        }
+        state.save_info(rt.store(), &info).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "could not save miner info")
+        })?;
+
+        Ok(())
+    })?;
+
+    if new_id.is_some() || new_multi_addrs.is_some() {
+        emit::peer_info_changed(rt, new_id.as_deref(), new_multi_addrs.as_deref())?;
+    }
+    Ok(())
+}
+
 fn activate_new_sector_infos(
     rt: &impl Runtime,
     precommits: Vec<&SectorPreCommitOnChainInfo>,
@@ -6029,9 +6270,19 @@ impl ActorCode for Actor {
         GetVestingFundsExported => get_vesting_funds,
         GetPeerIDExported => get_peer_id,
         GetMultiaddrsExported => get_multiaddresses,
+        GetVestingReleaseEpochExported => get_vesting_release_epoch,
+        ReleaseVestingFundsExported => release_vesting_funds,
+        GetDeadlineInfoExported => get_deadline_info,
+        GetSectorsExported => get_sectors,
+        GetBalances|GetBalancesExported => get_balances,
+        ChangePeerInfoExported => change_peer_info,
+        GetDisputableWindowedPoStsExported => get_disputable_windowed_posts,
         ProveCommitSectors3 => prove_commit_sectors3,
         ProveReplicaUpdates3 => prove_replica_updates3,
         ProveCommitSectorsNI => prove_commit_sectors_ni,
+        DeclareFaultsBySector => declare_faults_by_sector,
+        DeclareFaultsRecoveredBySector => declare_faults_recovered_by_sector,
+        UniversalReceiverHook => universal_receiver_hook,
     }
 }
 
@@ -6,6 +6,7 @@ use std::cmp::{self, max};
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::reward::math::PRECISION;
 use fil_actors_runtime::reward::{smooth, FilterEstimate};
+use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::EXPECTED_LEADERS_PER_EPOCH;
 use fvm_shared::bigint::{BigInt, Integer};
 use fvm_shared::clock::ChainEpoch;
@@ -33,9 +34,6 @@ pub const INITIAL_PLEDGE_PROJECTION_PERIOD: i64 =
 const LOCK_TARGET_FACTOR_NUM: u32 = 3;
 const LOCK_TARGET_FACTOR_DENOM: u32 = 10;
 
-pub const TERMINATION_REWARD_FACTOR_NUM: u32 = 1;
-pub const TERMINATION_REWARD_FACTOR_DENOM: u32 = 2;
-
 // * go impl has 75/100 but this is just simplified
 const LOCKED_REWARD_FACTOR_NUM: u32 = 3;
 const LOCKED_REWARD_FACTOR_DENOM: u32 = 4;
@@ -69,12 +67,6 @@ pub const CONTINUED_FAULT_PROJECTION_PERIOD: ChainEpoch =
 
 const TERMINATION_PENALTY_LOWER_BOUND_PROJECTIONS_PERIOD: ChainEpoch = (EPOCHS_IN_DAY * 35) / 10;
 
-// Maximum number of lifetime days penalized when a sector is terminated.
-pub const TERMINATION_LIFETIME_CAP: ChainEpoch = 140;
-
-// Multiplier of whole per-winner rewards for a consensus fault penalty.
-const CONSENSUS_FAULT_FACTOR: u64 = 5;
-
 const GAMMA_FIXED_POINT_FACTOR: u64 = 1000; // 3 decimal places
 
 /// The projected block reward a sector would earn over some period.
@@ -175,8 +167,10 @@ pub fn pledge_penalty_for_termination_lower_bound(
 
 /// Penalty to locked pledge collateral for the termination of a sector before scheduled expiry.
 /// SectorAge is the time between the sector's activation and termination.
+/// The fraction of rewards charged and the lifetime cap are taken from the policy.
 #[allow(clippy::too_many_arguments)]
 pub fn pledge_penalty_for_termination(
+    policy: &Policy,
     day_reward: &TokenAmount,
     sector_age: ChainEpoch,
     twenty_day_reward_at_activation: &TokenAmount,
@@ -188,7 +182,7 @@ pub fn pledge_penalty_for_termination(
 ) -> TokenAmount {
     // max(SP(t), BR(StartEpoch, 20d) + BR(StartEpoch, 1d) * terminationRewardFactor * min(SectorAgeInDays, 140))
     // and sectorAgeInDays = sectorAge / EpochsInDay
-    let lifetime_cap = TERMINATION_LIFETIME_CAP * EPOCHS_IN_DAY;
+    let lifetime_cap = policy.termination_lifetime_cap * EPOCHS_IN_DAY;
     let capped_sector_age = std::cmp::min(sector_age, lifetime_cap);
 
     let mut expected_reward: TokenAmount = day_reward * capped_sector_age;
@@ -198,8 +192,8 @@ pub fn pledge_penalty_for_termination(
 
     expected_reward += replaced_day_reward * relevant_replaced_age;
 
-    let penalized_reward = expected_reward * TERMINATION_REWARD_FACTOR_NUM;
-    let penalized_reward = penalized_reward.div_floor(TERMINATION_REWARD_FACTOR_DENOM);
+    let penalized_reward = expected_reward * policy.termination_reward_factor_num;
+    let penalized_reward = penalized_reward.div_floor(policy.termination_reward_factor_denom);
 
     cmp::max(
         pledge_penalty_for_termination_lower_bound(
@@ -313,8 +307,9 @@ pub fn initial_pledge_for_power(
     cmp::min(nominal_pledge, pledge_cap)
 }
 
-pub fn consensus_fault_penalty(this_epoch_reward: TokenAmount) -> TokenAmount {
-    (this_epoch_reward * CONSENSUS_FAULT_FACTOR).div_floor(EXPECTED_LEADERS_PER_EPOCH)
+/// The penalty for a consensus fault, a multiple of the expected reward per block winner.
+pub fn consensus_fault_penalty(policy: &Policy, this_epoch_reward: TokenAmount) -> TokenAmount {
+    (this_epoch_reward * policy.consensus_fault_factor).div_floor(EXPECTED_LEADERS_PER_EPOCH)
 }
 
 /// Returns the amount of a reward to vest, and the vesting schedule, for a reward amount.
@@ -331,6 +326,7 @@ lazy_static! {
     static ref ESTIMATED_SINGLE_PRE_COMMIT_GAS_USAGE: BigInt = BigInt::from(16433324);
 }
 
+/// The network fee burnt for proving an aggregate of sectors, at a base fee.
 pub fn aggregate_prove_commit_network_fee(
     aggregate_size: usize,
     base_fee: &TokenAmount,
@@ -338,6 +334,7 @@ pub fn aggregate_prove_commit_network_fee(
     aggregate_network_fee(aggregate_size, &ESTIMATED_SINGLE_PROVE_COMMIT_GAS_USAGE, base_fee)
 }
 
+/// The network fee burnt for pre-committing a batch of sectors, at a base fee.
 pub fn aggregate_pre_commit_network_fee(
     aggregate_size: usize,
     base_fee: &TokenAmount,
@@ -345,6 +342,9 @@ pub fn aggregate_pre_commit_network_fee(
     aggregate_network_fee(aggregate_size, &ESTIMATED_SINGLE_PRE_COMMIT_GAS_USAGE, base_fee)
 }
 
+/// The network fee for a batch or aggregate of sectors, each estimated to use some gas.
+/// The fee is a fixed fraction of that gas at the base fee, or at the batch balancer if the
+/// base fee is lower, so is non-decreasing in both the size and the base fee.
 pub fn aggregate_network_fee(
     aggregate_size: usize,
     gas_usage: &BigInt,
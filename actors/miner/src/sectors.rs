@@ -59,7 +59,10 @@ impl<'db, BS: Blockstore> Sectors<'db, BS> {
             .cloned())
     }
 
-    pub fn store(&mut self, infos: Vec<SectorOnChainInfo>) -> anyhow::Result<()> {
+    pub fn store(&mut self, mut infos: Vec<SectorOnChainInfo>) -> anyhow::Result<()> {
+        // Write in ascending sector number order so consecutive writes share AMT node traversal.
+        // The sort is stable, so the last of any duplicate sector numbers is still written last.
+        infos.sort_by_key(|info| info.sector_number);
         for info in infos {
             let sector_number = info.sector_number;
 
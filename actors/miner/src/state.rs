@@ -436,6 +436,17 @@ impl State {
         deadlines.find_sector(store, sector_number)
     }
 
+    /// Returns the sectors grouped by the deadline and partition they are assigned to.
+    pub fn find_sectors<BS: Blockstore>(
+        &self,
+        policy: &Policy,
+        store: &BS,
+        sector_numbers: &BitField,
+    ) -> anyhow::Result<DeadlineSectorMap> {
+        let deadlines = self.load_deadlines(store)?;
+        deadlines.find_sectors(policy, store, sector_numbers)
+    }
+
     /// Schedules each sector to expire at its next deadline end. If it can't find
     /// any given sector, it skips it.
     ///
@@ -990,6 +1001,45 @@ impl State {
         Ok(amount_unlocked)
     }
 
+    /// Returns the epoch from which the remaining vesting funds may be released early, or None
+    /// if the miner has live sectors, pre-commit deposits, pledge, debt or outstanding
+    /// termination fees, or nothing left vesting.
+    /// The cooldown runs from the miner's last reward, which a miner without sectors no longer
+    /// earns. That epoch is taken to be the vesting period before the last vesting entry, which
+    /// is at most a quantization and step later than the reward was actually received.
+    pub fn vesting_release_epoch<BS: Blockstore>(
+        &self,
+        policy: &Policy,
+        store: &BS,
+    ) -> Result<Option<ChainEpoch>, ActorError> {
+        if self.locked_funds.is_zero()
+            || !self.fee_debt.is_zero()
+            || !self.initial_pledge.is_zero()
+            || !self.pre_commit_deposits.is_zero()
+            || !self.early_terminations.is_empty()
+        {
+            return Ok(None);
+        }
+
+        let mut live_sectors = 0;
+        self.load_deadlines(store)?
+            .for_each(store, |_, deadline| {
+                live_sectors += deadline.live_sectors;
+                Ok(())
+            })
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate deadlines")?;
+        if live_sectors > 0 {
+            return Ok(None);
+        }
+
+        let vesting_funds = self
+            .load_vesting_funds(store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load vesting funds")?;
+        Ok(vesting_funds.funds.last().map(|fund| {
+            fund.epoch - REWARD_VESTING_SPEC.vest_period + policy.vesting_release_cooldown
+        }))
+    }
+
     /// CheckVestedFunds returns the amount of vested funds that have vested before the provided epoch.
     pub fn check_vested_funds<BS: Blockstore>(
         &self,
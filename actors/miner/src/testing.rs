@@ -3,6 +3,7 @@ use crate::{
     PreCommitMap, QuantSpec, SectorOnChainInfo, SectorOnChainInfoFlags, Sectors, State,
     NO_QUANTIZATION, PRECOMMIT_CONFIG,
 };
+use fil_actors_runtime::runtime::policy_constants::MAX_SECTOR_NUMBER;
 use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::{DealWeight, MessageAccumulator};
 use fvm_ipld_bitfield::BitField;
@@ -62,6 +63,13 @@ pub fn check_state_invariants<BS: Blockstore>(
         }
     };
 
+    if let Some(max_allocated) = allocated_sectors.last() {
+        acc.require(
+            *max_allocated <= MAX_SECTOR_NUMBER,
+            format!("allocated sector number {max_allocated} exceeds max {MAX_SECTOR_NUMBER}"),
+        );
+    }
+
     check_precommits(policy, state, store, &allocated_sectors, &acc);
 
     let mut all_sectors: BTreeMap<SectorNumber, SectorOnChainInfo> = BTreeMap::new();
@@ -894,6 +902,12 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
                     partition.unproven.is_empty(),
                     "snapshot partition has unproven sectors",
                 );
+                require_contains_all(
+                    &partition.live_sectors(),
+                    &partition.faults,
+                    &acc,
+                    "snapshot partition live does not contain faults",
+                );
 
                 Ok(())
             });
@@ -902,11 +916,42 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
         Err(e) => acc.add(format!("error loading partitions snapshot: {e}")),
     };
 
-    // Check that we don't have any proofs proving partitions that are not in the snapshot.
+    // Check that the proofs submitted this proving period prove disjoint partitions, all of
+    // which have been recorded as posted.
+    match deadline.optimistic_proofs_amt(store) {
+        Ok(proofs) => {
+            let mut proven = BitField::new();
+            let ret = proofs.for_each(|i, proof| {
+                acc.require(
+                    !proven.contains_any(&proof.partitions),
+                    format!("proof {i} proves partitions already proven by another proof"),
+                );
+                proven = BitField::union([&proven, &proof.partitions]);
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating proofs");
+            require_contains_all(
+                &deadline.partitions_posted,
+                &proven,
+                acc,
+                "proven partitions not recorded as posted",
+            );
+        }
+        Err(e) => acc.add(format!("error loading proofs: {e}")),
+    };
+
+    // Check that we don't have any proofs proving partitions that are not in the snapshot,
+    // nor partitions proven by more than one proof.
     match deadline.optimistic_proofs_snapshot_amt(store) {
         Ok(proofs_snapshot) => {
             if let Ok(partitions_snapshot) = deadline.partitions_snapshot_amt(store) {
-                let ret = proofs_snapshot.for_each(|_, proof| {
+                let mut proven = BitField::new();
+                let ret = proofs_snapshot.for_each(|i, proof| {
+                    acc.require(
+                        !proven.contains_any(&proof.partitions),
+                        format!("snapshot proof {i} proves partitions already proven by another proof"),
+                    );
+                    proven = BitField::union([&proven, &proof.partitions]);
                     for partition in proof.partitions.iter() {
                         match partitions_snapshot.get(partition) {
                             Ok(snapshot) => acc.require(
@@ -961,6 +1006,16 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
     // The queue may be a superset of the partitions that have expirations because we never remove from it.
     match BitFieldQueue::new(store, &deadline.expirations_epochs, quant) {
         Ok(expiration_queue) => {
+            let ret = expiration_queue.amt.for_each(|epoch, _| {
+                let epoch = epoch as ChainEpoch;
+                let quantized = quant.quantize_up(epoch);
+                acc.require(
+                    quantized == epoch,
+                    format!("deadline expiration queue key {epoch} is not quantized, expected {quantized}"),
+                );
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating deadline expiration queue");
             for (epoch, expiring_idx) in partitions_with_expirations {
                 match expiration_queue.amt.get(epoch as u64) {
                     Ok(expiration_bitfield) if expiration_bitfield.is_some() => {
@@ -20,11 +20,11 @@ use fvm_shared::ActorID;
 use serde::{Deserialize, Serialize};
 
 use fil_actors_runtime::reward::FilterEstimate;
-use fil_actors_runtime::{BatchReturn, DealWeight};
+use fil_actors_runtime::{validate_params, BatchReturn, DealWeight};
+pub use fil_actors_runtime::{PartitionSectors, SectorClaim};
 
 use crate::commd::CompactCommD;
 use crate::ext::verifreg::AllocationID;
-use crate::ext::verifreg::ClaimID;
 
 use super::beneficiary::*;
 
@@ -88,6 +88,14 @@ pub struct ChangeMultiaddrsParams {
     pub new_multi_addrs: Vec<BytesDe>,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct ChangePeerInfoParams {
+    /// The new peer ID, or None to leave it unchanged.
+    pub new_id: Option<BytesDe>,
+    /// The new multiaddrs, or None to leave them unchanged.
+    pub new_multi_addrs: Option<Vec<BytesDe>>,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct InternalSectorSetupForPresealParams {
     pub sectors: Vec<SectorNumber>,
@@ -251,13 +259,6 @@ pub struct ExtendSectorExpiration2Params {
     pub extensions: Vec<ExpirationExtension2>,
 }
 
-#[derive(Clone, Debug, Serialize_tuple, Deserialize_tuple)]
-pub struct SectorClaim {
-    pub sector_number: SectorNumber,
-    pub maintain_claims: Vec<ClaimID>,
-    pub drop_claims: Vec<ClaimID>,
-}
-
 #[derive(Clone, Debug, Serialize_tuple, Deserialize_tuple)]
 pub struct ExpirationExtension2 {
     pub deadline: u64,
@@ -286,12 +287,8 @@ pub struct TerminateSectorsParams {
     pub terminations: Vec<TerminationDeclaration>,
 }
 
-#[derive(Serialize_tuple, Deserialize_tuple)]
-pub struct TerminationDeclaration {
-    pub deadline: u64,
-    pub partition: u64,
-    pub sectors: BitField,
-}
+/// Sectors in a partition being terminated.
+pub type TerminationDeclaration = PartitionSectors;
 
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct TerminateSectorsReturn {
@@ -308,28 +305,26 @@ pub struct DeclareFaultsParams {
     pub faults: Vec<FaultDeclaration>,
 }
 
-#[derive(Serialize_tuple, Deserialize_tuple)]
-pub struct FaultDeclaration {
-    /// The deadline to which the faulty sectors are assigned, in range [0..WPoStPeriodDeadlines)
-    pub deadline: u64,
-    /// Partition index within the deadline containing the faulty sectors.
-    pub partition: u64,
-    /// Sectors in the partition being declared faulty.
-    pub sectors: BitField,
-}
+/// Sectors in a partition being declared faulty.
+pub type FaultDeclaration = PartitionSectors;
 
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct DeclareFaultsRecoveredParams {
     pub recoveries: Vec<RecoveryDeclaration>,
 }
 
+/// Sectors in a partition being declared recovered.
+pub type RecoveryDeclaration = PartitionSectors;
+
+/// Sectors being declared faulty, wherever they are assigned.
 #[derive(Serialize_tuple, Deserialize_tuple)]
-pub struct RecoveryDeclaration {
-    /// The deadline to which the recovered sectors are assigned, in range [0..WPoStPeriodDeadlines)
-    pub deadline: u64,
-    /// Partition index within the deadline containing the recovered sectors.
-    pub partition: u64,
-    /// Sectors in the partition being declared recovered.
+pub struct DeclareFaultsBySectorParams {
+    pub sectors: BitField,
+}
+
+/// Sectors being declared recovered, wherever they are assigned.
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct DeclareFaultsRecoveredBySectorParams {
     pub sectors: BitField,
 }
 
@@ -359,6 +354,8 @@ pub struct WithdrawBalanceParams {
     pub amount_requested: TokenAmount,
 }
 
+validate_params!(WithdrawBalanceParams { amount_requested: non_negative });
+
 #[derive(Serialize_tuple, Deserialize_tuple)]
 #[serde(transparent)]
 pub struct WithdrawBalanceReturn {
@@ -479,6 +476,8 @@ pub struct ApplyRewardParams {
     pub penalty: TokenAmount,
 }
 
+validate_params!(ApplyRewardParams { reward: non_negative, penalty: non_negative });
+
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Serialize_tuple, Deserialize_tuple)]
 pub struct DisputeWindowedPoStParams {
     pub deadline: u64,
@@ -604,11 +603,142 @@ pub struct GetAvailableBalanceReturn {
     pub available_balance: TokenAmount,
 }
 
+/// A breakdown of a miner's balance.
+/// The available balance is the balance less the pre-commit deposits, initial pledge and
+/// locked funds, and less the fee debt. It is negative if the miner can't cover its debt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetBalancesReturn {
+    pub balance: TokenAmount,
+    pub available_balance: TokenAmount,
+    pub pre_commit_deposits: TokenAmount,
+    pub initial_pledge: TokenAmount,
+    /// Rewards vesting.
+    pub locked_funds: TokenAmount,
+    pub fee_debt: TokenAmount,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct GetVestingFundsReturn {
     pub vesting_funds: Vec<(ChainEpoch, TokenAmount)>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct GetVestingReleaseEpochReturn {
+    /// The epoch from which the vesting funds may be released,
+    /// or None if the miner may not release them.
+    pub epoch: Option<ChainEpoch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ReleaseVestingFundsReturn {
+    pub amount_released: TokenAmount,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct GetDeadlineInfoParams {
+    /// The epoch at which to calculate the deadline.
+    pub epoch: ChainEpoch,
+}
+
+validate_params!(GetDeadlineInfoParams { epoch: non_negative });
+
+/// The Window PoSt deadline open at an epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetDeadlineInfoReturn {
+    /// First epoch of the proving period containing the deadline.
+    pub period_start: ChainEpoch,
+    /// Index of the deadline within its proving period.
+    pub index: u64,
+    /// First epoch from which a proof may be submitted.
+    pub open: ChainEpoch,
+    /// First epoch from which a proof may no longer be submitted.
+    pub close: ChainEpoch,
+    /// Epoch at which the chain is sampled for the challenge.
+    pub challenge: ChainEpoch,
+    /// First epoch at which a fault declaration for the deadline is rejected.
+    pub fault_cutoff: ChainEpoch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetSectorsParams {
+    /// Numbers of the sectors to look up.
+    pub sectors: BitField,
+    /// Sector number from which to resume the lookup, as returned by a previous call.
+    /// None starts from the lowest requested sector.
+    pub cursor: Option<SectorNumber>,
+    /// Maximum number of requested sector numbers to look up.
+    pub limit: u64,
+}
+
+validate_params!(GetSectorsParams { limit: range(1, u64::MAX) });
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetSectorsReturn {
+    /// Summaries of the requested sectors that exist, in sector number order.
+    pub sectors: Vec<SectorSummary>,
+    /// Cursor to pass to a subsequent call to continue the lookup, if requested sectors remain.
+    pub next_cursor: Option<SectorNumber>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct GetDisputableWindowedPoStsParams {
+    /// Index of the deadline whose proofs to return.
+    pub deadline: u64,
+}
+
+/// The Window PoSts of a deadline which may be disputed with DisputeWindowedPoSt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetDisputableWindowedPoStsReturn {
+    /// Epoch at which the chain was sampled for the challenge the proofs respond to.
+    pub challenge_epoch: ChainEpoch,
+    /// Proofs from the deadline's last challenge window that have not been disputed,
+    /// in submission order.
+    pub posts: Vec<DisputableWindowedPoSt>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct DisputableWindowedPoSt {
+    /// Index of the proof within the deadline, to dispute it by.
+    pub post_index: u64,
+    /// Partitions proved by the proof.
+    pub partitions: BitField,
+    /// The proofs, one per registered proof type of the sectors proven.
+    pub proofs: Vec<PoStProof>,
+}
+
+/// The commitment and economic terms of a sector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct SectorSummary {
+    pub sector_number: SectorNumber,
+    /// CommR
+    pub sealed_cid: Cid,
+    pub activation: ChainEpoch,
+    pub expiration: ChainEpoch,
+    #[serde(with = "bigint_ser")]
+    pub deal_weight: DealWeight,
+    #[serde(with = "bigint_ser")]
+    pub verified_deal_weight: DealWeight,
+    pub initial_pledge: TokenAmount,
+}
+
+impl From<&SectorOnChainInfo> for SectorSummary {
+    fn from(info: &SectorOnChainInfo) -> Self {
+        Self {
+            sector_number: info.sector_number,
+            sealed_cid: info.sealed_cid,
+            activation: info.activation,
+            expiration: info.expiration,
+            deal_weight: info.deal_weight.clone(),
+            verified_deal_weight: info.verified_deal_weight.clone(),
+            initial_pledge: info.initial_pledge.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct GetPeerIDReturn {
     #[serde(with = "strict_bytes")]
@@ -2,6 +2,7 @@ use fil_actor_miner::detail::BATCH_BALANCER;
 use fil_actor_miner::{aggregate_pre_commit_network_fee, aggregate_prove_commit_network_fee};
 use fvm_shared::econ::TokenAmount;
 use num_traits::zero;
+use rand::prelude::*;
 
 #[test]
 fn constant_fee_per_sector_when_base_fee_is_below_5_nfil() {
@@ -87,3 +88,21 @@ fn split_25_75() {
         }
     }
 }
+
+#[test]
+fn fee_is_monotonic_in_size_and_base_fee() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for fee_func in [aggregate_prove_commit_network_fee, aggregate_pre_commit_network_fee] {
+        for _ in 0..1_000 {
+            let size = rng.gen_range(0..1_000);
+            let more = size + rng.gen_range(0..1_000);
+            let base_fee = TokenAmount::from_atto(rng.gen_range(0..20_000_000_000u64));
+            let higher = &base_fee + TokenAmount::from_atto(rng.gen_range(0..20_000_000_000u64));
+
+            let fee = fee_func(size, &base_fee);
+            assert!(fee <= fee_func(more, &base_fee), "size {size} {more} at {base_fee}");
+            assert!(fee <= fee_func(size, &higher), "base fee {base_fee} {higher} at {size}");
+            assert!(fee_func(size, &BATCH_BALANCER) <= fee);
+        }
+    }
+}
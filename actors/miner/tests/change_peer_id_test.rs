@@ -52,6 +52,7 @@ fn change_peer_id_restricted_correctly() {
     // call the exported setter
 
     rt.expect_validate_caller_addr(h.caller_addrs());
+    util::expect_peer_info_changed_event(&rt, Some(&new_id), None);
 
     rt.call::<Actor>(Method::ChangePeerIDExported as u64, params).unwrap();
 
@@ -89,6 +89,7 @@ fn compacting_a_partition_with_both_live_and_dead_sectors_removes_dead_sectors_r
     );
     let sector_age = *rt.epoch.borrow() - terminated_sector.activation;
     let expected_fee = pledge_penalty_for_termination(
+        &rt.policy,
         &day_reward,
         sector_age,
         &twenty_day_reward,
@@ -1171,6 +1171,30 @@ fn cannot_declare_faults_recovered_in_missing_partitions() {
     assert_eq!(err.exit_code(), ExitCode::USR_NOT_FOUND);
 }
 
+#[test]
+fn invariants_flag_proofs_for_partitions_not_posted() {
+    let (_, rt) = setup();
+    let mut deadline = Deadline::new(rt.store()).unwrap();
+    add_sectors(&rt, &mut deadline, false);
+
+    // Record a proof for partition 1 without recording its sectors as proven.
+    deadline.record_post_proofs(rt.store(), &bitfield_from_slice(&[1]), &[]).unwrap();
+
+    let acc = MessageAccumulator::default();
+    check_deadline_state_invariants(
+        &deadline,
+        rt.store(),
+        QUANT_SPEC,
+        SECTOR_SIZE,
+        &sectors_as_map(&sectors()),
+        &acc,
+    );
+    assert!(acc
+        .messages()
+        .iter()
+        .any(|msg| msg.contains("proven partitions not recorded as posted")));
+}
+
 fn deadline_state() -> ExpectedDeadlineState {
     ExpectedDeadlineState {
         quant: QUANT_SPEC,
@@ -1,8 +1,16 @@
 use fil_actor_miner::pledge_penalty_for_continued_fault;
 use fil_actor_miner::power_for_sectors;
+use fil_actor_miner::{Actor, DeclareFaultsBySectorParams, Method, PowerPair};
+use fil_actors_runtime::test_utils::{
+    expect_abort, expect_abort_contains_message, ACCOUNT_ACTOR_CODE_ID,
+};
+use fvm_ipld_bitfield::BitField;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
 
+use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
 
 use num_traits::Zero;
 
@@ -64,3 +72,82 @@ fn declare_fault_pays_fee_at_window_post() {
     );
     h.check_state(&rt);
 }
+
+#[test]
+fn declare_faults_by_sector_finds_deadlines_and_partitions() {
+    let mut h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+    let all_sectors =
+        h.commit_and_prove_sectors(&rt, 2, DEFAULT_SECTOR_EXPIRATION as u64, vec![], true);
+    let pwr = power_for_sectors(h.sector_size, &all_sectors);
+
+    // advance to first proving period and submit so we'll have time to declare the fault next cycle
+    h.advance_and_submit_posts(&rt, &all_sectors);
+
+    // Declare the sectors as faulted, naming only their numbers
+    h.declare_faults_by_sector(&rt, &all_sectors);
+
+    // faults are recorded in the partitions holding the sectors
+    let st = h.get_state(&rt);
+    let mut faulty_power = PowerPair::zero();
+    for sector in &all_sectors {
+        let (dl_idx, p_idx) = st.find_sector(&rt.store, sector.sector_number).unwrap();
+        let partition = h.get_deadline(&rt, dl_idx).load_partition(&rt.store, p_idx).unwrap();
+        assert!(partition.faults.get(sector.sector_number));
+        faulty_power += &power_for_sectors(h.sector_size, std::slice::from_ref(sector));
+    }
+    assert_eq!(pwr, faulty_power);
+    h.check_state(&rt);
+}
+
+#[test]
+fn declare_faults_by_sector_rejects_unknown_sector() {
+    let mut h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+    let all_sectors =
+        h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION as u64, vec![], true);
+    h.advance_and_submit_posts(&rt, &all_sectors);
+
+    let unknown = all_sectors[0].sector_number + 1;
+    let params = DeclareFaultsBySectorParams {
+        sectors: BitField::try_from_bits([all_sectors[0].sector_number, unknown]).unwrap(),
+    };
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, h.worker);
+    rt.expect_validate_caller_addr(h.caller_addrs());
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        &format!("sector {} not due at any deadline", unknown),
+        rt.call::<Actor>(
+            Method::DeclareFaultsBySector as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.reset();
+    h.check_state(&rt);
+}
+
+#[test]
+fn declare_faults_by_sector_validates_caller_before_locating_sectors() {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+
+    // The sector doesn't exist, but the caller is rejected before it is looked up.
+    let params = DeclareFaultsBySectorParams { sectors: BitField::try_from_bits([100]).unwrap() };
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(1234));
+    rt.expect_validate_caller_addr(h.caller_addrs());
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        rt.call::<Actor>(
+            Method::DeclareFaultsBySector as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.verify();
+    h.check_state(&rt);
+}
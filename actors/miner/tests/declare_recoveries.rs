@@ -46,6 +46,33 @@ fn recovery_happy_path() {
     h.check_state(&rt);
 }
 
+#[test]
+fn recovery_by_sector_finds_deadline_and_partition() {
+    let (mut h, rt) = setup();
+    let one_sector = h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+
+    // advance to first proving period and submit so we'll have time to declare the fault next cycle
+    h.advance_and_submit_posts(&rt, &one_sector);
+
+    // Declare the sector as faulted
+    h.declare_faults(&rt, &one_sector);
+
+    // Declare recoveries naming only the sector number
+    h.declare_recoveries_by_sector(
+        &rt,
+        BitField::try_from_bits([one_sector[0].sector_number]).unwrap(),
+        TokenAmount::zero(),
+    )
+    .unwrap();
+
+    let st = h.get_state(&rt);
+    let (dl_idx, p_idx) = st.find_sector(&rt.store, one_sector[0].sector_number).unwrap();
+    let dl = h.get_deadline(&rt, dl_idx);
+    let p = dl.load_partition(&rt.store, p_idx).unwrap();
+    assert_eq!(p.faults, p.recoveries);
+    h.check_state(&rt);
+}
+
 #[test]
 fn recovery_must_pay_back_fee_debt() {
     let (mut h, rt) = setup();
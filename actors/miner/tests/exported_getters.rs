@@ -1,13 +1,19 @@
 use fil_actor_miner::{
-    Actor, GetAvailableBalanceReturn, GetOwnerReturn, GetSectorSizeReturn,
-    IsControllingAddressParam, IsControllingAddressReturn, Method,
+    Actor, GetAvailableBalanceReturn, GetBalancesReturn, GetDeadlineInfoParams,
+    GetDeadlineInfoReturn, GetOwnerReturn, GetSectorSizeReturn, GetSectorsParams, GetSectorsReturn,
+    IsControllingAddressParam, IsControllingAddressReturn, Method, SectorSummary,
 };
+use fil_actors_runtime::frc46::UniversalReceiverParams;
 use fil_actors_runtime::runtime::policy_constants::MAX_SECTOR_NUMBER;
-use fil_actors_runtime::test_utils::EVM_ACTOR_CODE_ID;
+use fil_actors_runtime::test_utils::{expect_abort, EVM_ACTOR_CODE_ID};
 use fil_actors_runtime::INIT_ACTOR_ADDR;
+use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
 use fvm_shared::{clock::ChainEpoch, econ::TokenAmount};
+use num_traits::Zero;
 use std::ops::Sub;
 
 mod util;
@@ -144,3 +150,153 @@ fn collateral_getters() {
 
     h.check_state(&rt);
 }
+
+#[test]
+fn balances_getter() {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    let precommit_epoch = PERIOD_OFFSET + 1;
+    rt.set_epoch(precommit_epoch);
+    h.construct_and_verify(&rt);
+    let dl_info = h.deadline(&rt);
+
+    let expiration =
+        dl_info.period_end() + DEFAULT_SECTOR_EXPIRATION * rt.policy.wpost_proving_period;
+    let precommit_params = h.make_pre_commit_params(100, precommit_epoch - 1, expiration, vec![]);
+    h.pre_commit_sector_and_get(&rt, precommit_params, PreCommitConfig::empty(), true);
+
+    let fee_debt = TokenAmount::from_whole(2);
+    let mut st = h.get_state(&rt);
+    st.fee_debt = fee_debt.clone();
+    rt.replace_state(&st);
+
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    rt.expect_validate_caller_any();
+    let ret: GetBalancesReturn = rt
+        .call::<Actor>(Method::GetBalancesExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+
+    assert!(st.pre_commit_deposits.is_positive());
+    assert_eq!(
+        GetBalancesReturn {
+            balance: BIG_BALANCE.clone(),
+            available_balance: &*BIG_BALANCE - &st.pre_commit_deposits - &fee_debt,
+            pre_commit_deposits: st.pre_commit_deposits.clone(),
+            initial_pledge: TokenAmount::zero(),
+            locked_funds: TokenAmount::zero(),
+            fee_debt,
+        },
+        ret
+    );
+    h.check_state(&rt);
+}
+
+#[test]
+fn deadline_info_getter() {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+
+    let get_deadline_info = |epoch: ChainEpoch| {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<Actor>(
+            Method::GetDeadlineInfoExported as u64,
+            IpldBlock::serialize_cbor(&GetDeadlineInfoParams { epoch }).unwrap(),
+        );
+        rt.verify();
+        ret.map(|r| r.unwrap().deserialize::<GetDeadlineInfoReturn>().unwrap())
+    };
+
+    // An epoch within the fourth deadline of some later proving period.
+    let window = rt.policy.wpost_challenge_window;
+    let period_start = PERIOD_OFFSET + 2 * rt.policy.wpost_proving_period;
+    let open = period_start + 3 * window;
+    let ret = get_deadline_info(open + 5).unwrap();
+    assert_eq!(period_start, ret.period_start);
+    assert_eq!(3, ret.index);
+    assert_eq!(open, ret.open);
+    assert_eq!(open + window, ret.close);
+    assert_eq!(open - rt.policy.wpost_challenge_lookback, ret.challenge);
+    assert_eq!(open - rt.policy.fault_declaration_cutoff, ret.fault_cutoff);
+
+    // The current deadline matches the state's.
+    let dl_info = h.deadline(&rt);
+    let ret = get_deadline_info(*rt.epoch.borrow()).unwrap();
+    assert_eq!((dl_info.index, dl_info.open), (ret.index, ret.open));
+
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, get_deadline_info(-1));
+    h.check_state(&rt);
+}
+
+#[test]
+fn sectors_getter() {
+    let mut h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+    let infos = h.commit_and_prove_sectors(&rt, 3, DEFAULT_SECTOR_EXPIRATION as u64, vec![], true);
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+
+    let get_sectors = |sectors: &BitField, cursor: Option<u64>, limit: u64| {
+        rt.expect_validate_caller_any();
+        let params = GetSectorsParams { sectors: sectors.clone(), cursor, limit };
+        let ret = rt.call::<Actor>(
+            Method::GetSectorsExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        ret.map(|r| r.unwrap().deserialize::<GetSectorsReturn>().unwrap())
+    };
+
+    // A missing sector is omitted, but still counts towards the limit.
+    let missing = infos[2].sector_number + 100;
+    let requested = BitField::try_from_bits([
+        infos[0].sector_number,
+        infos[2].sector_number,
+        missing,
+        infos[1].sector_number,
+    ])
+    .unwrap();
+    let ret = get_sectors(&requested, None, 2).unwrap();
+    assert_eq!(vec![SectorSummary::from(&infos[0]), SectorSummary::from(&infos[1])], ret.sectors);
+    assert_eq!(Some(infos[2].sector_number), ret.next_cursor);
+    assert_eq!(infos[0].sealed_cid, ret.sectors[0].sealed_cid);
+    assert_eq!(infos[0].initial_pledge, ret.sectors[0].initial_pledge);
+
+    let ret = get_sectors(&requested, ret.next_cursor, 2).unwrap();
+    assert_eq!(vec![SectorSummary::from(&infos[2])], ret.sectors);
+    assert_eq!(None, ret.next_cursor);
+
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, get_sectors(&requested, None, 0));
+    h.check_state(&rt);
+}
+
+#[test]
+fn token_receiver() {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    rt.expect_validate_caller_any();
+    let ret = rt
+        .call::<Actor>(
+            Method::UniversalReceiverHook as u64,
+            IpldBlock::serialize_cbor(&UniversalReceiverParams {
+                type_: 0,
+                payload: RawBytes::new(vec![1, 2, 3]),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+    assert!(ret.is_none());
+    rt.verify();
+}
@@ -1,8 +1,12 @@
 use fil_actors_runtime::test_utils::*;
 
-use fil_actor_miner::{Actor, ChangeMultiaddrsParams, GetMultiaddrsReturn, Method};
+use fil_actor_miner::{
+    Actor, ChangeMultiaddrsParams, ChangePeerInfoParams, GetMultiaddrsReturn, GetPeerIDReturn,
+    Method,
+};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::BytesDe;
+use fvm_shared::address::Address;
 use fvm_shared::error::ExitCode;
 
 mod util;
@@ -90,22 +94,8 @@ fn cant_set_large_multiaddrs() {
     let rt = MockRuntime::default();
     let h = util::ActorHarness::new(0);
 
-    let mut maddrs = Vec::new();
-    for i in 0..100 {
-        maddrs.push(BytesDe(vec![
-            i + 1,
-            i + 2,
-            i + 3,
-            i + 4,
-            i + 5,
-            i + 6,
-            i + 7,
-            i + 8,
-            i + 9,
-            i + 10,
-            i + 11,
-        ]));
-    }
+    let maddr_size = rt.policy.max_multiaddr_data / 10 + 1;
+    let maddrs = vec![BytesDe(vec![1; maddr_size]); 10];
 
     h.construct_and_verify(&rt);
     h.set_multiaddr_fail(&rt, maddrs);
@@ -140,6 +130,7 @@ fn get_and_change_multiaddrs_restricted_correctly() {
     // call the exported setter
 
     rt.expect_validate_caller_addr(h.caller_addrs());
+    util::expect_peer_info_changed_event(&rt, None, Some(&new_multiaddrs));
 
     rt.call::<Actor>(Method::ChangeMultiaddrsExported as u64, params).unwrap();
     rt.verify();
@@ -159,3 +150,126 @@ fn get_and_change_multiaddrs_restricted_correctly() {
 
     h.check_state(&rt);
 }
+
+#[test]
+fn cant_set_too_many_multiaddrs() {
+    let rt = MockRuntime::default();
+    let h = util::ActorHarness::new(0);
+    let maddrs = vec![BytesDe(vec![1]); rt.policy.max_multiaddr_count + 1];
+
+    h.construct_and_verify(&rt);
+    h.set_multiaddr_fail(&rt, maddrs);
+
+    h.check_state(&rt);
+}
+
+fn change_peer_info(rt: &MockRuntime, h: &util::ActorHarness, params: &ChangePeerInfoParams) {
+    rt.set_caller(*EVM_ACTOR_CODE_ID, h.worker);
+    rt.expect_validate_caller_addr(h.caller_addrs());
+    util::expect_peer_info_changed_event(
+        rt,
+        params.new_id.as_ref().map(|id| id.0.as_slice()),
+        params.new_multi_addrs.as_deref(),
+    );
+    rt.call::<Actor>(
+        Method::ChangePeerInfoExported as u64,
+        IpldBlock::serialize_cbor(params).unwrap(),
+    )
+    .unwrap();
+    rt.verify();
+}
+
+fn peer_info(rt: &MockRuntime) -> (Vec<u8>, Vec<BytesDe>) {
+    rt.expect_validate_caller_any();
+    let peer: GetPeerIDReturn = rt
+        .call::<Actor>(Method::GetPeerIDExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.expect_validate_caller_any();
+    let maddrs: GetMultiaddrsReturn = rt
+        .call::<Actor>(Method::GetMultiaddrsExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    (peer.peer_id, maddrs.multi_addrs)
+}
+
+#[test]
+fn can_change_peer_id_and_multiaddrs_together() {
+    let rt = MockRuntime::default();
+    let h = util::ActorHarness::new(0);
+    h.construct_and_verify(&rt);
+
+    let maddrs = vec![BytesDe(vec![1, 3, 3, 7]), BytesDe(vec![2, 4, 4, 8])];
+    change_peer_info(
+        &rt,
+        &h,
+        &ChangePeerInfoParams {
+            new_id: Some(BytesDe(vec![1, 2, 3])),
+            new_multi_addrs: Some(maddrs.clone()),
+        },
+    );
+    assert_eq!((vec![1, 2, 3], maddrs.clone()), peer_info(&rt));
+
+    // Omitted values are left unchanged.
+    change_peer_info(
+        &rt,
+        &h,
+        &ChangePeerInfoParams { new_id: Some(BytesDe(vec![4, 5])), new_multi_addrs: None },
+    );
+    assert_eq!((vec![4, 5], maddrs), peer_info(&rt));
+
+    change_peer_info(
+        &rt,
+        &h,
+        &ChangePeerInfoParams { new_id: None, new_multi_addrs: Some(vec![]) },
+    );
+    assert_eq!((vec![4, 5], vec![]), peer_info(&rt));
+
+    h.check_state(&rt);
+}
+
+#[test]
+fn change_peer_info_validates_params_and_caller() {
+    let rt = MockRuntime::default();
+    let h = util::ActorHarness::new(0);
+    h.construct_and_verify(&rt);
+    let initial = peer_info(&rt);
+
+    // An invalid multiaddr rejects the whole change.
+    let params = ChangePeerInfoParams {
+        new_id: Some(BytesDe(vec![1, 2, 3])),
+        new_multi_addrs: Some(vec![BytesDe(vec![])]),
+    };
+    rt.set_caller(*EVM_ACTOR_CODE_ID, h.worker);
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "invalid empty multiaddr",
+        rt.call::<Actor>(
+            Method::ChangePeerInfoExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.verify();
+
+    // Only the owner, worker or a control address may change peer info.
+    let params =
+        ChangePeerInfoParams { new_id: Some(BytesDe(vec![1, 2, 3])), new_multi_addrs: None };
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    rt.expect_validate_caller_addr(h.caller_addrs());
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        rt.call::<Actor>(
+            Method::ChangePeerInfoExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.verify();
+    assert_eq!(initial, peer_info(&rt));
+
+    h.check_state(&rt);
+}
@@ -5,6 +5,7 @@ use fil_actor_miner::PowerPair;
 use fil_actors_runtime::runtime::DomainSeparationTag;
 use fil_actors_runtime::test_utils::*;
 use fvm_ipld_bitfield::BitField;
+use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
@@ -1135,6 +1136,93 @@ fn cant_dispute_up_with_an_invalid_deadline() {
     rt.verify();
 }
 
+#[test]
+fn disputable_posts_are_returned_during_the_dispute_window() {
+    let period_offset = ChainEpoch::from(100);
+    let precommit_epoch = ChainEpoch::from(1);
+
+    let mut h = ActorHarness::new(period_offset);
+    h.set_proof_type(RegisteredSealProof::StackedDRG2KiBV1P1);
+
+    let rt = h.new_runtime();
+    rt.epoch.replace(precommit_epoch);
+    rt.balance.replace(BIG_BALANCE.clone());
+
+    h.construct_and_verify(&rt);
+
+    let sectors = h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    let sector = sectors[0].clone();
+    let pwr = miner::power_for_sector(h.sector_size, &sector);
+
+    let get_disputable = |deadline: u64| {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<miner::Actor>(
+            miner::Method::GetDisputableWindowedPoStsExported as u64,
+            IpldBlock::serialize_cbor(&miner::GetDisputableWindowedPoStsParams { deadline })
+                .unwrap(),
+        );
+        rt.verify();
+        ret.map(|r| r.unwrap().deserialize::<miner::GetDisputableWindowedPoStsReturn>().unwrap())
+    };
+
+    let state = h.get_state(&rt);
+    let (dlidx, pidx) = state.find_sector(&rt.store, sector.sector_number).unwrap();
+    let dlinfo = h.advance_to_deadline(&rt, dlidx);
+    h.submit_window_post(
+        &rt,
+        &dlinfo,
+        vec![miner::PoStPartition { index: pidx, skipped: make_empty_bitfield() }],
+        vec![sector.clone()],
+        PoStConfig::with_expected_power_delta(&pwr),
+    );
+
+    // Nothing may be disputed while the challenge window is open.
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    let ret = get_disputable(dlidx).unwrap();
+    assert!(ret.posts.is_empty());
+
+    // Once the window closes, the proof is returned with the challenge it responded to.
+    h.advance_deadline(&rt, CronConfig::empty());
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    let ret = get_disputable(dlidx).unwrap();
+    assert_eq!(dlinfo.challenge, ret.challenge_epoch);
+    let submitted = amt_to_vec::<miner::WindowedPoSt>(
+        &rt,
+        &h.get_deadline(&rt, dlidx).optimistic_post_submissions_snapshot,
+    );
+    assert_eq!(
+        vec![miner::DisputableWindowedPoSt {
+            post_index: 0,
+            partitions: submitted[0].partitions.clone(),
+            proofs: submitted[0].proofs.clone(),
+        }],
+        ret.posts
+    );
+    assert_bitfield_equals(&ret.posts[0].partitions, &[pidx]);
+
+    // A disputed proof is no longer returned.
+    let expected_result = PoStDisputeResult {
+        expected_power_delta: Some(-pwr.clone()),
+        expected_penalty: Some(miner::pledge_penalty_for_invalid_windowpost(
+            &h.epoch_reward_smooth,
+            &h.epoch_qa_power_smooth,
+            &pwr.qa,
+        )),
+        expected_reward: Some(miner::BASE_REWARD_FOR_DISPUTED_WINDOW_POST.clone()),
+        expected_pledge_delta: None,
+    };
+    h.dispute_window_post(&rt, &dlinfo, 0, &[sector], Some(expected_result));
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    assert!(get_disputable(dlidx).unwrap().posts.is_empty());
+
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "invalid deadline",
+        get_disputable(rt.policy.wpost_period_deadlines),
+    );
+    h.check_state(&rt);
+}
+
 #[test]
 fn can_dispute_test_after_proving_period_changes() {
     let period_offset = ChainEpoch::from(100);
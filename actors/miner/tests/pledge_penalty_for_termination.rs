@@ -1,9 +1,12 @@
 use fil_actor_miner::{
     pledge_penalty_for_termination, pledge_penalty_for_termination_lower_bound,
-    INITIAL_PLEDGE_FACTOR, TERMINATION_LIFETIME_CAP, TERMINATION_REWARD_FACTOR_DENOM,
-    TERMINATION_REWARD_FACTOR_NUM,
+    INITIAL_PLEDGE_FACTOR,
 };
 use fil_actors_runtime::reward::FilterEstimate;
+use fil_actors_runtime::runtime::policy_constants::{
+    TERMINATION_LIFETIME_CAP, TERMINATION_REWARD_FACTOR_DENOM, TERMINATION_REWARD_FACTOR_NUM,
+};
+use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::EPOCHS_IN_DAY;
 use fvm_shared::bigint::{BigInt, Zero};
 use fvm_shared::econ::TokenAmount;
@@ -50,6 +53,7 @@ fn when_undeclared_fault_fee_exceeds_expected_reward_returns_undeclared_fault_fe
     let sector_age = sector_age_in_days * EPOCHS_IN_DAY;
 
     let fee = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         sector_age,
         &twenty_day_reward,
@@ -73,6 +77,7 @@ fn when_expected_reward_exceeds_undeclared_fault_fee_returns_expected_reward() {
     let sector_age = sector_age_in_days * EPOCHS_IN_DAY;
 
     let fee = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         sector_age,
         &twenty_day_reward,
@@ -99,6 +104,7 @@ fn sector_age_is_capped() {
     let sector_age = sector_age_in_days * EPOCHS_IN_DAY;
 
     let fee = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         sector_age,
         &twenty_day_reward,
@@ -116,6 +122,29 @@ fn sector_age_is_capped() {
     assert_eq!(expected_fee, fee);
 }
 
+#[test]
+fn fee_follows_policy() {
+    let initial_pledge = undeclared_penalty();
+    let day_reward = initial_pledge.div_floor(INITIAL_PLEDGE_FACTOR);
+    let twenty_day_reward = &day_reward * INITIAL_PLEDGE_FACTOR;
+    let sector_age = 500 * EPOCHS_IN_DAY;
+
+    // The whole of 10 days' rewards are charged, rather than half of the default cap's.
+    let policy = Policy::default().builder().termination_fee(1, 1, 10).build();
+    let fee = pledge_penalty_for_termination(
+        &policy,
+        &day_reward,
+        sector_age,
+        &twenty_day_reward,
+        &power_estimate(),
+        &qa_sector_power(),
+        &reward_estimate(),
+        &TokenAmount::zero(),
+        0,
+    );
+    assert_eq!(&initial_pledge + &day_reward * 10, fee);
+}
+
 #[test]
 fn fee_for_replacement_eq_fee_for_original_sector_when_power_br_are_unchanged() {
     // initialPledge equal to undeclaredPenalty guarantees expected reward is greater
@@ -130,6 +159,7 @@ fn fee_for_replacement_eq_fee_for_original_sector_when_power_br_are_unchanged()
 
     // fee for old sector if had terminated when it was replaced
     let unreplaced_fee = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         sector_age,
         &twenty_day_reward,
@@ -142,6 +172,7 @@ fn fee_for_replacement_eq_fee_for_original_sector_when_power_br_are_unchanged()
 
     // actual fee including replacement parameters
     let actual_fee = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         replacement_age,
         &twenty_day_reward,
@@ -169,6 +200,7 @@ fn fee_for_replacement_eq_fee_for_same_sector_without_replacement_after_lifetime
 
     // fee for new sector with no replacement
     let noreplace = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         replacement_age,
         &twenty_day_reward,
@@ -181,6 +213,7 @@ fn fee_for_replacement_eq_fee_for_same_sector_without_replacement_after_lifetime
 
     // actual fee including replacement parameters
     let with_replace = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         replacement_age,
         &twenty_day_reward,
@@ -216,6 +249,7 @@ fn charges_for_replaced_sector_at_replaced_sector_day_rate() {
     let expected_fee = &twenty_day_reward + old_penalty + new_penalty;
 
     let fee = pledge_penalty_for_termination(
+        &Policy::default(),
         &day_reward,
         replacement_age,
         &twenty_day_reward,
@@ -0,0 +1,112 @@
+use fil_actor_miner::{locked_reward_from_reward, REWARD_VESTING_SPEC};
+use fil_actors_runtime::runtime::RuntimePolicy;
+use fil_actors_runtime::test_utils::{expect_abort_contains_message, ACCOUNT_ACTOR_CODE_ID};
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use num_traits::Zero;
+
+mod util;
+
+use util::*;
+
+const PERIOD_OFFSET: ChainEpoch = 100;
+
+fn setup_with_rewards() -> (ActorHarness, fil_actors_runtime::test_utils::MockRuntime, TokenAmount)
+{
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+
+    let reward = TokenAmount::from_whole(10);
+    h.apply_rewards(&rt, reward.clone(), TokenAmount::zero());
+    let (locked, _) = locked_reward_from_reward(reward);
+    assert_eq!(locked, h.get_locked_funds(&rt));
+    (h, rt, locked)
+}
+
+#[test]
+fn releases_vesting_funds_after_cooldown() {
+    let (h, rt, locked) = setup_with_rewards();
+
+    let last_vest =
+        h.get_state(&rt).load_vesting_funds(&rt.store).unwrap().funds.last().unwrap().epoch;
+    let release_epoch =
+        last_vest - REWARD_VESTING_SPEC.vest_period + rt.policy().vesting_release_cooldown;
+    assert_eq!(Some(release_epoch), h.get_vesting_release_epoch(&rt));
+    // The funds are released well before they would have finished vesting.
+    assert!(release_epoch < last_vest);
+
+    rt.set_epoch(release_epoch - 1);
+    expect_abort_contains_message(
+        ExitCode::USR_FORBIDDEN,
+        "before epoch",
+        h.release_vesting_funds(&rt, &locked),
+    );
+    rt.reset();
+
+    // Funds that have vested in the mean time are released too.
+    rt.set_epoch(release_epoch);
+    h.release_vesting_funds(&rt, &locked).unwrap();
+    let st = h.get_state(&rt);
+    assert!(st.locked_funds.is_zero());
+    assert!(st.load_vesting_funds(&rt.store).unwrap().funds.is_empty());
+    assert_eq!(None, h.get_vesting_release_epoch(&rt));
+    assert_eq!(BIG_BALANCE.clone(), h.get_available_balance(&rt).unwrap());
+    h.check_state(&rt);
+}
+
+#[test]
+fn no_release_with_debt_or_pledge() {
+    let (h, rt, locked) = setup_with_rewards();
+    let release_epoch = h.get_vesting_release_epoch(&rt).unwrap();
+    rt.set_epoch(release_epoch);
+
+    let mut st = h.get_state(&rt);
+    st.fee_debt = TokenAmount::from_atto(1);
+    rt.replace_state(&st);
+    assert_eq!(None, h.get_vesting_release_epoch(&rt));
+    expect_abort_contains_message(
+        ExitCode::USR_FORBIDDEN,
+        "cannot release vesting funds",
+        h.release_vesting_funds(&rt, &locked),
+    );
+    rt.reset();
+
+    st.fee_debt = TokenAmount::zero();
+    st.initial_pledge = TokenAmount::from_atto(1);
+    rt.replace_state(&st);
+    assert_eq!(None, h.get_vesting_release_epoch(&rt));
+
+    st.initial_pledge = TokenAmount::zero();
+    rt.replace_state(&st);
+    assert_eq!(Some(release_epoch), h.get_vesting_release_epoch(&rt));
+}
+
+#[test]
+fn only_owner_can_release() {
+    let (h, rt, _) = setup_with_rewards();
+    rt.set_epoch(h.get_vesting_release_epoch(&rt).unwrap());
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, h.worker);
+    rt.expect_validate_caller_addr(vec![h.owner]);
+    expect_abort_contains_message(
+        ExitCode::USR_FORBIDDEN,
+        "caller",
+        rt.call::<fil_actor_miner::Actor>(
+            fil_actor_miner::Method::ReleaseVestingFundsExported as u64,
+            None,
+        ),
+    );
+    rt.verify();
+}
+
+#[test]
+fn no_release_without_vesting_funds() {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+    assert_eq!(None, h.get_vesting_release_epoch(&rt));
+}
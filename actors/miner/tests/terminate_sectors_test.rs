@@ -10,9 +10,11 @@ use fil_actors_runtime::{
     BURNT_FUNDS_ACTOR_ADDR, EPOCHS_IN_DAY, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
     SYSTEM_ACTOR_ADDR,
 };
+use fvm_ipld_bitfield::iter::Ranges;
 use fvm_ipld_bitfield::BitField;
 use fvm_shared::{econ::TokenAmount, error::ExitCode, METHOD_SEND};
 use std::collections::HashMap;
+use std::iter;
 
 mod util;
 
@@ -178,6 +180,29 @@ fn cannot_terminate_a_sector_when_the_challenge_window_is_open() {
     h.check_state(&rt);
 }
 
+#[test]
+fn cannot_terminate_an_unbounded_sector_bitfield() {
+    let (h, rt) = setup();
+
+    // A single run covering every sector number encodes to a few bytes.
+    let params = TerminateSectorsParams {
+        terminations: vec![TerminationDeclaration {
+            deadline: 0,
+            partition: 0,
+            sectors: BitField::from_ranges(Ranges::new(iter::once(0..u64::MAX))),
+        }],
+    };
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, h.worker);
+    let res = rt.call::<Actor>(
+        Method::TerminateSectors as u64,
+        IpldBlock::serialize_cbor(&params).unwrap(),
+    );
+    expect_abort_contains_message(ExitCode::USR_ILLEGAL_ARGUMENT, "too many bits set", res);
+
+    h.check_state(&rt);
+}
+
 #[test]
 fn owner_cannot_terminate_if_market_fails() {
     let (mut h, rt) = setup();
@@ -329,6 +354,7 @@ fn calc_expected_fee_for_termination(
     );
     let sector_age = *rt.epoch.borrow() - sector.activation;
     pledge_penalty_for_termination(
+        &rt.policy,
         &day_reward,
         sector_age,
         &twenty_day_reward,
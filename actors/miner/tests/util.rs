@@ -15,7 +15,7 @@ use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::de::Deserialize;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::ser::Serialize;
-use fvm_ipld_encoding::{BytesDe, CborStore, RawBytes};
+use fvm_ipld_encoding::{BytesDe, BytesSer, CborStore, RawBytes};
 use fvm_shared::address::Address;
 use fvm_shared::bigint::BigInt;
 use fvm_shared::bigint::Zero;
@@ -64,22 +64,23 @@ use fil_actor_miner::{
     ChangeBeneficiaryParams, ChangeMultiaddrsParams, ChangePeerIDParams, ChangeWorkerAddressParams,
     CheckSectorProvenParams, CompactCommD, CompactPartitionsParams, CompactSectorNumbersParams,
     CronEventPayload, DataActivationNotification, Deadline, DeadlineInfo, Deadlines,
-    DeclareFaultsParams, DeclareFaultsRecoveredParams, DeferredCronEventParams,
-    DisputeWindowedPoStParams, ExpirationQueue, ExpirationSet, ExtendSectorExpiration2Params,
-    ExtendSectorExpirationParams, FaultDeclaration, GetAvailableBalanceReturn,
-    GetBeneficiaryReturn, GetControlAddressesReturn, GetMultiaddrsReturn, GetPeerIDReturn, Method,
+    DeclareFaultsBySectorParams, DeclareFaultsParams, DeclareFaultsRecoveredBySectorParams,
+    DeclareFaultsRecoveredParams, DeferredCronEventParams, DisputeWindowedPoStParams,
+    ExpirationQueue, ExpirationSet, ExtendSectorExpiration2Params, ExtendSectorExpirationParams,
+    FaultDeclaration, GetAvailableBalanceReturn, GetBeneficiaryReturn, GetControlAddressesReturn,
+    GetMultiaddrsReturn, GetPeerIDReturn, GetVestingReleaseEpochReturn, Method,
     Method as MinerMethod, MinerConstructorParams as ConstructorParams, MinerInfo, Partition,
     PendingBeneficiaryChange, PieceActivationManifest, PieceChange, PieceReturn, PoStPartition,
     PowerPair, PreCommitSectorBatchParams, PreCommitSectorBatchParams2, PreCommitSectorParams,
     ProveCommitAggregateParams, ProveCommitSectorParams, ProveCommitSectors3Params,
-    ProveCommitSectors3Return, QuantSpec, RecoveryDeclaration, ReportConsensusFaultParams,
-    SectorActivationManifest, SectorChanges, SectorContentChangedParams,
-    SectorContentChangedReturn, SectorOnChainInfo, SectorPreCommitInfo, SectorPreCommitOnChainInfo,
-    SectorReturn, SectorUpdateManifest, Sectors, State, SubmitWindowedPoStParams,
-    TerminateSectorsParams, TerminationDeclaration, VerifiedAllocationKey, VestingFunds,
-    WindowedPoSt, WithdrawBalanceParams, WithdrawBalanceReturn, CRON_EVENT_PROVING_DEADLINE,
-    NI_AGGREGATE_FEE_BASE_SECTOR_COUNT, NO_QUANTIZATION, REWARD_VESTING_SPEC, SECTORS_AMT_BITWIDTH,
-    SECTOR_CONTENT_CHANGED,
+    ProveCommitSectors3Return, QuantSpec, RecoveryDeclaration, ReleaseVestingFundsReturn,
+    ReportConsensusFaultParams, SectorActivationManifest, SectorChanges,
+    SectorContentChangedParams, SectorContentChangedReturn, SectorOnChainInfo, SectorPreCommitInfo,
+    SectorPreCommitOnChainInfo, SectorReturn, SectorUpdateManifest, Sectors, State,
+    SubmitWindowedPoStParams, TerminateSectorsParams, TerminationDeclaration,
+    VerifiedAllocationKey, VestingFunds, WindowedPoSt, WithdrawBalanceParams,
+    WithdrawBalanceReturn, CRON_EVENT_PROVING_DEADLINE, NI_AGGREGATE_FEE_BASE_SECTOR_COUNT,
+    NO_QUANTIZATION, REWARD_VESTING_SPEC, SECTORS_AMT_BITWIDTH, SECTOR_CONTENT_CHANGED,
 };
 use fil_actor_miner::{
     raw_power_for_sector, ProveCommitSectorsNIParams, ProveCommitSectorsNIReturn,
@@ -295,6 +296,7 @@ impl ActorHarness {
         caller_addrs.push(self.worker);
         caller_addrs.push(self.owner);
         rt.expect_validate_caller_addr(caller_addrs);
+        expect_peer_info_changed_event(rt, Some(&new_id), None);
 
         let result = rt
             .call::<Actor>(Method::ChangePeerID as u64, IpldBlock::serialize_cbor(&params).unwrap())
@@ -331,6 +333,7 @@ impl ActorHarness {
 
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
         rt.expect_validate_caller_addr(self.caller_addrs());
+        expect_peer_info_changed_event(rt, None, Some(&new_multiaddrs));
 
         let result = rt
             .call::<Actor>(
@@ -2283,6 +2286,64 @@ impl ActorHarness {
         ret
     }
 
+    pub fn declare_faults_by_sector(
+        &self,
+        rt: &MockRuntime,
+        fault_sector_infos: &[SectorOnChainInfo],
+    ) -> PowerPair {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
+        rt.expect_validate_caller_addr(self.caller_addrs());
+
+        let ss = fault_sector_infos[0].seal_proof.sector_size().unwrap();
+        let expected_delta = power_for_sectors(ss, fault_sector_infos).neg();
+        expect_update_power(rt, expected_delta.clone());
+
+        let sectors =
+            BitField::try_from_bits(fault_sector_infos.iter().map(|s| s.sector_number)).unwrap();
+        let params = DeclareFaultsBySectorParams { sectors };
+        rt.call::<Actor>(
+            Method::DeclareFaultsBySector as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap();
+        rt.verify();
+
+        expected_delta
+    }
+
+    pub fn declare_recoveries_by_sector(
+        &self,
+        rt: &MockRuntime,
+        recovery_sectors: BitField,
+        expected_debt_repaid: TokenAmount,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
+        rt.expect_validate_caller_addr(self.caller_addrs());
+
+        if expected_debt_repaid.is_positive() {
+            rt.expect_send_simple(
+                BURNT_FUNDS_ACTOR_ADDR,
+                METHOD_SEND,
+                None,
+                expected_debt_repaid,
+                None,
+                ExitCode::OK,
+            );
+        }
+
+        let params = DeclareFaultsRecoveredBySectorParams { sectors: recovery_sectors };
+        let ret = rt.call::<Actor>(
+            Method::DeclareFaultsRecoveredBySector as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        if ret.is_ok() {
+            rt.verify();
+        } else {
+            rt.reset();
+        }
+        ret
+    }
+
     pub fn continued_fault_penalty(&self, sectors: &[SectorOnChainInfo]) -> TokenAmount {
         let pwr = power_for_sectors(self.sector_size, sectors);
         pledge_penalty_for_continued_fault(
@@ -2389,7 +2450,7 @@ impl ActorHarness {
             ExitCode::OK,
         );
         let this_epoch_reward = TokenAmount::from_atto(self.epoch_reward_smooth.estimate());
-        let penalty_total = consensus_fault_penalty(this_epoch_reward.clone());
+        let penalty_total = consensus_fault_penalty(&rt.policy, this_epoch_reward.clone());
         let reward_total = reward_for_consensus_slash_report(&this_epoch_reward);
         rt.expect_send_simple(from, METHOD_SEND, None, reward_total.clone(), None, ExitCode::OK);
 
@@ -2557,6 +2618,7 @@ impl ActorHarness {
 
         rt.expect_validate_caller_addr(self.caller_addrs());
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
+        expect_peer_info_changed_event(rt, Some(&new_id), None);
 
         rt.call::<Actor>(Method::ChangePeerID as u64, IpldBlock::serialize_cbor(&params).unwrap())
             .unwrap();
@@ -3009,6 +3071,37 @@ impl ActorHarness {
         rt.verify();
         Ok(available_balance_ret.available_balance)
     }
+
+    pub fn get_vesting_release_epoch(&self, rt: &MockRuntime) -> Option<ChainEpoch> {
+        rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+        rt.expect_validate_caller_any();
+        let ret: GetVestingReleaseEpochReturn = rt
+            .call::<Actor>(Method::GetVestingReleaseEpochExported as u64, None)
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret.epoch
+    }
+
+    pub fn release_vesting_funds(
+        &self,
+        rt: &MockRuntime,
+        expected_released: &TokenAmount,
+    ) -> Result<(), ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.owner);
+        rt.expect_validate_caller_addr(vec![self.owner]);
+        expect_update_pledge(rt, &expected_released.neg());
+        let ret: ReleaseVestingFundsReturn = rt
+            .call::<Actor>(Method::ReleaseVestingFundsExported as u64, None)?
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        assert_eq!(expected_released, &ret.amount_released);
+        Ok(())
+    }
 }
 
 pub fn expect_sector_event(
@@ -3030,6 +3123,21 @@ pub fn expect_sector_event(
     rt.expect_emitted_event(base_event.build().unwrap());
 }
 
+pub fn expect_peer_info_changed_event(
+    rt: &MockRuntime,
+    peer_id: Option<&[u8]>,
+    multiaddrs: Option<&[BytesDe]>,
+) {
+    let mut event = EventBuilder::new().typ("peer-info-changed");
+    if let Some(peer_id) = peer_id {
+        event = event.field("peer-id", &BytesSer(peer_id));
+    }
+    if let Some(multiaddrs) = multiaddrs {
+        event = event.field("multiaddrs", multiaddrs);
+    }
+    rt.expect_emitted_event(event.build().unwrap());
+}
+
 pub fn expect_event(rt: &MockRuntime, typ: &str, sector: &SectorNumber) {
     rt.expect_emitted_event(
         EventBuilder::new().typ(typ).field_indexed("sector", sector).build().unwrap(),
@@ -3,24 +3,26 @@
 
 use std::collections::BTreeSet;
 
-use fvm_actor_utils::receiver::UniversalReceiverParams;
+use fil_actors_runtime::frc46::UniversalReceiverParams;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::MethodNum;
 use fvm_shared::METHOD_CONSTRUCTOR;
 use num_derive::FromPrimitive;
-use num_traits::Zero;
+use num_traits::{FromPrimitive, Zero};
+use serde::Serialize;
 
-use fil_actors_runtime::cbor::serialize_vec;
+use fil_actors_runtime::cbor::{deserialize, serialize, serialize_vec};
 use fil_actors_runtime::runtime::{ActorCode, Primitives, Runtime};
 use fil_actors_runtime::FIRST_EXPORTED_METHOD_NUMBER;
 use fil_actors_runtime::{
     actor_dispatch, actor_error, extract_send_result, resolve_to_actor_id, ActorContext,
-    ActorError, AsActorError, INIT_ACTOR_ADDR,
+    ActorError, AsActorError, BatchReturn, BatchReturnGen, ValidateParams, INIT_ACTOR_ADDR,
 };
 
 pub use self::state::*;
@@ -30,6 +32,7 @@ pub use self::types::*;
 fil_actors_runtime::wasm_trampoline!(Actor);
 
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 mod types;
 
@@ -48,6 +51,16 @@ pub enum Method {
     LockBalance = 9,
     // Method numbers derived from FRC-0042 standards
     UniversalReceiverHook = frc42_dispatch::method_hash!("Receive"),
+    ListPendingTransactionsExported = frc42_dispatch::method_hash!("ListPendingTransactions"),
+    GetPendingTransactionExported = frc42_dispatch::method_hash!("GetPendingTransaction"),
+    ProposeWithExpiryExported = frc42_dispatch::method_hash!("ProposeWithExpiry"),
+    PruneExpiredExported = frc42_dispatch::method_hash!("PruneExpired"),
+    ApproveBatchExported = frc42_dispatch::method_hash!("ApproveBatch"),
+    CancelBatchExported = frc42_dispatch::method_hash!("CancelBatch"),
+    SetGovernanceDelayExported = frc42_dispatch::method_hash!("SetGovernanceDelay"),
+    ApplyGovernanceChangesExported = frc42_dispatch::method_hash!("ApplyGovernanceChanges"),
+    SwapSignerWithApprovalsExported = frc42_dispatch::method_hash!("SwapSignerWithApprovals"),
+    PreviewProposalHashExported = frc42_dispatch::method_hash!("PreviewProposalHash"),
 }
 
 /// Multisig Actor
@@ -107,6 +120,8 @@ impl Actor {
             next_tx_id: Default::default(),
             start_epoch: Default::default(),
             unlock_duration: Default::default(),
+            governance_delay: 0,
+            pending_governance: Vec::new(),
         };
 
         if params.unlock_duration != 0 {
@@ -124,45 +139,23 @@ impl Actor {
     /// Multisig actor propose function
     pub fn propose(rt: &impl Runtime, params: ProposeParams) -> Result<ProposeReturn, ActorError> {
         rt.validate_immediate_caller_accept_any()?;
-        let proposer: Address = rt.message().caller();
-
-        if params.value.is_negative() {
-            return Err(actor_error!(
-                illegal_argument,
-                "proposed value must be non-negative, was {}",
-                params.value
-            ));
-        }
-
-        let (txn_id, txn) = rt.transaction(|st: &mut State, rt| {
-            if !st.is_signer(&proposer) {
-                return Err(actor_error!(forbidden, "{} is not a signer", proposer));
-            }
-
-            let mut ptx = PendingTxnMap::load(
-                rt.store(),
-                &st.pending_txs,
-                PENDING_TXN_CONFIG,
-                "pending txns",
-            )?;
-            let t_id = st.next_tx_id;
-            st.next_tx_id.0 += 1;
-
-            let txn = Transaction {
-                to: params.to,
-                value: params.value,
-                method: params.method,
-                params: params.params,
-                approved: Vec::new(),
-            };
-
-            ptx.set(&t_id, txn.clone())?;
-            st.pending_txs = ptx.flush()?;
-            Ok((t_id, txn))
-        })?;
+        Self::propose_transaction(rt, params.to, params.value, params.method, params.params, None)
+    }
 
-        let (applied, ret, code) = Self::approve_transaction(rt, txn_id, txn)?;
-        Ok(ProposeReturn { txn_id, applied, code, ret })
+    /// Proposes a transaction that may no longer be approved from the `expires_at` epoch.
+    pub fn propose_with_expiry(
+        rt: &impl Runtime,
+        params: ProposeWithExpiryParams,
+    ) -> Result<ProposeReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        Self::propose_transaction(
+            rt,
+            params.to,
+            params.value,
+            params.method,
+            params.params,
+            Some(params.expires_at),
+        )
     }
 
     /// Multisig actor approve function
@@ -171,33 +164,36 @@ impl Actor {
         let approver: Address = rt.message().caller();
 
         let id = params.id;
-        let (st, txn) = rt.transaction(|st: &mut State, rt| {
-            if !st.is_signer(&approver) {
-                return Err(actor_error!(forbidden; "{} is not a signer", approver));
-            }
-            let ptx = PendingTxnMap::load(
-                rt.store(),
-                &st.pending_txs,
-                PENDING_TXN_CONFIG,
-                "pending txns",
-            )?;
-
-            let txn = get_transaction(rt, &ptx, params.id, params.proposal_hash)?;
+        let (st, txn) = Self::validate_approval(rt, &approver, params)?;
+        Self::apply_approval(rt, &st, id, txn)
+    }
 
-            // Go implementation holds reference to state after transaction so state must be cloned
-            // to match to handle possible exit code inconsistency
-            Ok((st.clone(), txn.clone()))
-        })?;
+    /// Approves many pending transactions in one message.
+    /// An approval that fails validation is reported in the batch result and skipped.
+    pub fn approve_batch(
+        rt: &impl Runtime,
+        params: ApproveBatchParams,
+    ) -> Result<ApproveBatchReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let approver: Address = rt.message().caller();
 
-        let (applied, ret, code) = execute_transaction_if_approved(rt, &st, id, &txn)?;
-        if !applied {
-            // if the transaction hasn't already been approved, "process" the approval
-            // and see if the transaction can be executed
-            let (applied, ret, code) = Self::approve_transaction(rt, id, txn)?;
-            Ok(ApproveReturn { applied, code, ret })
-        } else {
-            Ok(ApproveReturn { applied, code, ret })
+        let mut batch_gen = BatchReturnGen::new(params.approvals.len());
+        let mut approvals = Vec::new();
+        for item in params.approvals {
+            let id = item.id;
+            // State is re-read for each item, since an earlier approval may have
+            // executed a transaction that changed it.
+            match Self::validate_approval(rt, &approver, item) {
+                Ok((st, txn)) => {
+                    approvals.push(Self::apply_approval(rt, &st, id, txn)?);
+                    batch_gen.add_success();
+                }
+                Err(e) => {
+                    batch_gen.add_fail(e.exit_code());
+                }
+            }
         }
+        Ok(ApproveBatchReturn { results: batch_gen.gen(), approvals })
     }
 
     /// Multisig actor cancel function
@@ -216,27 +212,45 @@ impl Actor {
                 PENDING_TXN_CONFIG,
                 "pending txns",
             )?;
+            cancel_transaction(rt, &mut ptx, &caller_addr, &params)?;
+            st.pending_txs = ptx.flush()?;
+            Ok(())
+        })
+    }
 
-            let tx = ptx.delete(&params.id)?.ok_or_else(|| {
-                actor_error!(not_found, "no such transaction {:?} to cancel", params.id)
-            })?;
+    /// Cancels many pending transactions in one message.
+    /// A cancellation that fails validation is reported in the batch result and skipped.
+    pub fn cancel_batch(
+        rt: &impl Runtime,
+        params: CancelBatchParams,
+    ) -> Result<BatchReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let caller_addr: Address = rt.message().caller();
 
-            // Check to make sure transaction proposer is caller address
-            if tx.approved.first() != Some(&caller_addr) {
-                return Err(actor_error!(forbidden; "Cannot cancel another signers transaction"));
+        rt.transaction(|st: &mut State, rt| {
+            if !st.is_signer(&caller_addr) {
+                return Err(actor_error!(forbidden; "{} is not a signer", caller_addr));
             }
 
-            let calculated_hash = compute_proposal_hash(&tx, rt)
-                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-                    format!("failed to compute proposal hash for (tx: {:?})", params.id)
-                })?;
-
-            if !params.proposal_hash.is_empty() && params.proposal_hash != calculated_hash {
-                return Err(actor_error!(illegal_state, "hash does not match proposal params"));
+            let mut ptx = PendingTxnMap::load(
+                rt.store(),
+                &st.pending_txs,
+                PENDING_TXN_CONFIG,
+                "pending txns",
+            )?;
+            let mut batch_gen = BatchReturnGen::new(params.cancellations.len());
+            for item in &params.cancellations {
+                match cancel_transaction(rt, &mut ptx, &caller_addr, item) {
+                    Ok(()) => {
+                        batch_gen.add_success();
+                    }
+                    Err(e) => {
+                        batch_gen.add_fail(e.exit_code());
+                    }
+                }
             }
-
             st.pending_txs = ptx.flush()?;
-            Ok(())
+            Ok(batch_gen.gen())
         })
     }
 
@@ -244,6 +258,13 @@ impl Actor {
     pub fn add_signer(rt: &impl Runtime, params: AddSignerParams) -> Result<(), ActorError> {
         let receiver = rt.message().receiver();
         rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+        if Self::defer_governance_change(rt, Method::AddSigner, &params)? {
+            return Ok(());
+        }
+        Self::apply_add_signer(rt, params)
+    }
+
+    fn apply_add_signer(rt: &impl Runtime, params: AddSignerParams) -> Result<(), ActorError> {
         let resolved_new_signer = resolve_to_actor_id(rt, &params.signer, true)?;
 
         rt.transaction(|st: &mut State, _| {
@@ -272,6 +293,16 @@ impl Actor {
     pub fn remove_signer(rt: &impl Runtime, params: RemoveSignerParams) -> Result<(), ActorError> {
         let receiver = rt.message().receiver();
         rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+        if Self::defer_governance_change(rt, Method::RemoveSigner, &params)? {
+            return Ok(());
+        }
+        Self::apply_remove_signer(rt, params)
+    }
+
+    fn apply_remove_signer(
+        rt: &impl Runtime,
+        params: RemoveSignerParams,
+    ) -> Result<(), ActorError> {
         let resolved_old_signer = resolve_to_actor_id(rt, &params.signer, false)?;
 
         rt.transaction(|st: &mut State, rt| {
@@ -319,6 +350,34 @@ impl Actor {
     pub fn swap_signer(rt: &impl Runtime, params: SwapSignerParams) -> Result<(), ActorError> {
         let receiver = rt.message().receiver();
         rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+        if Self::defer_governance_change(rt, Method::SwapSigner, &params)? {
+            return Ok(());
+        }
+        Self::apply_swap_signer(rt, params, false)
+    }
+
+    /// Swaps a signer as for SwapSigner, with the new signer taking over the old signer's
+    /// approvals of pending transactions rather than those approvals being dropped.
+    /// This allows rotating a signer's key without pending transactions losing approvals.
+    /// The new signer also becomes the requester of transactions the old signer proposed,
+    /// changing their proposal hashes.
+    pub fn swap_signer_with_approvals(
+        rt: &impl Runtime,
+        params: SwapSignerParams,
+    ) -> Result<(), ActorError> {
+        let receiver = rt.message().receiver();
+        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+        if Self::defer_governance_change(rt, Method::SwapSignerWithApprovalsExported, &params)? {
+            return Ok(());
+        }
+        Self::apply_swap_signer(rt, params, true)
+    }
+
+    fn apply_swap_signer(
+        rt: &impl Runtime,
+        params: SwapSignerParams,
+        keep_approvals: bool,
+    ) -> Result<(), ActorError> {
         let from_resolved = resolve_to_actor_id(rt, &params.from, false)?;
         let to_resolved = resolve_to_actor_id(rt, &params.to, true)?;
 
@@ -337,7 +396,15 @@ impl Actor {
             // Add new signer
             st.signers.push(Address::new_id(to_resolved));
 
-            st.purge_approvals(rt.store(), &Address::new_id(from_resolved))?;
+            if keep_approvals {
+                st.transfer_approvals(
+                    rt.store(),
+                    &Address::new_id(from_resolved),
+                    &Address::new_id(to_resolved),
+                )?;
+            } else {
+                st.purge_approvals(rt.store(), &Address::new_id(from_resolved))?;
+            }
             Ok(())
         })?;
 
@@ -351,7 +418,16 @@ impl Actor {
     ) -> Result<(), ActorError> {
         let receiver = rt.message().receiver();
         rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+        if Self::defer_governance_change(rt, Method::ChangeNumApprovalsThreshold, &params)? {
+            return Ok(());
+        }
+        Self::apply_change_num_approvals_threshold(rt, params)
+    }
 
+    fn apply_change_num_approvals_threshold(
+        rt: &impl Runtime,
+        params: ChangeNumApprovalsThresholdParams,
+    ) -> Result<(), ActorError> {
         rt.transaction(|st: &mut State, _| {
             // Check if valid threshold value
             if params.new_threshold == 0 || params.new_threshold > st.signers.len() as u64 {
@@ -390,6 +466,346 @@ impl Actor {
         Ok(())
     }
 
+    /// Sets the number of epochs for which approved signer and threshold changes are held
+    /// before taking effect. While a delay is in force, changing it is itself delayed.
+    pub fn set_governance_delay(
+        rt: &impl Runtime,
+        params: SetGovernanceDelayParams,
+    ) -> Result<(), ActorError> {
+        let receiver = rt.message().receiver();
+        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+        if params.delay < 0 {
+            return Err(actor_error!(
+                illegal_argument,
+                "negative governance delay {}",
+                params.delay
+            ));
+        }
+        if Self::defer_governance_change(rt, Method::SetGovernanceDelayExported, &params)? {
+            return Ok(());
+        }
+        Self::apply_set_governance_delay(rt, params)
+    }
+
+    fn apply_set_governance_delay(
+        rt: &impl Runtime,
+        params: SetGovernanceDelayParams,
+    ) -> Result<(), ActorError> {
+        rt.transaction(|st: &mut State, _| {
+            st.governance_delay = params.delay;
+            Ok(())
+        })
+    }
+
+    /// Applies, in the order they were approved, all pending governance changes that have
+    /// reached their effective epoch. May be called by anyone.
+    /// A change that is no longer valid is discarded and its failure reported in the result.
+    pub fn apply_governance_changes(rt: &impl Runtime) -> Result<BatchReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let curr_epoch = rt.curr_epoch();
+        let matured = rt.transaction(|st: &mut State, _| {
+            let (matured, pending) = std::mem::take(&mut st.pending_governance)
+                .into_iter()
+                .partition(|change| change.effective_at <= curr_epoch);
+            st.pending_governance = pending;
+            Ok::<Vec<PendingGovernanceChange>, ActorError>(matured)
+        })?;
+
+        let mut batch_gen = BatchReturnGen::new(matured.len());
+        for change in matured {
+            match Self::apply_governance_change(rt, change) {
+                Ok(()) => {
+                    batch_gen.add_success();
+                }
+                Err(e) => {
+                    batch_gen.add_fail(e.exit_code());
+                }
+            }
+        }
+        Ok(batch_gen.gen())
+    }
+
+    /// Queues a governance change if a governance delay is in force.
+    /// Returns whether the change was queued, in which case it must not be applied now.
+    fn defer_governance_change(
+        rt: &impl Runtime,
+        method: Method,
+        params: &impl Serialize,
+    ) -> Result<bool, ActorError> {
+        let params = serialize(params, "governance change params")?;
+        rt.transaction(|st: &mut State, rt| {
+            if st.governance_delay == 0 {
+                return Ok(false);
+            }
+            if st.pending_governance.len() >= MAX_PENDING_GOVERNANCE_CHANGES {
+                return Err(actor_error!(
+                    forbidden,
+                    "too many pending governance changes (max {})",
+                    MAX_PENDING_GOVERNANCE_CHANGES
+                ));
+            }
+            st.pending_governance.push(PendingGovernanceChange {
+                effective_at: rt.curr_epoch() + st.governance_delay,
+                method: method as MethodNum,
+                params,
+            });
+            Ok(true)
+        })
+    }
+
+    fn apply_governance_change(
+        rt: &impl Runtime,
+        change: PendingGovernanceChange,
+    ) -> Result<(), ActorError> {
+        match FromPrimitive::from_u64(change.method) {
+            Some(Method::AddSigner) => {
+                Self::apply_add_signer(rt, deserialize(&change.params, "add signer params")?)
+            }
+            Some(Method::RemoveSigner) => {
+                Self::apply_remove_signer(rt, deserialize(&change.params, "remove signer params")?)
+            }
+            Some(Method::SwapSigner) => Self::apply_swap_signer(
+                rt,
+                deserialize(&change.params, "swap signer params")?,
+                false,
+            ),
+            Some(Method::SwapSignerWithApprovalsExported) => Self::apply_swap_signer(
+                rt,
+                deserialize(&change.params, "swap signer params")?,
+                true,
+            ),
+            Some(Method::ChangeNumApprovalsThreshold) => {
+                Self::apply_change_num_approvals_threshold(
+                    rt,
+                    deserialize(&change.params, "change threshold params")?,
+                )
+            }
+            Some(Method::SetGovernanceDelayExported) => Self::apply_set_governance_delay(
+                rt,
+                deserialize(&change.params, "set governance delay params")?,
+            ),
+            _ => Err(actor_error!(
+                illegal_state,
+                "invalid pending governance change method {}",
+                change.method
+            )),
+        }
+    }
+
+    /// Returns a page of at most `limit` pending transaction IDs.
+    pub fn list_pending_transactions(
+        rt: &impl Runtime,
+        params: ListPendingTransactionsParams,
+    ) -> Result<ListPendingTransactionsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params.validate()?;
+        let st: State = rt.state()?;
+        let ptx =
+            PendingTxnMap::load(rt.store(), &st.pending_txs, PENDING_TXN_CONFIG, "pending txns")?;
+        let mut txn_ids = Vec::new();
+        let (_, next_cursor) =
+            ptx.for_each_ranged(params.cursor.as_ref(), Some(params.limit as usize), |id, _| {
+                txn_ids.push(id);
+                Ok(())
+            })?;
+        Ok(ListPendingTransactionsReturn { txn_ids, next_cursor })
+    }
+
+    /// Returns a pending transaction along with the proposal hash that binds approvals to it.
+    pub fn get_pending_transaction(
+        rt: &impl Runtime,
+        params: GetPendingTransactionParams,
+    ) -> Result<GetPendingTransactionReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        let ptx =
+            PendingTxnMap::load(rt.store(), &st.pending_txs, PENDING_TXN_CONFIG, "pending txns")?;
+        let txn = ptx
+            .get(&params.id)?
+            .ok_or_else(|| actor_error!(not_found, "no such transaction {:?}", params.id))?;
+        let proposal_hash = compute_proposal_hash(txn, rt)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to compute proposal hash for (tx: {:?})", params.id)
+            })?;
+        Ok(GetPendingTransactionReturn {
+            to: txn.to,
+            value: txn.value.clone(),
+            method: txn.method,
+            params: txn.params.clone(),
+            approved: txn.approved.clone(),
+            proposal_hash: proposal_hash.to_vec(),
+            expires_at: txn.expires_at,
+        })
+    }
+
+    /// Computes the hash a transaction with the given requester and payload would be approved
+    /// with, whether or not it has been proposed. May be called by anyone.
+    pub fn preview_proposal_hash(
+        rt: &impl Runtime,
+        params: PreviewProposalHashParams,
+    ) -> Result<PreviewProposalHashReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        // Approvals are recorded by ID address.
+        let requester =
+            rt.resolve_address(&params.requester).map(Address::new_id).ok_or_else(|| {
+                actor_error!(illegal_argument, "failed to resolve requester {}", params.requester)
+            })?;
+        let proposal_hash = hash_proposal(
+            &ProposalHashData {
+                requester: Some(&requester),
+                to: &params.to,
+                value: &params.value,
+                method: &params.method,
+                params: &params.params,
+            },
+            rt,
+        )
+        .with_context_code(ExitCode::USR_ILLEGAL_STATE, || "failed to compute proposal hash")?;
+        Ok(PreviewProposalHashReturn { proposal_hash: proposal_hash.to_vec() })
+    }
+
+    /// Removes the expired transactions among a page of at most `limit` pending transactions.
+    /// May be called by anyone.
+    pub fn prune_expired(
+        rt: &impl Runtime,
+        params: PruneExpiredParams,
+    ) -> Result<PruneExpiredReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params.validate()?;
+        let curr_epoch = rt.curr_epoch();
+        rt.transaction(|st: &mut State, rt| {
+            let mut ptx = PendingTxnMap::load(
+                rt.store(),
+                &st.pending_txs,
+                PENDING_TXN_CONFIG,
+                "pending txns",
+            )?;
+            let mut pruned = Vec::new();
+            let (_, next_cursor) = ptx.for_each_ranged(
+                params.cursor.as_ref(),
+                Some(params.limit as usize),
+                |id, txn| {
+                    if txn.is_expired(curr_epoch) {
+                        pruned.push(id);
+                    }
+                    Ok(())
+                },
+            )?;
+            for id in &pruned {
+                ptx.delete(id)?;
+            }
+            st.pending_txs = ptx.flush()?;
+            Ok(PruneExpiredReturn { pruned, next_cursor })
+        })
+    }
+
+    fn propose_transaction(
+        rt: &impl Runtime,
+        to: Address,
+        value: TokenAmount,
+        method: MethodNum,
+        params: RawBytes,
+        expires_at: Option<ChainEpoch>,
+    ) -> Result<ProposeReturn, ActorError> {
+        let proposer: Address = rt.message().caller();
+
+        if value.is_negative() {
+            return Err(actor_error!(
+                illegal_argument,
+                "proposed value must be non-negative, was {}",
+                value
+            ));
+        }
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= rt.curr_epoch() {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "expiration epoch {} must be after current epoch {}",
+                    expires_at,
+                    rt.curr_epoch()
+                ));
+            }
+        }
+
+        let (txn_id, txn) = rt.transaction(|st: &mut State, rt| {
+            if !st.is_signer(&proposer) {
+                return Err(actor_error!(forbidden, "{} is not a signer", proposer));
+            }
+
+            let mut ptx = PendingTxnMap::load(
+                rt.store(),
+                &st.pending_txs,
+                PENDING_TXN_CONFIG,
+                "pending txns",
+            )?;
+            let t_id = st.next_tx_id;
+            st.next_tx_id.0 += 1;
+
+            let txn = Transaction { to, value, method, params, approved: Vec::new(), expires_at };
+
+            ptx.set(&t_id, txn.clone())?;
+            st.pending_txs = ptx.flush()?;
+            Ok((t_id, txn))
+        })?;
+
+        let (applied, ret, code) = Self::approve_transaction(rt, txn_id, txn)?;
+        Ok(ProposeReturn { txn_id, applied, code, ret })
+    }
+
+    /// Checks that `approver` may approve the transaction identified by `params`,
+    /// returning the current state and the transaction.
+    fn validate_approval(
+        rt: &impl Runtime,
+        approver: &Address,
+        params: TxnIDParams,
+    ) -> Result<(State, Transaction), ActorError> {
+        rt.transaction(|st: &mut State, rt| {
+            if !st.is_signer(approver) {
+                return Err(actor_error!(forbidden; "{} is not a signer", approver));
+            }
+            let ptx = PendingTxnMap::load(
+                rt.store(),
+                &st.pending_txs,
+                PENDING_TXN_CONFIG,
+                "pending txns",
+            )?;
+
+            let txn = get_transaction(rt, &ptx, params.id, params.proposal_hash)?;
+
+            // A transaction already meeting the threshold (e.g. after it was lowered) is executed
+            // even if the approver has already approved it.
+            if (txn.approved.len() as u64) < st.num_approvals_threshold
+                && txn.approved.contains(approver)
+            {
+                return Err(actor_error!(forbidden, "{} already approved this message", approver));
+            }
+
+            // Go implementation holds reference to state after transaction so state must be cloned
+            // to match to handle possible exit code inconsistency
+            Ok((st.clone(), txn.clone()))
+        })
+    }
+
+    /// Executes the transaction if it is already approved, otherwise records the caller's
+    /// approval and executes it if that meets the threshold.
+    fn apply_approval(
+        rt: &impl Runtime,
+        st: &State,
+        id: TxnID,
+        txn: Transaction,
+    ) -> Result<ApproveReturn, ActorError> {
+        let (applied, ret, code) = execute_transaction_if_approved(rt, st, id, &txn)?;
+        if !applied {
+            // if the transaction hasn't already been approved, "process" the approval
+            // and see if the transaction can be executed
+            let (applied, ret, code) = Self::approve_transaction(rt, id, txn)?;
+            Ok(ApproveReturn { applied, code, ret })
+        } else {
+            Ok(ApproveReturn { applied, code, ret })
+        }
+    }
+
     fn approve_transaction(
         rt: &impl Runtime,
         tx_id: TxnID,
@@ -513,6 +929,15 @@ where
         .get(&txn_id)?
         .ok_or_else(|| actor_error!(not_found, "no such transaction {:?} for approval", txn_id))?;
 
+    if txn.is_expired(rt.curr_epoch()) {
+        return Err(actor_error!(
+            forbidden,
+            "transaction {:?} expired at epoch {}",
+            txn_id,
+            txn.expires_at.unwrap_or_default()
+        ));
+    }
+
     if !proposal_hash.is_empty() {
         let calculated_hash = compute_proposal_hash(txn, rt)
             .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
@@ -530,17 +955,58 @@ where
     Ok(txn)
 }
 
+/// Removes a pending transaction proposed by `caller`, checking the proposal hash if given.
+fn cancel_transaction<BS, RT>(
+    rt: &RT,
+    ptx: &mut PendingTxnMap<BS>,
+    caller: &Address,
+    params: &TxnIDParams,
+) -> Result<(), ActorError>
+where
+    BS: Blockstore,
+    RT: Runtime,
+{
+    let tx = ptx
+        .get(&params.id)?
+        .ok_or_else(|| actor_error!(not_found, "no such transaction {:?} to cancel", params.id))?;
+
+    // Check to make sure transaction proposer is caller address
+    if tx.approved.first() != Some(caller) {
+        return Err(actor_error!(forbidden; "Cannot cancel another signers transaction"));
+    }
+
+    let calculated_hash = compute_proposal_hash(tx, rt)
+        .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to compute proposal hash for (tx: {:?})", params.id)
+        })?;
+
+    if !params.proposal_hash.is_empty() && params.proposal_hash != calculated_hash {
+        return Err(actor_error!(illegal_state, "hash does not match proposal params"));
+    }
+
+    ptx.delete(&params.id)?;
+    Ok(())
+}
+
 /// Computes a digest of a proposed transaction. This digest is used to confirm identity
 /// of the transaction associated with an ID, which might change under chain re-orgs.
 pub fn compute_proposal_hash(txn: &Transaction, sys: &dyn Primitives) -> anyhow::Result<[u8; 32]> {
-    let proposal_hash = ProposalHashData {
-        requester: txn.approved.first(),
-        to: &txn.to,
-        value: &txn.value,
-        method: &txn.method,
-        params: &txn.params,
-    };
-    let data = serialize_vec(&proposal_hash, "proposal hash")?;
+    hash_proposal(
+        &ProposalHashData {
+            requester: txn.approved.first(),
+            to: &txn.to,
+            value: &txn.value,
+            method: &txn.method,
+            params: &txn.params,
+        },
+        sys,
+    )
+}
+
+/// Computes the digest of proposal data, for signers to check the hash they approve against
+/// the transaction they intend, without reading it from the chain.
+pub fn hash_proposal(data: &ProposalHashData, sys: &dyn Primitives) -> anyhow::Result<[u8; 32]> {
+    let data = serialize_vec(data, "proposal hash")?;
     Ok(sys.hash_blake2b(&data))
 }
 
@@ -562,6 +1028,16 @@ impl ActorCode for Actor {
       ChangeNumApprovalsThreshold => change_num_approvals_threshold,
       LockBalance => lock_balance,
       UniversalReceiverHook => universal_receiver_hook,
+      ListPendingTransactionsExported => list_pending_transactions,
+      GetPendingTransactionExported => get_pending_transaction,
+      ProposeWithExpiryExported => propose_with_expiry,
+      PruneExpiredExported => prune_expired,
+      ApproveBatchExported => approve_batch,
+      CancelBatchExported => cancel_batch,
+      SetGovernanceDelayExported => set_governance_delay,
+      ApplyGovernanceChangesExported => apply_governance_changes,
+      SwapSignerWithApprovalsExported => swap_signer_with_approvals,
+      PreviewProposalHashExported => preview_proposal_hash,
       _ => fallback,
     }
 }
@@ -3,7 +3,6 @@
 
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
-use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
 use fvm_shared::bigint::BigInt;
 use fvm_shared::bigint::Integer;
@@ -12,16 +11,18 @@ use fvm_shared::econ::TokenAmount;
 use indexmap::IndexMap;
 use num_traits::Zero;
 
-use fil_actors_runtime::{actor_error, ActorError, Config, Map2, DEFAULT_HAMT_CONFIG};
+use fil_actors_runtime::{
+    actor_error, tuple_with_optional_tail, ActorError, Config, Map2, DEFAULT_HAMT_CONFIG,
+};
 
-use super::types::Transaction;
+use super::types::{PendingGovernanceChange, Transaction};
 use super::TxnID;
 
 pub type PendingTxnMap<BS> = Map2<BS, TxnID, Transaction>;
 pub const PENDING_TXN_CONFIG: Config = DEFAULT_HAMT_CONFIG;
 
 /// Multisig actor state
-#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct State {
     pub signers: Vec<Address>,
     pub num_approvals_threshold: u64,
@@ -33,8 +34,25 @@ pub struct State {
     pub unlock_duration: ChainEpoch,
 
     pub pending_txs: Cid,
+
+    // Epochs for which approved signer and threshold changes are held before taking effect.
+    pub governance_delay: ChainEpoch,
+    // At most MAX_PENDING_GOVERNANCE_CHANGES.
+    pub pending_governance: Vec<PendingGovernanceChange>,
 }
 
+tuple_with_optional_tail!(State {
+    signers,
+    num_approvals_threshold,
+    next_tx_id,
+    initial_balance,
+    start_epoch,
+    unlock_duration,
+    pending_txs;
+    governance_delay,
+    pending_governance
+});
+
 impl State {
     /// Checks if `address` is in the list of signers
     pub fn is_signer(&self, address: &Address) -> bool {
@@ -107,6 +125,34 @@ impl State {
         Ok(())
     }
 
+    /// Iterates all pending transactions and replaces an address in each list of approvals,
+    /// if present, with another address, which must not already have approved any.
+    pub fn transfer_approvals<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        from: &Address,
+        to: &Address,
+    ) -> Result<(), ActorError> {
+        let mut txns =
+            PendingTxnMap::load(store, &self.pending_txs, PENDING_TXN_CONFIG, "pending txns")?;
+
+        let mut txns_to_update = IndexMap::new();
+        txns.for_each(|tx_id, txn: &Transaction| {
+            if txn.approved.contains(from) {
+                txns_to_update.insert(tx_id, txn.clone());
+            }
+            Ok(())
+        })?;
+
+        for (tx_id, mut txn) in txns_to_update {
+            txn.approved.iter_mut().filter(|approver| *approver == from).for_each(|a| *a = *to);
+            txns.set(&tx_id, txn)?;
+        }
+
+        self.pending_txs = txns.flush()?;
+        Ok(())
+    }
+
     pub(crate) fn check_available(
         &self,
         balance: TokenAmount,
@@ -5,7 +5,9 @@ use fvm_shared::address::Address;
 
 use fil_actors_runtime::MessageAccumulator;
 
-use crate::{PendingTxnMap, State, TxnID, PENDING_TXN_CONFIG, SIGNERS_MAX};
+use crate::{
+    PendingTxnMap, State, TxnID, MAX_PENDING_GOVERNANCE_CHANGES, PENDING_TXN_CONFIG, SIGNERS_MAX,
+};
 
 pub struct StateSummary {
     pub pending_tx_count: u64,
@@ -46,6 +48,15 @@ pub fn check_state_invariants<BS: Blockstore>(
         );
     }
 
+    acc.require(
+        state.governance_delay >= 0,
+        format!("negative governance delay {}", state.governance_delay),
+    );
+    acc.require(
+        state.pending_governance.len() <= MAX_PENDING_GOVERNANCE_CHANGES,
+        format!("too many pending governance changes {}", state.pending_governance.len()),
+    );
+
     // create lookup to test transaction approvals are multisig signers
     let signers = HashSet::<&Address>::from_iter(state.signers.iter());
 
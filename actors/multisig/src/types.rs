@@ -12,12 +12,16 @@ use fvm_shared::error::ExitCode;
 use fvm_shared::MethodNum;
 use serde::{Deserialize, Serialize};
 
-use fil_actors_runtime::MapKey;
+use fil_actors_runtime::{tuple_with_optional_tail, validate_params, BatchReturn, MapKey};
 
 /// SignersMax is the maximum number of signers allowed in a multisig. If more
 /// are required, please use a combining tree of multisigs.
 pub const SIGNERS_MAX: usize = 256;
 
+/// The maximum number of approved signer and threshold changes that may wait out a
+/// governance delay at once.
+pub const MAX_PENDING_GOVERNANCE_CHANGES: usize = 32;
+
 /// Transaction ID type
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Hash, Eq, PartialEq, PartialOrd)]
 #[serde(transparent)]
@@ -40,7 +44,7 @@ impl Display for TxnID {
 }
 
 /// Transaction type used in multisig actor
-#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Transaction {
     pub to: Address,
     pub value: TokenAmount,
@@ -48,6 +52,17 @@ pub struct Transaction {
     pub params: RawBytes,
 
     pub approved: Vec<Address>,
+    /// Epoch from which the transaction may no longer be approved, if any.
+    pub expires_at: Option<ChainEpoch>,
+}
+
+tuple_with_optional_tail!(Transaction { to, value, method, params, approved; expires_at });
+
+impl Transaction {
+    /// Whether the transaction can no longer be approved at `epoch`.
+    pub fn is_expired(&self, epoch: ChainEpoch) -> bool {
+        self.expires_at.is_some_and(|expires_at| epoch >= expires_at)
+    }
 }
 
 /// Data for a BLAKE2B-256 to be attached to methods referencing proposals via TXIDs.
@@ -56,6 +71,11 @@ pub struct Transaction {
 ///
 /// Requester - The requesting multisig wallet member.
 /// All other fields - From the "Transaction" struct.
+///
+/// A transaction's expiry is deliberately not hashed. It only limits when approvals are accepted,
+/// never what the transaction does, so an approval bound to the payload can't be redirected by a
+/// substitute with a different expiry. Leaving it out keeps hashes of proposals made before
+/// expiry was introduced, and previewed hashes, unchanged.
 #[derive(Serialize_tuple, Debug)]
 pub struct ProposalHashData<'a> {
     pub requester: Option<&'a Address>,
@@ -84,6 +104,17 @@ pub struct ProposeParams {
     pub params: RawBytes,
 }
 
+/// Propose with expiry method call parameters.
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct ProposeWithExpiryParams {
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: RawBytes,
+    /// Epoch from which the proposal may no longer be approved. Must be in the future.
+    pub expires_at: ChainEpoch,
+}
+
 /// Propose method call return.
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct ProposeReturn {
@@ -110,7 +141,7 @@ pub struct TxnIDParams {
 }
 
 /// Parameters for approve and cancel multisig functions.
-#[derive(Serialize_tuple, Deserialize_tuple)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
 pub struct ApproveReturn {
     /// Applied indicates if the transaction was applied as opposed to proposed but not applied
     /// due to lack of approvals
@@ -122,6 +153,29 @@ pub struct ApproveReturn {
     pub ret: RawBytes,
 }
 
+/// Approve batch params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ApproveBatchParams {
+    pub approvals: Vec<TxnIDParams>,
+}
+
+/// Approve batch return.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ApproveBatchReturn {
+    /// Result of each approval, in the order given.
+    pub results: BatchReturn,
+    /// Outcome of each successful approval, in the order given.
+    pub approvals: Vec<ApproveReturn>,
+}
+
+/// Cancel batch params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct CancelBatchParams {
+    pub cancellations: Vec<TxnIDParams>,
+}
+
 /// Add signer params.
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct AddSignerParams {
@@ -149,6 +203,106 @@ pub struct ChangeNumApprovalsThresholdParams {
     pub new_threshold: u64,
 }
 
+/// List pending transactions params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ListPendingTransactionsParams {
+    /// Transaction from which to resume listing, as returned by a previous call.
+    /// None starts from the beginning.
+    pub cursor: Option<TxnID>,
+    /// Maximum number of transaction IDs to return.
+    pub limit: u64,
+}
+
+validate_params!(ListPendingTransactionsParams { limit: range(1, u64::MAX) });
+
+/// List pending transactions return.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ListPendingTransactionsReturn {
+    pub txn_ids: Vec<TxnID>,
+    /// Cursor to pass to a subsequent call to continue listing, if more transactions remain.
+    pub next_cursor: Option<TxnID>,
+}
+
+/// Get pending transaction params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct GetPendingTransactionParams {
+    pub id: TxnID,
+}
+
+/// Get pending transaction return.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct GetPendingTransactionReturn {
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: RawBytes,
+    pub approved: Vec<Address>,
+    /// The hash that approvers should provide to bind an approval to this proposal.
+    #[serde(with = "strict_bytes")]
+    pub proposal_hash: Vec<u8>,
+    pub expires_at: Option<ChainEpoch>,
+}
+
+/// Preview proposal hash params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct PreviewProposalHashParams {
+    /// The signer proposing the transaction.
+    pub requester: Address,
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: RawBytes,
+}
+
+/// Preview proposal hash return.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct PreviewProposalHashReturn {
+    #[serde(with = "strict_bytes")]
+    pub proposal_hash: Vec<u8>,
+}
+
+/// Prune expired transactions params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct PruneExpiredParams {
+    /// Transaction from which to resume pruning, as returned by a previous call.
+    /// None starts from the beginning.
+    pub cursor: Option<TxnID>,
+    /// Maximum number of pending transactions to examine.
+    pub limit: u64,
+}
+
+validate_params!(PruneExpiredParams { limit: range(1, u64::MAX) });
+
+/// Prune expired transactions call return.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct PruneExpiredReturn {
+    /// IDs of the expired transactions that were removed.
+    pub pruned: Vec<TxnID>,
+    /// Cursor to pass to a subsequent call to continue pruning, if more transactions remain.
+    pub next_cursor: Option<TxnID>,
+}
+
+/// Set governance delay params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct SetGovernanceDelayParams {
+    /// Number of epochs an approved signer or threshold change waits before taking effect.
+    pub delay: ChainEpoch,
+}
+
+/// A signer or threshold change that has been approved but not yet taken effect.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct PendingGovernanceChange {
+    /// Epoch from which the change may be applied.
+    pub effective_at: ChainEpoch,
+    /// The multisig method making the change.
+    pub method: MethodNum,
+    /// Parameters to that method.
+    pub params: RawBytes,
+}
+
 /// Lock balance call params.
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct LockBalanceParams {
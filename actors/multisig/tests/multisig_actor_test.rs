@@ -1,14 +1,15 @@
 use fil_actor_multisig::testing::check_state_invariants;
 use fil_actor_multisig::{
-    compute_proposal_hash, Actor as MultisigActor, ConstructorParams, Method, ProposeReturn, State,
-    Transaction, TxnID, TxnIDParams, SIGNERS_MAX,
+    compute_proposal_hash, hash_proposal, Actor as MultisigActor, ConstructorParams, Method,
+    PreviewProposalHashParams, ProposalHashData, ProposeReturn, State, Transaction, TxnID,
+    TxnIDParams, MAX_PENDING_GOVERNANCE_CHANGES, SIGNERS_MAX,
 };
 use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::frc46::UniversalReceiverParams;
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::FIRST_EXPORTED_METHOD_NUMBER;
 use fil_actors_runtime::{INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR};
-use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::tuple::*;
 use fvm_ipld_encoding::{RawBytes, CBOR};
@@ -574,6 +575,7 @@ fn test_simple_propose() {
         method: METHOD_SEND,
         params: RawBytes::default(),
         approved: vec![anne],
+        expires_at: None,
     };
     let expect_txns = vec![(TxnID(0), txn0)];
     h.assert_transactions(&rt, expect_txns);
@@ -1144,6 +1146,7 @@ fn test_swap_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expires_at: None,
                 },
             ),
             (
@@ -1154,6 +1157,7 @@ fn test_swap_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expires_at: None,
                 },
             ),
         ],
@@ -1185,6 +1189,66 @@ fn test_swap_signer_deletes_solo_proposals() {
     check_state(&rt);
 }
 
+#[test]
+fn test_swap_signer_with_approvals_keeps_approvals() {
+    let msig = Address::new_id(100);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+    let darlene = Address::new_id(104);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 3, 0, 0, vec![anne, bob, chuck]);
+
+    // anne proposes a tx and bob approves
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let proposal_hash =
+        h.propose_ok(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+    h.approve_ok(&rt, TxnID(0), proposal_hash);
+    // anne proposes another tx alone
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    h.propose_ok(&rt, bob, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+
+    // swapping in a signer is rejected as for SwapSigner
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.swap_signers_with_approvals(&rt, anne, bob));
+    expect_abort(ExitCode::USR_FORBIDDEN, h.swap_signers_with_approvals(&rt, darlene, bob));
+
+    // darlene takes anne's place, including in the list of approvals
+    h.swap_signers_with_approvals(&rt, anne, darlene).unwrap();
+    let st: State = rt.get_state();
+    assert_eq!(vec![bob, chuck, darlene], st.signers);
+    let txn0 = Transaction {
+        to: chuck,
+        value: TokenAmount::zero(),
+        method: METHOD_SEND,
+        params: RawBytes::default(),
+        approved: vec![darlene, bob],
+        expires_at: None,
+    };
+    let txn1 = Transaction {
+        to: bob,
+        value: TokenAmount::zero(),
+        method: METHOD_SEND,
+        params: RawBytes::default(),
+        approved: vec![darlene],
+        expires_at: None,
+    };
+    h.assert_transactions(&rt, vec![(TxnID(0), txn0.clone()), (TxnID(1), txn1)]);
+    check_state(&rt);
+
+    // darlene is now the requester, so the proposal hash has changed
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, chuck);
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.approve(&rt, TxnID(0), proposal_hash));
+    // chuck's approval meets the threshold with those kept
+    let proposal_hash = compute_proposal_hash(&txn0, &rt).unwrap();
+    rt.expect_send_simple(chuck, METHOD_SEND, None, TokenAmount::zero(), None, ExitCode::OK);
+    h.approve_ok(&rt, TxnID(0), proposal_hash);
+    check_state(&rt);
+}
+
 #[test]
 fn test_remove_signer_removes_approvals() {
     let msig = Address::new_id(100);
@@ -1230,6 +1294,7 @@ fn test_remove_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expires_at: None,
                 },
             ),
             (
@@ -1240,6 +1305,7 @@ fn test_remove_signer_removes_approvals() {
                     method: METHOD_SEND,
                     params: RawBytes::default(),
                     approved: vec![bob],
+                    expires_at: None,
                 },
             ),
         ],
@@ -1305,6 +1371,7 @@ mod approval_tests {
             method: fake_method,
             params: fake_params.clone(),
             approved: vec![anne],
+            expires_at: None,
         };
         h.assert_transactions(&rt, vec![(TxnID(0), expect_txn)]);
 
@@ -1391,6 +1458,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params.clone(),
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -1441,6 +1509,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -1478,6 +1547,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -1511,7 +1581,8 @@ mod approval_tests {
                 value: send_value,
                 method: fake_method,
                 params: fake_params,
-                approved: vec![bob], //mismatch
+                approved: vec![bob], //mismatch,
+                expires_at: None,
             },
             &rt,
         )
@@ -1550,6 +1621,7 @@ mod approval_tests {
             method: fake_method,
             params: fake_params.clone(),
             approved: vec![anne],
+            expires_at: None,
         };
         h.assert_transactions(&rt, vec![(TxnID(0), expect_txn)]);
 
@@ -1647,6 +1719,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -1713,6 +1786,7 @@ mod approval_tests {
                     method: fake_method,
                     params: fake_params,
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -1948,6 +2022,7 @@ mod cancel_tests {
                     method: fake_method,
                     params: RawBytes::default(),
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -1989,6 +2064,7 @@ mod cancel_tests {
                     method: fake_method,
                     params: RawBytes::default(),
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -2031,6 +2107,7 @@ mod cancel_tests {
                     method: fake_method,
                     params: RawBytes::default(),
                     approved: vec![anne],
+                    expires_at: None,
                 },
             )],
         );
@@ -2082,7 +2159,8 @@ mod cancel_tests {
             value: send_value,
             method: fake_method,
             params: RawBytes::default(),
-            approved: vec![bob], // anne's approval is gone
+            approved: vec![bob], // anne's approval is gone,
+            expires_at: None,
         };
         let new_proposal_hash = compute_proposal_hash(&new_tx, &rt).unwrap();
         h.assert_transactions(&rt, vec![(TxnID(0), new_tx)]);
@@ -2451,6 +2529,493 @@ fn token_receiver() {
     assert!(ret.is_none());
 }
 
+#[test]
+fn list_and_inspect_pending_transactions() {
+    let msig = Address::new_id(1000);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let mut hashes = vec![];
+    for i in 0..3u8 {
+        let value = TokenAmount::from_atto(i + 1);
+        hashes.push(h.propose_ok(&rt, chuck, value, METHOD_SEND, RawBytes::default()));
+    }
+
+    let first = h.list_pending_transactions(&rt, None, 2).unwrap();
+    assert_eq!(2, first.txn_ids.len());
+    let second = h.list_pending_transactions(&rt, first.next_cursor, 2).unwrap();
+    assert_eq!(1, second.txn_ids.len());
+    assert!(second.next_cursor.is_none());
+    let mut ids: Vec<i64> =
+        first.txn_ids.iter().chain(second.txn_ids.iter()).map(|id| id.0).collect();
+    ids.sort();
+    assert_eq!(vec![0, 1, 2], ids);
+
+    let txn = h.get_pending_transaction(&rt, TxnID(1)).unwrap();
+    assert_eq!(chuck, txn.to);
+    assert_eq!(TokenAmount::from_atto(2), txn.value);
+    assert_eq!(METHOD_SEND, txn.method);
+    assert_eq!(vec![anne], txn.approved);
+    assert_eq!(hashes[1].to_vec(), txn.proposal_hash);
+
+    expect_abort(ExitCode::USR_NOT_FOUND, h.get_pending_transaction(&rt, TxnID(3)));
+    rt.reset();
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.list_pending_transactions(&rt, None, 0));
+    rt.reset();
+    check_state(&rt);
+}
+
+#[test]
+fn preview_proposal_hash_matches_proposed_transaction() {
+    let msig = Address::new_id(1000);
+    let anne = Address::new_id(101);
+    let anne_robust = Address::new_secp256k1(&[1u8; 65]).unwrap();
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    rt.add_id_address(anne_robust, anne);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+    let value = TokenAmount::from_atto(5);
+    let params = RawBytes::new(vec![1, 2, 3]);
+    let preview = |requester: Address| {
+        // Anyone may preview a hash.
+        rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+        h.preview_proposal_hash(
+            &rt,
+            PreviewProposalHashParams {
+                requester,
+                to: chuck,
+                value: value.clone(),
+                method: METHOD_SEND,
+                params: params.clone(),
+            },
+        )
+    };
+    let previewed = preview(anne_robust).unwrap();
+    assert_eq!(previewed, preview(anne).unwrap());
+    assert_ne!(previewed, preview(bob).unwrap());
+
+    let library_hash = hash_proposal(
+        &ProposalHashData {
+            requester: Some(&anne),
+            to: &chuck,
+            value: &value,
+            method: &METHOD_SEND,
+            params: &params,
+        },
+        &rt,
+    )
+    .unwrap();
+    assert_eq!(previewed, library_hash.to_vec());
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let proposed = h.propose_ok(&rt, chuck, value.clone(), METHOD_SEND, params.clone());
+    assert_eq!(previewed, proposed.to_vec());
+    assert_eq!(previewed, h.get_pending_transaction(&rt, TxnID(0)).unwrap().proposal_hash);
+
+    // The requester must exist.
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        preview(Address::new_secp256k1(&[2u8; 65]).unwrap()),
+    );
+    rt.reset();
+    check_state(&rt);
+}
+
+#[test]
+fn expired_transactions_cannot_be_approved_and_are_pruned() {
+    let msig = Address::new_id(1000);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+    rt.set_epoch(10);
+
+    // expiry must be in the future
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        h.propose_with_expiry(
+            &rt,
+            chuck,
+            TokenAmount::zero(),
+            METHOD_SEND,
+            RawBytes::default(),
+            10,
+        ),
+    );
+    rt.reset();
+
+    let value = TokenAmount::from_atto(1);
+    h.propose_with_expiry(&rt, chuck, value.clone(), METHOD_SEND, RawBytes::default(), 20).unwrap();
+    h.propose_with_expiry(&rt, chuck, value.clone(), METHOD_SEND, RawBytes::default(), 30).unwrap();
+    h.propose(&rt, chuck, value.clone(), METHOD_SEND, RawBytes::default()).unwrap();
+    let txn = h.get_pending_transaction(&rt, TxnID(0)).unwrap();
+    assert_eq!(Some(20), txn.expires_at);
+
+    // nothing has expired yet
+    let ret = h.prune_expired(&rt, None, 10).unwrap();
+    assert!(ret.pruned.is_empty());
+    assert_eq!(None, ret.next_cursor);
+
+    rt.set_epoch(20);
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+    expect_abort(ExitCode::USR_FORBIDDEN, h.approve(&rt, TxnID(0), [0u8; 32]));
+    rt.reset();
+
+    // anyone may prune
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, chuck);
+    let ret = h.prune_expired(&rt, None, 10).unwrap();
+    assert_eq!(vec![TxnID(0)], ret.pruned);
+    assert_eq!(None, ret.next_cursor);
+    h.assert_transactions(
+        &rt,
+        vec![
+            (
+                TxnID(1),
+                Transaction {
+                    to: chuck,
+                    value: value.clone(),
+                    method: METHOD_SEND,
+                    params: RawBytes::default(),
+                    approved: vec![anne],
+                    expires_at: Some(30),
+                },
+            ),
+            (
+                TxnID(2),
+                Transaction {
+                    to: chuck,
+                    value,
+                    method: METHOD_SEND,
+                    params: RawBytes::default(),
+                    approved: vec![anne],
+                    expires_at: None,
+                },
+            ),
+        ],
+    );
+    check_state(&rt);
+
+    // Pruning proceeds a page at a time.
+    rt.set_epoch(30);
+    let ret = h.prune_expired(&rt, None, 1).unwrap();
+    assert_eq!(vec![TxnID(1)], ret.pruned);
+    assert_eq!(Some(TxnID(2)), ret.next_cursor);
+    let ret = h.prune_expired(&rt, ret.next_cursor, 1).unwrap();
+    assert!(ret.pruned.is_empty());
+    assert_eq!(None, ret.next_cursor);
+    assert_eq!(1, h.list_pending_transactions(&rt, None, 10).unwrap().txn_ids.len());
+
+    // The page must not be empty.
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.prune_expired(&rt, None, 0));
+    rt.reset();
+    check_state(&rt);
+}
+
+#[test]
+fn transaction_without_expiry_keeps_encoding() {
+    let to = Address::new_id(TEST_CHUCK_ADDR);
+    let value = TokenAmount::from_atto(1);
+    let approved = vec![Address::new_id(TEST_ANNE_ADDR)];
+    // Transactions before expiry was introduced were five-element tuples.
+    let legacy = fvm_ipld_encoding::to_vec(&(
+        to,
+        value.clone(),
+        METHOD_SEND,
+        RawBytes::default(),
+        approved.clone(),
+    ))
+    .unwrap();
+    let mut txn = Transaction {
+        to,
+        value,
+        method: METHOD_SEND,
+        params: RawBytes::default(),
+        approved,
+        expires_at: None,
+    };
+    assert_eq!(legacy, fvm_ipld_encoding::to_vec(&txn).unwrap());
+    assert_eq!(txn, fvm_ipld_encoding::from_slice(&legacy).unwrap());
+
+    txn.expires_at = Some(20);
+    let encoded = fvm_ipld_encoding::to_vec(&txn).unwrap();
+    assert_eq!(txn, fvm_ipld_encoding::from_slice(&encoded).unwrap());
+}
+
+#[test]
+fn approve_batch_reports_per_item_results() {
+    let msig = Address::new_id(1000);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+    rt.set_balance(TokenAmount::from_atto(10));
+    rt.set_received(TokenAmount::zero());
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let hash0 =
+        h.propose_ok(&rt, chuck, TokenAmount::from_atto(1), METHOD_SEND, RawBytes::default());
+    let hash1 =
+        h.propose_ok(&rt, chuck, TokenAmount::from_atto(2), METHOD_SEND, RawBytes::default());
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+    rt.expect_send_simple(chuck, METHOD_SEND, None, TokenAmount::from_atto(1), None, ExitCode::OK);
+    let ret = h
+        .approve_batch(
+            &rt,
+            vec![
+                TxnIDParams { id: TxnID(0), proposal_hash: hash0.to_vec() },
+                // wrong hash
+                TxnIDParams { id: TxnID(1), proposal_hash: hash0.to_vec() },
+                // no such transaction
+                TxnIDParams { id: TxnID(2), proposal_hash: vec![] },
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        vec![ExitCode::OK, ExitCode::USR_ILLEGAL_ARGUMENT, ExitCode::USR_NOT_FOUND],
+        ret.results.codes()
+    );
+    assert_eq!(1, ret.approvals.len());
+    assert!(ret.approvals[0].applied);
+    assert_eq!(ExitCode::OK, ret.approvals[0].code);
+
+    // the second transaction remains pending with a single approval
+    h.assert_transactions(
+        &rt,
+        vec![(
+            TxnID(1),
+            Transaction {
+                to: chuck,
+                value: TokenAmount::from_atto(2),
+                method: METHOD_SEND,
+                params: RawBytes::default(),
+                approved: vec![anne],
+                expires_at: None,
+            },
+        )],
+    );
+
+    // a duplicate approval fails only that item
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let ret = h
+        .approve_batch(&rt, vec![TxnIDParams { id: TxnID(1), proposal_hash: hash1.to_vec() }])
+        .unwrap();
+    assert_eq!(vec![ExitCode::USR_FORBIDDEN], ret.results.codes());
+    assert!(ret.approvals.is_empty());
+    check_state(&rt);
+}
+
+#[test]
+fn cancel_batch_reports_per_item_results() {
+    let msig = Address::new_id(1000);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let hash0 = h.propose_ok(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+    h.propose_ok(&rt, chuck, TokenAmount::from_atto(1), METHOD_SEND, RawBytes::default());
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+    h.propose_ok(&rt, chuck, TokenAmount::from_atto(2), METHOD_SEND, RawBytes::default());
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let ret = h
+        .cancel_batch(
+            &rt,
+            vec![
+                TxnIDParams { id: TxnID(0), proposal_hash: hash0.to_vec() },
+                // wrong hash
+                TxnIDParams { id: TxnID(1), proposal_hash: hash0.to_vec() },
+                // proposed by another signer
+                TxnIDParams { id: TxnID(2), proposal_hash: vec![] },
+                // already cancelled
+                TxnIDParams { id: TxnID(0), proposal_hash: vec![] },
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        vec![
+            ExitCode::OK,
+            ExitCode::USR_ILLEGAL_STATE,
+            ExitCode::USR_FORBIDDEN,
+            ExitCode::USR_NOT_FOUND
+        ],
+        ret.codes()
+    );
+    let pending = h.list_pending_transactions(&rt, None, 10).unwrap();
+    let mut ids: Vec<i64> = pending.txn_ids.iter().map(|id| id.0).collect();
+    ids.sort();
+    assert_eq!(vec![1, 2], ids);
+
+    // non-signers cannot cancel
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, chuck);
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        h.cancel_batch(&rt, vec![TxnIDParams { id: TxnID(1), proposal_hash: vec![] }]),
+    );
+    rt.reset();
+    check_state(&rt);
+}
+
+#[test]
+fn governance_changes_wait_for_delay() {
+    let msig = Address::new_id(100);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 1, 0, 0, vec![anne, bob]);
+    rt.set_epoch(100);
+
+    // without a delay, changes apply immediately
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    h.set_governance_delay(&rt, 10).unwrap();
+    let st: State = rt.get_state();
+    assert_eq!(10, st.governance_delay);
+
+    // with a delay, changes are queued
+    h.add_signer(&rt, chuck, true).unwrap();
+    h.change_num_approvals_threshold(&rt, 3).unwrap();
+    rt.set_epoch(105);
+    h.set_governance_delay(&rt, 0).unwrap();
+    let st: State = rt.get_state();
+    assert_eq!(vec![anne, bob], st.signers);
+    assert_eq!(1, st.num_approvals_threshold);
+    assert_eq!(10, st.governance_delay);
+    assert_eq!(3, st.pending_governance.len());
+    assert_eq!(110, st.pending_governance[0].effective_at);
+    assert_eq!(115, st.pending_governance[2].effective_at);
+
+    // nothing has matured yet
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, chuck);
+    rt.set_epoch(109);
+    assert_eq!(0, h.apply_governance_changes(&rt).unwrap().size());
+
+    // the signer addition matures first and also raises the threshold to 2,
+    // leaving the explicit threshold change valid
+    rt.set_epoch(110);
+    let ret = h.apply_governance_changes(&rt).unwrap();
+    assert_eq!(vec![ExitCode::OK, ExitCode::OK], ret.codes());
+    let st: State = rt.get_state();
+    assert_eq!(vec![anne, bob, chuck], st.signers);
+    assert_eq!(3, st.num_approvals_threshold);
+    assert_eq!(1, st.pending_governance.len());
+
+    rt.set_epoch(115);
+    assert!(h.apply_governance_changes(&rt).unwrap().all_ok());
+    let st: State = rt.get_state();
+    assert_eq!(0, st.governance_delay);
+    assert!(st.pending_governance.is_empty());
+
+    // an invalid change is discarded when applied
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    h.set_governance_delay(&rt, 5).unwrap();
+    h.change_num_approvals_threshold(&rt, 4).unwrap();
+    rt.set_epoch(120);
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    let ret = h.apply_governance_changes(&rt).unwrap();
+    assert_eq!(vec![ExitCode::USR_ILLEGAL_ARGUMENT], ret.codes());
+    let st: State = rt.get_state();
+    assert_eq!(3, st.num_approvals_threshold);
+    assert!(st.pending_governance.is_empty());
+
+    // only the multisig itself may set the delay
+    expect_abort(ExitCode::USR_FORBIDDEN, h.set_governance_delay(&rt, 0));
+    rt.reset();
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.set_governance_delay(&rt, -1));
+    rt.reset();
+    check_state(&rt);
+}
+
+#[test]
+fn pending_governance_changes_are_bounded() {
+    let msig = Address::new_id(100);
+    let anne = Address::new_id(101);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 1, 0, 0, vec![anne]);
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    h.set_governance_delay(&rt, 10).unwrap();
+
+    for i in 0..=MAX_PENDING_GOVERNANCE_CHANGES as u64 {
+        rt.set_address_actor_type(Address::new_id(1000 + i), *ACCOUNT_ACTOR_CODE_ID);
+    }
+    for i in 0..MAX_PENDING_GOVERNANCE_CHANGES as u64 {
+        h.add_signer(&rt, Address::new_id(1000 + i), false).unwrap();
+    }
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        h.add_signer(&rt, Address::new_id(1000 + MAX_PENDING_GOVERNANCE_CHANGES as u64), false),
+    );
+    rt.reset();
+
+    // applying matured changes makes room for more
+    rt.set_epoch(10);
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    assert!(h.apply_governance_changes(&rt).unwrap().all_ok());
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    h.add_signer(&rt, Address::new_id(1000 + MAX_PENDING_GOVERNANCE_CHANGES as u64), false)
+        .unwrap();
+    check_state(&rt);
+}
+
+#[test]
+fn state_without_governance_keeps_encoding() {
+    let msig = Address::new_id(100);
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 1, 0, 0, vec![Address::new_id(101)]);
+
+    // State before governance delays were introduced was a seven-element tuple.
+    let st: State = rt.get_state();
+    let legacy = fvm_ipld_encoding::to_vec(&(
+        &st.signers,
+        st.num_approvals_threshold,
+        st.next_tx_id,
+        &st.initial_balance,
+        st.start_epoch,
+        st.unlock_duration,
+        st.pending_txs,
+    ))
+    .unwrap();
+    assert_eq!(legacy, fvm_ipld_encoding::to_vec(&st).unwrap());
+    let decoded: State = fvm_ipld_encoding::from_slice(&legacy).unwrap();
+    assert_eq!(0, decoded.governance_delay);
+    assert!(decoded.pending_governance.is_empty());
+
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    h.set_governance_delay(&rt, 10).unwrap();
+    let st: State = rt.get_state();
+    let decoded: State =
+        fvm_ipld_encoding::from_slice(&fvm_ipld_encoding::to_vec(&st).unwrap()).unwrap();
+    assert_eq!(10, decoded.governance_delay);
+}
+
 fn to_ipld_block(p: RawBytes) -> Option<IpldBlock> {
     Some(IpldBlock { codec: CBOR, data: p.to_vec() })
 }
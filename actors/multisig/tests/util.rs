@@ -1,12 +1,17 @@
 use fil_actor_multisig::{
     compute_proposal_hash, Actor, AddSignerParams, ApproveReturn, ConstructorParams, Method,
-    PendingTxnMap, ProposeParams, ProposeReturn, RemoveSignerParams, State, SwapSignerParams,
-    Transaction, TxnID, TxnIDParams, PENDING_TXN_CONFIG,
+    PendingTxnMap, ProposeParams, ProposeReturn, ProposeWithExpiryParams, RemoveSignerParams,
+    State, SwapSignerParams, Transaction, TxnID, TxnIDParams, PENDING_TXN_CONFIG,
+};
+use fil_actor_multisig::{
+    ApproveBatchParams, ApproveBatchReturn, CancelBatchParams, ChangeNumApprovalsThresholdParams,
+    GetPendingTransactionParams, GetPendingTransactionReturn, ListPendingTransactionsParams,
+    ListPendingTransactionsReturn, LockBalanceParams, PreviewProposalHashParams,
+    PreviewProposalHashReturn, PruneExpiredParams, PruneExpiredReturn, SetGovernanceDelayParams,
 };
-use fil_actor_multisig::{ChangeNumApprovalsThresholdParams, LockBalanceParams};
 use fil_actors_runtime::test_utils::*;
-use fil_actors_runtime::ActorError;
 use fil_actors_runtime::INIT_ACTOR_ADDR;
+use fil_actors_runtime::{ActorError, BatchReturn};
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
@@ -91,6 +96,22 @@ impl ActorHarness {
         ret
     }
 
+    pub fn swap_signers_with_approvals(
+        &self,
+        rt: &MockRuntime,
+        old_signer: Address,
+        new_signer: Address,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.expect_validate_caller_addr(vec![rt.receiver]);
+        let params = SwapSignerParams { from: old_signer, to: new_signer };
+        let ret = rt.call::<Actor>(
+            Method::SwapSignerWithApprovalsExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        ret
+    }
+
     pub fn propose_ok(
         &self,
         rt: &MockRuntime,
@@ -102,7 +123,14 @@ impl ActorHarness {
         let ret = self.propose(rt, to, value.clone(), method, params.clone());
         ret.unwrap().unwrap().deserialize::<ProposeReturn>().unwrap();
         // compute proposal hash
-        let txn = Transaction { to, value, method, params, approved: vec![*rt.caller.borrow()] };
+        let txn = Transaction {
+            to,
+            value,
+            method,
+            params,
+            approved: vec![*rt.caller.borrow()],
+            expires_at: None,
+        };
         compute_proposal_hash(&txn, rt).unwrap()
     }
 
@@ -133,6 +161,25 @@ impl ActorHarness {
         ret
     }
 
+    pub fn propose_with_expiry(
+        &self,
+        rt: &MockRuntime,
+        to: Address,
+        value: TokenAmount,
+        method: MethodNum,
+        params: RawBytes,
+        expires_at: ChainEpoch,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.expect_validate_caller_any();
+        let propose_params = ProposeWithExpiryParams { to, value, method, params, expires_at };
+        let ret = rt.call::<Actor>(
+            Method::ProposeWithExpiryExported as u64,
+            IpldBlock::serialize_cbor(&propose_params).unwrap(),
+        );
+        rt.verify();
+        ret
+    }
+
     pub fn approve(
         &self,
         rt: &MockRuntime,
@@ -200,6 +247,119 @@ impl ActorHarness {
         ret
     }
 
+    pub fn list_pending_transactions(
+        &self,
+        rt: &MockRuntime,
+        cursor: Option<TxnID>,
+        limit: u64,
+    ) -> Result<ListPendingTransactionsReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = ListPendingTransactionsParams { cursor, limit };
+        let ret = rt.call::<Actor>(
+            Method::ListPendingTransactionsExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn get_pending_transaction(
+        &self,
+        rt: &MockRuntime,
+        id: TxnID,
+    ) -> Result<GetPendingTransactionReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = GetPendingTransactionParams { id };
+        let ret = rt.call::<Actor>(
+            Method::GetPendingTransactionExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn preview_proposal_hash(
+        &self,
+        rt: &MockRuntime,
+        params: PreviewProposalHashParams,
+    ) -> Result<Vec<u8>, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<Actor>(
+            Method::PreviewProposalHashExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize::<PreviewProposalHashReturn>().unwrap().proposal_hash)
+    }
+
+    pub fn approve_batch(
+        &self,
+        rt: &MockRuntime,
+        approvals: Vec<TxnIDParams>,
+    ) -> Result<ApproveBatchReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = ApproveBatchParams { approvals };
+        let ret = rt.call::<Actor>(
+            Method::ApproveBatchExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn cancel_batch(
+        &self,
+        rt: &MockRuntime,
+        cancellations: Vec<TxnIDParams>,
+    ) -> Result<BatchReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = CancelBatchParams { cancellations };
+        let ret = rt.call::<Actor>(
+            Method::CancelBatchExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn set_governance_delay(
+        &self,
+        rt: &MockRuntime,
+        delay: ChainEpoch,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.expect_validate_caller_addr(vec![rt.receiver]);
+        let params = SetGovernanceDelayParams { delay };
+        let ret = rt.call::<Actor>(
+            Method::SetGovernanceDelayExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        ret
+    }
+
+    pub fn apply_governance_changes(&self, rt: &MockRuntime) -> Result<BatchReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<Actor>(Method::ApplyGovernanceChangesExported as u64, None);
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn prune_expired(
+        &self,
+        rt: &MockRuntime,
+        cursor: Option<TxnID>,
+        limit: u64,
+    ) -> Result<PruneExpiredReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = PruneExpiredParams { cursor, limit };
+        let ret = rt.call::<Actor>(
+            Method::PruneExpiredExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
     pub fn assert_transactions(
         &self,
         rt: &MockRuntime,
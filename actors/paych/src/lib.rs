@@ -4,8 +4,9 @@
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, extract_send_result, resolve_to_actor_id,
-    ActorContext, ActorDowncast, ActorError, Array,
+    actor_dispatch, actor_error, actor_exit_code, deserialize_block, extract_send_result,
+    payment_channel_address, payment_channel_nonce, resolve_to_actor_id, ActorContext,
+    ActorDowncast, ActorError, Array, ValidateParams,
 };
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::CBOR;
@@ -21,14 +22,17 @@ use num_traits::Zero;
 
 pub use self::state::{LaneState, Merge, State};
 pub use self::types::*;
+pub use self::voucher::*;
 
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(Actor);
 
 pub mod ext;
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 mod types;
+mod voucher;
 
 // * Updated to specs-actors commit: f47f461b0588e9f0c20c999f6f129c85d669a7aa (v3.0.2)
 
@@ -40,9 +44,12 @@ pub enum Method {
     UpdateChannelState = 2,
     Settle = 3,
     Collect = 4,
+    // Method numbers derived from FRC-0042 standards
+    ListLanesExported = frc42_dispatch::method_hash!("ListLanes"),
+    CollectImmediateExported = frc42_dispatch::method_hash!("CollectImmediate"),
 }
 
-pub const ERR_CHANNEL_STATE_UPDATE_AFTER_SETTLED: ExitCode = ExitCode::new(32);
+pub const ERR_CHANNEL_STATE_UPDATE_AFTER_SETTLED: ExitCode = actor_exit_code(32);
 
 /// Payment Channel actor
 pub struct Actor;
@@ -58,6 +65,23 @@ impl Actor {
         let to = resolve_to_actor_id(rt, &params.to, true).map(Address::new_id)?;
         let from = resolve_to_actor_id(rt, &params.from, true).map(Address::new_id)?;
 
+        // A channel at an f4 address in the init actor's namespace must be between the
+        // parties from which the address was derived.
+        let receiver = rt.message().receiver().id().unwrap();
+        if let Some(delegated) = rt.lookup_delegated_address(receiver) {
+            if let Some(nonce) = payment_channel_nonce(&delegated) {
+                if payment_channel_address(rt, &params.from, &params.to, nonce)? != delegated {
+                    return Err(actor_error!(
+                        illegal_argument,
+                        "channel address {} not derived from parties {} and {}",
+                        delegated,
+                        params.from,
+                        params.to
+                    ));
+                }
+            }
+        }
+
         let empty_arr_cid =
             Array::<(), _>::new_with_bit_width(rt.store(), LANE_STATES_AMT_BITWIDTH)
                 .flush()
@@ -135,25 +159,7 @@ impl Actor {
                     svpch_id, pch_addr));
         }
 
-        if rt.curr_epoch() < sv.time_lock_min {
-            return Err(actor_error!(illegal_argument; "cannot use this voucher yet"));
-        }
-
-        if sv.time_lock_max != 0 && rt.curr_epoch() > sv.time_lock_max {
-            return Err(actor_error!(illegal_argument; "this voucher has expired"));
-        }
-
-        if sv.amount.is_negative() {
-            return Err(actor_error!(illegal_argument;
-                    "voucher amount must be non-negative, was {}", sv.amount));
-        }
-
-        if !sv.secret_pre_image.is_empty() {
-            let hashed_secret: &[u8] = &rt.hash_blake2b(&params.secret);
-            if hashed_secret != sv.secret_pre_image.as_slice() {
-                return Err(actor_error!(illegal_argument; "incorrect secret"));
-            }
-        }
+        check_voucher_terms(&sv, &params.secret, rt.curr_epoch(), rt)?;
 
         if let Some(extra) = &sv.extra {
             extract_send_result(rt.send_simple(
@@ -170,75 +176,18 @@ impl Actor {
                 e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load lane states")
             })?;
 
-            // Find the voucher lane, create and insert it in sorted order if necessary.
-            let lane_id = sv.lane;
-            let lane_state = find_lane(&l_states, lane_id)?;
-
-            let mut lane_state = if let Some(state) = lane_state {
-                if state.nonce >= sv.nonce {
-                    return Err(actor_error!(illegal_argument;
-                        "voucher has an outdated nonce, existing: {}, voucher: {}, cannot redeem",
-                        state.nonce, sv.nonce));
-                }
-                state.clone()
-            } else {
-                LaneState::default()
-            };
-
-            // The next section actually calculates the payment amounts to update
-            // the payment channel state
-            // 1. (optional) sum already redeemed value of all merging lanes
-            let mut redeemed_from_others = TokenAmount::zero();
-            for merge in sv.merges {
-                if merge.lane == sv.lane {
-                    return Err(actor_error!(illegal_argument;
-                        "voucher cannot merge lanes into it's own lane"));
-                }
-                let mut other_ls = find_lane(&l_states, merge.lane)?
-                    .ok_or_else(|| {
-                        actor_error!(illegal_argument;
-                        "voucher specifies invalid merge lane {}", merge.lane)
-                    })?
-                    .clone();
-
-                if other_ls.nonce >= merge.nonce {
-                    return Err(actor_error!(illegal_argument;
-                            "merged lane in voucher has outdated nonce, cannot redeem"));
-                }
-
-                redeemed_from_others += &other_ls.redeemed;
-                other_ls.nonce = merge.nonce;
-                l_states.set(merge.lane, other_ls).map_err(|e| {
+            let redemption = redeem_voucher(&sv, &st.to_send, &rt.current_balance(), |id| {
+                Ok(find_lane(&l_states, id)?.cloned())
+            })?;
+            for (lane, lane_state) in redemption.merged_lanes {
+                l_states.set(lane, lane_state).map_err(|e| {
                     e.downcast_default(
                         ExitCode::USR_ILLEGAL_STATE,
-                        format!("failed to store lane {}", merge.lane),
+                        format!("failed to store lane {}", lane),
                     )
                 })?;
             }
-
-            // 2. To prevent double counting, remove already redeemed amounts (from
-            // voucher or other lanes) from the voucher amount
-            lane_state.nonce = sv.nonce;
-            let balance_delta = &sv.amount - (redeemed_from_others + &lane_state.redeemed);
-
-            // 3. set new redeemed value for merged-into lane
-            lane_state.redeemed = sv.amount;
-
-            // 4. check operation validity
-            let new_send_balance = balance_delta + &st.to_send;
-
-            if new_send_balance < TokenAmount::zero() {
-                return Err(actor_error!(illegal_argument;
-                    "voucher would leave channel balance negative"));
-            }
-
-            if new_send_balance > rt.current_balance() {
-                return Err(actor_error!(illegal_argument;
-                    "not enough funds in channel to cover voucher"));
-            }
-
-            // 5. add new redemption ToSend
-            st.to_send = new_send_balance;
+            st.to_send = redemption.to_send;
 
             // update channel settlingAt and MinSettleHeight if delayed by voucher
             if sv.min_settle_height != 0 {
@@ -250,10 +199,10 @@ impl Actor {
                 }
             }
 
-            l_states.set(lane_id, lane_state).map_err(|e| {
+            l_states.set(sv.lane, redemption.lane_state).map_err(|e| {
                 e.downcast_default(
                     ExitCode::USR_ILLEGAL_STATE,
-                    format!("failed to store lane {}", lane_id),
+                    format!("failed to store lane {}", sv.lane),
                 )
             })?;
 
@@ -289,20 +238,89 @@ impl Actor {
             return Err(actor_error!(forbidden; "payment channel not settling or settled"));
         }
 
-        // send ToSend to `to`
-        extract_send_result(rt.send_simple(&st.to, METHOD_SEND, None, st.to_send))
-            .map_err(|e| e.wrap("Failed to send funds to `to` address"))?;
+        pay_out_and_delete(rt, st)
+    }
 
-        // return remaining balance back to the "from" address.
-        extract_send_result(rt.send_simple(&st.from, METHOD_SEND, None, rt.current_balance()))
-            .map_err(|e| e.wrap("Failed to send funds to `from` address"))?;
+    /// Settles and collects the channel in one message, skipping the settlement delay.
+    /// The message must be sent by one party and carry the other party's signature over the
+    /// channel's current `to_send` amount.
+    pub fn collect_immediate(
+        rt: &impl Runtime,
+        params: CollectImmediateParams,
+    ) -> Result<(), ActorError> {
+        let st: State = rt.state()?;
+        rt.validate_immediate_caller_is(&[st.from, st.to])?;
+        let signer = if rt.message().caller() == st.from { st.to } else { st.from };
 
-        rt.delete_actor()?;
+        let agreement = CollectImmediateAgreement {
+            channel_addr: rt.message().receiver(),
+            to_send: st.to_send.clone(),
+        };
+        let agreement_bz = agreement.signing_bytes().map_err(|e| {
+            ActorError::serialization(format!("failed to serialize collect agreement: {}", e))
+        })?;
 
-        Ok(())
+        if !extract_send_result(rt.send(
+            &signer,
+            ext::account::AUTHENTICATE_MESSAGE_METHOD,
+            IpldBlock::serialize_cbor(&ext::account::AuthenticateMessageParams {
+                signature: params.signature.bytes,
+                message: agreement_bz,
+            })?,
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+        ))
+        .and_then(deserialize_block)
+        .context("collect agreement authentication failed")?
+        {
+            return Err(actor_error!(
+                illegal_argument,
+                "collect agreement sig authentication failed"
+            ));
+        }
+
+        pay_out_and_delete(rt, st)
+    }
+
+    /// Returns a page of at most `limit` lane states, in lane ID order.
+    pub fn list_lanes(
+        rt: &impl Runtime,
+        params: ListLanesParams,
+    ) -> Result<ListLanesReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params.validate()?;
+        let st: State = rt.state()?;
+        let l_states = Array::<LaneState, _>::load(&st.lane_states, rt.store())
+            .map_err(|e| e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load lanes"))?;
+
+        let mut lanes = Vec::new();
+        let (_, next_cursor) = l_states
+            .for_each_ranged(params.cursor, Some(params.limit), |lane, ls| {
+                lanes.push(LaneInfo { lane, redeemed: ls.redeemed.clone(), nonce: ls.nonce });
+                Ok(())
+            })
+            .map_err(|e| e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to list lanes"))?;
+        Ok(ListLanesReturn { lanes, next_cursor })
     }
 }
 
+/// Sends the redeemed amount to `to`, returns the remaining balance to `from`, and deletes
+/// the channel.
+fn pay_out_and_delete(rt: &impl Runtime, st: State) -> Result<(), ActorError> {
+    // send ToSend to `to`
+    extract_send_result(rt.send_simple(&st.to, METHOD_SEND, None, st.to_send))
+        .map_err(|e| e.wrap("Failed to send funds to `to` address"))?;
+
+    // return remaining balance back to the "from" address.
+    extract_send_result(rt.send_simple(&st.from, METHOD_SEND, None, rt.current_balance()))
+        .map_err(|e| e.wrap("Failed to send funds to `from` address"))?;
+
+    rt.delete_actor()?;
+
+    Ok(())
+}
+
 #[inline]
 fn find_lane<'a, BS>(
     ls: &'a Array<LaneState, BS>,
@@ -332,5 +350,7 @@ impl ActorCode for Actor {
         UpdateChannelState => update_channel_state,
         Settle => settle,
         Collect => collect,
+        ListLanesExported => list_lanes,
+        CollectImmediateExported => collect_immediate,
     }
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fil_actors_runtime::network::EPOCHS_IN_HOUR;
+use fil_actors_runtime::validate_params;
 use fvm_ipld_encoding::tuple::*;
 use fvm_ipld_encoding::{strict_bytes, to_vec, Error, RawBytes};
 use fvm_shared::address::Address;
@@ -126,3 +127,52 @@ impl From<SignedVoucher> for UpdateChannelStateParams {
         UpdateChannelStateParams { secret: vec![], sv }
     }
 }
+
+/// Agreement by one party to collect a channel immediately, without waiting for the
+/// settlement delay. Signed by the party not sending the collect message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct CollectImmediateAgreement {
+    /// ID address of the payment channel being collected.
+    pub channel_addr: Address,
+    /// Amount to be paid out to `to`, which must match the channel's redeemed amount.
+    pub to_send: TokenAmount,
+}
+
+impl CollectImmediateAgreement {
+    pub fn signing_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_vec(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct CollectImmediateParams {
+    /// Counterparty's signature over the `CollectImmediateAgreement` for the channel's
+    /// current state.
+    pub signature: Signature,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListLanesParams {
+    /// Lane ID from which to resume listing (inclusive), as returned by a previous call.
+    /// None starts from the first lane.
+    pub cursor: Option<u64>,
+    /// Maximum number of lanes to return.
+    pub limit: u64,
+}
+
+validate_params!(ListLanesParams { limit: range(1, u64::MAX) });
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct LaneInfo {
+    pub lane: u64,
+    pub redeemed: TokenAmount,
+    pub nonce: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListLanesReturn {
+    pub lanes: Vec<LaneInfo>,
+    /// Cursor to pass to a subsequent call to continue listing, if more lanes remain.
+    pub next_cursor: Option<u64>,
+}
@@ -0,0 +1,172 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fil_actors_runtime::runtime::Primitives;
+use fil_actors_runtime::{actor_error, ActorDowncast, ActorError, Array};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use num_traits::Zero;
+
+use crate::{
+    LaneState, SignedVoucher, State, ERR_CHANNEL_STATE_UPDATE_AFTER_SETTLED, MAX_LANE,
+    MAX_SECRET_SIZE,
+};
+
+/// The lane states and amount to send that result from redeeming a voucher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoucherRedemption {
+    /// The new state of the voucher's lane.
+    pub lane_state: LaneState,
+    /// The new states of the lanes merged into the voucher's lane, by lane ID.
+    pub merged_lanes: Vec<(u64, LaneState)>,
+    /// The channel's new amount to send.
+    pub to_send: TokenAmount,
+}
+
+/// Validates a voucher against a channel's state, as `UpdateChannelState` would, returning the
+/// state redeeming it would result in.
+/// This doesn't check the voucher's signature, that its channel address is the channel's, nor
+/// call its `extra` verification method, which all need the chain.
+///
+/// This lets clients check a voucher before they submit it.
+pub fn validate_voucher<BS: Blockstore>(
+    sv: &SignedVoucher,
+    secret: &[u8],
+    st: &State,
+    balance: &TokenAmount,
+    curr_epoch: ChainEpoch,
+    store: &BS,
+    sys: &dyn Primitives,
+) -> Result<VoucherRedemption, ActorError> {
+    if st.settling_at != 0 && curr_epoch >= st.settling_at {
+        return Err(ActorError::unchecked(
+            ERR_CHANNEL_STATE_UPDATE_AFTER_SETTLED,
+            "no vouchers can be processed after settling at epoch".to_string(),
+        ));
+    }
+    if secret.len() > MAX_SECRET_SIZE {
+        return Err(actor_error!(illegal_argument, "secret must be at most 256 bytes long"));
+    }
+    check_voucher_terms(sv, secret, curr_epoch, sys)?;
+
+    let lanes = Array::<LaneState, _>::load(&st.lane_states, store).map_err(|e| {
+        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load lane states")
+    })?;
+    redeem_voucher(sv, &st.to_send, balance, |id| {
+        lanes.get(id).map(|l| l.cloned()).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, format!("failed to load lane {}", id))
+        })
+    })
+}
+
+/// Checks a voucher's time locks and amount at an epoch, and that the secret matches its
+/// preimage, if it has one.
+pub fn check_voucher_terms(
+    sv: &SignedVoucher,
+    secret: &[u8],
+    curr_epoch: ChainEpoch,
+    sys: &dyn Primitives,
+) -> Result<(), ActorError> {
+    if curr_epoch < sv.time_lock_min {
+        return Err(actor_error!(illegal_argument; "cannot use this voucher yet"));
+    }
+
+    if sv.time_lock_max != 0 && curr_epoch > sv.time_lock_max {
+        return Err(actor_error!(illegal_argument; "this voucher has expired"));
+    }
+
+    if sv.amount.is_negative() {
+        return Err(actor_error!(illegal_argument;
+                "voucher amount must be non-negative, was {}", sv.amount));
+    }
+
+    if !sv.secret_pre_image.is_empty() {
+        let hashed_secret: &[u8] = &sys.hash_blake2b(secret);
+        if hashed_secret != sv.secret_pre_image.as_slice() {
+            return Err(actor_error!(illegal_argument; "incorrect secret"));
+        }
+    }
+    Ok(())
+}
+
+/// Computes the lane states and amount to send after redeeming a voucher, checking its lane
+/// and merged lanes' nonces, and that the channel's balance covers the amount to send.
+/// Lanes are looked up with `get_lane`.
+pub fn redeem_voucher(
+    sv: &SignedVoucher,
+    to_send: &TokenAmount,
+    balance: &TokenAmount,
+    mut get_lane: impl FnMut(u64) -> Result<Option<LaneState>, ActorError>,
+) -> Result<VoucherRedemption, ActorError> {
+    let mut find_lane = |id: u64| {
+        if id > MAX_LANE {
+            return Err(actor_error!(illegal_argument; "maximum lane ID is 2^63-1"));
+        }
+        get_lane(id)
+    };
+
+    // Find the voucher lane, creating it if necessary.
+    let mut lane_state = if let Some(state) = find_lane(sv.lane)? {
+        if state.nonce >= sv.nonce {
+            return Err(actor_error!(illegal_argument;
+                "voucher has an outdated nonce, existing: {}, voucher: {}, cannot redeem",
+                state.nonce, sv.nonce));
+        }
+        state
+    } else {
+        LaneState::default()
+    };
+
+    // 1. (optional) sum already redeemed value of all merging lanes
+    let mut redeemed_from_others = TokenAmount::zero();
+    let mut merged_lanes: Vec<(u64, LaneState)> = vec![];
+    for merge in &sv.merges {
+        if merge.lane == sv.lane {
+            return Err(actor_error!(illegal_argument;
+                "voucher cannot merge lanes into it's own lane"));
+        }
+        // A lane merged earlier in the voucher has its updated state.
+        let merged = merged_lanes.iter().rposition(|(id, _)| *id == merge.lane);
+        let mut other_ls = match merged {
+            Some(i) => merged_lanes[i].1.clone(),
+            None => find_lane(merge.lane)?.ok_or_else(|| {
+                actor_error!(illegal_argument;
+                "voucher specifies invalid merge lane {}", merge.lane)
+            })?,
+        };
+
+        if other_ls.nonce >= merge.nonce {
+            return Err(actor_error!(illegal_argument;
+                    "merged lane in voucher has outdated nonce, cannot redeem"));
+        }
+
+        redeemed_from_others += &other_ls.redeemed;
+        other_ls.nonce = merge.nonce;
+        merged_lanes.push((merge.lane, other_ls));
+    }
+
+    // 2. To prevent double counting, remove already redeemed amounts (from
+    // voucher or other lanes) from the voucher amount
+    lane_state.nonce = sv.nonce;
+    let balance_delta = &sv.amount - (redeemed_from_others + &lane_state.redeemed);
+
+    // 3. set new redeemed value for merged-into lane
+    lane_state.redeemed = sv.amount.clone();
+
+    // 4. check operation validity
+    let new_send_balance = balance_delta + to_send;
+
+    if new_send_balance < TokenAmount::zero() {
+        return Err(actor_error!(illegal_argument;
+            "voucher would leave channel balance negative"));
+    }
+
+    if new_send_balance > *balance {
+        return Err(actor_error!(illegal_argument;
+            "not enough funds in channel to cover voucher"));
+    }
+
+    Ok(VoucherRedemption { lane_state, merged_lanes, to_send: new_send_balance })
+}
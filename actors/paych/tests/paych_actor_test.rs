@@ -9,14 +9,16 @@ use derive_builder::Builder;
 use fil_actor_paych::ext::account::{AuthenticateMessageParams, AUTHENTICATE_MESSAGE_METHOD};
 use fil_actor_paych::testing::check_state_invariants;
 use fil_actor_paych::{
-    Actor as PaychActor, ConstructorParams, LaneState, Merge, Method, ModVerifyParams,
-    SignedVoucher, State as PState, UpdateChannelStateParams, MAX_LANE, SETTLE_DELAY,
+    validate_voucher, Actor as PaychActor, CollectImmediateAgreement, CollectImmediateParams,
+    ConstructorParams, LaneState, ListLanesParams, ListLanesReturn, Merge, Method, ModVerifyParams,
+    SignedVoucher, State as PState, UpdateChannelStateParams, VoucherRedemption, MAX_LANE,
+    SETTLE_DELAY,
 };
 
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::test_utils::*;
-use fil_actors_runtime::INIT_ACTOR_ADDR;
+use fil_actors_runtime::{payment_channel_address, ActorError, INIT_ACTOR_ADDR};
 use fvm_ipld_amt::Amt;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
@@ -110,6 +112,32 @@ mod paych_constructor {
         check_state(&rt);
     }
 
+    #[test]
+    fn create_paych_actor_at_derived_address() {
+        let payer_addr = Address::new_id(TEST_PAYER_ADDR);
+        let caller_addr = Address::new_id(TEST_CALLER_ADDR);
+        let rt = construct_runtime();
+        rt.actor_code_cids.borrow_mut().insert(caller_addr, *ACCOUNT_ACTOR_CODE_ID);
+
+        // An address derived from other parties is rejected.
+        let f4_addr = payment_channel_address(&rt, &caller_addr, &payer_addr, 1).unwrap();
+        rt.set_delegated_address(TEST_PAYCH_ADDR, f4_addr);
+        rt.set_caller(*INIT_ACTOR_CODE_ID, INIT_ACTOR_ADDR);
+        rt.expect_validate_caller_type(vec![Type::Init]);
+        let params = ConstructorParams { from: payer_addr, to: caller_addr };
+        expect_abort(
+            &rt,
+            METHOD_CONSTRUCTOR,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+        );
+
+        let f4_addr = payment_channel_address(&rt, &payer_addr, &caller_addr, 1).unwrap();
+        rt.set_delegated_address(TEST_PAYCH_ADDR, f4_addr);
+        construct_and_verify(&rt, payer_addr, caller_addr);
+        check_state(&rt);
+    }
+
     #[test]
     fn actor_doesnt_exist_test() {
         let rt = construct_runtime();
@@ -672,6 +700,81 @@ mod merge_tests {
     }
 }
 
+mod validate_voucher_tests {
+    use super::*;
+
+    fn validate(rt: &MockRuntime, sv: &SignedVoucher) -> Result<VoucherRedemption, ActorError> {
+        let state: PState = rt.get_state();
+        validate_voucher(sv, &[], &state, &rt.get_balance(), *rt.epoch.borrow(), rt.store(), rt)
+    }
+
+    #[test]
+    fn matches_redemption() {
+        let (rt, mut sv) = require_create_channel_with_lanes(2);
+        let state: PState = rt.get_state();
+        let merge_from = get_lane_state(&rt, &state.lane_states, 1);
+
+        sv.lane = 0;
+        sv.nonce = 10;
+        sv.amount = TokenAmount::from_atto(50);
+        sv.merges = vec![Merge { lane: 1, nonce: 10 }];
+        let redemption = validate(&rt, &sv).unwrap();
+        assert_eq!(LaneState { redeemed: sv.amount.clone(), nonce: 10 }, redemption.lane_state);
+        assert_eq!(
+            vec![(1, LaneState { redeemed: merge_from.redeemed, nonce: 10 })],
+            redemption.merged_lanes
+        );
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, state.from);
+        rt.expect_validate_caller_addr(vec![state.from, state.to]);
+        expect_authenticate_message(&rt, Address::new_id(PAYEE_ID), sv.clone(), ExitCode::OK);
+        call(
+            &rt,
+            Method::UpdateChannelState as u64,
+            IpldBlock::serialize_cbor(&UpdateChannelStateParams::from(sv)).unwrap(),
+        );
+        rt.verify();
+        let state: PState = rt.get_state();
+        assert_eq!(redemption.to_send, state.to_send);
+        assert_eq!(redemption.lane_state, get_lane_state(&rt, &state.lane_states, 0));
+        check_state(&rt);
+    }
+
+    #[test]
+    fn rejects_invalid_vouchers() {
+        let (rt, sv) = require_create_channel_with_lanes(2);
+
+        // The voucher returned has already been redeemed once.
+        let mut outdated = sv.clone();
+        outdated.nonce -= 1;
+        assert_eq!(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            validate(&rt, &outdated).unwrap_err().exit_code()
+        );
+
+        let mut expired = sv.clone();
+        expired.time_lock_max = *rt.epoch.borrow() - 1;
+        assert_eq!(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            validate(&rt, &expired).unwrap_err().exit_code()
+        );
+
+        let mut overdrawn = sv.clone();
+        overdrawn.amount = rt.get_balance() + TokenAmount::from_atto(1);
+        assert_eq!(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            validate(&rt, &overdrawn).unwrap_err().exit_code()
+        );
+
+        // A lane merged twice is checked against the nonce of its first merge.
+        let mut twice = sv;
+        twice.lane = 2;
+        twice.nonce = 1;
+        twice.merges = vec![Merge { lane: 0, nonce: 6 }, Merge { lane: 0, nonce: 5 }];
+        assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, validate(&rt, &twice).unwrap_err().exit_code());
+    }
+}
+
 mod update_channel_state_extra {
     use super::*;
     use fvm_ipld_encoding::CBOR;
@@ -1020,6 +1123,121 @@ mod actor_collect {
             check_state(&rt);
         }
     }
+
+    fn expect_authenticate_agreement(rt: &MockRuntime, signer: Address, exp_exit_code: ExitCode) {
+        let st: PState = rt.get_state();
+        let agreement =
+            CollectImmediateAgreement { channel_addr: rt.receiver, to_send: st.to_send };
+        rt.expect_send(
+            signer,
+            AUTHENTICATE_MESSAGE_METHOD,
+            IpldBlock::serialize_cbor(&AuthenticateMessageParams {
+                signature: vec![1, 2, 3],
+                message: agreement.signing_bytes().unwrap(),
+            })
+            .unwrap(),
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+            exp_exit_code.is_success().then(|| AUTHENTICATE_MESSAGE_RESPONSE.clone()).flatten(),
+            exp_exit_code,
+            None,
+        )
+    }
+
+    fn collect_immediate_params() -> Option<IpldBlock> {
+        IpldBlock::serialize_cbor(&CollectImmediateParams {
+            signature: Signature::new_bls(vec![1, 2, 3]),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn collect_immediate_skips_settlement() {
+        let (rt, _sv) = require_create_channel_with_lanes(1);
+        rt.epoch.replace(10);
+        let st: PState = rt.get_state();
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, st.to);
+        rt.expect_validate_caller_addr(vec![st.from, st.to]);
+        expect_authenticate_agreement(&rt, st.from, ExitCode::OK);
+        rt.expect_send_simple(
+            st.to,
+            METHOD_SEND,
+            Default::default(),
+            st.to_send.clone(),
+            Default::default(),
+            ExitCode::OK,
+        );
+        rt.expect_send_simple(
+            st.from,
+            METHOD_SEND,
+            Default::default(),
+            &*rt.balance.borrow() - &st.to_send,
+            Default::default(),
+            ExitCode::OK,
+        );
+        rt.expect_delete_actor();
+        let res = call(&rt, Method::CollectImmediateExported as u64, collect_immediate_params());
+        assert!(res.is_none());
+        rt.verify();
+        assert!(rt.is_deleted());
+    }
+
+    #[test]
+    fn collect_immediate_requires_counterparty_signature() {
+        let (rt, _sv) = require_create_channel_with_lanes(1);
+        let st: PState = rt.get_state();
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, st.from);
+        rt.expect_validate_caller_addr(vec![st.from, st.to]);
+        expect_authenticate_agreement(&rt, st.to, ExitCode::USR_ILLEGAL_ARGUMENT);
+        expect_abort(
+            &rt,
+            Method::CollectImmediateExported as u64,
+            collect_immediate_params(),
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+        );
+        rt.verify();
+        assert!(!rt.is_deleted());
+        check_state(&rt);
+    }
+}
+
+#[test]
+fn list_lanes_paginates() {
+    let (rt, _sv) = require_create_channel_with_lanes(5);
+
+    let list = |cursor: Option<u64>, limit: u64| {
+        rt.expect_validate_caller_any();
+        let params = ListLanesParams { cursor, limit };
+        let ret = rt
+            .call::<PaychActor>(
+                Method::ListLanesExported as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )
+            .map(|r| r.unwrap().deserialize::<ListLanesReturn>().unwrap());
+        rt.verify();
+        ret
+    };
+
+    let first = list(None, 3).unwrap();
+    assert_eq!(vec![0, 1, 2], first.lanes.iter().map(|l| l.lane).collect::<Vec<_>>());
+    assert_eq!(Some(3), first.next_cursor);
+    let second = list(first.next_cursor, 3).unwrap();
+    assert_eq!(vec![3, 4], second.lanes.iter().map(|l| l.lane).collect::<Vec<_>>());
+    assert_eq!(None, second.next_cursor);
+
+    let st: PState = rt.get_state();
+    for info in first.lanes.iter().chain(second.lanes.iter()) {
+        let ls = get_lane_state(&rt, &st.lane_states, info.lane);
+        assert_eq!(ls.redeemed, info.redeemed);
+        assert_eq!(ls.nonce, info.nonce);
+    }
+
+    assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, list(None, 0).unwrap_err().exit_code());
+    rt.reset();
+    check_state(&rt);
 }
 
 fn require_create_channel_with_lanes(num_lanes: u64) -> (MockRuntime, SignedVoucher) {
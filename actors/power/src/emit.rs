@@ -0,0 +1,61 @@
+// A namespace for helpers that build and emit power events.
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::bigint::bigint_ser::BigIntSer;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::sector::{RegisteredPoStProof, StoragePower};
+use fvm_shared::ActorID;
+
+use crate::Claim;
+
+/// Indicates a miner has been created, with a zero power claim.
+pub fn miner_created(
+    rt: &impl Runtime,
+    miner: ActorID,
+    window_post_proof_type: RegisteredPoStProof,
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("miner-created")
+            .field_indexed("miner", &miner)
+            .field("window-post-proof-type", &window_post_proof_type)
+            .build()?,
+    )
+}
+
+/// Indicates a miner's claimed power has changed by the given deltas.
+/// The resulting claim is included so that consumers can recover from a missed event.
+pub fn claimed_power_updated(
+    rt: &impl Runtime,
+    miner: ActorID,
+    raw_byte_delta: &StoragePower,
+    quality_adjusted_delta: &StoragePower,
+    claim: &Claim,
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("claimed-power-updated")
+            .field_indexed("miner", &miner)
+            .field("raw-byte-delta", &BigIntSer(raw_byte_delta))
+            .field("quality-adjusted-delta", &BigIntSer(quality_adjusted_delta))
+            .field("raw-byte-power", &BigIntSer(&claim.raw_byte_power))
+            .field("quality-adjusted-power", &BigIntSer(&claim.quality_adj_power))
+            .build()?,
+    )
+}
+
+/// Indicates a miner has enrolled a cron callback at an epoch.
+pub fn cron_event_enrolled(
+    rt: &impl Runtime,
+    miner: ActorID,
+    epoch: ChainEpoch,
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("cron-event-enrolled")
+            .field_indexed("miner", &miner)
+            .field("epoch", &epoch)
+            .build()?,
+    )
+}
@@ -16,8 +16,9 @@ use ext::init;
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, extract_send_result, ActorDowncast, ActorError,
-    Multimap, CRON_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, actor_exit_code, deserialize_block, extract_send_result,
+    ActorDowncast, ActorError, Multimap, CRON_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR,
+    SYSTEM_ACTOR_ADDR,
 };
 
 pub use self::policy::*;
@@ -27,10 +28,12 @@ pub use self::types::*;
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(Actor);
 
+mod emit;
 #[doc(hidden)]
 pub mod ext;
 mod policy;
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 mod types;
 
@@ -58,7 +61,7 @@ pub enum Method {
     MinerConsensusCountExported = frc42_dispatch::method_hash!("MinerConsensusCount"),
 }
 
-pub const ERR_TOO_MANY_PROVE_COMMITS: ExitCode = ExitCode::new(32);
+pub const ERR_TOO_MANY_PROVE_COMMITS: ExitCode = actor_exit_code(32);
 
 /// Storage Power Actor
 pub struct Actor;
@@ -129,6 +132,11 @@ impl Actor {
             st.save_claims(&mut claims)?;
             Ok(())
         })?;
+
+        let miner_id = id_address.id().map_err(|_| {
+            actor_error!(illegal_state, "init returned non-ID address {} for miner", id_address)
+        })?;
+        emit::miner_created(rt, miner_id, window_post_proof_type)?;
         Ok(CreateMinerReturn { id_address, robust_address })
     }
 
@@ -141,7 +149,7 @@ impl Actor {
         rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let miner_addr = rt.message().caller();
 
-        rt.transaction(|st: &mut State, rt| {
+        let claim = rt.transaction(|st: &mut State, rt| {
             let mut claims = st.load_claims(rt.store())?;
 
             st.add_to_claim(
@@ -151,10 +159,22 @@ impl Actor {
                 &params.raw_byte_delta,
                 &params.quality_adjusted_delta,
             )?;
+            let claim = claims
+                .get(&miner_addr)?
+                .cloned()
+                .ok_or_else(|| actor_error!(illegal_state, "no claim for actor {}", miner_addr))?;
 
             st.save_claims(&mut claims)?;
-            Ok(())
-        })
+            Ok(claim)
+        })?;
+
+        emit::claimed_power_updated(
+            rt,
+            miner_addr.id().unwrap(),
+            &params.raw_byte_delta,
+            &params.quality_adjusted_delta,
+            &claim,
+        )
     }
 
     fn enroll_cron_event(
@@ -194,7 +214,8 @@ impl Actor {
             })?;
             Ok(())
         })?;
-        Ok(())
+
+        emit::cron_event_enrolled(rt, rt.message().caller().id().unwrap(), params.event_epoch)
     }
 
     fn on_epoch_tick_end(rt: &impl Runtime) -> Result<(), ActorError> {
@@ -41,7 +41,7 @@ use fil_actors_runtime::test_utils::{
 };
 use fil_actors_runtime::REWARD_ACTOR_ADDR;
 use fil_actors_runtime::{
-    ActorError, INIT_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    ActorError, EventBuilder, INIT_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
 };
 use fil_actors_runtime::{Map2, MapKey, Multimap};
 use fil_actors_runtime::{CRON_ACTOR_ADDR, DEFAULT_HAMT_CONFIG};
@@ -74,6 +74,52 @@ pub fn new_harness() -> Harness {
     }
 }
 
+pub fn expect_miner_created_event(
+    rt: &MockRuntime,
+    miner: &Address,
+    window_post_proof_type: RegisteredPoStProof,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("miner-created")
+            .field_indexed("miner", &miner.id().unwrap())
+            .field("window-post-proof-type", &window_post_proof_type)
+            .build()
+            .unwrap(),
+    );
+}
+
+pub fn expect_claimed_power_updated_event(
+    rt: &MockRuntime,
+    miner: &Address,
+    raw_delta: &StoragePower,
+    qa_delta: &StoragePower,
+    claim: &Claim,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("claimed-power-updated")
+            .field_indexed("miner", &miner.id().unwrap())
+            .field("raw-byte-delta", &BigIntSer(raw_delta))
+            .field("quality-adjusted-delta", &BigIntSer(qa_delta))
+            .field("raw-byte-power", &BigIntSer(&claim.raw_byte_power))
+            .field("quality-adjusted-power", &BigIntSer(&claim.quality_adj_power))
+            .build()
+            .unwrap(),
+    );
+}
+
+pub fn expect_cron_event_enrolled_event(rt: &MockRuntime, miner: &Address, epoch: ChainEpoch) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("cron-event-enrolled")
+            .field_indexed("miner", &miner.id().unwrap())
+            .field("epoch", &epoch)
+            .build()
+            .unwrap(),
+    );
+}
+
 pub fn setup() -> (Harness, MockRuntime) {
     let rt = new_runtime();
     let h = new_harness();
@@ -163,6 +209,7 @@ impl Harness {
             peer,
             multiaddrs,
         };
+        expect_miner_created_event(rt, miner, window_post_proof_type);
         rt.call::<PowerActor>(
             Method::CreateMiner as MethodNum,
             IpldBlock::serialize_cbor(&params).unwrap(),
@@ -241,7 +288,13 @@ impl Harness {
             payload: payload.clone(),
         })
         .unwrap();
-        rt.call::<PowerActor>(Method::EnrollCronEvent as u64, params)?;
+        expect_cron_event_enrolled_event(rt, miner_address, epoch);
+        let ret = rt.call::<PowerActor>(Method::EnrollCronEvent as u64, params);
+        if ret.is_err() {
+            // No event is emitted if enrolment fails.
+            rt.reset();
+        }
+        ret?;
         rt.verify();
         Ok(())
     }
@@ -316,6 +369,12 @@ impl Harness {
         };
         rt.set_caller(*MINER_ACTOR_CODE_ID, miner);
         rt.expect_validate_caller_type(vec![Type::Miner]);
+        let claim = Claim {
+            raw_byte_power: &prev_cl.raw_byte_power + raw_delta,
+            quality_adj_power: &prev_cl.quality_adj_power + qa_delta,
+            ..prev_cl.clone()
+        };
+        expect_claimed_power_updated_event(rt, &miner, raw_delta, qa_delta, &claim);
         rt.call::<PowerActor>(
             Method::UpdateClaimedPower as MethodNum,
             IpldBlock::serialize_cbor(&params).unwrap(),
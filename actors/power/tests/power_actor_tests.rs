@@ -1074,6 +1074,7 @@ fn create_miner_restricted_correctly() {
         IpldBlock::serialize_cbor(&create_miner_ret).unwrap(),
         ExitCode::OK,
     );
+    expect_miner_created_event(&rt, &MINER, RegisteredPoStProof::StackedDRGWinning2KiBV1);
 
     let ret: CreateMinerReturn = rt
         .call::<PowerActor>(Method::CreateMinerExported as MethodNum, params)
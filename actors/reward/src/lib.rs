@@ -15,6 +15,7 @@ use log::{error, warn};
 use num_derive::FromPrimitive;
 
 pub use self::logic::*;
+pub use self::projection::*;
 pub use self::state::{Reward, State, VestingFunction};
 pub use self::types::*;
 
@@ -23,7 +24,9 @@ fil_actors_runtime::wasm_trampoline!(Actor);
 
 pub(crate) mod expneg;
 mod logic;
+mod projection;
 mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 mod types;
 
@@ -0,0 +1,135 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::StoragePower;
+
+use crate::State;
+
+/// Reward emission figures for a single projected epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochEmission {
+    /// The epoch the figures apply to.
+    pub epoch: ChainEpoch,
+    /// The total reward minted for block producers in this epoch (simple + baseline).
+    pub reward: TokenAmount,
+    /// The baseline power the network is targeting at this epoch.
+    pub baseline_power: StoragePower,
+    /// Ceiling of the effective network time reached at this epoch.
+    pub effective_network_time: ChainEpoch,
+    /// Sum of all rewards minted over the projection up to and including this epoch.
+    pub cumsum_minted: TokenAmount,
+}
+
+/// Projects simple and baseline minting forward from `state`, one epoch per item in
+/// `realized_power`, using exactly the same update path as the reward actor's cron hook.
+/// No runtime is involved, so this may be used off-chain to model emission schedules.
+///
+/// The first projected epoch is `state.epoch + 1`. Epochs are assumed to be non-null.
+pub fn project_emissions<I>(state: &State, realized_power: I) -> Vec<EpochEmission>
+where
+    I: IntoIterator<Item = StoragePower>,
+{
+    let mut st = state.clone();
+    let mut cumsum_minted = TokenAmount::default();
+    realized_power
+        .into_iter()
+        .map(|power| {
+            st.update_to_next_epoch_with_reward(&power);
+            cumsum_minted += &st.this_epoch_reward;
+            EpochEmission {
+                epoch: st.epoch,
+                reward: st.this_epoch_reward.clone(),
+                baseline_power: st.this_epoch_baseline_power.clone(),
+                effective_network_time: st.effective_network_time,
+                cumsum_minted: cumsum_minted.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Projects minting from genesis (a freshly constructed reward state with `realized_power[0]`)
+/// for each subsequent item in `realized_power`. The genesis epoch itself is included as the
+/// first entry of the result.
+pub fn project_emissions_from_genesis<I>(realized_power: I) -> Vec<EpochEmission>
+where
+    I: IntoIterator<Item = StoragePower>,
+{
+    let mut powers = realized_power.into_iter();
+    let Some(initial) = powers.next() else {
+        return Vec::new();
+    };
+    let genesis = State::new(initial);
+    let first = EpochEmission {
+        epoch: genesis.epoch,
+        reward: genesis.this_epoch_reward.clone(),
+        baseline_power: genesis.this_epoch_baseline_power.clone(),
+        effective_network_time: genesis.effective_network_time,
+        cumsum_minted: genesis.this_epoch_reward.clone(),
+    };
+    let mut projection = vec![first.clone()];
+    projection.extend(project_emissions(&genesis, powers).into_iter().map(|mut e| {
+        e.cumsum_minted += &first.cumsum_minted;
+        e
+    }));
+    projection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BASELINE_INITIAL_VALUE;
+    use num_traits::Zero;
+
+    #[test]
+    fn projection_matches_state_updates() {
+        let power = StoragePower::from(1u64 << 50);
+        let mut st = State::new(power.clone());
+        let projection = project_emissions(&st, std::iter::repeat(power.clone()).take(10));
+
+        assert_eq!(10, projection.len());
+        let mut total = TokenAmount::zero();
+        for step in projection {
+            st.update_to_next_epoch_with_reward(&power);
+            total += &st.this_epoch_reward;
+            assert_eq!(st.epoch, step.epoch);
+            assert_eq!(st.this_epoch_reward, step.reward);
+            assert_eq!(st.this_epoch_baseline_power, step.baseline_power);
+            assert_eq!(st.effective_network_time, step.effective_network_time);
+            assert_eq!(total, step.cumsum_minted);
+        }
+    }
+
+    #[test]
+    fn zero_power_mints_decaying_simple_reward_only() {
+        let projection =
+            project_emissions_from_genesis(std::iter::repeat(StoragePower::zero()).take(5));
+
+        assert_eq!(5, projection.len());
+        assert_eq!(0, projection[0].epoch);
+        for pair in projection.windows(2) {
+            assert_eq!(pair[0].epoch + 1, pair[1].epoch);
+            assert_eq!(0, pair[1].effective_network_time);
+            assert!(pair[1].reward < pair[0].reward);
+            assert_eq!(&pair[0].cumsum_minted + &pair[1].reward, pair[1].cumsum_minted);
+        }
+    }
+
+    #[test]
+    fn baseline_power_drives_effective_network_time() {
+        let projection = project_emissions_from_genesis(
+            std::iter::repeat(BASELINE_INITIAL_VALUE.clone() * 2).take(5),
+        );
+        for step in projection {
+            assert_eq!(step.epoch + 1, step.effective_network_time);
+        }
+    }
+
+    #[test]
+    fn empty_trajectory() {
+        assert!(project_emissions_from_genesis(std::iter::empty()).is_empty());
+        let st = State::new(StoragePower::zero());
+        assert!(project_emissions(&st, std::iter::empty()).is_empty());
+    }
+}
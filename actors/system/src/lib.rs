@@ -7,6 +7,7 @@ use fvm_ipld_encoding::CborStore;
 use fvm_shared::error::ExitCode;
 use fvm_shared::METHOD_CONSTRUCTOR;
 use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
 
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
@@ -21,12 +22,22 @@ fil_actors_runtime::wasm_trampoline!(Actor);
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
+    GetBuiltinActorsExported = frc42_dispatch::method_hash!("GetBuiltinActors"),
 }
 
 /// System actor state.
 #[derive(Default, Deserialize_tuple, Serialize_tuple, Debug, Clone)]
 pub struct State {
-    // builtin actor registry: Vec<(String, Cid)>
+    /// The data CID of the manifest of the live network's builtin actors bundle,
+    /// a registry `Vec<(String, Cid)>` of actor names and code CIDs.
+    /// The constructor stores an empty registry. The node sets the bundle's manifest data
+    /// in state at genesis, and each network upgrade's migration replaces it.
+    pub builtin_actors: Cid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GetBuiltinActorsReturn {
     pub builtin_actors: Cid,
 }
 
@@ -62,6 +73,13 @@ impl Actor {
         rt.create(&state)?;
         Ok(())
     }
+
+    /// Returns the data CID of the live network's builtin actors manifest.
+    pub fn get_builtin_actors(rt: &impl Runtime) -> Result<GetBuiltinActorsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        Ok(GetBuiltinActorsReturn { builtin_actors: state.builtin_actors })
+    }
 }
 
 impl ActorCode for Actor {
@@ -73,6 +91,7 @@ impl ActorCode for Actor {
 
     actor_dispatch! {
         Constructor => constructor,
+        GetBuiltinActorsExported => get_builtin_actors,
     }
 }
 
@@ -82,10 +101,14 @@ mod tests {
 
     use fvm_shared::MethodNum;
 
-    use fil_actors_runtime::test_utils::{MockRuntime, SYSTEM_ACTOR_CODE_ID};
+    use fil_actors_runtime::test_utils::{
+        MockRuntime, ACCOUNT_ACTOR_CODE_ID, SYSTEM_ACTOR_CODE_ID,
+    };
     use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
+    use fvm_ipld_encoding::CborStore;
+    use fvm_shared::address::Address;
 
-    use crate::{Actor, Method, State};
+    use crate::{Actor, GetBuiltinActorsReturn, Method, State};
 
     pub fn new_runtime() -> MockRuntime {
         MockRuntime {
@@ -107,4 +130,30 @@ mod tests {
         let builtin_actors = state.get_builtin_actors(&rt.store).unwrap();
         assert!(builtin_actors.is_empty());
     }
+
+    #[test]
+    fn get_builtin_actors() {
+        let rt = new_runtime();
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        rt.call::<Actor>(Method::Constructor as MethodNum, None).unwrap();
+
+        // As at genesis or a migration, the node installs the bundle's manifest data.
+        let entries = vec![("system".to_string(), *SYSTEM_ACTOR_CODE_ID)];
+        let builtin_actors =
+            rt.store.put_cbor(&entries, multihash_codetable::Code::Blake2b256).unwrap();
+        rt.replace_state(&State { builtin_actors });
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(1000));
+        rt.expect_validate_caller_any();
+        let ret: GetBuiltinActorsReturn = rt
+            .call::<Actor>(Method::GetBuiltinActorsExported as MethodNum, None)
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        assert_eq!(builtin_actors, ret.builtin_actors);
+        assert_eq!(entries, rt.get_state::<State>().get_builtin_actors(&rt.store).unwrap());
+    }
 }
@@ -28,6 +28,40 @@ pub fn verifier_balance(
     rt.emit_event(&event.build()?)
 }
 
+/// Indicates a verifier has granted datacap to a client.
+/// The amount is in datacap whole units (not TokenAmount).
+pub fn datacap_granted(
+    rt: &impl Runtime,
+    verifier: ActorID,
+    client: ActorID,
+    amount: &DataCap,
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("datacap-granted")
+            .field_indexed("verifier", &verifier)
+            .field_indexed("client", &client)
+            .field("amount", &BigIntSer(amount))
+            .build()?,
+    )
+}
+
+/// Indicates datacap has been removed from a client at the request of two verifiers.
+/// The amount is in datacap whole units (not TokenAmount).
+pub fn datacap_removed(
+    rt: &impl Runtime,
+    client: ActorID,
+    amount: &DataCap,
+) -> Result<(), ActorError> {
+    rt.emit_event(
+        &EventBuilder::new()
+            .typ("datacap-removed")
+            .field_indexed("client", &client)
+            .field("amount", &BigIntSer(amount))
+            .build()?,
+    )
+}
+
 /// Indicates a new allocation has been made.
 pub fn allocation(
     rt: &impl Runtime,
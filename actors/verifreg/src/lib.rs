@@ -1,10 +1,6 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
-use frc46_token::receiver::{FRC46TokenReceived, FRC46_TOKEN_TYPE};
-use frc46_token::token::types::{BurnParams, TransferParams};
-use frc46_token::token::TOKEN_PRECISION;
-use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
@@ -20,11 +16,14 @@ use num_derive::FromPrimitive;
 use num_traits::{Signed, Zero};
 
 use fil_actors_runtime::cbor::deserialize;
+use fil_actors_runtime::frc46::{
+    validate_tokens_received, BurnParams, TransferParams, UniversalReceiverParams, TOKEN_PRECISION,
+};
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Policy, Runtime};
 use fil_actors_runtime::{
     actor_dispatch, actor_error, deserialize_block, extract_send_result, resolve_to_actor_id,
-    ActorError, BatchReturn, DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
+    ActorError, BatchReturn, ValidateParams, DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
     SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fil_actors_runtime::{ActorContext, AsActorError, BatchReturnGen};
@@ -47,6 +46,7 @@ mod emit;
 pub mod expiration;
 pub mod ext;
 pub mod state;
+#[cfg(feature = "testing")]
 pub mod testing;
 pub mod types;
 
@@ -73,6 +73,8 @@ pub enum Method {
     ExtendClaimTermsExported = frc42_dispatch::method_hash!("ExtendClaimTerms"),
     RemoveExpiredClaimsExported = frc42_dispatch::method_hash!("RemoveExpiredClaims"),
     UniversalReceiverHook = frc42_dispatch::method_hash!("Receive"),
+    ListVerifiersExported = frc42_dispatch::method_hash!("ListVerifiers"),
+    GetAllocationsExported = frc42_dispatch::method_hash!("GetAllocations"),
 }
 
 pub struct Actor;
@@ -218,7 +220,8 @@ impl Actor {
             "failed to mint {} data cap to client {}",
             &params.allowance, client
         ))?;
-        Ok(())
+
+        emit::datacap_granted(rt, rt.message().caller().id().unwrap(), client_id, &params.allowance)
     }
 
     /// Removes DataCap allocated to a verified client.
@@ -300,6 +303,7 @@ impl Actor {
         let burnt = std::cmp::min(balance, params.data_cap_amount_to_remove);
         destroy(rt, &client, &burnt)
             .context(format!("failed to destroy {} from allowance for {}", &burnt, &client))?;
+        emit::datacap_removed(rt, client.id().unwrap(), &burnt)?;
 
         Ok(RemoveDataCapReturn {
             verified_client: client, // Changed to the resolved address
@@ -515,6 +519,56 @@ impl Actor {
         Ok(GetClaimsReturn { batch_info: batch_gen.gen(), claims })
     }
 
+    // get allocations for a client
+    pub fn get_allocations(
+        rt: &impl Runtime,
+        params: GetAllocationsParams,
+    ) -> Result<GetAllocationsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let mut batch_gen = BatchReturnGen::new(params.allocation_ids.len());
+        let st: State = rt.state()?;
+        let mut st_allocs = st.load_allocs(rt.store())?;
+        let mut allocations = Vec::new();
+        for id in params.allocation_ids {
+            let maybe_alloc = state::get_allocation(&mut st_allocs, params.client, id)?;
+            match maybe_alloc {
+                None => {
+                    batch_gen.add_fail(ExitCode::USR_NOT_FOUND);
+                    info!("no allocation {} for client {}", id, params.client,);
+                }
+                Some(alloc) => {
+                    batch_gen.add_success();
+                    allocations.push(alloc.clone());
+                }
+            };
+        }
+
+        Ok(GetAllocationsReturn { batch_info: batch_gen.gen(), allocations })
+    }
+
+    /// Returns a page of at most `limit` verifiers and their remaining allowances.
+    /// The order is deterministic for a given state, and a non-empty `next_cursor` in the
+    /// return value may be passed to a subsequent call to continue listing.
+    pub fn list_verifiers(
+        rt: &impl Runtime,
+        params: ListVerifiersParams,
+    ) -> Result<ListVerifiersReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        params.validate()?;
+        let st: State = rt.state()?;
+        let verifiers = st.load_verifiers(rt.store())?;
+        let mut page = Vec::new();
+        let (_, next_cursor) = verifiers.for_each_ranged(
+            params.cursor.as_ref(),
+            Some(params.limit as usize),
+            |address, allowance| {
+                page.push(VerifierAllowance { address, allowance: allowance.0.clone() });
+                Ok(())
+            },
+        )?;
+        Ok(ListVerifiersReturn { verifiers: page, next_cursor })
+    }
+
     /// Extends the maximum term of some claims up to the largest value they could have been
     /// originally allocated.
     /// Callable only by the claims' client.
@@ -911,32 +965,6 @@ fn remove_data_cap_request_is_valid(
     }
 }
 
-// Deserializes and validates a receiver hook payload, expecting only an FRC-46 transfer.
-fn validate_tokens_received(
-    params: &UniversalReceiverParams,
-    my_id: u64,
-) -> Result<FRC46TokenReceived, ActorError> {
-    if params.type_ != FRC46_TOKEN_TYPE {
-        return Err(actor_error!(
-            illegal_argument,
-            "invalid token type {}, expected {} (FRC-46)",
-            params.type_,
-            FRC46_TOKEN_TYPE
-        ));
-    }
-    let payload: FRC46TokenReceived = deserialize(&params.payload, "receiver hook payload")?;
-    // Payload to address must match receiving actor.
-    if payload.to != my_id {
-        return Err(actor_error!(
-            illegal_argument,
-            "token receiver expected to {}, was {}",
-            my_id,
-            payload.to
-        ));
-    }
-    Ok(payload)
-}
-
 // Validates an allocation request.
 fn validate_new_allocation(
     req: &AllocationRequest,
@@ -1107,8 +1135,10 @@ impl ActorCode for Actor {
         RemoveExpiredAllocations|RemoveExpiredAllocationsExported => remove_expired_allocations,
         ClaimAllocations => claim_allocations,
         GetClaims|GetClaimsExported => get_claims,
+        GetAllocationsExported => get_allocations,
         ExtendClaimTerms|ExtendClaimTermsExported => extend_claim_terms,
         RemoveExpiredClaims|RemoveExpiredClaimsExported => remove_expired_claims,
         UniversalReceiverHook => universal_receiver_hook,
+        ListVerifiersExported => list_verifiers,
     }
 }
@@ -13,7 +13,7 @@ use fvm_shared::sector::SectorNumber;
 use fvm_shared::{ActorID, HAMT_BIT_WIDTH};
 
 use fil_actors_runtime::{
-    actor_error, ActorError, AsActorError, Config, Map2, MapMap, DEFAULT_HAMT_CONFIG,
+    actor_error, take_ids, ActorError, AsActorError, Config, Map2, MapMap, DEFAULT_HAMT_CONFIG,
 };
 
 use crate::{AddrPairKey, AllocationID, ClaimID};
@@ -135,23 +135,12 @@ impl State {
             return Ok(vec![]);
         }
         let mut allocs = self.load_allocs(store)?;
-        // These local variables allow the id-associating map closure to move the allocations
-        // from the iterator rather than clone, without moving self.
-        let first_id = self.next_allocation_id;
-        let mut count = 0;
-        let count_ref = &mut count;
+        let count = new_allocs.len() as u64;
+        let first_id = take_ids(&mut self.next_allocation_id, count, "allocation")?;
         allocs
-            .put_many(
-                client,
-                new_allocs.into_iter().map(move |a| {
-                    let id = first_id + *count_ref;
-                    *count_ref += 1;
-                    (id, a)
-                }),
-            )
+            .put_many(client, (first_id..).zip(new_allocs))
             .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to put allocations")?;
         self.save_allocs(&mut allocs)?;
-        self.next_allocation_id += count;
         let allocated_ids = (first_id..first_id + count).collect();
         Ok(allocated_ids)
     }
@@ -11,7 +11,9 @@ use fil_actors_runtime::runtime::policy_constants::{
     MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION, MAXIMUM_VERIFIED_ALLOCATION_TERM,
     MINIMUM_VERIFIED_ALLOCATION_SIZE, MINIMUM_VERIFIED_ALLOCATION_TERM,
 };
-use fil_actors_runtime::{Map2, MessageAccumulator, DEFAULT_HAMT_CONFIG};
+use fil_actors_runtime::{
+    Map2, MessageAccumulator, DEFAULT_HAMT_CONFIG, ID_OVERFLOW_RISK_THRESHOLD,
+};
 
 use crate::{Allocation, AllocationID, Claim, ClaimID, DataCap, State};
 
@@ -28,6 +30,13 @@ pub fn check_state_invariants<BS: Blockstore>(
     prior_epoch: ChainEpoch,
 ) -> (StateSummary, MessageAccumulator) {
     let acc = MessageAccumulator::default();
+    acc.require(
+        state.next_allocation_id <= ID_OVERFLOW_RISK_THRESHOLD,
+        format!(
+            "next allocation id {} exceeds overflow risk threshold {}",
+            state.next_allocation_id, ID_OVERFLOW_RISK_THRESHOLD
+        ),
+    );
 
     // Load and check verifiers
     let mut all_verifiers = HashMap::new();
@@ -2,19 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use cid::Cid;
-use fil_actors_runtime::{BatchReturn, MapKey};
+use fil_actors_runtime::{validate_params, BatchReturn, MapKey};
+pub use fil_actors_runtime::{AllocationClaim, ProviderClaims, SectorAllocationClaims};
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
 use fvm_shared::bigint::{bigint_ser, BigInt};
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::crypto::signature::Signature;
 use fvm_shared::piece::PaddedPieceSize;
-use fvm_shared::sector::SectorNumber;
 use fvm_shared::sector::StoragePower;
 use fvm_shared::ActorID;
 use std::fmt::{Debug, Formatter};
 
-use crate::Claim;
+use crate::{Allocation, Claim};
 
 pub type AllocationID = u64;
 pub type ClaimID = u64;
@@ -133,21 +133,6 @@ pub struct RemoveExpiredAllocationsReturn {
     pub datacap_recovered: DataCap,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct SectorAllocationClaims {
-    pub sector: SectorNumber,
-    pub expiry: ChainEpoch,
-    pub claims: Vec<AllocationClaim>,
-}
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct AllocationClaim {
-    pub client: ActorID,
-    pub allocation_id: AllocationID,
-    pub data: Cid,
-    pub size: PaddedPieceSize,
-}
-
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct ClaimAllocationsParams {
     /// Allocations to claim, grouped by sector.
@@ -230,11 +215,7 @@ pub struct AllocationsResponse {
     pub new_allocations: Vec<AllocationID>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct GetClaimsParams {
-    pub provider: ActorID,
-    pub claim_ids: Vec<ClaimID>,
-}
+pub type GetClaimsParams = ProviderClaims;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct GetClaimsReturn {
@@ -243,14 +224,46 @@ pub struct GetClaimsReturn {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct RemoveExpiredClaimsParams {
-    // Provider to clean up (need not be the caller)
-    pub provider: ActorID,
-    // Optional list of claim IDs to attempt to remove.
-    // Empty means remove all eligible expired claims.
-    pub claim_ids: Vec<ClaimID>,
+pub struct GetAllocationsParams {
+    pub client: ActorID,
+    pub allocation_ids: Vec<AllocationID>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetAllocationsReturn {
+    pub batch_info: BatchReturn,
+    pub allocations: Vec<Allocation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListVerifiersParams {
+    // Verifier from which to resume listing, as returned by a previous call.
+    // None starts from the beginning.
+    pub cursor: Option<Address>,
+    // Maximum number of verifiers to return.
+    pub limit: u64,
+}
+
+validate_params!(ListVerifiersParams { limit: range(1, u64::MAX) });
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct VerifierAllowance {
+    pub address: Address,
+    #[serde(with = "bigint_ser")]
+    pub allowance: DataCap,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListVerifiersReturn {
+    pub verifiers: Vec<VerifierAllowance>,
+    // Cursor to pass to a subsequent call to continue listing, if more verifiers remain.
+    pub next_cursor: Option<Address>,
+}
+
+// The provider to clean up (need not be the caller), and an optional list of claim IDs
+// to attempt to remove. Empty means remove all eligible expired claims.
+pub type RemoveExpiredClaimsParams = ProviderClaims;
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct RemoveExpiredClaimsReturn {
     // Ids of the claims that were either specified by the caller or discovered to be expired.
@@ -3,10 +3,6 @@ use std::collections::HashMap;
 
 use cid::Cid;
 
-use frc46_token::receiver::{FRC46TokenReceived, FRC46_TOKEN_TYPE};
-use frc46_token::token::types::{BurnParams, BurnReturn, TransferParams};
-use frc46_token::token::TOKEN_PRECISION;
-use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
@@ -26,11 +22,16 @@ use fil_actor_verifreg::{
     ext, Actor as VerifregActor, AddVerifiedClientParams, AddVerifierParams, Allocation,
     AllocationClaim, AllocationID, AllocationRequest, AllocationRequests, AllocationsResponse,
     Claim, ClaimAllocationsParams, ClaimAllocationsReturn, ClaimExtensionRequest, ClaimID, DataCap,
-    ExtendClaimTermsParams, ExtendClaimTermsReturn, GetClaimsParams, GetClaimsReturn, Method,
+    ExtendClaimTermsParams, ExtendClaimTermsReturn, GetAllocationsParams, GetAllocationsReturn,
+    GetClaimsParams, GetClaimsReturn, ListVerifiersParams, ListVerifiersReturn, Method,
     RemoveExpiredAllocationsParams, RemoveExpiredAllocationsReturn, RemoveExpiredClaimsParams,
     RemoveExpiredClaimsReturn, SectorAllocationClaims, State,
 };
 use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::frc46::{
+    BurnParams, BurnReturn, FRC46TokenReceived, TransferParams, UniversalReceiverParams,
+    FRC46_TOKEN_TYPE, TOKEN_PRECISION,
+};
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::policy_constants::{
     MAXIMUM_VERIFIED_ALLOCATION_TERM, MINIMUM_VERIFIED_ALLOCATION_TERM,
@@ -243,6 +244,14 @@ impl Harness {
                     .field_indexed("client", &client_resolved.id().unwrap())
                     .build()?,
             );
+            rt.expect_emitted_event(
+                EventBuilder::new()
+                    .typ("datacap-granted")
+                    .field_indexed("verifier", &verifier.id().unwrap())
+                    .field_indexed("client", &client_resolved.id().unwrap())
+                    .field("amount", &BigIntSer(allowance))
+                    .build()?,
+            );
         }
         let ret = rt.call::<VerifregActor>(
             Method::AddVerifiedClient as MethodNum,
@@ -556,6 +565,46 @@ impl Harness {
         Ok(ret)
     }
 
+    pub fn get_allocations(
+        &self,
+        rt: &MockRuntime,
+        client: ActorID,
+        allocation_ids: Vec<AllocationID>,
+    ) -> Result<GetAllocationsReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = GetAllocationsParams { client, allocation_ids };
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::GetAllocationsExported as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize get allocations return");
+        rt.verify();
+        Ok(ret)
+    }
+
+    pub fn list_verifiers(
+        &self,
+        rt: &MockRuntime,
+        cursor: Option<Address>,
+        limit: u64,
+    ) -> Result<ListVerifiersReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = ListVerifiersParams { cursor, limit };
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::ListVerifiersExported as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize list verifiers return");
+        rt.verify();
+        Ok(ret)
+    }
+
     pub fn extend_claim_terms(
         &self,
         rt: &MockRuntime,
@@ -237,6 +237,39 @@ mod verifiers {
         h.remove_verifier(&rt, &VERIFIER).unwrap();
         h.check_state(&rt);
     }
+
+    #[test]
+    fn list_verifiers_paginates() {
+        let (h, rt) = new_harness();
+        let allowance = verifier_allowance(&rt);
+        let verifiers: Vec<Address> = (0..5).map(|i| Address::new_id(1000 + i)).collect();
+        for (i, v) in verifiers.iter().enumerate() {
+            rt.set_address_actor_type(*v, *ACCOUNT_ACTOR_CODE_ID);
+            h.add_verifier(&rt, v, &(allowance.clone() + i)).unwrap();
+        }
+
+        let mut listed = vec![];
+        let mut cursor = None;
+        loop {
+            let ret = h.list_verifiers(&rt, cursor, 2).unwrap();
+            assert!(ret.verifiers.len() <= 2);
+            listed.extend(ret.verifiers);
+            cursor = ret.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(verifiers.len(), listed.len());
+        for entry in listed {
+            assert_eq!(h.get_verifier_allowance(&rt, &entry.address), entry.allowance);
+        }
+
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.list_verifiers(&rt, None, 0));
+        rt.reset();
+        expect_abort(ExitCode::USR_NOT_FOUND, h.list_verifiers(&rt, Some(Address::new_id(999)), 2));
+        rt.reset();
+        h.check_state(&rt);
+    }
 }
 
 mod clients {
@@ -448,11 +481,20 @@ mod clients {
             EventBuilder::new()
                 .typ("verifier-balance")
                 .field_indexed("verifier", &VERIFIER.id().unwrap())
-                .field("balance", &BigIntSer(&(allowance_verifier - allowance_client)))
+                .field("balance", &BigIntSer(&(allowance_verifier - &allowance_client)))
                 .field_indexed("client", &CLIENT.id().unwrap())
                 .build()
                 .unwrap(),
         );
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("datacap-granted")
+                .field_indexed("verifier", &VERIFIER.id().unwrap())
+                .field_indexed("client", &CLIENT.id().unwrap())
+                .field("amount", &BigIntSer(&allowance_client))
+                .build()
+                .unwrap(),
+        );
 
         rt.expect_validate_caller_any();
         rt.call::<VerifregActor>(
@@ -908,6 +950,30 @@ mod allocs_claims {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn get_allocations() {
+        let (h, rt) = new_harness();
+        let alloc1 = make_alloc("1", CLIENT1, PROVIDER1, ALLOC_SIZE);
+        let alloc2 = make_alloc("2", CLIENT1, PROVIDER2, ALLOC_SIZE);
+        let alloc3 = make_alloc("3", CLIENT2, PROVIDER1, ALLOC_SIZE);
+        let id1 = h.create_alloc(&rt, &alloc1).unwrap();
+        let id2 = h.create_alloc(&rt, &alloc2).unwrap();
+        let id3 = h.create_alloc(&rt, &alloc3).unwrap();
+
+        // Allocations of another client, and unknown IDs, are not found.
+        let ret = h.get_allocations(&rt, CLIENT1, vec![id2, id3, id1, 100]).unwrap();
+        assert_eq!(2, ret.batch_info.success_count);
+        assert_eq!(vec![alloc2, alloc1], ret.allocations);
+        assert_eq!(
+            vec![
+                FailCode { idx: 1, code: ExitCode::USR_NOT_FOUND },
+                FailCode { idx: 3, code: ExitCode::USR_NOT_FOUND }
+            ],
+            ret.batch_info.fail_codes
+        );
+        h.check_state(&rt);
+    }
+
     #[test]
     fn extend_claims_basic() {
         let (h, rt) = new_harness();
@@ -1179,8 +1245,7 @@ mod allocs_claims {
 }
 
 mod datacap {
-    use frc46_token::receiver::FRC46_TOKEN_TYPE;
-    use fvm_actor_utils::receiver::UniversalReceiverParams;
+    use fil_actors_runtime::frc46::{UniversalReceiverParams, FRC46_TOKEN_TYPE};
     use fvm_ipld_encoding::ipld_block::IpldBlock;
     use fvm_shared::address::Address;
     use fvm_shared::econ::TokenAmount;
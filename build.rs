@@ -2,8 +2,9 @@ use fil_actor_bundler::Bundler;
 use fil_actors_runtime::runtime::builtins::Type;
 use num_traits::cast::FromPrimitive;
 use std::error::Error;
+use std::ffi::OsStr;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::thread;
 
@@ -34,6 +35,27 @@ const ACTORS: &[(&Package, &ID)] = &[
 
 const NETWORK_ENV: &str = "BUILD_FIL_NETWORK";
 
+/// Maximum size in bytes of any actor's bytecode, above which the build fails.
+const MAX_ACTOR_SIZE_ENV: &str = "BUILD_FIL_MAX_ACTOR_SIZE";
+
+/// Set to strip custom sections (names, debug info, etc.) from the actors' bytecode.
+/// Stripping changes the actors' code CIDs.
+const STRIP_CUSTOM_SECTIONS_ENV: &str = "BUILD_FIL_STRIP_CUSTOM_SECTIONS";
+
+/// Additional networks to build bundles for, as a comma-separated list or "all".
+const EXTRA_NETWORKS_ENV: &str = "BUILD_FIL_NETWORKS";
+
+/// Networks which may be built, matching those known to the runtime.
+const NETWORKS: &[&str] = &[
+    "mainnet",
+    "caterpillarnet",
+    "butterflynet",
+    "calibrationnet",
+    "devnet",
+    "testing",
+    "testing-fake-proofs",
+];
+
 /// Returns the configured network name, checking both the environment and feature flags.
 fn network_name() -> String {
     let env_network = std::env::var_os(NETWORK_ENV);
@@ -69,33 +91,47 @@ fn network_name() -> String {
     }.to_owned()
 }
 
+/// Returns the networks to build a bundle for: the configured network, followed by any others
+/// listed in the environment.
+fn network_names() -> Vec<String> {
+    let mut networks = vec![network_name()];
+    let extra = std::env::var(EXTRA_NETWORKS_ENV).unwrap_or_default();
+    let extra: Vec<&str> = match extra.trim() {
+        "all" => NETWORKS.to_vec(),
+        list => list.split(',').map(str::trim).filter(|n| !n.is_empty()).collect(),
+    };
+    for network in extra {
+        assert!(
+            NETWORKS.contains(&network),
+            "unknown network {} in {}",
+            network,
+            EXTRA_NETWORKS_ENV
+        );
+        if !networks.iter().any(|n| n == network) {
+            networks.push(network.to_owned());
+        }
+    }
+    networks
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Cargo executable location.
     let cargo = std::env::var_os("CARGO").expect("no CARGO env var");
     println!("cargo:warning=cargo: {:?}", &cargo);
 
-    let out_dir = std::env::var_os("OUT_DIR")
-        .as_ref()
-        .map(Path::new)
-        .map(|p| p.join("bundle"))
-        .expect("no OUT_DIR env var");
+    let out_dir = std::env::var_os("OUT_DIR").map(PathBuf::from).expect("no OUT_DIR env var");
     println!("cargo:warning=out_dir: {:?}", &out_dir);
 
-    // Compute the package names.
-    let packages =
-        ACTORS.iter().map(|(pkg, _)| String::from("fil_actor_") + pkg).collect::<Vec<String>>();
-
     let manifest_path =
         Path::new(&std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR unset"))
             .join("Cargo.toml");
     println!("cargo:warning=manifest_path={:?}", &manifest_path);
 
-    // Determine the network name.
-    let network_name = network_name();
-    println!("cargo:warning=network name: {}", network_name);
-
-    // Make sure we re-build if the network name changes.
+    // Make sure we re-build if the network names change.
     println!("cargo:rerun-if-env-changed={}", NETWORK_ENV);
+    println!("cargo:rerun-if-env-changed={}", EXTRA_NETWORKS_ENV);
+    println!("cargo:rerun-if-env-changed={}", MAX_ACTOR_SIZE_ENV);
+    println!("cargo:rerun-if-env-changed={}", STRIP_CUSTOM_SECTIONS_ENV);
 
     // Rerun if the source, dependencies, build options, build script _or_ actors have changed. We
     // need to check if the actors have changed because otherwise, when building in a workspace, we
@@ -107,8 +143,48 @@ fn main() -> Result<(), Box<dyn Error>> {
         println!("cargo:rerun-if-changed={}", file);
     }
 
+    // The configured network's bundle is built in `bundle`, and each other network's in a
+    // directory of its own, so that switching networks doesn't rebuild every actor.
+    let mut bundles = vec![];
+    for (i, network_name) in network_names().into_iter().enumerate() {
+        println!("cargo:warning=network name: {}", network_name);
+        let dir = if i == 0 {
+            out_dir.join("bundle")
+        } else {
+            out_dir.join("networks").join(&network_name)
+        };
+        let car = build_bundle(&cargo, &manifest_path, &network_name, &dir)?;
+        bundles.push((network_name, car));
+    }
+
+    // Index the bundles for inclusion in the crate.
+    let mut index = String::from(
+        "/// The bundled CARs for each network built, by network name.\n\
+         /// The first is the configured network's, also embedded as `BUNDLE_CAR`.\n\
+         pub const NETWORK_BUNDLES: &[(&str, &[u8])] = &[\n",
+    );
+    for (network_name, car) in &bundles {
+        index += &format!("    ({:?}, include_bytes!({:?})),\n", network_name, car);
+    }
+    index += "];\n";
+    std::fs::write(out_dir.join("bundles.rs"), index)?;
+
+    Ok(())
+}
+
+/// Builds the actors for a network, and bundles them into a CAR in `out_dir`.
+fn build_bundle(
+    cargo: &OsStr,
+    manifest_path: &Path,
+    network_name: &str,
+    out_dir: &Path,
+) -> Result<PathBuf, Box<dyn Error>> {
+    // Compute the package names.
+    let packages =
+        ACTORS.iter().map(|(pkg, _)| String::from("fil_actor_") + pkg).collect::<Vec<String>>();
+
     // Cargo build command for all actors at once.
-    let mut cmd = Command::new(&cargo);
+    let mut cmd = Command::new(cargo);
     cmd.arg("build")
         .args(packages.iter().map(|pkg| "-p=".to_owned() + pkg))
         .arg("--target=wasm32-unknown-unknown")
@@ -121,7 +197,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         .stderr(Stdio::piped())
         // We are supposed to only generate artifacts under OUT_DIR,
         // so set OUT_DIR as the target directory for this build.
-        .env("CARGO_TARGET_DIR", &out_dir)
+        .env("CARGO_TARGET_DIR", out_dir)
         // As we are being called inside a build-script, this env variable is set. However, we set
         // our own `RUSTFLAGS` and thus, we need to remove this. Otherwise cargo favors this
         // env variable.
@@ -156,17 +232,53 @@ fn main() -> Result<(), Box<dyn Error>> {
         return Err("actor build failed".into());
     }
 
-    let dst = Path::new(&out_dir).join("bundle.car");
+    let max_size = std::env::var(MAX_ACTOR_SIZE_ENV).ok().map(|s| {
+        s.trim()
+            .parse::<usize>()
+            .unwrap_or_else(|_| panic!("invalid {}: {}", MAX_ACTOR_SIZE_ENV, s))
+    });
+    let strip = std::env::var(STRIP_CUSTOM_SECTIONS_ENV)
+        .map(|s| !matches!(s.trim(), "" | "0" | "false"))
+        .unwrap_or(false);
+    let stripped_dir = out_dir.join("stripped");
+    if strip {
+        std::fs::create_dir_all(&stripped_dir)?;
+    }
+
+    let dst = out_dir.join("bundle.car");
     let mut bundler = Bundler::new(&dst);
+    let mut report = String::new();
+    let mut oversized = vec![];
     for (&(pkg, name), id) in ACTORS.iter().zip(1u32..) {
         assert_eq!(
             name,
             Type::from_u32(id).expect("type not defined").name(),
             "actor types don't match actors included in the bundle"
         );
-        let bytecode_path = Path::new(&out_dir)
-            .join("wasm32-unknown-unknown/wasm")
-            .join(format!("fil_actor_{}.wasm", pkg));
+        let mut bytecode_path =
+            out_dir.join("wasm32-unknown-unknown/wasm").join(format!("fil_actor_{}.wasm", pkg));
+        let mut bytecode = std::fs::read(&bytecode_path)?;
+        if strip {
+            bytecode = strip_custom_sections(&bytecode)
+                .unwrap_or_else(|err| panic!("failed to strip {:?}: {}", bytecode_path, err));
+            bytecode_path = stripped_dir.join(format!("fil_actor_{}.wasm", pkg));
+            std::fs::write(&bytecode_path, &bytecode)?;
+        }
+
+        let sections = wasm_sections(&bytecode)
+            .unwrap_or_else(|err| panic!("failed to parse {:?}: {}", bytecode_path, err));
+        let breakdown = sections
+            .iter()
+            .map(|(section, size)| format!("{} {}", section, size))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let line = format!("{}: {} bytes ({})", name, bytecode.len(), breakdown);
+        println!("cargo:warning=size of {}", line);
+        report += &line;
+        report.push('\n');
+        if max_size.map_or(false, |max| bytecode.len() > max) {
+            oversized.push(format!("{} ({} bytes)", name, bytecode.len()));
+        }
 
         // This actor version doesn't force synthetic CIDs; it uses genuine
         // content-addressed CIDs.
@@ -181,7 +293,106 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
     bundler.finish().expect("failed to finish bundle");
 
+    std::fs::write(out_dir.join("sizes.txt"), report)?;
+    if !oversized.is_empty() {
+        return Err(format!(
+            "actors exceed the {} of {} bytes: {}",
+            MAX_ACTOR_SIZE_ENV,
+            max_size.unwrap(),
+            oversized.join(", ")
+        )
+        .into());
+    }
+
     println!("cargo:warning=bundle={}", dst.display());
 
-    Ok(())
+    Ok(dst)
+}
+
+/// Standard section names, by section ID.
+const SECTION_NAMES: &[&str] = &[
+    "custom",
+    "type",
+    "import",
+    "function",
+    "table",
+    "memory",
+    "global",
+    "export",
+    "start",
+    "element",
+    "code",
+    "data",
+    "datacount",
+    "tag",
+];
+
+/// A section of a Wasm module: its ID, name, start offset and end offset.
+type Section = (u8, String, usize, usize);
+
+/// Reads an unsigned LEB128 integer at `*pos`, advancing past it.
+fn read_leb128(bytes: &[u8], pos: &mut usize) -> Result<usize, Box<dyn Error>> {
+    let mut result = 0usize;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or("unexpected end of module")?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+    Err("integer too long".into())
+}
+
+/// Splits a Wasm module into its sections, after the 8-byte preamble.
+fn parse_sections(bytes: &[u8]) -> Result<Vec<Section>, Box<dyn Error>> {
+    if bytes.len() < 8 || &bytes[..4] != b"\0asm" {
+        return Err("not a wasm module".into());
+    }
+    let mut sections = vec![];
+    let mut pos = 8;
+    while pos < bytes.len() {
+        let start = pos;
+        let id = bytes[pos];
+        pos += 1;
+        let size = read_leb128(bytes, &mut pos)?;
+        let end =
+            pos.checked_add(size).filter(|&end| end <= bytes.len()).ok_or("truncated section")?;
+        let name = if id == 0 {
+            let len = read_leb128(bytes, &mut pos)?;
+            let name = bytes.get(pos..pos + len).ok_or("truncated section name")?;
+            format!("custom:{}", String::from_utf8_lossy(name))
+        } else {
+            SECTION_NAMES
+                .get(id as usize)
+                .map_or_else(|| format!("unknown:{}", id), |n| n.to_string())
+        };
+        sections.push((id, name, start, end));
+        pos = end;
+    }
+    Ok(sections)
+}
+
+/// Returns the total size in bytes of each kind of section in a Wasm module, largest first.
+fn wasm_sections(bytes: &[u8]) -> Result<Vec<(String, usize)>, Box<dyn Error>> {
+    let mut totals: Vec<(String, usize)> = vec![];
+    for (_, name, start, end) in parse_sections(bytes)? {
+        match totals.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, total)) => *total += end - start,
+            None => totals.push((name, end - start)),
+        }
+    }
+    totals.sort_by(|a, b| b.1.cmp(&a.1));
+    Ok(totals)
+}
+
+/// Returns a Wasm module without its custom sections, which don't affect execution.
+fn strip_custom_sections(bytes: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut stripped = bytes[..8].to_vec();
+    for (id, _, start, end) in parse_sections(bytes)? {
+        if id != 0 {
+            stripped.extend_from_slice(&bytes[start..end]);
+        }
+    }
+    Ok(stripped)
 }
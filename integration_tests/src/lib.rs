@@ -14,6 +14,7 @@ pub mod deals;
 pub mod expects;
 pub mod tests;
 pub mod util;
+pub mod workload;
 
 // accounts for verifreg root signer and msig
 pub const VERIFREG_ROOT_KEY: &[u8] = &[200; fvm_shared::address::BLS_PUB_LEN];
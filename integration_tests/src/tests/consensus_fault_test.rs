@@ -0,0 +1,116 @@
+use export_macro::vm_test;
+use fil_actor_miner::{
+    consensus_fault_penalty, reward_for_consensus_slash_report, Method as MinerMethod,
+};
+use fil_actors_runtime::runtime::Policy;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sector::RegisteredSealProof;
+use num_traits::Zero;
+use vm_api::util::apply_code;
+use vm_api::VM;
+
+use crate::util::{
+    create_accounts, create_miner, double_fork_evidence, expect_invariants, get_network_stats,
+    invariant_failure_patterns, miner_info, report_consensus_fault, time_offset_evidence,
+    verify_test_consensus_fault, TestBlockHeader,
+};
+
+#[vm_test]
+pub fn report_consensus_fault_test(v: &dyn VM) {
+    let addrs = create_accounts(v, 2, &TokenAmount::from_whole(10_000));
+    let (owner, reporter) = (addrs[0], addrs[1]);
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (maddr, _) = create_miner(
+        v,
+        &owner,
+        &owner,
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+    v.set_epoch(200);
+
+    let epoch_reward =
+        TokenAmount::from_atto(get_network_stats(v).this_epoch_reward_smoothed.estimate());
+    let reporter_balance = v.balance(&reporter);
+    let miner_balance = v.balance(&maddr);
+    report_consensus_fault(v, &reporter, &maddr, double_fork_evidence(v, &maddr, 100));
+
+    // The penalty is burnt from the miner's balance, less the reporter's reward.
+    assert_eq!(
+        reporter_balance + reward_for_consensus_slash_report(&epoch_reward),
+        v.balance(&reporter)
+    );
+    assert_eq!(
+        miner_balance - consensus_fault_penalty(&Policy::default(), epoch_reward),
+        v.balance(&maddr)
+    );
+    assert_eq!(
+        v.epoch() + Policy::default().consensus_fault_ineligibility_duration,
+        miner_info(v, &maddr).consensus_fault_elapsed
+    );
+
+    // Another fault from before the end of the ineligibility period can't be reported.
+    apply_code(
+        v,
+        &reporter,
+        &maddr,
+        &TokenAmount::zero(),
+        MinerMethod::ReportConsensusFault as u64,
+        Some(time_offset_evidence(v, &maddr, 150)),
+        ExitCode::USR_FORBIDDEN,
+    );
+
+    expect_invariants(
+        v,
+        &Policy::default(),
+        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
+        None,
+    );
+}
+
+#[vm_test]
+pub fn report_invalid_consensus_fault_test(v: &dyn VM) {
+    let addrs = create_accounts(v, 3, &TokenAmount::from_whole(10_000));
+    let (owner, other_owner, reporter) = (addrs[0], addrs[1], addrs[2]);
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let post_proof = seal_proof.registered_window_post_proof().unwrap();
+    let (maddr, _) = create_miner(v, &owner, &owner, post_proof, &TokenAmount::from_whole(1_000));
+    let (other_maddr, _) =
+        create_miner(v, &other_owner, &other_owner, post_proof, &TokenAmount::from_whole(1_000));
+    v.set_epoch(200);
+    v.mut_primitives().override_verify_consensus_fault(verify_test_consensus_fault);
+
+    let report_code = |params, code| {
+        apply_code(
+            v,
+            &reporter,
+            &maddr,
+            &TokenAmount::zero(),
+            MinerMethod::ReportConsensusFault as u64,
+            Some(params),
+            code,
+        );
+    };
+
+    // A fault by another miner.
+    report_code(time_offset_evidence(v, &other_maddr, 100), ExitCode::USR_ILLEGAL_ARGUMENT);
+
+    // A header not signed by the worker.
+    let mut params = time_offset_evidence(v, &maddr, 100);
+    let mut header: TestBlockHeader = fvm_ipld_encoding::from_slice(&params.header2).unwrap();
+    header.timestamp += 1;
+    params.header2 = fvm_ipld_encoding::to_vec(&header).unwrap();
+    report_code(params, ExitCode::USR_ILLEGAL_ARGUMENT);
+
+    // The same header twice.
+    let mut params = time_offset_evidence(v, &maddr, 100);
+    params.header2 = params.header1.clone();
+    report_code(params, ExitCode::USR_ILLEGAL_ARGUMENT);
+
+    // A fault in the future.
+    report_code(double_fork_evidence(v, &maddr, 200), ExitCode::USR_ILLEGAL_ARGUMENT);
+
+    assert_eq!(-1, miner_info(v, &maddr).consensus_fault_elapsed);
+    report_consensus_fault(v, &reporter, &maddr, time_offset_evidence(v, &maddr, 100));
+}
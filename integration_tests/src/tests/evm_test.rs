@@ -3,23 +3,17 @@ use alloy_core::{primitives::Address as EthAddress, sol};
 
 use export_macro::vm_test;
 use fil_actors_evm_shared::uints::U256;
-use fil_actors_runtime::{
-    test_utils::ETHACCOUNT_ACTOR_CODE_ID, test_utils::EVM_ACTOR_CODE_ID, EAM_ACTOR_ADDR,
-    EAM_ACTOR_ID,
-};
+use fil_actors_runtime::{test_utils::EVM_ACTOR_CODE_ID, EAM_ACTOR_ADDR, EAM_ACTOR_ID};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
-use fvm_ipld_encoding::RawBytes;
 use fvm_ipld_encoding::{strict_bytes, BytesDe};
 use fvm_shared::ActorID;
-use fvm_shared::METHOD_SEND;
 use fvm_shared::{address::Address, econ::TokenAmount};
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
-use vm_api::util::{apply_ok, serialize_ok};
+use vm_api::util::serialize_ok;
 use vm_api::VM;
 
-use crate::util::create_accounts;
-use crate::TEST_FAUCET_ADDR;
+use crate::util::{create_accounts, create_eth_account};
 
 // Generate a statically typed interface for the contracts.
 sol!("../actors/evm/tests/contracts/Recursive.sol");
@@ -41,21 +35,7 @@ pub struct ContractParams(#[serde(with = "strict_bytes")] pub Vec<u8>);
 pub fn evm_eth_create_external_test(v: &dyn VM) {
     // create the EthAccount
     let eth_bits = hex_literal::hex!("FEEDFACECAFEBEEF000000000000000000000000");
-    let eth_addr = Address::new_delegated(EAM_ACTOR_ID, &eth_bits).unwrap();
-    apply_ok(
-        v,
-        &TEST_FAUCET_ADDR,
-        &eth_addr,
-        &TokenAmount::from_whole(10_000),
-        METHOD_SEND,
-        None::<RawBytes>,
-    );
-
-    let account = v.resolve_id_address(&eth_addr).unwrap();
-
-    let mut actor = v.actor(&account).unwrap();
-    actor.code = *ETHACCOUNT_ACTOR_CODE_ID;
-    v.set_actor(&account, actor);
+    let account = create_eth_account(v, &eth_bits, &TokenAmount::from_whole(10_000));
 
     // now create an empty contract
     let params = IpldBlock::serialize_cbor(&fil_actor_eam::CreateExternalParams(vec![])).unwrap();
@@ -882,3 +862,116 @@ pub fn evm_transient_reentry_test(v: &dyn VM) {
 
     assert!(event_emitted, "testReentry did not succeed as expected");
 }
+
+#[vm_test]
+pub fn evm_proxy_test(v: &dyn VM) {
+    // A proxy forwards calls to an implementation contract, which runs in the proxy's context:
+    // - with DELEGATECALL, the implementation sees the proxy's caller and value;
+    // - with CALLCODE, the implementation sees a call from the proxy, with the forwarded value.
+    // Either way, the implementation reads and writes the proxy's storage.
+    let account = create_accounts(v, 1, &TokenAmount::from_whole(10_000))[0];
+    let account_eth = id_to_eth(account.id().unwrap());
+
+    let create = |initcode: Vec<u8>| {
+        let create_result = v
+            .execute_message(
+                &account,
+                &EAM_ACTOR_ADDR,
+                &TokenAmount::zero(),
+                fil_actor_eam::Method::CreateExternal as u64,
+                Some(serialize_ok(&fil_actor_eam::CreateExternalParams(initcode))),
+            )
+            .unwrap();
+        assert!(
+            create_result.code.is_success(),
+            "failed to create the contract {}",
+            create_result.message
+        );
+        let create_return: fil_actor_eam::CreateExternalReturn =
+            create_result.ret.unwrap().deserialize().expect("failed to decode results");
+        create_return
+    };
+    let call = |contract: &Address, method: u8, value: TokenAmount| {
+        let mut params = [0u8; 4];
+        params[3] = method;
+        let call_result = v
+            .execute_message(
+                &account,
+                contract,
+                &value,
+                fil_actor_evm::Method::InvokeContract as u64,
+                Some(serialize_ok(&ContractParams(params.to_vec()))),
+            )
+            .unwrap();
+        assert!(
+            call_result.code.is_success(),
+            "failed to call the contract {}",
+            call_result.message
+        );
+        let BytesDe(return_value) =
+            call_result.ret.unwrap().deserialize().expect("failed to deserialize results");
+        return_value
+    };
+    let word = |n: u64| U256::from(n).to_bytes().to_vec();
+
+    let counter = create(
+        hex::decode(include_str!("../../../actors/evm/tests/contracts/counter.hex")).unwrap(),
+    );
+    let counter_addr = counter.robust_address.unwrap();
+    let proxy_bytecode =
+        hex::decode(include_str!("../../../actors/evm/tests/contracts/proxy.hex")).unwrap();
+    // The proxy's constructor stores the implementation address and forwarding mode.
+    let create_proxy = |use_callcode: bool| {
+        let mut initcode = proxy_bytecode.clone();
+        initcode.extend_from_slice(&[0u8; 12]);
+        initcode.extend_from_slice(&counter.eth_address.0);
+        initcode.extend(word(use_callcode as u64));
+        create(initcode)
+    };
+
+    // increment(), get(), caller(), address(), callvalue()
+    let (increment, get, caller, address, callvalue) = (1, 2, 3, 4, 5);
+
+    let delegate_proxy = create_proxy(false);
+    let delegate_proxy_addr = delegate_proxy.robust_address.unwrap();
+    assert_eq!(word(1), call(&delegate_proxy_addr, increment, TokenAmount::zero()));
+    assert_eq!(word(2), call(&delegate_proxy_addr, increment, TokenAmount::zero()));
+    assert_eq!(word(2), call(&delegate_proxy_addr, get, TokenAmount::zero()));
+    assert_eq!(
+        &call(&delegate_proxy_addr, caller, TokenAmount::zero())[12..],
+        account_eth.as_slice()
+    );
+    assert_eq!(
+        &call(&delegate_proxy_addr, address, TokenAmount::zero())[12..],
+        &delegate_proxy.eth_address.0
+    );
+    assert_eq!(
+        U256::from(&TokenAmount::from_atto(7)).to_bytes().to_vec(),
+        call(&delegate_proxy_addr, callvalue, TokenAmount::from_atto(7))
+    );
+
+    let callcode_proxy = create_proxy(true);
+    let callcode_proxy_addr = callcode_proxy.robust_address.unwrap();
+    assert_eq!(word(1), call(&callcode_proxy_addr, increment, TokenAmount::zero()));
+    assert_eq!(word(1), call(&callcode_proxy_addr, get, TokenAmount::zero()));
+    assert_eq!(
+        &call(&callcode_proxy_addr, caller, TokenAmount::zero())[12..],
+        &callcode_proxy.eth_address.0
+    );
+    assert_eq!(
+        &call(&callcode_proxy_addr, address, TokenAmount::zero())[12..],
+        &callcode_proxy.eth_address.0
+    );
+    assert_eq!(
+        U256::from(&TokenAmount::from_atto(7)).to_bytes().to_vec(),
+        call(&callcode_proxy_addr, callvalue, TokenAmount::from_atto(7))
+    );
+    // The forwarded value stays with the proxy.
+    assert_eq!(
+        TokenAmount::from_atto(7),
+        v.actor(&Address::new_id(callcode_proxy.actor_id)).unwrap().balance
+    );
+
+    // The implementation's own storage is untouched.
+    assert_eq!(word(0), call(&counter_addr, get, TokenAmount::zero()));
+}
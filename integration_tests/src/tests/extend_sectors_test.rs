@@ -27,11 +27,12 @@ use crate::expects::Expect;
 use crate::util::{
     advance_by_deadline_to_epoch, advance_by_deadline_to_epoch_while_proving,
     advance_by_deadline_to_index, advance_to_proving_deadline, bf_all, create_accounts,
-    create_miner, cron_tick, expect_invariants, invariant_failure_patterns,
+    create_miner, create_storage_parties, cron_tick, expect_invariants, invariant_failure_patterns,
     make_piece_manifests_from_deal_ids, market_add_balance, market_pending_deal_allocations,
     market_publish_deal, miner_precommit_one_sector_v2, miner_prove_sector,
     override_compute_unsealed_sector_cid, precommit_meta_data_from_deals, sector_deadline,
     submit_windowed_post, verifreg_add_client, verifreg_add_verifier, PrecommitMetadata,
+    StorageParties,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -112,9 +113,9 @@ pub fn extend(
 }
 
 pub fn extend_legacy_sector_with_deals_test(v: &dyn VM, do_extend2: bool) {
-    let addrs = create_accounts(v, 3, &TokenAmount::from_whole(10_000));
+    let StorageParties { owner, worker, verifier, client: verified_client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
     let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
-    let (owner, worker, verifier, verified_client) = (addrs[0], addrs[0], addrs[1], addrs[2]);
     let sector_number: SectorNumber = 100;
     let policy = Policy::default();
 
@@ -344,9 +345,9 @@ pub fn extend_legacy_sector_with_deals(v: &dyn VM) {
 
 #[vm_test]
 pub fn commit_sector_with_max_duration_deal_test(v: &dyn VM) {
-    let addrs = create_accounts(v, 3, &TokenAmount::from_whole(10_000));
+    let StorageParties { owner, worker, verifier, client: verified_client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
     let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
-    let (owner, worker, verifier, verified_client) = (addrs[0], addrs[0], addrs[1], addrs[2]);
     let sector_number: SectorNumber = 100;
     let policy = Policy::default();
 
@@ -537,9 +538,9 @@ pub fn extend_sector_up_to_max_relative_extension_test(v: &dyn VM) {
 pub fn extend_updated_sector_with_claims_test(v: &dyn VM) {
     override_compute_unsealed_sector_cid(v);
 
-    let addrs = create_accounts(v, 3, &TokenAmount::from_whole(10_000));
+    let StorageParties { owner, worker, verifier, client: verified_client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
     let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
-    let (owner, worker, verifier, verified_client) = (addrs[0], addrs[0], addrs[1], addrs[2]);
     let worker_id = worker.id().unwrap();
     let sector_number: SectorNumber = 100;
     let policy = Policy::default();
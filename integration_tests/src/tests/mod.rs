@@ -10,6 +10,8 @@ mod change_owner_test;
 pub use change_owner_test::*;
 mod commit_post_test;
 pub use commit_post_test::*;
+mod consensus_fault_test;
+pub use consensus_fault_test::*;
 mod datacap_tests;
 pub use datacap_tests::*;
 mod evm_test;
@@ -22,6 +24,8 @@ mod multisig_test;
 pub use multisig_test::*;
 mod init_test;
 pub use init_test::*;
+mod paych_test;
+pub use paych_test::*;
 mod power_scenario_tests;
 pub use power_scenario_tests::*;
 mod publish_deals_test;
@@ -56,6 +56,7 @@ pub fn proposal_hash_test(v: &dyn VM) {
         method: METHOD_SEND,
         approved: vec![alice],
         params: RawBytes::default(),
+        expires_at: None,
     };
 
     let wrong_hash = compute_proposal_hash(&wrong_tx, v.primitives()).unwrap();
@@ -77,6 +78,7 @@ pub fn proposal_hash_test(v: &dyn VM) {
         method: METHOD_SEND,
         approved: vec![alice],
         params: RawBytes::default(),
+        expires_at: None,
     };
 
     let correct_hash = compute_proposal_hash(&correct_tx, v.primitives()).unwrap();
@@ -268,7 +270,7 @@ pub fn swap_self_2_of_3_test(v: &dyn VM) {
     assert_invariants(v, &Policy::default(), None)
 }
 
-fn create_msig(v: &dyn VM, signers: &[Address], threshold: u64) -> Address {
+pub(crate) fn create_msig(v: &dyn VM, signers: &[Address], threshold: u64) -> Address {
     assert!(!signers.is_empty());
     let msig_ctor_params = serialize(
         &fil_actor_multisig::ConstructorParams {
@@ -0,0 +1,215 @@
+use export_macro::vm_test;
+use fil_actor_init::{Exec4Return, ExecPaymentChannelParams, Method as InitMethod};
+use fil_actor_multisig::{Method as MsigMethod, ProposeParams, ProposeReturn};
+use fil_actor_paych::{
+    Method as PaychMethod, SignedVoucher, State as PaychState, UpdateChannelStateParams,
+    SETTLE_DELAY,
+};
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::{payment_channel_address, INIT_ACTOR_ADDR};
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::crypto::signature::Signature;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::METHOD_SEND;
+use num_traits::Zero;
+use vm_api::builtin::Type;
+use vm_api::util::{apply_code, apply_ok, get_state};
+use vm_api::VM;
+
+use super::multisig_test::create_msig;
+use crate::util::{advance_to_epoch_with_cron, assert_invariants, create_accounts};
+
+/// A payer funds and creates a payment channel at an address known in advance, and the
+/// payee redeems a voucher addressed to it.
+#[vm_test]
+pub fn payment_channel_at_derived_address_test(v: &dyn VM) {
+    let addrs = create_accounts(v, 2, &TokenAmount::from_whole(10_000));
+    let (payer, payee) = (addrs[0], addrs[1]);
+    let channel = payment_channel_address(v.primitives(), &payer, &payee, 0).unwrap();
+
+    // Funds sent to the channel before it exists are held by a placeholder.
+    apply_ok(v, &payer, &channel, &TokenAmount::from_whole(10), METHOD_SEND, None::<RawBytes>);
+    let placeholder = v.resolve_id_address(&channel).unwrap();
+
+    // Creating a channel between other parties, or with another nonce, doesn't claim it.
+    let params = ExecPaymentChannelParams { from: payer, to: payee, nonce: 1 };
+    let ret: Exec4Return = apply_ok(
+        v,
+        &payer,
+        &INIT_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        InitMethod::ExecPaymentChannelExported as u64,
+        Some(params),
+    )
+    .deserialize()
+    .unwrap();
+    assert_ne!(placeholder, ret.id_address);
+
+    let params = ExecPaymentChannelParams { from: payer, to: payee, nonce: 0 };
+    let ret: Exec4Return = apply_ok(
+        v,
+        &payer,
+        &INIT_ACTOR_ADDR,
+        &TokenAmount::from_whole(90),
+        InitMethod::ExecPaymentChannelExported as u64,
+        Some(params.clone()),
+    )
+    .deserialize()
+    .unwrap();
+    assert_eq!(placeholder, ret.id_address);
+    let actor = v.actor(&placeholder).unwrap();
+    assert_eq!(&Type::PaymentChannel, v.actor_manifest().get(&actor.code).unwrap());
+    assert_eq!(TokenAmount::from_whole(100), actor.balance);
+
+    // The channel can't be created again.
+    apply_code(
+        v,
+        &payer,
+        &INIT_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        InitMethod::ExecPaymentChannelExported as u64,
+        Some(params),
+        ExitCode::USR_FORBIDDEN,
+    );
+
+    let mut sv = SignedVoucher {
+        channel_addr: channel,
+        time_lock_min: 0,
+        time_lock_max: 0,
+        secret_pre_image: vec![],
+        extra: None,
+        lane: 0,
+        nonce: 1,
+        amount: TokenAmount::from_whole(30),
+        min_settle_height: 0,
+        merges: vec![],
+        signature: None,
+    };
+    sv.signature = Some(Signature::new_bls(sv.signing_bytes().unwrap()));
+    apply_ok(
+        v,
+        &payee,
+        &channel,
+        &TokenAmount::zero(),
+        PaychMethod::UpdateChannelState as u64,
+        Some(UpdateChannelStateParams::from(sv)),
+    );
+    let st: PaychState = get_state(v, &placeholder).unwrap();
+    assert_eq!(TokenAmount::from_whole(30), st.to_send);
+    assert_invariants(v, &Policy::default(), None);
+}
+
+/// Collecting a settled channel pays out its balance and deletes it, after which messages and
+/// internal sends to it fail for want of a receiver.
+#[vm_test]
+pub fn payment_channel_collect_deletes_channel_test(v: &dyn VM) {
+    let addrs = create_accounts(v, 2, &TokenAmount::from_whole(10_000));
+    let (payer, payee) = (addrs[0], addrs[1]);
+    let ret: Exec4Return = apply_ok(
+        v,
+        &payer,
+        &INIT_ACTOR_ADDR,
+        &TokenAmount::from_whole(100),
+        InitMethod::ExecPaymentChannelExported as u64,
+        Some(ExecPaymentChannelParams { from: payer, to: payee, nonce: 0 }),
+    )
+    .deserialize()
+    .unwrap();
+    let channel = ret.id_address;
+
+    let mut sv = SignedVoucher {
+        channel_addr: channel,
+        time_lock_min: 0,
+        time_lock_max: 0,
+        secret_pre_image: vec![],
+        extra: None,
+        lane: 0,
+        nonce: 1,
+        amount: TokenAmount::from_whole(30),
+        min_settle_height: 0,
+        merges: vec![],
+        signature: None,
+    };
+    sv.signature = Some(Signature::new_bls(sv.signing_bytes().unwrap()));
+    apply_ok(
+        v,
+        &payee,
+        &channel,
+        &TokenAmount::zero(),
+        PaychMethod::UpdateChannelState as u64,
+        Some(UpdateChannelStateParams::from(sv)),
+    );
+    apply_ok(
+        v,
+        &payer,
+        &channel,
+        &TokenAmount::zero(),
+        PaychMethod::Settle as u64,
+        None::<RawBytes>,
+    );
+
+    // Collecting before the settlement delay has elapsed is forbidden.
+    apply_code(
+        v,
+        &payee,
+        &channel,
+        &TokenAmount::zero(),
+        PaychMethod::Collect as u64,
+        None::<RawBytes>,
+        ExitCode::USR_FORBIDDEN,
+    );
+    advance_to_epoch_with_cron(v, v.epoch() + SETTLE_DELAY);
+    let (payer_balance, payee_balance) = (v.balance(&payer), v.balance(&payee));
+    apply_ok(
+        v,
+        &payee,
+        &channel,
+        &TokenAmount::zero(),
+        PaychMethod::Collect as u64,
+        None::<RawBytes>,
+    );
+
+    assert!(v.actor(&channel).is_none());
+    assert_eq!(payer_balance + TokenAmount::from_whole(70), v.balance(&payer));
+    assert_eq!(payee_balance + TokenAmount::from_whole(30), v.balance(&payee));
+    assert_invariants(v, &Policy::default(), None);
+
+    // Messages to the channel, by ID or robust address, have no receiver.
+    let robust = payment_channel_address(v.primitives(), &payer, &payee, 0).unwrap();
+    for to in [channel, robust] {
+        apply_code(
+            v,
+            &payer,
+            &to,
+            &TokenAmount::from_whole(1),
+            METHOD_SEND,
+            None::<RawBytes>,
+            ExitCode::SYS_INVALID_RECEIVER,
+        );
+    }
+
+    // An internal send to the channel fails, and the funds stay with the sender.
+    let msig = create_msig(v, &[payer], 1);
+    apply_ok(v, &payer, &msig, &TokenAmount::from_whole(1), METHOD_SEND, None::<RawBytes>);
+    let ret: ProposeReturn = apply_ok(
+        v,
+        &payer,
+        &msig,
+        &TokenAmount::zero(),
+        MsigMethod::Propose as u64,
+        Some(ProposeParams {
+            to: channel,
+            value: TokenAmount::from_whole(1),
+            method: METHOD_SEND,
+            params: RawBytes::default(),
+        }),
+    )
+    .deserialize()
+    .unwrap();
+    assert!(ret.applied);
+    assert_eq!(ExitCode::USR_UNSPECIFIED, ret.code);
+    assert_eq!(TokenAmount::from_whole(1), v.balance(&msig));
+    assert!(v.actor(&channel).is_none());
+    assert_invariants(v, &Policy::default(), None);
+}
@@ -36,10 +36,10 @@ use crate::deals::{DealBatcher, DealOptions};
 use crate::expects::Expect;
 use crate::util::{
     advance_by_deadline_to_epoch, advance_by_deadline_to_index, advance_to_proving_deadline,
-    create_accounts, create_miner, datacap_create_allocations, market_add_balance,
+    create_miner, create_storage_parties, datacap_create_allocations, market_add_balance,
     market_list_deals, market_list_sectors_deals, override_compute_unsealed_sector_cid,
     precommit_sectors_v2, sector_info, submit_windowed_post, verifreg_add_client,
-    verifreg_add_verifier, verifreg_list_claims, PrecommitMetadata,
+    verifreg_add_verifier, verifreg_list_claims, PrecommitMetadata, StorageParties,
 };
 
 #[vm_test]
@@ -47,10 +47,10 @@ pub fn prove_replica_update2_test(v: &dyn VM) {
     override_compute_unsealed_sector_cid(v);
 
     let policy = Policy::default();
-    let addrs = create_accounts(v, 3, &TokenAmount::from_whole(10_000));
+    let StorageParties { owner, worker, verifier, client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
     let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
     let sector_size = seal_proof.sector_size().unwrap();
-    let (owner, worker, verifier, client) = (addrs[0], addrs[0], addrs[1], addrs[2]);
     let worker_id = worker.id().unwrap();
     let client_id = client.id().unwrap();
     let (maddr, _) = create_miner(
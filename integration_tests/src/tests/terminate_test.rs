@@ -154,7 +154,7 @@ pub fn terminate_sectors_test(v: &dyn VM) {
 
     let st: MarketState = get_state(v, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
     let store = DynBlockstore::wrap(v.blockstore());
-    let deal_states = DealMetaArray::load(&st.states, &store).unwrap();
+    let deal_states = DealMetaArray::load(&store, &st.states, "deal states").unwrap();
     for id in deal_ids.iter() {
         // deals are pending and don't yet have deal states
         let state = deal_states.get(*id).unwrap();
@@ -273,7 +273,7 @@ pub fn terminate_sectors_test(v: &dyn VM) {
     // termination synchronously deletes deal state
     let st: MarketState = get_state(v, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
     let store = DynBlockstore::wrap(v.blockstore());
-    let deal_states = DealMetaArray::load(&st.states, &store).unwrap();
+    let deal_states = DealMetaArray::load(&store, &st.states, "deal states").unwrap();
     for &id in deal_ids.iter() {
         let state = deal_states.get(id).unwrap();
         assert!(state.is_none());
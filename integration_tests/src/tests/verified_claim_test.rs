@@ -5,7 +5,7 @@ use fvm_ipld_encoding::RawBytes;
 use fvm_shared::bigint::Zero;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
-use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::piece::{PaddedPieceSize, PieceInfo};
 use fvm_shared::sector::{RegisteredSealProof, SectorNumber, StoragePower};
 
 use fil_actor_datacap::State as DatacapState;
@@ -14,13 +14,14 @@ use fil_actor_market::{
     PendingDealAllocationsMap, State as MarketState, PENDING_ALLOCATIONS_CONFIG,
 };
 use fil_actor_miner::{
-    max_prove_commit_duration, PowerPair, ProveCommitSectors3Params, SectorActivationManifest,
-    SectorClaim, State as MinerState,
+    max_prove_commit_duration, CompactCommD, PieceActivationManifest, PowerPair,
+    ProveCommitSectors3Params, SectorActivationManifest, SectorClaim, State as MinerState,
+    VerifiedAllocationKey,
 };
 use fil_actor_power::State as PowerState;
 use fil_actor_verifreg::{
-    Claim, Method as VerifregMethod, RemoveExpiredClaimsParams, RemoveExpiredClaimsReturn,
-    State as VerifregState,
+    AllocationRequest, Claim, Method as VerifregMethod, RemoveExpiredClaimsParams,
+    RemoveExpiredClaimsReturn, State as VerifregState,
 };
 use fil_actors_runtime::cbor::deserialize;
 use fil_actors_runtime::runtime::policy_constants::{
@@ -39,12 +40,18 @@ use vm_api::VM;
 use crate::util::{
     advance_by_deadline_to_epoch, advance_by_deadline_to_epoch_while_proving,
     advance_by_deadline_to_index, advance_to_proving_deadline, assert_invariants, create_accounts,
-    create_miner, cron_tick, datacap_extend_claim, datacap_get_balance, expect_invariants,
-    invariant_failure_patterns, make_piece_manifests_from_deal_ids, market_add_balance,
-    market_pending_deal_allocations, market_publish_deal, miner_extend_sector_expiration2,
-    miner_precommit_one_sector_v2, miner_prove_sector, precommit_meta_data_from_deals,
-    provider_settle_deal_payments, sector_deadline, submit_windowed_post, verifreg_add_client,
-    verifreg_add_verifier, verifreg_extend_claim_terms, verifreg_remove_expired_allocations,
+    create_miner, create_storage_parties, cron_tick, datacap_extend_claim, datacap_get_balance,
+    expect_invariants, invariant_failure_patterns, make_piece_manifests_from_deal_ids,
+    market_add_balance, market_pending_deal_allocations, market_publish_deal,
+    miner_extend_sector_expiration2, miner_precommit_one_sector_v2, miner_prove_sector,
+    precommit_meta_data_from_deals, provider_settle_deal_payments, sector_deadline,
+    submit_windowed_post, verifreg_add_client, verifreg_add_verifier, verifreg_extend_claim_terms,
+    verifreg_remove_expired_allocations, StorageParties,
+};
+use crate::util::{
+    datacap_create_allocations, market_list_deals, miner_power, miner_terminate_sectors,
+    override_compute_unsealed_sector_cid, verifreg_list_claims, verifreg_remove_expired_claims,
+    PrecommitMetadata,
 };
 
 /// Tests a scenario involving a verified deal from the built-in market, with associated
@@ -368,7 +375,7 @@ pub fn verified_claim_scenario_test(v: &dyn VM) {
 
     let market_state: MarketState = get_state(v, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
     let store = DynBlockstore::wrap(v.blockstore());
-    let proposals = DealArray::load(&market_state.proposals, &store).unwrap();
+    let proposals = DealArray::load(&store, &market_state.proposals, "deal proposals").unwrap();
     let proposal = proposals.get(deals[0]).unwrap().unwrap();
     // provider must process the deals to receive payment and cleanup state
     let ret = provider_settle_deal_payments(v, &miner_id, &deals);
@@ -387,9 +394,9 @@ pub fn verified_claim_scenario_test(v: &dyn VM) {
 
 #[vm_test]
 pub fn expired_allocations_test(v: &dyn VM) {
-    let addrs = create_accounts(v, 3, &TokenAmount::from_whole(10_000));
+    let StorageParties { owner, worker, verifier, client: verified_client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
     let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
-    let (owner, worker, verifier, verified_client) = (addrs[0], addrs[0], addrs[1], addrs[2]);
 
     // Create miner
     let (miner_id, _) = create_miner(
@@ -442,7 +449,7 @@ pub fn expired_allocations_test(v: &dyn VM) {
     // Deal has expired and cleaned up.
     let market_state: MarketState = get_state(v, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
     let store = DynBlockstore::wrap(v.blockstore());
-    let proposals = DealArray::load(&market_state.proposals, &store).unwrap();
+    let proposals = DealArray::load(&store, &market_state.proposals, "deal proposals").unwrap();
     assert!(proposals.get(deal1).unwrap().is_none());
     let pending_deal_allocs = PendingDealAllocationsMap::load(
         &store,
@@ -488,9 +495,9 @@ pub fn expired_allocations_test(v: &dyn VM) {
 
 #[vm_test]
 pub fn deal_passes_claim_fails_test(v: &dyn VM) {
-    let addrs = create_accounts(v, 3, &TokenAmount::from_whole(10_000));
+    let StorageParties { owner, worker, verifier, client: verified_client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
     let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
-    let (owner, worker, verifier, verified_client) = (addrs[0], addrs[0], addrs[1], addrs[2]);
 
     // Create miner
     let (miner_id, _) = create_miner(
@@ -629,7 +636,7 @@ pub fn deal_passes_claim_fails_test(v: &dyn VM) {
     // Verify deal state.
     let market_state: MarketState = get_state(v, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
     let store = DynBlockstore::wrap(v.blockstore());
-    let deal_states = DealMetaArray::load(&market_state.states, &store).unwrap();
+    let deal_states = DealMetaArray::load(&store, &market_state.states, "deal states").unwrap();
     // bad deal sector can't be confirmed for commit so bad deal must not be included
     let bad_deal_state = deal_states.get(bad_deal).unwrap();
     assert_eq!(None, bad_deal_state);
@@ -651,3 +658,172 @@ pub fn deal_passes_claim_fails_test(v: &dyn VM) {
     // run check before last change and confirm that we hit the expected broken state error
     assert_invariants(v, &Policy::default(), None);
 }
+
+/// Tests the full life of verified data in a sector: datacap is granted to a client, who
+/// allocates some directly via a datacap transfer and some through a verified deal, then the
+/// provider claims both allocations when proving the sector. The client extends a claim's term,
+/// the provider terminates the sector, and the claims are cleaned up once they expire.
+/// State invariants are checked after each step.
+#[vm_test]
+pub fn verified_deal_lifecycle_test(v: &dyn VM) {
+    override_compute_unsealed_sector_cid(v);
+    let StorageParties { owner, worker, verifier, client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let sector_number: SectorNumber = 100;
+    let policy = Policy::default();
+    let (maddr, _) = create_miner(
+        v,
+        &owner,
+        &worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+    let miner_id = maddr.id().unwrap();
+    let client_id = client.id().unwrap();
+    let piece_size = PaddedPieceSize(seal_proof.sector_size().unwrap() as u64 / 2);
+
+    // Grant datacap to the client.
+    let datacap = StoragePower::from(32_u128 << 40);
+    verifreg_add_verifier(v, &verifier, datacap.clone());
+    verifreg_add_client(v, &verifier, &client, datacap.clone());
+    assert_invariants_at_epoch_end(v, &policy);
+
+    // The client allocates half a sector directly by transferring datacap to the registry.
+    let deal_start = v.epoch() + max_prove_commit_duration(&policy, seal_proof).unwrap();
+    let term_min = 180 * EPOCHS_IN_DAY;
+    let term_max = term_min + MARKET_DEFAULT_ALLOCATION_TERM_BUFFER;
+    let direct_alloc = AllocationRequest {
+        provider: miner_id,
+        data: make_piece_cid(b"direct"),
+        size: piece_size,
+        term_min,
+        term_max,
+        expiration: deal_start,
+    };
+    let direct_alloc_id = datacap_create_allocations(v, &client, &[direct_alloc.clone()])[0];
+    assert_invariants_at_epoch_end(v, &policy);
+
+    // The client publishes a verified deal for the other half, which allocates the rest.
+    market_add_balance(v, &client, &client, &TokenAmount::from_whole(3));
+    market_add_balance(v, &worker, &maddr, &TokenAmount::from_whole(64));
+    let deals = market_publish_deal(
+        v,
+        &worker,
+        &client,
+        &maddr,
+        "deal".to_string(),
+        piece_size,
+        true,
+        deal_start,
+        term_min,
+    )
+    .ids;
+    let deal_alloc_id = market_pending_deal_allocations(v, &deals)[0];
+    assert_eq!(
+        TokenAmount::from_whole(datacap.clone()) - TokenAmount::from_whole(piece_size.0 * 2),
+        datacap_get_balance(v, &client)
+    );
+    assert_invariants_at_epoch_end(v, &policy);
+
+    // The provider pre-commits and proves a sector holding both pieces, claiming both allocations.
+    let mut pieces = vec![PieceActivationManifest {
+        cid: direct_alloc.data,
+        size: direct_alloc.size,
+        verified_allocation_key: Some(VerifiedAllocationKey {
+            client: client_id,
+            id: direct_alloc_id,
+        }),
+        notify: vec![],
+    }];
+    pieces.extend(make_piece_manifests_from_deal_ids(v, deals.clone()));
+    let piece_infos: Vec<PieceInfo> =
+        pieces.iter().map(|p| PieceInfo { size: p.size, cid: p.cid }).collect();
+    let commd = v.primitives().compute_unsealed_sector_cid(seal_proof, &piece_infos).unwrap();
+    miner_precommit_one_sector_v2(
+        v,
+        &worker,
+        &maddr,
+        seal_proof,
+        sector_number,
+        PrecommitMetadata { deals: vec![], commd: CompactCommD::of(commd) },
+        true,
+        deal_start + term_max,
+    );
+    advance_by_deadline_to_epoch(v, &maddr, deal_start);
+    miner_prove_sector(v, &worker, &maddr, sector_number, pieces);
+
+    let claims = verifreg_list_claims(v, miner_id);
+    assert_eq!(2, claims.len());
+    for id in [direct_alloc_id, deal_alloc_id] {
+        let claim = &claims[&id];
+        assert_eq!(sector_number, claim.sector);
+        assert_eq!(client_id, claim.client);
+        assert_eq!(term_max, claim.term_max);
+    }
+    assert_invariants_at_epoch_end(v, &policy);
+
+    // The sector gains verified power once proven in its deadline.
+    let (deadline_info, partition_index) = advance_to_proving_deadline(v, &maddr, sector_number);
+    let verified_power = PowerPair::new(
+        StoragePower::from(piece_size.0 * 2),
+        StoragePower::from(piece_size.0 * 2 * 10),
+    );
+    submit_windowed_post(
+        v,
+        &worker,
+        &maddr,
+        deadline_info,
+        partition_index,
+        Some(verified_power.clone()),
+    );
+    assert_eq!(verified_power, miner_power(v, &maddr));
+    assert_invariants_at_epoch_end(v, &policy);
+
+    // The client extends the direct claim's maximum term.
+    let extended_term_max = term_max + 180 * EPOCHS_IN_DAY;
+    verifreg_extend_claim_terms(v, &client, &maddr, direct_alloc_id, extended_term_max);
+    let claims = verifreg_list_claims(v, miner_id);
+    assert_eq!(extended_term_max, claims[&direct_alloc_id].term_max);
+    assert_eq!(term_max, claims[&deal_alloc_id].term_max);
+    assert_invariants_at_epoch_end(v, &policy);
+
+    // The provider terminates the sector, removing its power and the deal.
+    // Sectors can't be terminated while their deadline is, or is about to be, open.
+    advance_by_deadline_to_index(
+        v,
+        &maddr,
+        (deadline_info.index + 2) % policy.wpost_period_deadlines,
+    );
+    miner_terminate_sectors(v, &worker, &maddr, &[sector_number]);
+    assert_eq!(PowerPair::zero(), miner_power(v, &maddr));
+    assert!(market_list_deals(v).is_empty());
+    // The claims outlive the sector until their terms expire.
+    assert_eq!(2, verifreg_list_claims(v, miner_id).len());
+    assert_invariants_at_epoch_end(v, &policy);
+
+    // Once the claims have expired, anyone can clean them up.
+    let claims = verifreg_list_claims(v, miner_id);
+    let last_expiry = claims.values().map(|c| c.term_start + c.term_max).max().unwrap();
+    v.set_epoch(last_expiry + 1);
+    let ret =
+        verifreg_remove_expired_claims(v, &worker, &maddr, vec![direct_alloc_id, deal_alloc_id]);
+    assert_eq!(vec![direct_alloc_id, deal_alloc_id], ret.considered);
+    assert!(ret.results.all_ok(), "results had failures {}", ret.results);
+    assert!(verifreg_list_claims(v, miner_id).is_empty());
+
+    expect_invariants(
+        v,
+        &policy,
+        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
+        None,
+    );
+}
+
+/// Runs cron to finish the current epoch and moves to the next, then checks state invariants,
+/// which hold only at epoch boundaries.
+fn assert_invariants_at_epoch_end(v: &dyn VM, policy: &Policy) {
+    cron_tick(v);
+    v.set_epoch(v.epoch() + 1);
+    assert_invariants(v, policy, None);
+}
@@ -32,7 +32,8 @@ use vm_api::VM;
 use crate::expects::Expect;
 
 use crate::util::{
-    assert_invariants, create_accounts, verifier_balance_event_with_client, verifreg_add_verifier,
+    assert_invariants, create_accounts, datacap_granted_event, datacap_removed_event,
+    verifier_balance_event_with_client, verifreg_add_verifier,
 };
 use crate::{TEST_VERIFREG_ROOT_ADDR, TEST_VERIFREG_ROOT_ID};
 
@@ -84,11 +85,18 @@ pub fn remove_datacap_simple_successful_path_test(v: &dyn VM) {
             subinvocs: None,
             ..Default::default()
         }]),
-        events: Some(vec![verifier_balance_event_with_client(
-            verifier1.id().unwrap(),
-            verifier_datacap,
-            verified_client.id().unwrap(),
-        )]),
+        events: Some(vec![
+            verifier_balance_event_with_client(
+                verifier1.id().unwrap(),
+                verifier_datacap,
+                verified_client.id().unwrap(),
+            ),
+            datacap_granted_event(
+                verifier1.id().unwrap(),
+                verified_client.id().unwrap(),
+                verifier_allowance.clone(),
+            ),
+        ]),
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
@@ -439,6 +447,10 @@ fn expect_remove_datacap(
                 ..Default::default()
             },
         ]),
+        events: Some(vec![datacap_removed_event(
+            params.verified_client_to_remove.id().unwrap(),
+            params.data_cap_amount_to_remove.clone(),
+        )]),
         ..Default::default()
     }
 }
@@ -0,0 +1,130 @@
+use anyhow::anyhow;
+use cid::Cid;
+use fil_actor_miner::{Method as MinerMethod, ReportConsensusFaultParams};
+use fil_actors_runtime::test_utils::make_identity_cid;
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{strict_bytes, to_vec};
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::consensus::{ConsensusFault, ConsensusFaultType};
+use fvm_shared::econ::TokenAmount;
+use num_traits::Zero;
+use vm_api::util::apply_ok;
+use vm_api::VM;
+
+use super::miner_info;
+
+/// A stand-in for a block header, with just the fields needed to prove a consensus fault.
+/// Headers are "signed" by the miner's worker in the test VM's fake signature scheme,
+/// where the signature is the signed bytes themselves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct TestBlockHeader {
+    pub miner: Address,
+    pub worker: Address,
+    pub epoch: ChainEpoch,
+    pub parents: Vec<Cid>,
+    pub timestamp: u64,
+    #[serde(with = "strict_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl TestBlockHeader {
+    fn new(v: &dyn VM, miner: &Address, epoch: ChainEpoch, parents: Vec<Cid>) -> Self {
+        let worker = miner_info(v, miner).worker;
+        let mut header = TestBlockHeader {
+            miner: *miner,
+            worker,
+            epoch,
+            parents,
+            timestamp: epoch as u64 * 30,
+            signature: vec![],
+        };
+        header.signature = header.signing_bytes();
+        header
+    }
+
+    /// The bytes signed by the worker, being the header without its signature.
+    fn signing_bytes(&self) -> Vec<u8> {
+        to_vec(&TestBlockHeader { signature: vec![], ..self.clone() }).unwrap()
+    }
+}
+
+fn parents(epoch: ChainEpoch) -> Vec<Cid> {
+    vec![make_identity_cid(&epoch.to_be_bytes())]
+}
+
+/// Fabricates evidence of a miner producing two blocks at the same epoch.
+pub fn double_fork_evidence(
+    v: &dyn VM,
+    miner: &Address,
+    epoch: ChainEpoch,
+) -> ReportConsensusFaultParams {
+    let h1 = TestBlockHeader::new(v, miner, epoch, parents(epoch - 1));
+    let h2 = TestBlockHeader { timestamp: h1.timestamp + 1, ..h1.clone() };
+    let h2 = TestBlockHeader { signature: h2.signing_bytes(), ..h2 };
+    evidence(&h1, &h2)
+}
+
+/// Fabricates evidence of a miner producing two blocks with the same parents at different epochs.
+pub fn time_offset_evidence(
+    v: &dyn VM,
+    miner: &Address,
+    epoch: ChainEpoch,
+) -> ReportConsensusFaultParams {
+    let h1 = TestBlockHeader::new(v, miner, epoch, parents(epoch - 1));
+    let h2 = TestBlockHeader::new(v, miner, epoch + 1, parents(epoch - 1));
+    evidence(&h1, &h2)
+}
+
+fn evidence(h1: &TestBlockHeader, h2: &TestBlockHeader) -> ReportConsensusFaultParams {
+    ReportConsensusFaultParams {
+        header1: to_vec(h1).unwrap(),
+        header2: to_vec(h2).unwrap(),
+        header_extra: vec![],
+    }
+}
+
+/// Verifies consensus fault evidence made of [`TestBlockHeader`]s, for use as the VM's
+/// consensus fault primitive. Parent grinding faults aren't detected.
+pub fn verify_test_consensus_fault(
+    h1: &[u8],
+    h2: &[u8],
+    _extra: &[u8],
+) -> anyhow::Result<Option<ConsensusFault>> {
+    let h1: TestBlockHeader = fvm_ipld_encoding::from_slice(h1)?;
+    let h2: TestBlockHeader = fvm_ipld_encoding::from_slice(h2)?;
+    for h in [&h1, &h2] {
+        if h.signature != h.signing_bytes() {
+            return Err(anyhow!("invalid signature by {} on header at {}", h.worker, h.epoch));
+        }
+    }
+    if h1.miner != h2.miner || h1 == h2 || h1.epoch > h2.epoch {
+        return Ok(None);
+    }
+    let fault_type = if h1.epoch == h2.epoch {
+        ConsensusFaultType::DoubleForkMining
+    } else if h1.parents == h2.parents {
+        ConsensusFaultType::TimeOffsetMining
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(ConsensusFault { target: h1.miner, epoch: h2.epoch, fault_type }))
+}
+
+/// Installs the [`TestBlockHeader`] fault verifier and reports a fault to the miner.
+pub fn report_consensus_fault(
+    v: &dyn VM,
+    reporter: &Address,
+    miner: &Address,
+    params: ReportConsensusFaultParams,
+) {
+    v.mut_primitives().override_verify_consensus_fault(verify_test_consensus_fault);
+    apply_ok(
+        v,
+        reporter,
+        miner,
+        &TokenAmount::zero(),
+        MinerMethod::ReportConsensusFault as u64,
+        Some(params),
+    );
+}
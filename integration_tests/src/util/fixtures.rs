@@ -0,0 +1,67 @@
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::METHOD_SEND;
+use vm_api::util::{apply_ok, pk_addrs_from, secp_addrs_from};
+use vm_api::VM;
+
+use crate::TEST_FAUCET_ADDR;
+
+const ACCOUNT_SEED: u64 = 93837778;
+
+/// Returns addresses of created accounts in ID format
+pub fn create_accounts(v: &dyn VM, count: u64, balance: &TokenAmount) -> Vec<Address> {
+    create_accounts_seeded(v, count, balance, ACCOUNT_SEED, &TEST_FAUCET_ADDR)
+}
+
+/// Returns addresses of created accounts in ID format
+pub fn create_accounts_seeded(
+    v: &dyn VM,
+    count: u64,
+    balance: &TokenAmount,
+    seed: u64,
+    test_faucet_addr: &Address,
+) -> Vec<Address> {
+    fund_accounts(v, &pk_addrs_from(seed, count), balance, test_faucet_addr)
+}
+
+/// Returns addresses of created secp256k1 accounts in ID format
+pub fn create_secp_accounts_seeded(
+    v: &dyn VM,
+    count: u64,
+    balance: &TokenAmount,
+    seed: u64,
+    test_faucet_addr: &Address,
+) -> Vec<Address> {
+    fund_accounts(v, &secp_addrs_from(seed, count), balance, test_faucet_addr)
+}
+
+fn fund_accounts(
+    v: &dyn VM,
+    pk_addrs: &[Address],
+    balance: &TokenAmount,
+    test_faucet_addr: &Address,
+) -> Vec<Address> {
+    // Send funds from faucet to pk address, creating account actor
+    for pk_addr in pk_addrs {
+        apply_ok(v, test_faucet_addr, pk_addr, balance, METHOD_SEND, None::<RawBytes>);
+    }
+    // Normalize pk address to return id address of account actor
+    pk_addrs.iter().map(|pk_addr| v.resolve_id_address(pk_addr).unwrap()).collect()
+}
+
+/// The accounts taking part in a verified storage scenario.
+/// The miner's owner also acts as its worker.
+pub struct StorageParties {
+    pub owner: Address,
+    pub worker: Address,
+    pub verifier: Address,
+    pub client: Address,
+}
+
+/// Creates the owner, verifier and client accounts of a verified storage scenario,
+/// each funded with the given balance.
+pub fn create_storage_parties(v: &dyn VM, balance: &TokenAmount) -> StorageParties {
+    let addrs = create_accounts(v, 3, balance);
+    StorageParties { owner: addrs[0], worker: addrs[0], verifier: addrs[1], client: addrs[2] }
+}
@@ -1,4 +1,5 @@
 use cid::Cid;
+pub use consensus_fault::*;
 use fil_actor_market::{load_provider_sector_deals, DealProposal, DealState, State as MarketState};
 use fil_actor_miner::ext::verifreg::AllocationID;
 use fil_actor_miner::{
@@ -10,13 +11,16 @@ use fil_actor_power::State as PowerState;
 use fil_actor_reward::State as RewardState;
 use fil_actor_verifreg::{Claim, ClaimID, State as VerifregState};
 use fil_actors_runtime::cbor::serialize;
-use fil_actors_runtime::test_utils::make_piece_cid;
+use fil_actors_runtime::test_utils::{
+    make_piece_cid, ETHACCOUNT_ACTOR_CODE_ID, PLACEHOLDER_ACTOR_CODE_ID,
+};
 use fil_actors_runtime::ActorError;
 use fil_actors_runtime::{
-    parse_uint_key, runtime::Policy, MessageAccumulator, REWARD_ACTOR_ADDR,
+    parse_uint_key, runtime::Policy, MessageAccumulator, EAM_ACTOR_ID, REWARD_ACTOR_ADDR,
     STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
-use fil_builtin_actors_state::check::check_state_invariants;
+use fil_builtin_actors_state::check::{check_state_invariants, check_state_invariants_streaming};
+pub use fixtures::*;
 use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::{CborStore, RawBytes, DAG_CBOR};
 use fvm_shared::address::Address;
@@ -31,37 +35,42 @@ use num_traits::Zero;
 use regex::Regex;
 use std::collections::HashMap;
 use vm_api::{
-    util::{apply_ok, get_state, pk_addrs_from, DynBlockstore},
+    util::{apply_ok, get_state, DynBlockstore},
     VM,
 };
 pub use workflows::*;
 
 use crate::{MinerBalances, NetworkStats, TEST_FAUCET_ADDR};
 
+mod consensus_fault;
+mod fixtures;
 mod workflows;
 
-const ACCOUNT_SEED: u64 = 93837778;
+/// Returns the f410 address of an Ethereum address, in the EAM's namespace.
+pub fn eth_f4_addr(eth_addr: &[u8; 20]) -> Address {
+    Address::new_delegated(EAM_ACTOR_ID, eth_addr).unwrap()
+}
 
-/// Returns addresses of created accounts in ID format
-pub fn create_accounts(v: &dyn VM, count: u64, balance: &TokenAmount) -> Vec<Address> {
-    create_accounts_seeded(v, count, balance, ACCOUNT_SEED, &TEST_FAUCET_ADDR)
+/// Sends funds from the faucet to an unassigned f410 address, creating a placeholder actor there.
+/// Returns the ID address of the placeholder.
+pub fn create_placeholder(v: &dyn VM, eth_addr: &[u8; 20], balance: &TokenAmount) -> Address {
+    let f4_addr = eth_f4_addr(eth_addr);
+    apply_ok(v, &TEST_FAUCET_ADDR, &f4_addr, balance, METHOD_SEND, None::<RawBytes>);
+    let id_addr = v.resolve_id_address(&f4_addr).unwrap();
+    assert_eq!(*PLACEHOLDER_ACTOR_CODE_ID, v.actor(&id_addr).unwrap().code);
+    id_addr
 }
 
-/// Returns addresses of created accounts in ID format
-pub fn create_accounts_seeded(
-    v: &dyn VM,
-    count: u64,
-    balance: &TokenAmount,
-    seed: u64,
-    test_faucet_addr: &Address,
-) -> Vec<Address> {
-    let pk_addrs = pk_addrs_from(seed, count);
-    // Send funds from faucet to pk address, creating account actor
-    for pk_addr in pk_addrs.clone() {
-        apply_ok(v, test_faucet_addr, &pk_addr, balance, METHOD_SEND, None::<RawBytes>);
-    }
-    // Normalize pk address to return id address of account actor
-    pk_addrs.iter().map(|pk_addr| v.resolve_id_address(pk_addr).unwrap()).collect()
+/// Creates an Ethereum account at the f410 address of an Ethereum address.
+/// The account is a placeholder promoted to an EthAccount actor, as happens when a placeholder
+/// first sends a message.
+/// Returns the ID address of the account.
+pub fn create_eth_account(v: &dyn VM, eth_addr: &[u8; 20], balance: &TokenAmount) -> Address {
+    let id_addr = create_placeholder(v, eth_addr, balance);
+    let mut actor = v.actor(&id_addr).unwrap();
+    actor.code = *ETHACCOUNT_ACTOR_CODE_ID;
+    v.set_actor(&id_addr, actor);
+    id_addr
 }
 
 pub fn check_invariants(
@@ -79,6 +88,22 @@ pub fn check_invariants(
     )
 }
 
+/// Checks invariants as [`check_invariants`] does, in the state checker's streaming mode.
+pub fn check_invariants_streaming(
+    vm: &dyn VM,
+    policy: &Policy,
+    expected_balance_total: Option<TokenAmount>,
+) -> anyhow::Result<MessageAccumulator> {
+    check_state_invariants_streaming(
+        &DynBlockstore::wrap(vm.blockstore()),
+        &vm.actor_manifest(),
+        policy,
+        vm.actor_states(),
+        expected_balance_total,
+        vm.epoch() - 1,
+    )
+}
+
 pub fn assert_invariants(v: &dyn VM, policy: &Policy, expected_balance_total: Option<TokenAmount>) {
     check_invariants(v, policy, expected_balance_total).unwrap().assert_empty()
 }
@@ -45,7 +45,8 @@ use fil_actor_miner::{
     PowerPair, PreCommitSectorBatchParams2, ProveCommitAggregateParams, ProveCommitSectors3Params,
     RecoveryDeclaration, SectorActivationManifest, SectorClaim, SectorPreCommitInfo,
     SectorPreCommitOnChainInfo, State as MinerState, SubmitWindowedPoStParams,
-    VerifiedAllocationKey, WithdrawBalanceParams, WithdrawBalanceReturn,
+    TerminateSectorsParams, TerminationDeclaration, VerifiedAllocationKey, WithdrawBalanceParams,
+    WithdrawBalanceReturn,
 };
 use fil_actor_multisig::Method as MultisigMethod;
 use fil_actor_multisig::ProposeParams;
@@ -55,8 +56,8 @@ use fil_actor_verifreg::ClaimExtensionRequest;
 use fil_actor_verifreg::{state, AllocationRequests};
 use fil_actor_verifreg::{
     AddVerifiedClientParams, AllocationID, ClaimID, ClaimTerm, ExtendClaimTermsParams,
-    Method as VerifregMethod, RemoveExpiredAllocationsParams, State as VerifregState,
-    VerifierParams,
+    Method as VerifregMethod, RemoveExpiredAllocationsParams, RemoveExpiredClaimsParams,
+    RemoveExpiredClaimsReturn, State as VerifregState, VerifierParams,
 };
 use fil_actor_verifreg::{AllocationRequest, DataCap};
 use fil_actors_runtime::cbor::deserialize;
@@ -102,6 +103,30 @@ pub fn cron_tick(v: &dyn VM) {
     );
 }
 
+/// Advances to an epoch, running cron at each epoch before it.
+pub fn advance_to_epoch_with_cron(v: &dyn VM, epoch: ChainEpoch) {
+    advance_to_epoch_sampling_cron(v, epoch, 1)
+}
+
+/// Advances to an epoch, running cron every `interval` epochs starting from the current epoch,
+/// and at the last epoch before the target.
+/// The power and market actors' cron handlers catch up on any skipped epochs, so sparse cron is
+/// much faster for long advances while still processing deadlines, deal updates and vesting.
+pub fn advance_to_epoch_sampling_cron(v: &dyn VM, epoch: ChainEpoch, interval: ChainEpoch) {
+    assert!(interval > 0, "cron interval {} must be positive", interval);
+    let start = v.epoch();
+    assert!(epoch >= start, "cannot advance from epoch {} back to {}", start, epoch);
+    for e in (start..epoch).step_by(interval as usize) {
+        v.set_epoch(e);
+        cron_tick(v);
+    }
+    if epoch - 1 > start && (epoch - 1 - start) % interval != 0 {
+        v.set_epoch(epoch - 1);
+        cron_tick(v);
+    }
+    v.set_epoch(epoch);
+}
+
 pub fn create_miner(
     v: &dyn VM,
     owner: &Address,
@@ -450,6 +475,47 @@ pub fn prove_commit_sectors(
     }
 }
 
+/// Terminates sectors, each of which must be active and in a partition of its own deadline.
+pub fn miner_terminate_sectors(
+    v: &dyn VM,
+    worker: &Address,
+    maddr: &Address,
+    sector_numbers: &[SectorNumber],
+) {
+    let worker_id = v.resolve_id_address(worker).unwrap().id().unwrap();
+    let miner_id = v.resolve_id_address(maddr).unwrap().id().unwrap();
+    let terminations = sector_numbers
+        .iter()
+        .map(|&sector_number| {
+            let (deadline, partition) = sector_deadline(v, maddr, sector_number);
+            TerminationDeclaration { deadline, partition, sectors: make_bitfield(&[sector_number]) }
+        })
+        .collect();
+    apply_ok(
+        v,
+        worker,
+        maddr,
+        &TokenAmount::zero(),
+        MinerMethod::TerminateSectors as u64,
+        Some(TerminateSectorsParams { terminations }),
+    );
+    ExpectInvocation {
+        from: worker_id,
+        to: Address::new_id(miner_id),
+        method: MinerMethod::TerminateSectors as u64,
+        events: Some(
+            sector_numbers
+                .iter()
+                .map(|&sector_number| {
+                    Expect::build_miner_event("sector-terminated", miner_id, sector_number)
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    }
+    .matches(v.take_invocations().last().unwrap());
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn miner_extend_sector_expiration2(
     v: &dyn VM,
@@ -794,6 +860,31 @@ pub fn verifier_balance_event_with_client(
     }
 }
 
+pub fn datacap_granted_event(verifier: ActorID, client: ActorID, amount: DataCap) -> EmittedEvent {
+    EmittedEvent {
+        emitter: VERIFIED_REGISTRY_ACTOR_ID,
+        event: EventBuilder::new()
+            .typ("datacap-granted")
+            .field_indexed("verifier", &verifier)
+            .field_indexed("client", &client)
+            .field("amount", &BigIntSer(&amount))
+            .build()
+            .unwrap(),
+    }
+}
+
+pub fn datacap_removed_event(client: ActorID, amount: DataCap) -> EmittedEvent {
+    EmittedEvent {
+        emitter: VERIFIED_REGISTRY_ACTOR_ID,
+        event: EventBuilder::new()
+            .typ("datacap-removed")
+            .field_indexed("client", &client)
+            .field("amount", &BigIntSer(&amount))
+            .build()
+            .unwrap(),
+    }
+}
+
 pub fn verifreg_add_verifier(v: &dyn VM, verifier: &Address, data_cap: StoragePower) {
     let add_verifier_params = VerifierParams { address: *verifier, allowance: data_cap.clone() };
     // root address is msig, send proposal from root key
@@ -858,7 +949,7 @@ pub fn verifreg_add_client(
         VerifregMethod::AddVerifiedClient as u64,
         Some(add_client_params),
     );
-    let allowance_tokens = TokenAmount::from_whole(allowance);
+    let allowance_tokens = TokenAmount::from_whole(allowance.clone());
     ExpectInvocation {
         from: verifier_id,
         to: VERIFIED_REGISTRY_ACTOR_ADDR,
@@ -886,11 +977,14 @@ pub fn verifreg_add_client(
             )]),
             ..Default::default()
         }]),
-        events: Some(vec![verifier_balance_event_with_client(
-            verifier.id().unwrap(),
-            updated_verifier_balance,
-            client.id().unwrap(),
-        )]),
+        events: Some(vec![
+            verifier_balance_event_with_client(
+                verifier.id().unwrap(),
+                updated_verifier_balance,
+                client.id().unwrap(),
+            ),
+            datacap_granted_event(verifier.id().unwrap(), client.id().unwrap(), allowance),
+        ]),
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
@@ -920,6 +1014,59 @@ pub fn verifreg_extend_claim_terms(
     );
 }
 
+pub fn verifreg_remove_expired_claims(
+    v: &dyn VM,
+    caller: &Address,
+    provider: &Address,
+    ids: Vec<ClaimID>,
+) -> RemoveExpiredClaimsReturn {
+    let v_st: VerifregState = get_state(v, &VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
+    let store = DynBlockstore::wrap(v.blockstore());
+    let mut claims = v_st.load_claims(&store).unwrap();
+    let provider_id = provider.id().unwrap();
+    let expected_events: Vec<EmittedEvent> = ids
+        .iter()
+        .map(|id| {
+            let claim = claims.get(provider_id, *id).unwrap().unwrap();
+            Expect::build_verifreg_claim_event(
+                "claim-removed",
+                *id,
+                claim.client,
+                provider_id,
+                &claim.data,
+                claim.size.0,
+                claim.term_min,
+                claim.term_max,
+                claim.term_start,
+                claim.sector,
+            )
+        })
+        .collect();
+
+    let caller_id = v.resolve_id_address(caller).unwrap().id().unwrap();
+    let params = RemoveExpiredClaimsParams { provider: provider_id, claim_ids: ids };
+    let ret: RemoveExpiredClaimsReturn = apply_ok(
+        v,
+        caller,
+        &VERIFIED_REGISTRY_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        VerifregMethod::RemoveExpiredClaims as u64,
+        Some(params),
+    )
+    .deserialize()
+    .unwrap();
+    ExpectInvocation {
+        from: caller_id,
+        to: VERIFIED_REGISTRY_ACTOR_ADDR,
+        method: VerifregMethod::RemoveExpiredClaims as u64,
+        subinvocs: Some(vec![]),
+        events: Some(expected_events),
+        ..Default::default()
+    }
+    .matches(v.take_invocations().last().unwrap());
+    ret
+}
+
 pub fn verifreg_remove_expired_allocations(
     v: &dyn VM,
     caller: &Address,
@@ -0,0 +1,351 @@
+use fil_actor_miner::{
+    DeclareFaultsRecoveredParams, Method as MinerMethod, RecoveryDeclaration, State as MinerState,
+};
+use fil_actors_runtime::runtime::Policy;
+use fvm_ipld_bitfield::BitField;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::deal::DealID;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::{RegisteredSealProof, SectorNumber};
+use num_traits::Zero;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use vm_api::util::{apply_ok, get_state, DynBlockstore};
+use vm_api::VM;
+
+use crate::deals::{DealBatcher, DealOptions};
+use crate::util::{
+    advance_to_epoch_with_cron, create_accounts, create_miner, deadline_state,
+    make_piece_manifests_from_deal_ids, market_add_balance, miner_dline_info, miner_prove_sector,
+    precommit_meta_data_from_deals, precommit_sectors_v2, submit_windowed_post, PrecommitMetadata,
+};
+
+/// Environment variable that overrides the seed chosen by [`workload_seed`].
+pub const WORKLOAD_SEED_VAR: &str = "WORKLOAD_SEED";
+
+/// Returns the seed in WORKLOAD_SEED if set, otherwise a random one.
+/// The seed is printed so that a failing run can be reproduced exactly.
+pub fn workload_seed() -> u64 {
+    let seed = match std::env::var(WORKLOAD_SEED_VAR) {
+        Ok(s) => s.parse().unwrap_or_else(|_| panic!("invalid {}: {}", WORKLOAD_SEED_VAR, s)),
+        Err(_) => rand::random(),
+    };
+    println!("workload seed: {} (set {} to reproduce)", seed, WORKLOAD_SEED_VAR);
+    seed
+}
+
+/// The shape of a generated workload.
+/// Probabilities are per step, and a step is one WindowPoSt challenge window.
+#[derive(Clone, Debug)]
+pub struct WorkloadConfig {
+    pub miners: usize,
+    pub clients: usize,
+    /// Maximum number of deals published to one provider in a step.
+    pub max_deals_per_step: usize,
+    /// Probability of each miner pre-committing a batch of sectors.
+    pub onboard_probability: f64,
+    /// Maximum number of sectors pre-committed in one batch.
+    pub max_sectors_per_batch: usize,
+    /// Probability of a fault storm starting, in which a random subset of miners
+    /// stops submitting WindowPoSts and declaring recoveries.
+    pub fault_storm_probability: f64,
+    /// Maximum length of a fault storm, in steps.
+    pub max_storm_steps: i64,
+}
+
+impl Default for WorkloadConfig {
+    fn default() -> Self {
+        WorkloadConfig {
+            miners: 3,
+            clients: 3,
+            max_deals_per_step: 4,
+            onboard_probability: 0.3,
+            max_sectors_per_batch: 4,
+            fault_storm_probability: 0.02,
+            max_storm_steps: 96,
+        }
+    }
+}
+
+/// Counts of the operations performed by a workload.
+#[derive(Clone, Debug, Default)]
+pub struct WorkloadStats {
+    pub steps: u64,
+    pub deals_published: u64,
+    pub deals_activated: u64,
+    pub sectors_precommitted: u64,
+    pub sectors_proven: u64,
+    pub posts_submitted: u64,
+    pub recoveries_declared: u64,
+    pub fault_storms: u64,
+}
+
+struct WorkloadMiner {
+    worker: Address,
+    maddr: Address,
+    next_sector: SectorNumber,
+    /// Pre-committed sectors, with their deals, and the epoch from which they can be proven.
+    precommits: Vec<(SectorNumber, Vec<DealID>, ChainEpoch)>,
+    /// Published deals not yet in a sector, with their start epochs.
+    pending_deals: Vec<(DealID, ChainEpoch)>,
+    /// The epoch until which the miner is offline, if it's caught in a fault storm.
+    storm_until: Option<ChainEpoch>,
+}
+
+/// A deterministic generator of miner and deal activity against a VM.
+/// The same seed and config always perform the same operations.
+/// Every operation is expected to succeed, so a failing message points to an actor bug
+/// (or a generator bug).
+pub struct Workload<'vm> {
+    v: &'vm dyn VM,
+    config: WorkloadConfig,
+    rng: ChaCha8Rng,
+    policy: Policy,
+    seal_proof: RegisteredSealProof,
+    miners: Vec<WorkloadMiner>,
+    clients: Vec<Address>,
+    next_label: u64,
+    stats: WorkloadStats,
+}
+
+impl<'vm> Workload<'vm> {
+    /// Creates the workload's miners and clients, with market balances for deals.
+    /// The VM's epoch must be past genesis, so that sectors can be sealed.
+    pub fn new(v: &'vm dyn VM, config: WorkloadConfig, seed: u64) -> Self {
+        let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+        let accounts = create_accounts(
+            v,
+            (config.miners + config.clients) as u64,
+            &TokenAmount::from_whole(100_000),
+        );
+        let (workers, clients) = accounts.split_at(config.miners);
+        let miners = workers
+            .iter()
+            .map(|worker| {
+                let (maddr, _) = create_miner(
+                    v,
+                    worker,
+                    worker,
+                    seal_proof.registered_window_post_proof().unwrap(),
+                    &TokenAmount::from_whole(20_000),
+                );
+                market_add_balance(v, worker, &maddr, &TokenAmount::from_whole(10_000));
+                WorkloadMiner {
+                    worker: *worker,
+                    maddr,
+                    next_sector: 0,
+                    precommits: vec![],
+                    pending_deals: vec![],
+                    storm_until: None,
+                }
+            })
+            .collect();
+        for client in clients {
+            market_add_balance(v, client, client, &TokenAmount::from_whole(10_000));
+        }
+        Workload {
+            v,
+            config,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            policy: Policy::default(),
+            seal_proof,
+            miners,
+            clients: clients.to_vec(),
+            next_label: 0,
+            stats: WorkloadStats::default(),
+        }
+    }
+
+    pub fn miners(&self) -> Vec<Address> {
+        self.miners.iter().map(|m| m.maddr).collect()
+    }
+
+    pub fn stats(&self) -> &WorkloadStats {
+        &self.stats
+    }
+
+    /// Runs a number of steps.
+    pub fn run(&mut self, steps: u64) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Performs one challenge window's worth of activity, then advances to the next window
+    /// running cron at every epoch.
+    pub fn step(&mut self) {
+        let epoch = self.v.epoch();
+        for m in self.miners.iter_mut() {
+            if m.storm_until.is_some_and(|until| until <= epoch) {
+                m.storm_until = None;
+            }
+        }
+        if self.rng.gen_bool(self.config.fault_storm_probability) {
+            self.start_fault_storm();
+        }
+        for i in 0..self.miners.len() {
+            self.publish_deals(i);
+            if self.rng.gen_bool(self.config.onboard_probability) {
+                self.precommit(i);
+            }
+            self.prove_ready(i);
+            if self.miners[i].storm_until.is_none() {
+                self.declare_recoveries(i);
+                self.submit_posts(i);
+            }
+        }
+        advance_to_epoch_with_cron(self.v, epoch + self.policy.wpost_challenge_window);
+        self.stats.steps += 1;
+    }
+
+    fn start_fault_storm(&mut self) {
+        let until = self.v.epoch()
+            + self.rng.gen_range(1..=self.config.max_storm_steps)
+                * self.policy.wpost_challenge_window;
+        let first = self.rng.gen_range(0..self.miners.len());
+        for (i, m) in self.miners.iter_mut().enumerate() {
+            if i == first || self.rng.gen_bool(0.5) {
+                m.storm_until = Some(until);
+            }
+        }
+        self.stats.fault_storms += 1;
+    }
+
+    fn publish_deals(&mut self, i: usize) {
+        let count = self.rng.gen_range(0..=self.config.max_deals_per_step);
+        if count == 0 {
+            return;
+        }
+        // Deals start late enough to be activated by a sector pre-committed in the next few
+        // steps, and are otherwise left to time out.
+        let deal_start = self.v.epoch()
+            + self.policy.pre_commit_challenge_delay
+            + self.rng.gen_range(3..=12) * self.policy.wpost_challenge_window;
+        let opts = DealOptions { deal_start, ..Default::default() };
+        let m = &self.miners[i];
+        let mut batcher = DealBatcher::new(self.v, opts);
+        for _ in 0..count {
+            let client = self.clients[self.rng.gen_range(0..self.clients.len())];
+            batcher.stage_with_label(client, m.maddr, format!("workload-{}", self.next_label));
+            self.next_label += 1;
+        }
+        let ids = batcher.publish_ok(m.worker).ids;
+        self.stats.deals_published += ids.len() as u64;
+        self.miners[i].pending_deals.extend(ids.into_iter().map(|id| (id, deal_start)));
+    }
+
+    fn precommit(&mut self, i: usize) {
+        let v = self.v;
+        let count = self.rng.gen_range(1..=self.config.max_sectors_per_batch);
+        let ready = v.epoch() + self.policy.pre_commit_challenge_delay + 1;
+        // A sector is proven at the first step after it's ready, and its deals must start later.
+        let latest_activation = ready + self.policy.wpost_challenge_window;
+        let m = &mut self.miners[i];
+        m.pending_deals.retain(|(_, start)| *start > v.epoch());
+        let mut metadata = vec![];
+        let mut sector_deals = vec![];
+        for _ in 0..count {
+            let pos = m.pending_deals.iter().position(|(_, start)| *start > latest_activation);
+            let deals = match pos {
+                Some(pos) => vec![m.pending_deals.swap_remove(pos).0],
+                None => vec![],
+            };
+            metadata.push(if deals.is_empty() {
+                PrecommitMetadata::default()
+            } else {
+                precommit_meta_data_from_deals(v, &deals, self.seal_proof, false)
+            });
+            sector_deals.push(deals);
+        }
+        let st: MinerState = get_state(v, &m.maddr).unwrap();
+        let first = m.next_sector;
+        precommit_sectors_v2(
+            v,
+            count,
+            count,
+            metadata,
+            &m.worker,
+            &m.maddr,
+            self.seal_proof,
+            first,
+            !st.deadline_cron_active,
+            None,
+        );
+        for (j, deals) in sector_deals.into_iter().enumerate() {
+            m.precommits.push((first + j as u64, deals, ready));
+        }
+        m.next_sector += count as u64;
+        self.stats.sectors_precommitted += count as u64;
+    }
+
+    fn prove_ready(&mut self, i: usize) {
+        let v = self.v;
+        let m = &mut self.miners[i];
+        let (ready, waiting) = m.precommits.drain(..).partition(|(_, _, at)| *at <= v.epoch());
+        m.precommits = waiting;
+        for (sector, deals, _) in ready {
+            self.stats.deals_activated += deals.len() as u64;
+            let manifests = make_piece_manifests_from_deal_ids(v, deals);
+            miner_prove_sector(v, &m.worker, &m.maddr, sector, manifests);
+            self.stats.sectors_proven += 1;
+        }
+    }
+
+    /// Declares recovery of the faults in a deadline far enough ahead that its fault
+    /// declaration cutoff hasn't passed.
+    fn declare_recoveries(&mut self, i: usize) {
+        let v = self.v;
+        let m = &self.miners[i];
+        let dline = miner_dline_info(v, &m.maddr);
+        let deadline = (dline.index + 3) % self.policy.wpost_period_deadlines;
+        let mut recoveries = vec![];
+        deadline_state(v, &m.maddr, deadline)
+            .partitions_amt(&DynBlockstore::wrap(v.blockstore()))
+            .unwrap()
+            .for_each(|partition, p| {
+                let sectors = &p.faults - &p.recoveries;
+                if !sectors.is_empty() {
+                    recoveries.push(RecoveryDeclaration { deadline, partition, sectors });
+                }
+                Ok(())
+            })
+            .unwrap();
+        if recoveries.is_empty() {
+            return;
+        }
+        self.stats.recoveries_declared += recoveries.len() as u64;
+        apply_ok(
+            v,
+            &m.worker,
+            &m.maddr,
+            &TokenAmount::zero(),
+            MinerMethod::DeclareFaultsRecovered as u64,
+            Some(DeclareFaultsRecoveredParams { recoveries }),
+        );
+    }
+
+    /// Submits a WindowPoSt for each partition of the current deadline with sectors to prove.
+    fn submit_posts(&mut self, i: usize) {
+        let v = self.v;
+        let m = &self.miners[i];
+        let dline = miner_dline_info(v, &m.maddr);
+        let mut partitions = vec![];
+        deadline_state(v, &m.maddr, dline.index)
+            .partitions_amt(&DynBlockstore::wrap(v.blockstore()))
+            .unwrap()
+            .for_each(|partition, p| {
+                let live = &p.sectors - &p.terminated;
+                let unrecovered: BitField = &p.faults - &p.recoveries;
+                if !(&live - &unrecovered).is_empty() {
+                    partitions.push(partition);
+                }
+                Ok(())
+            })
+            .unwrap();
+        for partition in partitions {
+            submit_windowed_post(v, &m.worker, &m.maddr, dline, partition, None);
+            self.stats.posts_submitted += 1;
+        }
+    }
+}
@@ -13,7 +13,17 @@ static NETWORKS: &[(&str, &[&str])] = &[
     ),
     ("butterflynet", &["sector-512m", "sector-32g", "sector-64g", "min-power-2g"]),
     ("calibrationnet", &["sector-32g", "sector-64g", "min-power-32g"]),
-    ("devnet", &["sector-2k", "sector-8m", "small-deals", "short-precommit", "min-power-2k"]),
+    (
+        "devnet",
+        &[
+            "sector-2k",
+            "sector-8m",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+            "account-auth-delegation",
+        ],
+    ),
     (
         "testing",
         &[
@@ -26,6 +36,7 @@ static NETWORKS: &[(&str, &[&str])] = &[
             "short-precommit",
             "min-power-2k",
             "no-provider-deal-collateral",
+            "account-auth-delegation",
         ],
     ),
     (
@@ -40,6 +51,7 @@ static NETWORKS: &[(&str, &[&str])] = &[
             "short-precommit",
             "min-power-2k",
             "no-provider-deal-collateral",
+            "account-auth-delegation",
             "fake-proofs",
         ],
     ),
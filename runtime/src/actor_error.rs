@@ -88,6 +88,17 @@ impl ActorError {
         &self.msg
     }
 
+    /// Optional data associated with the error, such as EVM revert data.
+    pub fn data(&self) -> Option<&IpldBlock> {
+        self.data.as_ref()
+    }
+
+    /// Attaches data to the error, replacing any existing data.
+    pub fn with_data(mut self, data: Option<IpldBlock>) -> Self {
+        self.data = data;
+        self
+    }
+
     /// Extracts the optional associated data without copying.
     pub fn take_data(&mut self) -> Option<IpldBlock> {
         std::mem::take(&mut self.data)
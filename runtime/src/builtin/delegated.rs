@@ -0,0 +1,72 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use fvm_shared::address::{Address, Payload};
+use fvm_shared::ActorID;
+
+use crate::{actor_error, ActorError};
+
+/// The sub-address of an f4 address in the namespace of actor `NS`, where that actor assigns
+/// sub-addresses of exactly `N` bytes.
+/// `N` must be no more than the maximum sub-address length for the conversion to [`Address`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DelegatedAddress<const NS: ActorID, const N: usize>(pub [u8; N]);
+
+impl<const NS: ActorID, const N: usize> DelegatedAddress<NS, N> {
+    /// The actor whose namespace the sub-address is in.
+    pub const NAMESPACE: ActorID = NS;
+
+    /// Returns the sub-address of `addr` if it's an f4 address in this namespace, None if it's
+    /// any other kind of address or in another namespace, and an error if it's in this namespace
+    /// but of the wrong length.
+    pub fn from_address(addr: &Address) -> Result<Option<Self>, ActorError> {
+        match addr.payload() {
+            Payload::Delegated(d) if d.namespace() == NS => {
+                let subaddress = d.subaddress().try_into().map_err(|_| {
+                    actor_error!(
+                        illegal_argument,
+                        "sub-address of {} must be {} bytes, was {}",
+                        addr,
+                        N,
+                        d.subaddress().len()
+                    )
+                })?;
+                Ok(Some(Self(subaddress)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<const NS: ActorID, const N: usize> TryFrom<&Address> for DelegatedAddress<NS, N> {
+    type Error = ActorError;
+
+    fn try_from(addr: &Address) -> Result<Self, Self::Error> {
+        Self::from_address(addr)?.ok_or_else(|| {
+            actor_error!(illegal_argument, "{} is not an f4 address in namespace {}", addr, NS)
+        })
+    }
+}
+
+impl<const NS: ActorID, const N: usize> From<DelegatedAddress<NS, N>> for Address {
+    fn from(addr: DelegatedAddress<NS, N>) -> Self {
+        Address::new_delegated(NS, &addr.0).unwrap()
+    }
+}
+
+impl<const NS: ActorID, const N: usize> AsRef<[u8]> for DelegatedAddress<NS, N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const NS: ActorID, const N: usize> Display for DelegatedAddress<NS, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&Address::from(*self), f)
+    }
+}
+
+impl<const NS: ActorID, const N: usize> Debug for DelegatedAddress<NS, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
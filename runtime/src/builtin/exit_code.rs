@@ -0,0 +1,83 @@
+use fvm_shared::error::ExitCode;
+
+use crate::{ActorDowncast, ActorError};
+
+/// The first exit code for errors specific to one actor.
+/// User codes below this are common to all actors, and defined by [`ExitCode`].
+pub const FIRST_ACTOR_SPECIFIC_EXIT_CODE: u32 = 32;
+
+/// Returns an actor-specific exit code, for defining an actor's own errors.
+/// Panics, at compile time where used in a constant, if the code is in the range reserved
+/// for system or common user errors.
+pub const fn actor_exit_code(code: u32) -> ExitCode {
+    assert!(code >= FIRST_ACTOR_SPECIFIC_EXIT_CODE, "exit code is reserved");
+    ExitCode::new(code)
+}
+
+/// The ranges into which exit codes are divided.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitCodeRange {
+    Success,
+    /// Codes set by the VM, which an actor can't exit with.
+    System,
+    /// Codes common to all actors, defined by [`ExitCode`].
+    User,
+    /// Codes whose meaning is defined by the actor which exited with them.
+    ActorSpecific,
+}
+
+impl ExitCodeRange {
+    pub fn of(code: ExitCode) -> Self {
+        match code.value() {
+            0 => ExitCodeRange::Success,
+            v if v < ExitCode::FIRST_USER_EXIT_CODE => ExitCodeRange::System,
+            v if v < FIRST_ACTOR_SPECIFIC_EXIT_CODE => ExitCodeRange::User,
+            _ => ExitCodeRange::ActorSpecific,
+        }
+    }
+}
+
+/// Converts a HAMT error into an illegal state error, as for the [`Map2`](crate::Map2) wrapper,
+/// unless it wraps an actor error, whose exit code is kept.
+impl From<fvm_ipld_hamt::Error> for ActorError {
+    fn from(e: fvm_ipld_hamt::Error) -> Self {
+        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "HAMT error")
+    }
+}
+
+/// Converts an AMT error into an illegal state error, as for the [`Array2`](crate::Array2)
+/// wrapper, unless it wraps an actor error, whose exit code is kept.
+impl From<fvm_ipld_amt::Error> for ActorError {
+    fn from(e: fvm_ipld_amt::Error) -> Self {
+        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "AMT error")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_exit_codes() {
+        assert_eq!(ExitCodeRange::Success, ExitCodeRange::of(ExitCode::OK));
+        assert_eq!(ExitCodeRange::System, ExitCodeRange::of(ExitCode::SYS_OUT_OF_GAS));
+        assert_eq!(ExitCodeRange::User, ExitCodeRange::of(ExitCode::USR_ILLEGAL_ARGUMENT));
+        assert_eq!(ExitCodeRange::User, ExitCodeRange::of(ExitCode::new(31)));
+        assert_eq!(ExitCodeRange::ActorSpecific, ExitCodeRange::of(actor_exit_code(32)));
+        assert_eq!(ExitCodeRange::ActorSpecific, ExitCodeRange::of(actor_exit_code(1000)));
+    }
+
+    #[test]
+    fn collection_errors_keep_actor_exit_codes() {
+        let wrapped = fvm_ipld_hamt::Error::Dynamic(ActorError::forbidden("no".into()).into());
+        assert_eq!(ExitCode::USR_FORBIDDEN, ActorError::from(wrapped).exit_code());
+        let other = fvm_ipld_amt::Error::OutOfRange(1);
+        assert_eq!(ExitCode::USR_ILLEGAL_STATE, ActorError::from(other).exit_code());
+    }
+
+    #[test]
+    #[should_panic(expected = "exit code is reserved")]
+    fn common_codes_are_reserved() {
+        actor_exit_code(ExitCode::USR_FORBIDDEN.value());
+    }
+}
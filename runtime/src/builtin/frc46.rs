@@ -0,0 +1,53 @@
+// Copyright 2021-2023 Protocol Labs
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Canonical types for FRC-46 token transfers and the universal receiver hook.
+//!
+//! Actors that send or receive tokens (such as the datacap token) should use these types
+//! rather than depending on the token libraries directly, so that all built-in actors agree
+//! on the wire format.
+//!
+//! By convention, the `operator_data` of a transfer is a CBOR-encoded message interpreted by
+//! the receiving actor, e.g. the allocation requests accompanying a datacap transfer to the
+//! verified registry. Senders that don't address the receiver leave it empty.
+
+use fvm_shared::ActorID;
+
+pub use frc46_token::receiver::{FRC46TokenReceived, FRC46_TOKEN_TYPE};
+pub use frc46_token::token::types::{
+    AllowanceReturn, BalanceReturn, BurnFromParams, BurnFromReturn, BurnParams, BurnReturn,
+    DecreaseAllowanceParams, GetAllowanceParams, IncreaseAllowanceParams, MintReturn,
+    RevokeAllowanceParams, TransferFromParams, TransferFromReturn, TransferParams, TransferReturn,
+};
+pub use frc46_token::token::TOKEN_PRECISION;
+pub use fvm_actor_utils::receiver::UniversalReceiverParams;
+
+use crate::cbor::deserialize;
+use crate::{actor_error, ActorError};
+
+/// Deserializes and validates a receiver hook payload, expecting only an FRC-46 transfer
+/// to the receiving actor.
+pub fn validate_tokens_received(
+    params: &UniversalReceiverParams,
+    my_id: ActorID,
+) -> Result<FRC46TokenReceived, ActorError> {
+    if params.type_ != FRC46_TOKEN_TYPE {
+        return Err(actor_error!(
+            illegal_argument,
+            "invalid token type {}, expected {} (FRC-46)",
+            params.type_,
+            FRC46_TOKEN_TYPE
+        ));
+    }
+    let payload: FRC46TokenReceived = deserialize(&params.payload, "receiver hook payload")?;
+    // Payload to address must match receiving actor.
+    if payload.to != my_id {
+        return Err(actor_error!(
+            illegal_argument,
+            "token receiver expected to {}, was {}",
+            my_id,
+            payload.to
+        ));
+    }
+    Ok(payload)
+}
@@ -1,10 +1,15 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+pub use self::delegated::*;
+pub use self::exit_code::*;
 pub use self::network::*;
 pub use self::shared::*;
 pub use self::singletons::*;
 
+pub mod delegated;
+pub mod exit_code;
+pub mod frc46;
 pub mod network;
 pub mod reward;
 pub mod shared;
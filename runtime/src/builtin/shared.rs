@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use crate::runtime::builtins::Type;
-use crate::{actor_error, ActorContext, ActorError};
+use crate::runtime::Primitives;
+use crate::{actor_error, ActorContext, ActorError, INIT_ACTOR_ID};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
-use fvm_shared::address::Address;
+use fvm_shared::address::{Address, Payload};
 use fvm_shared::METHOD_SEND;
 use fvm_shared::{ActorID, MethodNum};
 use std::fmt::{Display, Formatter};
@@ -13,7 +14,41 @@ use crate::runtime::Runtime;
 
 pub const HAMT_BIT_WIDTH: u32 = 5;
 
-pub const FIRST_ACTOR_SPECIFIC_EXIT_CODE: u32 = 32;
+/// Length of the hash prefixing the nonce in the sub-address of a payment channel's f4 address.
+/// As for EAM addresses, 20 bytes make finding parties which collide with another channel's
+/// address infeasible.
+const PAYMENT_CHANNEL_HASH_LEN: usize = 20;
+
+/// Returns the f4 address, in the init actor's namespace, of a payment channel between two
+/// parties with a nonce chosen by the payer. Knowing the parties and nonce, the payee can
+/// address the channel before it is created.
+/// The sub-address is a prefix of the Blake2b-256 hash of the CBOR tuple `(from, to, nonce)`,
+/// followed by the big-endian nonce, so the channel can check its address against its parties.
+pub fn payment_channel_address(
+    prim: &(impl Primitives + ?Sized),
+    from: &Address,
+    to: &Address,
+    nonce: u64,
+) -> Result<Address, ActorError> {
+    let preimage = fvm_ipld_encoding::to_vec(&(from, to, nonce))
+        .map_err(|e| actor_error!(serialization; "failed to serialize channel parties: {}", e))?;
+    let mut subaddress = prim.hash_blake2b(&preimage)[..PAYMENT_CHANNEL_HASH_LEN].to_vec();
+    subaddress.extend_from_slice(&nonce.to_be_bytes());
+    Address::new_delegated(INIT_ACTOR_ID, &subaddress)
+        .map_err(|e| actor_error!(illegal_argument; "invalid payment channel address: {}", e))
+}
+
+/// Returns the nonce of an address of the form returned by [`payment_channel_address`],
+/// or None if the address isn't of that form.
+pub fn payment_channel_nonce(address: &Address) -> Option<u64> {
+    match address.payload() {
+        Payload::Delegated(d) if d.namespace() == INIT_ACTOR_ID => {
+            let nonce = d.subaddress().get(PAYMENT_CHANNEL_HASH_LEN..)?;
+            Some(u64::from_be_bytes(nonce.try_into().ok()?))
+        }
+        _ => None,
+    }
+}
 
 /// ResolveToActorID resolves the given address to its actor ID.
 /// If an actor ID for the given address doesn't exist yet, it tries to create one by sending
@@ -4,8 +4,13 @@ use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::version::NetworkVersion;
+use fvm_shared::MethodNum;
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::builtin::shared::FIRST_EXPORTED_METHOD_NUMBER;
+use crate::cbor::deserialize_strict;
+use crate::runtime::Runtime;
 use crate::ActorError;
 
 pub struct WithCodec<T, const CODEC: u64>(pub T);
@@ -66,6 +71,37 @@ macro_rules! actor_dispatch {
                 None => Err(actor_error!(unhandled_message; "invalid method: {}", method)),
             }
         }
+
+        $crate::actor_dispatch!(@methods $($(#[$m])* [$($method)*] $func $([$tag])?,)*);
+    };
+    (@methods $($(#[$m:meta])* [$($method:ident)*] $func:ident $([$tag:ident])?,)*) => {
+        fn methods<RT>() -> Vec<$crate::MethodDescriptor>
+        where
+            RT: $crate::runtime::Runtime,
+            RT::Blockstore: Clone,
+        {
+            #[allow(unused_mut)]
+            let mut methods = Vec::new();
+            $($(#[$m])*
+              $crate::actor_dispatch!(@describe RT methods [$($method)*] $func $($tag)?);)*
+            methods
+        }
+    };
+    (@describe $rt:ident $methods:ident [$($method:ident)*] $func:ident default_params) => {
+        $($methods.push($crate::describe_default::<$rt, _, _, _>(
+            stringify!($method),
+            Self::Methods::$method as u64,
+            stringify!($func),
+            Self::$func,
+        ));)*
+    };
+    (@describe $rt:ident $methods:ident [$($method:ident)*] $func:ident $(raw)?) => {
+        $($methods.push($crate::describe::<$rt, _, _>(
+            stringify!($method),
+            Self::Methods::$method as u64,
+            stringify!($func),
+            Self::$func,
+        ));)*
     };
     (@pattern) => {
         None
@@ -100,6 +136,8 @@ macro_rules! actor_dispatch_unrestricted {
                 None => Err(actor_error!(unhandled_message; "invalid method: {}", method)),
             }
         }
+
+        $crate::actor_dispatch!(@methods $($(#[$m])* [$($method)*] $func $([$tag])?,)*);
     };
     (@pattern) => {
         None
@@ -168,12 +206,125 @@ where
     F: FnOnce(&RT, A) -> Result<R, ActorError>,
     A: DeserializeOwned + Default,
     R: Serialize,
+    RT: Runtime,
 {
-    let arg = arg.as_ref().map(|b| b.deserialize()).transpose()?.unwrap_or_default();
+    let arg = arg.as_ref().map(|arg| deserialize_arg(rt, arg)).transpose()?.unwrap_or_default();
     // TODO: make this codec configurable
     maybe_into_block((func)(rt, arg)?, CBOR)
 }
 
+/// Describes a parameter or return value of an actor method.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ValueDescriptor {
+    /// The Rust type of the value, whose serialization defines its encoding.
+    pub type_name: &'static str,
+    /// The IPLD codec of the encoded value.
+    pub codec: u64,
+}
+
+impl ValueDescriptor {
+    /// Describes a value of type `T`, or returns `None` if `T` is `()` and so isn't encoded.
+    fn of<T>(codec: u64) -> Option<Self> {
+        let type_name = std::any::type_name::<T>();
+        (type_name != "()").then_some(ValueDescriptor { type_name, codec })
+    }
+}
+
+/// Describes an actor method, as dispatched by the actor, so that its messages can be decoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MethodDescriptor {
+    pub name: &'static str,
+    pub number: MethodNum,
+    /// Whether the method is exported to all callers, per FRC-0042.
+    pub exported: bool,
+    /// The name of the actor's function handling the method.
+    pub handler: &'static str,
+    /// The method's parameters, or `None` if it takes none.
+    pub params: Option<ValueDescriptor>,
+    /// Whether the parameters may be omitted, taking their default value.
+    pub params_optional: bool,
+    /// The method's return value, or `None` if it returns nothing.
+    pub returns: Option<ValueDescriptor>,
+    /// Whether the handler decodes its own parameters and encodes its own return value,
+    /// which are then not described.
+    pub raw: bool,
+}
+
+impl MethodDescriptor {
+    fn new(name: &'static str, number: MethodNum, handler: &'static str) -> Self {
+        MethodDescriptor {
+            name,
+            number,
+            exported: number >= FIRST_EXPORTED_METHOD_NUMBER,
+            handler,
+            params: None,
+            params_optional: false,
+            returns: None,
+            raw: false,
+        }
+    }
+}
+
+/// Describes the parameters and return value of the method dispatched by a [`Dispatcher`].
+pub trait Describe<RT> {
+    fn describe(method: &mut MethodDescriptor);
+}
+
+/// Describe a method dispatched with [`dispatch`].
+#[doc(hidden)]
+pub fn describe<RT, F, A>(
+    name: &'static str,
+    number: MethodNum,
+    handler: &'static str,
+    _func: F,
+) -> MethodDescriptor
+where
+    Dispatcher<F, A>: Describe<RT>,
+{
+    let mut method = MethodDescriptor::new(name, number, handler);
+    Dispatcher::<F, A>::describe(&mut method);
+    method
+}
+
+/// Describe a method dispatched with [`dispatch_default`].
+#[doc(hidden)]
+pub fn describe_default<RT, F, A, R>(
+    name: &'static str,
+    number: MethodNum,
+    handler: &'static str,
+    _func: F,
+) -> MethodDescriptor
+where
+    F: FnOnce(&RT, A) -> Result<R, ActorError>,
+    A: DeserializeOwned + Default,
+    R: Serialize,
+{
+    let mut method = MethodDescriptor::new(name, number, handler);
+    method.params = ValueDescriptor::of::<A>(CBOR);
+    method.params_optional = true;
+    method.returns = ValueDescriptor::of::<R>(CBOR);
+    method
+}
+
+/// The network version from which method parameters encoded as CBOR or DAG-CBOR must be
+/// canonical (see [`deserialize_strict`]).
+/// Earlier versions accept any encoding that decodes. Rejecting the others changes which messages
+/// succeed, so the check takes effect only with the network upgrade adopting it.
+pub const CANONICAL_PARAMS_NETWORK_VERSION: NetworkVersion = NetworkVersion::new(26);
+
+/// Deserializes method parameters, requiring a canonical encoding once the network has reached
+/// [`CANONICAL_PARAMS_NETWORK_VERSION`].
+fn deserialize_arg<A: DeserializeOwned>(
+    rt: &impl Runtime,
+    arg: &IpldBlock,
+) -> Result<A, ActorError> {
+    if rt.network_version() >= CANONICAL_PARAMS_NETWORK_VERSION {
+        deserialize_strict(arg)
+    } else {
+        Ok(arg.deserialize()?)
+    }
+}
+
 /// Convert the passed value into an IPLD Block, or None if it's `()`.
 fn maybe_into_block<T: Serialize>(v: T, codec: u64) -> Result<Option<IpldBlock>, ActorError> {
     if cast!(&v, &()).is_ok() {
@@ -238,6 +389,7 @@ where
     F: FnOnce(&RT, A) -> Result<R, ActorError>,
     A: DeserializeOwned,
     R: Serialize,
+    RT: Runtime,
 {
     fn call(
         self,
@@ -247,7 +399,7 @@ where
     ) -> Result<Option<IpldBlock>, ActorError> {
         match args {
             None => Err(ActorError::illegal_argument("method expects arguments".into())),
-            Some(arg) => maybe_into_block((self.func)(rt, arg.deserialize()?)?, CBOR),
+            Some(arg) => maybe_into_block((self.func)(rt, deserialize_arg(rt, &arg)?)?, CBOR),
         }
     }
 }
@@ -257,6 +409,7 @@ where
     F: FnOnce(&RT, WithCodec<A, CODEC>) -> Result<R, ActorError>,
     A: DeserializeOwned,
     R: Serialize,
+    RT: Runtime,
 {
     fn call(
         self,
@@ -270,7 +423,9 @@ where
                 "method expects parameters with codec {}, got codec {}",
                 CODEC, arg.codec,
             ))),
-            Some(arg) => maybe_into_block((self.func)(rt, WithCodec(arg.deserialize()?))?, CBOR),
+            Some(arg) => {
+                maybe_into_block((self.func)(rt, WithCodec(deserialize_arg(rt, &arg)?))?, CBOR)
+            }
         }
     }
 }
@@ -280,6 +435,7 @@ where
     F: FnOnce(&RT, A) -> Result<WithCodec<R, CODEC>, ActorError>,
     A: DeserializeOwned,
     R: Serialize,
+    RT: Runtime,
 {
     fn call(
         self,
@@ -289,7 +445,7 @@ where
     ) -> Result<Option<IpldBlock>, ActorError> {
         match args {
             None => Err(ActorError::illegal_argument("method expects arguments".into())),
-            Some(arg) => maybe_into_block((self.func)(rt, arg.deserialize()?)?.0, CODEC),
+            Some(arg) => maybe_into_block((self.func)(rt, deserialize_arg(rt, &arg)?)?.0, CODEC),
         }
     }
 }
@@ -300,6 +456,7 @@ where
     F: FnOnce(&RT, WithCodec<A, A_CODEC>) -> Result<WithCodec<R, R_CODEC>, ActorError>,
     A: DeserializeOwned,
     R: Serialize,
+    RT: Runtime,
 {
     fn call(
         self,
@@ -314,14 +471,93 @@ where
                 A_CODEC, arg.codec,
             ))),
             Some(arg) => {
-                maybe_into_block((self.func)(rt, WithCodec(arg.deserialize()?))?.0, R_CODEC)
+                maybe_into_block((self.func)(rt, WithCodec(deserialize_arg(rt, &arg)?))?.0, R_CODEC)
             }
         }
     }
 }
 
+impl<F, RT> Describe<RT> for Dispatcher<F, ()>
+where
+    F: FnOnce(&RT, u64, Option<IpldBlock>) -> Result<Option<IpldBlock>, ActorError>,
+{
+    fn describe(method: &mut MethodDescriptor) {
+        method.raw = true;
+    }
+}
+
+impl<F, R, RT> Describe<RT> for Dispatcher<F, (R,)>
+where
+    F: FnOnce(&RT) -> Result<R, ActorError>,
+    R: Serialize,
+{
+    fn describe(method: &mut MethodDescriptor) {
+        method.returns = ValueDescriptor::of::<R>(CBOR);
+    }
+}
+
+impl<F, R, RT, const CODEC: u64> Describe<RT> for Dispatcher<F, (WithCodec<R, CODEC>,)>
+where
+    F: FnOnce(&RT) -> Result<WithCodec<R, CODEC>, ActorError>,
+    R: Serialize,
+{
+    fn describe(method: &mut MethodDescriptor) {
+        method.returns = ValueDescriptor::of::<R>(CODEC);
+    }
+}
+
+impl<F, A, R, RT> Describe<RT> for Dispatcher<F, (A, R)>
+where
+    F: FnOnce(&RT, A) -> Result<R, ActorError>,
+    A: DeserializeOwned,
+    R: Serialize,
+{
+    fn describe(method: &mut MethodDescriptor) {
+        method.params = ValueDescriptor::of::<A>(CBOR);
+        method.returns = ValueDescriptor::of::<R>(CBOR);
+    }
+}
+
+impl<F, A, R, RT, const CODEC: u64> Describe<RT> for Dispatcher<F, (WithCodec<A, CODEC>, R)>
+where
+    F: FnOnce(&RT, WithCodec<A, CODEC>) -> Result<R, ActorError>,
+    A: DeserializeOwned,
+    R: Serialize,
+{
+    fn describe(method: &mut MethodDescriptor) {
+        method.params = ValueDescriptor::of::<A>(CODEC);
+        method.returns = ValueDescriptor::of::<R>(CBOR);
+    }
+}
+
+impl<F, A, R, RT, const CODEC: u64> Describe<RT> for Dispatcher<F, (A, WithCodec<R, CODEC>)>
+where
+    F: FnOnce(&RT, A) -> Result<WithCodec<R, CODEC>, ActorError>,
+    A: DeserializeOwned,
+    R: Serialize,
+{
+    fn describe(method: &mut MethodDescriptor) {
+        method.params = ValueDescriptor::of::<A>(CBOR);
+        method.returns = ValueDescriptor::of::<R>(CODEC);
+    }
+}
+
+impl<F, A, R, RT, const A_CODEC: u64, const R_CODEC: u64> Describe<RT>
+    for Dispatcher<F, (WithCodec<A, A_CODEC>, WithCodec<R, R_CODEC>)>
+where
+    F: FnOnce(&RT, WithCodec<A, A_CODEC>) -> Result<WithCodec<R, R_CODEC>, ActorError>,
+    A: DeserializeOwned,
+    R: Serialize,
+{
+    fn describe(method: &mut MethodDescriptor) {
+        method.params = ValueDescriptor::of::<A>(A_CODEC);
+        method.returns = ValueDescriptor::of::<R>(R_CODEC);
+    }
+}
+
 #[test]
 fn test_dispatch() {
+    use crate::test_utils::MockRuntime;
     use crate::ActorError;
     use fvm_ipld_encoding::ipld_block::IpldBlock;
     use fvm_ipld_encoding::DAG_CBOR;
@@ -332,10 +568,6 @@ fn test_dispatch() {
         foo: String,
     }
 
-    trait Runtime {}
-    struct MockRuntime;
-    impl Runtime for MockRuntime {}
-
     fn with_arg(_: &impl Runtime, foo: SomeArgs) -> Result<(), ActorError> {
         assert_eq!(foo.foo, "foo");
         Ok(())
@@ -378,7 +610,7 @@ fn test_dispatch() {
         Ok(args)
     }
 
-    let rt = MockRuntime;
+    let mut rt = MockRuntime::new();
     let arg = IpldBlock::serialize_cbor(&SomeArgs { foo: "foo".into() })
         .expect("failed to serialize arguments");
     let arg_dag = IpldBlock::serialize_dag_cbor(&SomeArgs { foo: "foo".into() })
@@ -403,4 +635,64 @@ fn test_dispatch() {
         .expect_err("should fail because we specified the wrong codec");
     let _ = dispatch(&rt, 1, with_arg, None).expect_err("should have required an argument");
     let _ = dispatch(&rt, 1, without_arg, arg).expect_err("should have required an argument");
+    let non_canonical = IpldBlock {
+        codec: CBOR,
+        data: vec![0xa1, 0x63, b'f', b'o', b'o', 0x78, 0x03, b'f', b'o', b'o'],
+    };
+    dispatch(&rt, 1, with_arg, Some(non_canonical.clone()))
+        .expect("should have accepted a non-minimal string length before the upgrade");
+    rt.network_version = CANONICAL_PARAMS_NETWORK_VERSION;
+    let _ = dispatch(&rt, 1, with_arg, Some(non_canonical))
+        .expect_err("should have rejected a non-minimal string length");
+}
+
+#[test]
+fn test_describe() {
+    use crate::ActorError;
+    use fvm_ipld_encoding::ipld_block::IpldBlock;
+    use fvm_ipld_encoding::DAG_CBOR;
+
+    struct MockRuntime;
+
+    fn with_arg_ret(_: &MockRuntime, foo: String) -> Result<u64, ActorError> {
+        Ok(foo.len() as u64)
+    }
+
+    fn without_arg(_: &MockRuntime) -> Result<(), ActorError> {
+        Ok(())
+    }
+
+    fn raw(
+        _: &MockRuntime,
+        _: u64,
+        args: Option<IpldBlock>,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        Ok(args)
+    }
+
+    fn codec_out(_: &MockRuntime, foo: String) -> Result<WithCodec<String, DAG_CBOR>, ActorError> {
+        Ok(foo.into())
+    }
+
+    let method = describe::<MockRuntime, _, _>("WithArgRet", 2, "with_arg_ret", with_arg_ret);
+    assert_eq!(
+        Some(ValueDescriptor { type_name: "alloc::string::String", codec: CBOR }),
+        method.params
+    );
+    assert_eq!(Some(ValueDescriptor { type_name: "u64", codec: CBOR }), method.returns);
+    assert!(!method.exported && !method.raw && !method.params_optional);
+
+    let method = describe::<MockRuntime, _, _>("WithoutArg", 1 << 24, "without_arg", without_arg);
+    assert_eq!((None, None), (method.params, method.returns));
+    assert!(method.exported);
+
+    let method = describe::<MockRuntime, _, _>("Raw", 3, "raw", raw);
+    assert!(method.raw);
+
+    let method = describe::<MockRuntime, _, _>("CodecOut", 4, "codec_out", codec_out);
+    assert_eq!(DAG_CBOR, method.returns.unwrap().codec);
+
+    let method =
+        describe_default::<MockRuntime, _, _, _>("Default", 5, "with_arg_ret", with_arg_ret);
+    assert!(method.params_optional);
 }
@@ -12,7 +12,10 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use unsigned_varint::decode::Error as UVarintError;
 
-pub use dispatch::{dispatch, dispatch_default, WithCodec};
+pub use dispatch::{
+    describe, describe_default, dispatch, dispatch_default, MethodDescriptor, ValueDescriptor,
+    WithCodec, CANONICAL_PARAMS_NETWORK_VERSION,
+};
 pub use {fvm_ipld_amt, fvm_ipld_hamt};
 
 #[cfg(feature = "fil-actor")]
@@ -39,6 +42,11 @@ macro_rules! wasm_trampoline {
         pub extern "C" fn invoke(param: u32) -> u32 {
             $crate::runtime::fvm::trampoline::<$target>(param)
         }
+
+        #[no_mangle]
+        pub extern "C" fn upgrade(params: u32, upgrade_info: u32) -> u32 {
+            $crate::runtime::fvm::upgrade_trampoline::<$target>(params, upgrade_info)
+        }
     };
 }
 
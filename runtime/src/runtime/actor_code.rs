@@ -3,9 +3,10 @@
 
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::upgrade::UpgradeInfo;
 use fvm_shared::MethodNum;
 
-use crate::{ActorError, Runtime};
+use crate::{actor_error, ActorError, MethodDescriptor, Runtime};
 
 /// Interface for invoking methods on an Actor
 pub trait ActorCode {
@@ -25,4 +26,35 @@ pub trait ActorCode {
         // https://github.com/filecoin-project/builtin-actors/issues/133
         RT: Runtime,
         RT::Blockstore: Blockstore + Clone;
+
+    /// Describes the methods the actor dispatches, in dispatch order.
+    /// The runtime type serves only to resolve the methods' handlers.
+    fn methods<RT>() -> Vec<MethodDescriptor>
+    where
+        RT: Runtime,
+        RT::Blockstore: Clone,
+    {
+        Vec::new()
+    }
+
+    /// Invoked on the new code when an actor's code is replaced, to migrate the actor's state
+    /// from the schema of the old code, identified in `info`.
+    /// The VM only invokes this when the actor itself requests the upgrade, so there is no
+    /// caller to validate.
+    /// Returning an error rejects the upgrade, leaving the actor's code and state unchanged.
+    /// The default implementation rejects all upgrades.
+    fn upgrade<RT>(
+        rt: &RT,
+        info: UpgradeInfo,
+        params: Option<IpldBlock>,
+    ) -> Result<Option<IpldBlock>, ActorError>
+    where
+        RT: Runtime,
+        RT::Blockstore: Blockstore + Clone,
+    {
+        let _ = (rt, params);
+        Err(
+            actor_error!(forbidden; "{} actor does not support upgrade from {}", Self::name(), info.old_code_cid),
+        )
+    }
 }
@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use fvm_shared::address::{Address, Payload};
+use fvm_shared::ActorID;
+
+use super::Runtime;
+
+/// An address resolver which memoizes the actor IDs it resolves addresses to.
+///
+/// Batched methods often resolve the same robust address many times in one invocation,
+/// e.g. the client of each deal published together. The cache saves walking the Init actor's
+/// address table again for each.
+/// An actor's ID never changes once assigned, so a cached ID can never be stale.
+/// Failed resolutions are not cached, since a send to an address may create an actor for it.
+/// The cache is meant to live for one invocation.
+pub struct AddressResolver<'a, RT> {
+    rt: &'a RT,
+    cache: RefCell<HashMap<Address, ActorID>>,
+}
+
+impl<'a, RT: Runtime> AddressResolver<'a, RT> {
+    pub fn new(rt: &'a RT) -> Self {
+        Self { rt, cache: RefCell::new(HashMap::new()) }
+    }
+
+    /// Resolves an address to an actor ID, as [`Runtime::resolve_address`] does.
+    /// An ID address is returned directly.
+    pub fn resolve(&self, address: &Address) -> Option<ActorID> {
+        if let &Payload::ID(id) = address.payload() {
+            return Some(id);
+        }
+        if let Some(id) = self.cache.borrow().get(address) {
+            return Some(*id);
+        }
+        let id = self.rt.resolve_address(address)?;
+        self.cache.borrow_mut().insert(*address, id);
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fvm_shared::address::Address;
+
+    use super::AddressResolver;
+    use crate::runtime::Runtime;
+    use crate::test_utils::MockRuntime;
+
+    #[test]
+    fn caches_resolved_addresses() {
+        let rt = MockRuntime::default();
+        rt.in_call.replace(true);
+        let robust = Address::new_secp256k1(&[1; 65]).unwrap();
+        rt.add_id_address(robust, Address::new_id(101));
+
+        let resolver = AddressResolver::new(&rt);
+        assert_eq!(Some(101), resolver.resolve(&robust));
+
+        // The resolution is served from the cache once the runtime forgets it.
+        rt.id_addresses.borrow_mut().clear();
+        assert_eq!(Some(101), resolver.resolve(&robust));
+        assert_eq!(None, rt.resolve_address(&robust));
+    }
+
+    #[test]
+    fn does_not_cache_failed_resolutions() {
+        let rt = MockRuntime::default();
+        rt.in_call.replace(true);
+        let robust = Address::new_secp256k1(&[1; 65]).unwrap();
+
+        let resolver = AddressResolver::new(&rt);
+        assert_eq!(None, resolver.resolve(&robust));
+
+        rt.add_id_address(robust, Address::new_id(101));
+        assert_eq!(Some(101), resolver.resolve(&robust));
+        assert_eq!(Some(7), resolver.resolve(&Address::new_id(7)));
+    }
+}
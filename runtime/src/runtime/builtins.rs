@@ -1 +1,46 @@
 pub use vm_api::builtin::Type;
+
+/// Expands a macro with the registry of builtin actors implemented natively: for each,
+/// its [`Type`] variant and the path of the type implementing
+/// [`ActorCode`](crate::runtime::ActorCode) for it, as `Type => path,` items.
+/// The actor's code name is [`Type::name`].
+///
+/// The paths name the actor crates, which the invoking crate must depend on.
+/// The placeholder actor has no code, so is not listed; a `match` over [`Type`] built from
+/// the registry must handle it separately, and fails to compile if any other type is missing.
+///
+/// ```ignore
+/// macro_rules! actor_names {
+///     ($($typ:ident => $actor:path,)*) => {
+///         fn actor_name(t: Type) -> Option<String> {
+///             match t {
+///                 $(Type::$typ => Some(<$actor as ActorCode>::name().to_string()),)*
+///                 Type::Placeholder => None,
+///             }
+///         }
+///     };
+/// }
+/// builtin_actors!(actor_names);
+/// ```
+#[macro_export]
+macro_rules! builtin_actors {
+    ($callback:ident) => {
+        $callback! {
+            System => ::fil_actor_system::Actor,
+            Init => ::fil_actor_init::Actor,
+            Cron => ::fil_actor_cron::Actor,
+            Account => ::fil_actor_account::Actor,
+            Power => ::fil_actor_power::Actor,
+            Miner => ::fil_actor_miner::Actor,
+            Market => ::fil_actor_market::Actor,
+            PaymentChannel => ::fil_actor_paych::Actor,
+            Multisig => ::fil_actor_multisig::Actor,
+            Reward => ::fil_actor_reward::Actor,
+            VerifiedRegistry => ::fil_actor_verifreg::Actor,
+            DataCap => ::fil_actor_datacap::Actor,
+            EVM => ::fil_actor_evm::EvmContractActor,
+            EAM => ::fil_actor_eam::EamActor,
+            EthAccount => ::fil_actor_ethaccount::EthAccountActor,
+        }
+    };
+}
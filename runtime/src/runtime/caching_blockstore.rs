@@ -0,0 +1,135 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anyhow::Result;
+use cid::Cid;
+use fvm_ipld_blockstore::{Block, Blockstore};
+use multihash_codetable::Code;
+
+/// The most block data a [`CachingBlockstore`] holds, beyond which blocks are read through
+/// without being cached.
+pub const MAX_CACHED_BYTES: usize = 1 << 22;
+
+/// A blockstore which caches the blocks read from and written to another.
+///
+/// Actors often load the same state, and the same HAMT and AMT nodes, several times in one
+/// invocation. The cache saves reading each block again, which costs gas in the FVM.
+/// Blocks are addressed by content, so a cached block can never be stale, and writing a
+/// block merely adds it to the cache.
+/// The cache is meant to live for one invocation, and holds at most [`MAX_CACHED_BYTES`].
+///
+/// Cloning shares the cache, rather than copying it.
+#[derive(Debug, Clone)]
+pub struct CachingBlockstore<BS> {
+    inner: BS,
+    cache: Rc<RefCell<Cache>>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    blocks: HashMap<Cid, Vec<u8>>,
+    bytes: usize,
+}
+
+impl Cache {
+    fn insert(&mut self, cid: Cid, block: &[u8]) {
+        if self.bytes + block.len() <= MAX_CACHED_BYTES && !self.blocks.contains_key(&cid) {
+            self.bytes += block.len();
+            self.blocks.insert(cid, block.to_vec());
+        }
+    }
+}
+
+impl<BS: Blockstore> CachingBlockstore<BS> {
+    pub fn new(inner: BS) -> Self {
+        Self { inner, cache: Default::default() }
+    }
+
+    /// Returns the number of bytes of block data cached.
+    pub fn cached_bytes(&self) -> usize {
+        self.cache.borrow().bytes
+    }
+}
+
+impl<BS: Blockstore> Blockstore for CachingBlockstore<BS> {
+    fn get(&self, cid: &Cid) -> Result<Option<Vec<u8>>> {
+        if let Some(block) = self.cache.borrow().blocks.get(cid) {
+            return Ok(Some(block.clone()));
+        }
+        let block = self.inner.get(cid)?;
+        if let Some(block) = &block {
+            self.cache.borrow_mut().insert(*cid, block);
+        }
+        Ok(block)
+    }
+
+    fn has(&self, cid: &Cid) -> Result<bool> {
+        Ok(self.cache.borrow().blocks.contains_key(cid) || self.inner.has(cid)?)
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> Result<()> {
+        self.inner.put_keyed(k, block)?;
+        self.cache.borrow_mut().insert(*k, block);
+        Ok(())
+    }
+
+    fn put<D>(&self, code: Code, block: &Block<D>) -> Result<Cid>
+    where
+        D: AsRef<[u8]>,
+    {
+        // The inner store computes the CID, which may be cheaper than hashing here.
+        let k = self.inner.put(code, block)?;
+        self.cache.borrow_mut().insert(k, block.data.as_ref());
+        Ok(k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fvm_ipld_encoding::CborStore;
+
+    use super::*;
+    use crate::test_blockstores::{MemoryBlockstore, TrackingBlockstore};
+
+    #[test]
+    fn reads_are_cached() {
+        let tracking = TrackingBlockstore::new(MemoryBlockstore::new());
+        let store = CachingBlockstore::new(&tracking);
+        let cid = store.put_cbor(&"hello", Code::Blake2b256).unwrap();
+        assert_eq!(1, tracking.take_stats().w);
+
+        // A written block is read from the cache, as is a block read before.
+        assert_eq!(Some("hello".to_string()), store.get_cbor(&cid).unwrap());
+        assert_eq!(0, tracking.stats().r);
+
+        let other = tracking.put_cbor(&"world", Code::Blake2b256).unwrap();
+        let clone = store.clone();
+        for _ in 0..3 {
+            assert_eq!(Some("world".to_string()), clone.get_cbor(&other).unwrap());
+            assert_eq!(Some("world".to_string()), store.get_cbor(&other).unwrap());
+        }
+        assert_eq!(1, tracking.stats().r);
+    }
+
+    #[test]
+    fn cache_is_bounded() {
+        let tracking = TrackingBlockstore::new(MemoryBlockstore::new());
+        let store = CachingBlockstore::new(&tracking);
+        let big = vec![0u8; MAX_CACHED_BYTES / 2 + 1];
+        let first = tracking.put_cbor(&big, Code::Blake2b256).unwrap();
+        let mut other = big.clone();
+        other[0] = 1;
+        let second = tracking.put_cbor(&other, Code::Blake2b256).unwrap();
+
+        store.get(&first).unwrap();
+        store.get(&second).unwrap();
+        assert!(store.cached_bytes() <= MAX_CACHED_BYTES);
+        tracking.take_stats();
+
+        // The first block is cached, but the second didn't fit.
+        store.get(&first).unwrap();
+        store.get(&second).unwrap();
+        assert_eq!(1, tracking.stats().r);
+    }
+}
@@ -25,6 +25,7 @@ use fvm_shared::sector::{
     WindowPoStVerifyInfo,
 };
 use fvm_shared::sys::SendFlags;
+use fvm_shared::upgrade::UpgradeInfo;
 use fvm_shared::version::NetworkVersion;
 use fvm_shared::{ActorID, MethodNum, Response};
 use multihash_codetable::Code;
@@ -39,12 +40,14 @@ use crate::runtime::actor_blockstore::ActorBlockstore;
 use crate::runtime::builtins::Type;
 use crate::runtime::randomness::draw_randomness;
 use crate::runtime::{
-    ActorCode, DomainSeparationTag, MessageInfo, Policy, Primitives, RuntimePolicy,
+    panic_abort_message, ActorCode, CachingBlockstore, DomainSeparationTag, MessageInfo, Policy,
+    Primitives, RuntimePolicy, PANIC_EXIT_CODE,
 };
 use crate::{actor_error, ActorError, AsActorError, Runtime, SendError};
 
 /// A runtime that bridges to the FVM environment through the FVM SDK.
-pub struct FvmRuntime<B = ActorBlockstore> {
+/// Blocks are cached for the duration of the invocation.
+pub struct FvmRuntime<B = CachingBlockstore<ActorBlockstore>> {
     blockstore: B,
     /// Indicates whether we are in a state transaction. During such, sending
     /// messages is prohibited.
@@ -58,7 +61,7 @@ pub struct FvmRuntime<B = ActorBlockstore> {
 impl Default for FvmRuntime {
     fn default() -> Self {
         FvmRuntime {
-            blockstore: ActorBlockstore,
+            blockstore: CachingBlockstore::new(ActorBlockstore),
             in_transaction: RefCell::new(false),
             caller_validated: RefCell::new(false),
             policy: Policy::default(),
@@ -295,7 +298,8 @@ where
         let state_cid = fvm::sself::root()
             .map_err(|_| actor_error!(illegal_argument; "failed to get actor root state CID"))?;
 
-        let mut state = ActorBlockstore
+        let mut state = self
+            .blockstore
             .get_cbor::<S>(&state_cid)
             .map_err(|_| actor_error!(illegal_argument; "failed to get actor state"))?
             .expect("State does not exist for actor state root");
@@ -305,7 +309,7 @@ where
         self.in_transaction.replace(false);
 
         let ret = result?;
-        let new_root = ActorBlockstore.put_cbor(&state, Code::Blake2b256)
+        let new_root = self.blockstore.put_cbor(&state, Code::Blake2b256)
             .map_err(|e| actor_error!(illegal_argument; "failed to write actor state in transaction: {}", e.to_string()))?;
         fvm::sself::set_root(&new_root)?;
         Ok(ret)
@@ -568,7 +572,7 @@ where
 ///
 /// The trampoline takes care of boilerplate:
 ///
-/// 0.  Initialize logging if debugging is enabled.
+/// 0.  Initialize logging if debugging is enabled, and convert panics into aborts.
 /// 1.  Obtains the parameter data from the FVM by fetching the parameters block.
 /// 2.  Obtains the method number for the invocation.
 /// 3.  Creates an FVM runtime shim.
@@ -578,9 +582,7 @@ where
 pub fn trampoline<C: ActorCode>(params: u32) -> u32 {
     init_logging(C::name());
 
-    std::panic::set_hook(Box::new(|info| {
-        fvm::vm::abort(ExitCode::USR_ASSERTION_FAILED.value(), Some(&format!("{}", info)))
-    }));
+    install_panic_hook();
 
     let method = fvm::message::method_number();
     let params = fvm::message::params_raw(params).expect("params block invalid");
@@ -607,6 +609,46 @@ pub fn trampoline<C: ActorCode>(params: u32) -> u32 {
     }
 }
 
+/// A convenience function that built-in actors can delegate their upgrade entrypoint to.
+/// The FVM invokes the new code's upgrade entrypoint when an actor replaces its own code.
+///
+/// It follows the same steps as `trampoline`, except that it also decodes the upgrade info
+/// (identifying the old code) and invokes the actor's `upgrade` handler rather than a method.
+/// Since the upgrade is requested by the actor itself, the caller need not be validated.
+pub fn upgrade_trampoline<C: ActorCode>(params: u32, upgrade_info: u32) -> u32 {
+    init_logging(C::name());
+
+    install_panic_hook();
+
+    let params = fvm::message::params_raw(params).expect("params block invalid");
+    let info: UpgradeInfo = fvm::message::params_raw(upgrade_info)
+        .expect("upgrade info block invalid")
+        .expect("upgrade info missing")
+        .deserialize()
+        .expect("upgrade info invalid");
+
+    let rt = FvmRuntime::default();
+    let ret = C::upgrade(&rt, info, params).unwrap_or_else(|mut err| {
+        fvm::vm::exit(err.exit_code().value(), err.take_data(), Some(err.msg()))
+    });
+
+    match ret {
+        None => NO_DATA_BLOCK_ID,
+        Some(ret_block) => fvm::ipld::put_block(ret_block.codec, ret_block.data.as_slice())
+            .expect("failed to write result"),
+    }
+}
+
+/// Installs a panic hook which aborts with [`PANIC_EXIT_CODE`] rather than trapping, so that a
+/// panic surfaces as a well-defined exit code. When debugging, the abort carries the panic's
+/// message, truncated.
+fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let msg = panic_abort_message(info, fvm::debug::enabled());
+        fvm::vm::abort(PANIC_EXIT_CODE.value(), Some(&msg))
+    }));
+}
+
 /// If debugging is enabled in the VM, installs a logger that sends messages to the FVM log syscall.
 /// Messages are prefixed with "[LEVEL] ".
 /// If debugging is not enabled, no logger will be installed which means that log!() and
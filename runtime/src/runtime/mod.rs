@@ -7,6 +7,7 @@ use fvm_ipld_encoding::CborStore;
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
 use fvm_shared::randomness::RANDOMNESS_LENGTH;
 use fvm_shared::version::NetworkVersion;
 use fvm_shared::{ActorID, MethodNum, Response};
@@ -14,13 +15,19 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 pub use self::actor_code::*;
+pub use self::address_resolver::AddressResolver;
+pub use self::caching_blockstore::{CachingBlockstore, MAX_CACHED_BYTES};
+pub use self::panic::{panic_abort_message, MAX_PANIC_MESSAGE_LEN, PANIC_EXIT_CODE};
 pub use self::policy::*;
 pub use self::randomness::DomainSeparationTag;
 use crate::runtime::builtins::Type;
 use crate::{actor_error, ActorError, SendError};
 
 mod actor_code;
+mod address_resolver;
 pub mod builtins;
+mod caching_blockstore;
+mod panic;
 pub mod policy;
 mod randomness;
 
@@ -90,6 +97,15 @@ pub trait Runtime: Primitives + RuntimePolicy {
     /// If the argument is an ID address it is returned directly.
     fn resolve_address(&self, address: &Address) -> Option<ActorID>;
 
+    /// Returns a resolver which memoizes the IDs it resolves addresses to, for methods which
+    /// resolve the same addresses repeatedly.
+    fn address_resolver(&self) -> AddressResolver<'_, Self>
+    where
+        Self: Sized,
+    {
+        AddressResolver::new(self)
+    }
+
     /// Looks up the "delegated" address of an actor by ID, if any. Returns None if either the
     /// target actor doesn't exist, or doesn't have an f4 address.
     fn lookup_delegated_address(&self, id: ActorID) -> Option<Address>;
@@ -195,6 +211,27 @@ pub trait Runtime: Primitives + RuntimePolicy {
         self.send(to, method, params, value, None, SendFlags::empty())
     }
 
+    /// Sends a message to another actor as [`Runtime::send`] does, also reporting the gas used.
+    /// The callee's exit code is returned in the result rather than as an error, for callers
+    /// which carry on after a failed call. Only a failure to send at all is an error.
+    fn send_for_result(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        flags: SendFlags,
+    ) -> Result<SendResult, SendError> {
+        let before = self.gas_available();
+        let ret = self.send(to, method, params, value, gas_limit, flags)?;
+        Ok(SendResult {
+            exit_code: ret.exit_code,
+            return_data: ret.return_data,
+            gas_used: before.saturating_sub(self.gas_available()),
+        })
+    }
+
     /// Computes an address for a new actor. The returned address is intended to uniquely refer to
     /// the actor even in the event of a chain re-org (whereas an ID-address might refer to a
     /// different actor after messages are re-ordered).
@@ -257,6 +294,36 @@ pub trait Runtime: Primitives + RuntimePolicy {
     /// Returns true if the call is read_only.
     /// All state updates, including actor creation and balance transfers, are rejected in read_only calls.
     fn read_only(&self) -> bool;
+
+    /// Records a debugging message.
+    /// This is a no-op in the FVM. Test runtimes record the message, for tests to inspect, and
+    /// pass it to the `log` crate.
+    fn log(&self, _level: log::Level, _msg: &str) {}
+}
+
+/// The outcome of a message sent with [`Runtime::send_for_result`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendResult {
+    pub exit_code: ExitCode,
+    pub return_data: Option<IpldBlock>,
+    /// The gas consumed by the call, including the cost of sending it.
+    pub gas_used: u64,
+}
+
+impl SendResult {
+    /// Returns the return value if the call succeeded, or else an error with the callee's
+    /// exit code, as for [`extract_send_result`](crate::extract_send_result).
+    pub fn into_result(self) -> Result<Option<IpldBlock>, ActorError> {
+        if self.exit_code.is_success() {
+            Ok(self.return_data)
+        } else {
+            Err(ActorError::checked(
+                self.exit_code,
+                format!("send aborted with code {}", self.exit_code),
+                self.return_data,
+            ))
+        }
+    }
 }
 
 /// Message information available to the actor about executing message.
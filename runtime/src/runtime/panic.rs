@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use fvm_shared::error::ExitCode;
+
+/// The exit code with which an actor aborts when it panics.
+pub const PANIC_EXIT_CODE: ExitCode = ExitCode::USR_ASSERTION_FAILED;
+
+/// Maximum length in bytes of the message carried by the abort for a panic.
+pub const MAX_PANIC_MESSAGE_LEN: usize = 1024;
+
+const TRUNCATION_MARKER: &str = "...";
+
+/// Formats the message carried by the abort for a panic.
+/// The panic's message and location are included only when debugging, truncated to
+/// [`MAX_PANIC_MESSAGE_LEN`] bytes, since formatting them costs gas.
+pub fn panic_abort_message(panic: &impl Display, debug: bool) -> String {
+    if !debug {
+        return "actor panicked".to_string();
+    }
+    let mut msg = format!("actor panicked: {}", panic);
+    if msg.len() > MAX_PANIC_MESSAGE_LEN {
+        let mut end = MAX_PANIC_MESSAGE_LEN - TRUNCATION_MARKER.len();
+        while !msg.is_char_boundary(end) {
+            end -= 1;
+        }
+        msg.truncate(end);
+        msg.push_str(TRUNCATION_MARKER);
+    }
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_is_omitted_unless_debugging() {
+        assert_eq!("actor panicked", panic_abort_message(&"oops", false));
+        assert_eq!("actor panicked: oops", panic_abort_message(&"oops", true));
+    }
+
+    #[test]
+    fn long_message_is_truncated_at_char_boundary() {
+        let long = "é".repeat(MAX_PANIC_MESSAGE_LEN);
+        let msg = panic_abort_message(&long, true);
+        assert!(msg.len() <= MAX_PANIC_MESSAGE_LEN);
+        assert!(msg.len() > MAX_PANIC_MESSAGE_LEN - 8);
+        assert!(msg.starts_with("actor panicked: éé"));
+        assert!(msg.ends_with("é..."));
+    }
+}
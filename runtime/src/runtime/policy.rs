@@ -1,6 +1,5 @@
 use fvm_shared::clock::ChainEpoch;
-use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof, StoragePower};
-use num_traits::FromPrimitive;
+use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof, SectorSize, StoragePower};
 use serde::{Deserialize, Serialize};
 
 // A trait for runtime policy configuration
@@ -59,6 +58,9 @@ pub struct Policy {
     /// MaxMultiaddrData is the maximum amount of data that can be stored in multiaddrs.
     pub max_multiaddr_data: usize,
 
+    /// The maximum number of multiaddrs a miner may have.
+    pub max_multiaddr_count: usize,
+
     /// The maximum number of partitions that may be required to be loaded in a single invocation.
     /// This limits the number of simultaneous fault, recovery, or sector-extension declarations.
     pub addressed_partitions_max: u64,
@@ -105,6 +107,10 @@ pub struct Policy {
     /// Staging period for a miner worker key change.
     pub worker_key_change_delay: ChainEpoch,
 
+    /// Period after its last reward before a miner with no sectors, pre-commitments or debt
+    /// may release its remaining vesting funds early.
+    pub vesting_release_cooldown: ChainEpoch,
+
     /// Minimum number of epochs past the current epoch a sector may be set to expire.
     pub min_sector_expiration: i64,
 
@@ -121,6 +127,21 @@ pub struct Policy {
     /// for permissioned actor methods and winning block elections.
     pub consensus_fault_ineligibility_duration: ChainEpoch,
 
+    /// Multiple of the expected reward per block winner burnt as a consensus fault penalty.
+    pub consensus_fault_factor: u64,
+
+    /// Numerator of the fraction of a terminated sector's expected lifetime rewards
+    /// charged as a termination fee.
+    pub termination_reward_factor_num: u64,
+
+    /// Denominator of the fraction of a terminated sector's expected lifetime rewards
+    /// charged as a termination fee.
+    pub termination_reward_factor_denom: u64,
+
+    /// Maximum number of days of a sector's lifetime for which its rewards are charged
+    /// when it is terminated.
+    pub termination_lifetime_cap: ChainEpoch,
+
     /// The maximum number of new sectors that may be staged by a miner during a single proving period.
     pub new_sectors_per_period_max: usize,
 
@@ -163,13 +184,84 @@ pub struct Policy {
     /// allocation's maximum term.
     pub market_default_allocation_term_buffer: i64,
 
+    /// Numerator of the minimum fraction of a deal's provider collateral that is slashed
+    /// when the deal is terminated early, however little of its term remains
+    pub deal_termination_penalty_floor_num: i64,
+
+    /// Denominator of the minimum fraction of a deal's provider collateral that is slashed
+    /// when the deal is terminated early, however little of its term remains
+    pub deal_termination_penalty_floor_denom: i64,
+
     // --- power ---
     /// Minimum miner consensus power
     pub minimum_consensus_power: StoragePower,
+
+    // --- cron ---
+    /// The gas available to each cron entry's call during an epoch tick.
+    /// An entry which exhausts it fails without consuming the gas needed by the entries after it.
+    pub cron_entry_gas_limit: u64,
+
+    // --- account ---
+    /// Whether account actors may delegate message authentication to another actor.
+    /// Intended for account abstraction experiments, so disabled on production networks.
+    pub account_auth_delegation: bool,
 }
 
 impl Default for Policy {
+    /// The policy for the network selected by this crate's features.
     fn default() -> Policy {
+        Policy::network(NetworkParams::from_features())
+    }
+}
+
+impl Policy {
+    /// The policy for Filecoin mainnet, independent of the features this crate was built with.
+    pub fn mainnet() -> Policy {
+        Policy::network(NetworkParams {
+            sector_sizes: vec![SectorSize::_32GiB, SectorSize::_64GiB],
+            pre_commit_challenge_delay: 150,
+            minimum_verified_allocation_size: 1 << 20,
+            prov_collateral_percent_supply_num: 1,
+            minimum_consensus_power: 10 << 40,
+            account_auth_delegation: false,
+        })
+    }
+
+    /// The policy for the calibration network, independent of the features this crate was
+    /// built with.
+    pub fn calibnet() -> Policy {
+        Policy::network(NetworkParams {
+            sector_sizes: vec![SectorSize::_32GiB, SectorSize::_64GiB],
+            pre_commit_challenge_delay: 150,
+            minimum_verified_allocation_size: 1 << 20,
+            prov_collateral_percent_supply_num: 1,
+            minimum_consensus_power: 32 << 30,
+            account_auth_delegation: false,
+        })
+    }
+
+    /// The policy for local development networks, with small sectors, small deals, a short
+    /// pre-commit challenge delay and account authentication delegation, independent of the
+    /// features this crate was built with.
+    pub fn devnet() -> Policy {
+        Policy::network(NetworkParams {
+            sector_sizes: vec![SectorSize::_2KiB, SectorSize::_8MiB],
+            pre_commit_challenge_delay: 10,
+            minimum_verified_allocation_size: 256,
+            prov_collateral_percent_supply_num: 1,
+            minimum_consensus_power: 2 << 10,
+            account_auth_delegation: true,
+        })
+    }
+
+    /// Returns a builder for overriding individual fields of this policy.
+    pub fn builder(self) -> PolicyBuilder {
+        PolicyBuilder { policy: self }
+    }
+
+    /// Builds the policy for a network from the values that differ between networks.
+    /// Every other value is the same on all networks, whatever features this crate was built with.
+    fn network(params: NetworkParams) -> Policy {
         Policy {
             max_aggregated_sectors: policy_constants::MAX_AGGREGATED_SECTORS,
             min_aggregated_sectors: policy_constants::MIN_AGGREGATED_SECTORS,
@@ -188,53 +280,197 @@ impl Default for Policy {
             max_control_addresses: policy_constants::MAX_CONTROL_ADDRESSES,
             max_peer_id_length: policy_constants::MAX_PEER_ID_LENGTH,
             max_multiaddr_data: policy_constants::MAX_MULTIADDR_DATA,
+            max_multiaddr_count: policy_constants::MAX_MULTIADDR_COUNT,
             addressed_partitions_max: policy_constants::ADDRESSED_PARTITIONS_MAX,
             declarations_max: policy_constants::DECLARATIONS_MAX,
             addressed_sectors_max: policy_constants::ADDRESSED_SECTORS_MAX,
             posted_partitions_max: policy_constants::POSTED_PARTITIONS_MAX,
             max_pre_commit_randomness_lookback:
                 policy_constants::MAX_PRE_COMMIT_RANDOMNESS_LOOKBACK,
-            valid_prove_commit_ni_proof_type: ProofSet::default_seal_ni_proofs(),
+            valid_prove_commit_ni_proof_type: ProofSet::seal_ni_proofs(&params.sector_sizes),
             max_aggregated_sectors_ni: policy_constants::MAX_AGGREGATED_SECTORS_NI,
             min_aggregated_sectors_ni: policy_constants::MIN_AGGREGATED_SECTORS_NI,
             max_prove_commit_ni_randomness_lookback: policy_constants::MAX_PROVE_COMMIT_NI_LOOKBACK,
-            pre_commit_challenge_delay: policy_constants::PRE_COMMIT_CHALLENGE_DELAY,
+            pre_commit_challenge_delay: params.pre_commit_challenge_delay,
             wpost_challenge_lookback: policy_constants::WPOST_CHALLENGE_LOOKBACK,
             fault_declaration_cutoff: policy_constants::FAULT_DECLARATION_CUTOFF,
             fault_max_age: policy_constants::FAULT_MAX_AGE,
             worker_key_change_delay: policy_constants::WORKER_KEY_CHANGE_DELAY,
+            vesting_release_cooldown: policy_constants::VESTING_RELEASE_COOLDOWN,
             min_sector_expiration: policy_constants::MIN_SECTOR_EXPIRATION,
             max_sector_expiration_extension: policy_constants::MAX_SECTOR_EXPIRATION_EXTENSION,
             deal_limit_denominator: policy_constants::DEAL_LIMIT_DENOMINATOR,
             consensus_fault_ineligibility_duration:
                 policy_constants::CONSENSUS_FAULT_INELIGIBILITY_DURATION,
+            consensus_fault_factor: policy_constants::CONSENSUS_FAULT_FACTOR,
+            termination_reward_factor_num: policy_constants::TERMINATION_REWARD_FACTOR_NUM,
+            termination_reward_factor_denom: policy_constants::TERMINATION_REWARD_FACTOR_DENOM,
+            termination_lifetime_cap: policy_constants::TERMINATION_LIFETIME_CAP,
             new_sectors_per_period_max: policy_constants::NEW_SECTORS_PER_PERIOD_MAX,
             chain_finality: policy_constants::CHAIN_FINALITY,
 
-            valid_post_proof_type: ProofSet::default_post_proofs(),
-            valid_pre_commit_proof_type: ProofSet::default_precommit_seal_proofs(),
-            minimum_verified_allocation_size: StoragePower::from_i32(
-                policy_constants::MINIMUM_VERIFIED_ALLOCATION_SIZE,
-            )
-            .unwrap(),
+            valid_post_proof_type: ProofSet::post_proofs(&params.sector_sizes),
+            valid_pre_commit_proof_type: ProofSet::precommit_seal_proofs(&params.sector_sizes),
+            minimum_verified_allocation_size: StoragePower::from(
+                params.minimum_verified_allocation_size,
+            ),
             minimum_verified_allocation_term: policy_constants::MINIMUM_VERIFIED_ALLOCATION_TERM,
             maximum_verified_allocation_term: policy_constants::MAXIMUM_VERIFIED_ALLOCATION_TERM,
             maximum_verified_allocation_expiration:
                 policy_constants::MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION,
             end_of_life_claim_drop_period: policy_constants::END_OF_LIFE_CLAIM_DROP_PERIOD,
             deal_updates_interval: policy_constants::DEAL_UPDATES_INTERVAL,
-            prov_collateral_percent_supply_num:
-                policy_constants::PROV_COLLATERAL_PERCENT_SUPPLY_NUM,
+            prov_collateral_percent_supply_num: params.prov_collateral_percent_supply_num,
             prov_collateral_percent_supply_denom:
                 policy_constants::PROV_COLLATERAL_PERCENT_SUPPLY_DENOM,
             market_default_allocation_term_buffer:
                 policy_constants::MARKET_DEFAULT_ALLOCATION_TERM_BUFFER,
+            deal_termination_penalty_floor_num:
+                policy_constants::DEAL_TERMINATION_PENALTY_FLOOR_NUM,
+            deal_termination_penalty_floor_denom:
+                policy_constants::DEAL_TERMINATION_PENALTY_FLOOR_DENOM,
+
+            minimum_consensus_power: StoragePower::from(params.minimum_consensus_power),
 
-            minimum_consensus_power: StoragePower::from(policy_constants::MINIMUM_CONSENSUS_POWER),
+            cron_entry_gas_limit: policy_constants::CRON_ENTRY_GAS_LIMIT,
+
+            account_auth_delegation: params.account_auth_delegation,
+        }
+    }
+}
+
+// The policy values that differ between networks.
+// These correspond to the features enabled for each network by this crate's build script.
+struct NetworkParams {
+    sector_sizes: Vec<SectorSize>,
+    pre_commit_challenge_delay: ChainEpoch,
+    minimum_verified_allocation_size: i64,
+    prov_collateral_percent_supply_num: i64,
+    minimum_consensus_power: i64,
+    account_auth_delegation: bool,
+}
+
+impl NetworkParams {
+    /// The values selected by this crate's features.
+    fn from_features() -> NetworkParams {
+        NetworkParams {
+            sector_sizes: enabled_sector_sizes(),
+            pre_commit_challenge_delay: policy_constants::PRE_COMMIT_CHALLENGE_DELAY,
+            minimum_verified_allocation_size: policy_constants::MINIMUM_VERIFIED_ALLOCATION_SIZE
+                .into(),
+            prov_collateral_percent_supply_num:
+                policy_constants::PROV_COLLATERAL_PERCENT_SUPPLY_NUM,
+            minimum_consensus_power: policy_constants::MINIMUM_CONSENSUS_POWER,
+            account_auth_delegation: policy_constants::ACCOUNT_AUTH_DELEGATION,
         }
     }
 }
 
+/// Builds a policy by overriding individual fields of a base policy.
+#[derive(Debug, Clone)]
+pub struct PolicyBuilder {
+    policy: Policy,
+}
+
+impl Default for PolicyBuilder {
+    fn default() -> Self {
+        Policy::default().builder()
+    }
+}
+
+impl PolicyBuilder {
+    /// Sets the minimum miner consensus power.
+    pub fn minimum_consensus_power(mut self, power: StoragePower) -> Self {
+        self.policy.minimum_consensus_power = power;
+        self
+    }
+
+    /// Sets the proving period and the number of deadlines within it.
+    /// The challenge window is derived so that deadlines exactly divide the proving period.
+    pub fn proving_period(mut self, period: ChainEpoch, deadlines: u64) -> Self {
+        self.policy.wpost_proving_period = period;
+        self.policy.wpost_period_deadlines = deadlines;
+        self.policy.wpost_challenge_window = period / deadlines as ChainEpoch;
+        self
+    }
+
+    /// Sets the minimum fraction of provider collateral slashed when a deal is terminated early.
+    pub fn deal_termination_penalty_floor(mut self, num: i64, denom: i64) -> Self {
+        self.policy.deal_termination_penalty_floor_num = num;
+        self.policy.deal_termination_penalty_floor_denom = denom;
+        self
+    }
+
+    /// Sets the pre-commit challenge delay.
+    pub fn pre_commit_challenge_delay(mut self, delay: ChainEpoch) -> Self {
+        self.policy.pre_commit_challenge_delay = delay;
+        self
+    }
+
+    /// Sets the minimum size of a verified allocation.
+    pub fn minimum_verified_allocation_size(mut self, size: StoragePower) -> Self {
+        self.policy.minimum_verified_allocation_size = size;
+        self
+    }
+
+    /// Enables exactly the PoSt, seal and NI-PoRep proof types for the given sector sizes.
+    pub fn sector_sizes(mut self, sizes: &[SectorSize]) -> Self {
+        self.policy.valid_post_proof_type = ProofSet::post_proofs(sizes);
+        self.policy.valid_pre_commit_proof_type = ProofSet::precommit_seal_proofs(sizes);
+        self.policy.valid_prove_commit_ni_proof_type = ProofSet::seal_ni_proofs(sizes);
+        self
+    }
+
+    /// Sets the seal proof types permitted for pre-commitment.
+    pub fn pre_commit_proofs(mut self, proofs: ProofSet) -> Self {
+        self.policy.valid_pre_commit_proof_type = proofs;
+        self
+    }
+
+    /// Sets the PoSt proof types permitted for new miners.
+    pub fn post_proofs(mut self, proofs: ProofSet) -> Self {
+        self.policy.valid_post_proof_type = proofs;
+        self
+    }
+
+    /// Sets the non-interactive PoRep proof types permitted.
+    pub fn ni_proofs(mut self, proofs: ProofSet) -> Self {
+        self.policy.valid_prove_commit_ni_proof_type = proofs;
+        self
+    }
+
+    /// Sets the multiple of the expected reward per block winner burnt for a consensus fault.
+    pub fn consensus_fault_factor(mut self, factor: u64) -> Self {
+        self.policy.consensus_fault_factor = factor;
+        self
+    }
+
+    /// Sets the fraction of a terminated sector's expected rewards charged as a termination
+    /// fee, and the maximum number of days of the sector's lifetime charged.
+    pub fn termination_fee(mut self, num: u64, denom: u64, lifetime_cap: ChainEpoch) -> Self {
+        self.policy.termination_reward_factor_num = num;
+        self.policy.termination_reward_factor_denom = denom;
+        self.policy.termination_lifetime_cap = lifetime_cap;
+        self
+    }
+
+    /// Sets the gas available to each cron entry's call during an epoch tick.
+    pub fn cron_entry_gas_limit(mut self, limit: u64) -> Self {
+        self.policy.cron_entry_gas_limit = limit;
+        self
+    }
+
+    /// Enables or disables delegation of account actors' message authentication.
+    pub fn account_auth_delegation(mut self, enabled: bool) -> Self {
+        self.policy.account_auth_delegation = enabled;
+        self
+    }
+
+    pub fn build(self) -> Policy {
+        self.policy
+    }
+}
+
 pub mod policy_constants {
     use fvm_shared::clock::ChainEpoch;
     use fvm_shared::sector::SectorNumber;
@@ -289,6 +525,8 @@ pub mod policy_constants {
 
     pub const MAX_MULTIADDR_DATA: usize = 1024;
 
+    pub const MAX_MULTIADDR_COUNT: usize = 32;
+
     // With 48 deadlines (half-hour), 300 partitions per declaration permits addressing a full EiB
     // of partitions of 32GiB sectors with 1 message per epoch within a single half-hour deadline.
     // A miner can of course submit more messages.
@@ -333,6 +571,8 @@ pub mod policy_constants {
     // key or allowing the owner account to submit PoSts while a key change is pending.
     pub const WORKER_KEY_CHANGE_DELAY: ChainEpoch = CHAIN_FINALITY;
 
+    pub const VESTING_RELEASE_COOLDOWN: ChainEpoch = 30 * EPOCHS_IN_DAY;
+
     pub const MIN_SECTOR_EXPIRATION: i64 = 180 * EPOCHS_IN_DAY;
 
     pub const MAX_SECTOR_EXPIRATION_EXTENSION: i64 = 1278 * EPOCHS_IN_DAY;
@@ -342,6 +582,13 @@ pub mod policy_constants {
 
     pub const CONSENSUS_FAULT_INELIGIBILITY_DURATION: ChainEpoch = CHAIN_FINALITY;
 
+    pub const CONSENSUS_FAULT_FACTOR: u64 = 5;
+
+    pub const TERMINATION_REWARD_FACTOR_NUM: u64 = 1;
+    pub const TERMINATION_REWARD_FACTOR_DENOM: u64 = 2;
+
+    pub const TERMINATION_LIFETIME_CAP: ChainEpoch = 140;
+
     pub const NEW_SECTORS_PER_PERIOD_MAX: usize = 128 << 10;
 
     /// This is a conservative value that is chosen via simulations of all known attacks.
@@ -367,6 +614,9 @@ pub mod policy_constants {
 
     pub const MARKET_DEFAULT_ALLOCATION_TERM_BUFFER: i64 = 90 * EPOCHS_IN_DAY;
 
+    pub const DEAL_TERMINATION_PENALTY_FLOOR_NUM: i64 = 1;
+    pub const DEAL_TERMINATION_PENALTY_FLOOR_DENOM: i64 = 10;
+
     #[cfg(feature = "min-power-2k")]
     pub const MINIMUM_CONSENSUS_POWER: i64 = 2 << 10;
     #[cfg(feature = "min-power-2g")]
@@ -379,6 +629,17 @@ pub mod policy_constants {
         feature = "min-power-32g"
     )))]
     pub const MINIMUM_CONSENSUS_POWER: i64 = 10 << 40;
+
+    // The cron tick is given 10,000 times the 10B block gas limit.
+    // Each entry may use 40% of it: enough for the power actor's miner crons or the market's
+    // deal updates in a busy epoch, while an entry exhausting its allowance still leaves
+    // enough for the other.
+    pub const CRON_ENTRY_GAS_LIMIT: u64 = 4_000 * 10_000_000_000;
+
+    #[cfg(not(feature = "account-auth-delegation"))]
+    pub const ACCOUNT_AUTH_DELEGATION: bool = false;
+    #[cfg(feature = "account-auth-delegation")]
+    pub const ACCOUNT_AUTH_DELEGATION: bool = true;
 }
 
 /// A set indicating which proofs are considered valid, optimised for lookup of a small number of
@@ -387,6 +648,23 @@ pub mod policy_constants {
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct ProofSet(Vec<bool>);
 
+/// The sector sizes enabled by this crate's features.
+#[allow(clippy::vec_init_then_push, unused_mut)]
+fn enabled_sector_sizes() -> Vec<SectorSize> {
+    let mut sizes = Vec::new();
+    #[cfg(feature = "sector-2k")]
+    sizes.push(SectorSize::_2KiB);
+    #[cfg(feature = "sector-8m")]
+    sizes.push(SectorSize::_8MiB);
+    #[cfg(feature = "sector-512m")]
+    sizes.push(SectorSize::_512MiB);
+    #[cfg(feature = "sector-32g")]
+    sizes.push(SectorSize::_32GiB);
+    #[cfg(feature = "sector-64g")]
+    sizes.push(SectorSize::_64GiB);
+    sizes
+}
+
 /// The number of total possible types (enum variants) of RegisteredPoStProof
 const REGISTERED_POST_PROOF_VARIANTS: usize = 15;
 
@@ -396,94 +674,79 @@ const REGISTERED_SEAL_PROOF_VARIANTS: usize = 20;
 impl ProofSet {
     /// Create a `ProofSet` for enabled `RegisteredPoStProof`s
     pub fn default_post_proofs() -> Self {
-        let mut proofs = vec![false; REGISTERED_POST_PROOF_VARIANTS];
-        #[cfg(feature = "sector-2k")]
-        {
-            proofs[i64::from(RegisteredPoStProof::StackedDRGWindow2KiBV1P1) as usize] = true;
-        }
-        #[cfg(feature = "sector-8m")]
-        {
-            proofs[i64::from(RegisteredPoStProof::StackedDRGWindow8MiBV1P1) as usize] = true;
-        }
-        #[cfg(feature = "sector-512m")]
-        {
-            proofs[i64::from(RegisteredPoStProof::StackedDRGWindow512MiBV1P1) as usize] = true;
-        }
-        #[cfg(feature = "sector-32g")]
-        {
-            proofs[i64::from(RegisteredPoStProof::StackedDRGWindow32GiBV1P1) as usize] = true;
-        }
-        #[cfg(feature = "sector-64g")]
-        {
-            proofs[i64::from(RegisteredPoStProof::StackedDRGWindow64GiBV1P1) as usize] = true;
-        }
-        ProofSet(proofs)
+        Self::post_proofs(&enabled_sector_sizes())
     }
 
     /// Create a `ProofSet` for enabled `RegisteredSealProof`s
     pub fn default_precommit_seal_proofs() -> Self {
-        let mut proofs = vec![false; REGISTERED_SEAL_PROOF_VARIANTS];
-        #[cfg(feature = "sector-2k")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG2KiBV1P1) as usize] = true;
-            proofs
-                [i64::from(RegisteredSealProof::StackedDRG2KiBV1P1_Feat_SyntheticPoRep) as usize] =
-                true;
-        }
-        #[cfg(feature = "sector-8m")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG8MiBV1P1) as usize] = true;
-            proofs
-                [i64::from(RegisteredSealProof::StackedDRG8MiBV1P1_Feat_SyntheticPoRep) as usize] =
-                true;
-        }
-        #[cfg(feature = "sector-512m")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG512MiBV1P1) as usize] = true;
-            proofs[i64::from(RegisteredSealProof::StackedDRG512MiBV1P1_Feat_SyntheticPoRep)
-                as usize] = true;
-        }
-        #[cfg(feature = "sector-32g")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG32GiBV1P1) as usize] = true;
-            proofs[i64::from(RegisteredSealProof::StackedDRG32GiBV1P1_Feat_SyntheticPoRep)
-                as usize] = true;
-        }
-        #[cfg(feature = "sector-64g")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG64GiBV1P1) as usize] = true;
-            proofs[i64::from(RegisteredSealProof::StackedDRG64GiBV1P1_Feat_SyntheticPoRep)
-                as usize] = true;
-        }
-        ProofSet(proofs)
+        Self::precommit_seal_proofs(&enabled_sector_sizes())
     }
 
     pub fn default_seal_ni_proofs() -> Self {
-        let mut proofs = vec![false; REGISTERED_SEAL_PROOF_VARIANTS];
-        #[cfg(feature = "sector-2k")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG2KiBV1P2_Feat_NiPoRep) as usize] = true;
-        }
-        #[cfg(feature = "sector-8m")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG8MiBV1P2_Feat_NiPoRep) as usize] = true;
-        }
-        #[cfg(feature = "sector-512m")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG512MiBV1P2_Feat_NiPoRep) as usize] =
-                true;
+        Self::seal_ni_proofs(&enabled_sector_sizes())
+    }
+
+    /// Create a `ProofSet` of the window PoSt proofs for the given sector sizes.
+    pub fn post_proofs(sizes: &[SectorSize]) -> Self {
+        let mut proofs = ProofSet(vec![false; REGISTERED_POST_PROOF_VARIANTS]);
+        for size in sizes {
+            proofs.insert(match size {
+                SectorSize::_2KiB => RegisteredPoStProof::StackedDRGWindow2KiBV1P1,
+                SectorSize::_8MiB => RegisteredPoStProof::StackedDRGWindow8MiBV1P1,
+                SectorSize::_512MiB => RegisteredPoStProof::StackedDRGWindow512MiBV1P1,
+                SectorSize::_32GiB => RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+                SectorSize::_64GiB => RegisteredPoStProof::StackedDRGWindow64GiBV1P1,
+            });
         }
-        #[cfg(feature = "sector-32g")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG32GiBV1P2_Feat_NiPoRep) as usize] =
-                true;
+        proofs
+    }
+
+    /// Create a `ProofSet` of the interactive seal proofs, with and without synthetic PoRep,
+    /// for the given sector sizes.
+    pub fn precommit_seal_proofs(sizes: &[SectorSize]) -> Self {
+        let mut proofs = ProofSet(vec![false; REGISTERED_SEAL_PROOF_VARIANTS]);
+        for size in sizes {
+            let (plain, synthetic) = match size {
+                SectorSize::_2KiB => (
+                    RegisteredSealProof::StackedDRG2KiBV1P1,
+                    RegisteredSealProof::StackedDRG2KiBV1P1_Feat_SyntheticPoRep,
+                ),
+                SectorSize::_8MiB => (
+                    RegisteredSealProof::StackedDRG8MiBV1P1,
+                    RegisteredSealProof::StackedDRG8MiBV1P1_Feat_SyntheticPoRep,
+                ),
+                SectorSize::_512MiB => (
+                    RegisteredSealProof::StackedDRG512MiBV1P1,
+                    RegisteredSealProof::StackedDRG512MiBV1P1_Feat_SyntheticPoRep,
+                ),
+                SectorSize::_32GiB => (
+                    RegisteredSealProof::StackedDRG32GiBV1P1,
+                    RegisteredSealProof::StackedDRG32GiBV1P1_Feat_SyntheticPoRep,
+                ),
+                SectorSize::_64GiB => (
+                    RegisteredSealProof::StackedDRG64GiBV1P1,
+                    RegisteredSealProof::StackedDRG64GiBV1P1_Feat_SyntheticPoRep,
+                ),
+            };
+            proofs.insert(plain);
+            proofs.insert(synthetic);
         }
-        #[cfg(feature = "sector-64g")]
-        {
-            proofs[i64::from(RegisteredSealProof::StackedDRG64GiBV1P2_Feat_NiPoRep) as usize] =
-                true;
+        proofs
+    }
+
+    /// Create a `ProofSet` of the non-interactive seal proofs for the given sector sizes.
+    pub fn seal_ni_proofs(sizes: &[SectorSize]) -> Self {
+        let mut proofs = ProofSet(vec![false; REGISTERED_SEAL_PROOF_VARIANTS]);
+        for size in sizes {
+            proofs.insert(match size {
+                SectorSize::_2KiB => RegisteredSealProof::StackedDRG2KiBV1P2_Feat_NiPoRep,
+                SectorSize::_8MiB => RegisteredSealProof::StackedDRG8MiBV1P2_Feat_NiPoRep,
+                SectorSize::_512MiB => RegisteredSealProof::StackedDRG512MiBV1P2_Feat_NiPoRep,
+                SectorSize::_32GiB => RegisteredSealProof::StackedDRG32GiBV1P2_Feat_NiPoRep,
+                SectorSize::_64GiB => RegisteredSealProof::StackedDRG64GiBV1P2_Feat_NiPoRep,
+            });
         }
-        ProofSet(proofs)
+        proofs
     }
 
     /// Checks if the requested proof type exists in the set
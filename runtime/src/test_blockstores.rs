@@ -7,6 +7,9 @@ use anyhow::Result;
 use cid::Cid;
 
 use fvm_ipld_blockstore::Blockstore;
+use fvm_shared::error::ExitCode;
+
+use crate::ActorError;
 
 /// Stats for a [MemoryBlockstore] this indicates the amount of read and written data
 /// to the wrapped store.
@@ -22,22 +25,130 @@ pub struct BSStats {
     pub bw: usize,
 }
 
+impl std::ops::Sub for BSStats {
+    type Output = BSStats;
+
+    /// The operations performed between two snapshots of the stats.
+    fn sub(self, earlier: BSStats) -> BSStats {
+        BSStats {
+            r: self.r - earlier.r,
+            w: self.w - earlier.w,
+            br: self.br - earlier.br,
+            bw: self.bw - earlier.bw,
+        }
+    }
+}
+
+/// Wrapper around any `Blockstore` counting the blocks and bytes read and written through it.
+/// Useful for measuring the IPLD churn of an operation against a store that doesn't track it.
+#[derive(Debug, Default)]
+pub struct TrackingBlockstore<BS> {
+    inner: BS,
+    stats: RefCell<BSStats>,
+}
+
+impl<BS: Blockstore> TrackingBlockstore<BS> {
+    pub fn new(inner: BS) -> Self {
+        Self { inner, stats: Default::default() }
+    }
+
+    /// Returns the operations performed since construction or the last reset.
+    pub fn stats(&self) -> BSStats {
+        *self.stats.borrow()
+    }
+
+    /// Returns the operations performed since construction or the last reset,
+    /// and resets the counts.
+    pub fn take_stats(&self) -> BSStats {
+        self.stats.take()
+    }
+
+    pub fn into_inner(self) -> BS {
+        self.inner
+    }
+}
+
+impl<BS: Blockstore> Blockstore for TrackingBlockstore<BS> {
+    fn get(&self, cid: &Cid) -> Result<Option<Vec<u8>>> {
+        let bytes = self.inner.get(cid)?;
+        let mut stats = self.stats.borrow_mut();
+        stats.r += 1;
+        if let Some(bytes) = &bytes {
+            stats.br += bytes.len();
+        }
+        Ok(bytes)
+    }
+
+    fn has(&self, cid: &Cid) -> Result<bool> {
+        self.stats.borrow_mut().r += 1;
+        self.inner.has(cid)
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> Result<()> {
+        self.inner.put_keyed(k, block)?;
+        let mut stats = self.stats.borrow_mut();
+        stats.w += 1;
+        stats.bw += block.len();
+        Ok(())
+    }
+}
+
 /// Wrapper around `Blockstore` to tracking reads and writes for verification.
 /// This struct should only be used for testing.
 #[derive(Debug, Default)]
 pub struct MemoryBlockstore {
     blocks: RefCell<HashMap<Cid, Vec<u8>>>,
     pub stats: RefCell<BSStats>,
+    // Injected failures, as the number of operations to succeed first and the code to fail with.
+    fail_get: RefCell<Option<(usize, ExitCode)>>,
+    fail_put: RefCell<Option<(usize, ExitCode)>>,
 }
 
 impl MemoryBlockstore {
     pub fn new() -> Self {
-        Self { blocks: Default::default(), stats: Default::default() }
+        Default::default()
+    }
+
+    /// Returns the CIDs of all blocks in the store, in no particular order.
+    /// Doesn't count as a read.
+    pub fn cids(&self) -> Vec<Cid> {
+        self.blocks.borrow().keys().copied().collect()
+    }
+
+    /// Causes the `nth` subsequent get (counting from 1) to fail with an `ActorError` carrying
+    /// the exit code. Later gets succeed.
+    pub fn fail_nth_get(&self, nth: usize, code: ExitCode) {
+        assert!(nth > 0, "nth must be at least 1");
+        self.fail_get.replace(Some((nth - 1, code)));
+    }
+
+    /// Causes the `nth` subsequent put (counting from 1) to fail with an `ActorError` carrying
+    /// the exit code. Later puts succeed.
+    pub fn fail_nth_put(&self, nth: usize, code: ExitCode) {
+        assert!(nth > 0, "nth must be at least 1");
+        self.fail_put.replace(Some((nth - 1, code)));
+    }
+}
+
+fn injected_failure(slot: &RefCell<Option<(usize, ExitCode)>>, op: &str) -> Result<()> {
+    let mut slot = slot.borrow_mut();
+    match slot.as_mut() {
+        Some((0, code)) => {
+            let err = ActorError::unchecked(*code, format!("injected blockstore {op} failure"));
+            *slot = None;
+            Err(err.into())
+        }
+        Some((remaining, _)) => {
+            *remaining -= 1;
+            Ok(())
+        }
+        None => Ok(()),
     }
 }
 
 impl Blockstore for MemoryBlockstore {
     fn get(&self, cid: &Cid) -> Result<Option<Vec<u8>>> {
+        injected_failure(&self.fail_get, "get")?;
         let mut stats = self.stats.borrow_mut();
         stats.r += 1;
 
@@ -55,6 +166,7 @@ impl Blockstore for MemoryBlockstore {
     }
 
     fn put_keyed(&self, k: &Cid, block: &[u8]) -> Result<()> {
+        injected_failure(&self.fail_put, "put")?;
         let mut stats = self.stats.borrow_mut();
         stats.w += 1;
         stats.bw += block.len();
@@ -107,4 +219,23 @@ mod tests {
         );
         assert_eq!(*tr_store.stats.borrow(), BSStats { r: 4, br: total_len, w: 3, bw: total_len });
     }
+
+    #[test]
+    fn tracking_wrapper_counts_per_operation() {
+        let inner = MemoryBlockstore::new();
+        let block = Block::new(0x55, &b"foobar"[..]);
+        let cid = inner.put(Code::Blake2b256, &block).unwrap();
+
+        let tr_store = TrackingBlockstore::new(&inner);
+        let before = tr_store.stats();
+        assert_eq!(tr_store.get(&cid).unwrap().as_deref(), Some(block.data));
+        tr_store.put(Code::Sha2_256, &Block::new(0x55, &b"b2"[..])).unwrap();
+        assert_eq!(BSStats { r: 1, br: block.len(), w: 1, bw: 2 }, tr_store.take_stats() - before);
+        assert_eq!(BSStats::default(), tr_store.stats());
+        // The wrapped store saw the same operations, plus the initial put.
+        assert_eq!(
+            BSStats { r: 1, br: block.len(), w: 2, bw: block.len() + 2 },
+            *inner.stats.borrow()
+        );
+    }
 }
@@ -0,0 +1,250 @@
+use crate::{ActorError, AsActorError};
+use cid::Cid;
+use fvm_ipld_amt as amt;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_shared::error::ExitCode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Wraps an AMT to provide a convenient array API.
+/// Any errors are returned with exit code indicating illegal state.
+/// The name is not persisted in state, but adorns any error messages.
+pub struct Array2<BS, V>
+where
+    BS: Blockstore,
+    V: DeserializeOwned + Serialize,
+{
+    amt: amt::Amt<V, BS>,
+    name: &'static str,
+}
+
+impl<BS, V> Array2<BS, V>
+where
+    BS: Blockstore,
+    V: DeserializeOwned + Serialize,
+{
+    /// Creates a new, empty array.
+    pub fn empty(store: BS, bit_width: u32, name: &'static str) -> Self {
+        Self { amt: amt::Amt::new_with_bit_width(store, bit_width), name }
+    }
+
+    /// Creates a new empty array and flushes it to the store.
+    /// Returns the CID of the empty array root.
+    pub fn flush_empty(store: BS, bit_width: u32) -> Result<Cid, ActorError> {
+        Self::empty(store, bit_width, "empty").flush()
+    }
+
+    /// Loads an array from the store.
+    /// The bit width is persisted in the root node, so need not be provided.
+    pub fn load(store: BS, root: &Cid, name: &'static str) -> Result<Self, ActorError> {
+        Ok(Self {
+            amt: amt::Amt::load(root, store)
+                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("failed to load AMT '{}'", name)
+                })?,
+            name,
+        })
+    }
+
+    /// Flushes the array's contents to the store.
+    /// Returns the root node CID.
+    pub fn flush(&mut self) -> Result<Cid, ActorError> {
+        self.amt.flush().with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to flush AMT '{}'", self.name)
+        })
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn count(&self) -> u64 {
+        self.amt.count()
+    }
+
+    /// Returns whether the array is empty.
+    pub fn is_empty(&self) -> bool {
+        self.amt.count() == 0
+    }
+
+    /// Returns a reference to the value at an index, if present.
+    pub fn get(&self, index: u64) -> Result<Option<&V>, ActorError> {
+        self.amt.get(index).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to get index {index} from AMT '{}'", self.name)
+        })
+    }
+
+    /// Sets the value at an index, replacing any existing value.
+    pub fn set(&mut self, index: u64, value: V) -> Result<(), ActorError> {
+        self.amt.set(index, value).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to set index {index} in AMT '{}'", self.name)
+        })
+    }
+
+    /// Removes the value at an index.
+    /// Returns the value previously at the index, if any.
+    pub fn delete(&mut self, index: u64) -> Result<Option<V>, ActorError> {
+        self.amt.delete(index).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to delete index {index} from AMT '{}'", self.name)
+        })
+    }
+
+    /// Sets many values at once.
+    /// Values are written in ascending index order so that consecutive writes share the
+    /// traversal of interior nodes. If an index appears more than once, the last value wins.
+    pub fn batch_set(
+        &mut self,
+        values: impl IntoIterator<Item = (u64, V)>,
+    ) -> Result<(), ActorError> {
+        let mut values: Vec<_> = values.into_iter().collect();
+        // Stable sort keeps the last of any duplicate indices last.
+        values.sort_by_key(|(index, _)| *index);
+        values.into_iter().try_for_each(|(index, value)| self.set(index, value))
+    }
+
+    /// Removes many values at once, in ascending index order.
+    /// If `strict` is set, it is an error for any index to be absent.
+    /// Returns whether any value was removed.
+    pub fn batch_delete(
+        &mut self,
+        indices: impl IntoIterator<Item = u64>,
+        strict: bool,
+    ) -> Result<bool, ActorError> {
+        self.amt
+            .batch_delete(indices, strict)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to batch delete from AMT '{}'", self.name)
+            })
+    }
+
+    /// Returns references to the values at many indices, in the order requested.
+    /// Lookups are performed in ascending index order.
+    pub fn get_many(&self, indices: &[u64]) -> Result<Vec<Option<&V>>, ActorError> {
+        let mut order: Vec<usize> = (0..indices.len()).collect();
+        order.sort_by_key(|&i| indices[i]);
+        let mut found = vec![None; indices.len()];
+        for i in order {
+            found[i] = self.get(indices[i])?;
+        }
+        Ok(found)
+    }
+
+    /// Iterates over all index-value pairs in the array, in index order.
+    pub fn for_each<F>(&self, mut f: F) -> Result<(), ActorError>
+    where
+        F: FnMut(u64, &V) -> Result<(), ActorError>,
+    {
+        for kv in &self.amt {
+            let (k, v) = kv.with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("error traversing AMT {}", self.name)
+            })?;
+            f(k, v)?;
+        }
+        Ok(())
+    }
+
+    /// Iterates over at most `max` index-value pairs in the array, beginning at the first index
+    /// at or after `start_at` if provided, otherwise at the first index.
+    /// Returns the number of entries visited and the index at which to resume, if any remain.
+    /// Unlike a map's, the resume index need not be present when passed to a later call.
+    pub fn for_each_ranged<F>(
+        &self,
+        start_at: Option<u64>,
+        max: Option<u64>,
+        mut f: F,
+    ) -> Result<(u64, Option<u64>), ActorError>
+    where
+        F: FnMut(u64, &V) -> Result<(), ActorError>,
+    {
+        let iter = self
+            .amt
+            .iter_from(start_at.unwrap_or(0))
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("error traversing AMT {}", self.name)
+            })?;
+        let mut traversed = 0u64;
+        for kv in iter {
+            let (k, v) = kv.with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("error traversing AMT {}", self.name)
+            })?;
+            if max.is_some_and(|max| traversed >= max) {
+                return Ok((traversed, Some(k)));
+            }
+            f(k, v)?;
+            traversed += 1;
+        }
+        Ok((traversed, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_blockstores::MemoryBlockstore;
+
+    #[test]
+    fn basic_set_get_delete() {
+        let bs = MemoryBlockstore::new();
+        let mut a = Array2::<_, String>::empty(&bs, 5, "test");
+        assert!(a.is_empty());
+        a.set(1234, "1234".to_string()).unwrap();
+        assert!(a.get(2222).unwrap().is_none());
+        assert_eq!(&"1234".to_string(), a.get(1234).unwrap().unwrap());
+        assert_eq!(1, a.count());
+
+        let root = a.flush().unwrap();
+        let mut a = Array2::<_, String>::load(&bs, &root, "test").unwrap();
+        assert_eq!(Some("1234".to_string()), a.delete(1234).unwrap());
+        assert_eq!(None, a.delete(1234).unwrap());
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn batch_operations() {
+        let bs = MemoryBlockstore::new();
+        let mut a = Array2::<_, String>::empty(&bs, 3, "test");
+        a.batch_set([(30, "a".to_string()), (10, "b".to_string()), (30, "c".to_string())]).unwrap();
+        assert_eq!(2, a.count());
+
+        let found = a.get_many(&[30, 20, 10]).unwrap();
+        assert_eq!(vec![Some(&"c".to_string()), None, Some(&"b".to_string())], found);
+
+        assert!(a.batch_delete([20], true).is_err());
+        assert!(a.batch_delete([10, 20], false).unwrap());
+        assert!(!a.batch_delete([10, 20], false).unwrap());
+        assert_eq!(1, a.count());
+    }
+
+    #[test]
+    fn for_each_callback_exitcode_propagates() {
+        let bs = MemoryBlockstore::new();
+        let mut a = Array2::<_, String>::empty(bs, 5, "test");
+        a.set(1234, "1234".to_string()).unwrap();
+        let res = a.for_each(|_, _| Err(ActorError::forbidden("test".to_string())));
+        assert_eq!(res.unwrap_err(), ActorError::forbidden("test".to_string()));
+    }
+
+    #[test]
+    fn for_each_ranged_pages() {
+        let bs = MemoryBlockstore::new();
+        let mut a = Array2::<_, u64>::empty(bs, 3, "test");
+        for i in 0..25u64 {
+            a.set(i * 2, i).unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (count, next) = a
+                .for_each_ranged(cursor, Some(10), |k, v| {
+                    assert_eq!(k, v * 2);
+                    seen.push(*v);
+                    Ok(())
+                })
+                .unwrap();
+            assert!(count <= 10);
+            cursor = next;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!((0..25).collect::<Vec<_>>(), seen);
+    }
+}
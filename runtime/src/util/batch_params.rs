@@ -0,0 +1,57 @@
+//! Building blocks for the parameters of methods acting on batches of sectors or claims.
+//! Actors alias these rather than defining their own, so methods taking the same shape of
+//! batch agree on it, and on its encoding.
+
+use cid::Cid;
+use fvm_ipld_bitfield::BitField;
+use fvm_ipld_encoding::tuple::*;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::SectorNumber;
+use fvm_shared::ActorID;
+
+/// A selection of sectors within one partition of a miner's deadline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct PartitionSectors {
+    /// The deadline to which the sectors are assigned, in range [0..WPoStPeriodDeadlines)
+    pub deadline: u64,
+    /// Partition index within the deadline containing the sectors.
+    pub partition: u64,
+    /// Sectors in the partition being selected.
+    pub sectors: BitField,
+}
+
+/// A sector with verified claims, and which of them to keep when updating the sector.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct SectorClaim {
+    pub sector_number: SectorNumber,
+    /// IDs of claims to carry over.
+    pub maintain_claims: Vec<u64>,
+    /// IDs of claims to drop, which must have reached their minimum term.
+    pub drop_claims: Vec<u64>,
+}
+
+/// A selection of claims held by one provider.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ProviderClaims {
+    pub provider: ActorID,
+    /// IDs of the provider's claims.
+    pub claim_ids: Vec<u64>,
+}
+
+/// Allocations to be claimed by a provider for the pieces of one sector.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct SectorAllocationClaims {
+    pub sector: SectorNumber,
+    pub expiry: ChainEpoch,
+    pub claims: Vec<AllocationClaim>,
+}
+
+/// An allocation to be claimed for a piece, which must match the allocation's terms.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct AllocationClaim {
+    pub client: ActorID,
+    pub allocation_id: u64,
+    pub data: Cid,
+    pub size: PaddedPieceSize,
+}
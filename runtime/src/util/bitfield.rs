@@ -0,0 +1,142 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_ipld_bitfield::BitField;
+
+use crate::runtime::Policy;
+use crate::{actor_error, ActorError};
+
+/// Bounds on the size of a bitfield received as a parameter.
+///
+/// A run-length encoded bitfield can describe a very large set in a few bytes, so the cost of
+/// operating on one must be bounded by checking its decoded size rather than its encoded length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitFieldLimits {
+    /// The maximum number of set bits.
+    pub max_bits: u64,
+    /// The maximum number of runs of consecutive set bits.
+    pub max_runs: u64,
+}
+
+impl BitFieldLimits {
+    /// Limits for a bitfield of sector numbers addressed in a single invocation.
+    pub fn addressed_sectors(policy: &Policy) -> Self {
+        BitFieldLimits {
+            max_bits: policy.addressed_sectors_max,
+            max_runs: policy.addressed_sectors_max,
+        }
+    }
+}
+
+/// Checks that a bitfield is within the limits, returning an illegal argument error if not.
+pub fn validate_bitfield(bf: &BitField, limits: BitFieldLimits) -> Result<(), ActorError> {
+    let runs = bf.ranges().count() as u64;
+    if runs > limits.max_runs {
+        return Err(actor_error!(
+            illegal_argument,
+            "bitfield has too many runs: {} > {}",
+            runs,
+            limits.max_runs
+        ));
+    }
+    let bits = bf.len();
+    if bits > limits.max_bits {
+        return Err(actor_error!(
+            illegal_argument,
+            "bitfield has too many bits set: {} > {}",
+            bits,
+            limits.max_bits
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the bits set in `a` but not in `b`, after checking both operands against the limits.
+pub fn checked_difference(
+    a: &BitField,
+    b: &BitField,
+    limits: BitFieldLimits,
+) -> Result<BitField, ActorError> {
+    validate_bitfield(a, limits)?;
+    validate_bitfield(b, limits)?;
+    Ok(a - b)
+}
+
+/// Returns the bits set in both `a` and `b`, after checking both operands against the limits.
+pub fn checked_intersection(
+    a: &BitField,
+    b: &BitField,
+    limits: BitFieldLimits,
+) -> Result<BitField, ActorError> {
+    validate_bitfield(a, limits)?;
+    validate_bitfield(b, limits)?;
+    Ok(a & b)
+}
+
+/// Returns the bits set in either `a` or `b`, failing if the result exceeds the limits.
+pub fn checked_union(
+    a: &BitField,
+    b: &BitField,
+    limits: BitFieldLimits,
+) -> Result<BitField, ActorError> {
+    validate_bitfield(a, limits)?;
+    validate_bitfield(b, limits)?;
+    let union = a | b;
+    validate_bitfield(&union, limits)?;
+    Ok(union)
+}
+
+#[cfg(test)]
+mod test {
+    use fvm_ipld_bitfield::iter::Ranges;
+    use fvm_ipld_bitfield::BitField;
+    use fvm_shared::error::ExitCode;
+    use std::iter;
+
+    use super::{
+        checked_difference, checked_intersection, checked_union, validate_bitfield, BitFieldLimits,
+    };
+
+    const LIMITS: BitFieldLimits = BitFieldLimits { max_bits: 10, max_runs: 3 };
+
+    #[test]
+    fn validate_limits() {
+        validate_bitfield(&BitField::new(), LIMITS).unwrap();
+        validate_bitfield(&BitField::try_from_bits(0..10).unwrap(), LIMITS).unwrap();
+        validate_bitfield(&BitField::try_from_bits([0, 2, 4]).unwrap(), LIMITS).unwrap();
+
+        // One run too many, although few bits are set.
+        let err =
+            validate_bitfield(&BitField::try_from_bits([0, 2, 4, 6]).unwrap(), LIMITS).unwrap_err();
+        assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, err.exit_code());
+
+        // A single run that is too long.
+        let bf = BitField::from_ranges(Ranges::new(iter::once(0..u64::MAX)));
+        let err = validate_bitfield(&bf, LIMITS).unwrap_err();
+        assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, err.exit_code());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = BitField::try_from_bits(0..6).unwrap();
+        let b = BitField::try_from_bits(4..10).unwrap();
+        assert_eq!(
+            BitField::try_from_bits(0..4).unwrap(),
+            checked_difference(&a, &b, LIMITS).unwrap()
+        );
+        assert_eq!(
+            BitField::try_from_bits(4..6).unwrap(),
+            checked_intersection(&a, &b, LIMITS).unwrap()
+        );
+        assert_eq!(BitField::try_from_bits(0..10).unwrap(), checked_union(&a, &b, LIMITS).unwrap());
+
+        // Each operand is within the limits but their union is not.
+        let c = BitField::try_from_bits(6..11).unwrap();
+        let err = checked_union(&a, &c, LIMITS).unwrap_err();
+        assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, err.exit_code());
+
+        let huge = BitField::from_ranges(Ranges::new(iter::once(0..u64::MAX)));
+        checked_difference(&huge, &a, LIMITS).unwrap_err();
+        checked_intersection(&a, &huge, LIMITS).unwrap_err();
+    }
+}
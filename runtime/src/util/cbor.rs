@@ -1,4 +1,5 @@
-use fvm_ipld_encoding::{to_vec, RawBytes};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::{to_vec, RawBytes, CBOR, DAG_CBOR};
 use serde::{de, ser};
 
 use crate::ActorError;
@@ -33,3 +34,135 @@ pub fn deserialize<O: de::DeserializeOwned>(v: &RawBytes, desc: &str) -> Result<
 pub fn deserialize_params<O: de::DeserializeOwned>(params: &RawBytes) -> Result<O, ActorError> {
     deserialize(params, "method parameters")
 }
+
+/// Deserialises a block as a structure, first checking that a CBOR or DAG-CBOR block is encoded
+/// canonically (see [`check_canonical`]), so that every implementation accepts the same encodings.
+pub fn deserialize_strict<O: de::DeserializeOwned>(block: &IpldBlock) -> Result<O, ActorError> {
+    if block.codec == CBOR || block.codec == DAG_CBOR {
+        check_canonical(&block.data).map_err(|e| {
+            ActorError::serialization(format!("non-canonical encoding of parameters: {}", e))
+        })?;
+    }
+    Ok(block.deserialize()?)
+}
+
+/// Maximum nesting depth of a canonically encoded item.
+const MAX_DEPTH: usize = 256;
+
+// CBOR major types.
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+const MAJOR_SIMPLE: u8 = 7;
+
+/// The only tag permitted, marking a CID.
+const CID_TAG: u64 = 42;
+
+/// Checks that bytes hold exactly one item in canonical DAG-CBOR encoding:
+/// - lengths are definite, and integers and lengths are minimally encoded;
+/// - floats are 64-bit, and the only simple values are `false`, `true` and `null`;
+/// - the only tag is that of a CID;
+/// - map keys are strings, sorted by length and then bytewise, without duplicates;
+/// - no bytes follow the item.
+pub fn check_canonical(bytes: &[u8]) -> Result<(), String> {
+    let end = check_item(bytes, 0, 0)?;
+    if end != bytes.len() {
+        return Err(format!("{} trailing bytes", bytes.len() - end));
+    }
+    Ok(())
+}
+
+/// Checks the item at `pos`, returning the position following it.
+fn check_item(bytes: &[u8], pos: usize, depth: usize) -> Result<usize, String> {
+    if depth > MAX_DEPTH {
+        return Err("nesting too deep".into());
+    }
+    let initial = *bytes.get(pos).ok_or("unexpected end of input")?;
+    let (major, info) = (initial >> 5, initial & 0x1f);
+    if major == MAJOR_SIMPLE {
+        return match info {
+            20..=22 => Ok(pos + 1),
+            27 => take(bytes, pos + 1, 8),
+            25 | 26 => Err(format!("float of less than 64 bits at {}", pos)),
+            _ => Err(format!("unsupported simple value {} at {}", info, pos)),
+        };
+    }
+    let (value, next) = read_argument(bytes, pos, info)?;
+    match major {
+        MAJOR_UNSIGNED | MAJOR_NEGATIVE => Ok(next),
+        MAJOR_BYTES => take(bytes, next, value),
+        MAJOR_TEXT => {
+            let end = take(bytes, next, value)?;
+            std::str::from_utf8(&bytes[next..end])
+                .map_err(|_| format!("invalid UTF-8 string at {}", pos))?;
+            Ok(end)
+        }
+        MAJOR_ARRAY => {
+            let mut pos = next;
+            for _ in 0..value {
+                pos = check_item(bytes, pos, depth + 1)?;
+            }
+            Ok(pos)
+        }
+        MAJOR_MAP => {
+            let mut pos = next;
+            let mut prev_key: Option<&[u8]> = None;
+            for _ in 0..value {
+                let key_start = pos;
+                if bytes.get(pos).map(|b| b >> 5) != Some(MAJOR_TEXT) {
+                    return Err(format!("map key at {} is not a string", pos));
+                }
+                pos = check_item(bytes, pos, depth + 1)?;
+                let key = &bytes[key_start..pos];
+                // Minimal encoding makes the encoded keys' order that of their lengths, then bytes.
+                if let Some(prev) = prev_key {
+                    if (prev.len(), prev) >= (key.len(), key) {
+                        return Err(format!(
+                            "map key at {} is duplicated or out of order",
+                            key_start
+                        ));
+                    }
+                }
+                prev_key = Some(key);
+                pos = check_item(bytes, pos, depth + 1)?;
+            }
+            Ok(pos)
+        }
+        MAJOR_TAG if value == CID_TAG => check_item(bytes, next, depth + 1),
+        MAJOR_TAG => Err(format!("unsupported tag {} at {}", value, pos)),
+        _ => unreachable!("major type is 3 bits"),
+    }
+}
+
+/// Reads the argument of the item at `pos` whose initial byte has additional info `info`,
+/// returning it and the position following it.
+fn read_argument(bytes: &[u8], pos: usize, info: u8) -> Result<(u64, usize), String> {
+    let (len, min) = match info {
+        0..=23 => return Ok((info as u64, pos + 1)),
+        24 => (1, 24),
+        25 => (2, 1 << 8),
+        26 => (4, 1 << 16),
+        27 => (8, 1 << 32),
+        31 => return Err(format!("indefinite length at {}", pos)),
+        _ => return Err(format!("reserved additional info {} at {}", info, pos)),
+    };
+    let end = take(bytes, pos + 1, len)?;
+    let value = bytes[pos + 1..end].iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+    if value < min {
+        return Err(format!("non-minimal integer encoding at {}", pos));
+    }
+    Ok((value, end))
+}
+
+/// Returns the position `len` bytes after `pos`, if the input is that long.
+fn take(bytes: &[u8], pos: usize, len: u64) -> Result<usize, String> {
+    usize::try_from(len)
+        .ok()
+        .and_then(|len| pos.checked_add(len))
+        .filter(|end| *end <= bytes.len())
+        .ok_or_else(|| "unexpected end of input".to_string())
+}
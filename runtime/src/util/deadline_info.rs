@@ -1,9 +1,17 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+//! Proving period and deadline calculations for storage miners.
+//!
+//! A miner's proving period is divided into a fixed number of deadlines, each lasting one
+//! challenge window. Each miner's proving periods are offset from multiples of the proving
+//! period length by a miner-specific offset. These calculations are shared so that actors and
+//! tools outside the miner actor compute deadlines in exactly the same way.
+
 use fvm_shared::clock::ChainEpoch;
 use serde::{Deserialize, Serialize};
 
+use crate::runtime::Policy;
 use crate::QuantSpec;
 
 /// Deadline calculations with respect to a current epoch.
@@ -158,3 +166,73 @@ impl DeadlineInfo {
         QuantSpec { unit: self.w_post_proving_period, offset: self.last() }
     }
 }
+
+/// Returns deadline calculations for a deadline in the proving period starting at
+/// `proving_period_start`.
+pub fn new_deadline_info(
+    policy: &Policy,
+    proving_period_start: ChainEpoch,
+    deadline_idx: u64,
+    current_epoch: ChainEpoch,
+) -> DeadlineInfo {
+    DeadlineInfo::new(
+        proving_period_start,
+        deadline_idx,
+        current_epoch,
+        policy.wpost_period_deadlines,
+        policy.wpost_proving_period,
+        policy.wpost_challenge_window,
+        policy.wpost_challenge_lookback,
+        policy.fault_declaration_cutoff,
+    )
+}
+
+/// Returns the quantization spec for expirations and other events due at a deadline,
+/// which rounds to the last epoch of each instance of the deadline.
+pub fn quant_spec_for_deadline(policy: &Policy, di: &DeadlineInfo) -> QuantSpec {
+    QuantSpec { unit: policy.wpost_proving_period, offset: di.last() }
+}
+
+// Determine current period start and deadline index directly from current epoch and
+// the offset implied by the proving period. This works correctly even for the state
+// of a miner actor without an active deadline cron
+pub fn new_deadline_info_from_offset_and_epoch(
+    policy: &Policy,
+    period_start_seed: ChainEpoch,
+    current_epoch: ChainEpoch,
+) -> DeadlineInfo {
+    let q = QuantSpec { unit: policy.wpost_proving_period, offset: period_start_seed };
+    let current_period_start = q.quantize_down(current_epoch);
+    let current_deadline_idx =
+        ((current_epoch - current_period_start) / policy.wpost_challenge_window) as u64;
+    new_deadline_info(policy, current_period_start, current_deadline_idx, current_epoch)
+}
+
+/// Computes the epoch at which a proving period should start such that it is greater than the current epoch, and
+/// has a defined offset from being an exact multiple of WPoStProvingPeriod.
+/// A miner is exempt from Winow PoSt until the first full proving period starts.
+pub fn current_proving_period_start(
+    policy: &Policy,
+    current_epoch: ChainEpoch,
+    offset: ChainEpoch,
+) -> ChainEpoch {
+    let curr_modulus = current_epoch % policy.wpost_proving_period;
+
+    let period_progress = if curr_modulus >= offset {
+        curr_modulus - offset
+    } else {
+        policy.wpost_proving_period - (offset - curr_modulus)
+    };
+
+    current_epoch - period_progress
+}
+
+/// Returns the index of the deadline open at the current epoch, in a proving period
+/// starting at `period_start`.
+pub fn current_deadline_index(
+    policy: &Policy,
+    current_epoch: ChainEpoch,
+    period_start: ChainEpoch,
+) -> u64 {
+    ((current_epoch - period_start) / policy.wpost_challenge_window) as u64
+}
@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+
+use cid::Cid;
+use fvm_ipld_amt as amt;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::{BytesDe, CborStore};
+use fvm_shared::error::ExitCode;
+use ipld_core::ipld::Ipld;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{ActorError, AsActorError, MapKey};
+
+/// A difference in one entry between two versions of a map or array.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change<K, V> {
+    Added(K, V),
+    Removed(K, V),
+    /// The key, with its old and new values.
+    Modified(K, V, V),
+}
+
+impl<K, V> Change<K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Change::Added(k, _) | Change::Removed(k, _) | Change::Modified(k, _, _) => k,
+        }
+    }
+}
+
+/// Returns the entries which differ between two HAMTs with the same configuration, such as
+/// two versions of a [`Map2`](crate::Map2).
+/// Subtrees with the same CID in both are skipped without being loaded, so the cost is
+/// proportional to the size of the difference rather than of the maps.
+/// Changes are returned in the HAMT's internal order, which is deterministic for given roots.
+pub fn diff_map<BS, K, V>(
+    store: &BS,
+    old: &Cid,
+    new: &Cid,
+    name: &'static str,
+) -> Result<Vec<Change<K, V>>, ActorError>
+where
+    BS: Blockstore,
+    K: MapKey,
+    V: DeserializeOwned,
+{
+    let mut changes = Vec::new();
+    if old != new {
+        let ctx = HamtDiff { store, name };
+        ctx.diff_nodes(&ctx.load(old)?, &ctx.load(new)?, &mut changes)?;
+    }
+    changes
+        .into_iter()
+        .map(|change| {
+            let key = |k: Vec<u8>| {
+                K::from_bytes(&k).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("invalid key in HAMT '{}'", name)
+                })
+            };
+            let value = |v: Ipld| {
+                ipld_core::serde::from_ipld::<V>(v)
+                    .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                        format!("failed to decode value in HAMT '{}'", name)
+                    })
+            };
+            Ok(match change {
+                Change::Added(k, v) => Change::Added(key(k)?, value(v)?),
+                Change::Removed(k, v) => Change::Removed(key(k)?, value(v)?),
+                Change::Modified(k, o, n) => Change::Modified(key(k)?, value(o)?, value(n)?),
+            })
+        })
+        .collect()
+}
+
+/// Returns the entries which differ between two AMTs with the same bit width, such as
+/// two versions of an [`Array2`](crate::Array2).
+/// Subtrees with the same CID in both are skipped without being loaded.
+/// Changes are returned in index order.
+pub fn diff_array<BS, V>(
+    store: &BS,
+    old: &Cid,
+    new: &Cid,
+    name: &'static str,
+) -> Result<Vec<Change<u64, V>>, ActorError>
+where
+    BS: Blockstore,
+    V: DeserializeOwned + Serialize + Clone,
+{
+    if old == new {
+        return Ok(Vec::new());
+    }
+    let load = |root: &Cid| {
+        amt::Amt::<V, _>::load(root, store).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to load AMT '{}'", name)
+        })
+    };
+    let changes = amt::diff(&load(old)?, &load(new)?)
+        .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to diff AMT '{}'", name)
+        })?;
+    Ok(changes
+        .into_iter()
+        .filter_map(|c| match (c.before, c.after) {
+            (None, Some(v)) => Some(Change::Added(c.key, v)),
+            (Some(v), None) => Some(Change::Removed(c.key, v)),
+            (Some(o), Some(n)) => Some(Change::Modified(c.key, o, n)),
+            (None, None) => None,
+        })
+        .collect())
+}
+
+/// A HAMT node, as a map from bit position to pointer.
+/// Nodes are read in their serialized form, `[bitfield, [pointer...]]`, where each pointer
+/// is a link to a child node, or a bucket of `[key, value]` pairs.
+type Node = BTreeMap<usize, Ipld>;
+
+struct HamtDiff<'a, BS> {
+    store: &'a BS,
+    name: &'static str,
+}
+
+impl<BS: Blockstore> HamtDiff<'_, BS> {
+    fn load(&self, cid: &Cid) -> Result<Node, ActorError> {
+        let (BytesDe(bitfield), pointers): (BytesDe, Vec<Ipld>) = self
+            .store
+            .get_cbor(cid)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to load node {} of HAMT '{}'", cid, self.name)
+            })?
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("missing node {} of HAMT '{}'", cid, self.name)
+            })?;
+        if pointers.len() != bitfield.iter().map(|b| b.count_ones() as usize).sum::<usize>() {
+            return Err(ActorError::illegal_state(format!(
+                "pointers don't match bitfield in node {} of HAMT '{}'",
+                cid, self.name
+            )));
+        }
+        // The bitfield is big-endian; pointers are ordered by ascending bit position.
+        let positions = (0..bitfield.len() * 8)
+            .filter(|i| bitfield[bitfield.len() - 1 - i / 8] & (1 << (i % 8)) != 0);
+        Ok(positions.zip(pointers).collect())
+    }
+
+    fn diff_nodes(
+        &self,
+        old: &Node,
+        new: &Node,
+        changes: &mut Vec<Change<Vec<u8>, Ipld>>,
+    ) -> Result<(), ActorError> {
+        let mut positions: Vec<_> = old.keys().chain(new.keys()).collect();
+        positions.sort();
+        positions.dedup();
+        for pos in positions {
+            match (old.get(pos), new.get(pos)) {
+                (Some(o), Some(n)) if o == n => {}
+                (Some(Ipld::Link(o)), Some(Ipld::Link(n))) => {
+                    self.diff_nodes(&self.load(o)?, &self.load(n)?, changes)?
+                }
+                (o, n) => {
+                    // A bucket on either side is compared against all entries on the other.
+                    let mut old_entries = BTreeMap::new();
+                    let mut new_entries = BTreeMap::new();
+                    if let Some(o) = o {
+                        self.collect(o, &mut old_entries)?;
+                    }
+                    if let Some(n) = n {
+                        self.collect(n, &mut new_entries)?;
+                    }
+                    for (k, o) in old_entries {
+                        match new_entries.remove(&k) {
+                            Some(n) if n == o => {}
+                            Some(n) => changes.push(Change::Modified(k, o, n)),
+                            None => changes.push(Change::Removed(k, o)),
+                        }
+                    }
+                    changes.extend(new_entries.into_iter().map(|(k, n)| Change::Added(k, n)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn collect(
+        &self,
+        pointer: &Ipld,
+        entries: &mut BTreeMap<Vec<u8>, Ipld>,
+    ) -> Result<(), ActorError> {
+        match pointer {
+            Ipld::Link(cid) => {
+                for child in self.load(cid)?.values() {
+                    self.collect(child, entries)?;
+                }
+            }
+            Ipld::List(bucket) => {
+                for kv in bucket {
+                    match kv {
+                        Ipld::List(kv) if kv.len() == 2 => match &kv[0] {
+                            Ipld::Bytes(k) => {
+                                entries.insert(k.clone(), kv[1].clone());
+                            }
+                            _ => return Err(self.malformed()),
+                        },
+                        _ => return Err(self.malformed()),
+                    }
+                }
+            }
+            _ => return Err(self.malformed()),
+        }
+        Ok(())
+    }
+
+    fn malformed(&self) -> ActorError {
+        ActorError::illegal_state(format!("malformed pointer in HAMT '{}'", self.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_blockstores::MemoryBlockstore;
+    use crate::{Array2, Map2, DEFAULT_HAMT_CONFIG};
+
+    #[test]
+    fn diff_map_reports_changes() {
+        let bs = MemoryBlockstore::new();
+        let mut m = Map2::<_, u64, u64>::empty(&bs, DEFAULT_HAMT_CONFIG, "m");
+        for i in 0..500u64 {
+            m.set(&i, i).unwrap();
+        }
+        let old = m.flush().unwrap();
+        assert!(diff_map::<_, u64, u64>(&bs, &old, &old, "m").unwrap().is_empty());
+
+        m.set(&7, 70).unwrap();
+        m.delete(&300).unwrap();
+        m.set(&1000, 1).unwrap();
+        let new = m.flush().unwrap();
+
+        let mut changes = diff_map::<_, u64, u64>(&bs, &old, &new, "m").unwrap();
+        changes.sort_by_key(|c| *c.key());
+        assert_eq!(
+            vec![Change::Modified(7, 7, 70), Change::Removed(300, 300), Change::Added(1000, 1)],
+            changes
+        );
+
+        // Diffing against an empty map yields every entry.
+        let empty = Map2::<_, u64, u64>::flush_empty(&bs, DEFAULT_HAMT_CONFIG).unwrap();
+        let added = diff_map::<_, u64, u64>(&bs, &empty, &new, "m").unwrap();
+        assert_eq!(500, added.len());
+        assert!(added.iter().all(|c| matches!(c, Change::Added(..))));
+    }
+
+    #[test]
+    fn diff_array_reports_changes() {
+        let bs = MemoryBlockstore::new();
+        let mut a = Array2::<_, String>::empty(&bs, 3, "a");
+        for i in 0..100u64 {
+            a.set(i, i.to_string()).unwrap();
+        }
+        let old = a.flush().unwrap();
+        a.set(5, "five".to_string()).unwrap();
+        a.delete(50).unwrap();
+        a.set(1000, "new".to_string()).unwrap();
+        let new = a.flush().unwrap();
+
+        assert_eq!(
+            vec![
+                Change::Modified(5, "5".to_string(), "five".to_string()),
+                Change::Removed(50, "50".to_string()),
+                Change::Added(1000, "new".to_string()),
+            ],
+            diff_array::<_, String>(&bs, &old, &new, "a").unwrap()
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{actor_error, ActorError};
+
+/// Counters for deal, allocation and actor IDs at or above this are at risk of overflow
+/// wherever the IDs are handled as signed 64-bit integers, as sector numbers are.
+/// The state checker flags counters past it, long before they could wrap.
+pub const ID_OVERFLOW_RISK_THRESHOLD: u64 = i64::MAX as u64;
+
+/// Takes `count` consecutive IDs from a counter, returning the first and advancing the counter
+/// past the last. Fails rather than wrapping if the counter would overflow.
+pub fn take_ids(counter: &mut u64, count: u64, kind: &str) -> Result<u64, ActorError> {
+    let next = counter.checked_add(count).ok_or_else(|| {
+        actor_error!(
+            illegal_state,
+            "{} IDs exhausted: cannot take {} from {}",
+            kind,
+            count,
+            counter
+        )
+    })?;
+    Ok(std::mem::replace(counter, next))
+}
+
+/// Takes the next ID from a counter, advancing it. Fails rather than wrapping if the counter
+/// would overflow.
+pub fn take_id(counter: &mut u64, kind: &str) -> Result<u64, ActorError> {
+    take_ids(counter, 1, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use fvm_shared::error::ExitCode;
+
+    use super::*;
+
+    #[test]
+    fn ids_are_taken_in_order() {
+        let mut counter = 5;
+        assert_eq!(5, take_id(&mut counter, "test").unwrap());
+        assert_eq!(6, take_ids(&mut counter, 3, "test").unwrap());
+        assert_eq!(9, counter);
+        assert_eq!(9, take_ids(&mut counter, 0, "test").unwrap());
+        assert_eq!(9, counter);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let mut counter = u64::MAX - 1;
+        assert_eq!(u64::MAX - 1, take_id(&mut counter, "test").unwrap());
+        let err = take_id(&mut counter, "test").unwrap_err();
+        assert_eq!(ExitCode::USR_ILLEGAL_STATE, err.exit_code());
+        assert_eq!("test IDs exhausted: cannot take 1 from 18446744073709551615", err.msg());
+        assert_eq!(u64::MAX, counter);
+        assert_eq!(u64::MAX, take_ids(&mut counter, 0, "test").unwrap());
+    }
+}
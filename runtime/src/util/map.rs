@@ -149,7 +149,42 @@ where
             })
     }
 
+    /// Inserts many key-value pairs.
+    /// HAMT nodes are addressed by key hash, so the order of insertion has no effect on the
+    /// resulting root or the nodes traversed. If a key appears more than once, the last value wins.
+    pub fn batch_set(&mut self, entries: impl IntoIterator<Item = (K, V)>) -> Result<(), ActorError>
+    where
+        V: PartialEq,
+    {
+        entries.into_iter().try_for_each(|(k, v)| self.set(&k, v).map(|_| ()))
+    }
+
+    /// Removes many keys, returning the value previously associated with each, in the order given.
+    pub fn batch_delete<'a>(
+        &mut self,
+        keys: impl IntoIterator<Item = &'a K>,
+    ) -> Result<Vec<Option<V>>, ActorError>
+    where
+        K: 'a,
+    {
+        keys.into_iter().map(|k| self.delete(k)).collect()
+    }
+
+    /// Returns references to the values associated with many keys, in the order given.
+    pub fn get_many<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a K>,
+    ) -> Result<Vec<Option<&V>>, ActorError>
+    where
+        K: 'a,
+    {
+        keys.into_iter().map(|k| self.get(k)).collect()
+    }
+
     /// Iterates over all key-value pairs in the map.
+    /// Iteration order is the HAMT's internal order, i.e. of the hashes of the keys.
+    /// It is deterministic for a given set of entries, independent of the order in which
+    /// they were inserted, but unrelated to the ordering of the keys themselves.
     pub fn for_each<F>(&self, mut f: F) -> Result<(), ActorError>
     where
         F: FnMut(K, &V) -> Result<(), ActorError>,
@@ -165,6 +200,52 @@ where
         }
         Ok(())
     }
+
+    /// Iterates over at most `max` key-value pairs in the map, beginning at `starting_key`
+    /// (inclusive) if provided, otherwise at the first key.
+    /// Iteration order is the same as [`Self::for_each`].
+    /// Returns the number of entries visited and the key at which to resume, if any remain.
+    /// Passing that key as `starting_key` to a later call continues where this one stopped,
+    /// even if the map has since changed, so long as the key itself remains present.
+    /// A starting key which is not present in the map is an error with exit code
+    /// `USR_NOT_FOUND`.
+    pub fn for_each_ranged<F>(
+        &self,
+        starting_key: Option<&K>,
+        max: Option<usize>,
+        mut f: F,
+    ) -> Result<(usize, Option<K>), ActorError>
+    where
+        F: FnMut(K, &V) -> Result<(), ActorError>,
+    {
+        let iter = match starting_key {
+            Some(key) => {
+                let k: hamt::BytesKey = key
+                    .to_bytes()
+                    .context_code(ExitCode::USR_ASSERTION_FAILED, "invalid key")?
+                    .into();
+                self.hamt.iter_from(&k).with_context_code(ExitCode::USR_NOT_FOUND, || {
+                    format!("starting key {key:?} not found in HAMT '{}'", self.name)
+                })?
+            }
+            None => self.hamt.iter(),
+        };
+        let mut traversed = 0usize;
+        for kv in iter {
+            let (k, v) = kv.with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("error traversing HAMT {}", self.name)
+            })?;
+            let k = K::from_bytes(k).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("invalid key in HAMT {}", self.name)
+            })?;
+            if max.is_some_and(|max| traversed >= max) {
+                return Ok((traversed, Some(k)));
+            }
+            f(k, v)?;
+            traversed += 1;
+        }
+        Ok((traversed, None))
+    }
 }
 
 impl MapKey for Vec<u8> {
@@ -245,6 +326,19 @@ mod tests {
         assert_eq!(&"1234".to_string(), m.get(&1234).unwrap().unwrap());
     }
 
+    #[test]
+    fn batch_operations() {
+        let bs = MemoryBlockstore::new();
+        let mut m = Map2::<_, u64, String>::empty(bs, DEFAULT_HAMT_CONFIG, "empty");
+        m.batch_set([(1, "a".to_string()), (2, "b".to_string()), (1, "c".to_string())]).unwrap();
+        assert_eq!(
+            vec![Some(&"c".to_string()), None, Some(&"b".to_string())],
+            m.get_many(&[1, 3, 2]).unwrap()
+        );
+        assert_eq!(vec![Some("b".to_string()), None], m.batch_delete(&[2, 3]).unwrap());
+        assert!(!m.contains_key(&2).unwrap());
+    }
+
     #[test]
     fn for_each_callback_exitcode_propagates() {
         let bs = MemoryBlockstore::new();
@@ -254,4 +348,67 @@ mod tests {
         assert!(res.is_err());
         assert_eq!(res.unwrap_err(), ActorError::forbidden("test".to_string()));
     }
+
+    #[test]
+    fn for_each_ranged_pages() {
+        let bs = MemoryBlockstore::new();
+        let mut m = Map2::<_, u64, u64>::empty(bs, DEFAULT_HAMT_CONFIG, "empty");
+        for i in 0..25u64 {
+            m.set(&i, i * 10).unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (count, next) = m
+                .for_each_ranged(cursor.as_ref(), Some(10), |k, v| {
+                    assert_eq!(k * 10, *v);
+                    seen.push(k);
+                    Ok(())
+                })
+                .unwrap();
+            assert!(count <= 10);
+            cursor = next;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        seen.sort();
+        assert_eq!((0..25).collect::<Vec<_>>(), seen);
+
+        // A missing starting key is an error.
+        let mut other =
+            Map2::<_, u64, u64>::empty(MemoryBlockstore::new(), DEFAULT_HAMT_CONFIG, "o");
+        other.set(&1, 1).unwrap();
+        assert!(other.for_each_ranged(Some(&2), None, |_, _| Ok(())).is_err());
+    }
+
+    #[test]
+    fn iteration_order_is_independent_of_insertion_order() {
+        let keys = |m: &Map2<_, u64, u64>| {
+            let mut keys = Vec::new();
+            m.for_each(|k, _| {
+                keys.push(k);
+                Ok(())
+            })
+            .unwrap();
+            keys
+        };
+        let mut forward = Map2::empty(MemoryBlockstore::new(), DEFAULT_HAMT_CONFIG, "forward");
+        let mut backward = Map2::empty(MemoryBlockstore::new(), DEFAULT_HAMT_CONFIG, "backward");
+        for i in 0..100u64 {
+            forward.set(&i, i).unwrap();
+            backward.set(&(99 - i), 99 - i).unwrap();
+        }
+        assert_eq!(forward.flush().unwrap(), backward.flush().unwrap());
+        assert_eq!(keys(&forward), keys(&backward));
+
+        // A page's resume key stays valid across changes to other entries.
+        let (_, next) = forward.for_each_ranged(None, Some(10), |_, _| Ok(())).unwrap();
+        let next = next.unwrap();
+        let first = keys(&forward)[0];
+        forward.delete(&first).unwrap();
+        let (count, _) = forward.for_each_ranged(Some(&next), None, |_, _| Ok(())).unwrap();
+        assert_eq!(90, count);
+    }
 }
@@ -1,24 +1,42 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+pub use self::array::*;
+pub use self::batch_params::*;
 pub use self::batch_return::*;
+pub use self::bitfield::*;
+pub use self::deadline_info::*;
+pub use self::diff::*;
 pub use self::downcast::*;
 pub use self::events::*;
+pub use self::ids::*;
 pub use self::map::*;
 pub use self::mapmap::MapMap;
 pub use self::message_accumulator::MessageAccumulator;
 pub use self::multimap::*;
+pub use self::optional_tail::*;
+pub use self::quantize::*;
 pub use self::set::Set;
 pub use self::set_multimap::SetMultimap;
 pub use self::set_multimap::SetMultimapConfig;
+pub use self::validation::*;
 
+mod array;
+mod batch_params;
 mod batch_return;
+mod bitfield;
 pub mod cbor;
+mod deadline_info;
+mod diff;
 mod downcast;
 mod events;
+mod ids;
 mod map;
 mod mapmap;
 mod message_accumulator;
 mod multimap;
+mod optional_tail;
+mod quantize;
 mod set;
 mod set_multimap;
+mod validation;
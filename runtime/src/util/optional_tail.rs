@@ -0,0 +1,141 @@
+/// Checks whether a value is its type's default.
+pub fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Implements tuple encoding for a struct whose last fields were added after values of it
+/// were already stored.
+///
+/// The fields after the `;` form an optional tail, which is omitted from the encoding while all
+/// of its fields have their default values. A value which doesn't use the added fields is thus
+/// encoded exactly as before they were added. Decoding accepts the tuple with or without the
+/// whole tail, and defaults the tail's fields when it is absent.
+///
+/// ```ignore
+/// tuple_with_optional_tail!(State { address; auth_delegate });
+/// ```
+#[macro_export]
+macro_rules! tuple_with_optional_tail {
+    ($name:ident { $($field:ident),+ ; $($tail:ident),+ $(,)? }) => {
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::std::result::Result<S::Ok, S::Error> {
+                use ::serde::ser::SerializeTuple;
+                let with_tail = false $(|| !$crate::is_default(&self.$tail))+;
+                let mut len = $crate::tuple_with_optional_tail!(@count $($field)+);
+                if with_tail {
+                    len += $crate::tuple_with_optional_tail!(@count $($tail)+);
+                }
+                let mut tuple = serializer.serialize_tuple(len)?;
+                $(tuple.serialize_element(&self.$field)?;)+
+                if with_tail {
+                    $(tuple.serialize_element(&self.$tail)?;)+
+                }
+                tuple.end()
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::std::result::Result<Self, D::Error> {
+                struct TupleVisitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for TupleVisitor {
+                    type Value = $name;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut ::std::fmt::Formatter,
+                    ) -> ::std::fmt::Result {
+                        formatter.write_str(concat!(
+                            "a tuple of ",
+                            stringify!($name),
+                            " with or without its optional fields"
+                        ))
+                    }
+
+                    fn visit_seq<A: ::serde::de::SeqAccess<'de>>(
+                        self,
+                        mut seq: A,
+                    ) -> ::std::result::Result<$name, A::Error> {
+                        use ::serde::de::Error;
+                        let mut len = 0;
+                        $(
+                            let $field = seq
+                                .next_element()?
+                                .ok_or_else(|| A::Error::invalid_length(len, &self))?;
+                            len += 1;
+                        )+
+                        let required = len;
+                        $(
+                            let $tail = match seq.next_element()? {
+                                Some(value) => {
+                                    len += 1;
+                                    value
+                                }
+                                None => ::std::default::Default::default(),
+                            };
+                        )+
+                        let optional = $crate::tuple_with_optional_tail!(@count $($tail)+);
+                        if len != required && len != required + optional {
+                            return Err(A::Error::invalid_length(len, &self));
+                        }
+                        if seq.next_element::<::serde::de::IgnoredAny>()?.is_some() {
+                            return Err(A::Error::invalid_length(len + 1, &self));
+                        }
+                        Ok($name { $($field,)+ $($tail,)+ })
+                    }
+                }
+
+                deserializer.deserialize_seq(TupleVisitor)
+            }
+        }
+    };
+    (@count $($field:ident)+) => {
+        <[()]>::len(&[$($crate::tuple_with_optional_tail!(@unit $field)),+])
+    };
+    (@unit $field:ident) => {
+        ()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use fvm_ipld_encoding::{from_slice, to_vec};
+
+    #[derive(Debug, PartialEq)]
+    struct Record {
+        a: u64,
+        b: u64,
+        c: Option<u64>,
+        d: Vec<u64>,
+    }
+
+    tuple_with_optional_tail!(Record { a, b; c, d });
+
+    #[test]
+    fn omits_default_tail() {
+        let record = Record { a: 1, b: 2, c: None, d: vec![] };
+        let bytes = to_vec(&record).unwrap();
+        assert_eq!(to_vec(&(1, 2)).unwrap(), bytes);
+        assert_eq!(record, from_slice(&bytes).unwrap());
+    }
+
+    #[test]
+    fn encodes_whole_tail_if_any_field_is_set() {
+        let record = Record { a: 1, b: 2, c: None, d: vec![3] };
+        let bytes = to_vec(&record).unwrap();
+        assert_eq!(to_vec(&(1, 2, None::<u64>, vec![3])).unwrap(), bytes);
+        assert_eq!(record, from_slice(&bytes).unwrap());
+    }
+
+    #[test]
+    fn rejects_partial_tail_and_extra_elements() {
+        assert!(from_slice::<Record>(&to_vec(&(1,)).unwrap()).is_err());
+        assert!(from_slice::<Record>(&to_vec(&(1, 2, Some(3))).unwrap()).is_err());
+        assert!(from_slice::<Record>(&to_vec(&(1, 2, Some(3), vec![4], 5)).unwrap()).is_err());
+    }
+}
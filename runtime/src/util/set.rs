@@ -56,6 +56,12 @@ where
         self.0.delete(key)
     }
 
+    /// Checks whether the set has no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Iterates through all keys in the set.
     pub fn for_each<F>(&self, mut f: F) -> Result<(), ActorError>
     where
@@ -64,6 +70,22 @@ where
         self.0.for_each(|s, _| f(s))
     }
 
+    /// Iterates over at most `max` keys in the set, beginning at `starting_key` (inclusive)
+    /// if provided, otherwise at the first key.
+    /// Returns the number of keys visited and the key at which to resume, if any remain.
+    /// Iteration order and resumption are as for [`Map2::for_each_ranged`].
+    pub fn for_each_ranged<F>(
+        &self,
+        starting_key: Option<&K>,
+        max: Option<usize>,
+        mut f: F,
+    ) -> Result<(usize, Option<K>), ActorError>
+    where
+        F: FnMut(K) -> Result<(), ActorError>,
+    {
+        self.0.for_each_ranged(starting_key, max, |k, _| f(k))
+    }
+
     /// Collects all keys from the set into a vector.
     pub fn collect_keys(&self) -> Result<Vec<K>, ActorError> {
         let mut ret_keys = Vec::new();
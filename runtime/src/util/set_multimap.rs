@@ -104,6 +104,7 @@ where
     }
 
     /// Removes a value from the set associated with a key, if it was present.
+    /// The key is removed if its set becomes empty.
     #[inline]
     pub fn remove(&mut self, key: &K, value: V) -> Result<(), ActorError> {
         let mut set = match self.get(key)? {
@@ -112,8 +113,12 @@ where
         };
 
         set.delete(&value)?;
-        let new_root = set.flush()?;
-        self.outer.set(key, new_root)?;
+        if set.is_empty() {
+            self.outer.delete(key)?;
+        } else {
+            let new_root = set.flush()?;
+            self.outer.set(key, new_root)?;
+        }
         Ok(())
     }
 
@@ -132,6 +137,22 @@ where
         self.outer.for_each(|k, v| f(k, v))
     }
 
+    /// Iterates over at most `max` keys, beginning at `starting_key` (inclusive) if provided,
+    /// otherwise at the first key.
+    /// Returns the number of keys visited and the key at which to resume, if any remain.
+    /// Iteration order and resumption are as for [`Map2::for_each_ranged`](crate::Map2::for_each_ranged).
+    pub fn for_each_ranged<F>(
+        &self,
+        starting_key: Option<&K>,
+        max: Option<usize>,
+        mut f: F,
+    ) -> Result<(usize, Option<K>), ActorError>
+    where
+        F: FnMut(K, &Cid) -> Result<(), ActorError>,
+    {
+        self.outer.for_each_ranged(starting_key, max, |k, v| f(k, v))
+    }
+
     /// Iterates values for a key.
     pub fn for_each_in<F>(&self, key: &K, f: F) -> Result<(), ActorError>
     where
@@ -145,4 +166,23 @@ where
 
         set.for_each(f)
     }
+
+    /// Iterates over at most `max` values for a key, beginning at `starting_value` (inclusive)
+    /// if provided, otherwise at the first value.
+    /// Returns the number of values visited and the value at which to resume, if any remain.
+    pub fn for_each_in_ranged<F>(
+        &self,
+        key: &K,
+        starting_value: Option<&V>,
+        max: Option<usize>,
+        f: F,
+    ) -> Result<(usize, Option<V>), ActorError>
+    where
+        F: FnMut(V) -> Result<(), ActorError>,
+    {
+        match self.get(key)? {
+            Some(set) => set.for_each_ranged(starting_value, max, f),
+            None => Ok((0, None)),
+        }
+    }
 }
@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use num_traits::Zero;
+
+use crate::{actor_error, ActorError};
+
+/// Parameters which can be checked for validity before a method acts on them.
+///
+/// Implement with [`validate_params!`](crate::validate_params) where the checks are simple
+/// bounds on fields.
+pub trait ValidateParams {
+    /// Returns an illegal-argument error describing the first invalid field, if any.
+    fn validate(&self) -> Result<(), ActorError>;
+}
+
+/// Requires a value to be zero or more.
+pub fn require_non_negative<T: PartialOrd + Zero + Display>(
+    name: &str,
+    value: &T,
+) -> Result<(), ActorError> {
+    if *value < T::zero() {
+        return Err(actor_error!(illegal_argument, "negative {}: {}", name, value));
+    }
+    Ok(())
+}
+
+/// Requires a value to lie within an inclusive range.
+pub fn require_in_range<T: PartialOrd + Display>(
+    name: &str,
+    value: &T,
+    min: &T,
+    max: &T,
+) -> Result<(), ActorError> {
+    if value < min || value > max {
+        return Err(actor_error!(
+            illegal_argument,
+            "{} {} out of range [{}, {}]",
+            name,
+            value,
+            min,
+            max
+        ));
+    }
+    Ok(())
+}
+
+/// Requires a collection to hold at most `max` items.
+pub fn require_max_len(name: &str, len: u64, max: u64) -> Result<(), ActorError> {
+    if len > max {
+        return Err(actor_error!(illegal_argument, "{} length {} exceeds max {}", name, len, max));
+    }
+    Ok(())
+}
+
+/// Requires a collection to hold at least one item.
+pub fn require_non_empty(name: &str, len: u64) -> Result<(), ActorError> {
+    if len == 0 {
+        return Err(actor_error!(illegal_argument, "empty {}", name));
+    }
+    Ok(())
+}
+
+/// Implements [`ValidateParams`] for a parameter struct from a list of rules on its fields.
+/// A field may be listed more than once to apply several rules, which are checked in order.
+///
+/// The rules are:
+/// - `non_negative`: the field is zero or more.
+/// - `range(min, max)`: the field lies within `min..=max`.
+/// - `max_len(max)`: the field's `len()` is at most `max`.
+/// - `non_empty`: the field's `len()` is more than zero.
+///
+/// ```ignore
+/// validate_params!(WithdrawBalanceParams {
+///     amount: non_negative,
+/// });
+/// ```
+#[macro_export]
+macro_rules! validate_params {
+    ($params:ty { $($field:ident: $rule:ident $(($($arg:expr),*))?),* $(,)? }) => {
+        impl $crate::ValidateParams for $params {
+            fn validate(&self) -> Result<(), $crate::ActorError> {
+                $($crate::validate_params!(@rule self.$field, stringify!($field), $rule $(($($arg),*))?);)*
+                Ok(())
+            }
+        }
+    };
+    (@rule $value:expr, $name:expr, non_negative) => {
+        $crate::require_non_negative($name, &$value)?
+    };
+    (@rule $value:expr, $name:expr, range($min:expr, $max:expr)) => {
+        $crate::require_in_range($name, &$value, &$min, &$max)?
+    };
+    (@rule $value:expr, $name:expr, max_len($max:expr)) => {
+        $crate::require_max_len($name, $value.len() as u64, ($max) as u64)?
+    };
+    (@rule $value:expr, $name:expr, non_empty) => {
+        $crate::require_non_empty($name, $value.len() as u64)?
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use fvm_shared::econ::TokenAmount;
+    use fvm_shared::error::ExitCode;
+
+    use crate::ValidateParams;
+
+    struct Params {
+        amount: TokenAmount,
+        limit: u64,
+        items: Vec<u8>,
+    }
+
+    validate_params!(Params {
+        amount: non_negative,
+        limit: range(1, 10),
+        items: non_empty,
+        items: max_len(2),
+    });
+
+    fn params() -> Params {
+        Params { amount: TokenAmount::from_atto(1), limit: 5, items: vec![1] }
+    }
+
+    fn assert_invalid(params: Params, msg: &str) {
+        let err = params.validate().unwrap_err();
+        assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, err.exit_code());
+        assert_eq!(msg, err.msg());
+    }
+
+    #[test]
+    fn accepts_valid_params() {
+        params().validate().unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        assert_invalid(
+            Params { amount: TokenAmount::from_atto(-1), ..params() },
+            "negative amount: -0.000000000000000001",
+        );
+        assert_invalid(Params { limit: 0, ..params() }, "limit 0 out of range [1, 10]");
+        assert_invalid(Params { limit: 11, ..params() }, "limit 11 out of range [1, 10]");
+        assert_invalid(Params { items: vec![], ..params() }, "empty items");
+        assert_invalid(Params { items: vec![1, 2, 3], ..params() }, "items length 3 exceeds max 2");
+    }
+}
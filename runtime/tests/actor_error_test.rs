@@ -0,0 +1,35 @@
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::test_utils::{expect_abort_with_data, MockRuntime};
+use fil_actors_runtime::{actor_error, extract_send_result};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+
+#[test]
+fn send_failure_carries_return_data() {
+    let rt = MockRuntime::new();
+    rt.in_call.replace(true);
+    let to = Address::new_id(1000);
+    let revert = IpldBlock::serialize_cbor(&"revert reason").unwrap();
+    rt.expect_send_simple(
+        to,
+        2,
+        None,
+        TokenAmount::from_atto(0),
+        revert.clone(),
+        ExitCode::USR_UNSPECIFIED,
+    );
+    let res = extract_send_result(rt.send_simple(&to, 2, None, TokenAmount::from_atto(0)));
+    expect_abort_with_data(ExitCode::USR_UNSPECIFIED, revert, res);
+    rt.verify();
+}
+
+#[test]
+fn data_is_attached_and_taken() {
+    let data = IpldBlock::serialize_cbor(&42u64).unwrap();
+    let mut err = actor_error!(illegal_argument; "bad").with_data(data.clone());
+    assert_eq!(data.as_ref(), err.data());
+    assert_eq!(data, err.take_data());
+    assert_eq!(None, err.data());
+}
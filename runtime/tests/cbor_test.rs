@@ -0,0 +1,103 @@
+use fil_actors_runtime::cbor::{check_canonical, deserialize_strict};
+use fil_actors_runtime::test_utils::make_identity_cid;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{BytesDe, BytesSer, CBOR, IPLD_RAW};
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use std::collections::BTreeMap;
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, PartialEq)]
+struct Params {
+    address: Address,
+    amount: TokenAmount,
+    count: u64,
+    names: Vec<String>,
+}
+
+#[test]
+fn accepts_canonical_encodings() {
+    let params = Params {
+        address: Address::new_id(1234),
+        amount: TokenAmount::from_whole(10),
+        count: u64::MAX,
+        names: vec!["a".into(), "".into()],
+    };
+    let block = IpldBlock::serialize_cbor(&params).unwrap().unwrap();
+    assert_eq!(params, deserialize_strict::<Params>(&block).unwrap());
+
+    let map: BTreeMap<String, u64> =
+        [("aa", 1), ("b", 2), ("c", 3)].into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+    let cid = make_identity_cid(b"foo");
+    for bytes in [
+        fvm_ipld_encoding::to_vec(&map).unwrap(),
+        fvm_ipld_encoding::to_vec(&cid).unwrap(),
+        fvm_ipld_encoding::to_vec(&(-1i64, 1.5f64, true, ())).unwrap(),
+        fvm_ipld_encoding::to_vec(&BytesSer(&[0u8; 300])).unwrap(),
+    ] {
+        check_canonical(&bytes).unwrap();
+    }
+}
+
+#[test]
+fn rejects_malformed_encodings() {
+    for (hex, reason) in [
+        // Indefinite-length array [1]
+        ("9f01ff", "indefinite length"),
+        // Indefinite-length byte string
+        ("5f4101ff", "indefinite length"),
+        // 1 encoded in one extra byte
+        ("1801", "non-minimal"),
+        // 255 encoded in two bytes
+        ("1900ff", "non-minimal"),
+        // A one-element array whose length is encoded in four bytes
+        ("9a0000000101", "non-minimal"),
+        // -1 encoded in eight bytes
+        ("3b0000000000000000", "non-minimal"),
+        // 1 followed by a second item
+        ("0102", "trailing bytes"),
+        // {"b": 1, "a": 2}
+        ("a2616201616102", "out of order"),
+        // {"aa": 1, "b": 2}, ordered bytewise rather than by length
+        ("a262616101616202", "out of order"),
+        // {"a": 1, "a": 2}
+        ("a2616101616102", "duplicated"),
+        // {1: 2}
+        ("a10102", "not a string"),
+        // 1.5 as a 16-bit float
+        ("f93e00", "float"),
+        // undefined
+        ("f7", "simple value"),
+        // A date tag
+        ("c074323031332d30332d32315432303a30343a30305a", "tag"),
+        // Reserved additional info
+        ("1c", "reserved"),
+        // A byte string longer than its input
+        ("4501", "end of input"),
+        // An array with too few items
+        ("830102", "end of input"),
+        // Invalid UTF-8
+        ("61ff", "UTF-8"),
+    ] {
+        let bytes = hex::decode(hex).unwrap();
+        let err = check_canonical(&bytes).expect_err(hex);
+        assert!(err.contains(reason), "{}: expected {:?}, got {:?}", hex, reason, err);
+    }
+}
+
+#[test]
+fn strict_decoding_rejects_non_canonical_params() {
+    // [1] with its element encoded in an extra byte
+    let block = IpldBlock { codec: CBOR, data: hex::decode("811801").unwrap() };
+    let err = deserialize_strict::<(u64,)>(&block).unwrap_err();
+    assert_eq!(ExitCode::USR_SERIALIZATION, err.exit_code());
+    assert!(err.msg().contains("non-canonical"), "{}", err.msg());
+
+    // The leniently decoded value is the same.
+    assert_eq!((1,), block.deserialize::<(u64,)>().unwrap());
+
+    // Other codecs aren't checked.
+    let block = IpldBlock { codec: IPLD_RAW, data: vec![1, 2, 3] };
+    assert_eq!(vec![1, 2, 3], deserialize_strict::<BytesDe>(&block).unwrap().0);
+}
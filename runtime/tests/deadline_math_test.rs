@@ -0,0 +1,62 @@
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::{
+    current_deadline_index, current_proving_period_start, new_deadline_info,
+    new_deadline_info_from_offset_and_epoch, quant_spec_for_deadline, QuantSpec,
+};
+use fvm_shared::clock::ChainEpoch;
+use rand::prelude::*;
+
+const ITERATIONS: usize = 10_000;
+
+#[test]
+fn quantize_properties() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..ITERATIONS {
+        let unit = rng.gen_range(1..5_000);
+        let offset = rng.gen_range(-10_000..10_000);
+        let epoch = rng.gen_range(-100_000..100_000);
+        let q = QuantSpec { unit, offset };
+
+        let up = q.quantize_up(epoch);
+        let down = q.quantize_down(epoch);
+        // Results are quantization epochs, bracketing the input within one unit.
+        assert_eq!(0, (up - offset).rem_euclid(unit), "{unit} {offset} {epoch}");
+        assert_eq!(0, (down - offset).rem_euclid(unit), "{unit} {offset} {epoch}");
+        assert!(down <= epoch && epoch <= up, "{unit} {offset} {epoch}");
+        assert!(up - down == 0 || up - down == unit, "{unit} {offset} {epoch}");
+        // Quantization epochs are fixed points.
+        assert_eq!(up, q.quantize_up(up));
+        assert_eq!(down, q.quantize_down(down));
+    }
+}
+
+#[test]
+fn deadline_info_properties() {
+    let policy = Policy::default();
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0..ITERATIONS {
+        let offset = rng.gen_range(0..policy.wpost_proving_period);
+        let epoch: ChainEpoch = rng.gen_range(0..10_000_000);
+
+        let di = new_deadline_info_from_offset_and_epoch(&policy, offset, epoch);
+        assert!(di.period_started() && !di.period_elapsed());
+        assert!(di.index < policy.wpost_period_deadlines);
+        assert!(di.is_open(), "deadline for current epoch {epoch} not open: {di:?}");
+        assert_eq!(offset, di.period_start.rem_euclid(policy.wpost_proving_period));
+        assert_eq!(di.close - di.open, policy.wpost_challenge_window);
+        assert!(di.challenge < di.open && di.fault_cutoff < di.open);
+
+        // The explicit computations agree with the offset-based one.
+        let period_start = current_proving_period_start(&policy, epoch, offset);
+        assert_eq!(di.period_start, period_start);
+        assert_eq!(di.index, current_deadline_index(&policy, epoch, period_start));
+        assert_eq!(di, new_deadline_info(&policy, period_start, di.index, epoch));
+
+        // Quantizing to the deadline lands on the last epoch of an instance of the deadline.
+        let quant = quant_spec_for_deadline(&policy, &di);
+        assert_eq!(di.last(), quant.quantize_up(epoch));
+        assert_eq!(di.quant_spec().offset, quant.offset);
+        let next = quant.quantize_up(di.last() + 1);
+        assert_eq!(di.last() + policy.wpost_proving_period, next);
+    }
+}
@@ -0,0 +1,36 @@
+use fil_actors_runtime::{DelegatedAddress, EAM_ACTOR_ID};
+use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
+
+type Subaddress = DelegatedAddress<EAM_ACTOR_ID, 4>;
+
+#[test]
+fn round_trips_through_address() {
+    let addr = Address::new_delegated(EAM_ACTOR_ID, &[1, 2, 3, 4]).unwrap();
+    let sub = Subaddress::try_from(&addr).unwrap();
+    assert_eq!([1, 2, 3, 4], sub.0);
+    assert_eq!(addr, Address::from(sub));
+    assert_eq!(addr.to_string(), sub.to_string());
+}
+
+#[test]
+fn rejects_other_addresses() {
+    // Other kinds of address, and other namespaces, aren't sub-addresses of this namespace.
+    for addr in [
+        Address::new_id(EAM_ACTOR_ID),
+        Address::new_delegated(EAM_ACTOR_ID + 1, &[1, 2, 3, 4]).unwrap(),
+    ] {
+        assert_eq!(None, Subaddress::from_address(&addr).unwrap());
+        assert_eq!(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            Subaddress::try_from(&addr).unwrap_err().exit_code()
+        );
+    }
+
+    // A sub-address of the wrong length in this namespace is an error.
+    let addr = Address::new_delegated(EAM_ACTOR_ID, &[1, 2, 3]).unwrap();
+    assert_eq!(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        Subaddress::from_address(&addr).unwrap_err().exit_code()
+    );
+}
@@ -0,0 +1,67 @@
+use fil_actors_runtime::runtime::{DomainSeparationTag, Primitives, Runtime};
+use fil_actors_runtime::test_utils::{ExpectedVerifySig, MockRuntime, MockSyscall};
+use fil_actors_runtime::ActorError;
+use fvm_shared::address::Address;
+use fvm_shared::crypto::signature::Signature;
+use fvm_shared::error::ExitCode;
+
+#[test]
+fn nth_randomness_call_fails() {
+    let rt = MockRuntime::new();
+    rt.fail_syscall(MockSyscall::GetRandomness, 2, ExitCode::USR_ILLEGAL_ARGUMENT);
+    for _ in 0..2 {
+        rt.expect_get_randomness_from_tickets(
+            DomainSeparationTag::SealRandomness,
+            1,
+            vec![],
+            [1u8; 32],
+        );
+    }
+
+    let tag = DomainSeparationTag::SealRandomness;
+    assert_eq!([1u8; 32], rt.get_randomness_from_tickets(tag, 1, &[]).unwrap());
+    let err = rt.get_randomness_from_tickets(tag, 1, &[]).unwrap_err();
+    assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, err.exit_code());
+    // The failed call consumed no expectation, and the failure is not repeated.
+    assert_eq!([1u8; 32], rt.get_randomness_from_tickets(tag, 1, &[]).unwrap());
+    rt.verify();
+}
+
+#[test]
+fn verify_signature_fails_without_expectation() {
+    let rt = MockRuntime::new();
+    rt.fail_syscall(MockSyscall::VerifySignature, 1, ExitCode::USR_FORBIDDEN);
+    let sig = Signature::new_secp256k1(vec![]);
+    let signer = Address::new_id(100);
+    let err = rt.verify_signature(&sig, &signer, b"data").unwrap_err();
+    assert_eq!(ExitCode::USR_FORBIDDEN, err.downcast::<ActorError>().unwrap().exit_code());
+
+    rt.expect_verify_signature(ExpectedVerifySig {
+        sig: sig.clone(),
+        signer,
+        plaintext: b"data".to_vec(),
+        result: Ok(()),
+    });
+    rt.verify_signature(&sig, &signer, b"data").unwrap();
+    rt.verify();
+}
+
+#[test]
+fn blockstore_failures_surface_from_state_access() {
+    let rt = MockRuntime::new();
+    rt.fail_syscall(MockSyscall::BlockstorePut, 1, ExitCode::USR_SERIALIZATION);
+    let err = rt.create(&1u64).unwrap_err();
+    assert_eq!(ExitCode::USR_SERIALIZATION, err.exit_code());
+    rt.create(&1u64).unwrap();
+
+    rt.fail_syscall(MockSyscall::BlockstoreGet, 2, ExitCode::USR_ILLEGAL_STATE);
+    assert_eq!(1, rt.state::<u64>().unwrap());
+    let err = rt
+        .transaction(|st: &mut u64, _| {
+            *st += 1;
+            Ok(())
+        })
+        .unwrap_err();
+    assert_eq!(ExitCode::USR_ILLEGAL_STATE, err.exit_code());
+    assert_eq!(1, rt.state::<u64>().unwrap());
+}
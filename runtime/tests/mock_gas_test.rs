@@ -0,0 +1,57 @@
+use fil_actors_runtime::runtime::{Primitives, Runtime};
+use fil_actors_runtime::test_utils::{MockPriceList, MockRuntime};
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+
+#[test]
+fn price_list_accumulates_gas() {
+    let rt = MockRuntime::new();
+    rt.create(&1u64).unwrap();
+    // Nothing is charged until a price list is installed.
+    assert_eq!(0, rt.gas_used());
+
+    rt.set_price_list(MockPriceList {
+        state_read: 10,
+        state_write: 100,
+        send: 1000,
+        hash: 1,
+        ..Default::default()
+    });
+    rt.transaction(|st: &mut u64, _| {
+        *st += 1;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(110, rt.gas_used());
+
+    rt.hash_blake2b(b"data");
+    rt.expect_gas_charge(5);
+    rt.charge_gas("explicit", 5);
+    rt.in_call.replace(true);
+    rt.expect_send_simple(
+        Address::new_id(1000),
+        2,
+        None,
+        TokenAmount::from_atto(0),
+        None,
+        ExitCode::OK,
+    );
+    rt.send_simple(&Address::new_id(1000), 2, None, TokenAmount::from_atto(0)).unwrap();
+    rt.verify();
+    rt.assert_gas_used_between(1116, 1116);
+
+    rt.reset_gas_used();
+    rt.state::<u64>().unwrap();
+    rt.assert_gas_used_between(0, 10);
+}
+
+#[test]
+#[should_panic(expected = "gas used 10 not in range [0, 9]")]
+fn gas_assertion_fails_outside_range() {
+    let rt = MockRuntime::new();
+    rt.create(&1u64).unwrap();
+    rt.set_price_list(MockPriceList { state_read: 10, ..Default::default() });
+    rt.state::<u64>().unwrap();
+    rt.assert_gas_used_between(0, 9);
+}
@@ -0,0 +1,16 @@
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::test_utils::MockRuntime;
+use log::Level;
+
+#[test]
+fn records_logged_messages() {
+    let rt = MockRuntime::new();
+    rt.log(Level::Debug, "first");
+    rt.log(Level::Warn, &format!("second {}", 2));
+
+    assert_eq!(
+        vec![(Level::Debug, "first".to_string()), (Level::Warn, "second 2".to_string())],
+        rt.take_logs()
+    );
+    assert!(rt.take_logs().is_empty());
+}
@@ -0,0 +1,38 @@
+use fil_actors_runtime::runtime::{ActorCode, Runtime};
+use fil_actors_runtime::test_utils::MockRuntime;
+use fil_actors_runtime::ActorError;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::MethodNum;
+
+/// An actor which panics in every method.
+struct PanickingActor;
+
+impl ActorCode for PanickingActor {
+    type Methods = ();
+
+    fn name() -> &'static str {
+        "Panicking"
+    }
+
+    fn invoke_method<RT>(
+        _: &RT,
+        method: MethodNum,
+        _: Option<IpldBlock>,
+    ) -> Result<Option<IpldBlock>, ActorError>
+    where
+        RT: Runtime,
+        RT::Blockstore: Blockstore + Clone,
+    {
+        panic!("method {} is broken", method)
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "actor code panicked, which aborts with exit code 24: method 2 is broken"
+)]
+fn panic_in_actor_code_fails_the_test() {
+    let rt = MockRuntime::new();
+    let _ = rt.call::<PanickingActor>(2, None);
+}
@@ -0,0 +1,55 @@
+use fil_actors_runtime::runtime::{DomainSeparationTag, Primitives, Runtime};
+use fil_actors_runtime::test_utils::{MockRuntime, ACCOUNT_ACTOR_CODE_ID};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
+use fvm_shared::crypto::signature::Signature;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
+
+#[test]
+fn relaxed_syscalls_use_defaults() {
+    let rt = MockRuntime::new();
+    rt.in_call.replace(true);
+    rt.set_relaxed(true);
+    rt.set_balance(TokenAmount::from_atto(100));
+    let to = Address::new_id(1000);
+
+    rt.validate_immediate_caller_accept_any().unwrap();
+    let tag = DomainSeparationTag::SealRandomness;
+    assert_eq!([0u8; 32], rt.get_randomness_from_tickets(tag, 1, &[]).unwrap());
+    assert_eq!([0u8; 32], rt.get_beacon_randomness(1).unwrap());
+    rt.verify_signature(&Signature::new_bls(vec![]), &to, b"data").unwrap();
+
+    let ret = rt.send(&to, 2, None, TokenAmount::from_atto(40), None, SendFlags::empty()).unwrap();
+    assert_eq!(ExitCode::OK, ret.exit_code);
+    assert!(ret.return_data.is_none());
+    assert_eq!(TokenAmount::from_atto(60), rt.current_balance());
+    rt.verify();
+}
+
+#[test]
+fn relaxed_caller_validation_checks_caller() {
+    let rt = MockRuntime::new();
+    rt.in_call.replace(true);
+    rt.set_relaxed(true);
+    let (caller, other) = (Address::new_id(1000), Address::new_id(1001));
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, caller);
+
+    rt.validate_immediate_caller_is(&[caller]).unwrap();
+    let err = rt.validate_immediate_caller_is(&[other]).unwrap_err();
+    assert_eq!(ExitCode::USR_FORBIDDEN, err.exit_code());
+    rt.verify();
+}
+
+#[test]
+#[should_panic(expected = "expected params")]
+fn relaxed_still_matches_expectations() {
+    let rt = MockRuntime::new();
+    rt.in_call.replace(true);
+    rt.set_relaxed(true);
+    let to = Address::new_id(1000);
+    rt.expect_send_simple(to, 2, None, TokenAmount::from_atto(0), None, ExitCode::OK);
+    let params = IpldBlock::serialize_cbor(&1u64).unwrap();
+    rt.send_simple(&to, 2, params, TokenAmount::from_atto(0)).unwrap();
+}
@@ -0,0 +1,50 @@
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::test_utils::MockRuntime;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
+
+fn send_u64(rt: &MockRuntime, to: Address, value: u64) -> u64 {
+    rt.send(
+        &to,
+        2,
+        IpldBlock::serialize_cbor(&value).unwrap(),
+        TokenAmount::from_atto(0),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap()
+    .return_data
+    .unwrap()
+    .deserialize()
+    .unwrap()
+}
+
+#[test]
+fn typed_sends_match_out_of_order() {
+    let rt = MockRuntime::new();
+    rt.in_call.replace(true);
+    let (a, b) = (Address::new_id(1000), Address::new_id(1001));
+    rt.expect_send_typed(a, 2, &1u64, TokenAmount::from_atto(0), &10u64, ExitCode::OK);
+    rt.expect_send_typed(b, 2, &2u64, TokenAmount::from_atto(0), &20u64, ExitCode::OK);
+    rt.expect_send_typed(a, 2, &3u64, TokenAmount::from_atto(0), &30u64, ExitCode::OK);
+    rt.expect_sends_unordered();
+
+    assert_eq!(30, send_u64(&rt, a, 3));
+    assert_eq!(20, send_u64(&rt, b, 2));
+    assert_eq!(10, send_u64(&rt, a, 1));
+    rt.verify();
+}
+
+#[test]
+#[should_panic(expected = "expected params")]
+fn sends_match_in_order_by_default() {
+    let rt = MockRuntime::new();
+    rt.in_call.replace(true);
+    let a = Address::new_id(1000);
+    rt.expect_send_typed(a, 2, &1u64, TokenAmount::from_atto(0), &10u64, ExitCode::OK);
+    rt.expect_send_typed(a, 2, &2u64, TokenAmount::from_atto(0), &20u64, ExitCode::OK);
+    send_u64(&rt, a, 2);
+}
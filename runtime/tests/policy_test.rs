@@ -0,0 +1,74 @@
+use fil_actors_runtime::runtime::{Policy, ProofSet};
+use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof, SectorSize, StoragePower};
+
+#[test]
+fn network_presets() {
+    let mainnet = Policy::mainnet();
+    assert!(mainnet.valid_post_proof_type.contains(RegisteredPoStProof::StackedDRGWindow32GiBV1P1));
+    assert!(mainnet.valid_post_proof_type.contains(RegisteredPoStProof::StackedDRGWindow64GiBV1P1));
+    assert!(!mainnet.valid_post_proof_type.contains(RegisteredPoStProof::StackedDRGWindow2KiBV1P1));
+    assert!(mainnet
+        .valid_pre_commit_proof_type
+        .contains(RegisteredSealProof::StackedDRG32GiBV1P1_Feat_SyntheticPoRep));
+    assert!(mainnet
+        .valid_prove_commit_ni_proof_type
+        .contains(RegisteredSealProof::StackedDRG64GiBV1P2_Feat_NiPoRep));
+    assert_eq!(150, mainnet.pre_commit_challenge_delay);
+    assert_eq!(StoragePower::from(10i64 << 40), mainnet.minimum_consensus_power);
+    assert!(!mainnet.account_auth_delegation);
+    assert_eq!(5, mainnet.consensus_fault_factor);
+    assert_eq!(
+        (1, 2, 140),
+        (
+            mainnet.termination_reward_factor_num,
+            mainnet.termination_reward_factor_denom,
+            mainnet.termination_lifetime_cap
+        )
+    );
+
+    let calibnet = Policy::calibnet();
+    assert_eq!(mainnet.valid_post_proof_type, calibnet.valid_post_proof_type);
+    assert_eq!(StoragePower::from(32i64 << 30), calibnet.minimum_consensus_power);
+
+    let devnet = Policy::devnet();
+    assert!(devnet.valid_post_proof_type.contains(RegisteredPoStProof::StackedDRGWindow2KiBV1P1));
+    assert!(!devnet.valid_post_proof_type.contains(RegisteredPoStProof::StackedDRGWindow32GiBV1P1));
+    assert!(devnet.valid_pre_commit_proof_type.contains(RegisteredSealProof::StackedDRG8MiBV1P1));
+    assert_eq!(10, devnet.pre_commit_challenge_delay);
+    assert_eq!(StoragePower::from(256), devnet.minimum_verified_allocation_size);
+    assert!(devnet.account_auth_delegation);
+}
+
+#[test]
+fn builder_overrides_fields() {
+    let policy = Policy::mainnet()
+        .builder()
+        .minimum_consensus_power(StoragePower::from(2048))
+        .proving_period(480, 12)
+        .sector_sizes(&[SectorSize::_512MiB])
+        .account_auth_delegation(true)
+        .consensus_fault_factor(2)
+        .termination_fee(1, 4, 70)
+        .cron_entry_gas_limit(1_000_000)
+        .build();
+    assert_eq!(StoragePower::from(2048), policy.minimum_consensus_power);
+    assert_eq!(480, policy.wpost_proving_period);
+    assert_eq!(12, policy.wpost_period_deadlines);
+    assert_eq!(40, policy.wpost_challenge_window);
+    assert!(policy.account_auth_delegation);
+    assert_eq!(2, policy.consensus_fault_factor);
+    assert_eq!(
+        (1, 4, 70),
+        (
+            policy.termination_reward_factor_num,
+            policy.termination_reward_factor_denom,
+            policy.termination_lifetime_cap
+        )
+    );
+    assert_eq!(1_000_000, policy.cron_entry_gas_limit);
+    assert_eq!(ProofSet::post_proofs(&[SectorSize::_512MiB]), policy.valid_post_proof_type);
+    assert!(policy.valid_pre_commit_proof_type.contains(RegisteredSealProof::StackedDRG512MiBV1P1));
+    assert!(!policy.valid_pre_commit_proof_type.contains(RegisteredSealProof::StackedDRG32GiBV1P1));
+    // Fields not overridden are retained from the base policy.
+    assert_eq!(Policy::mainnet().chain_finality, policy.chain_finality);
+}
@@ -0,0 +1,74 @@
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::test_utils::MockRuntime;
+use fil_actors_runtime::EventBuilder;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::{ErrorNumber, ExitCode};
+use fvm_shared::sys::SendFlags;
+use fvm_shared::METHOD_SEND;
+
+#[test]
+fn read_only_rejects_side_effects() {
+    let rt = MockRuntime::new();
+    rt.create(&42u64).unwrap();
+    rt.set_balance(TokenAmount::from_atto(100));
+
+    rt.set_read_only(true);
+    assert!(rt.read_only());
+
+    // Reading state is permitted.
+    assert_eq!(42u64, rt.state::<u64>().unwrap());
+
+    // State updates are rejected and leave state unchanged.
+    let err = rt
+        .transaction(|st: &mut u64, _| {
+            *st = 43;
+            Ok(())
+        })
+        .unwrap_err();
+    assert_eq!(ExitCode::USR_READ_ONLY, err.exit_code());
+    assert_eq!(42u64, rt.state::<u64>().unwrap());
+    let root = rt.get_state_root().unwrap();
+    assert_eq!(ExitCode::USR_READ_ONLY, rt.set_state_root(&root).unwrap_err().exit_code());
+    assert_eq!(ExitCode::USR_READ_ONLY, rt.create(&1u64).unwrap_err().exit_code());
+
+    // Event emission is rejected.
+    let event = EventBuilder::new().typ("test").build().unwrap();
+    assert_eq!(ExitCode::USR_READ_ONLY, rt.emit_event(&event).unwrap_err().exit_code());
+
+    rt.in_call.replace(true);
+    // Value transfers are rejected.
+    let to = Address::new_id(1000);
+    let err = rt
+        .send(&to, METHOD_SEND, None, TokenAmount::from_atto(1), None, SendFlags::empty())
+        .unwrap_err();
+    assert_eq!(ErrorNumber::ReadOnly, err.0);
+    assert_eq!(TokenAmount::from_atto(100), rt.get_balance());
+
+    // Sends without value are permitted, and the read-only flag is propagated.
+    rt.expect_send(
+        to,
+        2,
+        None,
+        TokenAmount::from_atto(0),
+        None,
+        SendFlags::READ_ONLY,
+        None,
+        ExitCode::OK,
+        None,
+    );
+    rt.send(&to, 2, None, TokenAmount::from_atto(0), None, SendFlags::empty()).unwrap();
+    rt.verify();
+
+    assert_eq!(ExitCode::USR_READ_ONLY, rt.delete_actor().unwrap_err().exit_code());
+    rt.in_call.replace(false);
+
+    // Leaving read-only mode restores normal behaviour.
+    rt.set_read_only(false);
+    rt.transaction(|st: &mut u64, _| {
+        *st = 43;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(43u64, rt.state::<u64>().unwrap());
+}
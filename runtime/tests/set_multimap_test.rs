@@ -29,6 +29,22 @@ fn put_remove() {
     assert!(smm.get(&epoch).unwrap().is_none());
 }
 
+#[test]
+fn remove_last_value_removes_key() {
+    let store = MemoryBlockstore::new();
+    let mut smm = SetMultimap::<_, ChainEpoch, u64>::empty(&store, CONFIG, "t");
+    let empty_root = smm.flush().unwrap();
+
+    let epoch: ChainEpoch = 100;
+    smm.put(&epoch, 8).unwrap();
+    smm.put(&epoch, 2).unwrap();
+    smm.remove(&epoch, 8).unwrap();
+    assert!(smm.get(&epoch).unwrap().is_some());
+    smm.remove(&epoch, 2).unwrap();
+    assert!(smm.get(&epoch).unwrap().is_none());
+    assert_eq!(empty_root, smm.flush().unwrap());
+}
+
 #[test]
 fn for_each() {
     let store = MemoryBlockstore::new();
@@ -51,3 +67,50 @@ fn for_each() {
 
     assert_eq!(vals.len(), 3);
 }
+
+#[test]
+fn for_each_ranged_resumes_from_cursor() {
+    let store = MemoryBlockstore::new();
+    let mut smm = SetMultimap::<_, ChainEpoch, u64>::empty(&store, CONFIG, "t");
+    for epoch in 0..10 {
+        smm.put_many(&epoch, &[1, 2, 3, 4, 5]).unwrap();
+    }
+
+    let mut epochs = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (count, next) = smm
+            .for_each_ranged(cursor.as_ref(), Some(3), |k, _| {
+                epochs.push(k);
+                Ok(())
+            })
+            .unwrap();
+        assert!(count <= 3);
+        cursor = next;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    epochs.sort();
+    assert_eq!((0..10).collect::<Vec<_>>(), epochs);
+
+    let mut vals = Vec::new();
+    let (count, next) = smm
+        .for_each_in_ranged(&4, None, Some(2), |v| {
+            vals.push(v);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(2, count);
+    let (count, next) = smm
+        .for_each_in_ranged(&4, next.as_ref(), None, |v| {
+            vals.push(v);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!((3, None), (count, next));
+    vals.sort();
+    assert_eq!(vec![1, 2, 3, 4, 5], vals);
+
+    assert_eq!((0, None), smm.for_each_in_ranged(&99, None, None, |_| Ok(())).unwrap());
+}
@@ -0,0 +1,79 @@
+use cid::Cid;
+use fil_actors_runtime::runtime::{ActorCode, Runtime};
+use fil_actors_runtime::test_utils::{expect_abort, MockRuntime, ACCOUNT_ACTOR_CODE_ID};
+use fil_actors_runtime::{actor_error, ActorError};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::error::ExitCode;
+use fvm_shared::upgrade::UpgradeInfo;
+use fvm_shared::MethodNum;
+use multihash_codetable::Code;
+
+/// An actor whose state was a single number in its previous version, and is now a pair.
+struct UpgradingActor;
+
+impl ActorCode for UpgradingActor {
+    type Methods = ();
+
+    fn name() -> &'static str {
+        "Upgrading"
+    }
+
+    fn invoke_method<RT>(
+        _: &RT,
+        _: MethodNum,
+        _: Option<IpldBlock>,
+    ) -> Result<Option<IpldBlock>, ActorError>
+    where
+        RT: Runtime,
+        RT::Blockstore: Blockstore + Clone,
+    {
+        Err(actor_error!(unhandled_message; "no methods"))
+    }
+
+    fn upgrade<RT>(
+        rt: &RT,
+        info: UpgradeInfo,
+        params: Option<IpldBlock>,
+    ) -> Result<Option<IpldBlock>, ActorError>
+    where
+        RT: Runtime,
+        RT::Blockstore: Blockstore + Clone,
+    {
+        if info.old_code_cid != *ACCOUNT_ACTOR_CODE_ID {
+            return Err(
+                actor_error!(illegal_argument; "cannot upgrade from {}", info.old_code_cid),
+            );
+        }
+        let extra: u64 = params.unwrap().deserialize()?;
+        let old: u64 = rt.state()?;
+        rt.set_state_root(&rt.store().put_cbor(&(old, extra), Code::Blake2b256).unwrap())?;
+        Ok(IpldBlock::serialize_cbor(&"upgraded")?)
+    }
+}
+
+#[test]
+fn upgrade_migrates_state() {
+    let rt = MockRuntime::new();
+    rt.replace_state(&7u64);
+    let ret = rt
+        .call_upgrade::<UpgradingActor>(
+            *ACCOUNT_ACTOR_CODE_ID,
+            IpldBlock::serialize_cbor(&8u64).unwrap(),
+        )
+        .unwrap();
+    assert_eq!("upgraded", ret.unwrap().deserialize::<String>().unwrap());
+    assert_eq!((7u64, 8u64), rt.get_state::<(u64, u64)>());
+}
+
+#[test]
+fn rejected_upgrade_does_not_change_state() {
+    let rt = MockRuntime::new();
+    rt.replace_state(&7u64);
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        rt.call_upgrade::<UpgradingActor>(Cid::default(), None),
+    );
+    assert_eq!(7u64, rt.get_state::<u64>());
+}
@@ -1,3 +1,6 @@
+use cid::Cid;
+use multihash_codetable::{Code, MultihashDigest};
+
 /// The bundled CAR embedded as a byte slice for easy consumption by Rust programs.
 ///
 /// The root CID of the CAR points to an actor index data structure. It is a
@@ -25,3 +28,31 @@
 /// The Filecoin client must import the contents of CAR into the blockstore, but
 /// may opt to exclude the index data structure.
 pub const BUNDLE_CAR: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/bundle/bundle.car"));
+
+// Bundles for further networks are built when listed in the BUILD_FIL_NETWORKS environment
+// variable, or all of them if it is "all".
+include!(concat!(env!("OUT_DIR"), "/bundles.rs"));
+
+/// The name of the network for which [`BUNDLE_CAR`] was built.
+pub const NETWORK: &str = NETWORK_BUNDLES[0].0;
+
+/// Returns the bundled CAR for a network, if it was built.
+pub fn network_bundle(network: &str) -> Option<&'static [u8]> {
+    NETWORK_BUNDLES.iter().find(|(name, _)| *name == network).map(|(_, car)| *car)
+}
+
+/// The IPLD codec of actor bytecode blocks.
+const IPLD_RAW: u64 = 0x55;
+
+/// Computes the code CID of an actor's Wasm bytecode, as the FVM does when loading a bundle:
+/// a CIDv1 of the raw bytecode with a Blake2b-256 multihash.
+pub fn compute_code_cid(wasm: &[u8]) -> Cid {
+    Cid::new_v1(IPLD_RAW, Code::Blake2b256.digest(wasm))
+}
+
+/// Computes the code CIDs of a sequence of actors' Wasm bytecode.
+pub fn compute_code_cids<'a>(wasms: impl IntoIterator<Item = &'a [u8]>) -> Vec<Cid> {
+    wasms.into_iter().map(compute_code_cid).collect()
+}
+
+pub mod verify;
@@ -1,22 +1,96 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::io::Write;
+use std::path::PathBuf;
 
-use fil_builtin_actors_bundle::BUNDLE_CAR;
+use fil_builtin_actors_bundle::verify::{verify_bundle, ExpectedCids};
+use fil_builtin_actors_bundle::{network_bundle, BUNDLE_CAR, NETWORK_BUNDLES};
 
 #[derive(Parser)]
 #[clap(name = env!("CARGO_PKG_NAME"))]
 #[clap(version = env!("CARGO_PKG_VERSION"))]
 #[clap(about = "Writes a CAR file containing Wasm bytecode for Filecoin actors.", long_about = None)]
+#[clap(args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
     /// The output car path. Defaults to STDOUT.
     #[clap(short, long, required = false)]
     output: Option<String>,
+    /// The network whose bundle to write, which must have been built.
+    /// Defaults to the network configured at build time.
+    #[clap(short, long, required = false)]
+    network: Option<String>,
+    /// Writes the bundle of every network built to this directory, as
+    /// `builtin-actors-<network>.car`.
+    #[clap(long, required = false, conflicts_with_all = ["output", "network"])]
+    all_networks: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Checks a bundle CAR's code CIDs against its bytecode, that its manifest lists exactly
+    /// the builtin actors, and optionally that it matches pinned CIDs.
+    Verify {
+        /// The bundle CAR.
+        car: PathBuf,
+        /// A JSON file of the expected manifest and actor code CIDs.
+        #[clap(long)]
+        expected: Option<PathBuf>,
+        /// Writes the bundle's CIDs to this file, in the format of the expected CIDs,
+        /// if the bundle verifies.
+        #[clap(long)]
+        pin: Option<PathBuf>,
+    },
+}
+
+fn verify(car: PathBuf, expected: Option<PathBuf>, pin: Option<PathBuf>) -> anyhow::Result<()> {
+    let car = std::fs::read(&car)?;
+    let expected = match expected {
+        Some(path) => Some(ExpectedCids::from_json(&std::fs::read_to_string(path)?)?),
+        None => None,
+    };
+    let verification = verify_bundle(&car, expected.as_ref())?;
+    println!("manifest {}", verification.manifest);
+    for (name, cid) in &verification.actors {
+        println!("{:<20} {}", name, cid);
+    }
+    if !verification.is_ok() {
+        for problem in &verification.problems {
+            eprintln!("{}", problem);
+        }
+        anyhow::bail!("bundle failed verification with {} problems", verification.problems.len());
+    }
+    if let Some(path) = pin {
+        std::fs::write(path, verification.pinned().to_json())?;
+    }
+    Ok(())
 }
 
-fn main() -> Result<(), std::io::Error> {
+fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    if let Some(Command::Verify { car, expected, pin }) = cli.command {
+        return verify(car, expected, pin);
+    }
+    if let Some(dir) = cli.all_networks {
+        std::fs::create_dir_all(&dir)?;
+        for (network, car) in NETWORK_BUNDLES {
+            std::fs::write(dir.join(format!("builtin-actors-{}.car", network)), car)?;
+        }
+        return Ok(());
+    }
+    let car = match cli.network {
+        Some(network) => network_bundle(&network).ok_or_else(|| {
+            let built: Vec<_> = NETWORK_BUNDLES.iter().map(|(name, _)| *name).collect();
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no bundle built for network {}, only for {:?}", network, built),
+            )
+        })?,
+        None => BUNDLE_CAR,
+    };
     match cli.output {
-        Some(path) => std::fs::write(path, BUNDLE_CAR),
-        None => std::io::stdout().write_all(BUNDLE_CAR),
+        Some(path) => std::fs::write(path, car)?,
+        None => std::io::stdout().write_all(car)?,
     }
+    Ok(())
 }
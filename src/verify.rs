@@ -0,0 +1,166 @@
+//! Verification of a bundle CAR against the actors it must contain and, optionally, a pinned
+//! set of expected CIDs, for node release pipelines.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use cid::Cid;
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_encoding::CborStore;
+
+use crate::compute_code_cid;
+
+/// The names of the actors a bundle's manifest must list, each exactly once.
+pub const ACTOR_NAMES: &[&str] = &[
+    "system",
+    "init",
+    "cron",
+    "account",
+    "storagepower",
+    "storageminer",
+    "storagemarket",
+    "paymentchannel",
+    "multisig",
+    "reward",
+    "verifiedregistry",
+    "datacap",
+    "placeholder",
+    "evm",
+    "eam",
+    "ethaccount",
+];
+
+/// The CIDs a bundle is pinned to, as read from or written to an expected-CIDs file.
+///
+/// The file is a JSON object with the manifest CID, optionally, and the code CID of each actor
+/// by name:
+/// `{"manifest": "bafy...", "actors": {"account": "bafk...", ...}}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExpectedCids {
+    pub manifest: Option<Cid>,
+    pub actors: BTreeMap<String, Cid>,
+}
+
+impl ExpectedCids {
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let parse = |v: &serde_json::Value| -> anyhow::Result<Cid> {
+            let s = v.as_str().ok_or_else(|| anyhow!("expected a CID string, got {v}"))?;
+            Cid::try_from(s).with_context(|| format!("invalid CID {s}"))
+        };
+        let manifest = value.get("manifest").map(parse).transpose()?;
+        let actors = value
+            .get("actors")
+            .and_then(|a| a.as_object())
+            .ok_or_else(|| anyhow!("missing actors object"))?
+            .iter()
+            .map(|(name, cid)| Ok((name.clone(), parse(cid)?)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { manifest, actors })
+    }
+
+    pub fn to_json(&self) -> String {
+        let actors: serde_json::Map<String, serde_json::Value> =
+            self.actors.iter().map(|(name, cid)| (name.clone(), cid.to_string().into())).collect();
+        let mut value = serde_json::json!({ "actors": actors });
+        if let Some(manifest) = self.manifest {
+            value["manifest"] = manifest.to_string().into();
+        }
+        serde_json::to_string_pretty(&value).unwrap()
+    }
+}
+
+/// The result of verifying a bundle.
+#[derive(Clone, Debug, Default)]
+pub struct Verification {
+    /// The CID of the bundle's manifest.
+    pub manifest: Cid,
+    /// The code CID of each actor listed in the manifest, by name.
+    pub actors: BTreeMap<String, Cid>,
+    /// Descriptions of each way the bundle is malformed or differs from what was expected.
+    pub problems: Vec<String>,
+}
+
+impl Verification {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// The CIDs of the bundle, to pin for later verification.
+    pub fn pinned(&self) -> ExpectedCids {
+        ExpectedCids { manifest: Some(self.manifest), actors: self.actors.clone() }
+    }
+}
+
+/// Loads a bundle CAR, recomputes each actor's code CID from its bytecode, and checks the
+/// manifest lists exactly the builtin actors, each once.
+/// If expected CIDs are given, the bundle must match them exactly.
+///
+/// Problems with the bundle's content are collected in the returned verification.
+/// An error is returned only if the CAR or its manifest can't be read at all.
+pub fn verify_bundle(car: &[u8], expected: Option<&ExpectedCids>) -> anyhow::Result<Verification> {
+    let store = MemoryBlockstore::new();
+    let roots = futures::executor::block_on(fvm_ipld_car::load_car(&store, car))
+        .context("failed to load bundle CAR")?;
+    let manifest = *roots.first().ok_or_else(|| anyhow!("bundle has no roots"))?;
+    let (version, data): (u32, Cid) =
+        store.get_cbor(&manifest)?.ok_or_else(|| anyhow!("missing manifest {manifest}"))?;
+    if version != 1 {
+        return Err(anyhow!("unsupported manifest version {version}"));
+    }
+    let entries: Vec<(String, Cid)> =
+        store.get_cbor(&data)?.ok_or_else(|| anyhow!("missing manifest data {data}"))?;
+
+    let mut verification = Verification { manifest, ..Default::default() };
+    let problems = &mut verification.problems;
+    for (name, cid) in entries {
+        if !ACTOR_NAMES.contains(&name.as_str()) {
+            problems.push(format!("unexpected actor {name} in manifest"));
+        }
+        match store.get(&cid)? {
+            Some(wasm) => {
+                let computed = compute_code_cid(&wasm);
+                if computed != cid {
+                    problems.push(format!(
+                        "actor {name} has code CID {cid} but its bytecode hashes to {computed}"
+                    ));
+                }
+            }
+            None => problems.push(format!("bytecode of actor {name} ({cid}) missing from CAR")),
+        }
+        if verification.actors.insert(name.clone(), cid).is_some() {
+            problems.push(format!("actor {name} listed more than once in manifest"));
+        }
+    }
+    for name in ACTOR_NAMES {
+        if !verification.actors.contains_key(*name) {
+            problems.push(format!("actor {name} missing from manifest"));
+        }
+    }
+
+    if let Some(expected) = expected {
+        if let Some(manifest) = expected.manifest {
+            if manifest != verification.manifest {
+                problems.push(format!(
+                    "manifest is {} but {manifest} was expected",
+                    verification.manifest
+                ));
+            }
+        }
+        for (name, cid) in &verification.actors {
+            match expected.actors.get(name) {
+                Some(e) if e != cid => {
+                    problems.push(format!("actor {name} has code CID {cid} but {e} was expected"))
+                }
+                Some(_) => {}
+                None => problems.push(format!("actor {name} is not in the expected CIDs")),
+            }
+        }
+        for name in expected.actors.keys() {
+            if !verification.actors.contains_key(name) {
+                problems.push(format!("expected actor {name} is not in the bundle"));
+            }
+        }
+    }
+    Ok(verification)
+}
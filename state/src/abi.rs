@@ -0,0 +1,48 @@
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::ActorCode;
+use fil_actors_runtime::test_utils::MockRuntime;
+use fil_actors_runtime::{builtin_actors, MethodDescriptor};
+use num_traits::FromPrimitive;
+use serde::Serialize;
+
+/// Describes the methods of a builtin actor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ActorAbi {
+    /// The actor type's name, as in the bundle manifest.
+    pub actor_type: &'static str,
+    /// The actor's name, as used in debugging.
+    pub name: &'static str,
+    pub methods: Vec<MethodDescriptor>,
+}
+
+fn actor_abi<A: ActorCode>(actor_type: Type) -> ActorAbi {
+    // The runtime type only resolves the method handlers, which the descriptions don't depend on.
+    ActorAbi {
+        actor_type: actor_type.name(),
+        name: A::name(),
+        methods: A::methods::<MockRuntime>(),
+    }
+}
+
+macro_rules! actor_abis {
+    ($($typ:ident => $actor:ty,)*) => {
+        /// Describes the methods of every builtin actor, in actor type order.
+        pub fn builtin_actors_abi() -> Vec<ActorAbi> {
+            (1..)
+                .map_while(Type::from_i32)
+                .filter_map(|t| match t {
+                    $(Type::$typ => Some(actor_abi::<$actor>(t)),)*
+                    // The placeholder has no code to invoke.
+                    Type::Placeholder => None,
+                })
+                .collect()
+        }
+    };
+}
+
+builtin_actors!(actor_abis);
+
+/// Returns the descriptions of every builtin actor's methods as JSON.
+pub fn builtin_actors_abi_json() -> String {
+    serde_json::to_string_pretty(&builtin_actors_abi()).expect("failed to serialize actor ABI")
+}
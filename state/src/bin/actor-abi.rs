@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use fil_builtin_actors_state::abi::builtin_actors_abi_json;
+
+#[derive(Parser)]
+#[clap(name = "actor-abi")]
+#[clap(version = env!("CARGO_PKG_VERSION"))]
+#[clap(about = "Writes a JSON description of the builtin actors' methods.", long_about = None)]
+struct Cli {
+    /// The output path. Defaults to STDOUT.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<(), std::io::Error> {
+    let cli = Cli::parse();
+    let json = builtin_actors_abi_json();
+    match cli.output {
+        Some(path) => std::fs::write(path, json + "\n"),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    }
+}
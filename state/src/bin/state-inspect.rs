@@ -0,0 +1,89 @@
+use std::fs::File;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use cid::Cid;
+use clap::Parser;
+use fil_builtin_actors_state::inspect::{
+    inspect_actor, load_tree, manifest_from_bundle, manifest_from_tree,
+};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::address::Address;
+
+#[derive(Parser)]
+#[clap(name = "state-inspect")]
+#[clap(version = env!("CARGO_PKG_VERSION"))]
+#[clap(about = "Decodes and prints an actor's state from a CAR snapshot.", long_about = None)]
+struct Cli {
+    /// The CAR containing the state tree.
+    car: PathBuf,
+    /// The actor's address.
+    address: String,
+    /// The state root. Defaults to the CAR's first root.
+    #[clap(long)]
+    root: Option<String>,
+    /// A bundle CAR whose manifest identifies the actor types.
+    /// Defaults to the manifest referenced by the system actor.
+    #[clap(long)]
+    bundle: Option<PathBuf>,
+    /// Prints only the named collection of the state, e.g. `proposals` or `deadlines`.
+    #[clap(long)]
+    collection: Option<String>,
+    /// Prints only the entries of collections with this key, e.g. a deal ID.
+    #[clap(long)]
+    key: Option<String>,
+    /// Prints JSON rather than text.
+    #[clap(long)]
+    json: bool,
+}
+
+fn load_car(store: &MemoryBlockstore, path: &PathBuf) -> anyhow::Result<Vec<Cid>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let file = futures::io::AllowStdIo::new(file);
+    let roots = futures::executor::block_on(fvm_ipld_car::load_car(store, file))
+        .with_context(|| format!("failed to load {}", path.display()))?;
+    Ok(roots)
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let store = MemoryBlockstore::default();
+    let roots = load_car(&store, &cli.car)?;
+    let root = match &cli.root {
+        Some(root) => Cid::from_str(root)?,
+        None => *roots.first().ok_or_else(|| anyhow!("CAR has no roots"))?,
+    };
+    let tree = load_tree(&store, &root)?;
+    let manifest = match &cli.bundle {
+        Some(bundle) => {
+            let roots = load_car(&store, bundle)?;
+            manifest_from_bundle(
+                &store,
+                roots.first().ok_or_else(|| anyhow!("bundle has no roots"))?,
+            )?
+        }
+        None => manifest_from_tree(&store, &tree)?,
+    };
+    let address = Address::from_str(&cli.address)?;
+
+    let mut inspection = inspect_actor(&store, &tree, &manifest, &address)?;
+    if let Some(name) = &cli.collection {
+        inspection.collections.retain(|(n, _)| n == name);
+        if inspection.collections.is_empty() {
+            return Err(anyhow!("no collection {} in {}", name, inspection.address));
+        }
+    }
+    if let Some(key) = &cli.key {
+        for (_, entries) in inspection.collections.iter_mut() {
+            entries.retain(|(k, _)| k == key);
+        }
+    }
+
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&inspection.to_json())?);
+    } else {
+        print!("{}", inspection);
+    }
+    Ok(())
+}
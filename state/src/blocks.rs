@@ -0,0 +1,168 @@
+//! Accounting of the blocks making up a state tree, for sizing snapshots and for spotting
+//! blocks left behind in a store, such as by a buggy migration.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use anyhow::Context;
+use cid::Cid;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::DAG_CBOR;
+use ipld_core::ipld::Ipld;
+
+use crate::inspect::load_tree;
+
+/// The multihash code of identity hashes, whose CIDs carry their data inline.
+const IDENTITY_HASH: u64 = 0;
+
+/// A blockstore that can enumerate the blocks it holds.
+pub trait ListBlockstore: Blockstore {
+    /// Returns the CIDs of all blocks in the store, in any order.
+    fn list(&self) -> anyhow::Result<Vec<Cid>>;
+}
+
+impl ListBlockstore for MemoryBlockstore {
+    fn list(&self) -> anyhow::Result<Vec<Cid>> {
+        Ok(self.cids())
+    }
+}
+
+/// A count of blocks and their total size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlockStats {
+    pub blocks: u64,
+    pub bytes: u64,
+}
+
+impl BlockStats {
+    fn add(&mut self, bytes: usize) {
+        self.blocks += 1;
+        self.bytes += bytes as u64;
+    }
+}
+
+/// The blocks reachable from a state root, and optionally those in the store that aren't.
+#[derive(Clone, Debug, Default)]
+pub struct BlockReport {
+    /// Blocks reachable from the states of actors, by actor type name, or "unknown" for actors
+    /// whose code isn't in the manifest. A block shared between actors is counted once, for
+    /// the first actor in address order to reach it.
+    pub by_actor_type: BTreeMap<String, BlockStats>,
+    /// Blocks of the state tree itself, and others it links to outside any actor's state,
+    /// such as actor code.
+    pub tree: BlockStats,
+    /// Blocks linked to but absent from the store.
+    pub missing: Vec<Cid>,
+    /// Blocks in the store not reachable from the root, if the store has been listed.
+    pub unreachable: Option<Vec<Cid>>,
+    /// The size of the unreachable blocks.
+    pub unreachable_stats: BlockStats,
+    reachable: HashSet<Cid>,
+}
+
+impl BlockReport {
+    /// The blocks reachable from the root, in total.
+    pub fn reachable_stats(&self) -> BlockStats {
+        self.by_actor_type.values().fold(self.tree, |acc, s| BlockStats {
+            blocks: acc.blocks + s.blocks,
+            bytes: acc.bytes + s.bytes,
+        })
+    }
+
+    /// Lists the store, recording the blocks in it that aren't reachable from the root.
+    pub fn find_unreachable<BS: ListBlockstore>(&mut self, store: &BS) -> anyhow::Result<()> {
+        let mut stats = BlockStats::default();
+        let mut unreachable = vec![];
+        for cid in store.list()? {
+            if self.reachable.contains(&cid) {
+                continue;
+            }
+            let block = store.get(&cid)?.with_context(|| format!("listed block {cid} missing"))?;
+            stats.add(block.len());
+            unreachable.push(cid);
+        }
+        unreachable.sort();
+        self.unreachable = Some(unreachable);
+        self.unreachable_stats = stats;
+        Ok(())
+    }
+}
+
+/// Walks all blocks reachable from the state tree at `root`, attributing them to the types of
+/// the actors whose state reaches them.
+/// Links are followed out of DAG-CBOR blocks. Blocks of other codecs are counted but not
+/// decoded, and identity-hashed CIDs are skipped as they aren't stored.
+pub fn block_report<BS: Blockstore>(
+    store: &BS,
+    root: &Cid,
+    manifest: &BTreeMap<Cid, Type>,
+) -> anyhow::Result<BlockReport> {
+    let mut report = BlockReport::default();
+    let tree = load_tree(store, root)?;
+    for actor in tree.values() {
+        let type_name = manifest.get(&actor.code).map_or("unknown", |t| t.name());
+        let mut stats = report.by_actor_type.get(type_name).copied().unwrap_or_default();
+        walk(store, &actor.state, &mut report.reachable, &mut report.missing, &mut stats)?;
+        report.by_actor_type.insert(type_name.to_string(), stats);
+    }
+    walk(store, root, &mut report.reachable, &mut report.missing, &mut report.tree)?;
+    report.missing.sort();
+    Ok(report)
+}
+
+fn walk<BS: Blockstore>(
+    store: &BS,
+    start: &Cid,
+    seen: &mut HashSet<Cid>,
+    missing: &mut Vec<Cid>,
+    stats: &mut BlockStats,
+) -> anyhow::Result<()> {
+    let mut stack = vec![*start];
+    while let Some(cid) = stack.pop() {
+        if cid.hash().code() == IDENTITY_HASH || !seen.insert(cid) {
+            continue;
+        }
+        let Some(block) = store.get(&cid)? else {
+            missing.push(cid);
+            continue;
+        };
+        stats.add(block.len());
+        if cid.codec() == DAG_CBOR {
+            let ipld: Ipld = fvm_ipld_encoding::from_slice(&block)
+                .with_context(|| format!("failed to decode block {cid}"))?;
+            push_links(&ipld, &mut stack);
+        }
+    }
+    Ok(())
+}
+
+fn push_links(ipld: &Ipld, stack: &mut Vec<Cid>) {
+    match ipld {
+        Ipld::Link(cid) => stack.push(*cid),
+        Ipld::List(items) => items.iter().for_each(|i| push_links(i, stack)),
+        Ipld::Map(entries) => entries.values().for_each(|v| push_links(v, stack)),
+        _ => {}
+    }
+}
+
+impl fmt::Display for BlockReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, name: &str, s: &BlockStats| {
+            writeln!(f, "{:<20} {:>10} blocks {:>14} bytes", name, s.blocks, s.bytes)
+        };
+        for (name, stats) in &self.by_actor_type {
+            row(f, name, stats)?;
+        }
+        row(f, "(state tree)", &self.tree)?;
+        row(f, "reachable", &self.reachable_stats())?;
+        if self.unreachable.is_some() {
+            row(f, "unreachable", &self.unreachable_stats)?;
+        }
+        if !self.missing.is_empty() {
+            writeln!(f, "{} linked blocks missing from the store", self.missing.len())?;
+        }
+        Ok(())
+    }
+}
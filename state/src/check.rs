@@ -1,14 +1,17 @@
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use anyhow::bail;
 use cid::Cid;
 use fil_actor_account::State as AccountState;
-use fil_actor_cron::State as CronState;
+use fil_actor_cron::{Entry as CronEntry, State as CronState};
 use fil_actor_datacap::State as DataCapState;
 use fil_actor_init::State as InitState;
-use fil_actor_market::State as MarketState;
+use fil_actor_market::{Method as MarketMethod, State as MarketState};
 use fil_actor_miner::CronEventPayload;
 use fil_actor_miner::PowerPair;
 use fil_actor_miner::State as MinerState;
@@ -17,21 +20,28 @@ use fil_actor_miner::CRON_EVENT_PROVING_DEADLINE;
 use fil_actor_multisig::State as MultisigState;
 use fil_actor_paych::State as PaychState;
 use fil_actor_power::testing::MinerCronEvent;
-use fil_actor_power::State as PowerState;
+use fil_actor_power::{Method as PowerMethod, State as PowerState};
 use fil_actor_reward::State as RewardState;
-use fil_actor_verifreg::{DataCap, State as VerifregState};
+use fil_actor_verifreg::{Claim, ClaimID, DataCap, State as VerifregState};
 use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::{BSStats, TrackingBlockstore};
 use fil_actors_runtime::DealWeight;
 use fil_actors_runtime::MessageAccumulator;
-use fil_actors_runtime::VERIFIED_REGISTRY_ACTOR_ADDR;
+use fil_actors_runtime::{
+    BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR, INIT_ACTOR_ADDR,
+    REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
+};
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::from_slice;
 use fvm_ipld_encoding::CborStore;
 use fvm_shared::address::Address;
 use fvm_shared::address::Protocol;
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::deal::DealID;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::sector::SectorNumber;
+use fvm_shared::ActorID;
 use num_traits::Zero;
 
 use fil_actor_account::testing as account;
@@ -68,6 +78,245 @@ pub fn check_state_invariants<BS: Blockstore>(
     expected_balance_total: Option<TokenAmount>,
     prior_epoch: ChainEpoch,
 ) -> anyhow::Result<MessageAccumulator> {
+    check_actors(
+        store,
+        manifest,
+        policy,
+        tree.iter().map(|(key, actor)| (*key, actor.clone())),
+        expected_balance_total,
+        prior_epoch,
+        false,
+        false,
+    )
+    .map(|(acc, _, _)| acc)
+}
+
+/// Checks invariants as [`check_state_invariants`] does, also returning the time and
+/// blockstore reads spent checking each actor.
+pub fn check_state_invariants_profiled<BS: Blockstore>(
+    store: &BS,
+    manifest: &BTreeMap<Cid, Type>,
+    policy: &Policy,
+    tree: &BTreeMap<Address, ActorState>,
+    expected_balance_total: Option<TokenAmount>,
+    prior_epoch: ChainEpoch,
+) -> anyhow::Result<(MessageAccumulator, CheckProfile)> {
+    check_actors(
+        store,
+        manifest,
+        policy,
+        tree.iter().map(|(key, actor)| (*key, actor.clone())),
+        expected_balance_total,
+        prior_epoch,
+        false,
+        false,
+    )
+    .map(|(acc, profile, _)| (acc, profile))
+}
+
+/// Checks the same invariants as [`check_state_invariants`], without holding the whole state
+/// tree or every actor's state summary in memory.
+///
+/// Each miner is checked against the singleton actors as soon as they have all been seen,
+/// after which its summary is discarded. Only the messages, and the aggregates needed by
+/// later checks, are kept.
+/// Memory is bounded when the singleton actors come before the miners, as they do when
+/// iterating the tree in address order. Miners seen earlier are held until the singletons are.
+pub fn check_state_invariants_streaming<BS, I>(
+    store: &BS,
+    manifest: &BTreeMap<Cid, Type>,
+    policy: &Policy,
+    actors: I,
+    expected_balance_total: Option<TokenAmount>,
+    prior_epoch: ChainEpoch,
+) -> anyhow::Result<MessageAccumulator>
+where
+    BS: Blockstore,
+    I: IntoIterator<Item = (Address, ActorState)>,
+{
+    check_actors(store, manifest, policy, actors, expected_balance_total, prior_epoch, true, false)
+        .map(|(acc, _, _)| acc)
+}
+
+/// Checks invariants as [`check_state_invariants`] does, but tolerating actors whose code CID
+/// isn't in the manifest, as on networks bearing user-deployed actors, rather than failing.
+///
+/// Such actors are skipped, besides counting toward the total balance. Returns a census of the
+/// actors with each code CID, from which the unknown codes can be reported.
+pub fn check_state_invariants_tolerant<BS: Blockstore>(
+    store: &BS,
+    manifest: &BTreeMap<Cid, Type>,
+    policy: &Policy,
+    tree: &BTreeMap<Address, ActorState>,
+    expected_balance_total: Option<TokenAmount>,
+    prior_epoch: ChainEpoch,
+) -> anyhow::Result<(MessageAccumulator, CodeCensus)> {
+    check_actors(
+        store,
+        manifest,
+        policy,
+        tree.iter().map(|(key, actor)| (*key, actor.clone())),
+        expected_balance_total,
+        prior_epoch,
+        false,
+        true,
+    )
+    .map(|(acc, _, census)| (acc, census))
+}
+
+/// The number of actors in a state tree with each code CID.
+#[derive(Clone, Debug, Default)]
+pub struct CodeCensus {
+    pub codes: BTreeMap<Cid, CodeCount>,
+}
+
+/// The actors with one code CID.
+#[derive(Clone, Debug, Default)]
+pub struct CodeCount {
+    /// The name of the code's actor type, or None if the code isn't in the manifest.
+    pub type_name: Option<String>,
+    pub actors: u64,
+}
+
+impl CodeCensus {
+    fn record(&mut self, code: Cid, actor_type: Option<&Type>) {
+        let count = self.codes.entry(code).or_insert_with(|| CodeCount {
+            type_name: actor_type.map(|t| t.name().to_string()),
+            actors: 0,
+        });
+        count.actors += 1;
+    }
+
+    /// The code CIDs which aren't in the manifest, and whose actors weren't checked.
+    pub fn unknown_codes(&self) -> Vec<&Cid> {
+        self.codes.iter().filter(|(_, c)| c.type_name.is_none()).map(|(code, _)| code).collect()
+    }
+
+    /// The number of actors whose code isn't in the manifest.
+    pub fn unknown_actors(&self) -> u64 {
+        self.codes.values().filter(|c| c.type_name.is_none()).map(|c| c.actors).sum()
+    }
+}
+
+impl fmt::Display for CodeCensus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (code, count) in &self.codes {
+            writeln!(
+                f,
+                "{:>10} {:<20} {}",
+                count.actors,
+                count.type_name.as_deref().unwrap_or("unknown"),
+                code
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The time and blockstore reads spent checking an actor's own state.
+/// Cross-actor checks aren't attributed to any actor.
+#[derive(Clone, Debug)]
+pub struct ActorCheckCost {
+    pub address: Address,
+    /// The name of the actor's type.
+    pub type_name: String,
+    pub elapsed: Duration,
+    pub reads: BSStats,
+}
+
+/// The costs of checking each actor, for finding which checks are worth optimizing and which
+/// actors have pathologically large state.
+#[derive(Clone, Debug, Default)]
+pub struct CheckProfile {
+    /// Costs in the order the actors were checked.
+    pub actors: Vec<ActorCheckCost>,
+    /// The time spent on the whole check, including cross-actor checks.
+    pub elapsed: Duration,
+}
+
+impl CheckProfile {
+    /// The `n` actors that took longest to check, slowest first.
+    pub fn hotspots(&self, n: usize) -> Vec<&ActorCheckCost> {
+        let mut costs: Vec<_> = self.actors.iter().collect();
+        costs.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then(b.reads.r.cmp(&a.reads.r)));
+        costs.truncate(n);
+        costs
+    }
+
+    /// The total time and reads spent checking actors of each type, by type name.
+    pub fn by_actor_type(&self) -> BTreeMap<&str, (Duration, BSStats)> {
+        let mut totals = BTreeMap::<&str, (Duration, BSStats)>::new();
+        for cost in &self.actors {
+            let (elapsed, reads) = totals.entry(&cost.type_name).or_default();
+            *elapsed += cost.elapsed;
+            reads.r += cost.reads.r;
+            reads.br += cost.reads.br;
+        }
+        totals
+    }
+
+    /// A report of the time spent by actor type, and a table of the `n` slowest actors.
+    pub fn report(&self, n: usize) -> HotspotReport<'_> {
+        HotspotReport { profile: self, n }
+    }
+}
+
+/// Displays a [`CheckProfile`] as a table.
+pub struct HotspotReport<'a> {
+    profile: &'a CheckProfile,
+    n: usize,
+}
+
+impl fmt::Display for HotspotReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "checked {} actors in {:.3?}",
+            self.profile.actors.len(),
+            self.profile.elapsed
+        )?;
+        for (name, (elapsed, reads)) in self.profile.by_actor_type() {
+            writeln!(
+                f,
+                "{:<20} {:>12.3?} {:>10} reads {:>14} bytes",
+                name, elapsed, reads.r, reads.br
+            )?;
+        }
+        writeln!(f, "slowest {} actors:", self.n)?;
+        for cost in self.profile.hotspots(self.n) {
+            writeln!(
+                f,
+                "{:<12} {:<20} {:>12.3?} {:>10} reads {:>14} bytes",
+                cost.address.to_string(),
+                cost.type_name,
+                cost.elapsed,
+                cost.reads.r,
+                cost.reads.br
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_actors<BS, I>(
+    store: &BS,
+    manifest: &BTreeMap<Cid, Type>,
+    policy: &Policy,
+    actors: I,
+    expected_balance_total: Option<TokenAmount>,
+    prior_epoch: ChainEpoch,
+    streaming: bool,
+    tolerate_unknown: bool,
+) -> anyhow::Result<(MessageAccumulator, CheckProfile, CodeCensus)>
+where
+    BS: Blockstore,
+    I: IntoIterator<Item = (Address, ActorState)>,
+{
+    let started = Instant::now();
+    let mut profile = CheckProfile::default();
+    let mut census = CodeCensus::default();
+    let store = &TrackingBlockstore::new(store);
     let acc = MessageAccumulator::default();
     let mut total_fil = TokenAmount::zero();
 
@@ -83,7 +332,13 @@ pub fn check_state_invariants<BS: Blockstore>(
     let mut verifreg_summary: Option<verifreg::StateSummary> = None;
     let mut datacap_summary: Option<frc46_token::token::state::StateSummary> = None;
 
-    tree.iter().try_for_each(|(key, actor)| -> anyhow::Result<()> {
+    // Miners already checked against the singletons, and the claims not yet checked
+    // against their provider.
+    let mut checked_miners = HashSet::<Address>::new();
+    let mut claims_by_provider = HashMap::<ActorID, Vec<(ClaimID, Claim)>>::new();
+
+    actors.into_iter().try_for_each(|(key, actor)| -> anyhow::Result<()> {
+        let cross_acc = &acc;
         let acc = acc.with_prefix(format!("{key} "));
 
         if key.protocol() != Protocol::ID {
@@ -91,6 +346,9 @@ pub fn check_state_invariants<BS: Blockstore>(
         }
         total_fil += &actor.balance;
 
+        let actor_started = Instant::now();
+        store.take_stats();
+        census.record(actor.code, manifest.get(&actor.code));
         match manifest.get(&actor.code) {
             Some(Type::System) => (),
             Some(Type::Init) => {
@@ -107,9 +365,11 @@ pub fn check_state_invariants<BS: Blockstore>(
             }
             Some(Type::Account) => {
                 let state = get_state!(store, actor, AccountState);
-                let (summary, msgs) = account::check_state_invariants(&state, key);
+                let (summary, msgs) = account::check_state_invariants(&state, &key);
                 acc.with_prefix("account: ").add_all(&msgs);
-                account_summaries.push(summary);
+                if !streaming {
+                    account_summaries.push(summary);
+                }
             }
             Some(Type::Power) => {
                 let state = get_state!(store, actor, PowerState);
@@ -122,7 +382,7 @@ pub fn check_state_invariants<BS: Blockstore>(
                 let (summary, msgs) =
                     miner::check_state_invariants(policy, &state, store, &actor.balance);
                 acc.with_prefix("miner: ").add_all(&msgs);
-                miner_summaries.insert(*key, summary);
+                miner_summaries.insert(key, summary);
             }
             Some(Type::Market) => {
                 let state = get_state!(store, actor, MarketState);
@@ -135,13 +395,17 @@ pub fn check_state_invariants<BS: Blockstore>(
                 let state = get_state!(store, actor, PaychState);
                 let (summary, msgs) = paych::check_state_invariants(&state, store, &actor.balance);
                 acc.with_prefix("paych: ").add_all(&msgs);
-                paych_summaries.push(summary);
+                if !streaming {
+                    paych_summaries.push(summary);
+                }
             }
             Some(Type::Multisig) => {
                 let state = get_state!(store, actor, MultisigState);
                 let (summary, msgs) = multisig::check_state_invariants(&state, store);
                 acc.with_prefix("multisig: ").add_all(&msgs);
-                multisig_summaries.push(summary);
+                if !streaming {
+                    multisig_summaries.push(summary);
+                }
             }
             Some(Type::Reward) => {
                 let state = get_state!(store, actor, RewardState);
@@ -152,8 +416,12 @@ pub fn check_state_invariants<BS: Blockstore>(
             }
             Some(Type::VerifiedRegistry) => {
                 let state = get_state!(store, actor, VerifregState);
-                let (summary, msgs) = verifreg::check_state_invariants(&state, store, prior_epoch);
+                let (mut summary, msgs) =
+                    verifreg::check_state_invariants(&state, store, prior_epoch);
                 acc.with_prefix("verifreg: ").add_all(&msgs);
+                for (id, claim) in std::mem::take(&mut summary.claims) {
+                    claims_by_provider.entry(claim.provider).or_default().push((id, claim));
+                }
                 verifreg_summary = Some(summary);
             }
             Some(Type::DataCap) => {
@@ -166,21 +434,49 @@ pub fn check_state_invariants<BS: Blockstore>(
             Some(Type::EVM) => {}
             Some(Type::EAM) => {}
             Some(Type::EthAccount) => {}
+            None if tolerate_unknown => {}
             None => {
                 bail!("unexpected actor code CID {} for address {}", actor.code, key);
             }
         };
+        profile.actors.push(ActorCheckCost {
+            address: key,
+            type_name: manifest.get(&actor.code).map_or("unknown", |t| t.name()).to_string(),
+            elapsed: actor_started.elapsed(),
+            reads: store.take_stats(),
+        });
 
+        if streaming
+            && power_summary.is_some()
+            && market_summary.is_some()
+            && verifreg_summary.is_some()
+        {
+            check_miners(
+                cross_acc,
+                &mut miner_summaries,
+                &mut checked_miners,
+                power_summary.as_ref(),
+                &mut claims_by_provider,
+            );
+        }
         Ok(())
     })?;
 
     // Perform cross-actor checks from state summaries here.
-    if let Some(power_summary) = power_summary {
-        check_miner_against_power(&acc, &miner_summaries, &power_summary);
+    if let Some(cron_summary) = cron_summary {
+        check_cron_entries(&acc, &cron_summary);
     }
 
+    check_miners(
+        &acc,
+        &mut miner_summaries,
+        &mut checked_miners,
+        power_summary.as_ref(),
+        &mut claims_by_provider,
+    );
+
     if let Some(market_summary) = market_summary.clone() {
-        check_deal_states_against_sectors(&acc, &miner_summaries, &market_summary);
+        check_deal_states_against_sectors(&acc, &checked_miners, &market_summary);
     }
 
     if let Some(verifreg_summary) = verifreg_summary {
@@ -190,7 +486,15 @@ pub fn check_state_invariants<BS: Blockstore>(
         if let Some(market_summary) = market_summary {
             check_market_against_verifreg(&acc, &market_summary, &verifreg_summary);
         }
-        check_verifreg_against_miners(&acc, &verifreg_summary, &miner_summaries);
+        // Claims remaining weren't checked against any miner.
+        for (provider, claims) in claims_by_provider {
+            for _ in claims {
+                acc.add(format!(
+                    "claim provider {} is not found in miner summaries",
+                    Address::new_id(provider)
+                ));
+            }
+        }
     }
 
     if let Some(expected_balance_total) = expected_balance_total {
@@ -200,79 +504,122 @@ pub fn check_state_invariants<BS: Blockstore>(
         );
     }
 
-    Ok(acc)
+    profile.elapsed = started.elapsed();
+    Ok((acc, profile, census))
+}
+
+/// Checks that cron calls exactly the power and market actors, in that order.
+fn check_cron_entries(acc: &MessageAccumulator, cron_summary: &cron::StateSummary) {
+    let expected = [
+        CronEntry {
+            receiver: STORAGE_POWER_ACTOR_ADDR,
+            method_num: PowerMethod::OnEpochTickEnd as u64,
+        },
+        CronEntry {
+            receiver: STORAGE_MARKET_ACTOR_ADDR,
+            method_num: MarketMethod::CronTick as u64,
+        },
+    ];
+    acc.require(
+        cron_summary.entries == expected,
+        format!("cron entries {:?} are not the power and market ticks", cron_summary.entries),
+    );
+}
+
+/// Checks each miner against the singletons, discarding its summary after.
+fn check_miners(
+    acc: &MessageAccumulator,
+    miner_summaries: &mut HashMap<Address, miner::StateSummary>,
+    checked_miners: &mut HashSet<Address>,
+    power_summary: Option<&power::StateSummary>,
+    claims_by_provider: &mut HashMap<ActorID, Vec<(ClaimID, Claim)>>,
+) {
+    for (address, miner_summary) in miner_summaries.drain() {
+        if let Some(power_summary) = power_summary {
+            check_miner_against_power(acc, &address, &miner_summary, power_summary);
+        }
+        if let Some(claims) = address.id().ok().and_then(|id| claims_by_provider.remove(&id)) {
+            check_claims_against_miner(acc, &address, &miner_summary, &claims);
+        }
+        checked_miners.insert(address);
+    }
 }
 
 fn check_miner_against_power(
     acc: &MessageAccumulator,
-    miner_summaries: &HashMap<Address, miner::StateSummary>,
+    address: &Address,
+    miner_summary: &miner::StateSummary,
     power_summary: &power::StateSummary,
 ) {
-    for (address, miner_summary) in miner_summaries {
-        //check claim
-        if let Some(claim) = power_summary.claims.get(address) {
-            let claim_power =
-                PowerPair::new(claim.raw_byte_power.clone(), claim.quality_adj_power.clone());
-            acc.require(miner_summary.active_power == claim_power, format!("miner {address} computed active power {:?} does not match claim {claim_power:?}", miner_summary.active_power));
-            acc.require(
-                miner_summary.window_post_proof_type == claim.window_post_proof_type,
-                format!(
-                    "miner seal proof type {:?} does not match claim proof type {:?}",
-                    miner_summary.window_post_proof_type, claim.window_post_proof_type
-                ),
-            );
-        } else {
-            acc.add(format!("miner {address} has no power claim"));
-        }
+    //check claim
+    if let Some(claim) = power_summary.claims.get(address) {
+        let claim_power =
+            PowerPair::new(claim.raw_byte_power.clone(), claim.quality_adj_power.clone());
+        acc.require(
+            miner_summary.active_power == claim_power,
+            format!(
+                "miner {address} computed active power {:?} does not match claim {claim_power:?}",
+                miner_summary.active_power
+            ),
+        );
+        acc.require(
+            miner_summary.window_post_proof_type == claim.window_post_proof_type,
+            format!(
+                "miner seal proof type {:?} does not match claim proof type {:?}",
+                miner_summary.window_post_proof_type, claim.window_post_proof_type
+            ),
+        );
+    } else {
+        acc.add(format!("miner {address} has no power claim"));
+    }
 
-        //check crons
-        let mut proving_period_cron: Option<&MinerCronEvent> = None;
-        if let Some(crons) = power_summary.crons.get(address) {
-            for event in crons {
-                match from_slice::<CronEventPayload>(event.payload.bytes()) {
-                    Ok(payload) => {
-                        acc.require(
-                            matches!(
-                                payload.event_type,
-                                CRON_EVENT_PROCESS_EARLY_TERMINATIONS | CRON_EVENT_PROVING_DEADLINE
-                            ),
-                            format!(
-                                "miner {address} has unexpected cron event type {}",
-                                payload.event_type
-                            ),
-                        );
-                        if payload.event_type == CRON_EVENT_PROVING_DEADLINE {
-                            if proving_period_cron.is_some() {
-                                acc.add(format!("miner {address} has duplicate proving period crons at epoch {} and {}", proving_period_cron.as_ref().unwrap().epoch, event.epoch));
-                            }
-                            proving_period_cron = Some(event);
+    //check crons
+    let mut proving_period_cron: Option<&MinerCronEvent> = None;
+    if let Some(crons) = power_summary.crons.get(address) {
+        for event in crons {
+            match from_slice::<CronEventPayload>(event.payload.bytes()) {
+                Ok(payload) => {
+                    acc.require(
+                        matches!(
+                            payload.event_type,
+                            CRON_EVENT_PROCESS_EARLY_TERMINATIONS | CRON_EVENT_PROVING_DEADLINE
+                        ),
+                        format!(
+                            "miner {address} has unexpected cron event type {}",
+                            payload.event_type
+                        ),
+                    );
+                    if payload.event_type == CRON_EVENT_PROVING_DEADLINE {
+                        if proving_period_cron.is_some() {
+                            acc.add(format!("miner {address} has duplicate proving period crons at epoch {} and {}", proving_period_cron.as_ref().unwrap().epoch, event.epoch));
                         }
+                        proving_period_cron = Some(event);
                     }
-                    Err(e) => acc.add(format!(
-                        "miner {address} registered cron at epoch {} with wrong or corrupt payload: {e}",
-                        event.epoch
-                    )),
                 }
-                acc.require(proving_period_cron.is_some() == miner_summary.deadline_cron_active, format!("miner {address} has invalid deadline_cron_active ({}) for proving_period_cron status ({})", miner_summary.deadline_cron_active, proving_period_cron.is_some()));
-                acc.require(
-                    proving_period_cron.is_some(),
-                    format!("miner {address} has no proving period cron"),
-                );
+                Err(e) => acc.add(format!(
+                    "miner {address} registered cron at epoch {} with wrong or corrupt payload: {e}",
+                    event.epoch
+                )),
             }
-        } else {
-            // with deferred and discontinued crons it is normal for a miner actor to have no cron
-            // events
+            acc.require(proving_period_cron.is_some() == miner_summary.deadline_cron_active, format!("miner {address} has invalid deadline_cron_active ({}) for proving_period_cron status ({})", miner_summary.deadline_cron_active, proving_period_cron.is_some()));
             acc.require(
-                !miner_summary.deadline_cron_active,
-                format!("miner {address} has no cron events but the deadline cron is active"),
+                proving_period_cron.is_some(),
+                format!("miner {address} has no proving period cron"),
             );
         }
+    } else {
+        // with deferred and discontinued crons it is normal for a miner actor to have no cron
+        // events
+        acc.require(
+            !miner_summary.deadline_cron_active,
+            format!("miner {address} has no cron events but the deadline cron is active"),
+        );
     }
 }
 
 fn check_deal_states_against_sectors(
     acc: &MessageAccumulator,
-    miner_summaries: &HashMap<Address, miner::StateSummary>,
+    miners: &HashSet<Address>,
     market_summary: &market::StateSummary,
 ) {
     // Check that all active deals are included within a non-terminated sector.
@@ -284,15 +631,10 @@ fn check_deal_states_against_sectors(
             continue;
         }
 
-        let _miner_summary = if let Some(miner_summary) = miner_summaries.get(&deal.provider) {
-            miner_summary
-        } else {
-            acc.add(format!(
-                "provider {} for deal {} not found among miners",
-                deal.provider, &deal_id
-            ));
-            continue;
-        };
+        acc.require(
+            miners.contains(&deal.provider),
+            format!("provider {} for deal {} not found among miners", deal.provider, &deal_id),
+        );
     }
 }
 
@@ -308,6 +650,27 @@ fn check_verifreg_against_datacap(
             format!("verifier {} is also a datacap token holder", verifier),
         );
     }
+    // Data cap is held only by verifreg, in escrow for allocations, and by clients.
+    // Other builtin actors have no means to spend it, so any they hold has leaked.
+    for singleton in [
+        SYSTEM_ACTOR_ADDR,
+        INIT_ACTOR_ADDR,
+        REWARD_ACTOR_ADDR,
+        CRON_ACTOR_ADDR,
+        STORAGE_POWER_ACTOR_ADDR,
+        STORAGE_MARKET_ACTOR_ADDR,
+        DATACAP_TOKEN_ACTOR_ADDR,
+        BURNT_FUNDS_ACTOR_ADDR,
+    ] {
+        if let Some(balance) =
+            datacap_summary.balance_map.as_ref().unwrap().get(&singleton.id().unwrap())
+        {
+            acc.require(
+                balance.is_zero(),
+                format!("datacap {} stranded at builtin actor {}", balance, singleton),
+            );
+        }
+    }
     // Verifreg token balance matches unclaimed allocations.
     let pending_alloc_total: DataCap =
         verifreg_summary.allocations.values().map(|alloc| alloc.size.0).sum();
@@ -378,29 +741,49 @@ fn check_market_against_verifreg(
                 deal_id
             ),
         );
+        acc.require(
+            info.client.id().unwrap() == alloc.client,
+            format!(
+                "mismatched clients {} {} on alloc {} and deal {}",
+                alloc.client,
+                info.client.id().unwrap(),
+                allocation_id,
+                deal_id
+            ),
+        );
+        acc.require(
+            info.piece_size == alloc.size,
+            format!(
+                "mismatched sizes {} {} on alloc {} and deal {}",
+                alloc.size.0, info.piece_size.0, allocation_id, deal_id
+            ),
+        );
+    }
+
+    // Every verified deal yet to be activated holds the allocation that will be claimed
+    // when it is, or its data cap has leaked.
+    let pending_alloc_deals: HashSet<DealID> =
+        market_summary.alloc_id_to_deal_id.values().copied().collect();
+    for (deal_id, deal) in &market_summary.deals {
+        if deal.verified_deal && deal.sector_start_epoch == -1 {
+            acc.require(
+                pending_alloc_deals.contains(deal_id),
+                format!("pending verified deal {} has no allocation", deal_id),
+            );
+        }
     }
 }
 
-fn check_verifreg_against_miners(
+fn check_claims_against_miner(
     acc: &MessageAccumulator,
-    verifreg_summary: &verifreg::StateSummary,
-    miner_summaries: &HashMap<Address, miner::StateSummary>,
+    maddr: &Address,
+    miner_summary: &miner::StateSummary,
+    claims: &[(ClaimID, Claim)],
 ) {
     // Accumulates the weight of claims for each sector.
-    let mut sector_claim_verified_weights: BTreeMap<(Address, SectorNumber), DealWeight> =
-        BTreeMap::new();
-
-    for (id, claim) in &verifreg_summary.claims {
-        // All claims are indexed by valid providers
-        let maddr = Address::new_id(claim.provider);
-        let miner_summary = match miner_summaries.get(&maddr) {
-            None => {
-                acc.add(format!("claim provider {} is not found in miner summaries", maddr));
-                continue;
-            }
-            Some(summary) => summary,
-        };
+    let mut sector_claim_verified_weights: BTreeMap<SectorNumber, DealWeight> = BTreeMap::new();
 
+    for (id, claim) in claims {
         // Find sectors associated with claims.
         // A claim might not have a sector if the sector was terminated and cleaned up.
         if let Some(sector) = miner_summary.live_data_sectors.get(&claim.sector) {
@@ -436,13 +819,11 @@ fn check_verifreg_against_miners(
                 );
                 let expected_duration = sector.sector_expiration - claim.term_start;
                 let expected_weight = DealWeight::from(claim.size.0) * expected_duration;
-                *sector_claim_verified_weights.entry((maddr, claim.sector)).or_default() +=
-                    expected_weight;
+                *sector_claim_verified_weights.entry(claim.sector).or_default() += expected_weight;
             }
         }
     }
-    for ((maddr, sector), claim_weight) in &sector_claim_verified_weights {
-        let miner_summary = miner_summaries.get(maddr).unwrap();
+    for (sector, claim_weight) in &sector_claim_verified_weights {
         let sector = miner_summary.live_data_sectors.get(sector).unwrap();
         acc.require(
             sector.verified_deal_weight == *claim_weight,
@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{anyhow, Context};
+use cid::Cid;
+use fil_actor_init::State as InitState;
+use fil_actor_market::{DealArray, DealMetaArray, State as MarketState};
+use fil_actor_miner::{SectorOnChainInfo, State as MinerState};
+use fil_actor_power::{ClaimsMap, State as PowerState, CLAIMS_CONFIG};
+use fil_actor_system::State as SystemState;
+use fil_actor_verifreg::state::{DataCapMap, DATACAP_MAP_CONFIG};
+use fil_actor_verifreg::State as VerifregState;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::{
+    diff_map, Array2, Change, Map2, DEFAULT_HAMT_CONFIG, INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::{Address, Protocol};
+use ipld_core::ipld::Ipld;
+use num_traits::FromPrimitive;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use vm_api::ActorState;
+
+/// A decoded value, in both its typed and generic IPLD forms.
+#[derive(Clone, Debug)]
+pub struct Decoded {
+    /// The typed value's debug rendering, or the generic value's if its type isn't known.
+    pub pretty: String,
+    pub ipld: Ipld,
+}
+
+impl Decoded {
+    pub fn new<T: Serialize + fmt::Debug>(value: &T) -> anyhow::Result<Self> {
+        let ipld = fvm_ipld_encoding::from_slice(&fvm_ipld_encoding::to_vec(value)?)?;
+        Ok(Decoded { pretty: format!("{:#?}", value), ipld })
+    }
+}
+
+/// An actor and its decoded state.
+#[derive(Clone, Debug)]
+pub struct Inspection {
+    pub address: Address,
+    pub actor: ActorState,
+    pub actor_type: Option<Type>,
+    pub state: Decoded,
+    /// Collections referenced by the state, by name, with their entries in key order.
+    pub collections: Vec<(String, Vec<(String, Decoded)>)>,
+}
+
+/// Loads the actors of the state tree at `root`.
+/// The root may be the actors HAMT, or a versioned state root wrapping it.
+pub fn load_tree<BS: Blockstore>(
+    store: &BS,
+    root: &Cid,
+) -> anyhow::Result<BTreeMap<Address, ActorState>> {
+    let actors_root = actors_root(store, root)?;
+    let actors =
+        Map2::<_, Address, ActorState>::load(store, &actors_root, DEFAULT_HAMT_CONFIG, "actors")?;
+    let mut tree = BTreeMap::new();
+    actors.for_each(|address, actor| {
+        tree.insert(address, actor.clone());
+        Ok(())
+    })?;
+    Ok(tree)
+}
+
+/// Returns the actors which differ between the state trees at `old` and `new`.
+/// Either root may be the actors HAMT, or a versioned state root wrapping it.
+/// Only the parts of the trees which differ are loaded.
+pub fn diff_trees<BS: Blockstore>(
+    store: &BS,
+    old: &Cid,
+    new: &Cid,
+) -> anyhow::Result<Vec<Change<Address, ActorState>>> {
+    let (old, new) = (actors_root(store, old)?, actors_root(store, new)?);
+    Ok(diff_map(store, &old, &new, "actors")?)
+}
+
+/// Returns the root of the actors HAMT of the state tree at `root`.
+fn actors_root<BS: Blockstore>(store: &BS, root: &Cid) -> anyhow::Result<Cid> {
+    let block: Ipld = store.get_cbor(root)?.ok_or_else(|| anyhow!("missing state root {root}"))?;
+    Ok(match block {
+        // StateRoot { version, actors, info }
+        Ipld::List(fields) if fields.len() == 3 => match fields[1] {
+            Ipld::Link(actors) => actors,
+            _ => return Err(anyhow!("malformed state root {root}")),
+        },
+        _ => *root,
+    })
+}
+
+/// Reads the builtin actor types from the manifest referenced by the system actor's state.
+pub fn manifest_from_tree<BS: Blockstore>(
+    store: &BS,
+    tree: &BTreeMap<Address, ActorState>,
+) -> anyhow::Result<BTreeMap<Cid, Type>> {
+    let system = tree.get(&SYSTEM_ACTOR_ADDR).ok_or_else(|| anyhow!("no system actor"))?;
+    let state: SystemState =
+        store.get_cbor(&system.state)?.ok_or_else(|| anyhow!("SystemState is empty"))?;
+    manifest_from_entries(state.get_builtin_actors(store).map_err(|e| anyhow!(e))?)
+}
+
+/// Reads the builtin actor types from a bundle's (version 1) manifest.
+pub fn manifest_from_bundle<BS: Blockstore>(
+    store: &BS,
+    manifest: &Cid,
+) -> anyhow::Result<BTreeMap<Cid, Type>> {
+    let (version, data): (u32, Cid) =
+        store.get_cbor(manifest)?.ok_or_else(|| anyhow!("missing manifest {manifest}"))?;
+    if version != 1 {
+        return Err(anyhow!("unsupported manifest version {version}"));
+    }
+    let entries: Vec<(String, Cid)> =
+        store.get_cbor(&data)?.ok_or_else(|| anyhow!("missing manifest data {data}"))?;
+    manifest_from_entries(entries)
+}
+
+fn manifest_from_entries(entries: Vec<(String, Cid)>) -> anyhow::Result<BTreeMap<Cid, Type>> {
+    entries
+        .into_iter()
+        .map(|(name, code)| {
+            (1..)
+                .map_while(Type::from_i32)
+                .find(|t| t.name() == name)
+                .map(|t| (code, t))
+                .ok_or_else(|| anyhow!("unknown actor type {name}"))
+        })
+        .collect()
+}
+
+/// Decodes an actor's state, and the contents of the collections it references.
+/// A non-ID address is resolved through the init actor.
+pub fn inspect_actor<BS: Blockstore>(
+    store: &BS,
+    tree: &BTreeMap<Address, ActorState>,
+    manifest: &BTreeMap<Cid, Type>,
+    address: &Address,
+) -> anyhow::Result<Inspection> {
+    let address = resolve_address(store, tree, address)?;
+    let actor = tree.get(&address).ok_or_else(|| anyhow!("no actor at {address}"))?.clone();
+    let actor_type = manifest.get(&actor.code).copied();
+    let mut collections = vec![];
+    let state = match actor_type {
+        Some(Type::Init) => {
+            let st: InitState = get_state(store, &actor)?;
+            let address_map = Map2::<_, Address, u64>::load(
+                store,
+                &st.address_map,
+                DEFAULT_HAMT_CONFIG,
+                "addresses",
+            )?;
+            collections.push(("address_map".into(), map_entries(&address_map)?));
+            Decoded::new(&st)?
+        }
+        Some(Type::Power) => {
+            let st: PowerState = get_state(store, &actor)?;
+            let claims = ClaimsMap::load(store, &st.claims, CLAIMS_CONFIG, "claims")?;
+            collections.push(("claims".into(), map_entries(&claims)?));
+            Decoded::new(&st)?
+        }
+        Some(Type::Miner) => {
+            let st: MinerState = get_state(store, &actor)?;
+            let sectors = Array2::<_, SectorOnChainInfo>::load(store, &st.sectors, "sectors")?;
+            collections.push(("sectors".into(), array_entries(&sectors)?));
+            let deadlines = st.load_deadlines(store)?;
+            let mut entries = vec![];
+            for (i, _) in deadlines.due.iter().enumerate() {
+                let deadline = deadlines.load_deadline(store, i as u64)?;
+                entries.push((i.to_string(), Decoded::new(&deadline)?));
+            }
+            collections.push(("deadlines".into(), entries));
+            Decoded::new(&st)?
+        }
+        Some(Type::Market) => {
+            let st: MarketState = get_state(store, &actor)?;
+            let proposals = DealArray::load(store, &st.proposals, "proposals")?;
+            collections.push(("proposals".into(), array_entries(&proposals)?));
+            let states = DealMetaArray::load(store, &st.states, "deal states")?;
+            collections.push(("states".into(), array_entries(&states)?));
+            Decoded::new(&st)?
+        }
+        Some(Type::VerifiedRegistry) => {
+            let st: VerifregState = get_state(store, &actor)?;
+            let verifiers =
+                DataCapMap::load(store, &st.verifiers, DATACAP_MAP_CONFIG, "verifiers")?;
+            collections.push(("verifiers".into(), map_entries(&verifiers)?));
+            Decoded::new(&st)?
+        }
+        _ => {
+            let ipld: Ipld = get_state(store, &actor)?;
+            Decoded { pretty: format!("{:#?}", ipld), ipld }
+        }
+    };
+    Ok(Inspection { address, actor, actor_type, state, collections })
+}
+
+fn resolve_address<BS: Blockstore>(
+    store: &BS,
+    tree: &BTreeMap<Address, ActorState>,
+    address: &Address,
+) -> anyhow::Result<Address> {
+    if address.protocol() == Protocol::ID {
+        return Ok(*address);
+    }
+    let init = tree.get(&INIT_ACTOR_ADDR).ok_or_else(|| anyhow!("no init actor"))?;
+    let st: InitState = get_state(store, init)?;
+    st.resolve_address(store, address)?.ok_or_else(|| anyhow!("address {address} not found"))
+}
+
+fn get_state<BS: Blockstore, T: DeserializeOwned>(
+    store: &BS,
+    actor: &ActorState,
+) -> anyhow::Result<T> {
+    store
+        .get_cbor(&actor.state)
+        .with_context(|| format!("failed to decode state {}", actor.state))?
+        .ok_or_else(|| anyhow!("missing state {}", actor.state))
+}
+
+fn map_entries<BS: Blockstore, K, V>(map: &Map2<BS, K, V>) -> anyhow::Result<Vec<(String, Decoded)>>
+where
+    K: fil_actors_runtime::MapKey + fmt::Display,
+    V: DeserializeOwned + Serialize + fmt::Debug,
+{
+    let mut entries = vec![];
+    map.for_each(|k, v| {
+        entries.push((k.to_string(), Decoded::new(v)));
+        Ok(())
+    })?;
+    entries.into_iter().map(|(k, v)| Ok((k, v?))).collect()
+}
+
+fn array_entries<BS: Blockstore, V>(array: &Array2<BS, V>) -> anyhow::Result<Vec<(String, Decoded)>>
+where
+    V: DeserializeOwned + Serialize + fmt::Debug,
+{
+    let mut entries = vec![];
+    array.for_each(|i, v| {
+        entries.push((i.to_string(), Decoded::new(v)));
+        Ok(())
+    })?;
+    entries.into_iter().map(|(k, v)| Ok((k, v?))).collect()
+}
+
+impl Inspection {
+    /// Returns the inspection as JSON, with states in their generic IPLD form.
+    /// Links are written as `{"/": "<cid>"}` and bytes as `0x`-prefixed hex strings.
+    pub fn to_json(&self) -> Value {
+        let collections: serde_json::Map<_, _> = self
+            .collections
+            .iter()
+            .map(|(name, entries)| {
+                let entries = entries.iter().map(|(k, v)| (k.clone(), ipld_to_json(&v.ipld)));
+                (name.clone(), Value::Object(entries.collect()))
+            })
+            .collect();
+        json!({
+            "address": self.address.to_string(),
+            "type": self.actor_type.map(|t| t.name()),
+            "code": {"/": self.actor.code.to_string()},
+            "head": {"/": self.actor.state.to_string()},
+            "sequence": self.actor.sequence,
+            "balance": self.actor.balance.atto().to_string(),
+            "delegated_address": self.actor.delegated_address.map(|a| a.to_string()),
+            "state": ipld_to_json(&self.state.ipld),
+            "collections": collections,
+        })
+    }
+}
+
+fn ipld_to_json(ipld: &Ipld) -> Value {
+    match ipld {
+        Ipld::Null => Value::Null,
+        Ipld::Bool(b) => Value::Bool(*b),
+        Ipld::Integer(i) => match i64::try_from(*i) {
+            Ok(i) => Value::from(i),
+            Err(_) => Value::String(i.to_string()),
+        },
+        Ipld::Float(f) => Value::from(*f),
+        Ipld::String(s) => Value::String(s.clone()),
+        Ipld::Bytes(b) => Value::String(format!("0x{}", hex::encode(b))),
+        Ipld::List(items) => Value::Array(items.iter().map(ipld_to_json).collect()),
+        Ipld::Map(entries) => {
+            Value::Object(entries.iter().map(|(k, v)| (k.clone(), ipld_to_json(v))).collect())
+        }
+        Ipld::Link(cid) => json!({"/": cid.to_string()}),
+    }
+}
+
+impl fmt::Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let type_name = self.actor_type.map_or("unknown type", |t| t.name());
+        writeln!(f, "{} ({})", self.address, type_name)?;
+        writeln!(f, "code: {}", self.actor.code)?;
+        writeln!(f, "head: {}", self.actor.state)?;
+        writeln!(f, "sequence: {}", self.actor.sequence)?;
+        writeln!(f, "balance: {}", self.actor.balance)?;
+        if let Some(delegated) = self.actor.delegated_address {
+            writeln!(f, "delegated address: {}", delegated)?;
+        }
+        writeln!(f, "state: {}", self.state.pretty)?;
+        for (name, entries) in &self.collections {
+            writeln!(f, "{} ({} entries):", name, entries.len())?;
+            for (key, value) in entries {
+                writeln!(f, "  {}: {}", key, value.pretty.replace('\n', "\n  "))?;
+            }
+        }
+        Ok(())
+    }
+}
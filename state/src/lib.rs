@@ -1 +1,4 @@
+pub mod abi;
+pub mod blocks;
 pub mod check;
+pub mod inspect;
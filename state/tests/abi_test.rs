@@ -0,0 +1,43 @@
+use std::collections::HashSet;
+
+use fil_actor_market::Method as MarketMethod;
+use fil_actors_runtime::ValueDescriptor;
+use fil_builtin_actors_state::abi::{builtin_actors_abi, builtin_actors_abi_json};
+use fvm_ipld_encoding::CBOR;
+
+#[test]
+fn describes_builtin_actor_methods() {
+    let abi = builtin_actors_abi();
+    for actor in &abi {
+        let numbers: HashSet<_> = actor.methods.iter().map(|m| m.number).collect();
+        assert_eq!(actor.methods.len(), numbers.len(), "duplicate method in {}", actor.name);
+        assert!(!actor.methods.is_empty(), "no methods for {}", actor.name);
+    }
+
+    let market = abi.iter().find(|a| a.actor_type == "storagemarket").unwrap();
+    let publish: Vec<_> =
+        market.methods.iter().filter(|m| m.handler == "publish_storage_deals").collect();
+    assert_eq!(2, publish.len());
+    assert_eq!(MarketMethod::PublishStorageDeals as u64, publish[0].number);
+    assert!(!publish[0].exported);
+    assert_eq!(MarketMethod::PublishStorageDealsExported as u64, publish[1].number);
+    assert!(publish[1].exported);
+    assert_eq!(
+        Some(ValueDescriptor {
+            type_name: "fil_actor_market::types::PublishStorageDealsParams",
+            codec: CBOR
+        }),
+        publish[0].params
+    );
+    assert_eq!(
+        "fil_actor_market::types::PublishStorageDealsReturn",
+        publish[0].returns.as_ref().unwrap().type_name
+    );
+
+    let evm = abi.iter().find(|a| a.actor_type == "evm").unwrap();
+    let invoke = evm.methods.iter().find(|m| m.name == "InvokeContract").unwrap();
+    assert!(invoke.params_optional);
+
+    let json: serde_json::Value = serde_json::from_str(&builtin_actors_abi_json()).unwrap();
+    assert_eq!(abi.len(), json.as_array().unwrap().len());
+}
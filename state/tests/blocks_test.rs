@@ -0,0 +1,56 @@
+use fil_actors_integration_tests::util::{create_accounts, create_miner};
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::ACTOR_TYPES;
+use fil_builtin_actors_state::blocks::{block_report, ListBlockstore};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::RegisteredSealProof;
+use multihash_codetable::Code;
+use test_vm::TestVM;
+
+#[test]
+fn reports_reachable_and_unreachable_blocks() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+
+    // A store holding exactly the exported state tree has no garbage.
+    let path = std::env::temp_dir().join(format!("state_blocks_{}.car", std::process::id()));
+    let root = v.export_car(&path).unwrap();
+    let store = MemoryBlockstore::new();
+    let file = futures::io::AllowStdIo::new(std::fs::File::open(&path).unwrap());
+    futures::executor::block_on(fvm_ipld_car::load_car(&store, file)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut report = block_report(&store, &root, &ACTOR_TYPES).unwrap();
+    assert!(report.missing.is_empty());
+    assert!(report.by_actor_type["storageminer"].blocks > 0);
+    assert!(report.by_actor_type["account"].bytes > 0);
+    assert!(report.tree.blocks > 0);
+    let reachable = report.reachable_stats();
+    assert_eq!(store.list().unwrap().len() as u64, reachable.blocks);
+
+    report.find_unreachable(&store).unwrap();
+    assert_eq!(Some(vec![]), report.unreachable);
+
+    // A block no state links to is reported as garbage.
+    let garbage = store.put_cbor(&"left behind", Code::Blake2b256).unwrap();
+    report.find_unreachable(&store).unwrap();
+    assert_eq!(Some(vec![garbage]), report.unreachable);
+    assert_eq!(store.get(&garbage).unwrap().unwrap().len() as u64, report.unreachable_stats.bytes);
+    assert!(report.to_string().contains("unreachable"));
+
+    // The VM's own store retains superseded states.
+    let mut report = block_report(v.store.as_ref(), &root, &ACTOR_TYPES).unwrap();
+    report.find_unreachable(v.store.as_ref()).unwrap();
+    assert_eq!(reachable, report.reachable_stats());
+    assert!(!report.unreachable.unwrap().is_empty());
+}
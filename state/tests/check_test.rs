@@ -0,0 +1,227 @@
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_init::State as InitState;
+use fil_actor_market::State as MarketState;
+use fil_actor_verifreg::State as VerifregState;
+use fil_actors_integration_tests::util::{
+    create_accounts, create_miner, create_storage_parties, market_add_balance, market_publish_deal,
+    verifreg_add_client, verifreg_add_verifier, StorageParties,
+};
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::{
+    DATACAP_TOKEN_ACTOR_ADDR, EPOCHS_IN_DAY, ID_OVERFLOW_RISK_THRESHOLD, INIT_ACTOR_ADDR,
+    STORAGE_MARKET_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fil_builtin_actors_state::check::{
+    check_state_invariants, check_state_invariants_profiled, check_state_invariants_tolerant,
+};
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::{RegisteredSealProof, StoragePower};
+use test_vm::{TestVM, FAUCET_ROOT_KEY, TEST_FAUCET_ADDR};
+use vm_api::util::{mutate_state, DynBlockstore};
+use vm_api::VM;
+
+#[test]
+fn profiles_each_actor_check() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+
+    let tree = v.actor_states();
+    let (acc, profile) = check_state_invariants_profiled(
+        v.store.as_ref(),
+        &v.actor_manifest(),
+        &Policy::default(),
+        &tree,
+        None,
+        v.epoch() - 1,
+    )
+    .unwrap();
+    acc.assert_empty();
+
+    // Every actor is profiled once, in address order.
+    let profiled: Vec<_> = profile.actors.iter().map(|c| c.address).collect();
+    assert_eq!(tree.keys().copied().collect::<Vec<_>>(), profiled);
+    let miner_cost = profile.actors.iter().find(|c| c.address == miner).unwrap();
+    assert_eq!("storageminer", miner_cost.type_name);
+    assert!(miner_cost.reads.r > 0);
+    assert!(miner_cost.reads.br > 0);
+    assert!(profile.by_actor_type()["account"].1.r > 0);
+    assert!(
+        profile.actors.iter().map(|c| c.elapsed).sum::<std::time::Duration>() <= profile.elapsed
+    );
+
+    // Hotspots are the slowest actors, slowest first.
+    let hotspots = profile.hotspots(3);
+    assert_eq!(3, hotspots.len());
+    assert!(hotspots.windows(2).all(|w| w[0].elapsed >= w[1].elapsed));
+    assert_eq!(tree.len(), profile.hotspots(usize::MAX).len());
+
+    let report = profile.report(3).to_string();
+    assert!(report.contains(&format!("checked {} actors", tree.len())));
+    assert!(report.contains("storageminer"));
+    assert!(report.contains("slowest 3 actors"));
+}
+
+#[test]
+fn tolerant_check_counts_unknown_codes() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    let tree = v.actor_states();
+
+    // Leave the EAM's code out of the manifest, as though it were a user actor.
+    let mut manifest = v.actor_manifest();
+    let eam_code = *manifest.iter().find(|(_, t)| **t == Type::EAM).unwrap().0;
+    manifest.remove(&eam_code);
+
+    let err = check_state_invariants(
+        v.store.as_ref(),
+        &manifest,
+        &Policy::default(),
+        &tree,
+        None,
+        v.epoch() - 1,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("unexpected actor code CID"));
+
+    let (acc, census) = check_state_invariants_tolerant(
+        v.store.as_ref(),
+        &manifest,
+        &Policy::default(),
+        &tree,
+        None,
+        v.epoch() - 1,
+    )
+    .unwrap();
+    acc.assert_empty();
+
+    assert_eq!(vec![&eam_code], census.unknown_codes());
+    assert_eq!(1, census.unknown_actors());
+    assert_eq!(tree.len() as u64, census.codes.values().map(|c| c.actors).sum::<u64>());
+    let accounts =
+        census.codes.values().find(|c| c.type_name.as_deref() == Some("account")).unwrap();
+    assert!(accounts.actors >= 2);
+    assert!(census.to_string().contains(&format!("unknown              {eam_code}")));
+}
+
+#[test]
+fn datacap_leaks_are_flagged() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let StorageParties { owner, worker, verifier, client } =
+        create_storage_parties(&v, &TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &v,
+        &owner,
+        &worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+    let datacap = StoragePower::from(32_u128 << 40);
+    verifreg_add_verifier(&v, &verifier, datacap.clone());
+    verifreg_add_client(&v, &verifier, &client, datacap);
+    market_add_balance(&v, &client, &client, &TokenAmount::from_whole(3));
+    market_add_balance(&v, &worker, &miner, &TokenAmount::from_whole(64));
+    let deal_id = market_publish_deal(
+        &v,
+        &worker,
+        &client,
+        &miner,
+        "deal".to_string(),
+        PaddedPieceSize(32u64 << 30),
+        true,
+        v.epoch() + 1000,
+        180 * EPOCHS_IN_DAY,
+    )
+    .ids[0];
+
+    let check = |v: &TestVM| {
+        check_state_invariants(
+            v.store.as_ref(),
+            &v.actor_manifest(),
+            &Policy::default(),
+            &v.actor_states(),
+            None,
+            v.epoch() - 1,
+        )
+        .unwrap()
+    };
+    check(&v).assert_empty();
+
+    // Drop the pending verified deal's allocation, and strand data cap at the market actor.
+    mutate_state(&v, &STORAGE_MARKET_ACTOR_ADDR, |st: &mut MarketState| {
+        let store = DynBlockstore::wrap(v.blockstore());
+        let mut pending = st.load_pending_deal_allocation_ids(&store).unwrap();
+        pending.delete(&deal_id).unwrap();
+        st.save_pending_deal_allocation_ids(&mut pending).unwrap();
+    });
+    mutate_state(&v, &DATACAP_TOKEN_ACTOR_ADDR, |st: &mut DataCapState| {
+        let store = DynBlockstore::wrap(v.blockstore());
+        let stranded = TokenAmount::from_whole(1);
+        st.token
+            .change_balance_by(&store, STORAGE_MARKET_ACTOR_ADDR.id().unwrap(), &stranded)
+            .unwrap();
+        st.token.supply += stranded;
+    });
+
+    let messages = check(&v).messages();
+    assert!(messages.contains(&format!("pending verified deal {deal_id} has no allocation")));
+    assert!(messages
+        .contains(&format!("datacap 1.0 stranded at builtin actor {STORAGE_MARKET_ACTOR_ADDR}")));
+}
+
+#[test]
+fn id_counters_past_overflow_risk_are_flagged() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let check = |v: &TestVM| {
+        check_state_invariants(
+            v.store.as_ref(),
+            &v.actor_manifest(),
+            &Policy::default(),
+            &v.actor_states(),
+            None,
+            v.epoch() - 1,
+        )
+        .unwrap()
+    };
+    check(&v).assert_empty();
+
+    let risky = ID_OVERFLOW_RISK_THRESHOLD + 1;
+    mutate_state(&v, &STORAGE_MARKET_ACTOR_ADDR, |st: &mut MarketState| st.next_id = risky);
+    mutate_state(&v, &VERIFIED_REGISTRY_ACTOR_ADDR, |st: &mut VerifregState| {
+        st.next_allocation_id = risky
+    });
+    mutate_state(&v, &INIT_ACTOR_ADDR, |st: &mut InitState| st.next_id = risky);
+    let messages = check(&v).messages();
+    for counter in ["next deal id", "next allocation id", "next id"] {
+        let expected = format!(
+            "{counter} {risky} exceeds overflow risk threshold {ID_OVERFLOW_RISK_THRESHOLD}"
+        );
+        assert!(messages.iter().any(|m| m.ends_with(&expected)), "{expected} not in {messages:?}");
+    }
+
+    // An ID counter behind an ID already issued would issue it again.
+    mutate_state(&v, &INIT_ACTOR_ADDR, |st: &mut InitState| {
+        st.next_id = TEST_FAUCET_ADDR.id().unwrap()
+    });
+    let messages = check(&v).messages();
+    assert!(
+        messages.iter().any(|m| m.contains(&format!(
+            "ID {} of {} not below next id",
+            TEST_FAUCET_ADDR.id().unwrap(),
+            Address::new_bls(FAUCET_ROOT_KEY).unwrap()
+        ))),
+        "unexpected messages: {messages:?}"
+    );
+}
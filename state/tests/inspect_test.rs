@@ -0,0 +1,63 @@
+use fil_actors_integration_tests::deals::{DealBatcher, DealOptions};
+use fil_actors_integration_tests::util::{create_accounts, create_miner, market_add_balance};
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::ACTOR_TYPES;
+use fil_actors_runtime::STORAGE_MARKET_ACTOR_ADDR;
+use fil_builtin_actors_state::inspect::{inspect_actor, load_tree};
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::RegisteredSealProof;
+use test_vm::TestVM;
+use vm_api::VM;
+
+#[test]
+fn inspects_exported_state() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    let (worker, client) = (addrs[0], addrs[1]);
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner_id, miner_robust) = create_miner(
+        &v,
+        &worker,
+        &worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+    market_add_balance(&v, &worker, &miner_id, &TokenAmount::from_whole(1_000));
+    market_add_balance(&v, &client, &client, &TokenAmount::from_whole(1_000));
+    let deal_start = v.epoch() + Policy::default().pre_commit_challenge_delay + 1;
+    let opts =
+        DealOptions { deal_start, piece_size: PaddedPieceSize(1 << 30), ..Default::default() };
+    let mut batcher = DealBatcher::new(&v, opts);
+    batcher.stage(client, miner_id);
+    batcher.stage(client, miner_id);
+    batcher.publish_ok(worker);
+
+    let path = std::env::temp_dir().join(format!("state_inspect_{}.car", std::process::id()));
+    let root = v.export_car(&path).unwrap();
+    let store = MemoryBlockstore::new();
+    let file = futures::io::AllowStdIo::new(std::fs::File::open(&path).unwrap());
+    futures::executor::block_on(fvm_ipld_car::load_car(&store, file)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let tree = load_tree(&store, &root).unwrap();
+
+    // Market deals are listed by ID.
+    let market = inspect_actor(&store, &tree, &ACTOR_TYPES, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
+    let (name, proposals) = &market.collections[0];
+    assert_eq!("proposals", name);
+    let deal_ids: Vec<&str> = proposals.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(vec!["0", "1"], deal_ids);
+    assert!(proposals[0].1.pretty.contains("DealProposal"), "{}", proposals[0].1.pretty);
+    let json = market.to_json();
+    assert_eq!("storagemarket", json["type"]);
+    assert_eq!(2, json["collections"]["proposals"].as_object().unwrap().len());
+    assert!(json["head"]["/"].is_string());
+
+    // A miner is found by its robust address, and its deadlines are listed.
+    let miner = inspect_actor(&store, &tree, &ACTOR_TYPES, &miner_robust).unwrap();
+    assert_eq!(miner_id, miner.address);
+    let deadlines = miner.collections.iter().find(|(n, _)| n == "deadlines").unwrap();
+    assert_eq!(Policy::default().wpost_period_deadlines as usize, deadlines.1.len());
+    assert!(miner.to_string().contains("storageminer"));
+}
@@ -0,0 +1,516 @@
+//! Golden CBOR encodings of actor state and method parameters.
+//!
+//! Each actor's vectors are checked in at `tests/vectors/<actor>.txt`, one `name hex` pair per
+//! line. A test encodes a value of each type with distinct field values, so that reordering
+//! fields or changing their serde attributes changes the encoding and fails the test, then
+//! decodes the checked-in bytes and checks they re-encode identically.
+//!
+//! An intended change of encoding must come with a state migration. Once that's in place, run
+//! the tests with `UPDATE_VECTORS=1` to rewrite the vectors, and review the diff.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use cid::Cid;
+use fil_actors_runtime::reward::FilterEstimate;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::make_identity_cid;
+use fvm_ipld_bitfield::BitField;
+use fvm_ipld_encoding::{to_vec, BytesDe, RawBytes};
+use fvm_shared::address::Address;
+use fvm_shared::bigint::BigInt;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof, StoragePower};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The checked-in vectors for one actor, and the results of checking values against them.
+struct Corpus {
+    path: PathBuf,
+    expected: BTreeMap<String, String>,
+    actual: BTreeMap<String, String>,
+    errors: Vec<String>,
+}
+
+impl Corpus {
+    fn load(actor: &str) -> Self {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/vectors")
+            .join(format!("{actor}.txt"));
+        let text = std::fs::read_to_string(&path).unwrap_or_default();
+        let expected = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(|l| {
+                let (name, hex) = l.split_once(' ').unwrap_or_else(|| panic!("bad vector {l:?}"));
+                (name.to_string(), hex.trim().to_string())
+            })
+            .collect();
+        Corpus { path, expected, actual: BTreeMap::new(), errors: Vec::new() }
+    }
+
+    fn check<T: Serialize + DeserializeOwned>(&mut self, name: &str, value: &T) {
+        let encoded = hex::encode(to_vec(value).unwrap());
+        match self.expected.get(name) {
+            None => self.errors.push(format!("no vector for {name}, encoded as {encoded}")),
+            Some(expected) if *expected != encoded => self.errors.push(format!(
+                "{name} encoding changed\n  expected {expected}\n  actual   {encoded}"
+            )),
+            Some(expected) => {
+                let bytes = hex::decode(expected).unwrap();
+                match fvm_ipld_encoding::from_slice::<T>(&bytes) {
+                    Ok(decoded) => {
+                        if to_vec(&decoded).unwrap() != bytes {
+                            self.errors.push(format!("{name} doesn't round-trip"))
+                        }
+                    }
+                    Err(e) => self.errors.push(format!("{name} failed to decode: {e}")),
+                }
+            }
+        }
+        self.actual.insert(name.to_string(), encoded);
+    }
+
+    fn finish(mut self) {
+        if std::env::var_os("UPDATE_VECTORS").is_some() {
+            let mut text = String::from("# Generated by tests/vectors_test.rs. Do not edit.\n");
+            for (name, hex) in &self.actual {
+                text.push_str(&format!("{name} {hex}\n"));
+            }
+            std::fs::write(&self.path, text).unwrap();
+            return;
+        }
+        for name in self.expected.keys().filter(|n| !self.actual.contains_key(*n)) {
+            self.errors.push(format!("stale vector {name}"));
+        }
+        assert!(
+            self.errors.is_empty(),
+            "{} (run with UPDATE_VECTORS=1 after reviewing):\n{}",
+            self.path.display(),
+            self.errors.join("\n")
+        );
+    }
+}
+
+fn id(n: u64) -> Address {
+    Address::new_id(n)
+}
+
+fn cid(name: &str) -> Cid {
+    make_identity_cid(name.as_bytes())
+}
+
+fn atto(n: u64) -> TokenAmount {
+    TokenAmount::from_atto(n)
+}
+
+#[test]
+fn account() {
+    let mut c = Corpus::load("account");
+    c.check("State", &fil_actor_account::State { address: id(101), auth_delegate: None });
+    c.finish();
+}
+
+#[test]
+fn cron() {
+    use fil_actor_cron::{Entry, State};
+    let mut c = Corpus::load("cron");
+    let entry = Entry { receiver: id(101), method_num: 2 };
+    c.check("Entry", &entry);
+    c.check("State", &State { entries: vec![entry, Entry { receiver: id(102), method_num: 3 }] });
+    c.finish();
+}
+
+#[test]
+fn datacap() {
+    use fil_actor_datacap::State;
+    let mut c = Corpus::load("datacap");
+    let store = MemoryBlockstore::new();
+    let mut token = frc46_token::token::state::TokenState::new_with_bit_width(&store, 5).unwrap();
+    token.supply = atto(1001);
+    c.check("State", &State { governor: id(101), token });
+    c.finish();
+}
+
+#[test]
+fn evm() {
+    use fil_actor_evm::{BytecodeHash, State, Tombstone, TransientData, TransientDataLifespan};
+    let mut c = Corpus::load("evm");
+    c.check(
+        "State",
+        &State {
+            bytecode: cid("bytecode"),
+            bytecode_hash: BytecodeHash::EMPTY,
+            contract_state: cid("contract_state"),
+            transient_data: Some(TransientData {
+                transient_data_state: cid("transient_data_state"),
+                transient_data_lifespan: TransientDataLifespan { origin: 101, nonce: 2 },
+            }),
+            nonce: 3,
+            tombstone: Some(Tombstone { origin: 102, nonce: 4 }),
+        },
+    );
+    c.finish();
+}
+
+#[test]
+fn init() {
+    use fil_actor_init::{ExecParams, ExecReturn, State};
+    let mut c = Corpus::load("init");
+    c.check(
+        "State",
+        &State {
+            address_map: cid("address_map"),
+            next_id: 1001,
+            network_name: "vectors".to_string(),
+        },
+    );
+    c.check(
+        "ExecParams",
+        &ExecParams { code_cid: cid("code"), constructor_params: RawBytes::new(vec![0xde, 0xad]) },
+    );
+    c.check("ExecReturn", &ExecReturn { id_address: id(101), robust_address: id(102) });
+    c.finish();
+}
+
+#[test]
+fn market() {
+    use fil_actor_market::{DealProposal, DealState, Label, State, WithdrawBalanceParams};
+    let mut c = Corpus::load("market");
+    c.check(
+        "State",
+        &State {
+            proposals: cid("proposals"),
+            states: cid("states"),
+            pending_proposals: cid("pending_proposals"),
+            escrow_table: cid("escrow_table"),
+            locked_table: cid("locked_table"),
+            next_id: 1001,
+            deal_ops_by_epoch: cid("deal_ops_by_epoch"),
+            last_cron: 2,
+            total_client_locked_collateral: atto(3),
+            total_provider_locked_collateral: atto(4),
+            total_client_storage_fee: atto(5),
+            pending_deal_allocation_ids: cid("pending_deal_allocation_ids"),
+            provider_sectors: cid("provider_sectors"),
+            superseded_proposals: None,
+        },
+    );
+    let proposal = |label| DealProposal {
+        piece_cid: cid("piece"),
+        piece_size: PaddedPieceSize(2048),
+        verified_deal: true,
+        client: id(101),
+        provider: id(102),
+        label,
+        start_epoch: 3,
+        end_epoch: 4,
+        storage_price_per_epoch: atto(5),
+        provider_collateral: atto(6),
+        client_collateral: atto(7),
+    };
+    c.check("DealProposal/string_label", &proposal(Label::String("label".to_string())));
+    c.check("DealProposal/bytes_label", &proposal(Label::Bytes(vec![0xbe, 0xef])));
+    c.check(
+        "DealState",
+        &DealState {
+            sector_number: 1,
+            sector_start_epoch: 2,
+            last_updated_epoch: 3,
+            slash_epoch: 4,
+        },
+    );
+    c.check(
+        "WithdrawBalanceParams",
+        &WithdrawBalanceParams { provider_or_client: id(101), amount: atto(2) },
+    );
+    c.finish();
+}
+
+#[test]
+fn miner() {
+    use fil_actor_miner::{MinerInfo, SectorOnChainInfo, SectorOnChainInfoFlags, State};
+    let mut c = Corpus::load("miner");
+    c.check(
+        "State",
+        &State {
+            info: cid("info"),
+            pre_commit_deposits: atto(1),
+            locked_funds: atto(2),
+            vesting_funds: cid("vesting_funds"),
+            fee_debt: atto(3),
+            initial_pledge: atto(4),
+            pre_committed_sectors: cid("pre_committed_sectors"),
+            pre_committed_sectors_cleanup: cid("pre_committed_sectors_cleanup"),
+            allocated_sectors: cid("allocated_sectors"),
+            sectors: cid("sectors"),
+            proving_period_start: 5,
+            current_deadline: 6,
+            deadlines: cid("deadlines"),
+            early_terminations: BitField::try_from_bits([7, 8]).unwrap(),
+            deadline_cron_active: true,
+        },
+    );
+    c.check(
+        "MinerInfo",
+        &MinerInfo::new(
+            101,
+            102,
+            vec![103, 104],
+            b"peer".to_vec(),
+            vec![BytesDe(b"multiaddr".to_vec())],
+            RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        )
+        .unwrap(),
+    );
+    c.check(
+        "SectorOnChainInfo",
+        &SectorOnChainInfo {
+            sector_number: 1,
+            seal_proof: RegisteredSealProof::StackedDRG32GiBV1P1,
+            sealed_cid: cid("sealed"),
+            deprecated_deal_ids: vec![2, 3],
+            activation: 4,
+            expiration: 5,
+            deal_weight: BigInt::from(6),
+            verified_deal_weight: BigInt::from(7),
+            initial_pledge: atto(8),
+            expected_day_reward: atto(9),
+            expected_storage_pledge: atto(10),
+            power_base_epoch: 11,
+            replaced_day_reward: atto(12),
+            sector_key_cid: Some(cid("sector_key")),
+            flags: SectorOnChainInfoFlags::SIMPLE_QA_POWER,
+        },
+    );
+    c.finish();
+}
+
+#[test]
+fn multisig() {
+    use fil_actor_multisig::{PendingGovernanceChange, ProposeParams, State, Transaction};
+    let mut c = Corpus::load("multisig");
+    c.check(
+        "State",
+        &State {
+            signers: vec![id(101), id(102)],
+            num_approvals_threshold: 2,
+            next_tx_id: fil_actor_multisig::TxnID(3),
+            initial_balance: atto(4),
+            start_epoch: 5,
+            unlock_duration: 6,
+            pending_txs: cid("pending_txs"),
+            governance_delay: 7,
+            pending_governance: vec![PendingGovernanceChange {
+                effective_at: 8,
+                method: 9,
+                params: RawBytes::new(vec![0xde, 0xad]),
+            }],
+        },
+    );
+    c.check(
+        "Transaction",
+        &Transaction {
+            to: id(101),
+            value: atto(2),
+            method: 3,
+            params: RawBytes::new(vec![0xde, 0xad]),
+            approved: vec![id(102)],
+            expires_at: Some(4),
+        },
+    );
+    // State and transactions predating governance delays and expiry keep their encoding.
+    c.check(
+        "StateWithoutGovernance",
+        &State {
+            signers: vec![id(101), id(102)],
+            num_approvals_threshold: 2,
+            next_tx_id: fil_actor_multisig::TxnID(3),
+            initial_balance: atto(4),
+            start_epoch: 5,
+            unlock_duration: 6,
+            pending_txs: cid("pending_txs"),
+            governance_delay: 0,
+            pending_governance: vec![],
+        },
+    );
+    c.check(
+        "TransactionWithoutExpiry",
+        &Transaction {
+            to: id(101),
+            value: atto(2),
+            method: 3,
+            params: RawBytes::new(vec![0xde, 0xad]),
+            approved: vec![id(102)],
+            expires_at: None,
+        },
+    );
+    c.check(
+        "ProposeParams",
+        &ProposeParams {
+            to: id(101),
+            value: atto(2),
+            method: 3,
+            params: RawBytes::new(vec![0xde, 0xad]),
+        },
+    );
+    c.finish();
+}
+
+#[test]
+fn paych() {
+    use fil_actor_paych::{LaneState, State};
+    let mut c = Corpus::load("paych");
+    c.check(
+        "State",
+        &State {
+            from: id(101),
+            to: id(102),
+            to_send: atto(3),
+            settling_at: 4,
+            min_settle_height: 5,
+            lane_states: cid("lane_states"),
+        },
+    );
+    c.check("LaneState", &LaneState { redeemed: atto(1), nonce: 2 });
+    c.finish();
+}
+
+#[test]
+fn power() {
+    use fil_actor_power::{Claim, CreateMinerParams, State};
+    let mut c = Corpus::load("power");
+    let power = |n: u64| StoragePower::from(n);
+    c.check(
+        "State",
+        &State {
+            total_raw_byte_power: power(1),
+            total_bytes_committed: power(2),
+            total_quality_adj_power: power(3),
+            total_qa_bytes_committed: power(4),
+            total_pledge_collateral: atto(5),
+            this_epoch_raw_byte_power: power(6),
+            this_epoch_quality_adj_power: power(7),
+            this_epoch_pledge_collateral: atto(8),
+            this_epoch_qa_power_smoothed: FilterEstimate::new(BigInt::from(9), BigInt::from(10)),
+            miner_count: 11,
+            miner_above_min_power_count: 12,
+            ramp_start_epoch: 13,
+            ramp_duration_epochs: 14,
+            cron_event_queue: cid("cron_event_queue"),
+            first_cron_epoch: 15,
+            claims: cid("claims"),
+            proof_validation_batch: Some(cid("proof_validation_batch")),
+        },
+    );
+    c.check(
+        "Claim",
+        &Claim {
+            window_post_proof_type: RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+            raw_byte_power: power(1),
+            quality_adj_power: power(2),
+        },
+    );
+    c.check(
+        "CreateMinerParams",
+        &CreateMinerParams {
+            owner: id(101),
+            worker: id(102),
+            window_post_proof_type: RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+            peer: b"peer".to_vec(),
+            multiaddrs: vec![BytesDe(b"multiaddr".to_vec())],
+        },
+    );
+    c.finish();
+}
+
+#[test]
+fn reward() {
+    use fil_actor_reward::{State, ThisEpochRewardReturn};
+    let mut c = Corpus::load("reward");
+    let smoothed = FilterEstimate::new(BigInt::from(1), BigInt::from(2));
+    c.check(
+        "State",
+        &State {
+            cumsum_baseline: BigInt::from(1),
+            cumsum_realized: BigInt::from(2),
+            effective_network_time: 3,
+            effective_baseline_power: StoragePower::from(4),
+            this_epoch_reward: atto(5),
+            this_epoch_reward_smoothed: smoothed.clone(),
+            this_epoch_baseline_power: StoragePower::from(6),
+            epoch: 7,
+            total_storage_power_reward: atto(8),
+            simple_total: atto(9),
+            baseline_total: atto(10),
+        },
+    );
+    c.check(
+        "ThisEpochRewardReturn",
+        &ThisEpochRewardReturn {
+            this_epoch_reward_smoothed: smoothed,
+            this_epoch_baseline_power: StoragePower::from(3),
+        },
+    );
+    c.finish();
+}
+
+#[test]
+fn system() {
+    let mut c = Corpus::load("system");
+    c.check("State", &fil_actor_system::State { builtin_actors: cid("builtin_actors") });
+    c.check(
+        "GetBuiltinActorsReturn",
+        &fil_actor_system::GetBuiltinActorsReturn { builtin_actors: cid("builtin_actors") },
+    );
+    c.finish();
+}
+
+#[test]
+fn verifreg() {
+    use fil_actor_verifreg::{Allocation, Claim, State, VerifierParams};
+    let mut c = Corpus::load("verifreg");
+    c.check(
+        "State",
+        &State {
+            root_key: id(101),
+            verifiers: cid("verifiers"),
+            remove_data_cap_proposal_ids: cid("remove_data_cap_proposal_ids"),
+            allocations: cid("allocations"),
+            next_allocation_id: 2,
+            claims: cid("claims"),
+        },
+    );
+    c.check(
+        "Allocation",
+        &Allocation {
+            client: 101,
+            provider: 102,
+            data: cid("data"),
+            size: PaddedPieceSize(2048),
+            term_min: 3,
+            term_max: 4,
+            expiration: 5,
+        },
+    );
+    c.check(
+        "Claim",
+        &Claim {
+            provider: 101,
+            client: 102,
+            data: cid("data"),
+            size: PaddedPieceSize(2048),
+            term_min: 3,
+            term_max: 4,
+            term_start: 5,
+            sector: 6,
+        },
+    );
+    c.check(
+        "VerifierParams",
+        &VerifierParams { address: id(101), allowance: StoragePower::from(2) },
+    );
+    c.finish();
+}
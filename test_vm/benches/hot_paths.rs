@@ -0,0 +1,289 @@
+//! Native benchmarks of hot actor code paths, executed in the test VM.
+//! These catch performance regressions in actor code before they show up in gas measurements
+//! of the WASM bundle, but include the test VM's own overhead.
+//!
+//! Run with `cargo bench -p test_vm`, optionally followed by `-- <filter>` to select benchmarks
+//! by name.
+
+use std::time::{Duration, Instant};
+
+use fil_actor_datacap::Method as DataCapMethod;
+use fil_actor_market::next_update_epoch;
+use fil_actor_miner::SectorPreCommitOnChainInfo;
+use fil_actor_verifreg::{
+    AddVerifiedClientParams, AllocationRequest, AllocationRequests, Method as VerifregMethod,
+};
+use fil_actors_integration_tests::deals::{DealBatcher, DealOptions};
+use fil_actors_integration_tests::util::{
+    advance_by_deadline_to_epoch, advance_to_epoch_sampling_cron, advance_to_epoch_with_cron,
+    advance_to_proving_deadline, create_accounts, create_miner, make_piece_manifests_from_deal_ids,
+    market_add_balance, miner_prove_sector, precommit_meta_data_from_deals, precommit_sectors_v2,
+    prove_commit_sectors, submit_windowed_post, verifreg_add_client, verifreg_add_verifier,
+};
+use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::frc46::TransferParams;
+use fil_actors_runtime::runtime::policy_constants::MINIMUM_VERIFIED_ALLOCATION_SIZE;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::make_piece_cid;
+use fil_actors_runtime::{DATACAP_TOKEN_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR};
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::deal::DealID;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::{RegisteredSealProof, StoragePower};
+use num_traits::Zero;
+use test_vm::TestVM;
+use vm_api::util::apply_ok;
+use vm_api::VM;
+
+const SEAL_PROOF: RegisteredSealProof = RegisteredSealProof::StackedDRG32GiBV1P1;
+
+/// Times `run` over fresh state from `setup`, and prints a summary of the samples.
+fn bench<S>(
+    filter: &Option<String>,
+    name: &str,
+    samples: usize,
+    mut setup: impl FnMut() -> S,
+    mut run: impl FnMut(&mut S),
+) {
+    if filter.as_ref().is_some_and(|f| !name.contains(f.as_str())) {
+        return;
+    }
+    let mut times: Vec<Duration> = (0..samples)
+        .map(|_| {
+            let mut state = setup();
+            let start = Instant::now();
+            run(&mut state);
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    let mean = times.iter().sum::<Duration>() / samples as u32;
+    println!(
+        "{:<40} min {:>10.3?}  median {:>10.3?}  mean {:>10.3?}  max {:>10.3?}  ({} samples)",
+        name,
+        times[0],
+        times[samples / 2],
+        mean,
+        times[samples - 1],
+        samples
+    );
+}
+
+struct Miner {
+    v: TestVM,
+    worker: Address,
+    maddr: Address,
+}
+
+/// A VM with a single miner and a funded client.
+fn setup_miner() -> (Miner, Address) {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(100_000));
+    let (worker, client) = (addrs[0], addrs[1]);
+    let (maddr, _) = create_miner(
+        &v,
+        &worker,
+        &worker,
+        SEAL_PROOF.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(10_000),
+    );
+    market_add_balance(&v, &worker, &maddr, &TokenAmount::from_whole(10_000));
+    market_add_balance(&v, &client, &client, &TokenAmount::from_whole(10_000));
+    v.set_epoch(200);
+    (Miner { v, worker, maddr }, client)
+}
+
+/// Pre-commits sectors, and advances to the epoch at which they can be proven.
+fn precommit(m: &Miner, count: usize, deals: &[DealID]) -> Vec<SectorPreCommitOnChainInfo> {
+    let per_sector = deals.len().div_ceil(count).max(1);
+    let metadata = deals
+        .chunks(per_sector)
+        .map(|ids| precommit_meta_data_from_deals(&m.v, ids, SEAL_PROOF, false))
+        .collect();
+    let precommits = precommit_sectors_v2(
+        &m.v, count, count, metadata, &m.worker, &m.maddr, SEAL_PROOF, 0, true, None,
+    );
+    let prove_time = m.v.epoch() + Policy::default().pre_commit_challenge_delay + 1;
+    advance_by_deadline_to_epoch(&m.v, &m.maddr, prove_time);
+    precommits
+}
+
+fn stage_deals(m: &Miner, client: Address, count: usize, deal_start: ChainEpoch) -> DealBatcher {
+    let mut batcher = DealBatcher::new(&m.v, DealOptions { deal_start, ..Default::default() });
+    for _ in 0..count {
+        batcher.stage(client, m.maddr);
+    }
+    batcher
+}
+
+fn window_post(filter: &Option<String>) {
+    bench(
+        filter,
+        "submit_windowed_post_32_sectors",
+        10,
+        || {
+            let (m, _) = setup_miner();
+            let precommits = precommit(&m, 32, &[]);
+            prove_commit_sectors(&m.v, &m.worker, &m.maddr, precommits, 32);
+            let (dline, partition) = advance_to_proving_deadline(&m.v, &m.maddr, 0);
+            (m, dline, partition)
+        },
+        |(m, dline, partition)| {
+            submit_windowed_post(&m.v, &m.worker, &m.maddr, *dline, *partition, None)
+        },
+    );
+}
+
+fn publish_deals(filter: &Option<String>) {
+    bench(
+        filter,
+        "publish_storage_deals_128",
+        10,
+        || {
+            let (m, client) = setup_miner();
+            let deal_start = m.v.epoch() + Policy::default().pre_commit_challenge_delay + 1;
+            (m, client, deal_start)
+        },
+        |(m, client, deal_start)| {
+            stage_deals(m, *client, 128, *deal_start).publish_ok(m.worker);
+        },
+    );
+}
+
+fn prove_commit_aggregate(filter: &Option<String>) {
+    bench(
+        filter,
+        "prove_commit_aggregate_32",
+        10,
+        || {
+            let (m, _) = setup_miner();
+            let precommits = precommit(&m, 32, &[]);
+            (m, precommits)
+        },
+        |(m, precommits)| prove_commit_sectors(&m.v, &m.worker, &m.maddr, precommits.clone(), 32),
+    );
+}
+
+fn market_cron(filter: &Option<String>) {
+    // A short update interval, so that every deal's payment is settled within a few hundred
+    // epochs of cron.
+    const INTERVAL: ChainEpoch = 200;
+    bench(
+        filter,
+        "market_cron_settle_128_deals",
+        5,
+        || {
+            let (mut m, client) = setup_miner();
+            m.v = m.v.with_policy(Policy { deal_updates_interval: INTERVAL, ..Default::default() });
+            let deal_start = m.v.epoch() + Policy::default().pre_commit_challenge_delay + INTERVAL;
+            let deals = stage_deals(&m, client, 128, deal_start).publish_ok(m.worker).ids;
+            for (sector, precommit) in precommit(&m, 4, &deals).iter().enumerate() {
+                let ids = deals[sector * 32..(sector + 1) * 32].to_vec();
+                let manifests = make_piece_manifests_from_deal_ids(&m.v, ids);
+                miner_prove_sector(
+                    &m.v,
+                    &m.worker,
+                    &m.maddr,
+                    precommit.info.sector_number,
+                    manifests,
+                );
+            }
+            // Process the deals' first updates, so the next ones settle a full interval.
+            let updates = deals.iter().map(|id| next_update_epoch(*id, INTERVAL, deal_start));
+            let last_update = updates.max().unwrap();
+            advance_to_epoch_sampling_cron(&m.v, last_update + 1, INTERVAL / 4);
+            m
+        },
+        |m| advance_to_epoch_with_cron(&m.v, m.v.epoch() + INTERVAL),
+    );
+}
+
+fn verifreg_clients(filter: &Option<String>) {
+    bench(
+        filter,
+        "verifreg_add_verified_client_100",
+        10,
+        || {
+            let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+            let addrs = create_accounts(&v, 101, &TokenAmount::from_whole(1_000));
+            verifreg_add_verifier(&v, &addrs[0], StoragePower::from(1u64 << 50));
+            (v, addrs)
+        },
+        |(v, addrs)| {
+            for client in &addrs[1..] {
+                let params = AddVerifiedClientParams {
+                    address: *client,
+                    allowance: StoragePower::from(MINIMUM_VERIFIED_ALLOCATION_SIZE),
+                };
+                apply_ok(
+                    v,
+                    &addrs[0],
+                    &VERIFIED_REGISTRY_ACTOR_ADDR,
+                    &TokenAmount::zero(),
+                    VerifregMethod::AddVerifiedClient as u64,
+                    Some(params),
+                );
+            }
+        },
+    );
+}
+
+fn verifreg_allocations(filter: &Option<String>) {
+    bench(
+        filter,
+        "verifreg_create_allocations_100",
+        10,
+        || {
+            let (m, client) = setup_miner();
+            let verifier = create_accounts(&m.v, 1, &TokenAmount::from_whole(1_000))[0];
+            verifreg_add_verifier(&m.v, &verifier, StoragePower::from(1u64 << 50));
+            verifreg_add_client(&m.v, &verifier, &client, StoragePower::from(1u64 << 40));
+            (m, client)
+        },
+        |(m, client)| {
+            let policy = Policy::default();
+            let allocations = (0..100)
+                .map(|i| AllocationRequest {
+                    provider: m.maddr.id().unwrap(),
+                    data: make_piece_cid(format!("alloc-{}", i).as_bytes()),
+                    size: PaddedPieceSize(MINIMUM_VERIFIED_ALLOCATION_SIZE as u64),
+                    term_min: policy.minimum_verified_allocation_term,
+                    term_max: policy.maximum_verified_allocation_term,
+                    expiration: m.v.epoch() + policy.maximum_verified_allocation_expiration,
+                })
+                .collect::<Vec<_>>();
+            let operator_data = serialize(
+                &AllocationRequests { allocations, extensions: vec![] },
+                "allocation requests",
+            )
+            .unwrap();
+            let params = TransferParams {
+                to: VERIFIED_REGISTRY_ACTOR_ADDR,
+                amount: TokenAmount::from_whole(100 * MINIMUM_VERIFIED_ALLOCATION_SIZE as u64),
+                operator_data,
+            };
+            apply_ok(
+                &m.v,
+                client,
+                &DATACAP_TOKEN_ACTOR_ADDR,
+                &TokenAmount::zero(),
+                DataCapMethod::TransferExported as u64,
+                Some(params),
+            );
+        },
+    );
+}
+
+fn main() {
+    // Cargo passes flags such as --bench, which aren't filters.
+    let filter = std::env::args().skip(1).find(|a| !a.starts_with("--"));
+    window_post(&filter);
+    publish_deals(&filter);
+    prove_commit_aggregate(&filter);
+    market_cron(&filter);
+    verifreg_clients(&filter);
+    verifreg_allocations(&filter);
+}
@@ -0,0 +1,68 @@
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+
+use anyhow::anyhow;
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::DAG_CBOR;
+use fvm_shared::commcid::{FIL_COMMITMENT_SEALED, FIL_COMMITMENT_UNSEALED};
+use integer_encoding::VarInt;
+use ipld_core::ipld::Ipld;
+use serde::Serialize;
+
+// Multihash code of identity CIDs, which inline their data rather than referencing a block.
+const IDENTITY_HASH: u64 = 0;
+
+#[derive(Serialize)]
+struct CarHeader {
+    roots: Vec<Cid>,
+    version: u64,
+}
+
+/// Writes a CARv1 containing the blocks reachable from a root.
+/// Links are followed through DAG-CBOR blocks. Identity CIDs, and piece and sector
+/// commitments (which don't reference blocks), are not written.
+pub fn write_car(bs: &impl Blockstore, root: Cid, out: &mut impl Write) -> anyhow::Result<()> {
+    let header = fvm_ipld_encoding::to_vec(&CarHeader { roots: vec![root], version: 1 })?;
+    out.write_all(&header.len().encode_var_vec())?;
+    out.write_all(&header)?;
+
+    let mut seen = HashSet::from([root]);
+    let mut queue = VecDeque::from([root]);
+    while let Some(cid) = queue.pop_front() {
+        if cid.hash().code() == IDENTITY_HASH
+            || [FIL_COMMITMENT_SEALED, FIL_COMMITMENT_UNSEALED].contains(&cid.codec())
+        {
+            continue;
+        }
+        let block = bs.get(&cid)?.ok_or_else(|| anyhow!("missing block {}", cid))?;
+        let cid_bytes = cid.to_bytes();
+        out.write_all(&(cid_bytes.len() + block.len()).encode_var_vec())?;
+        out.write_all(&cid_bytes)?;
+        out.write_all(&block)?;
+
+        if cid.codec() == DAG_CBOR {
+            let ipld: Ipld = fvm_ipld_encoding::from_slice(&block)?;
+            for link in links(&ipld) {
+                if seen.insert(link) {
+                    queue.push_back(link);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn links(ipld: &Ipld) -> Vec<Cid> {
+    let mut found = vec![];
+    let mut stack = vec![ipld];
+    while let Some(node) = stack.pop() {
+        match node {
+            Ipld::Link(cid) => found.push(*cid),
+            Ipld::List(items) => stack.extend(items),
+            Ipld::Map(entries) => stack.extend(entries.values()),
+            _ => {}
+        }
+    }
+    found
+}
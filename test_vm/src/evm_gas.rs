@@ -0,0 +1,49 @@
+//! Ethereum gas costs of EVM instructions, for comparing the gas used by contracts in the
+//! test VM with what they would use on Ethereum.
+
+use fil_actor_evm::interpreter::opcodes::*;
+use fil_actor_evm::interpreter::trace::TraceStep;
+
+/// Gas charged by Ethereum for every transaction.
+pub const ETHEREUM_TRANSACTION_GAS: u64 = 21_000;
+/// Gas charged by Ethereum for a transaction creating a contract, in addition.
+pub const ETHEREUM_CREATE_GAS: u64 = 32_000;
+
+/// Returns the static Ethereum gas cost of an instruction, as of the Cancun fork.
+///
+/// Dynamic costs, such as for memory expansion, copied bytes and log data, aren't included.
+/// State access costs are those of cold access, and storage writes are costed as setting a
+/// slot from zero, so the cost of state-heavy code is overestimated rather than under.
+pub fn ethereum_gas(opcode: u8) -> u64 {
+    match opcode {
+        STOP | RETURN | REVERT | INVALID => 0,
+        JUMPDEST => 1,
+        ADDRESS | ORIGIN | CALLER | CALLVALUE | CALLDATASIZE | CODESIZE | GASPRICE
+        | RETURNDATASIZE | COINBASE | TIMESTAMP | NUMBER | PREVRANDAO | GASLIMIT | CHAINID
+        | BASEFEE | POP | PC | MSIZE | GAS | PUSH0 => 2,
+        ADD | SUB | NOT | LT | GT | SLT | SGT | EQ | ISZERO | AND | OR | XOR | BYTE | SHL | SHR
+        | SAR | CALLDATALOAD | CALLDATACOPY | CODECOPY | RETURNDATACOPY | MLOAD | MSTORE
+        | MSTORE8 | MCOPY => 3,
+        PUSH1..=PUSH32 | DUP1..=DUP16 | SWAP1..=SWAP16 => 3,
+        MUL | DIV | SDIV | MOD | SMOD | SIGNEXTEND | SELFBALANCE => 5,
+        ADDMOD | MULMOD | JUMP => 8,
+        EXP | JUMPI => 10,
+        BLOCKHASH => 20,
+        KECCAK256 => 30,
+        TLOAD | TSTORE => 100,
+        LOG0..=LOG4 => 375 * (1 + (opcode - LOG0) as u64),
+        SLOAD => 2_100,
+        BALANCE | EXTCODESIZE | EXTCODECOPY | EXTCODEHASH => 2_600,
+        CALL | DELEGATECALL | STATICCALL => 2_600,
+        SELFDESTRUCT => 5_000,
+        SSTORE => 22_100,
+        CREATE | CREATE2 => 32_000,
+        _ => 0,
+    }
+}
+
+/// Returns the Ethereum gas cost of the instructions in a trace, by [`ethereum_gas`],
+/// excluding that of any transaction.
+pub fn ethereum_trace_gas(trace: &[TraceStep]) -> u64 {
+    trace.iter().map(|step| ethereum_gas(step.opcode)).sum()
+}
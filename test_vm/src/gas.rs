@@ -0,0 +1,69 @@
+use fil_actors_runtime::test_blockstores::BSStats;
+
+/// The gas limit of a block, and the default limit of each message executed in gas mode.
+pub const BLOCK_GAS_LIMIT: u64 = 10_000_000_000;
+
+/// Gas prices charged by the test VM in gas mode.
+/// The defaults are of the same order as the FVM's prices, but are not kept in sync with them.
+/// Gas used is useful for detecting large changes in cost, not for estimating real gas.
+#[derive(Clone, Debug)]
+pub struct PriceList {
+    /// Charged once for each top-level message.
+    pub on_chain_message: u64,
+    /// Charged per byte of top-level message parameters.
+    pub on_chain_message_per_byte: u64,
+    /// Charged for each invocation, including that of the top-level message.
+    pub send: u64,
+    /// Charged per block read from the store.
+    pub block_read: u64,
+    pub block_read_per_byte: u64,
+    /// Charged per block written to the store.
+    pub block_write: u64,
+    pub block_write_per_byte: u64,
+    pub emit_event: u64,
+    pub verify_signature: u64,
+    pub hash: u64,
+    pub recover_secp_public_key: u64,
+    pub compute_unsealed_sector_cid: u64,
+    pub verify_post: u64,
+    pub verify_consensus_fault: u64,
+    pub verify_replica_update: u64,
+    /// Charged per seal in a batch verification.
+    pub verify_seal: u64,
+    /// Charged per sector in an aggregate seal verification.
+    pub verify_aggregate_seal: u64,
+}
+
+impl Default for PriceList {
+    fn default() -> Self {
+        PriceList {
+            on_chain_message: 38_863,
+            on_chain_message_per_byte: 1_300,
+            send: 29_233,
+            block_read: 114_617,
+            block_read_per_byte: 10,
+            block_write: 353_640,
+            block_write_per_byte: 1_300,
+            emit_event: 50_000,
+            verify_signature: 2_000_000,
+            hash: 31_355,
+            recover_secp_public_key: 1_637_292,
+            compute_unsealed_sector_cid: 49_299_973,
+            verify_post: 200_000_000,
+            verify_consensus_fault: 495_422,
+            verify_replica_update: 36_316_136,
+            verify_seal: 2_000,
+            verify_aggregate_seal: 450_000,
+        }
+    }
+}
+
+impl PriceList {
+    /// Gas charged for blockstore access.
+    pub fn io_gas(&self, stats: &BSStats) -> u64 {
+        stats.r as u64 * self.block_read
+            + stats.br as u64 * self.block_read_per_byte
+            + stats.w as u64 * self.block_write
+            + stats.bw as u64 * self.block_write_per_byte
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use cid::Cid;
+use fil_actor_account::State as AccountState;
+use fil_actor_cron::{Entry as CronEntry, State as CronState};
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_init::{ExecReturn, State as InitState};
+use fil_actor_market::{Method as MarketMethod, State as MarketState};
+use fil_actor_power::{Method as MethodPower, State as PowerState};
+use fil_actor_reward::State as RewardState;
+use fil_actor_system::State as SystemState;
+use fil_actor_verifreg::State as VerifRegState;
+use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::{Policy, EMPTY_ARR_CID};
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::*;
+use fil_actors_runtime::{
+    BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR, EAM_ACTOR_ADDR,
+    INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
+    SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fvm_shared::address::Address;
+use fvm_shared::bigint::Zero;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::StoragePower;
+use fvm_shared::METHOD_SEND;
+use vm_api::util::serialize_ok;
+use vm_api::{new_actor, VM};
+
+use crate::{TestVM, FAUCET_ROOT_KEY, VERIFREG_ROOT_KEY};
+
+/// Singleton actors which a genesis may leave out.
+/// The system, init and burnt funds actors are always present.
+pub const OPTIONAL_SINGLETONS: [Type; 7] = [
+    Type::Reward,
+    Type::Cron,
+    Type::Power,
+    Type::Market,
+    Type::VerifiedRegistry,
+    Type::DataCap,
+    Type::EAM,
+];
+
+/// Builds the genesis state of a test VM: the builtin singleton actors, the verified registry's
+/// root key holder, a faucet account for funding test accounts, and any other funded accounts.
+///
+/// The default genesis is that of [`TestVM::new_with_singletons`], and the actor IDs of its
+/// verified registry root and faucet are the `TEST_*` constants. Choosing other root signers or
+/// leaving the verified registry out changes the IDs of the faucet and any accounts after it.
+pub struct GenesisBuilder {
+    without: BTreeSet<Type>,
+    reward_balance: TokenAmount,
+    faucet_balance: TokenAmount,
+    accounts: Vec<(Address, TokenAmount)>,
+    verifreg_root_signers: Vec<Address>,
+    verifreg_root_threshold: u64,
+    policy: Policy,
+}
+
+impl Default for GenesisBuilder {
+    fn default() -> Self {
+        Self {
+            without: BTreeSet::new(),
+            reward_balance: TokenAmount::from_whole(1_100_000_000i64),
+            faucet_balance: TokenAmount::from_whole(1_000_000_000i64),
+            accounts: vec![],
+            verifreg_root_signers: vec![Address::new_bls(VERIFREG_ROOT_KEY).unwrap()],
+            verifreg_root_threshold: 1,
+            policy: Policy::default(),
+        }
+    }
+}
+
+impl GenesisBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leaves a singleton actor out of the genesis. Panics unless it is one of
+    /// [`OPTIONAL_SINGLETONS`].
+    pub fn without(mut self, actor: Type) -> Self {
+        assert!(OPTIONAL_SINGLETONS.contains(&actor), "{} actor is required", actor.name());
+        self.without.insert(actor);
+        self
+    }
+
+    /// Sets the balance of the reward actor, from which block rewards are paid.
+    pub fn reward_balance(mut self, balance: TokenAmount) -> Self {
+        self.reward_balance = balance;
+        self
+    }
+
+    /// Sets the balance of the faucet from which test accounts are funded.
+    pub fn faucet_balance(mut self, balance: TokenAmount) -> Self {
+        self.faucet_balance = balance;
+        self
+    }
+
+    /// Adds an account for a key address, funded with a balance. Accounts are created in the
+    /// order added, after the faucet.
+    pub fn account(mut self, key: Address, balance: TokenAmount) -> Self {
+        self.accounts.push((key, balance));
+        self
+    }
+
+    /// Sets the key addresses of the signers of the verified registry's root multisig, and the
+    /// number of them which must approve its proposals.
+    pub fn verifreg_root(mut self, signers: Vec<Address>, threshold: u64) -> Self {
+        assert!(!signers.is_empty(), "verified registry root needs a signer");
+        self.verifreg_root_signers = signers;
+        self.verifreg_root_threshold = threshold;
+        self
+    }
+
+    /// Sets the policy observed by actors, both during and after genesis.
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn includes(&self, actor: Type) -> bool {
+        !self.without.contains(&actor)
+    }
+
+    /// Builds the genesis state in a store.
+    pub fn build(self, store: impl Into<Rc<MemoryBlockstore>>) -> Genesis {
+        let store = store.into();
+        let accounts_total: TokenAmount = self.accounts.iter().map(|(_, b)| b).sum();
+        let reward_total = if self.includes(Type::Reward) {
+            self.reward_balance.clone()
+        } else {
+            TokenAmount::zero()
+        };
+        let circulating_supply = &reward_total + &self.faucet_balance + &accounts_total;
+
+        let v = TestVM::new(Rc::clone(&store)).with_policy(self.policy.clone());
+        v.set_circulating_supply(circulating_supply.clone());
+
+        // system
+        let sys_st = SystemState::new(&store).unwrap();
+        let sys_head = v.put_store(&sys_st);
+        // delegate faucet and account funds to system so we can construct them by sending to key addresses
+        let sys_value = &self.faucet_balance + &accounts_total;
+        v.set_actor(
+            &SYSTEM_ACTOR_ADDR,
+            new_actor(*SYSTEM_ACTOR_CODE_ID, sys_head, 0, sys_value, None),
+        );
+
+        // init
+        let init_st = InitState::new(&store, "integration-test".to_string()).unwrap();
+        let init_head = v.put_store(&init_st);
+        v.set_actor(
+            &INIT_ACTOR_ADDR,
+            new_actor(*INIT_ACTOR_CODE_ID, init_head, 0, TokenAmount::zero(), None),
+        );
+
+        // reward
+        if self.includes(Type::Reward) {
+            let reward_head = v.put_store(&RewardState::new(StoragePower::zero()));
+            v.set_actor(
+                &REWARD_ACTOR_ADDR,
+                new_actor(*REWARD_ACTOR_CODE_ID, reward_head, 0, reward_total, None),
+            );
+        }
+
+        // cron, ticking whichever of power and market are present
+        if self.includes(Type::Cron) {
+            let mut builtin_entries = vec![];
+            if self.includes(Type::Power) {
+                builtin_entries.push(CronEntry {
+                    receiver: STORAGE_POWER_ACTOR_ADDR,
+                    method_num: MethodPower::OnEpochTickEnd as u64,
+                });
+            }
+            if self.includes(Type::Market) {
+                builtin_entries.push(CronEntry {
+                    receiver: STORAGE_MARKET_ACTOR_ADDR,
+                    method_num: MarketMethod::CronTick as u64,
+                });
+            }
+            let cron_head = v.put_store(&CronState { entries: builtin_entries });
+            v.set_actor(
+                &CRON_ACTOR_ADDR,
+                new_actor(*CRON_ACTOR_CODE_ID, cron_head, 0, TokenAmount::zero(), None),
+            );
+        }
+
+        // power
+        if self.includes(Type::Power) {
+            let power_head = v.put_store(&PowerState::new(&v.store).unwrap());
+            v.set_actor(
+                &STORAGE_POWER_ACTOR_ADDR,
+                new_actor(*POWER_ACTOR_CODE_ID, power_head, 0, TokenAmount::zero(), None),
+            );
+        }
+
+        // market
+        if self.includes(Type::Market) {
+            let market_head = v.put_store(&MarketState::new(&v.store).unwrap());
+            v.set_actor(
+                &STORAGE_MARKET_ACTOR_ADDR,
+                new_actor(*MARKET_ACTOR_CODE_ID, market_head, 0, TokenAmount::zero(), None),
+            );
+        }
+
+        // verifreg, with its root multisig
+        if self.includes(Type::VerifiedRegistry) {
+            let root_msig_addr = self.create_verifreg_root(&v);
+            let verifreg_head = v.put_store(&VerifRegState::new(&v.store, root_msig_addr).unwrap());
+            v.set_actor(
+                &VERIFIED_REGISTRY_ACTOR_ADDR,
+                new_actor(*VERIFREG_ACTOR_CODE_ID, verifreg_head, 0, TokenAmount::zero(), None),
+            );
+        }
+
+        // Ethereum Address Manager
+        if self.includes(Type::EAM) {
+            v.set_actor(
+                &EAM_ACTOR_ADDR,
+                new_actor(*EAM_ACTOR_CODE_ID, EMPTY_ARR_CID, 0, TokenAmount::zero(), None),
+            );
+        }
+
+        // datacap
+        if self.includes(Type::DataCap) {
+            let datacap_head =
+                v.put_store(&DataCapState::new(&v.store, VERIFIED_REGISTRY_ACTOR_ADDR).unwrap());
+            v.set_actor(
+                &DATACAP_TOKEN_ACTOR_ADDR,
+                new_actor(*DATACAP_TOKEN_ACTOR_CODE_ID, datacap_head, 0, TokenAmount::zero(), None),
+            );
+        }
+
+        // burnt funds
+        let burnt_funds_head =
+            v.put_store(&AccountState { address: BURNT_FUNDS_ACTOR_ADDR, auth_delegate: None });
+        v.set_actor(
+            &BURNT_FUNDS_ACTOR_ADDR,
+            new_actor(*ACCOUNT_ACTOR_CODE_ID, burnt_funds_head, 0, TokenAmount::zero(), None),
+        );
+
+        // create a faucet for setting up test accounts, then any other accounts
+        let faucet = Address::new_bls(FAUCET_ROOT_KEY).unwrap();
+        for (key, balance) in std::iter::once((&faucet, &self.faucet_balance))
+            .chain(self.accounts.iter().map(|(k, b)| (k, b)))
+        {
+            let res =
+                v.execute_message(&SYSTEM_ACTOR_ADDR, key, balance, METHOD_SEND, None).unwrap();
+            assert!(
+                res.code.is_success(),
+                "failed to create genesis account {}: {}",
+                key,
+                res.message
+            );
+        }
+
+        Genesis { store, state_root: v.checkpoint(), circulating_supply, policy: self.policy }
+    }
+
+    // Creates accounts for the verified registry root signers, and the root multisig over them.
+    fn create_verifreg_root(&self, v: &TestVM) -> Address {
+        let mut signers = vec![];
+        for key in &self.verifreg_root_signers {
+            v.execute_message(&INIT_ACTOR_ADDR, key, &TokenAmount::zero(), METHOD_SEND, None)
+                .unwrap();
+            signers.push(v.resolve_id_address(key).unwrap());
+        }
+        let msig_ctor_params = serialize(
+            &fil_actor_multisig::ConstructorParams {
+                signers,
+                num_approvals_threshold: self.verifreg_root_threshold,
+                unlock_duration: 0,
+                start_epoch: 0,
+            },
+            "multisig ctor params",
+        )
+        .unwrap();
+        let msig_ctor_ret: ExecReturn = v
+            .execute_message(
+                &SYSTEM_ACTOR_ADDR,
+                &INIT_ACTOR_ADDR,
+                &TokenAmount::zero(),
+                fil_actor_init::Method::Exec as u64,
+                Some(serialize_ok(&fil_actor_init::ExecParams {
+                    code_cid: *MULTISIG_ACTOR_CODE_ID,
+                    constructor_params: msig_ctor_params,
+                })),
+            )
+            .unwrap()
+            .ret
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        msig_ctor_ret.id_address
+    }
+}
+
+/// A genesis state, from which any number of independent test VMs may start.
+pub struct Genesis {
+    store: Rc<MemoryBlockstore>,
+    pub state_root: Cid,
+    pub circulating_supply: TokenAmount,
+    pub policy: Policy,
+}
+
+impl Genesis {
+    /// Returns a new VM at epoch zero in the genesis state.
+    /// VMs share the genesis store, but their states are independent since blocks are
+    /// immutable.
+    pub fn vm(&self) -> TestVM {
+        let v = TestVM::new(Rc::clone(&self.store)).with_policy(self.policy.clone());
+        v.state_root.replace(self.state_root);
+        v.set_circulating_supply(self.circulating_supply.clone());
+        v
+    }
+}
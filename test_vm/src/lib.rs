@@ -1,24 +1,11 @@
 use cid::Cid;
-use fil_actor_account::State as AccountState;
-use fil_actor_cron::{Entry as CronEntry, State as CronState};
-use fil_actor_datacap::State as DataCapState;
-use fil_actor_init::{ExecReturn, State as InitState};
-use fil_actor_market::{Method as MarketMethod, State as MarketState};
-use fil_actor_power::{Method as MethodPower, State as PowerState};
-use fil_actor_reward::State as RewardState;
-use fil_actor_system::State as SystemState;
-use fil_actor_verifreg::State as VerifRegState;
-use fil_actors_runtime::cbor::serialize;
+use fil_actor_evm::interpreter::trace::TraceStep;
+use fil_actor_init::State as InitState;
 use fil_actors_runtime::runtime::builtins::Type;
-use fil_actors_runtime::runtime::{Policy, Primitives, EMPTY_ARR_CID};
-use fil_actors_runtime::test_blockstores::MemoryBlockstore;
-use fil_actors_runtime::DATACAP_TOKEN_ACTOR_ADDR;
+use fil_actors_runtime::runtime::{Policy, Primitives};
+use fil_actors_runtime::test_blockstores::{BSStats, MemoryBlockstore};
 use fil_actors_runtime::{test_utils::*, Map2, DEFAULT_HAMT_CONFIG};
-use fil_actors_runtime::{
-    BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, EAM_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR,
-    STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
-    VERIFIED_REGISTRY_ACTOR_ADDR,
-};
+use fil_actors_runtime::{ActorError, CANONICAL_PARAMS_NETWORK_VERSION, INIT_ACTOR_ADDR};
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::CborStore;
@@ -28,23 +15,38 @@ use fvm_shared::bigint::Zero;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
-use fvm_shared::sector::StoragePower;
+use fvm_shared::upgrade::UpgradeInfo;
 use fvm_shared::version::NetworkVersion;
 use fvm_shared::{MethodNum, METHOD_SEND};
 use multihash_codetable::Code;
 use serde::ser;
 use std::cell::{RefCell, RefMut};
 use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
 use std::rc::Rc;
 use vm_api::trace::InvocationTrace;
-use vm_api::{new_actor, ActorState, MessageResult, MockPrimitives, VMError, VM};
+use vm_api::{ActorState, MessageResult, MockPrimitives, VMError, VM};
 
-use vm_api::util::{get_state, serialize_ok};
+use vm_api::util::get_state;
 
+mod car;
+pub use car::*;
 mod constants;
 pub use constants::*;
+mod evm_gas;
+pub use evm_gas::*;
+mod gas;
+pub use gas::*;
+mod genesis;
+pub use genesis::*;
 mod messaging;
 pub use messaging::*;
+mod metrics;
+pub use metrics::*;
+mod tipset;
+pub use tipset::*;
 
 /// An in-memory rust-execution VM for testing builtin-actors that yields sensible stack traces and debug info
 pub struct TestVM {
@@ -52,14 +54,27 @@ pub struct TestVM {
     pub store: Rc<MemoryBlockstore>,
     pub state_root: RefCell<Cid>,
     actors_dirty: RefCell<bool>,
-    actors_cache: RefCell<HashMap<Address, ActorState>>,
+    // Actors changed since the last checkpoint, with None for those deleted.
+    actors_cache: RefCell<HashMap<Address, Option<ActorState>>>,
     invocations: RefCell<Vec<InvocationTrace>>,
+    // Blockstore operations performed by each message's execution, in order.
+    io_stats: RefCell<Vec<BSStats>>,
+    // Gas accounting, enabled by installing a price list.
+    price_list: Option<PriceList>,
+    gas_limit: RefCell<u64>,
+    // Gas charged to the executing message, excluding blockstore access.
+    gas_charged: RefCell<u64>,
+    // Blockstore stats at the start of the executing message.
+    message_stats: RefCell<BSStats>,
+    gas_used: RefCell<Vec<u64>>,
+    metrics: Option<Rc<dyn VmMetrics>>,
     // MachineContext equivalents
     network_version: NetworkVersion,
     curr_epoch: RefCell<ChainEpoch>,
     circulating_supply: RefCell<TokenAmount>,
     base_fee: RefCell<TokenAmount>,
     timestamp: RefCell<u64>,
+    policy: Policy,
 }
 
 impl TestVM {
@@ -78,160 +93,123 @@ impl TestVM {
             circulating_supply: RefCell::new(TokenAmount::zero()),
             actors_dirty: RefCell::new(false),
             actors_cache: RefCell::new(HashMap::new()),
-            network_version: NetworkVersion::V16,
+            // Every message's params are decoded as strictly as on the latest network.
+            network_version: CANONICAL_PARAMS_NETWORK_VERSION,
             curr_epoch: RefCell::new(ChainEpoch::zero()),
             invocations: RefCell::new(vec![]),
+            io_stats: RefCell::new(vec![]),
+            price_list: None,
+            gas_limit: RefCell::new(BLOCK_GAS_LIMIT),
+            gas_charged: RefCell::new(0),
+            message_stats: RefCell::new(BSStats::default()),
+            gas_used: RefCell::new(vec![]),
+            metrics: None,
             base_fee: RefCell::new(TokenAmount::zero()),
             timestamp: RefCell::new(0),
+            policy: Policy::default(),
         }
     }
 
-    pub fn new_with_singletons(store: impl Into<Rc<MemoryBlockstore>>) -> TestVM {
-        let reward_total = TokenAmount::from_whole(1_100_000_000i64);
-        let faucet_total = TokenAmount::from_whole(1_000_000_000i64);
-
-        let store = store.into();
+    /// Replaces the policy observed by actors executing in this VM.
+    /// Invariant checks should be run against the same policy, available from `policy()`.
+    pub fn with_policy(mut self, policy: Policy) -> TestVM {
+        self.policy = policy;
+        self
+    }
 
-        let v = TestVM::new(Rc::clone(&store));
-        v.set_circulating_supply(&reward_total + &faucet_total);
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
 
-        // system
-        let sys_st = SystemState::new(&store).unwrap();
-        let sys_head = v.put_store(&sys_st);
-        let sys_value = faucet_total.clone(); // delegate faucet funds to system so we can construct faucet by sending to bls addr
-        v.set_actor(
-            &SYSTEM_ACTOR_ADDR,
-            new_actor(*SYSTEM_ACTOR_CODE_ID, sys_head, 0, sys_value, None),
-        );
+    /// Returns the blockstore reads and writes performed while executing each message since the
+    /// last call, in message order.
+    /// The counts include the VM's own access to the actors table, which is similar for
+    /// messages with similar call graphs, so are best compared between runs of the same scenario.
+    pub fn take_io_stats(&self) -> Vec<BSStats> {
+        self.io_stats.take()
+    }
 
-        // init
-        let init_st = InitState::new(&store, "integration-test".to_string()).unwrap();
-        let init_head = v.put_store(&init_st);
-        v.set_actor(
-            &INIT_ACTOR_ADDR,
-            new_actor(*INIT_ACTOR_CODE_ID, init_head, 0, TokenAmount::zero(), None),
-        );
+    /// Enables gas mode, in which each message is charged for its invocations, syscalls and
+    /// blockstore access according to the price list.
+    /// A message that exceeds the gas limit fails with SYS_OUT_OF_GAS and its state changes are
+    /// reverted. Execution isn't interrupted when gas runs out, but subsequent sends fail.
+    pub fn with_price_list(mut self, prices: PriceList) -> TestVM {
+        self.price_list = Some(prices);
+        self
+    }
 
-        // reward
+    /// Sets the gas limit for subsequent messages executed in gas mode.
+    pub fn set_gas_limit(&self, limit: u64) {
+        self.gas_limit.replace(limit);
+    }
 
-        let reward_head = v.put_store(&RewardState::new(StoragePower::zero()));
-        v.set_actor(
-            &REWARD_ACTOR_ADDR,
-            new_actor(*REWARD_ACTOR_CODE_ID, reward_head, 0, reward_total, None),
-        );
+    /// Returns the gas used by each message since the last call, in message order.
+    /// Gas used is zero for messages executed without a price list.
+    pub fn take_gas_used(&self) -> Vec<u64> {
+        self.gas_used.take()
+    }
 
-        // cron
-        let builtin_entries = vec![
-            CronEntry {
-                receiver: STORAGE_POWER_ACTOR_ADDR,
-                method_num: MethodPower::OnEpochTickEnd as u64,
-            },
-            CronEntry {
-                receiver: STORAGE_MARKET_ACTOR_ADDR,
-                method_num: MarketMethod::CronTick as u64,
-            },
-        ];
-        let cron_head = v.put_store(&CronState { entries: builtin_entries });
-        v.set_actor(
-            &CRON_ACTOR_ADDR,
-            new_actor(*CRON_ACTOR_CODE_ID, cron_head, 0, TokenAmount::zero(), None),
-        );
+    /// Installs a sink for metrics of subsequently applied messages and invoked methods.
+    /// The caller keeps a reference to the sink to read or export the metrics.
+    pub fn with_metrics(mut self, metrics: Rc<dyn VmMetrics>) -> TestVM {
+        self.metrics = Some(metrics);
+        self
+    }
 
-        // power
-        let power_head = v.put_store(&PowerState::new(&v.store).unwrap());
-        v.set_actor(
-            &STORAGE_POWER_ACTOR_ADDR,
-            new_actor(*POWER_ACTOR_CODE_ID, power_head, 0, TokenAmount::zero(), None),
-        );
+    pub(crate) fn report_invocation(&self, actor: Type, method: MethodNum, code: ExitCode) {
+        if let Some(metrics) = &self.metrics {
+            metrics.method_invoked(actor, method, code);
+        }
+    }
 
-        // market
-        let market_head = v.put_store(&MarketState::new(&v.store).unwrap());
-        v.set_actor(
-            &STORAGE_MARKET_ACTOR_ADDR,
-            new_actor(*MARKET_ACTOR_CODE_ID, market_head, 0, TokenAmount::zero(), None),
-        );
+    /// Starts recording the instructions executed by EVM contracts, discarding any recorded so far.
+    pub fn start_evm_trace(&self) {
+        fil_actor_evm::interpreter::trace::start();
+    }
 
-        // verifreg
-        // initialize verifreg root signer
-        v.execute_message(
-            &INIT_ACTOR_ADDR,
-            &Address::new_bls(VERIFREG_ROOT_KEY).unwrap(),
-            &TokenAmount::zero(),
-            METHOD_SEND,
-            None,
-        )
-        .unwrap();
-        let verifreg_root_signer =
-            v.resolve_id_address(&Address::new_bls(VERIFREG_ROOT_KEY).unwrap()).unwrap();
-        assert_eq!(TEST_VERIFREG_ROOT_SIGNER_ADDR, verifreg_root_signer);
-        // verifreg root msig
-        let msig_ctor_params = serialize(
-            &fil_actor_multisig::ConstructorParams {
-                signers: vec![verifreg_root_signer],
-                num_approvals_threshold: 1,
-                unlock_duration: 0,
-                start_epoch: 0,
-            },
-            "multisig ctor params",
-        )
-        .unwrap();
-        let msig_ctor_ret: ExecReturn = v
-            .execute_message(
-                &SYSTEM_ACTOR_ADDR,
-                &INIT_ACTOR_ADDR,
-                &TokenAmount::zero(),
-                fil_actor_init::Method::Exec as u64,
-                Some(serialize_ok(&fil_actor_init::ExecParams {
-                    code_cid: *MULTISIG_ACTOR_CODE_ID,
-                    constructor_params: msig_ctor_params,
-                })),
-            )
-            .unwrap()
-            .ret
-            .unwrap()
-            .deserialize()
-            .unwrap();
-        let root_msig_addr = msig_ctor_ret.id_address;
-        assert_eq!(TEST_VERIFREG_ROOT_ADDR, root_msig_addr);
-        // verifreg
-        let verifreg_head = v.put_store(&VerifRegState::new(&v.store, root_msig_addr).unwrap());
-        v.set_actor(
-            &VERIFIED_REGISTRY_ACTOR_ADDR,
-            new_actor(*VERIFREG_ACTOR_CODE_ID, verifreg_head, 0, TokenAmount::zero(), None),
-        );
+    /// Stops recording EVM instructions, returning those executed since the trace was started.
+    pub fn take_evm_trace(&self) -> Vec<TraceStep> {
+        fil_actor_evm::interpreter::trace::stop()
+    }
 
-        // Ethereum Address Manager
-        v.set_actor(
-            &EAM_ACTOR_ADDR,
-            new_actor(*EAM_ACTOR_CODE_ID, EMPTY_ARR_CID, 0, TokenAmount::zero(), None),
-        );
+    pub(crate) fn charge_gas(&self, price: impl FnOnce(&PriceList) -> u64) {
+        if let Some(prices) = &self.price_list {
+            *self.gas_charged.borrow_mut() += price(prices);
+        }
+    }
 
-        // datacap
-        let datacap_head =
-            v.put_store(&DataCapState::new(&v.store, VERIFIED_REGISTRY_ACTOR_ADDR).unwrap());
-        v.set_actor(
-            &DATACAP_TOKEN_ACTOR_ADDR,
-            new_actor(*DATACAP_TOKEN_ACTOR_CODE_ID, datacap_head, 0, TokenAmount::zero(), None),
-        );
+    /// Gas used by the executing message so far.
+    pub(crate) fn message_gas_used(&self) -> u64 {
+        match &self.price_list {
+            Some(prices) => {
+                let io = *self.store.stats.borrow() - *self.message_stats.borrow();
+                *self.gas_charged.borrow() + prices.io_gas(&io)
+            }
+            None => 0,
+        }
+    }
 
-        // burnt funds
-        let burnt_funds_head = v.put_store(&AccountState { address: BURNT_FUNDS_ACTOR_ADDR });
-        v.set_actor(
-            &BURNT_FUNDS_ACTOR_ADDR,
-            new_actor(*ACCOUNT_ACTOR_CODE_ID, burnt_funds_head, 0, TokenAmount::zero(), None),
-        );
+    pub(crate) fn gas_available(&self) -> u64 {
+        match &self.price_list {
+            Some(_) => self.gas_limit.borrow().saturating_sub(self.message_gas_used()),
+            None => u32::MAX.into(),
+        }
+    }
 
-        // create a faucet with 1 billion FIL for setting up test accounts
-        v.execute_message(
-            &SYSTEM_ACTOR_ADDR,
-            &Address::new_bls(FAUCET_ROOT_KEY).unwrap(),
-            &faucet_total,
-            METHOD_SEND,
-            None,
-        )
-        .unwrap();
+    pub(crate) fn out_of_gas(&self) -> bool {
+        self.price_list.is_some() && self.message_gas_used() > *self.gas_limit.borrow()
+    }
 
-        v.checkpoint();
+    /// Creates a VM in the default genesis state of [`GenesisBuilder`].
+    pub fn new_with_singletons(store: impl Into<Rc<MemoryBlockstore>>) -> TestVM {
+        let v = GenesisBuilder::new().build(store).vm();
+        let verifreg_root_signer =
+            v.resolve_id_address(&Address::new_bls(VERIFREG_ROOT_KEY).unwrap()).unwrap();
+        assert_eq!(TEST_VERIFREG_ROOT_SIGNER_ADDR, verifreg_root_signer);
+        assert_eq!(
+            TEST_FAUCET_ADDR,
+            v.resolve_id_address(&Address::new_bls(FAUCET_ROOT_KEY).unwrap()).unwrap()
+        );
         v
     }
 
@@ -252,7 +230,14 @@ impl TestVM {
             )
             .unwrap();
         for (addr, act) in self.actors_cache.borrow().iter() {
-            actors.set(addr.to_bytes().into(), act.clone()).unwrap();
+            match act {
+                Some(act) => {
+                    actors.set(addr.to_bytes().into(), act.clone()).unwrap();
+                }
+                None => {
+                    actors.delete(&BytesKey::from(addr.to_bytes())).unwrap();
+                }
+            }
         }
 
         self.state_root.replace(actors.flush().unwrap());
@@ -260,12 +245,87 @@ impl TestVM {
         *self.state_root.borrow()
     }
 
+    /// Removes an actor from the state tree at the next checkpoint. Its addresses remain
+    /// mapped by the init actor.
+    pub(crate) fn delete_actor(&self, key: &Address) {
+        self.actors_cache.borrow_mut().insert(*key, None);
+        self.actors_dirty.replace(true);
+    }
+
     pub fn rollback(&self, root: Cid) {
         self.actors_cache.replace(HashMap::new());
         self.state_root.replace(root);
         self.actors_dirty.replace(false);
     }
 
+    /// Writes the current state tree to a CAR file rooted at the state root, for inspection
+    /// with external tools after a test fails.
+    pub fn export_car(&self, path: impl AsRef<Path>) -> anyhow::Result<Cid> {
+        let root = self.checkpoint();
+        let mut out = BufWriter::new(File::create(path)?);
+        write_car(self.store.as_ref(), root, &mut out)?;
+        out.flush()?;
+        Ok(root)
+    }
+
+    /// Replaces an actor's code and invokes the new code's upgrade handler, as when an actor
+    /// upgrades itself. If the handler rejects the upgrade, the actor's code and state are
+    /// left unchanged.
+    pub fn upgrade_actor(
+        &self,
+        address: &Address,
+        new_code_cid: Cid,
+        params: Option<IpldBlock>,
+    ) -> MessageResult {
+        let id = self.resolve_id_address(address).expect("actor to upgrade not found");
+        let mut actor = self.actor(&id).expect("actor to upgrade not found");
+        let prior_root = self.checkpoint();
+        let old_code_cid = actor.code;
+        actor.code = new_code_cid;
+        self.set_actor(&id, actor);
+
+        let top = TopCtx {
+            originator_stable_addr: id,
+            originator_call_seq: 0,
+            new_actor_addr_count: RefCell::new(0),
+            circ_supply: TokenAmount::from_whole(1_000_000_000),
+        };
+        // The actor asks for its own upgrade, so is both the caller and receiver.
+        let msg = InternalMessage {
+            from: id.id().unwrap(),
+            to: id,
+            value: TokenAmount::zero(),
+            method: METHOD_SEND,
+            params,
+        };
+        let mut ctx = InvocationCtx {
+            v: self,
+            top,
+            msg,
+            allow_side_effects: RefCell::new(true),
+            caller_validated: RefCell::new(false),
+            read_only: false,
+            policy: &self.policy,
+            subinvocations: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
+            logs: RefCell::new(vec![]),
+        };
+        match ctx.invoke_upgrade(UpgradeInfo { old_code_cid }) {
+            Err(mut ae) => {
+                self.rollback(prior_root);
+                MessageResult {
+                    code: ae.exit_code(),
+                    message: ae.msg().to_string(),
+                    ret: ae.take_data(),
+                }
+            }
+            Ok(ret) => {
+                self.checkpoint();
+                MessageResult { code: ExitCode::OK, message: "OK".to_string(), ret }
+            }
+        }
+    }
+
     fn actor_map(&self) -> Map2<&MemoryBlockstore, Address, ActorState> {
         Map2::load(self.store.as_ref(), &self.checkpoint(), DEFAULT_HAMT_CONFIG, "actors").unwrap()
     }
@@ -322,11 +382,31 @@ impl VM for TestVM {
             allow_side_effects: RefCell::new(true),
             caller_validated: RefCell::new(false),
             read_only: false,
-            policy: &Policy::default(),
+            policy: &self.policy,
             subinvocations: RefCell::new(vec![]),
             events: RefCell::new(vec![]),
+            logs: RefCell::new(vec![]),
         };
-        let res = new_ctx.invoke();
+        let stats_before = *self.store.stats.borrow();
+        self.message_stats.replace(stats_before);
+        self.gas_charged.replace(0);
+        let params_len = new_ctx.msg.params.as_ref().map_or(0, |p| p.data.len() as u64);
+        self.charge_gas(|p| p.on_chain_message + params_len * p.on_chain_message_per_byte);
+        let mut res = new_ctx.invoke();
+        if self.out_of_gas() {
+            res = Err(ActorError::unchecked(
+                ExitCode::SYS_OUT_OF_GAS,
+                format!("message exceeded gas limit {}", self.gas_limit.borrow()),
+            ));
+        }
+        let io = *self.store.stats.borrow() - stats_before;
+        let gas_used = self.message_gas_used().min(*self.gas_limit.borrow());
+        self.io_stats.borrow_mut().push(io);
+        self.gas_used.borrow_mut().push(gas_used);
+        if let Some(metrics) = &self.metrics {
+            let code = res.as_ref().map_or_else(|e| e.exit_code(), |_| ExitCode::OK);
+            metrics.message_applied(&MessageMetrics { code, gas_used, io });
+        }
 
         let invoc = new_ctx.gather_trace(res.clone());
         RefMut::map(self.invocations.borrow_mut(), |invocs| {
@@ -376,19 +456,19 @@ impl VM for TestVM {
     fn actor(&self, address: &Address) -> Option<ActorState> {
         // check for inclusion in cache of changed actors
         if let Some(act) = self.actors_cache.borrow().get(address) {
-            return Some(act.clone());
+            return act.clone();
         }
         // go to persisted map
         let actors = self.actor_map();
         let actor = actors.get(address).unwrap().cloned();
         actor.iter().for_each(|a| {
-            self.actors_cache.borrow_mut().insert(*address, a.clone());
+            self.actors_cache.borrow_mut().insert(*address, Some(a.clone()));
         });
         actor
     }
 
     fn set_actor(&self, key: &Address, a: ActorState) {
-        self.actors_cache.borrow_mut().insert(*key, a);
+        self.actors_cache.borrow_mut().insert(*key, Some(a));
         self.actors_dirty.replace(true);
     }
 
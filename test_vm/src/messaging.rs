@@ -1,20 +1,6 @@
 use anyhow::anyhow;
 use cid::Cid;
-use fil_actor_account::Actor as AccountActor;
-use fil_actor_cron::Actor as CronActor;
-use fil_actor_datacap::Actor as DataCapActor;
-use fil_actor_eam::EamActor;
-use fil_actor_ethaccount::EthAccountActor;
-use fil_actor_evm::EvmContractActor;
-use fil_actor_init::{Actor as InitActor, State as InitState};
-use fil_actor_market::Actor as MarketActor;
-use fil_actor_miner::Actor as MinerActor;
-use fil_actor_multisig::Actor as MultisigActor;
-use fil_actor_paych::Actor as PaychActor;
-use fil_actor_power::Actor as PowerActor;
-use fil_actor_reward::Actor as RewardActor;
-use fil_actor_system::Actor as SystemActor;
-use fil_actor_verifreg::Actor as VerifregActor;
+use fil_actor_init::State as InitState;
 use multihash_codetable::Code;
 
 use fil_actors_runtime::runtime::builtins::Type;
@@ -22,7 +8,7 @@ use fil_actors_runtime::runtime::{
     ActorCode, DomainSeparationTag, MessageInfo, Policy, Primitives, Runtime, RuntimePolicy,
     EMPTY_ARR_CID,
 };
-use fil_actors_runtime::{actor_error, SendError};
+use fil_actors_runtime::{actor_error, builtin_actors, SendError};
 use fil_actors_runtime::{test_utils::*, SYSTEM_ACTOR_ID};
 use fil_actors_runtime::{ActorError, INIT_ACTOR_ADDR};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
@@ -39,7 +25,7 @@ use fvm_shared::crypto::signature::{
     Signature, SECP_PUB_LEN, SECP_SIG_LEN, SECP_SIG_MESSAGE_HASH_SIZE,
 };
 use fvm_shared::econ::TokenAmount;
-use fvm_shared::error::ExitCode;
+use fvm_shared::error::{ErrorNumber, ExitCode};
 use fvm_shared::event::ActorEvent;
 use fvm_shared::piece::PieceInfo;
 
@@ -50,13 +36,14 @@ use fvm_shared::sector::{
 };
 
 use fvm_shared::sys::SendFlags;
+use fvm_shared::upgrade::UpgradeInfo;
 use fvm_shared::version::NetworkVersion;
 use fvm_shared::{ActorID, MethodNum, Response, IPLD_RAW, METHOD_CONSTRUCTOR, METHOD_SEND};
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::cell::{RefCell, RefMut};
-use vm_api::trace::{EmittedEvent, InvocationTrace};
+use vm_api::trace::{ActorLog, EmittedEvent, InvocationTrace};
 use vm_api::util::get_state;
 use vm_api::{new_actor, ActorState, VM};
 
@@ -114,6 +101,7 @@ pub struct InvocationCtx<'invocation> {
     pub policy: &'invocation Policy,
     pub subinvocations: RefCell<Vec<InvocationTrace>>,
     pub events: RefCell<Vec<EmittedEvent>>,
+    pub logs: RefCell<Vec<ActorLog>>,
 }
 
 impl<'invocation> InvocationCtx<'invocation> {
@@ -122,9 +110,14 @@ impl<'invocation> InvocationCtx<'invocation> {
         target: &Address,
     ) -> Result<(ActorState, Address), ActorError> {
         if let Some(a) = self.v.resolve_id_address(target) {
-            if let Some(act) = self.v.actor(&a) {
-                return Ok((act, a));
-            }
+            // An address mapped to an ID with no actor is that of a deleted actor.
+            return match self.v.actor(&a) {
+                Some(act) => Ok((act, a)),
+                None => Err(ActorError::unchecked(
+                    ExitCode::SYS_INVALID_RECEIVER,
+                    format!("no actor at address {} ({})", target, a),
+                )),
+            };
         };
 
         // Address does not yet exist, create it
@@ -179,6 +172,7 @@ impl<'invocation> InvocationCtx<'invocation> {
                 policy: self.policy,
                 subinvocations: RefCell::new(vec![]),
                 events: RefCell::new(vec![]),
+                logs: RefCell::new(vec![]),
             };
             if is_account {
                 new_ctx.create_actor(*ACCOUNT_ACTOR_CODE_ID, target_id, None).unwrap();
@@ -221,6 +215,7 @@ impl<'invocation> InvocationCtx<'invocation> {
             exit_code: code,
             subinvocations: self.subinvocations.take(),
             events: self.events.take(),
+            logs: self.logs.take(),
         }
     }
 
@@ -230,6 +225,7 @@ impl<'invocation> InvocationCtx<'invocation> {
 
     pub fn invoke(&mut self) -> Result<Option<IpldBlock>, ActorError> {
         let prior_root = self.v.checkpoint();
+        self.v.charge_gas(|p| p.send);
 
         // Transfer funds
         let mut from_actor = self.v.actor(&Address::new_id(self.msg.from)).unwrap();
@@ -271,38 +267,65 @@ impl<'invocation> InvocationCtx<'invocation> {
         // call target actor
         let to_actor = self.v.actor(&to_addr).unwrap();
         let params = self.msg.params.clone();
-        let mut res = match ACTOR_TYPES.get(&to_actor.code).expect("Target actor is not a builtin")
-        {
-            Type::Account => AccountActor::invoke_method(self, self.msg.method, params),
-            Type::Cron => CronActor::invoke_method(self, self.msg.method, params),
-            Type::Init => InitActor::invoke_method(self, self.msg.method, params),
-            Type::Market => MarketActor::invoke_method(self, self.msg.method, params),
-            Type::Miner => MinerActor::invoke_method(self, self.msg.method, params),
-            Type::Multisig => MultisigActor::invoke_method(self, self.msg.method, params),
-            Type::System => SystemActor::invoke_method(self, self.msg.method, params),
-            Type::Reward => RewardActor::invoke_method(self, self.msg.method, params),
-            Type::Power => PowerActor::invoke_method(self, self.msg.method, params),
-            Type::PaymentChannel => PaychActor::invoke_method(self, self.msg.method, params),
-            Type::VerifiedRegistry => VerifregActor::invoke_method(self, self.msg.method, params),
-            Type::DataCap => DataCapActor::invoke_method(self, self.msg.method, params),
-            Type::Placeholder => {
-                Err(ActorError::unhandled_message("placeholder actors only handle method 0".into()))
-            }
-            Type::EVM => EvmContractActor::invoke_method(self, self.msg.method, params),
-            Type::EAM => EamActor::invoke_method(self, self.msg.method, params),
-            Type::EthAccount => EthAccountActor::invoke_method(self, self.msg.method, params),
-        };
+        let actor_type = *ACTOR_TYPES.get(&to_actor.code).expect("Target actor is not a builtin");
+        let mut res = invoke_native(self, actor_type, self.msg.method, params);
         if res.is_ok() && !*self.caller_validated.borrow() {
             res = Err(actor_error!(assertion_failed, "failed to validate caller"));
         }
         if res.is_err() {
             self.v.rollback(prior_root)
         };
+        let code = res.as_ref().map_or_else(|e| e.exit_code(), |_| ExitCode::OK);
+        self.v.report_invocation(actor_type, self.msg.method, code);
 
         res
     }
+
+    /// Invokes the upgrade handler of the target actor's (new) code.
+    /// The caller is responsible for rolling back the code change if the upgrade is rejected.
+    pub fn invoke_upgrade(&mut self, info: UpgradeInfo) -> Result<Option<IpldBlock>, ActorError> {
+        let to_actor = self.v.actor(&self.msg.to).unwrap();
+        let params = self.msg.params.clone();
+        let actor_type = *ACTOR_TYPES.get(&to_actor.code).expect("Target actor is not a builtin");
+        upgrade_native(self, actor_type, info, params)
+    }
+}
+
+// Dispatches to each builtin actor's native code.
+macro_rules! native_dispatch {
+    ($($typ:ident => $actor:ty,)*) => {
+        fn invoke_native(
+            rt: &InvocationCtx,
+            actor_type: Type,
+            method: MethodNum,
+            params: Option<IpldBlock>,
+        ) -> Result<Option<IpldBlock>, ActorError> {
+            match actor_type {
+                $(Type::$typ => <$actor as ActorCode>::invoke_method(rt, method, params),)*
+                Type::Placeholder => Err(ActorError::unhandled_message(
+                    "placeholder actors only handle method 0".into(),
+                )),
+            }
+        }
+
+        fn upgrade_native(
+            rt: &InvocationCtx,
+            actor_type: Type,
+            info: UpgradeInfo,
+            params: Option<IpldBlock>,
+        ) -> Result<Option<IpldBlock>, ActorError> {
+            match actor_type {
+                $(Type::$typ => <$actor as ActorCode>::upgrade(rt, info, params),)*
+                Type::Placeholder => {
+                    Err(ActorError::forbidden("placeholder actors cannot be upgraded".into()))
+                }
+            }
+        }
+    };
 }
 
+builtin_actors!(native_dispatch);
+
 impl<'invocation> Runtime for InvocationCtx<'invocation> {
     type Blockstore = Rc<MemoryBlockstore>;
 
@@ -503,6 +526,16 @@ impl<'invocation> Runtime for InvocationCtx<'invocation> {
         if !*self.allow_side_effects.borrow() {
             return Ok(Response { exit_code: ExitCode::SYS_ASSERTION_FAILED, return_data: None });
         }
+        if self.v.out_of_gas() {
+            return Ok(Response { exit_code: ExitCode::SYS_OUT_OF_GAS, return_data: None });
+        }
+
+        // Like the FVM, fail the syscall if the receiver has an ID but no actor.
+        if let Some(id) = self.v.resolve_id_address(to) {
+            if self.v.actor(&id).is_none() {
+                return Err(SendError(ErrorNumber::NotFound));
+            }
+        }
 
         let from_id = self.resolve_address(&self.to()).unwrap();
 
@@ -517,6 +550,7 @@ impl<'invocation> Runtime for InvocationCtx<'invocation> {
             policy: self.policy,
             subinvocations: RefCell::new(vec![]),
             events: RefCell::new(vec![]),
+            logs: RefCell::new(vec![]),
         };
         let res = new_ctx.invoke();
         let invoc = new_ctx.gather_trace(res.clone());
@@ -611,7 +645,26 @@ impl<'invocation> Runtime for InvocationCtx<'invocation> {
     }
 
     fn delete_actor(&self) -> Result<(), ActorError> {
-        panic!("TODO implement me")
+        if !*self.allow_side_effects.borrow() {
+            return Err(
+                actor_error!(assertion_failed; "delete_actor is not allowed during transaction"),
+            );
+        }
+        if self.read_only() {
+            return Err(ActorError::unchecked(
+                ExitCode::USR_READ_ONLY,
+                "cannot delete actor in read-only mode".into(),
+            ));
+        }
+        let addr = self.to();
+        let balance = self.v.actor(&addr).unwrap().balance;
+        if !balance.is_zero() {
+            return Err(
+                actor_error!(illegal_state; "cannot delete actor {} with balance {}", addr, balance),
+            );
+        }
+        self.v.delete_actor(&addr);
+        Ok(())
     }
 
     fn resolve_builtin_actor_type(&self, code_id: &Cid) -> Option<Type> {
@@ -626,7 +679,9 @@ impl<'invocation> Runtime for InvocationCtx<'invocation> {
         self.top.circ_supply.clone()
     }
 
-    fn charge_gas(&self, _name: &'static str, _compute: i64) {}
+    fn charge_gas(&self, _name: &'static str, compute: i64) {
+        self.v.charge_gas(|_| compute as u64);
+    }
 
     fn base_fee(&self) -> TokenAmount {
         TokenAmount::zero()
@@ -637,7 +692,7 @@ impl<'invocation> Runtime for InvocationCtx<'invocation> {
     }
 
     fn gas_available(&self) -> u64 {
-        u32::MAX.into()
+        self.v.gas_available()
     }
 
     fn tipset_timestamp(&self) -> u64 {
@@ -649,6 +704,13 @@ impl<'invocation> Runtime for InvocationCtx<'invocation> {
     }
 
     fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError> {
+        if self.read_only() {
+            return Err(ActorError::unchecked(
+                ExitCode::USR_READ_ONLY,
+                "cannot emit events in read-only mode".to_string(),
+            ));
+        }
+        self.v.charge_gas(|p| p.emit_event);
         self.events
             .borrow_mut()
             .push(EmittedEvent { emitter: self.msg.to.id().unwrap(), event: event.clone() });
@@ -658,6 +720,11 @@ impl<'invocation> Runtime for InvocationCtx<'invocation> {
     fn read_only(&self) -> bool {
         self.read_only
     }
+
+    fn log(&self, level: log::Level, msg: &str) {
+        log::log!(level, "{}: {}", self.msg.to, msg);
+        self.logs.borrow_mut().push(ActorLog { level, msg: msg.to_string() });
+    }
 }
 
 impl Primitives for InvocationCtx<'_> {
@@ -667,10 +734,12 @@ impl Primitives for InvocationCtx<'_> {
         signer: &Address,
         plaintext: &[u8],
     ) -> Result<(), anyhow::Error> {
+        self.v.charge_gas(|p| p.verify_signature);
         self.v.primitives().verify_signature(signature, signer, plaintext)
     }
 
     fn hash_blake2b(&self, data: &[u8]) -> [u8; 32] {
+        self.v.charge_gas(|p| p.hash);
         self.v.primitives().hash_blake2b(data)
     }
 
@@ -679,14 +748,17 @@ impl Primitives for InvocationCtx<'_> {
         proof_type: RegisteredSealProof,
         pieces: &[PieceInfo],
     ) -> Result<Cid, anyhow::Error> {
+        self.v.charge_gas(|p| p.compute_unsealed_sector_cid);
         self.v.primitives().compute_unsealed_sector_cid(proof_type, pieces)
     }
 
     fn hash(&self, hasher: SupportedHashes, data: &[u8]) -> Vec<u8> {
+        self.v.charge_gas(|p| p.hash);
         self.v.primitives().hash(hasher, data)
     }
 
     fn hash_64(&self, hasher: SupportedHashes, data: &[u8]) -> ([u8; 64], usize) {
+        self.v.charge_gas(|p| p.hash);
         self.v.primitives().hash_64(hasher, data)
     }
 
@@ -695,10 +767,12 @@ impl Primitives for InvocationCtx<'_> {
         hash: &[u8; SECP_SIG_MESSAGE_HASH_SIZE],
         signature: &[u8; SECP_SIG_LEN],
     ) -> Result<[u8; SECP_PUB_LEN], anyhow::Error> {
+        self.v.charge_gas(|p| p.recover_secp_public_key);
         self.v.primitives().recover_secp_public_key(hash, signature)
     }
 
     fn verify_post(&self, verify_info: &WindowPoStVerifyInfo) -> Result<(), anyhow::Error> {
+        self.v.charge_gas(|p| p.verify_post);
         for proof in &verify_info.proofs {
             if proof.proof_bytes.eq(&TEST_VM_INVALID_POST.as_bytes().to_vec()) {
                 return Err(anyhow!("invalid proof"));
@@ -710,25 +784,29 @@ impl Primitives for InvocationCtx<'_> {
 
     fn verify_consensus_fault(
         &self,
-        _h1: &[u8],
-        _h2: &[u8],
-        _extra: &[u8],
+        h1: &[u8],
+        h2: &[u8],
+        extra: &[u8],
     ) -> Result<Option<ConsensusFault>, anyhow::Error> {
-        Ok(None)
+        self.v.charge_gas(|p| p.verify_consensus_fault);
+        self.v.primitives().verify_consensus_fault(h1, h2, extra)
     }
 
     fn batch_verify_seals(&self, batch: &[SealVerifyInfo]) -> anyhow::Result<Vec<bool>> {
+        self.v.charge_gas(|p| p.verify_seal * batch.len() as u64);
         Ok(vec![true; batch.len()]) // everyone wins
     }
 
     fn verify_aggregate_seals(
         &self,
-        _aggregate: &AggregateSealVerifyProofAndInfos,
+        aggregate: &AggregateSealVerifyProofAndInfos,
     ) -> Result<(), anyhow::Error> {
+        self.v.charge_gas(|p| p.verify_aggregate_seal * aggregate.infos.len() as u64);
         Ok(())
     }
 
     fn verify_replica_update(&self, replica: &ReplicaUpdateInfo) -> Result<(), anyhow::Error> {
+        self.v.charge_gas(|p| p.verify_replica_update);
         self.v.primitives().verify_replica_update(replica)
     }
 }
@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::test_blockstores::BSStats;
+use fvm_shared::error::ExitCode;
+use fvm_shared::MethodNum;
+
+/// A sink for metrics of the test VM's execution, installed with `TestVM::with_metrics`.
+/// Implementations that don't track some metric can leave its method as the default no-op.
+pub trait VmMetrics {
+    /// Called once each top-level message has been applied, whether or not it succeeded.
+    fn message_applied(&self, _message: &MessageMetrics) {}
+
+    /// Called once each actor method invoked returns, including methods invoked by internal
+    /// sends. Plain value transfers invoke no method so aren't reported.
+    fn method_invoked(&self, _actor: Type, _method: MethodNum, _code: ExitCode) {}
+}
+
+/// Metrics of the application of one top-level message.
+#[derive(Clone, Copy, Debug)]
+pub struct MessageMetrics {
+    pub code: ExitCode,
+    /// Gas used, being zero unless the VM has a price list.
+    pub gas_used: u64,
+    /// Blockstore operations performed, including those of the VM itself.
+    /// State written by a message that fails remains in the blockstore, so is included.
+    pub io: BSStats,
+}
+
+/// Counts of messages or invocations, by whether they exited successfully.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResultCounts {
+    pub ok: u64,
+    pub failed: u64,
+}
+
+/// Accumulates metrics in memory, for export in the Prometheus text format.
+#[derive(Debug, Default)]
+pub struct CountingMetrics {
+    messages: RefCell<ResultCounts>,
+    gas_used: RefCell<u64>,
+    io: RefCell<BSStats>,
+    invocations: RefCell<BTreeMap<(Type, MethodNum), ResultCounts>>,
+}
+
+impl CountingMetrics {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The number of messages applied, by success.
+    pub fn messages(&self) -> ResultCounts {
+        *self.messages.borrow()
+    }
+
+    pub fn gas_used(&self) -> u64 {
+        *self.gas_used.borrow()
+    }
+
+    /// Blockstore operations performed by all messages.
+    pub fn io(&self) -> BSStats {
+        *self.io.borrow()
+    }
+
+    /// The number of invocations of an actor method, by success.
+    pub fn invocations(&self, actor: Type, method: MethodNum) -> ResultCounts {
+        self.invocations.borrow().get(&(actor, method)).copied().unwrap_or_default()
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format, with names prefixed
+    /// by `test_vm_`. Invocations are labelled by actor type name and method number.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let messages = self.messages();
+        counter(&mut out, "messages_applied_total", "Top-level messages applied.");
+        writeln!(out, "test_vm_messages_applied_total{{result=\"ok\"}} {}", messages.ok).unwrap();
+        writeln!(out, "test_vm_messages_applied_total{{result=\"failed\"}} {}", messages.failed)
+            .unwrap();
+
+        let io = self.io();
+        for (name, help, value) in [
+            ("gas_used_total", "Gas used by messages.", self.gas_used()),
+            ("blockstore_reads_total", "Blocks read from the blockstore.", io.r as u64),
+            ("blockstore_writes_total", "Blocks written to the blockstore.", io.w as u64),
+            ("blockstore_read_bytes_total", "Bytes read from the blockstore.", io.br as u64),
+            ("state_written_bytes_total", "Bytes written to the blockstore.", io.bw as u64),
+        ] {
+            counter(&mut out, name, help);
+            writeln!(out, "test_vm_{} {}", name, value).unwrap();
+        }
+
+        counter(&mut out, "method_invocations_total", "Actor methods invoked.");
+        for ((actor, method), counts) in self.invocations.borrow().iter() {
+            for (result, count) in [("ok", counts.ok), ("failed", counts.failed)] {
+                if count > 0 {
+                    writeln!(
+                        out,
+                        "test_vm_method_invocations_total{{actor=\"{}\",method=\"{}\",result=\"{}\"}} {}",
+                        actor.name(),
+                        method,
+                        result,
+                        count
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        out
+    }
+}
+
+fn counter(out: &mut String, name: &str, help: &str) {
+    writeln!(out, "# HELP test_vm_{} {}", name, help).unwrap();
+    writeln!(out, "# TYPE test_vm_{} counter", name).unwrap();
+}
+
+fn count(counts: &mut ResultCounts, code: ExitCode) {
+    if code.is_success() {
+        counts.ok += 1;
+    } else {
+        counts.failed += 1;
+    }
+}
+
+impl VmMetrics for CountingMetrics {
+    fn message_applied(&self, message: &MessageMetrics) {
+        count(&mut self.messages.borrow_mut(), message.code);
+        *self.gas_used.borrow_mut() += message.gas_used;
+        let mut io = self.io.borrow_mut();
+        io.r += message.io.r;
+        io.w += message.io.w;
+        io.br += message.io.br;
+        io.bw += message.io.bw;
+    }
+
+    fn method_invoked(&self, actor: Type, method: MethodNum, code: ExitCode) {
+        count(self.invocations.borrow_mut().entry((actor, method)).or_default(), code);
+    }
+}
@@ -0,0 +1,91 @@
+use cid::Cid;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::MethodNum;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use vm_api::{MessageResult, VM};
+
+use crate::TestVM;
+
+/// A message to apply as one of a tipset's, with [`TestVM::apply_tipset_in_any_order`].
+#[derive(Clone)]
+pub struct TipsetMessage {
+    pub from: Address,
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: Option<IpldBlock>,
+}
+
+impl TestVM {
+    /// Applies messages which ought to be independent of one another, first in the order given
+    /// and then in `orderings - 1` further orders shuffled from `seed`, starting each time from
+    /// the same state. Panics unless every order yields the same message results and state root,
+    /// flushing out order dependence in actor logic.
+    ///
+    /// Messages from the same sender, or creating actors, are not independent, since their
+    /// sequence numbers and new actor IDs depend on order.
+    /// The VM is left in the state after the last order, with the traces of that order only.
+    /// Returns the results in the order the messages were given.
+    pub fn apply_tipset_in_any_order(
+        &self,
+        messages: &[TipsetMessage],
+        orderings: usize,
+        seed: u64,
+    ) -> Vec<MessageResult> {
+        assert!(orderings > 0, "at least one ordering is required");
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let start_root = self.checkpoint();
+        let traces_len = self.invocations.borrow().len();
+        let io_stats_len = self.io_stats.borrow().len();
+        let gas_used_len = self.gas_used.borrow().len();
+
+        let mut order: Vec<usize> = (0..messages.len()).collect();
+        let mut expected: Option<(Vec<MessageResult>, Cid, Vec<usize>)> = None;
+        for i in 0..orderings {
+            if i > 0 {
+                order.shuffle(&mut rng);
+                self.rollback(start_root);
+                self.invocations.borrow_mut().truncate(traces_len);
+                self.io_stats.borrow_mut().truncate(io_stats_len);
+                self.gas_used.borrow_mut().truncate(gas_used_len);
+            }
+
+            let mut results: Vec<Option<MessageResult>> = vec![None; messages.len()];
+            for &idx in &order {
+                let m = &messages[idx];
+                let res = self
+                    .execute_message(&m.from, &m.to, &m.value, m.method, m.params.clone())
+                    .unwrap();
+                results[idx] = Some(res);
+            }
+            let results: Vec<MessageResult> = results.into_iter().map(Option::unwrap).collect();
+            let root = self.checkpoint();
+
+            match &expected {
+                None => expected = Some((results, root, order.clone())),
+                Some((first_results, first_root, first_order)) => {
+                    for (idx, (first, res)) in first_results.iter().zip(&results).enumerate() {
+                        assert!(
+                            first.code == res.code && first.ret == res.ret,
+                            "message {idx} result differs between orders {first_order:?} and \
+                            {order:?}: {} '{}' vs {} '{}'",
+                            first.code,
+                            first.message,
+                            res.code,
+                            res.message
+                        );
+                    }
+                    assert_eq!(
+                        *first_root, root,
+                        "state root differs between orders {first_order:?} and {order:?}"
+                    );
+                }
+            }
+        }
+        expected.unwrap().0
+    }
+}
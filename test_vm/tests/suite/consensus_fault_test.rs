@@ -0,0 +1,19 @@
+use fil_actors_integration_tests::tests::{
+    report_consensus_fault_test, report_invalid_consensus_fault_test,
+};
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use test_vm::TestVM;
+
+#[test]
+fn report_consensus_fault() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    report_consensus_fault_test(&v);
+}
+
+#[test]
+fn report_invalid_consensus_fault() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    report_invalid_consensus_fault_test(&v);
+}
@@ -0,0 +1,136 @@
+//! Calibrates the gas used by contracts in the test VM against their gas on Ethereum.
+//! The test VM charges for syscalls and state access as the FVM does, so these fail when
+//! the interpreter's use of them drifts, e.g. by loading state more than it needs to.
+
+use fil_actor_evm::interpreter::trace::TraceStep;
+use fil_actors_evm_shared::address::EthAddress;
+use fil_actors_integration_tests::util::create_accounts;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::EAM_ACTOR_ADDR;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::BytesDe;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::MethodNum;
+use num_traits::Zero;
+use test_vm::{
+    ethereum_trace_gas, PriceList, TestVM, ETHEREUM_CREATE_GAS, ETHEREUM_TRANSACTION_GAS,
+};
+use vm_api::util::serialize_ok;
+use vm_api::VM;
+
+/// The bounds of test VM gas per unit of Ethereum gas, by scenario.
+/// Each is within a quarter of that measured when the scenario was calibrated.
+/// Creating an actor reads and writes much more state than creating a contract on Ethereum.
+const CALIBRATION: &[(&str, f64, f64)] = &[
+    ("simplecoin deploy", 141.0, 235.0),
+    ("simplecoin send", 31.0, 52.0),
+    ("simplecoin balance", 45.0, 76.0),
+];
+
+struct GasRun {
+    v: TestVM,
+    sender: Address,
+    ratios: Vec<(&'static str, f64)>,
+}
+
+impl GasRun {
+    fn new() -> Self {
+        let v = TestVM::new_with_singletons(MemoryBlockstore::new())
+            .with_price_list(PriceList::default());
+        let sender = create_accounts(&v, 1, &TokenAmount::from_whole(10_000))[0];
+        GasRun { v, sender, ratios: vec![] }
+    }
+
+    /// Executes a message, recording its gas relative to Ethereum's.
+    fn execute(
+        &mut self,
+        scenario: &'static str,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        create: bool,
+    ) -> Option<IpldBlock> {
+        self.v.take_gas_used();
+        self.v.start_evm_trace();
+        let res =
+            self.v.execute_message(&self.sender, to, &TokenAmount::zero(), method, params).unwrap();
+        let trace: Vec<TraceStep> = self.v.take_evm_trace();
+        assert!(res.code.is_success(), "{} failed: {}", scenario, res.message);
+
+        let mut ethereum_gas = ETHEREUM_TRANSACTION_GAS + ethereum_trace_gas(&trace);
+        if create {
+            ethereum_gas += ETHEREUM_CREATE_GAS;
+        }
+        let gas: u64 = self.v.take_gas_used().iter().sum();
+        self.ratios.push((scenario, gas as f64 / ethereum_gas as f64));
+        res.ret
+    }
+
+    fn check(&self) {
+        for (scenario, ratio) in &self.ratios {
+            let (_, low, high) = CALIBRATION.iter().find(|(s, ..)| s == scenario).unwrap();
+            assert!(
+                (*low..=*high).contains(ratio),
+                "{} used {:.2} gas per Ethereum gas, outside the calibrated {}..{}",
+                scenario,
+                ratio,
+                low,
+                high
+            );
+        }
+    }
+}
+
+/// ABI-encodes a call, with each argument a 32-byte word.
+fn calldata(selector: [u8; 4], args: &[[u8; 32]]) -> Vec<u8> {
+    let mut data = selector.to_vec();
+    args.iter().for_each(|arg| data.extend_from_slice(arg));
+    data
+}
+
+fn word(bytes: &[u8]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(bytes);
+    word
+}
+
+#[test]
+fn simplecoin_gas_is_calibrated() {
+    let mut run = GasRun::new();
+    let bytecode =
+        hex::decode(include_str!("../../../actors/evm/tests/contracts/simplecoin.hex")).unwrap();
+    let ret = run.execute(
+        "simplecoin deploy",
+        &EAM_ACTOR_ADDR,
+        fil_actor_eam::Method::CreateExternal as u64,
+        Some(serialize_ok(&fil_actor_eam::CreateExternalParams(bytecode))),
+        true,
+    );
+    let created: fil_actor_eam::CreateExternalReturn = ret.unwrap().deserialize().unwrap();
+    let contract = Address::new_id(created.actor_id);
+
+    let receiver = EthAddress::from_id(1000);
+    let invoke = |input: Vec<u8>| Some(serialize_ok(&fvm_ipld_encoding::BytesSer(&input)));
+    // sendCoin(address,uint256)
+    let ret = run.execute(
+        "simplecoin send",
+        &contract,
+        fil_actor_evm::Method::InvokeContract as u64,
+        invoke(calldata([0x90, 0xb9, 0x8a, 0x11], &[word(&receiver.0), word(&[100])])),
+        false,
+    );
+    let BytesDe(sufficient) = ret.unwrap().deserialize().unwrap();
+    assert_eq!(word(&[1]).to_vec(), sufficient);
+    // getBalance(address)
+    let ret = run.execute(
+        "simplecoin balance",
+        &contract,
+        fil_actor_evm::Method::InvokeContract as u64,
+        invoke(calldata([0xf8, 0xb2, 0xcb, 0x4f], &[word(&receiver.0)])),
+        false,
+    );
+    let BytesDe(balance) = ret.unwrap().deserialize().unwrap();
+    assert_eq!(word(&[100]).to_vec(), balance);
+    run.check();
+}
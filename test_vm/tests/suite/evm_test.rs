@@ -1,10 +1,19 @@
+use fil_actor_evm::interpreter::opcodes;
+use fil_actors_evm_shared::uints::U256;
 use fil_actors_integration_tests::tests::{
     evm_call_test, evm_create_test, evm_delegatecall_test, evm_empty_initcode_test,
-    evm_eth_create_external_test, evm_init_revert_data_test, evm_staticcall_delegatecall_test,
-    evm_staticcall_test, evm_transient_nested_test, evm_transient_reentry_test,
+    evm_eth_create_external_test, evm_init_revert_data_test, evm_proxy_test,
+    evm_staticcall_delegatecall_test, evm_staticcall_test, evm_transient_nested_test,
+    evm_transient_reentry_test,
 };
+use fil_actors_integration_tests::util::create_accounts;
 use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::EAM_ACTOR_ADDR;
+use fvm_shared::econ::TokenAmount;
+use num_traits::Zero;
 use test_vm::TestVM;
+use vm_api::util::serialize_ok;
+use vm_api::VM;
 
 #[test]
 fn evm_call() {
@@ -54,6 +63,13 @@ fn evm_staticcall_delegatecall() {
     evm_staticcall_delegatecall_test(&v);
 }
 
+#[test]
+fn evm_proxy() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    evm_proxy_test(&v);
+}
+
 #[test]
 fn evm_init_revert_data() {
     let store = MemoryBlockstore::new();
@@ -74,3 +90,31 @@ fn evm_transient_reentry() {
     let v = TestVM::new_with_singletons(store);
     evm_transient_reentry_test(&v);
 }
+
+#[test]
+fn evm_trace_records_instructions() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let account = create_accounts(&v, 1, &TokenAmount::from_whole(10_000))[0];
+
+    // PUSH1 0x42; PUSH1 0x0; MSTORE; PUSH1 0x20; PUSH1 0x0; REVERT
+    let initcode = vec![0x60, 0x42, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xfd];
+    v.start_evm_trace();
+    v.execute_message(
+        &account,
+        &EAM_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        fil_actor_eam::Method::CreateExternal as u64,
+        Some(serialize_ok(&fil_actor_eam::CreateExternalParams(initcode))),
+    )
+    .unwrap();
+    let trace = v.take_evm_trace();
+
+    let names: Vec<_> = trace.iter().map(|s| opcodes::name(s.opcode).unwrap()).collect();
+    assert_eq!(vec!["PUSH1", "PUSH1", "MSTORE", "PUSH1", "PUSH1", "REVERT"], names);
+    assert_eq!(vec![0, 2, 4, 5, 7, 9], trace.iter().map(|s| s.pc).collect::<Vec<_>>());
+    assert_eq!(vec![U256::from(0x42), U256::zero()], trace[2].stack);
+
+    // Nothing is recorded once the trace is taken.
+    assert!(v.take_evm_trace().is_empty());
+}
@@ -4,20 +4,27 @@ mod batch_onboarding_deals_test;
 mod change_beneficiary_test;
 mod change_owner_test;
 mod commit_post_test;
+mod consensus_fault_test;
 mod datacap_tests;
+mod evm_gas_test;
 mod evm_test;
 mod extend_sectors_test;
 mod init_test;
 mod market_miner_withdrawal_test;
 mod multisig_test;
+mod param_decoding_proptest;
+mod paych_test;
 mod power_scenario_tests;
 mod prove_commit3_test;
 mod prove_commit_niporep_test;
 mod publish_deals_test;
 mod replica_update3_test;
 mod replica_update_test;
+mod state_compat_test;
+mod state_invariants_proptest;
 mod terminate_test;
 mod test_vm_test;
 mod verified_claim_test;
 mod verifreg_remove_datacap_test;
 mod withdraw_balance_test;
+mod workload_test;
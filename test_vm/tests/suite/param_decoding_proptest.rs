@@ -0,0 +1,320 @@
+//! Property tests checking that every builtin actor rejects malformed method parameters
+//! with an error exit code, rather than panicking.
+//! Parameters are arbitrary bytes that do not decode as DAG-CBOR, so no method gets as far
+//! as touching the runtime, and the mock runtime needs no expectations.
+//! Set PROPTEST_CASES to run many more cases than the default when fuzzing locally.
+
+use fil_actors_runtime::runtime::ActorCode;
+use fil_actors_runtime::test_utils::{MockRuntime, SYSTEM_ACTOR_CODE_ID};
+use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::{BytesDe, DAG_CBOR};
+use fvm_shared::error::ExitCode;
+use fvm_shared::MethodNum;
+use proptest::prelude::*;
+use serde::de::IgnoredAny;
+
+/// Calls each method with the parameters, checking that the call fails to decode them.
+fn check_methods<A: ActorCode>(methods: &[MethodNum], data: &[u8]) -> Result<(), TestCaseError> {
+    for &method in methods {
+        let rt = MockRuntime::new();
+        // A builtin caller, so internal methods get past the API restriction to decoding.
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        let params = IpldBlock { codec: DAG_CBOR, data: data.to_vec() };
+        let err = rt.call::<A>(method, Some(params)).expect_err("malformed params were accepted");
+        prop_assert!(
+            [ExitCode::USR_SERIALIZATION, ExitCode::USR_ILLEGAL_ARGUMENT]
+                .contains(&err.exit_code()),
+            "{} method {} failed with {}: {}",
+            A::name(),
+            method,
+            err.exit_code(),
+            err.msg()
+        );
+        rt.verify();
+    }
+    Ok(())
+}
+
+/// Bytes that are not a valid DAG-CBOR encoding of any value.
+fn malformed_cbor() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..128).prop_filter("valid CBOR", |data| {
+        // The decoder doesn't check the major type of byte strings, so a header of the
+        // wrong type followed by that many bytes decodes as bytes.
+        fvm_ipld_encoding::from_slice::<IgnoredAny>(data).is_err()
+            && fvm_ipld_encoding::from_slice::<BytesDe>(data).is_err()
+    })
+}
+
+proptest! {
+    #[test]
+    fn malformed_params_are_rejected(data in malformed_cbor()) {
+        check_methods::<fil_actor_account::Actor>(
+            &[
+                fil_actor_account::Method::Constructor as MethodNum,
+                fil_actor_account::Method::PubkeyAddress as MethodNum,
+                fil_actor_account::Method::PubkeyAddressExported as MethodNum,
+                fil_actor_account::Method::AuthenticateMessageExported as MethodNum,
+                fil_actor_account::Method::SetAuthDelegateExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_cron::Actor>(
+            &[
+                fil_actor_cron::Method::Constructor as MethodNum,
+                fil_actor_cron::Method::EpochTick as MethodNum,
+                fil_actor_cron::Method::ListEntriesExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_datacap::Actor>(
+            &[
+                fil_actor_datacap::Method::Constructor as MethodNum,
+                fil_actor_datacap::Method::MintExported as MethodNum,
+                fil_actor_datacap::Method::DestroyExported as MethodNum,
+                fil_actor_datacap::Method::NameExported as MethodNum,
+                fil_actor_datacap::Method::SymbolExported as MethodNum,
+                fil_actor_datacap::Method::GranularityExported as MethodNum,
+                fil_actor_datacap::Method::TotalSupplyExported as MethodNum,
+                fil_actor_datacap::Method::BalanceExported as MethodNum,
+                fil_actor_datacap::Method::TransferExported as MethodNum,
+                fil_actor_datacap::Method::TransferFromExported as MethodNum,
+                fil_actor_datacap::Method::IncreaseAllowanceExported as MethodNum,
+                fil_actor_datacap::Method::DecreaseAllowanceExported as MethodNum,
+                fil_actor_datacap::Method::RevokeAllowanceExported as MethodNum,
+                fil_actor_datacap::Method::BurnExported as MethodNum,
+                fil_actor_datacap::Method::BurnFromExported as MethodNum,
+                fil_actor_datacap::Method::AllowanceExported as MethodNum,
+                fil_actor_datacap::Method::ListBalancesExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_eam::EamActor>(
+            &[
+                fil_actor_eam::Method::Constructor as MethodNum,
+                fil_actor_eam::Method::Create as MethodNum,
+                fil_actor_eam::Method::Create2 as MethodNum,
+                fil_actor_eam::Method::CreateExternal as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_ethaccount::EthAccountActor>(
+            &[
+                fil_actor_ethaccount::Method::Constructor as MethodNum,
+                fil_actor_ethaccount::Method::AuthenticateMessageExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_evm::EvmContractActor>(
+            &[
+                fil_actor_evm::Method::Constructor as MethodNum,
+                fil_actor_evm::Method::Resurrect as MethodNum,
+                fil_actor_evm::Method::GetBytecode as MethodNum,
+                fil_actor_evm::Method::GetBytecodeHash as MethodNum,
+                fil_actor_evm::Method::GetStorageAt as MethodNum,
+                fil_actor_evm::Method::InvokeContractDelegate as MethodNum,
+                fil_actor_evm::Method::InvokeContract as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_init::Actor>(
+            &[
+                fil_actor_init::Method::Constructor as MethodNum,
+                fil_actor_init::Method::Exec as MethodNum,
+                fil_actor_init::Method::Exec4 as MethodNum,
+                fil_actor_init::Method::PreviewAddressExported as MethodNum,
+                fil_actor_init::Method::ExecPaymentChannelExported as MethodNum,
+                fil_actor_init::Method::ResolveAddressExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_market::Actor>(
+            &[
+                fil_actor_market::Method::Constructor as MethodNum,
+                fil_actor_market::Method::AddBalance as MethodNum,
+                fil_actor_market::Method::WithdrawBalance as MethodNum,
+                fil_actor_market::Method::PublishStorageDeals as MethodNum,
+                fil_actor_market::Method::VerifyDealsForActivation as MethodNum,
+                fil_actor_market::Method::BatchActivateDeals as MethodNum,
+                fil_actor_market::Method::OnMinerSectorsTerminate as MethodNum,
+                fil_actor_market::Method::CronTick as MethodNum,
+                fil_actor_market::Method::BatchComputeDataCommitment as MethodNum,
+                fil_actor_market::Method::AddBalanceExported as MethodNum,
+                fil_actor_market::Method::WithdrawBalanceExported as MethodNum,
+                fil_actor_market::Method::PublishStorageDealsExported as MethodNum,
+                fil_actor_market::Method::GetBalanceExported as MethodNum,
+                fil_actor_market::Method::GetDealDataCommitmentExported as MethodNum,
+                fil_actor_market::Method::GetDealClientExported as MethodNum,
+                fil_actor_market::Method::GetDealProviderExported as MethodNum,
+                fil_actor_market::Method::GetDealLabelExported as MethodNum,
+                fil_actor_market::Method::GetDealTermExported as MethodNum,
+                fil_actor_market::Method::GetDealTotalPriceExported as MethodNum,
+                fil_actor_market::Method::GetDealClientCollateralExported as MethodNum,
+                fil_actor_market::Method::GetDealProviderCollateralExported as MethodNum,
+                fil_actor_market::Method::GetDealVerifiedExported as MethodNum,
+                fil_actor_market::Method::GetDealActivationExported as MethodNum,
+                fil_actor_market::Method::GetDealSectorExported as MethodNum,
+                fil_actor_market::Method::GetDealCollateralBoundsExported as MethodNum,
+                fil_actor_market::Method::BatchComputeDataCommitmentExported as MethodNum,
+                fil_actor_market::Method::SettleDealPaymentsExported as MethodNum,
+                fil_actor_market::Method::SectorContentChangedExported as MethodNum,
+                fil_actor_market::Method::AmendDealExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_miner::Actor>(
+            &[
+                fil_actor_miner::Method::Constructor as MethodNum,
+                fil_actor_miner::Method::ControlAddresses as MethodNum,
+                fil_actor_miner::Method::ChangeWorkerAddress as MethodNum,
+                fil_actor_miner::Method::ChangePeerID as MethodNum,
+                fil_actor_miner::Method::SubmitWindowedPoSt as MethodNum,
+                fil_actor_miner::Method::ExtendSectorExpiration as MethodNum,
+                fil_actor_miner::Method::TerminateSectors as MethodNum,
+                fil_actor_miner::Method::DeclareFaults as MethodNum,
+                fil_actor_miner::Method::DeclareFaultsRecovered as MethodNum,
+                fil_actor_miner::Method::OnDeferredCronEvent as MethodNum,
+                fil_actor_miner::Method::CheckSectorProven as MethodNum,
+                fil_actor_miner::Method::ApplyRewards as MethodNum,
+                fil_actor_miner::Method::ReportConsensusFault as MethodNum,
+                fil_actor_miner::Method::WithdrawBalance as MethodNum,
+                fil_actor_miner::Method::InternalSectorSetupForPreseal as MethodNum,
+                fil_actor_miner::Method::ChangeMultiaddrs as MethodNum,
+                fil_actor_miner::Method::CompactPartitions as MethodNum,
+                fil_actor_miner::Method::CompactSectorNumbers as MethodNum,
+                fil_actor_miner::Method::ConfirmChangeWorkerAddress as MethodNum,
+                fil_actor_miner::Method::RepayDebt as MethodNum,
+                fil_actor_miner::Method::ChangeOwnerAddress as MethodNum,
+                fil_actor_miner::Method::DisputeWindowedPoSt as MethodNum,
+                fil_actor_miner::Method::ProveCommitAggregate as MethodNum,
+                fil_actor_miner::Method::ProveReplicaUpdates as MethodNum,
+                fil_actor_miner::Method::PreCommitSectorBatch2 as MethodNum,
+                fil_actor_miner::Method::ChangeBeneficiary as MethodNum,
+                fil_actor_miner::Method::GetBeneficiary as MethodNum,
+                fil_actor_miner::Method::ExtendSectorExpiration2 as MethodNum,
+                fil_actor_miner::Method::ProveCommitSectors3 as MethodNum,
+                fil_actor_miner::Method::ProveReplicaUpdates3 as MethodNum,
+                fil_actor_miner::Method::ProveCommitSectorsNI as MethodNum,
+                fil_actor_miner::Method::DeclareFaultsBySector as MethodNum,
+                fil_actor_miner::Method::DeclareFaultsRecoveredBySector as MethodNum,
+                fil_actor_miner::Method::ChangeWorkerAddressExported as MethodNum,
+                fil_actor_miner::Method::ChangePeerIDExported as MethodNum,
+                fil_actor_miner::Method::WithdrawBalanceExported as MethodNum,
+                fil_actor_miner::Method::ChangeMultiaddrsExported as MethodNum,
+                fil_actor_miner::Method::ChangePeerInfoExported as MethodNum,
+                fil_actor_miner::Method::ConfirmChangeWorkerAddressExported as MethodNum,
+                fil_actor_miner::Method::RepayDebtExported as MethodNum,
+                fil_actor_miner::Method::ChangeOwnerAddressExported as MethodNum,
+                fil_actor_miner::Method::ChangeBeneficiaryExported as MethodNum,
+                fil_actor_miner::Method::GetBeneficiaryExported as MethodNum,
+                fil_actor_miner::Method::GetOwnerExported as MethodNum,
+                fil_actor_miner::Method::IsControllingAddressExported as MethodNum,
+                fil_actor_miner::Method::GetSectorSizeExported as MethodNum,
+                fil_actor_miner::Method::GetAvailableBalanceExported as MethodNum,
+                fil_actor_miner::Method::GetVestingFundsExported as MethodNum,
+                fil_actor_miner::Method::GetPeerIDExported as MethodNum,
+                fil_actor_miner::Method::GetMultiaddrsExported as MethodNum,
+                fil_actor_miner::Method::GetVestingReleaseEpochExported as MethodNum,
+                fil_actor_miner::Method::ReleaseVestingFundsExported as MethodNum,
+                fil_actor_miner::Method::GetDeadlineInfoExported as MethodNum,
+                fil_actor_miner::Method::GetSectorsExported as MethodNum,
+                fil_actor_miner::Method::GetBalancesExported as MethodNum,
+                fil_actor_miner::Method::GetDisputableWindowedPoStsExported as MethodNum,
+                fil_actor_miner::Method::UniversalReceiverHook as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_multisig::Actor>(
+            &[
+                fil_actor_multisig::Method::Constructor as MethodNum,
+                fil_actor_multisig::Method::Propose as MethodNum,
+                fil_actor_multisig::Method::Approve as MethodNum,
+                fil_actor_multisig::Method::Cancel as MethodNum,
+                fil_actor_multisig::Method::AddSigner as MethodNum,
+                fil_actor_multisig::Method::RemoveSigner as MethodNum,
+                fil_actor_multisig::Method::SwapSigner as MethodNum,
+                fil_actor_multisig::Method::ChangeNumApprovalsThreshold as MethodNum,
+                fil_actor_multisig::Method::LockBalance as MethodNum,
+                fil_actor_multisig::Method::UniversalReceiverHook as MethodNum,
+                fil_actor_multisig::Method::ListPendingTransactionsExported as MethodNum,
+                fil_actor_multisig::Method::GetPendingTransactionExported as MethodNum,
+                fil_actor_multisig::Method::PreviewProposalHashExported as MethodNum,
+                fil_actor_multisig::Method::ProposeWithExpiryExported as MethodNum,
+                fil_actor_multisig::Method::PruneExpiredExported as MethodNum,
+                fil_actor_multisig::Method::ApproveBatchExported as MethodNum,
+                fil_actor_multisig::Method::CancelBatchExported as MethodNum,
+                fil_actor_multisig::Method::SetGovernanceDelayExported as MethodNum,
+                fil_actor_multisig::Method::ApplyGovernanceChangesExported as MethodNum,
+                fil_actor_multisig::Method::SwapSignerWithApprovalsExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_paych::Actor>(
+            &[
+                fil_actor_paych::Method::Constructor as MethodNum,
+                fil_actor_paych::Method::UpdateChannelState as MethodNum,
+                fil_actor_paych::Method::Settle as MethodNum,
+                fil_actor_paych::Method::Collect as MethodNum,
+                fil_actor_paych::Method::ListLanesExported as MethodNum,
+                fil_actor_paych::Method::CollectImmediateExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_power::Actor>(
+            &[
+                fil_actor_power::Method::Constructor as MethodNum,
+                fil_actor_power::Method::CreateMiner as MethodNum,
+                fil_actor_power::Method::UpdateClaimedPower as MethodNum,
+                fil_actor_power::Method::EnrollCronEvent as MethodNum,
+                fil_actor_power::Method::OnEpochTickEnd as MethodNum,
+                fil_actor_power::Method::UpdatePledgeTotal as MethodNum,
+                fil_actor_power::Method::CurrentTotalPower as MethodNum,
+                fil_actor_power::Method::CreateMinerExported as MethodNum,
+                fil_actor_power::Method::NetworkRawPowerExported as MethodNum,
+                fil_actor_power::Method::MinerRawPowerExported as MethodNum,
+                fil_actor_power::Method::MinerCountExported as MethodNum,
+                fil_actor_power::Method::MinerConsensusCountExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_reward::Actor>(
+            &[
+                fil_actor_reward::Method::Constructor as MethodNum,
+                fil_actor_reward::Method::AwardBlockReward as MethodNum,
+                fil_actor_reward::Method::ThisEpochReward as MethodNum,
+                fil_actor_reward::Method::UpdateNetworkKPI as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_system::Actor>(
+            &[
+                fil_actor_system::Method::Constructor as MethodNum,
+                fil_actor_system::Method::GetBuiltinActorsExported as MethodNum,
+            ],
+            &data,
+        )?;
+        check_methods::<fil_actor_verifreg::Actor>(
+            &[
+                fil_actor_verifreg::Method::Constructor as MethodNum,
+                fil_actor_verifreg::Method::AddVerifier as MethodNum,
+                fil_actor_verifreg::Method::RemoveVerifier as MethodNum,
+                fil_actor_verifreg::Method::AddVerifiedClient as MethodNum,
+                fil_actor_verifreg::Method::RemoveVerifiedClientDataCap as MethodNum,
+                fil_actor_verifreg::Method::RemoveExpiredAllocations as MethodNum,
+                fil_actor_verifreg::Method::ClaimAllocations as MethodNum,
+                fil_actor_verifreg::Method::GetClaims as MethodNum,
+                fil_actor_verifreg::Method::ExtendClaimTerms as MethodNum,
+                fil_actor_verifreg::Method::RemoveExpiredClaims as MethodNum,
+                fil_actor_verifreg::Method::AddVerifiedClientExported as MethodNum,
+                fil_actor_verifreg::Method::RemoveExpiredAllocationsExported as MethodNum,
+                fil_actor_verifreg::Method::GetClaimsExported as MethodNum,
+                fil_actor_verifreg::Method::ExtendClaimTermsExported as MethodNum,
+                fil_actor_verifreg::Method::RemoveExpiredClaimsExported as MethodNum,
+                fil_actor_verifreg::Method::UniversalReceiverHook as MethodNum,
+                fil_actor_verifreg::Method::ListVerifiersExported as MethodNum,
+                fil_actor_verifreg::Method::GetAllocationsExported as MethodNum,
+            ],
+            &data,
+        )?;
+    }
+}
@@ -0,0 +1,19 @@
+use fil_actors_integration_tests::tests::{
+    payment_channel_at_derived_address_test, payment_channel_collect_deletes_channel_test,
+};
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use test_vm::TestVM;
+
+#[test]
+fn payment_channel_at_derived_address() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    payment_channel_at_derived_address_test(&v);
+}
+
+#[test]
+fn payment_channel_collect_deletes_channel() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    payment_channel_collect_deletes_channel_test(&v);
+}
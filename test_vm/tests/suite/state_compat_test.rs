@@ -0,0 +1,235 @@
+//! Checks that state written by earlier releases of the actors remains usable by the current code.
+//!
+//! Each fixture under `tests/fixtures/state` is a CAR rooted at a [`StateFixture`], written by the
+//! [`build_fixture`] scenario as it ran on the release the file is named after. The current code
+//! must load each fixture's state cleanly, then execute a further scenario on top of it.
+//! A failure here means a state schema change which needs a migration.
+//!
+//! To add a fixture for the current release, run this test with `UPDATE_STATE_FIXTURES=1` and
+//! check in the new file. Fixtures from earlier releases must never be regenerated.
+
+use std::path::{Path, PathBuf};
+
+use cid::Cid;
+use fil_actor_miner::max_prove_commit_duration;
+use fil_actors_integration_tests::util::{
+    advance_by_deadline_to_epoch, advance_by_deadline_to_index, advance_to_proving_deadline,
+    assert_invariants, create_miner, create_storage_parties, cron_tick,
+    make_piece_manifests_from_deal_ids, market_add_balance, market_publish_deal, miner_balance,
+    miner_precommit_one_sector_v2, miner_prove_sector, precommit_meta_data_from_deals,
+    submit_windowed_post, verifreg_add_client, verifreg_add_verifier, withdraw_balance,
+    StorageParties,
+};
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::EPOCHS_IN_DAY;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::{RegisteredSealProof, SectorNumber, StoragePower};
+use multihash_codetable::Code;
+use test_vm::{write_car, TestVM};
+use vm_api::VM;
+
+const SEAL_PROOF: RegisteredSealProof = RegisteredSealProof::StackedDRG32GiBV1P1;
+const SECTOR_NUMBER: SectorNumber = 100;
+
+/// The root of a fixture: a state tree, the VM context it was written in, and the actors of
+/// interest within it.
+#[derive(Serialize_tuple, Deserialize_tuple)]
+struct StateFixture {
+    state_root: Cid,
+    epoch: ChainEpoch,
+    circulating_supply: TokenAmount,
+    owner: Address,
+    verifier: Address,
+    client: Address,
+    miner: Address,
+}
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/state")
+}
+
+/// Builds state exercising each actor which holds non-trivial state: a miner with a proven
+/// sector holding an activated verified deal, with its market escrow, claim and data cap.
+fn build_fixture(v: &TestVM) -> StateFixture {
+    let StorageParties { owner, worker, verifier, client } =
+        create_storage_parties(v, &TokenAmount::from_whole(10_000));
+    let miner = create_miner(
+        v,
+        &owner,
+        &worker,
+        SEAL_PROOF.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    )
+    .0;
+    v.set_epoch(200);
+
+    let datacap = StoragePower::from(32_u128 << 40);
+    verifreg_add_verifier(v, &verifier, datacap.clone());
+    verifreg_add_client(v, &verifier, &client, datacap);
+    market_add_balance(v, &client, &client, &TokenAmount::from_whole(3));
+    market_add_balance(v, &worker, &miner, &TokenAmount::from_whole(64));
+
+    let deal_start = v.epoch() + max_prove_commit_duration(&Policy::default(), SEAL_PROOF).unwrap();
+    let deals = publish_verified_deal(v, &worker, &client, &miner, "deal1", deal_start);
+    miner_precommit_one_sector_v2(
+        v,
+        &worker,
+        &miner,
+        SEAL_PROOF,
+        SECTOR_NUMBER,
+        precommit_meta_data_from_deals(v, &deals, SEAL_PROOF, false),
+        true,
+        deal_start + 180 * EPOCHS_IN_DAY,
+    );
+    advance_by_deadline_to_epoch(v, &miner, deal_start);
+    miner_prove_sector(
+        v,
+        &worker,
+        &miner,
+        SECTOR_NUMBER,
+        make_piece_manifests_from_deal_ids(v, deals),
+    );
+    cron_tick(v);
+
+    let (dline_info, partition) = advance_to_proving_deadline(v, &miner, SECTOR_NUMBER);
+    submit_windowed_post(v, &worker, &miner, dline_info, partition, None);
+    // Close the deadline so the state is written at a consistent epoch.
+    let deadlines = Policy::default().wpost_period_deadlines;
+    advance_by_deadline_to_index(v, &miner, (dline_info.index + 1) % deadlines);
+
+    StateFixture {
+        state_root: v.checkpoint(),
+        epoch: v.epoch(),
+        circulating_supply: v.circulating_supply(),
+        owner,
+        verifier,
+        client,
+        miner,
+    }
+}
+
+/// Continues from a fixture's state, running the current code through methods of each actor
+/// whose state the fixture holds.
+fn exercise_fixture(v: &TestVM, f: &StateFixture) {
+    // Prove the existing sector through its next proving period.
+    let (dline_info, partition) = advance_to_proving_deadline(v, &f.miner, SECTOR_NUMBER);
+    submit_windowed_post(v, &f.owner, &f.miner, dline_info, partition, None);
+    let deadlines = Policy::default().wpost_period_deadlines;
+    advance_by_deadline_to_index(v, &f.miner, (dline_info.index + 1) % deadlines);
+
+    // Onboard a second sector with a new verified deal, spending more of the client's data cap.
+    let sector_number = SECTOR_NUMBER + 1;
+    let deal_start = v.epoch() + max_prove_commit_duration(&Policy::default(), SEAL_PROOF).unwrap();
+    let deals = publish_verified_deal(v, &f.owner, &f.client, &f.miner, "deal2", deal_start);
+    miner_precommit_one_sector_v2(
+        v,
+        &f.owner,
+        &f.miner,
+        SEAL_PROOF,
+        sector_number,
+        precommit_meta_data_from_deals(v, &deals, SEAL_PROOF, false),
+        false,
+        deal_start + 180 * EPOCHS_IN_DAY,
+    );
+    advance_by_deadline_to_epoch(v, &f.miner, deal_start);
+    miner_prove_sector(
+        v,
+        &f.owner,
+        &f.miner,
+        sector_number,
+        make_piece_manifests_from_deal_ids(v, deals),
+    );
+    cron_tick(v);
+    v.set_epoch(v.epoch() + 1);
+
+    // Withdraw some of the miner's available balance, and create another miner.
+    let available = miner_balance(v, &f.miner).available_balance;
+    let amount = TokenAmount::from_whole(1);
+    assert!(available >= amount, "miner available balance {} too low", available);
+    withdraw_balance(v, &f.owner, &f.miner, &amount, &amount);
+    create_miner(
+        v,
+        &f.verifier,
+        &f.verifier,
+        SEAL_PROOF.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1),
+    );
+}
+
+fn publish_verified_deal(
+    v: &TestVM,
+    worker: &Address,
+    client: &Address,
+    miner: &Address,
+    label: &str,
+    deal_start: ChainEpoch,
+) -> Vec<u64> {
+    market_publish_deal(
+        v,
+        worker,
+        client,
+        miner,
+        label.to_string(),
+        PaddedPieceSize(32u64 << 30),
+        true,
+        deal_start,
+        180 * EPOCHS_IN_DAY,
+    )
+    .ids
+}
+
+fn write_fixture(path: &Path) {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let fixture = build_fixture(&v);
+    assert_invariants(&v, &Policy::default(), None);
+    let root = v.store.put_cbor(&fixture, Code::Blake2b256).unwrap();
+    let mut out = std::fs::File::create(path).unwrap();
+    write_car(v.store.as_ref(), root, &mut out).unwrap();
+}
+
+fn load_fixture(path: &Path) -> (TestVM, StateFixture) {
+    let store = MemoryBlockstore::new();
+    let file = futures::io::AllowStdIo::new(std::fs::File::open(path).unwrap());
+    let roots = futures::executor::block_on(fvm_ipld_car::load_car(&store, file)).unwrap();
+    assert_eq!(1, roots.len(), "fixture {} must have one root", path.display());
+    let fixture: StateFixture = store.get_cbor(&roots[0]).unwrap().unwrap();
+    assert!(store.has(&fixture.state_root).unwrap());
+
+    let v = TestVM::new(store);
+    v.state_root.replace(fixture.state_root);
+    v.set_epoch(fixture.epoch);
+    v.set_circulating_supply(fixture.circulating_supply.clone());
+    (v, fixture)
+}
+
+#[test]
+fn state_fixtures_remain_compatible() {
+    let dir = fixture_dir();
+    if std::env::var_os("UPDATE_STATE_FIXTURES").is_some() {
+        std::fs::create_dir_all(&dir).unwrap();
+        write_fixture(&dir.join(format!("v{}.car", env!("CARGO_PKG_VERSION_MAJOR"))));
+    }
+
+    let mut fixtures: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "car"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no state fixtures in {}", dir.display());
+
+    for path in fixtures {
+        let (v, fixture) = load_fixture(&path);
+        assert_invariants(&v, &Policy::default(), None);
+        exercise_fixture(&v, &fixture);
+        assert_invariants(&v, &Policy::default(), None);
+    }
+}
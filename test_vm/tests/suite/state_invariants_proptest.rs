@@ -0,0 +1,217 @@
+//! Property tests checking state invariants after each step of random operation sequences.
+//! Operations that aren't valid in the state reached by earlier operations are skipped,
+//! so that shrinking can remove any operation from a failing sequence.
+
+use fil_actor_miner::{
+    deadline_is_mutable, Method as MinerMethod, State as MinerState, TerminateSectorsParams,
+    TerminationDeclaration,
+};
+use fil_actors_integration_tests::util::{
+    advance_by_deadline_to_epoch, check_invariants, check_invariants_streaming, create_accounts,
+    create_miner, invariant_failure_patterns, make_bitfield, market_add_balance,
+    market_publish_deal, miner_dline_info, miner_prove_sector, precommit_sectors_v2,
+    sector_deadline,
+};
+use fil_actors_runtime::network::EPOCHS_IN_DAY;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::{RegisteredSealProof, SectorNumber};
+use num_traits::Zero;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use test_vm::TestVM;
+use vm_api::util::{apply_ok, get_state};
+use vm_api::VM;
+
+#[derive(Clone, Debug)]
+enum Op {
+    /// Publishes a deal from the client, which is never activated.
+    PublishDeal,
+    /// Pre-commits and proves a batch of sectors.
+    Onboard(usize),
+    /// Advances through deadlines without submitting any PoSt, faulting sectors.
+    SkipDeadlines(i64),
+    /// Terminates a live sector, if its deadline is mutable.
+    Terminate(Index),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        Just(Op::PublishDeal),
+        (1..=4usize).prop_map(Op::Onboard),
+        (1..=3i64).prop_map(Op::SkipDeadlines),
+        any::<Index>().prop_map(Op::Terminate),
+    ]
+}
+
+struct Scenario {
+    v: TestVM,
+    policy: Policy,
+    worker: Address,
+    client: Address,
+    maddr: Address,
+    seal_proof: RegisteredSealProof,
+    next_sector: SectorNumber,
+    live_sectors: Vec<SectorNumber>,
+    deals_published: u64,
+}
+
+impl Scenario {
+    fn new() -> Self {
+        let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+        let policy = Policy::default();
+        let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(100_000));
+        let (worker, client) = (addrs[0], addrs[1]);
+        let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+        let (maddr, _) = create_miner(
+            &v,
+            &worker,
+            &worker,
+            seal_proof.registered_window_post_proof().unwrap(),
+            &TokenAmount::from_whole(10_000),
+        );
+        market_add_balance(&v, &worker, &maddr, &TokenAmount::from_whole(1_000));
+        market_add_balance(&v, &client, &client, &TokenAmount::from_whole(1_000));
+        v.set_epoch(200);
+        Scenario {
+            v,
+            policy,
+            worker,
+            client,
+            maddr,
+            seal_proof,
+            next_sector: 100,
+            live_sectors: vec![],
+            deals_published: 0,
+        }
+    }
+
+    fn apply(&mut self, op: &Op) {
+        let v = &self.v;
+        match op {
+            Op::PublishDeal => {
+                let start = v.epoch() + self.policy.pre_commit_challenge_delay + EPOCHS_IN_DAY;
+                market_publish_deal(
+                    v,
+                    &self.worker,
+                    &self.client,
+                    &self.maddr,
+                    format!("deal-{}", self.deals_published),
+                    PaddedPieceSize(32 << 30),
+                    false,
+                    start,
+                    181 * EPOCHS_IN_DAY,
+                );
+                self.deals_published += 1;
+            }
+            Op::Onboard(count) => {
+                let first = self.next_sector;
+                let st: MinerState = get_state(v, &self.maddr).unwrap();
+                precommit_sectors_v2(
+                    v,
+                    *count,
+                    *count,
+                    vec![],
+                    &self.worker,
+                    &self.maddr,
+                    self.seal_proof,
+                    first,
+                    !st.deadline_cron_active,
+                    None,
+                );
+                let prove_time = v.epoch() + self.policy.pre_commit_challenge_delay + 1;
+                advance_by_deadline_to_epoch(v, &self.maddr, prove_time);
+                for sector in first..first + *count as u64 {
+                    miner_prove_sector(v, &self.worker, &self.maddr, sector, vec![]);
+                    self.live_sectors.push(sector);
+                }
+                self.next_sector += *count as u64;
+            }
+            Op::SkipDeadlines(n) => {
+                let dline = miner_dline_info(v, &self.maddr);
+                let target = dline.close + (n - 1) * self.policy.wpost_challenge_window + 1;
+                advance_by_deadline_to_epoch(v, &self.maddr, target);
+            }
+            Op::Terminate(index) => {
+                if self.live_sectors.is_empty() {
+                    return;
+                }
+                let i = index.index(self.live_sectors.len());
+                let sector = self.live_sectors[i];
+                let (deadline, partition) = sector_deadline(v, &self.maddr, sector);
+                let st: MinerState = get_state(v, &self.maddr).unwrap();
+                let mutable = deadline_is_mutable(
+                    &self.policy,
+                    st.current_proving_period_start(&self.policy, v.epoch()),
+                    deadline,
+                    v.epoch(),
+                );
+                if !mutable {
+                    return;
+                }
+                let params = TerminateSectorsParams {
+                    terminations: vec![TerminationDeclaration {
+                        deadline,
+                        partition,
+                        sectors: make_bitfield(&[sector]),
+                    }],
+                };
+                apply_ok(
+                    v,
+                    &self.worker,
+                    &self.maddr,
+                    &TokenAmount::zero(),
+                    MinerMethod::TerminateSectors as u64,
+                    Some(params),
+                );
+                self.live_sectors.swap_remove(i);
+            }
+        }
+    }
+
+    /// Returns the invariant violations of the current state.
+    /// The streaming mode of the checker must find the same violations.
+    fn violations(&self) -> Vec<String> {
+        let acc = check_invariants(&self.v, &self.policy, None).unwrap();
+        let mut messages = acc.messages();
+        let mut streamed =
+            check_invariants_streaming(&self.v, &self.policy, None).unwrap().messages();
+        messages.sort();
+        streamed.sort();
+        assert_eq!(messages, streamed);
+        messages
+            .into_iter()
+            // Cron only runs at the end of deadlines, so the reward actor skips epochs.
+            .filter(|m| !invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.is_match(m))
+            .collect()
+    }
+
+    fn epoch(&self) -> ChainEpoch {
+        self.v.epoch()
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig { cases: 8, ..ProptestConfig::default() })]
+
+    #[test]
+    fn invariants_hold_after_every_operation(ops in prop::collection::vec(op(), 1..8)) {
+        let mut scenario = Scenario::new();
+        for (i, op) in ops.iter().enumerate() {
+            scenario.apply(op);
+            let violations = scenario.violations();
+            prop_assert!(
+                violations.is_empty(),
+                "invariants violated after op {} {:?} at epoch {}:\n{}",
+                i,
+                op,
+                scenario.epoch(),
+                violations.join("\n")
+            );
+        }
+    }
+}
@@ -1,16 +1,47 @@
 use fil_actor_account::State as AccountState;
-use fil_actors_integration_tests::util::{assert_invariants, check_invariants};
+use fil_actor_cron::{Entry as CronEntry, State as CronState};
+use fil_actor_miner::{ChangeWorkerAddressParams, Method as MinerMethod};
+use fil_actor_multisig::State as MultisigState;
+use fil_actor_multisig::{Method as MultisigMethod, ProposeParams};
+use fil_actor_power::{CreateMinerParams, Method as PowerMethod};
+use fil_actor_reward::State as RewardState;
+use fil_actor_verifreg::{Method as VerifregMethod, State as VerifregState, VerifierParams};
+use fil_actors_integration_tests::util::{
+    advance_to_epoch_sampling_cron, advance_to_epoch_with_cron, assert_invariants,
+    check_invariants, create_accounts, create_eth_account, create_miner, create_placeholder,
+    eth_f4_addr, miner_info,
+};
+use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{Policy, EMPTY_ARR_CID};
 use fil_actors_runtime::test_blockstores::MemoryBlockstore;
-use fil_actors_runtime::test_utils::{ACCOUNT_ACTOR_CODE_ID, PAYCH_ACTOR_CODE_ID};
-use fvm_shared::address::Address;
+use fil_actors_runtime::test_utils::{
+    ACCOUNT_ACTOR_CODE_ID, ETHACCOUNT_ACTOR_CODE_ID, MULTISIG_ACTOR_CODE_ID, PAYCH_ACTOR_CODE_ID,
+    PLACEHOLDER_ACTOR_CODE_ID,
+};
+use fil_actors_runtime::{
+    CRON_ACTOR_ADDR, EAM_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
+    STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ID,
+};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::{Address, Protocol};
+use fvm_shared::bigint::bigint_ser::BigIntSer;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sector::{RegisteredPoStProof, SectorSize, StoragePower};
 use fvm_shared::METHOD_SEND;
 use num_traits::Zero;
-use test_vm::{TestVM, FIRST_TEST_USER_ADDR, TEST_FAUCET_ADDR};
-use vm_api::util::{get_state, pk_addrs_from};
-use vm_api::{new_actor, VM};
+use std::rc::Rc;
+use test_vm::{
+    CountingMetrics, GenesisBuilder, PriceList, ResultCounts, TestVM, TipsetMessage,
+    FAUCET_ROOT_KEY, FIRST_TEST_USER_ADDR, TEST_FAUCET_ADDR, TEST_VERIFREG_ROOT_ADDR,
+    TEST_VERIFREG_ROOT_SIGNER_ADDR,
+};
+use vm_api::util::{
+    apply_code_with_overrides, apply_ok, describe_last_message, get_state, pk_addrs_from,
+    secp_addrs_from,
+};
+use vm_api::{new_actor, MessageOverrides, VM};
 
 #[test]
 fn state_control() {
@@ -42,6 +73,92 @@ fn state_control() {
     assert!(err.to_string().contains("AccountState is empty"), "unexpected error: {err}");
 }
 
+#[test]
+fn cron_enrolls_only_power_and_market() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    assert_invariants(&v, &Policy::default(), None);
+
+    let mut st: CronState = get_state(&v, &CRON_ACTOR_ADDR).unwrap();
+    st.entries.push(CronEntry { receiver: Address::new_id(1000), method_num: 2 });
+    let mut cron = v.actor(&CRON_ACTOR_ADDR).unwrap();
+    cron.state = v.put_store(&st);
+    v.set_actor(&CRON_ACTOR_ADDR, cron);
+
+    let msgs = check_invariants(&v, &Policy::default(), None).unwrap().messages();
+    assert_eq!(1, msgs.len(), "unexpected messages: {msgs:?}");
+    assert!(
+        msgs[0].contains("are not the power and market ticks"),
+        "unexpected message: {}",
+        msgs[0]
+    );
+}
+
+#[test]
+fn genesis_vms_are_independent() {
+    let genesis = GenesisBuilder::new().build(MemoryBlockstore::new());
+    let v1 = genesis.vm();
+    let v2 = genesis.vm();
+    assert_eq!(genesis.state_root, v1.checkpoint());
+    assert_invariants(&v1, &Policy::default(), None);
+
+    create_accounts(&v1, 1, &TokenAmount::from_whole(1));
+    assert_ne!(genesis.state_root, v1.checkpoint());
+    assert_eq!(genesis.state_root, v2.checkpoint());
+    assert_eq!(
+        FIRST_TEST_USER_ADDR,
+        create_accounts(&v2, 1, &TokenAmount::from_whole(1))[0].id().unwrap()
+    );
+}
+
+#[test]
+fn genesis_builder_configures_actors_and_balances() {
+    let keys = pk_addrs_from(7, 3);
+    let (signers, funded) = (keys[..2].to_vec(), keys[2]);
+    let genesis = GenesisBuilder::new()
+        .without(Type::Market)
+        .without(Type::EAM)
+        .reward_balance(TokenAmount::from_whole(100))
+        .faucet_balance(TokenAmount::from_whole(10))
+        .account(funded, TokenAmount::from_whole(1))
+        .verifreg_root(signers.clone(), 2)
+        .build(MemoryBlockstore::new());
+    let v = genesis.vm();
+    assert_eq!(TokenAmount::from_whole(111), genesis.circulating_supply);
+    assert_eq!(genesis.circulating_supply, v.circulating_supply());
+
+    assert!(v.actor(&STORAGE_MARKET_ACTOR_ADDR).is_none());
+    assert!(v.actor(&EAM_ACTOR_ADDR).is_none());
+    assert_eq!(TokenAmount::from_whole(100), v.balance(&REWARD_ACTOR_ADDR));
+    let cron: CronState = get_state(&v, &CRON_ACTOR_ADDR).unwrap();
+    assert_eq!(
+        vec![STORAGE_POWER_ACTOR_ADDR],
+        cron.entries.iter().map(|e| e.receiver).collect::<Vec<_>>()
+    );
+
+    // The root multisig follows its two signers, then the faucet and the funded account.
+    let root = Address::new_id(TEST_VERIFREG_ROOT_ADDR.id().unwrap() + 1);
+    let verifreg: VerifregState = get_state(&v, &VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
+    assert_eq!(root, verifreg.root_key);
+    let msig: MultisigState = get_state(&v, &root).unwrap();
+    let signer_ids: Vec<Address> =
+        signers.iter().map(|s| v.resolve_id_address(s).unwrap()).collect();
+    assert_eq!(signer_ids, msig.signers);
+    assert_eq!(2, msig.num_approvals_threshold);
+    let faucet = v.resolve_id_address(&Address::new_bls(FAUCET_ROOT_KEY).unwrap()).unwrap();
+    assert_eq!(Address::new_id(root.id().unwrap() + 1), faucet);
+    assert_eq!(TokenAmount::from_whole(10), v.balance(&faucet));
+    let funded_id = v.resolve_id_address(&funded).unwrap();
+    assert_eq!(Address::new_id(faucet.id().unwrap() + 1), funded_id);
+    assert_eq!(TokenAmount::from_whole(1), v.balance(&funded_id));
+}
+
+#[test]
+#[should_panic(expected = "init actor is required")]
+fn genesis_requires_init() {
+    let _ = GenesisBuilder::new().without(Type::Init);
+}
+
 fn assert_account_actor(
     exp_call_seq: u64,
     exp_bal: TokenAmount,
@@ -108,6 +225,40 @@ fn test_sent() {
     assert_invariants(&v, &Policy::default(), None)
 }
 
+#[test]
+fn failed_message_is_described_with_decoded_params_and_state() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 1, &TokenAmount::from_whole(10));
+    let params = VerifierParams { address: addrs[0], allowance: StoragePower::from(1234) };
+
+    // An allowance below the minimum allocation size is rejected.
+    let res = v
+        .execute_message(
+            &addrs[0],
+            &VERIFIED_REGISTRY_ACTOR_ADDR,
+            &TokenAmount::zero(),
+            VerifregMethod::AddVerifier as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, res.code);
+
+    let description = describe_last_message(&v, &VERIFIED_REGISTRY_ACTOR_ADDR);
+    let expected_params = format!(
+        "params: [0x{}, 0x{}]",
+        hex::encode(addrs[0].to_bytes()),
+        hex::encode([0, 0x04, 0xd2])
+    );
+    assert!(description.contains(&expected_params), "{}", description);
+    assert!(
+        description.contains(&format!("exit {}", ExitCode::USR_ILLEGAL_ARGUMENT)),
+        "{}",
+        description
+    );
+    assert!(description.contains("(verifiedregistry actor"), "{}", description);
+}
+
 #[test]
 fn test_pk_gen() {
     let addrs = pk_addrs_from(5, 2);
@@ -115,4 +266,374 @@ fn test_pk_gen() {
     let addrs = pk_addrs_from(6, 1);
     let first_addr_seeded_six = addrs[0];
     assert_ne!(second_addr_seeded_five, first_addr_seeded_six);
+
+    let secp_addrs = secp_addrs_from(5, 2);
+    assert_eq!(secp_addrs, secp_addrs_from(5, 2));
+    assert_ne!(secp_addrs[0], secp_addrs[1]);
+    assert!(secp_addrs.iter().all(|a| a.protocol() == Protocol::Secp256k1));
+}
+
+#[test]
+fn policy_is_observed_by_actors() {
+    let store = MemoryBlockstore::new();
+    let policy = Policy::mainnet().builder().sector_sizes(&[SectorSize::_2KiB]).build();
+    let v = TestVM::new_with_singletons(store).with_policy(policy.clone());
+    assert_eq!(&policy, v.policy());
+
+    let addrs = create_accounts(&v, 1, &TokenAmount::from_whole(10_000));
+    let owner = addrs[0];
+    let params = CreateMinerParams {
+        owner,
+        worker: owner,
+        window_post_proof_type: RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        peer: vec![],
+        multiaddrs: vec![],
+    };
+    // The 32GiB proof is excluded by the VM's policy, so miner construction fails.
+    let res = v
+        .execute_message(
+            &owner,
+            &STORAGE_POWER_ACTOR_ADDR,
+            &TokenAmount::zero(),
+            PowerMethod::CreateMiner as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, res.code);
+
+    create_miner(
+        &v,
+        &owner,
+        &owner,
+        RegisteredPoStProof::StackedDRGWindow2KiBV1P1,
+        &TokenAmount::zero(),
+    );
+    assert_invariants(&v, v.policy(), None);
+}
+
+#[test]
+fn message_overrides_are_seen_by_the_callee_only() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    let (owner, new_worker) = (addrs[0], addrs[1]);
+    let (maddr, _) = create_miner(
+        &v,
+        &owner,
+        &owner,
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        &TokenAmount::zero(),
+    );
+    let (epoch, base_fee, supply) = (v.epoch(), v.base_fee(), v.circulating_supply());
+
+    let overrides = MessageOverrides {
+        epoch: Some(epoch + 1000),
+        base_fee: Some(TokenAmount::from_nano(100)),
+        circulating_supply: Some(TokenAmount::from_whole(1)),
+    };
+    let params = ChangeWorkerAddressParams { new_worker, new_control_addresses: vec![] };
+    apply_code_with_overrides(
+        &v,
+        &owner,
+        &maddr,
+        &TokenAmount::zero(),
+        MinerMethod::ChangeWorkerAddress as u64,
+        Some(params),
+        &overrides,
+        ExitCode::OK,
+    );
+
+    // The worker change is scheduled from the overridden epoch.
+    let info = miner_info(&v, &maddr);
+    assert_eq!(
+        epoch + 1000 + v.policy().worker_key_change_delay,
+        info.pending_worker_key.unwrap().effective_at
+    );
+    assert_eq!((epoch, base_fee, supply), (v.epoch(), v.base_fee(), v.circulating_supply()));
+}
+
+#[test]
+fn rejected_upgrade_leaves_actor_unchanged() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 1, &TokenAmount::from_whole(10_000));
+    let before = v.actor(&addrs[0]).unwrap();
+
+    // Built-in actors don't yet support upgrade, so the account rejects becoming a multisig.
+    let res = v.upgrade_actor(&addrs[0], *MULTISIG_ACTOR_CODE_ID, None);
+    assert_eq!(ExitCode::USR_FORBIDDEN, res.code);
+    assert!(
+        res.message.contains("does not support upgrade"),
+        "unexpected message: {}",
+        res.message
+    );
+    let id = v.resolve_id_address(&addrs[0]).unwrap();
+    assert_eq!(before, v.actor(&id).unwrap());
+}
+
+#[test]
+fn io_stats_are_recorded_per_message() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    v.take_io_stats();
+
+    // A plain value transfer only reads, while creating a miner writes new state.
+    v.execute_message(&addrs[0], &addrs[1], &TokenAmount::from_atto(1), METHOD_SEND, None).unwrap();
+    create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        &TokenAmount::zero(),
+    );
+    let stats = v.take_io_stats();
+    assert_eq!(2, stats.len());
+    assert!(stats[0].r > 0 && stats[0].w == 0, "unexpected send stats: {:?}", stats[0]);
+    assert!(stats[1].w > 0 && stats[1].bw > 0, "unexpected create stats: {:?}", stats[1]);
+    assert!(v.take_io_stats().is_empty());
+}
+
+#[test]
+fn gas_mode_charges_messages_and_enforces_limit() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store).with_price_list(PriceList::default());
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    v.take_gas_used();
+
+    v.execute_message(&addrs[0], &addrs[1], &TokenAmount::from_atto(1), METHOD_SEND, None).unwrap();
+    create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        &TokenAmount::zero(),
+    );
+    let gas = v.take_gas_used();
+    assert_eq!(2, gas.len());
+    assert!(0 < gas[0] && gas[0] < gas[1], "unexpected gas used: {:?}", gas);
+
+    // A message exceeding its limit fails and its effects are reverted.
+    let balance = v.balance(&addrs[1]);
+    v.set_gas_limit(gas[0] - 1);
+    let res = v
+        .execute_message(&addrs[0], &addrs[1], &TokenAmount::from_atto(1), METHOD_SEND, None)
+        .unwrap();
+    assert_eq!(ExitCode::SYS_OUT_OF_GAS, res.code);
+    assert_eq!(balance, v.balance(&addrs[1]));
+    assert_eq!(vec![gas[0] - 1], v.take_gas_used());
+}
+
+#[test]
+fn exported_car_contains_state_tree() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 1, &TokenAmount::from_whole(10_000));
+    create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        &TokenAmount::zero(),
+    );
+    let path = std::env::temp_dir().join(format!("test_vm_export_{}.car", std::process::id()));
+    let root = v.export_car(&path).unwrap();
+
+    // Loading the CAR into an empty store reproduces the whole state tree.
+    let loaded = MemoryBlockstore::new();
+    let file = futures::io::AllowStdIo::new(std::fs::File::open(&path).unwrap());
+    let roots = futures::executor::block_on(fvm_ipld_car::load_car(&loaded, file)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(vec![root], roots);
+    let v2 = TestVM::new(loaded);
+    v2.state_root.replace(root);
+    assert_eq!(v.actor_states(), v2.actor_states());
+    assert_invariants(&v2, &Policy::default(), None);
+}
+
+#[test]
+fn metrics_count_messages_and_invocations() {
+    let metrics = Rc::new(CountingMetrics::new());
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new()).with_metrics(metrics.clone());
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    let before = metrics.messages();
+    let (maddr, _) = create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        &TokenAmount::zero(),
+    );
+    let written = metrics.io().bw;
+    // Only the owner may change the worker.
+    let params = ChangeWorkerAddressParams { new_worker: addrs[1], new_control_addresses: vec![] };
+    let res = v
+        .execute_message(
+            &addrs[1],
+            &maddr,
+            &TokenAmount::zero(),
+            MinerMethod::ChangeWorkerAddress as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::USR_FORBIDDEN, res.code);
+
+    assert_eq!(ResultCounts { ok: before.ok + 1, failed: before.failed + 1 }, metrics.messages());
+    let ok = ResultCounts { ok: 1, failed: 0 };
+    assert_eq!(ok, metrics.invocations(Type::Power, PowerMethod::CreateMiner as u64));
+    assert_eq!(ok, metrics.invocations(Type::Init, fil_actor_init::Method::Exec as u64));
+    assert_eq!(ok, metrics.invocations(Type::Miner, MinerMethod::Constructor as u64));
+    assert_eq!(
+        ResultCounts { ok: 0, failed: 1 },
+        metrics.invocations(Type::Miner, MinerMethod::ChangeWorkerAddress as u64)
+    );
+    assert!(written > 0);
+
+    let exported = metrics.to_prometheus();
+    assert!(exported.contains("# TYPE test_vm_state_written_bytes_total counter"));
+    assert!(exported.contains(&format!(
+        "test_vm_messages_applied_total{{result=\"failed\"}} {}",
+        before.failed + 1
+    )));
+    assert!(exported.contains(&format!(
+        "test_vm_method_invocations_total{{actor=\"storageminer\",method=\"{}\",result=\"failed\"}} 1",
+        MinerMethod::ChangeWorkerAddress as u64
+    )));
+}
+
+#[test]
+fn emitted_events_are_traced() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 1, &TokenAmount::from_whole(10_000));
+    let verifier_id = v.resolve_id_address(&addrs[0]).unwrap().id().unwrap();
+    let allowance = StoragePower::from(1u64 << 40);
+
+    let add_verifier = VerifierParams { address: addrs[0], allowance: allowance.clone() };
+    let proposal = ProposeParams {
+        to: VERIFIED_REGISTRY_ACTOR_ADDR,
+        value: TokenAmount::zero(),
+        method: VerifregMethod::AddVerifier as u64,
+        params: serialize(&add_verifier, "add verifier params").unwrap(),
+    };
+    apply_ok(
+        &v,
+        &TEST_VERIFREG_ROOT_SIGNER_ADDR,
+        &TEST_VERIFREG_ROOT_ADDR,
+        &TokenAmount::zero(),
+        MultisigMethod::Propose as u64,
+        Some(proposal),
+    );
+
+    // The event is emitted by the verified registry, two calls below the top-level message.
+    let trace = v.take_invocations().pop().unwrap();
+    assert!(trace.events.is_empty());
+    assert_eq!(1, trace.all_events().len());
+    trace.expect_event(VERIFIED_REGISTRY_ACTOR_ID, "$type", "verifier-balance");
+    trace.expect_event(VERIFIED_REGISTRY_ACTOR_ID, "verifier", &verifier_id);
+    trace.expect_event(VERIFIED_REGISTRY_ACTOR_ID, "balance", &BigIntSer(&allowance));
+}
+
+#[test]
+fn advancing_with_cron_runs_cron_up_to_target() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    v.take_invocations();
+    let start = v.epoch();
+    // The reward actor, updated by power's cron, tracks the epoch after the last cron.
+    let reward_epoch = |v: &TestVM| get_state::<RewardState>(v, &REWARD_ACTOR_ADDR).unwrap().epoch;
+
+    advance_to_epoch_with_cron(&v, start + 20);
+    assert_eq!(start + 20, v.epoch());
+    assert_eq!(20, v.take_invocations().len());
+    assert_eq!(start + 20, reward_epoch(&v));
+
+    // Sampled cron ticks at each multiple of the interval and at the last epoch, catching up
+    // on the skipped epochs.
+    let start = v.epoch();
+    advance_to_epoch_sampling_cron(&v, start + 95, 10);
+    assert_eq!(start + 95, v.epoch());
+    assert_eq!(11, v.take_invocations().len());
+    assert_eq!(start + 95, reward_epoch(&v));
+
+    // No extra tick when the last epoch is itself sampled.
+    advance_to_epoch_sampling_cron(&v, v.epoch() + 11, 10);
+    assert_eq!(2, v.take_invocations().len());
+}
+
+#[test]
+fn f4_placeholders_and_eth_accounts() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let eth_addr = [0xee; 20];
+    let f4_addr = eth_f4_addr(&eth_addr);
+
+    // Sending to an unassigned f4 address creates a placeholder, which further sends fund.
+    let placeholder = create_placeholder(&v, &eth_addr, &TokenAmount::from_atto(1));
+    let actor = v.actor(&placeholder).unwrap();
+    assert_eq!(*PLACEHOLDER_ACTOR_CODE_ID, actor.code);
+    assert_eq!(Some(f4_addr), actor.delegated_address);
+    v.execute_message(&TEST_FAUCET_ADDR, &f4_addr, &TokenAmount::from_atto(2), METHOD_SEND, None)
+        .unwrap();
+    assert_eq!(TokenAmount::from_atto(3), v.balance(&placeholder));
+    assert_eq!(Some(placeholder), v.resolve_id_address(&f4_addr));
+
+    // An eth account can send messages from its f4 address.
+    let account = create_eth_account(&v, &[0xef; 20], &TokenAmount::from_whole(1));
+    assert_eq!(*ETHACCOUNT_ACTOR_CODE_ID, v.actor(&account).unwrap().code);
+    let res = v
+        .execute_message(
+            &eth_f4_addr(&[0xef; 20]),
+            &f4_addr,
+            &TokenAmount::from_atto(1),
+            METHOD_SEND,
+            None,
+        )
+        .unwrap();
+    assert_eq!(ExitCode::OK, res.code);
+    assert_eq!(TokenAmount::from_atto(4), v.balance(&placeholder));
+}
+
+fn transfer(from: Address, to: Address, whole: u64) -> TipsetMessage {
+    TipsetMessage {
+        from,
+        to,
+        value: TokenAmount::from_whole(whole),
+        method: METHOD_SEND,
+        params: None,
+    }
+}
+
+#[test]
+fn independent_messages_apply_in_any_order() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 4, &TokenAmount::from_whole(10));
+    v.take_invocations();
+
+    let messages = [
+        transfer(addrs[0], addrs[1], 1),
+        transfer(addrs[1], addrs[2], 2),
+        transfer(addrs[2], addrs[3], 3),
+        transfer(addrs[3], addrs[0], 4),
+    ];
+    let results = v.apply_tipset_in_any_order(&messages, 5, 0);
+
+    assert!(results.iter().all(|r| r.code == ExitCode::OK));
+    assert_eq!(TokenAmount::from_whole(13), v.balance(&addrs[0]));
+    assert_eq!(TokenAmount::from_whole(9), v.balance(&addrs[1]));
+    assert_eq!(TokenAmount::from_whole(9), v.balance(&addrs[2]));
+    assert_eq!(TokenAmount::from_whole(9), v.balance(&addrs[3]));
+    // Only the traces of the last order are kept.
+    assert_eq!(messages.len(), v.take_invocations().len());
+}
+
+#[test]
+#[should_panic(expected = "message 1 result differs between orders")]
+fn order_dependent_messages_are_detected() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 3, &TokenAmount::from_whole(10));
+
+    // The second transfer can only be funded by the first.
+    let messages = [transfer(addrs[0], addrs[1], 10), transfer(addrs[1], addrs[2], 15)];
+    v.apply_tipset_in_any_order(&messages, 8, 0);
 }
@@ -1,5 +1,6 @@
 use fil_actors_integration_tests::tests::{
     deal_passes_claim_fails_test, expired_allocations_test, verified_claim_scenario_test,
+    verified_deal_lifecycle_test,
 };
 use fil_actors_runtime::test_blockstores::MemoryBlockstore;
 use test_vm::TestVM;
@@ -24,3 +25,10 @@ fn deal_passes_claim_fails() {
     let v = TestVM::new_with_singletons(store);
     deal_passes_claim_fails_test(&v);
 }
+
+#[test]
+fn verified_deal_lifecycle() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    verified_deal_lifecycle_test(&v);
+}
@@ -0,0 +1,53 @@
+use fil_actors_integration_tests::util::{assert_invariants, get_network_stats};
+use fil_actors_integration_tests::workload::{workload_seed, Workload, WorkloadConfig};
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use test_vm::TestVM;
+use vm_api::VM;
+
+fn run_workload(config: &WorkloadConfig, seed: u64, steps: u64) -> TestVM {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    v.set_epoch(200);
+    let mut workload = Workload::new(&v, config.clone(), seed);
+    workload.run(steps);
+    v
+}
+
+#[test]
+fn workload_is_deterministic() {
+    // Storms up to a proving period long, so that faults and recoveries happen within the run.
+    let config =
+        WorkloadConfig { fault_storm_probability: 0.05, max_storm_steps: 48, ..Default::default() };
+    let seed = workload_seed();
+    let v1 = run_workload(&config, seed, 120);
+    let v2 = run_workload(&config, seed, 120);
+    assert_eq!(v1.checkpoint(), v2.checkpoint());
+    assert_invariants(&v1, &Policy::default(), None);
+}
+
+/// Runs a long workload, reporting the size of the state as it grows.
+/// Run with `cargo test -p test_vm --test all_tests soak -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn soak_workload() {
+    let config = WorkloadConfig { miners: 10, clients: 10, ..Default::default() };
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    v.set_epoch(200);
+    let mut workload = Workload::new(&v, config, workload_seed());
+    let path = std::env::temp_dir().join(format!("test_vm_soak_{}.car", std::process::id()));
+    for _ in 0..20 {
+        workload.run(144);
+        v.export_car(&path).unwrap();
+        let size = std::fs::metadata(&path).unwrap().len();
+        let stats = get_network_stats(&v);
+        println!(
+            "epoch {} state {} bytes, raw power {}, {:?}",
+            v.epoch(),
+            size,
+            stats.total_raw_byte_power,
+            workload.stats()
+        );
+        assert_invariants(&v, &Policy::default(), None);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
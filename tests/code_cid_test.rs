@@ -0,0 +1,42 @@
+use cid::Cid;
+use fil_builtin_actors_bundle::{
+    compute_code_cid, compute_code_cids, network_bundle, BUNDLE_CAR, NETWORK, NETWORK_BUNDLES,
+};
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_encoding::CborStore;
+
+/// Returns the manifest CID of a bundle, checking its actors' code CIDs match their bytecode.
+fn check_bundle(car: &[u8]) -> Cid {
+    let store = MemoryBlockstore::new();
+    let roots = futures::executor::block_on(fvm_ipld_car::load_car(&store, car)).unwrap();
+    let (version, data): (u32, Cid) = store.get_cbor(&roots[0]).unwrap().unwrap();
+    assert_eq!(1, version);
+    let actors: Vec<(String, Cid)> = store.get_cbor(&data).unwrap().unwrap();
+    assert!(!actors.is_empty());
+
+    let bytecode: Vec<Vec<u8>> =
+        actors.iter().map(|(_, cid)| store.get(cid).unwrap().unwrap()).collect();
+    for ((name, cid), wasm) in actors.iter().zip(&bytecode) {
+        assert_eq!(*cid, compute_code_cid(wasm), "code CID mismatch for {}", name);
+    }
+    let expected: Vec<Cid> = actors.iter().map(|(_, cid)| *cid).collect();
+    assert_eq!(expected, compute_code_cids(bytecode.iter().map(Vec::as_slice)));
+    roots[0]
+}
+
+#[test]
+fn bundle_code_cids_match_bytecode() {
+    check_bundle(BUNDLE_CAR);
+}
+
+#[test]
+fn network_bundles_are_distinct() {
+    assert_eq!(NETWORK, NETWORK_BUNDLES[0].0);
+    assert_eq!(Some(BUNDLE_CAR), network_bundle(NETWORK));
+    assert_eq!(None, network_bundle("nonet"));
+    let mut manifests: Vec<Cid> =
+        NETWORK_BUNDLES.iter().map(|(_, car)| check_bundle(car)).collect();
+    manifests.sort();
+    manifests.dedup();
+    assert_eq!(NETWORK_BUNDLES.len(), manifests.len());
+}
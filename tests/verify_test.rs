@@ -0,0 +1,98 @@
+use cid::Cid;
+use fil_builtin_actors_bundle::verify::{verify_bundle, ExpectedCids, ACTOR_NAMES};
+use fil_builtin_actors_bundle::BUNDLE_CAR;
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_encoding::CborStore;
+use multihash_codetable::{Code, MultihashDigest};
+
+fn varint(mut n: usize, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Builds a bundle CAR from the actors of the default bundle, with the manifest rewritten by
+/// `edit`.
+fn rewrite_bundle(edit: impl FnOnce(&mut Vec<(String, Cid)>)) -> Vec<u8> {
+    let store = MemoryBlockstore::new();
+    let roots = futures::executor::block_on(fvm_ipld_car::load_car(&store, BUNDLE_CAR)).unwrap();
+    let (_, data): (u32, Cid) = store.get_cbor(&roots[0]).unwrap().unwrap();
+    let mut actors: Vec<(String, Cid)> = store.get_cbor(&data).unwrap().unwrap();
+    let mut blocks: Vec<(Cid, Vec<u8>)> =
+        actors.iter().map(|(_, cid)| (*cid, store.get(cid).unwrap().unwrap())).collect();
+    edit(&mut actors);
+    let data = store.put_cbor(&actors, Code::Blake2b256).unwrap();
+    let manifest = store.put_cbor(&(1u32, data), Code::Blake2b256).unwrap();
+    blocks.extend([manifest, data].map(|cid| (cid, store.get(&cid).unwrap().unwrap())));
+
+    #[derive(serde::Serialize)]
+    struct CarHeader {
+        roots: Vec<Cid>,
+        version: u64,
+    }
+    let mut car = vec![];
+    let header =
+        fvm_ipld_encoding::to_vec(&CarHeader { roots: vec![manifest], version: 1 }).unwrap();
+    varint(header.len(), &mut car);
+    car.extend(header);
+    for (cid, block) in blocks {
+        let cid_bytes = cid.to_bytes();
+        varint(cid_bytes.len() + block.len(), &mut car);
+        car.extend(cid_bytes);
+        car.extend(block);
+    }
+    car
+}
+
+#[test]
+fn bundle_verifies() {
+    let verification = verify_bundle(BUNDLE_CAR, None).unwrap();
+    assert!(verification.is_ok(), "{:?}", verification.problems);
+    assert_eq!(ACTOR_NAMES.len(), verification.actors.len());
+
+    // The bundle matches its own pinned CIDs, which round trip through JSON.
+    let pinned = ExpectedCids::from_json(&verification.pinned().to_json()).unwrap();
+    assert_eq!(verification.pinned(), pinned);
+    assert!(verify_bundle(BUNDLE_CAR, Some(&pinned)).unwrap().is_ok());
+}
+
+#[test]
+fn bundle_differing_from_expected_cids_fails() {
+    let verification = verify_bundle(BUNDLE_CAR, None).unwrap();
+    let mut expected = verification.pinned();
+    expected.manifest = Some(verification.actors["account"]);
+    expected.actors.insert("cron".to_string(), verification.actors["init"]);
+    expected.actors.remove("evm");
+    expected.actors.insert("future".to_string(), verification.actors["eam"]);
+
+    let problems = verify_bundle(BUNDLE_CAR, Some(&expected)).unwrap().problems;
+    assert_eq!(4, problems.len(), "{:?}", problems);
+    assert!(problems[0].starts_with("manifest is"));
+    assert!(problems.iter().any(|p| p.starts_with("actor cron has code CID")));
+    assert!(problems.contains(&"actor evm is not in the expected CIDs".to_string()));
+    assert!(problems.contains(&"expected actor future is not in the bundle".to_string()));
+}
+
+#[test]
+fn malformed_bundle_fails() {
+    let unstored = Cid::new_v1(0x55, Code::Blake2b256.digest(b"not in the bundle"));
+    let car = rewrite_bundle(|actors| {
+        let account = actors.iter().find(|(name, _)| name == "account").unwrap().1;
+        actors.retain(|(name, _)| name != "eam");
+        actors.push(("extra".to_string(), account));
+        actors.push(("account".to_string(), account));
+        actors.iter_mut().find(|(name, _)| name == "cron").unwrap().1 = unstored;
+    });
+    let problems = verify_bundle(&car, None).unwrap().problems;
+    assert_eq!(
+        vec![
+            format!("bytecode of actor cron ({unstored}) missing from CAR"),
+            "unexpected actor extra in manifest".to_string(),
+            "actor account listed more than once in manifest".to_string(),
+            "actor eam missing from manifest".to_string(),
+        ],
+        problems
+    );
+}
@@ -63,6 +63,35 @@ pub trait VM {
         params: Option<IpldBlock>,
     ) -> Result<MessageResult, VMError>;
 
+    /// Send a message between the two specified actors, with the epoch, base fee and
+    /// circulating supply seen during its execution overridden.
+    /// The VM's own values are restored afterwards.
+    fn execute_message_with_overrides(
+        &self,
+        from: &Address,
+        to: &Address,
+        value: &TokenAmount,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        overrides: &MessageOverrides,
+    ) -> Result<MessageResult, VMError> {
+        let (epoch, base_fee, supply) = (self.epoch(), self.base_fee(), self.circulating_supply());
+        if let Some(epoch) = overrides.epoch {
+            self.set_epoch(epoch);
+        }
+        if let Some(base_fee) = &overrides.base_fee {
+            self.set_base_fee(base_fee.clone());
+        }
+        if let Some(supply) = &overrides.circulating_supply {
+            self.set_circulating_supply(supply.clone());
+        }
+        let res = self.execute_message(from, to, value, method, params);
+        self.set_epoch(epoch);
+        self.set_base_fee(base_fee);
+        self.set_circulating_supply(supply);
+        res
+    }
+
     /// Send a message without charging gas
     fn execute_message_implicit(
         &self,
@@ -115,6 +144,14 @@ pub trait VM {
     fn set_timestamp(&self, timestamp: u64);
 }
 
+/// Values to be seen by a message in place of the VM's own.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MessageOverrides {
+    pub epoch: Option<ChainEpoch>,
+    pub base_fee: Option<TokenAmount>,
+    pub circulating_supply: Option<TokenAmount>,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct MessageResult {
     pub code: ExitCode,
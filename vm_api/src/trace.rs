@@ -1,9 +1,14 @@
+use std::fmt;
+
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::{to_vec, CBOR, DAG_CBOR};
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::{ErrorNumber, ExitCode};
 use fvm_shared::event::ActorEvent;
 use fvm_shared::{ActorID, MethodNum};
+use ipld_core::ipld::Ipld;
+use serde::Serialize;
 
 type ReturnValue = Option<IpldBlock>;
 
@@ -13,6 +18,13 @@ pub struct EmittedEvent {
     pub event: ActorEvent,
 }
 
+/// A debugging message logged by an actor through the runtime.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActorLog {
+    pub level: log::Level,
+    pub msg: String,
+}
+
 /// A trace of an actor method invocation.
 #[derive(Clone, Debug)]
 pub struct InvocationTrace {
@@ -28,6 +40,137 @@ pub struct InvocationTrace {
     pub return_value: ReturnValue,
     pub subinvocations: Vec<InvocationTrace>,
     pub events: Vec<EmittedEvent>,
+    pub logs: Vec<ActorLog>,
+}
+
+impl EmittedEvent {
+    /// Returns the encoded value of the first entry with a key, if any.
+    pub fn value(&self, key: &str) -> Option<&[u8]> {
+        self.event.entries.iter().find(|e| e.key == key).map(|e| e.value.as_slice())
+    }
+}
+
+impl InvocationTrace {
+    /// Returns the events emitted by this invocation and its subinvocations, depth-first.
+    pub fn all_events(&self) -> Vec<&EmittedEvent> {
+        let mut events: Vec<&EmittedEvent> = self.events.iter().collect();
+        for sub in &self.subinvocations {
+            events.extend(sub.all_events());
+        }
+        events
+    }
+
+    /// Returns the messages logged by this invocation and its subinvocations, depth-first.
+    pub fn all_logs(&self) -> Vec<&ActorLog> {
+        let mut logs: Vec<&ActorLog> = self.logs.iter().collect();
+        for sub in &self.subinvocations {
+            logs.extend(sub.all_logs());
+        }
+        logs
+    }
+
+    /// Asserts that this invocation or one of its subinvocations emitted an event from an
+    /// emitter with an entry for a key whose value is the CBOR encoding of a value.
+    /// Other entries of the event are not checked.
+    pub fn expect_event<T: Serialize + ?Sized>(&self, emitter: ActorID, key: &str, value: &T) {
+        let expected = to_vec(value).unwrap();
+        let events = self.all_events();
+        assert!(
+            events
+                .iter()
+                .any(|e| e.emitter == emitter && e.value(key) == Some(expected.as_slice())),
+            "expected event from {} with {} = {:?}, emitted events: {:?}",
+            emitter,
+            key,
+            expected,
+            events
+        );
+    }
+}
+
+/// Renders the trace as a tree, one invocation per line with its subinvocations indented
+/// below it, and params and return values decoded from CBOR where possible.
+impl fmt::Display for InvocationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl InvocationTrace {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{} -> {} method {} value {}: ",
+            "",
+            Address::new_id(self.from),
+            self.to,
+            self.method,
+            self.value,
+            indent = depth * 2
+        )?;
+        match self.error_number {
+            Some(err) => write!(f, "syscall error {:?}", err)?,
+            None => write!(f, "exit {}", self.exit_code)?,
+        }
+        writeln!(f)?;
+        writeln!(f, "{:indent$}  params: {}", "", fmt_block(&self.params), indent = depth * 2)?;
+        writeln!(
+            f,
+            "{:indent$}  return: {}",
+            "",
+            fmt_block(&self.return_value),
+            indent = depth * 2
+        )?;
+        for log in &self.logs {
+            writeln!(f, "{:indent$}  {}: {}", "", log.level, log.msg, indent = depth * 2)?;
+        }
+        for sub in &self.subinvocations {
+            sub.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a block as IPLD if it's CBOR that decodes, otherwise as hex.
+pub fn fmt_block(block: &Option<IpldBlock>) -> String {
+    match block {
+        None => "none".to_string(),
+        Some(b) => {
+            let decoded = if b.codec == CBOR || b.codec == DAG_CBOR {
+                fvm_ipld_encoding::from_slice::<Ipld>(&b.data).ok()
+            } else {
+                None
+            };
+            match decoded {
+                Some(ipld) => fmt_ipld(&ipld),
+                None => format!("codec {:#x} bytes 0x{}", b.codec, hex::encode(&b.data)),
+            }
+        }
+    }
+}
+
+/// Formats IPLD compactly, with bytes as hex.
+pub fn fmt_ipld(ipld: &Ipld) -> String {
+    match ipld {
+        Ipld::Null => "null".to_string(),
+        Ipld::Bool(b) => b.to_string(),
+        Ipld::Integer(i) => i.to_string(),
+        Ipld::Float(x) => x.to_string(),
+        Ipld::String(s) => format!("{:?}", s),
+        Ipld::Bytes(b) => format!("0x{}", hex::encode(b)),
+        Ipld::List(items) => {
+            format!("[{}]", items.iter().map(fmt_ipld).collect::<Vec<_>>().join(", "))
+        }
+        Ipld::Map(entries) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|(k, v)| format!("{:?}: {}", k, fmt_ipld(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Ipld::Link(cid) => cid.to_string(),
+    }
 }
 
 /// An expectation for a method invocation trace.
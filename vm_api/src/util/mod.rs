@@ -1,6 +1,6 @@
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::{CborStore, RawBytes};
-use fvm_shared::address::{Address, BLS_PUB_LEN};
+use fvm_shared::address::{Address, BLS_PUB_LEN, SECP_PUB_LEN};
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::MethodNum;
@@ -13,16 +13,27 @@ mod blockstore;
 pub use blockstore::*;
 use serde::de::DeserializeOwned;
 
-use crate::VM;
+use crate::trace::fmt_ipld;
+use crate::{MessageOverrides, VM};
 
 /// Generate count addresses by seeding an rng
 pub fn pk_addrs_from(seed: u64, count: u64) -> Vec<Address> {
+    let mut rng = rng_from_seed(seed);
+    (0..count).map(|_| new_bls_from_rng(&mut rng)).collect()
+}
+
+/// Generate count secp256k1 addresses by seeding an rng
+pub fn secp_addrs_from(seed: u64, count: u64) -> Vec<Address> {
+    let mut rng = rng_from_seed(seed);
+    (0..count).map(|_| new_secp_from_rng(&mut rng)).collect()
+}
+
+fn rng_from_seed(seed: u64) -> ChaCha8Rng {
     let mut seed_arr = [0u8; 32];
     for (i, b) in seed.to_ne_bytes().iter().enumerate() {
         seed_arr[i] = *b;
     }
-    let mut rng = ChaCha8Rng::from_seed(seed_arr);
-    (0..count).map(|_| new_bls_from_rng(&mut rng)).collect()
+    ChaCha8Rng::from_seed(seed_arr)
 }
 
 /// Generate nice 32 byte arrays sampled uniformly at random based off of a u64 seed
@@ -32,6 +43,14 @@ fn new_bls_from_rng(rng: &mut ChaCha8Rng) -> Address {
     Address::new_bls(&bytes).unwrap()
 }
 
+/// Generate an uncompressed secp256k1 public key's worth of random bytes
+fn new_secp_from_rng(rng: &mut ChaCha8Rng) -> Address {
+    let mut bytes = [0u8; SECP_PUB_LEN];
+    bytes[0] = 0x04;
+    rng.fill_bytes(&mut bytes[1..]);
+    Address::new_secp256k1(&bytes).unwrap()
+}
+
 pub fn apply_ok<S: Serialize>(
     v: &dyn VM,
     from: &Address,
@@ -54,7 +73,41 @@ pub fn apply_code<S: Serialize>(
 ) -> RawBytes {
     let params = params.map(|p| IpldBlock::serialize_cbor(&p).unwrap().unwrap());
     let res = v.execute_message(from, to, value, method, params).unwrap();
-    assert_eq!(code, res.code, "expected code {}, got {} ({})", code, res.code, res.message);
+    assert_eq!(
+        code,
+        res.code,
+        "expected code {}, got {} ({})\n{}",
+        code,
+        res.code,
+        res.message,
+        describe_last_message(v, to)
+    );
+    res.ret.map_or(RawBytes::default(), |b| RawBytes::new(b.data))
+}
+
+/// Applies a message as [`apply_code`] does, with values seen by the message overridden.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_code_with_overrides<S: Serialize>(
+    v: &dyn VM,
+    from: &Address,
+    to: &Address,
+    value: &TokenAmount,
+    method: MethodNum,
+    params: Option<S>,
+    overrides: &MessageOverrides,
+    code: ExitCode,
+) -> RawBytes {
+    let params = params.map(|p| IpldBlock::serialize_cbor(&p).unwrap().unwrap());
+    let res = v.execute_message_with_overrides(from, to, value, method, params, overrides).unwrap();
+    assert_eq!(
+        code,
+        res.code,
+        "expected code {}, got {} ({})\n{}",
+        code,
+        res.code,
+        res.message,
+        describe_last_message(v, to)
+    );
     res.ret.map_or(RawBytes::default(), |b| RawBytes::new(b.data))
 }
 
@@ -69,9 +122,46 @@ pub fn apply_ok_implicit<S: Serialize>(
     let code = ExitCode::OK;
     let params = params.map(|p| IpldBlock::serialize_cbor(&p).unwrap().unwrap());
     let res = v.execute_message_implicit(from, to, value, method, params).unwrap();
-    assert_eq!(code, res.code, "expected code {}, got {} ({})", code, res.code, res.message);
+    assert_eq!(
+        code,
+        res.code,
+        "expected code {}, got {} ({})\n{}",
+        code,
+        res.code,
+        res.message,
+        describe_last_message(v, to)
+    );
     res.ret.map_or(RawBytes::default(), |b| RawBytes::new(b.data))
 }
+
+/// Describes the last message applied, for the message of a failed assertion: its invocation
+/// trace with params and return values decoded, and the state of its receiver afterwards.
+/// Takes the VM's invocation traces.
+pub fn describe_last_message(v: &dyn VM, to: &Address) -> String {
+    let mut out = String::from("invocation trace:\n");
+    if let Some(trace) = v.take_invocations().pop() {
+        out.push_str(&trace.to_string());
+    }
+    match v.actor(to) {
+        Some(actor) => {
+            let name = v.actor_manifest().get(&actor.code).map_or("unknown", |t| t.name());
+            let state = v
+                .blockstore()
+                .get(&actor.state)
+                .ok()
+                .flatten()
+                .and_then(|b| fvm_ipld_encoding::from_slice(&b).ok())
+                .map_or_else(|| "undecodable".to_string(), |ipld| fmt_ipld(&ipld));
+            out.push_str(&format!(
+                "receiver {} ({} actor, balance {}) state {}: {}\n",
+                to, name, actor.balance, actor.state, state
+            ));
+        }
+        None => out.push_str(&format!("receiver {} does not exist\n", to)),
+    }
+    out
+}
+
 pub fn get_state<T: DeserializeOwned>(v: &dyn VM, a: &Address) -> Option<T> {
     let cid = v.actor(a).unwrap().state;
     v.blockstore().get(&cid).unwrap().map(|slice| fvm_ipld_encoding::from_slice(&slice).unwrap())